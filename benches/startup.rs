@@ -0,0 +1,181 @@
+//! Cold start of the app over a stored list, headless.
+//!
+//! Each run is a fresh process, so opening the database is paid every time as
+//! it is at launch. A run renders `TodoApp` in a `VirtualDom` and reports how
+//! long the first render took, which is when the shell is on screen, and how
+//! long until the stored todos were rendered, which is when the list
+//! responds. The store lives in a temporary data folder.
+//!
+//! There is no webview, so scripts the app runs, such as its timers, never
+//! finish, as if the window sat idle.
+//!
+//! ```text
+//! cargo bench --bench startup --no-default-features --features ui,sqlite -- [todos] [runs]
+//! ```
+
+use dioxus::dioxus_core::{Mutation, Mutations};
+use dioxus::document::{Document, Eval, EvalError, Evaluator};
+use dioxus::prelude::*;
+use dioxus::signals::Owner;
+use std::cell::RefCell;
+use std::process::Command;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use todo::components::TodoApp;
+use todo::models::TodoList;
+use todo::utils::constants::data_dir::DATA_DIR_ENV;
+use todo::utils::{self, keys};
+
+const DEFAULT_TODOS: usize = 5_000;
+const DEFAULT_RUNS: usize = 15;
+/// Text every stored todo starts with, looked for in the rendered list
+const SEEDED_TEXT: &str = "Seeded todo";
+/// Longest wait for the list before a run gives up
+const RUN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Stores a list of `size` todos with a mix of tags and completed ones.
+fn seed(size: usize) {
+    let mut list = TodoList::new();
+    for i in 0..size {
+        let id = list.add(format!("{SEEDED_TEXT} {i} about the Quarterly Report"));
+        if i % 3 == 0 {
+            list.toggle(id);
+        }
+        if i % 5 == 0 {
+            list.add_tag(id, format!("Tag {}", i % 40));
+        }
+    }
+    utils::save(keys::TODOS, &list).expect("the seeded list is saved");
+}
+
+/// Script that is never answered, like one waiting on a timer.
+struct IdleScript;
+
+impl Evaluator for IdleScript {
+    fn send(&self, _data: serde_json::Value) -> Result<(), EvalError> {
+        Ok(())
+    }
+
+    fn poll_recv(&mut self, _: &mut Context<'_>) -> Poll<Result<serde_json::Value, EvalError>> {
+        Poll::Pending
+    }
+
+    fn poll_join(&mut self, _: &mut Context<'_>) -> Poll<Result<serde_json::Value, EvalError>> {
+        Poll::Pending
+    }
+}
+
+/// Document whose scripts never finish. Without it, loops that wait on a
+/// timer would fail at once and spin.
+#[derive(Default)]
+struct IdleDocument {
+    owners: RefCell<Vec<Owner>>,
+}
+
+impl Document for IdleDocument {
+    fn eval(&self, _js: String) -> Eval {
+        let owner = Owner::default();
+        let eval = Eval::new(owner.insert(Box::new(IdleScript) as Box<dyn Evaluator>));
+        self.owners.borrow_mut().push(owner);
+        eval
+    }
+}
+
+/// The app in an idle document.
+fn app() -> Element {
+    use_context_provider(|| Rc::new(IdleDocument::default()) as Rc<dyn Document>);
+    rsx! {
+        TodoApp {}
+    }
+}
+
+/// Checks if a render put a stored todo on screen.
+fn shows_seeded_todo(mutations: &Mutations) -> bool {
+    mutations.edits.iter().any(|edit| {
+        matches!(edit, Mutation::CreateTextNode { value, .. } if value.starts_with(SEEDED_TEXT))
+    })
+}
+
+/// Starts the app once, printing the milliseconds to the shell and to the
+/// stored todos.
+fn run() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let start = Instant::now();
+        let mut dom = VirtualDom::new(app);
+        let mut mutations = Mutations::default();
+        dom.rebuild(&mut mutations);
+        let shell = start.elapsed();
+
+        let mut interactive = shows_seeded_todo(&mutations).then_some(shell);
+        while interactive.is_none() && start.elapsed() < RUN_TIMEOUT {
+            let _ = tokio::time::timeout(RUN_TIMEOUT, dom.wait_for_work()).await;
+            let mut mutations = Mutations::default();
+            dom.render_immediate(&mut mutations);
+            if shows_seeded_todo(&mutations) {
+                interactive = Some(start.elapsed());
+            }
+        }
+        let interactive = interactive.expect("the stored todos are rendered");
+        println!(
+            "{:.2} {:.2}",
+            shell.as_secs_f64() * 1000.0,
+            interactive.as_secs_f64() * 1000.0
+        );
+    });
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    values[values.len() / 2]
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("--seed") => return seed(args[1].parse().unwrap()),
+        Some("--run") => return run(),
+        _ => {}
+    }
+    let numbers: Vec<usize> = args.iter().filter_map(|arg| arg.parse().ok()).collect();
+    let todos = numbers.first().copied().unwrap_or(DEFAULT_TODOS);
+    let runs = numbers.get(1).copied().unwrap_or(DEFAULT_RUNS);
+
+    let data_dir = std::env::temp_dir().join(format!("todo-startup-bench-{}", std::process::id()));
+    let exe = std::env::current_exe().unwrap();
+    let child = |args: &[&str]| {
+        let output = Command::new(&exe)
+            .args(args)
+            .env(DATA_DIR_ENV, &data_dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    child(&["--seed", &todos.to_string()]);
+    let (mut shells, mut interactives): (Vec<f64>, Vec<f64>) = (0..runs)
+        .map(|_| {
+            let times = child(&["--run"]);
+            let mut times = times
+                .split_whitespace()
+                .map(|ms| ms.parse::<f64>().unwrap());
+            (times.next().unwrap(), times.next().unwrap())
+        })
+        .unzip();
+    let _ = std::fs::remove_dir_all(&data_dir);
+
+    println!(
+        "startup/{todos}: shell {:.1} ms, interactive {:.1} ms (median of {runs} cold runs)",
+        median(&mut shells),
+        median(&mut interactives)
+    );
+}
@@ -0,0 +1,159 @@
+use criterion::{BenchmarkId, Criterion, criterion_group};
+use dioxus::dioxus_core::NoOpMutations;
+use dioxus::prelude::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use todo::components::TodoList as TodoListComponent;
+use todo::models::{FilterCriteria, FilterState, Todo, TodoList, WeekStart};
+use todo::utils::clock::{Clock, SystemClock};
+
+const SIZES: [usize; 2] = [1_000, 10_000];
+
+/// System allocator that counts allocations, used to report per-render allocations.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Builds a list with a mix of completed, tagged and mixed-case todos.
+fn build_list(size: usize) -> TodoList {
+    let mut list = TodoList::new();
+    for i in 0..size {
+        let id = list.add(format!("Todo number {} about the Quarterly Report", i));
+        if i % 3 == 0 {
+            list.toggle(id);
+        }
+        if i % 5 == 0 {
+            list.add_tag(id, "Work".to_string());
+        }
+    }
+    list
+}
+
+/// Mirrors the filtering done by the TodoList component on every render.
+fn render_path(todos: &[Todo], filter: FilterState, search_text: &str) -> Vec<Todo> {
+    let term = search_text.to_lowercase();
+    let today = SystemClock.today_local();
+    todos
+        .iter()
+        .filter(|todo| {
+            filter.matches(todo, today, WeekStart::default()) && todo.matches_search(&term)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Renders the TodoList component the way TodoApp does, from a list signal
+/// shared through context so the benchmark can change it between renders.
+#[component]
+fn ListRoot(list: TodoList) -> Element {
+    let list = use_context_provider(|| Signal::new(list));
+    rsx! {
+        TodoListComponent {
+            todos: list.read().all().into_iter().map(Rc::new).collect(),
+            criteria: FilterCriteria::default(),
+            on_toggle: |_| {},
+            on_delete: |_| {},
+            on_restore: |_| {},
+            on_update: |_| {},
+            on_due_date_change: |_| {},
+            on_tag_add: |_| {},
+            on_tag_remove: |_| {},
+            on_notes_change: |_| {},
+            on_extract: |_| {},
+            on_reveal: |_| {},
+            on_lock_change: |_| {},
+            on_reorder: |_| {},
+        }
+    }
+}
+
+/// Mounts the todo list component with every todo visible.
+fn mount_list(list: &TodoList) -> VirtualDom {
+    let mut dom = VirtualDom::new_with_props(ListRoot, ListRootProps { list: list.clone() });
+    dom.rebuild(&mut NoOpMutations);
+    dom
+}
+
+/// Toggles the first todo and re-renders, as happens after a click.
+fn rerender_list(dom: &mut VirtualDom) {
+    dom.in_runtime(|| {
+        ScopeId::APP.in_runtime(|| consume_context::<Signal<TodoList>>().write().toggle(1));
+    });
+    dom.render_immediate(&mut NoOpMutations);
+}
+
+fn bench_todo_list(c: &mut Criterion) {
+    for size in SIZES {
+        let list = build_list(size);
+        let todos = list.all();
+
+        c.bench_with_input(BenchmarkId::new("all", size), &list, |b, list| {
+            b.iter(|| black_box(list.all()))
+        });
+        c.bench_with_input(BenchmarkId::new("filtered", size), &list, |b, list| {
+            let today = SystemClock.today_local();
+            b.iter(|| black_box(list.filtered(FilterState::Active, today)))
+        });
+        c.bench_with_input(BenchmarkId::new("search", size), &list, |b, list| {
+            b.iter(|| black_box(list.search("report 99")))
+        });
+        c.bench_with_input(BenchmarkId::new("reorder", size), &size, |b, &size| {
+            let mut list = build_list(size);
+            b.iter(|| {
+                list.reorder(1, size);
+                list.reorder(size, 1);
+            })
+        });
+        c.bench_with_input(BenchmarkId::new("render_path", size), &todos, |b, todos| {
+            b.iter(|| black_box(render_path(todos, FilterState::All, "Report 99")))
+        });
+        c.bench_with_input(
+            BenchmarkId::new("render_component", size),
+            &list,
+            |b, list| {
+                let mut dom = mount_list(list);
+                b.iter(|| rerender_list(&mut dom))
+            },
+        );
+    }
+}
+
+/// Prints how many allocations a re-render of the list component makes.
+fn report_render_allocations() {
+    for size in SIZES {
+        let mut dom = mount_list(&build_list(size));
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        rerender_list(&mut dom);
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+        println!(
+            "render_component/{}: {} allocations per re-render ({:.1} per todo)",
+            size,
+            allocations,
+            allocations as f64 / size as f64
+        );
+    }
+}
+
+criterion_group!(benches, bench_todo_list);
+
+fn main() {
+    report_render_allocations();
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+}
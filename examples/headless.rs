@@ -0,0 +1,46 @@
+//! Using the todo engine from a plain Rust program, without the UI.
+//!
+//! Run with `cargo run --example headless --no-default-features`.
+
+use todo::engine::{MemoryStorage, SearchQuery, Store, export_json, import_json};
+
+fn main() {
+    // Any RawStorage backend works; SqliteStorage is available with the `sqlite` feature
+    let storage = MemoryStorage::new();
+
+    // Load (nothing is stored yet, so the list starts empty), change, flush
+    let mut store = Store::load(Box::new(storage.clone())).expect("failed to load todos");
+    store.mutate(|list| {
+        let report = list.add("Write the quarterly report".to_string());
+        list.add_tag(report, "Work".to_string());
+        let milk = list.add("Buy milk".to_string());
+        list.toggle_completion(milk);
+        list.add("Call the plumber".to_string());
+    });
+    println!("Save status before flush: {}", store.status().label());
+    store.flush().expect("failed to save todos");
+    println!("Save status after flush: {}", store.status().label());
+
+    // A second store on the same backend sees the saved todos
+    let store = Store::load(Box::new(storage)).expect("failed to reload todos");
+    let list = store.list();
+    println!(
+        "{} todos, {} active, {} completed",
+        list.total_count(),
+        list.active_count(),
+        list.completed_count()
+    );
+
+    // The same query syntax as the search box
+    let query = SearchQuery::parse("completed:<1d");
+    let now = chrono::Utc::now();
+    for todo in list.all().iter().filter(|todo| query.matches(todo, now)) {
+        println!("Completed today: {}", todo.text);
+    }
+
+    // Round-trip through the JSON export format
+    let json = export_json(list).expect("failed to export");
+    let imported = import_json(&json).expect("failed to import");
+    assert_eq!(imported.all().len(), list.total_count());
+    println!("Exported {} bytes of JSON", json.len());
+}
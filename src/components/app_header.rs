@@ -1,28 +1,92 @@
+use crate::components::theme_manager::use_motion;
+use crate::utils::autosave::SaveStatus;
+use crate::utils::theme;
 use dioxus::prelude::*;
 
-/// Component for displaying the app header with title and theme toggle
+/// Component for displaying the app header with title, save status and theme toggle
+///
+/// Children, such as the due soon bell, are shown before the save status.
+/// The profile, unless it is the default one, is shown next to the title.
 #[component]
 pub fn AppHeader(
     #[props(into)] title: String,
     is_dark_mode: bool,
     on_toggle_theme: EventHandler<()>,
+    on_toggle_settings: EventHandler<()>,
+    save_status: SaveStatus,
+    on_save: EventHandler<()>,
+    #[props(default)] profile: Option<String>,
+    children: Element,
 ) -> Element {
-    let text_class = "text-gray-800 dark:text-gray-200";
-    let text_secondary_class = "text-gray-600 dark:text-gray-400";
+    let motion_class = use_motion("transition-colors");
+
+    let text_class = theme::primary_text_class(is_dark_mode);
+    let text_secondary_class = theme::secondary_text_class(is_dark_mode);
+    let hover_class = theme::hover_class(is_dark_mode);
+    let profile_class = theme::tint_class(theme::Accent::Purple, is_dark_mode);
+    let save_status_class = match save_status {
+        SaveStatus::Saved | SaveStatus::Saving => text_secondary_class,
+        SaveStatus::Unsaved => theme::warning_text_class(is_dark_mode),
+        SaveStatus::Failed(_) | SaveStatus::Blocked => theme::danger_text_class(is_dark_mode),
+    };
+    let save_status_hover_class = match save_status {
+        SaveStatus::Saved | SaveStatus::Saving => "",
+        _ => "hover:underline",
+    };
+    let save_status_title = match &save_status {
+        SaveStatus::Saved => "All changes saved".to_string(),
+        SaveStatus::Saving => "Saving your changes".to_string(),
+        SaveStatus::Unsaved => "Click or press Ctrl+S to save now".to_string(),
+        SaveStatus::Failed(error) => format!("{}. Click or press Ctrl+S to retry", error),
+        SaveStatus::Blocked => {
+            "The stored todos could not be loaded, so nothing is saved until they are recovered"
+                .to_string()
+        }
+    };
 
     rsx! {
         div {
             class: "flex justify-between items-center mb-8",
-            h1 {
-                class: "text-2xl sm:text-3xl font-bold {text_class} transition-colors",
-                "{title}"
+            div {
+                class: "flex items-center gap-2 min-w-0",
+                h1 {
+                    class: "text-2xl sm:text-3xl font-bold {text_class} {motion_class}",
+                    "{title}"
+                }
+                if let Some(profile) = profile {
+                    span {
+                        class: "px-2 py-0.5 text-xs rounded-full truncate {profile_class}",
+                        title: "Profile",
+                        "{profile}"
+                    }
+                }
             }
-            // Dark mode toggle
-            button {
-                class: "p-2 rounded-full hover:bg-gray-200 dark:hover:bg-gray-700 transition-colors {text_secondary_class}",
-                onclick: move |_| on_toggle_theme.call(()),
-                aria_label: "Toggle dark mode",
-                if is_dark_mode { "🌞" } else { "🌙" }
+            div {
+                class: "flex items-center space-x-1",
+                {children}
+                // Save status, click to save now
+                button {
+                    r#type: "button",
+                    class: "px-2 py-1 text-xs rounded {motion_class} {save_status_class} {save_status_hover_class}",
+                    title: "{save_status_title}",
+                    aria_live: "polite",
+                    onclick: move |_| on_save.call(()),
+                    "{save_status.label()}"
+                }
+                // Settings toggle
+                button {
+                    class: "p-2 rounded-full {hover_class} {motion_class} {text_secondary_class}",
+                    onclick: move |_| on_toggle_settings.call(()),
+                    aria_label: "Toggle settings",
+                    "⚙️"
+                }
+                // Dark mode toggle
+                button {
+                    class: "p-2 rounded-full {hover_class} {motion_class} {text_secondary_class}",
+                    onclick: move |_| on_toggle_theme.call(()),
+                    aria_label: "Toggle dark mode",
+                    if is_dark_mode { "🌞" } else { "🌙" }
+                }
             }
         }
     }
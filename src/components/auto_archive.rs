@@ -0,0 +1,40 @@
+use crate::components::clock::use_clock;
+use crate::utils::constants::todo::AUTO_ARCHIVE_CHECK_HOURS;
+use crate::utils::timer;
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Logic running the auto-archive policy
+///
+/// Calls `archive` once the todos have loaded and then every
+/// [`AUTO_ARCHIVE_CHECK_HOURS`], passing the ids it moved to `on_archived`
+/// whenever there were any.
+pub fn use_auto_archive(
+    loaded: Signal<bool>,
+    archive: Callback<DateTime<Utc>, Vec<usize>>,
+    on_archived: impl FnMut(Vec<usize>) + Copy + 'static,
+) {
+    // The schedule starts once, even if the todos are loaded again from a backup
+    let started = use_hook(|| Rc::new(Cell::new(false)));
+    let clock = use_clock();
+
+    use_effect(move || {
+        if !loaded() || started.replace(true) {
+            return;
+        }
+        let mut on_archived = on_archived;
+        let clock = clock.clone();
+        spawn(async move {
+            loop {
+                let archived = archive(clock.now_utc());
+                if !archived.is_empty() {
+                    on_archived(archived);
+                }
+                timer::sleep(Duration::from_secs(AUTO_ARCHIVE_CHECK_HOURS * 60 * 60)).await;
+            }
+        });
+    });
+}
@@ -0,0 +1,235 @@
+use crate::models::{AutoTagRule, AutoTagger};
+use crate::utils::theme;
+use dioxus::prelude::*;
+
+/// Props for the AutoTagRules component.
+#[derive(Props, PartialEq, Clone)]
+pub struct AutoTagRulesProps {
+    /// The saved rules
+    pub rules: Vec<AutoTagRule>,
+    /// Callback when a rule is added, changed or removed
+    pub on_rules_change: EventHandler<Vec<AutoTagRule>>,
+    /// Callback applying the rules to existing todos, returning how many were tagged
+    pub on_apply_to_existing: Callback<(), usize>,
+    /// Whether dark mode is enabled
+    #[props(default = false)]
+    pub is_dark_mode: bool,
+}
+
+/// Describes a rule in a single line, like `buy, order → Shopping`.
+fn describe_rule(rule: &AutoTagRule) -> String {
+    if rule.is_regex {
+        format!("/{}/ → {}", rule.pattern, rule.tag)
+    } else {
+        format!("{} → {}", rule.pattern, rule.tag)
+    }
+}
+
+/// Editor for the rules that tag todos automatically
+#[component]
+pub fn AutoTagRules(props: AutoTagRulesProps) -> Element {
+    let mut draft = use_signal(AutoTagRule::default);
+    let mut editing_index = use_signal(|| None::<usize>);
+    let mut error = use_signal(|| None::<String>);
+    let mut sample = use_signal(String::new);
+    let mut applied = use_signal(|| None::<usize>);
+
+    // Regex errors are reported here, before the rule is stored
+    let save_rule = {
+        let rules = props.rules.clone();
+        move |evt: Event<FormData>| {
+            evt.prevent_default();
+            let rule = AutoTagRule {
+                pattern: draft.read().pattern.trim().to_string(),
+                tag: draft.read().tag.trim().to_string(),
+                ..draft()
+            };
+            if let Err(e) = rule.validate() {
+                error.set(Some(e.to_string()));
+                return;
+            }
+
+            let mut rules = rules.clone();
+            match editing_index() {
+                Some(index) if index < rules.len() => rules[index] = rule,
+                _ => rules.push(rule),
+            }
+            props.on_rules_change.call(rules);
+            draft.set(AutoTagRule::default());
+            editing_index.set(None);
+            error.set(None);
+        }
+    };
+
+    // Tags the sample text would get from the saved rules and the draft
+    let sample_tags = {
+        let mut rules = props.rules.clone();
+        if draft.read().validate().is_ok() {
+            match editing_index() {
+                Some(index) if index < rules.len() => rules[index] = draft(),
+                _ => rules.push(draft()),
+            }
+        }
+        AutoTagger::new(&rules).tags_for(&sample.read()).join(", ")
+    };
+
+    let text_class = theme::secondary_text_class(props.is_dark_mode);
+    let input_class = theme::form_control_class(props.is_dark_mode);
+    let button_class = theme::secondary_button_class(props.is_dark_mode);
+    let link_class = theme::link_class(props.is_dark_mode);
+
+    rsx! {
+      div { class: "flex flex-col space-y-2 text-sm {text_class}",
+        if props.rules.is_empty() {
+          p { "No rules yet. Rules add a tag when a todo's text matches, like buy, order → Shopping." }
+        }
+        ul { class: "space-y-1",
+          for (index, rule) in props.rules.iter().enumerate() {
+            li { key: "{index}", class: "flex items-center gap-2",
+              span { class: "flex-1 truncate font-mono", "{describe_rule(rule)}" }
+              button {
+                r#type: "button",
+                class: "text-xs {link_class}",
+                onclick: {
+                    let rule = rule.clone();
+                    move |_| {
+                        draft.set(rule.clone());
+                        editing_index.set(Some(index));
+                        error.set(None);
+                    }
+                },
+                "Edit"
+              }
+              button {
+                r#type: "button",
+                class: "text-xs {link_class}",
+                onclick: {
+                    let rules = props.rules.clone();
+                    move |_| {
+                        let mut rules = rules.clone();
+                        rules.remove(index);
+                        props.on_rules_change.call(rules);
+                        editing_index.set(None);
+                    }
+                },
+                "Delete"
+              }
+            }
+          }
+        }
+
+        form { class: "flex flex-wrap items-center gap-2", onsubmit: save_rule,
+          input {
+            class: "{input_class} flex-1 min-w-32",
+            placeholder: if draft.read().is_regex { "Regular expression" } else { "Words, comma separated" },
+            aria_label: "Pattern",
+            value: "{draft.read().pattern}",
+            oninput: move |evt| draft.write().pattern = evt.value(),
+          }
+          label { class: "flex items-center gap-1",
+            input {
+              r#type: "checkbox",
+              checked: draft.read().is_regex,
+              onchange: move |evt| draft.write().is_regex = evt.checked(),
+            }
+            "Regex"
+          }
+          input {
+            class: "{input_class} w-28",
+            placeholder: "Tag",
+            aria_label: "Tag",
+            value: "{draft.read().tag}",
+            oninput: move |evt| draft.write().tag = evt.value(),
+          }
+          button { r#type: "submit", class: button_class,
+            if editing_index().is_some() { "Update rule" } else { "Add rule" }
+          }
+          if editing_index().is_some() {
+            button {
+              r#type: "button",
+              class: "text-xs {link_class}",
+              onclick: move |_| {
+                  draft.set(AutoTagRule::default());
+                  editing_index.set(None);
+                  error.set(None);
+              },
+              "Cancel"
+            }
+          }
+        }
+        if let Some(message) = error() {
+          p { class: "text-red-500", role: "alert", "{message}" }
+        }
+
+        div { class: "flex items-center gap-2",
+          input {
+            class: "{input_class} flex-1",
+            placeholder: "Try the rules on a sample todo",
+            aria_label: "Sample todo text",
+            value: "{sample.read()}",
+            oninput: move |evt| sample.set(evt.value()),
+          }
+          if !sample.read().is_empty() {
+            span {
+              if sample_tags.is_empty() { "No tags" } else { "Tags: {sample_tags}" }
+            }
+          }
+        }
+
+        div { class: "flex items-center gap-2",
+          button {
+            r#type: "button",
+            class: button_class,
+            disabled: props.rules.is_empty(),
+            onclick: move |_| applied.set(Some(props.on_apply_to_existing.call(()))),
+            "Apply rules to existing todos"
+          }
+          if let Some(count) = applied() {
+            span { aria_live: "polite",
+              if count == 1 { "Tagged 1 todo." } else { "Tagged {count} todos." }
+            }
+          }
+        }
+      }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::Mutations;
+
+    #[test]
+    fn test_describe_rule() {
+        let mut rule = AutoTagRule {
+            pattern: "buy, order".to_string(),
+            is_regex: false,
+            tag: "Shopping".to_string(),
+        };
+        assert_eq!(describe_rule(&rule), "buy, order → Shopping");
+
+        rule.is_regex = true;
+        rule.pattern = r"\bcall\b".to_string();
+        assert_eq!(describe_rule(&rule), r"/\bcall\b/ → Shopping");
+    }
+
+    #[test]
+    fn test_auto_tag_rules_rendering() {
+        let mut app = VirtualDom::new(|| {
+            rsx! {
+                AutoTagRules {
+                    rules: vec![AutoTagRule {
+                        pattern: "buy".to_string(),
+                        is_regex: false,
+                        tag: "Shopping".to_string(),
+                    }],
+                    on_rules_change: |_| {},
+                    on_apply_to_existing: |_| 0,
+                    is_dark_mode: true,
+                }
+            }
+        });
+
+        app.rebuild(&mut Mutations::default());
+    }
+}
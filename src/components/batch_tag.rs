@@ -0,0 +1,182 @@
+use crate::components::{Modal, ModalSize};
+use crate::utils::theme;
+use dioxus::prelude::*;
+
+/// Whether a batch tag dialog adds its tag or removes it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchTagMode {
+    /// "Tag all visible…"
+    Add,
+    /// "Untag all visible…"
+    Remove,
+}
+
+impl BatchTagMode {
+    /// Gets the dialog's heading.
+    fn title(self) -> &'static str {
+        match self {
+            BatchTagMode::Add => "Tag all visible",
+            BatchTagMode::Remove => "Untag all visible",
+        }
+    }
+}
+
+/// Dialog adding a tag to every todo shown, or removing one from them
+///
+/// Offers the given tags to pick from: tags in use when adding, and the tags
+/// the todos shown carry when removing, which is the only tags it accepts
+/// then.
+#[component]
+pub fn BatchTagDialog(
+    mode: BatchTagMode,
+    count: usize,
+    tags: Vec<String>,
+    on_apply: EventHandler<String>,
+    on_close: EventHandler<()>,
+    is_dark_mode: bool,
+) -> Element {
+    let mut tag_text = use_signal(String::new);
+
+    let input_class = theme::form_control_class(is_dark_mode);
+    let hint_class = theme::muted_text_class(is_dark_mode);
+    let suggestion_class = theme::tag_suggestion_class(is_dark_mode);
+    let apply_class = theme::primary_button_class(is_dark_mode);
+
+    let tag = tag_text.read().trim().to_string();
+    let can_apply = match mode {
+        BatchTagMode::Add => !tag.is_empty(),
+        BatchTagMode::Remove => tags.contains(&tag),
+    } && count > 0;
+    let todos = if count == 1 { "todo" } else { "todos" };
+    let preview = match mode {
+        BatchTagMode::Remove if tags.is_empty() => "None of the todos shown has a tag.".to_string(),
+        BatchTagMode::Add => format!("Adds the tag to the {count} {todos} shown."),
+        BatchTagMode::Remove => format!("Removes the tag from the {count} {todos} shown."),
+    };
+
+    rsx! {
+        Modal {
+            title: mode.title(),
+            on_close,
+            size: ModalSize::Small,
+            is_dark_mode,
+            form {
+                onsubmit: move |evt| {
+                    evt.prevent_default();
+                    if can_apply {
+                        on_apply.call(tag_text.read().trim().to_string());
+                    }
+                },
+                label { class: "block text-sm mb-1", r#for: "batch-tag", "Tag" }
+                input {
+                    id: "batch-tag",
+                    class: "{input_class}",
+                    autofocus: true,
+                    value: "{tag_text}",
+                    oninput: move |evt| tag_text.set(evt.value()),
+                }
+                if !tags.is_empty() {
+                    div { class: "mt-2 flex flex-wrap gap-1.5",
+                        for tag in tags.iter().cloned() {
+                            button {
+                                key: "{tag}",
+                                r#type: "button",
+                                class: "{suggestion_class}",
+                                onclick: {
+                                    let tag = tag.clone();
+                                    move |_| tag_text.set(tag.clone())
+                                },
+                                "{tag}"
+                            }
+                        }
+                    }
+                }
+                p { class: "mt-2 text-sm {hint_class}", aria_live: "polite", "{preview}" }
+                div { class: "flex justify-end space-x-2 mt-4",
+                    button {
+                        r#type: "button",
+                        class: "px-3 py-1 rounded text-sm {hint_class} hover:underline",
+                        onclick: move |_| on_close.call(()),
+                        "Cancel"
+                    }
+                    button {
+                        r#type: "submit",
+                        class: "{apply_class} text-sm",
+                        disabled: !can_apply,
+                        match mode {
+                            BatchTagMode::Add => "Tag all",
+                            BatchTagMode::Remove => "Untag all",
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Applied = Rc<RefCell<Vec<String>>>;
+
+    /// Renders the dialog for two todos shown, recording the applied tags.
+    fn dialog(mode: BatchTagMode, tags: &[&str]) -> (TestDom, Applied) {
+        let applied = Applied::default();
+        let tags: Vec<String> = tags.iter().map(|tag| tag.to_string()).collect();
+        let dom = TestDom::with_props(
+            move |applied: Applied| {
+                rsx! {
+                    BatchTagDialog {
+                        mode,
+                        count: 2,
+                        tags: tags.clone(),
+                        on_apply: move |tag| applied.borrow_mut().push(tag),
+                        on_close: |_| {},
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            applied.clone(),
+        );
+        (dom, applied)
+    }
+
+    #[test]
+    fn test_adding_takes_a_typed_or_picked_tag() {
+        let (mut dom, applied) = dialog(BatchTagMode::Add, &["home"]);
+        assert!(dom.text().contains("Adds the tag to the 2 todos shown."));
+
+        let form = dom.first("form").unwrap();
+        dom.submit(form);
+        assert!(applied.borrow().is_empty());
+
+        let input = dom.first("input").unwrap();
+        dom.input(input, "  v2-release ");
+        dom.submit(form);
+        let suggestion = dom.find("button", "home").unwrap();
+        dom.click(suggestion);
+        dom.submit(form);
+        assert_eq!(*applied.borrow(), ["v2-release", "home"]);
+    }
+
+    #[test]
+    fn test_removing_takes_only_tags_shown() {
+        let (mut dom, applied) = dialog(BatchTagMode::Remove, &["work"]);
+        let form = dom.first("form").unwrap();
+        let input = dom.first("input").unwrap();
+
+        dom.input(input, "home");
+        dom.submit(form);
+        assert!(applied.borrow().is_empty());
+        dom.input(input, "work");
+        dom.submit(form);
+        assert_eq!(*applied.borrow(), ["work"]);
+
+        let (dom, _) = dialog(BatchTagMode::Remove, &[]);
+        assert!(dom.text().contains("None of the todos shown has a tag."));
+    }
+}
@@ -0,0 +1,265 @@
+use crate::models::{AppSettings, TodoList};
+use crate::sync::caldav::{CalDavClient, CalDavError, plan_sync};
+use crate::utils::constants::caldav::SYNC_INTERVAL_MINS;
+use crate::utils::theme;
+use crate::utils::timer;
+use chrono::{Local, Utc};
+use dioxus::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Progress of the CalDAV sync, shared between the background sync and settings.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SyncStatus {
+    /// Whether a sync or preview is talking to the server
+    pub running: bool,
+    /// Outcome of the last sync or preview
+    pub message: Option<String>,
+}
+
+/// Describes a count of todos, like "1 todo" or "3 todos".
+fn todos(count: usize) -> String {
+    format!("{} {}", count, if count == 1 { "todo" } else { "todos" })
+}
+
+/// Syncs the todos with the collection in the settings once.
+///
+/// The list is read after fetching, and edits made while the server side is
+/// pushed are kept for the next sync rather than overwritten.
+async fn sync_once(
+    mut todo_list: Signal<TodoList>,
+    mut settings: Signal<AppSettings>,
+) -> Result<String, CalDavError> {
+    let client = CalDavClient::new(&settings.peek().caldav)?;
+    let resources = client.fetch().await?;
+    let synced_at = Utc::now();
+    let snapshot = todo_list.peek().clone();
+    let plan = plan_sync(&snapshot, resources);
+    let pushed = client.push(&plan, &snapshot, synced_at).await;
+    let pulled = if plan.is_empty() {
+        0
+    } else {
+        plan.apply(&mut todo_list.write(), &pushed.links, synced_at)
+    };
+    settings.write().caldav.last_synced_at = Some(Utc::now());
+
+    let mut message = format!(
+        "Synced: {} from the server, {} to the server",
+        todos(pulled),
+        todos(pushed.links.len() + pushed.deleted)
+    );
+    if !pushed.failed.is_empty() {
+        message.push_str(&format!(
+            "; {} refused and retried next time ({})",
+            pushed.failed.len(),
+            pushed.failed[0]
+        ));
+    }
+    if !plan.skipped.is_empty() {
+        message.push_str(&format!("; {} unreadable skipped", plan.skipped.len()));
+    }
+    Ok(message)
+}
+
+/// Runs one sync unless one is running already, reporting in `status`.
+async fn run_sync(
+    todo_list: Signal<TodoList>,
+    settings: Signal<AppSettings>,
+    mut status: Signal<SyncStatus>,
+) {
+    if status.peek().running {
+        return;
+    }
+    status.write().running = true;
+    let message = match sync_once(todo_list, settings).await {
+        Ok(message) => message,
+        Err(error) => error.to_string(),
+    };
+    status.set(SyncStatus {
+        running: false,
+        message: Some(message),
+    });
+}
+
+/// Logic syncing the todos with a CalDAV collection in the background
+///
+/// Once the todos have loaded, syncs every [`SYNC_INTERVAL_MINS`] while sync
+/// is turned on and the first sync, which is previewed, has been made. The
+/// status is provided as context for [`CalDavSyncSettings`].
+pub fn use_caldav_sync(todo_list: Signal<TodoList>, loaded: Signal<bool>) {
+    let settings = use_context::<Signal<AppSettings>>();
+    let status = use_context_provider(|| Signal::new(SyncStatus::default()));
+    // The schedule starts once, even if the todos are loaded again from a backup
+    let started = use_hook(|| Rc::new(Cell::new(false)));
+
+    use_effect(move || {
+        if !loaded() || started.replace(true) {
+            return;
+        }
+        spawn(async move {
+            loop {
+                let caldav = settings.peek().caldav.clone();
+                if caldav.enabled && caldav.is_configured() && caldav.last_synced_at.is_some() {
+                    run_sync(todo_list, settings, status).await;
+                }
+                timer::sleep(Duration::from_secs(SYNC_INTERVAL_MINS * 60)).await;
+            }
+        });
+    });
+}
+
+/// Settings section connecting to a CalDAV task collection
+///
+/// The first sync shows what it would change on both sides and waits for
+/// the user to apply it; later syncs run in the background or from
+/// "Sync now". Must be rendered below [`use_caldav_sync`].
+#[component]
+pub fn CalDavSyncSettings(todo_list: Signal<TodoList>, is_dark_mode: bool) -> Element {
+    let mut settings = use_context::<Signal<AppSettings>>();
+    let mut status = use_context::<Signal<SyncStatus>>();
+    let mut preview = use_signal(|| None::<Vec<String>>);
+
+    let caldav = settings().caldav;
+    let configured = caldav.is_configured();
+    let running = status().running;
+
+    let heading_class = theme::primary_text_class(is_dark_mode);
+    let text_class = theme::secondary_text_class(is_dark_mode);
+    let input_class = theme::form_control_class(is_dark_mode);
+    let button_class = theme::secondary_button_class(is_dark_mode);
+
+    // Fetches and plans without changing anything, listing what would change
+    let show_preview = move |_| {
+        if status.peek().running {
+            return;
+        }
+        status.write().running = true;
+        spawn(async move {
+            let result = async {
+                let client = CalDavClient::new(&settings.peek().caldav)?;
+                let resources = client.fetch().await?;
+                let list = todo_list.peek().clone();
+                Ok::<_, CalDavError>(plan_sync(&list, resources).describe(&list))
+            }
+            .await;
+            let message = match result {
+                Ok(lines) => {
+                    preview.set(Some(lines));
+                    None
+                }
+                Err(error) => Some(error.to_string()),
+            };
+            status.set(SyncStatus {
+                running: false,
+                message,
+            });
+        });
+    };
+    let sync_now = move |_| {
+        preview.set(None);
+        spawn(run_sync(todo_list, settings, status));
+    };
+
+    let last_synced = caldav.last_synced_at.map(|at| {
+        at.with_timezone(&Local)
+            .format("%b %d, %Y %H:%M")
+            .to_string()
+    });
+
+    rsx! {
+      h3 { class: "text-sm font-medium mt-4 mb-2 {heading_class}", "CalDAV sync" }
+      div { class: "grid grid-cols-[auto_1fr] items-center gap-2 mb-2 text-sm {text_class}",
+        label { r#for: "caldav-url", "Task list URL" }
+        input {
+          id: "caldav-url",
+          class: "{input_class}",
+          r#type: "url",
+          placeholder: "https://cloud.example.com/remote.php/dav/calendars/me/tasks/",
+          value: "{caldav.url}",
+          onchange: move |evt: Event<FormData>| settings.write().caldav.url = evt.value(),
+        }
+        label { r#for: "caldav-username", "Username" }
+        input {
+          id: "caldav-username",
+          class: "{input_class}",
+          autocomplete: "username",
+          value: "{caldav.username}",
+          onchange: move |evt: Event<FormData>| settings.write().caldav.username = evt.value(),
+        }
+        label { r#for: "caldav-password", "App password" }
+        input {
+          id: "caldav-password",
+          class: "{input_class}",
+          r#type: "password",
+          autocomplete: "current-password",
+          value: "{caldav.app_password}",
+          onchange: move |evt: Event<FormData>| settings.write().caldav.app_password = evt.value(),
+        }
+        label { r#for: "caldav-enabled", "Sync every {SYNC_INTERVAL_MINS} minutes" }
+        input {
+          id: "caldav-enabled",
+          class: "justify-self-start",
+          r#type: "checkbox",
+          checked: caldav.enabled,
+          onchange: move |evt: Event<FormData>| settings.write().caldav.enabled = evt.checked(),
+        }
+      }
+      div { class: "flex items-center space-x-2 text-sm {text_class}",
+        if caldav.last_synced_at.is_none() {
+          button {
+            r#type: "button",
+            class: "{button_class}",
+            disabled: !configured || running,
+            onclick: show_preview,
+            "Preview first sync"
+          }
+        } else {
+          button {
+            r#type: "button",
+            class: "{button_class}",
+            disabled: !configured || running,
+            onclick: sync_now,
+            "Sync now"
+          }
+        }
+        if running {
+          span { "Syncing…" }
+        } else if let Some(last_synced) = last_synced {
+          span { "Last synced {last_synced}" }
+        }
+      }
+      if let Some(lines) = preview() {
+        div { class: "mt-2 text-sm {text_class}", aria_live: "polite",
+          if lines.is_empty() {
+            p { "Both sides already match." }
+          } else {
+            p { "The first sync will make these changes:" }
+            ul { class: "list-disc ml-5 max-h-48 overflow-y-auto",
+              for line in lines {
+                li { "{line}" }
+              }
+            }
+          }
+          div { class: "flex space-x-2 mt-2",
+            button {
+              r#type: "button",
+              class: "{button_class}",
+              disabled: running,
+              onclick: sync_now,
+              "Sync"
+            }
+            button {
+              r#type: "button",
+              class: "{button_class}",
+              onclick: move |_| preview.set(None),
+              "Cancel"
+            }
+          }
+        }
+      }
+      if let Some(message) = status().message {
+        p { class: "mt-2 text-sm {text_class}", "{message}" }
+      }
+    }
+}
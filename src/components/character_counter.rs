@@ -0,0 +1,61 @@
+use crate::utils::constants::todo::MAX_TODO_TEXT_LENGTH;
+use crate::utils::text::{self, LengthLevel};
+use crate::utils::theme;
+use dioxus::prelude::*;
+
+/// Component showing how many characters of the todo text limit are used
+///
+/// Turns amber at 90% of the limit and red at the limit.
+#[component]
+pub fn CharacterCounter(text: String, is_dark_mode: bool) -> Element {
+    let count = text::char_count(&text);
+    let level = text::length_level(count);
+    let counter_class = match level {
+        LengthLevel::Normal => theme::muted_text_class(is_dark_mode),
+        LengthLevel::Warning => theme::warning_text_class(is_dark_mode),
+        LengthLevel::Limit => theme::danger_text_class(is_dark_mode),
+    };
+    let weight_class = if level == LengthLevel::Limit {
+        "font-semibold"
+    } else {
+        ""
+    };
+
+    rsx! {
+        span {
+            class: "text-xs tabular-nums {counter_class} {weight_class}",
+            aria_live: "polite",
+            "{count}/{MAX_TODO_TEXT_LENGTH}"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::{Mutation, Mutations};
+
+    #[test]
+    fn test_character_counter_rendering() {
+        let mut app = VirtualDom::new_with_props(
+            |text: String| {
+                rsx! {
+                    CharacterCounter { text, is_dark_mode: false }
+                }
+            },
+            "  Buy milk  ".to_string(),
+        );
+
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        let texts: Vec<String> = mutations
+            .edits
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Mutation::CreateTextNode { value, .. } => Some(value),
+                _ => None,
+            })
+            .collect();
+        assert!(texts.contains(&format!("8/{}", MAX_TODO_TEXT_LENGTH)));
+    }
+}
@@ -0,0 +1,32 @@
+use crate::utils::clock::{Clock, SystemClock};
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// The clock components read the time from, when one is provided as context
+///
+/// Without it components use the [`SystemClock`]; tests provide a
+/// [`FixedClock`](crate::utils::clock::FixedClock) to render a chosen moment.
+#[derive(Clone)]
+pub struct AppClock(pub Rc<dyn Clock>);
+
+/// Logic for reading the current time
+///
+/// Returns the clock provided by an ancestor as [`AppClock`], or the system
+/// clock.
+pub fn use_clock() -> Rc<dyn Clock> {
+    use_hook(|| {
+        try_consume_context::<AppClock>()
+            .map(|clock| clock.0)
+            .unwrap_or_else(|| Rc::new(SystemClock))
+    })
+}
+
+/// Logic for remembering when the app was started
+///
+/// Returns the clock's time at the first render, from which todos count as
+/// added this session.
+pub fn use_session_start() -> DateTime<Utc> {
+    let clock = use_clock();
+    use_hook(move || clock.now_utc())
+}
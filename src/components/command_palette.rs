@@ -0,0 +1,214 @@
+use crate::components::theme_manager::use_motion;
+use crate::models::Todo;
+use crate::models::palette::{self, CommandRegistry, PaletteItem};
+use crate::utils::constants::ui::PALETTE_MAX_RESULTS;
+use crate::utils::theme;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Quick switcher opened with Ctrl+K, searching commands, tags and todos
+///
+/// ↑/↓ move the highlight, Enter picks the highlighted result and Escape
+/// closes. Picking a result closes the switcher before acting on it.
+#[component]
+pub fn CommandPalette(
+    registry: CommandRegistry,
+    tags: Vec<String>,
+    todos: Vec<Rc<Todo>>,
+    on_select_tag: EventHandler<String>,
+    on_reveal_todo: EventHandler<usize>,
+    on_close: EventHandler<()>,
+    is_dark_mode: bool,
+) -> Element {
+    let motion_class = use_motion("transition-colors duration-100");
+
+    let mut query = use_signal(String::new);
+    let mut highlighted = use_signal(|| 0usize);
+
+    let results = palette::rank(
+        &query(),
+        &registry,
+        &tags,
+        todos.iter().map(|todo| &**todo),
+        PALETTE_MAX_RESULTS,
+    );
+    let count = results.len();
+
+    let pick = {
+        let registry = registry.clone();
+        move |item: PaletteItem| {
+            on_close.call(());
+            match item {
+                PaletteItem::Command(id) => {
+                    registry.run(id);
+                }
+                PaletteItem::Tag(tag) => on_select_tag.call(tag),
+                PaletteItem::Todo(id) => on_reveal_todo.call(id),
+            }
+        }
+    };
+
+    let handle_key_down = {
+        let results = results.clone();
+        let pick = pick.clone();
+        move |evt: Event<KeyboardData>| {
+            match evt.key() {
+                Key::ArrowDown => highlighted.set(palette::step_highlight(highlighted(), 1, count)),
+                Key::ArrowUp => highlighted.set(palette::step_highlight(highlighted(), -1, count)),
+                Key::Enter => {
+                    if let Some(found) = results.get(highlighted()) {
+                        pick(found.item.clone());
+                    }
+                }
+                Key::Escape => on_close.call(()),
+                _ => return,
+            }
+            evt.prevent_default();
+            // Keep the keys away from the app-wide shortcuts
+            evt.stop_propagation();
+        }
+    };
+
+    let card_class = theme::modal_panel_class(is_dark_mode);
+    let input_class = format!(
+        "{} {}",
+        theme::border_class(is_dark_mode),
+        theme::placeholder_class(is_dark_mode)
+    );
+    let highlight_class = theme::highlight_class(is_dark_mode);
+    let kind_class = theme::secondary_text_class(is_dark_mode);
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-40 flex items-start justify-center bg-black/40 p-4 pt-24",
+            onclick: move |_| on_close.call(()),
+            div {
+                class: "w-full max-w-lg rounded-lg border shadow-lg overflow-hidden {card_class}",
+                role: "dialog",
+                aria_modal: "true",
+                aria_label: "Quick switcher",
+                onclick: move |evt| evt.stop_propagation(),
+                input {
+                    class: "w-full px-4 py-3 border-b outline-none text-sm bg-transparent {input_class}",
+                    placeholder: "Search todos, tags and commands…",
+                    autofocus: true,
+                    role: "combobox",
+                    aria_expanded: "true",
+                    aria_controls: "palette-results",
+                    value: "{query}",
+                    oninput: move |evt| {
+                        query.set(evt.value());
+                        highlighted.set(0);
+                    },
+                    onkeydown: handle_key_down,
+                }
+                ul { id: "palette-results", class: "max-h-80 overflow-y-auto py-1", role: "listbox",
+                    if results.is_empty() {
+                        li { class: "px-4 py-2 text-sm {kind_class}", "No matches" }
+                    }
+                    for (index, found) in results.into_iter().enumerate() {
+                        {
+                            let kind = match found.item {
+                                PaletteItem::Command(_) => "Command",
+                                PaletteItem::Tag(_) => "Tag",
+                                PaletteItem::Todo(_) => "Todo",
+                            };
+                            let is_highlighted = index == highlighted();
+                            let pick = pick.clone();
+                            rsx! {
+                                li {
+                                    key: "{index}-{found.title}",
+                                    class: "flex items-center justify-between gap-3 px-4 py-2 text-sm cursor-pointer {motion_class}",
+                                    class: if is_highlighted { "{highlight_class}" },
+                                    role: "option",
+                                    aria_selected: "{is_highlighted}",
+                                    onmouseenter: move |_| highlighted.set(index),
+                                    onclick: move |_| pick(found.item.clone()),
+                                    span { class: "truncate", "{found.title}" }
+                                    span { class: "text-xs flex-shrink-0 {kind_class}", "{kind}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use crate::models::palette::Command;
+    use std::cell::RefCell;
+
+    type Calls = Rc<RefCell<Vec<String>>>;
+
+    /// Renders the switcher with one command, one tag and one todo.
+    fn render(calls: Calls) -> TestDom {
+        TestDom::with_props(
+            |calls: Calls| {
+                let mut registry = CommandRegistry::new();
+                let ran = calls.clone();
+                registry.register(Command::new(
+                    "theme.toggle",
+                    "Toggle dark mode",
+                    move || ran.borrow_mut().push("theme.toggle".to_string()),
+                ));
+                let (tagged, revealed, closed) = (calls.clone(), calls.clone(), calls.clone());
+                rsx! {
+                    CommandPalette {
+                        registry,
+                        tags: vec!["Work".to_string()],
+                        todos: vec![Rc::new(Todo::new(7, "Write the weekly report".to_string()))],
+                        on_select_tag: move |tag| tagged.borrow_mut().push(format!("tag {tag}")),
+                        on_reveal_todo: move |id| revealed.borrow_mut().push(format!("todo {id}")),
+                        on_close: move |_| closed.borrow_mut().push("close".to_string()),
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            calls,
+        )
+    }
+
+    #[test]
+    fn test_enter_picks_the_highlighted_result() {
+        let calls = Calls::default();
+        let mut dom = render(calls.clone());
+        let field = dom.first("input").unwrap();
+
+        dom.input(field, "weekly");
+        assert!(dom.find("span", "Toggle dark mode").is_none());
+        dom.key_down(field, Key::Enter);
+        assert_eq!(*calls.borrow(), vec!["close", "todo 7"]);
+    }
+
+    #[test]
+    fn test_arrows_move_the_highlight_and_wrap() {
+        let calls = Calls::default();
+        let mut dom = render(calls.clone());
+        let field = dom.first("input").unwrap();
+
+        // The command comes first, then the tag
+        dom.key_down(field, Key::ArrowDown);
+        dom.key_down(field, Key::Enter);
+        dom.key_down(field, Key::ArrowDown);
+        dom.key_down(field, Key::Enter);
+        assert_eq!(
+            *calls.borrow(),
+            vec!["close", "tag Work", "close", "theme.toggle"]
+        );
+    }
+
+    #[test]
+    fn test_clicking_a_command_runs_it() {
+        let calls = Calls::default();
+        let mut dom = render(calls.clone());
+
+        dom.click(dom.find("span", "Toggle dark mode").unwrap());
+        assert_eq!(*calls.borrow(), vec!["close", "theme.toggle"]);
+        assert!(dom.find("li", "No matches").is_none());
+    }
+}
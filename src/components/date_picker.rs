@@ -0,0 +1,356 @@
+use crate::components::clock::use_clock;
+use crate::models::{AppSettings, WeekStart};
+use crate::utils::dates::{self, add_months, clamp_date, first_of_month, month_grid};
+use crate::utils::theme;
+use chrono::{Datelike, Duration, NaiveDate};
+use dioxus::prelude::*;
+
+/// Format of dates typed into the picker's field
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Props for the DatePicker component.
+#[derive(Props, PartialEq, Clone)]
+pub struct DatePickerProps {
+    /// The picked date, `None` if there is none
+    pub value: Option<NaiveDate>,
+    /// Callback when a date is picked, or `None` when the field is cleared
+    pub on_change: EventHandler<Option<NaiveDate>>,
+    /// The earliest date that can be picked
+    #[props(default)]
+    pub min: Option<NaiveDate>,
+    /// The latest date that can be picked
+    #[props(default)]
+    pub max: Option<NaiveDate>,
+    /// Accessible name of the typed field
+    #[props(default = "Date".to_string())]
+    pub label: String,
+    /// Whether dark mode is enabled
+    #[props(default = false)]
+    pub is_dark_mode: bool,
+}
+
+/// Gets the date a navigation key moves the highlighted day to, before
+/// the bounds are applied, or `None` if the key does not navigate.
+fn step(key: &Key, date: NaiveDate) -> Option<NaiveDate> {
+    match key {
+        Key::ArrowLeft => Some(date - Duration::days(1)),
+        Key::ArrowRight => Some(date + Duration::days(1)),
+        Key::ArrowUp => Some(date - Duration::days(7)),
+        Key::ArrowDown => Some(date + Duration::days(7)),
+        Key::PageUp => Some(add_months(date, -1)),
+        Key::PageDown => Some(add_months(date, 1)),
+        _ => None,
+    }
+}
+
+/// A date field with a month grid popover
+///
+/// The grid is driven by the keyboard: the arrow keys move by a day or a
+/// week, Page Up and Page Down by a month, Enter picks the highlighted day
+/// and Escape closes the grid. Dates can also be typed as `YYYY-MM-DD`, and
+/// clearing the field removes the date. Days outside `min` and `max` cannot
+/// be picked. Weeks start on the day chosen in the settings.
+#[component]
+pub fn DatePicker(props: DatePickerProps) -> Element {
+    let clock = use_clock();
+    let settings = use_hook(try_consume_context::<Signal<AppSettings>>);
+    let week_start =
+        settings.map_or_else(WeekStart::default, |settings| settings.read().week_start);
+
+    let (min, max) = (props.min, props.max);
+    let today = clock.today_local();
+    let start = clamp_date(props.value.unwrap_or(today), min, max);
+
+    let mut open = use_signal(|| false);
+    // The day the keyboard is on, which decides the month shown
+    let mut highlighted = use_signal(|| start);
+    let mut typed_error = use_signal(|| None::<String>);
+
+    let in_range = move |date: NaiveDate| clamp_date(date, min, max) == date;
+    let mut pick = move |date: NaiveDate| {
+        if in_range(date) {
+            open.set(false);
+            typed_error.set(None);
+            props.on_change.call(Some(date));
+        }
+    };
+
+    let handle_typed = move |evt: Event<FormData>| {
+        let value = evt.value();
+        let value = value.trim();
+        if value.is_empty() {
+            typed_error.set(None);
+            props.on_change.call(None);
+            return;
+        }
+        match NaiveDate::parse_from_str(value, DATE_FORMAT) {
+            Ok(date) if in_range(date) => pick(date),
+            Ok(_) => typed_error.set(Some("That date is out of range".to_string())),
+            Err(_) => typed_error.set(Some("Type a date like 2025-03-18".to_string())),
+        }
+    };
+
+    let handle_key_down = move |evt: Event<KeyboardData>| {
+        let key = evt.key();
+        match key {
+            Key::Enter => pick(highlighted()),
+            Key::Escape => open.set(false),
+            _ => match step(&key, highlighted()) {
+                Some(date) => highlighted.set(clamp_date(date, min, max)),
+                None => return,
+            },
+        }
+        evt.prevent_default();
+        // Keep the keys away from the dialog and the app-wide shortcuts
+        evt.stop_propagation();
+    };
+
+    let shown = highlighted();
+    let month_label = shown.format("%B %Y").to_string();
+    let grid = month_grid(shown, week_start);
+    let day_names = dates::weekdays(week_start).map(|day| day.to_string()[..2].to_string());
+
+    let border_class = theme::border_class(props.is_dark_mode);
+    let field_class = theme::field_class(props.is_dark_mode);
+    let menu_class = theme::menu_class(props.is_dark_mode);
+    let caption_class = theme::secondary_text_class(props.is_dark_mode);
+    let nav_class = theme::muted_text_class(props.is_dark_mode);
+    let accent_class = theme::accent_text_class(props.is_dark_mode);
+    let error_class = theme::danger_text_class(props.is_dark_mode);
+
+    rsx! {
+      div { class: "relative inline-block",
+        div { class: "flex items-center space-x-1",
+          input {
+            r#type: "text",
+            class: "px-2 py-1 border {border_class} {field_class} rounded shadow-sm focus:outline-none focus:ring-1 focus:ring-blue-400 text-xs w-28",
+            "aria-label": "{props.label}",
+            placeholder: "YYYY-MM-DD",
+            value: props.value.map(|date| date.format(DATE_FORMAT).to_string()).unwrap_or_default(),
+            onchange: handle_typed,
+          }
+          button {
+            r#type: "button",
+            class: "px-1 text-xs {nav_class}",
+            "aria-label": "Choose date",
+            "aria-expanded": open(),
+            onclick: move |_| {
+                if !open() {
+                    highlighted.set(clamp_date(props.value.unwrap_or(today), min, max));
+                }
+                open.toggle();
+            },
+            "📅"
+          }
+        }
+        if let Some(error) = typed_error() {
+          p { class: "mt-1 text-xs {error_class}", role: "alert", "{error}" }
+        }
+        if open() {
+          div { class: "absolute z-20 mt-1 p-2 rounded border shadow-lg {menu_class}",
+            div { class: "flex items-center justify-between mb-1 text-xs",
+              button {
+                r#type: "button",
+                class: "px-2 {nav_class}",
+                "aria-label": "Previous month",
+                onclick: move |_| highlighted.set(clamp_date(add_months(highlighted(), -1), min, max)),
+                "‹"
+              }
+              span { class: "font-medium", "{month_label}" }
+              button {
+                r#type: "button",
+                class: "px-2 {nav_class}",
+                "aria-label": "Next month",
+                onclick: move |_| highlighted.set(clamp_date(add_months(highlighted(), 1), min, max)),
+                "›"
+              }
+            }
+            div {
+              role: "grid",
+              tabindex: "0",
+              autofocus: true,
+              class: "focus:outline-none",
+              "aria-label": "{month_label}",
+              onkeydown: handle_key_down,
+              div { role: "row", class: "grid grid-cols-7 text-center text-[10px] {caption_class}",
+                for name in day_names {
+                  span { key: "{name}", role: "columnheader", "{name}" }
+                }
+              }
+              for week in grid {
+                div { key: "{week[0]}", role: "row", class: "grid grid-cols-7",
+                  for day in week {
+                    button {
+                      key: "{day}",
+                      r#type: "button",
+                      role: "gridcell",
+                      tabindex: "-1",
+                      class: format!(
+                          "w-7 h-7 text-xs rounded {} {} {}",
+                          theme::calendar_day_class(
+                              Some(day) == props.value,
+                              first_of_month(day) == first_of_month(shown),
+                              props.is_dark_mode,
+                          ),
+                          if day == shown { "ring-2 ring-blue-400" } else { "" },
+                          if in_range(day) { "" } else { "opacity-30 cursor-not-allowed" },
+                      ),
+                      "data-date": "{day}",
+                      "data-highlighted": day == shown,
+                      "aria-selected": Some(day) == props.value,
+                      disabled: !in_range(day),
+                      onclick: move |_| pick(day),
+                      "{day.day()}"
+                    }
+                  }
+                }
+              }
+            }
+            if props.value.is_some() {
+              button {
+                r#type: "button",
+                class: "mt-1 text-xs {accent_class}",
+                onclick: move |_| {
+                    open.set(false);
+                    props.on_change.call(None);
+                },
+                "Clear"
+              }
+            }
+          }
+        }
+      }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Picked = Rc<RefCell<Vec<Option<NaiveDate>>>>;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    /// Renders a picker on March 12, bounded to the first half of 2025,
+    /// with its grid open.
+    fn open_picker(picked: Picked) -> TestDom {
+        let mut dom = TestDom::with_props(
+            |picked: Picked| {
+                rsx! {
+                    DatePicker {
+                        value: Some(date(3, 12)),
+                        min: date(1, 1),
+                        max: date(6, 30),
+                        on_change: move |date| picked.borrow_mut().push(date),
+                    }
+                }
+            },
+            picked,
+        );
+        dom.click(
+            dom.find_by_attribute("button", "aria-label", "Choose date")
+                .unwrap(),
+        );
+        dom
+    }
+
+    fn highlighted(dom: &TestDom) -> String {
+        let cell = dom
+            .find_by_attribute("button", "data-highlighted", "true")
+            .unwrap();
+        dom.attribute(cell, "data-date").unwrap().to_string()
+    }
+
+    fn press(dom: &mut TestDom, keys: &[Key]) {
+        for key in keys {
+            let grid = dom.find_by_attribute("div", "role", "grid").unwrap();
+            dom.key_down(grid, key.clone());
+        }
+    }
+
+    #[test]
+    fn test_arrow_keys_wrap_across_month_boundaries() {
+        let mut dom = open_picker(Picked::default());
+        assert_eq!(highlighted(&dom), "2025-03-12");
+        assert!(dom.text().contains("March 2025"));
+
+        press(&mut dom, &[Key::ArrowDown, Key::ArrowDown, Key::ArrowDown]);
+        assert_eq!(highlighted(&dom), "2025-04-02");
+        assert!(dom.text().contains("April 2025"));
+
+        press(&mut dom, &[Key::ArrowLeft, Key::ArrowLeft]);
+        assert_eq!(highlighted(&dom), "2025-03-31");
+        press(&mut dom, &[Key::ArrowRight, Key::ArrowUp]);
+        assert_eq!(highlighted(&dom), "2025-03-25");
+    }
+
+    #[test]
+    fn test_page_keys_move_by_month_within_bounds() {
+        let mut dom = open_picker(Picked::default());
+        press(&mut dom, &[Key::PageUp, Key::PageUp]);
+        assert_eq!(highlighted(&dom), "2025-01-12");
+        // Stops at the earliest allowed date
+        press(&mut dom, &[Key::PageUp]);
+        assert_eq!(highlighted(&dom), "2025-01-01");
+
+        press(&mut dom, &vec![Key::PageDown; 8]);
+        assert_eq!(highlighted(&dom), "2025-06-30");
+        assert!(dom.text().contains("June 2025"));
+    }
+
+    #[test]
+    fn test_enter_picks_the_highlighted_day_and_closes() {
+        let picked = Picked::default();
+        let mut dom = open_picker(picked.clone());
+        press(&mut dom, &[Key::ArrowRight, Key::Enter]);
+
+        assert_eq!(*picked.borrow(), [Some(date(3, 13))]);
+        assert!(dom.find_by_attribute("div", "role", "grid").is_none());
+    }
+
+    #[test]
+    fn test_days_out_of_range_cannot_be_picked() {
+        let picked = Picked::default();
+        let mut dom = open_picker(picked.clone());
+        press(&mut dom, &vec![Key::PageDown; 4]);
+        let july = dom
+            .find_by_attribute("button", "data-date", "2025-07-01")
+            .unwrap();
+        assert_eq!(dom.attribute(july, "disabled"), Some("true"));
+
+        let day = dom
+            .find_by_attribute("button", "data-date", "2025-06-18")
+            .unwrap();
+        dom.click(day);
+        assert_eq!(*picked.borrow(), [Some(date(6, 18))]);
+    }
+
+    #[test]
+    fn test_typed_entry_and_clearing() {
+        let picked = Picked::default();
+        let mut dom = open_picker(picked.clone());
+        let field = dom
+            .find_by_attribute("input", "aria-label", "Date")
+            .unwrap();
+
+        dom.change(field, "2025-05-02");
+        dom.change(field, "next week");
+        assert!(dom.text().contains("Type a date like"));
+        dom.change(field, "2026-01-01");
+        assert!(dom.text().contains("That date is out of range"));
+        dom.change(field, "");
+        assert_eq!(*picked.borrow(), [Some(date(5, 2)), None]);
+    }
+
+    #[test]
+    fn test_escape_closes_the_grid_only() {
+        let mut dom = open_picker(Picked::default());
+        press(&mut dom, &[Key::Escape]);
+        assert!(dom.find_by_attribute("div", "role", "grid").is_none());
+    }
+}
@@ -0,0 +1,111 @@
+use crate::components::theme_manager::use_motion;
+use crate::utils::dates::DayChip;
+use crate::utils::theme;
+use chrono::NaiveDate;
+use dioxus::prelude::*;
+
+/// Component showing the coming days as chips to schedule and filter todos
+///
+/// While a todo is `dragging`, the chips are outlined as drop targets and the
+/// chip under the drag is reported through `on_drag_target`, so dropping there
+/// schedules the todo instead of reordering it. Clicking a chip shows only the
+/// todos due that day.
+#[component]
+pub fn DayPlanner(
+    days: Vec<DayChip>,
+    selected: Option<NaiveDate>,
+    dragging: bool,
+    drop_target: Option<NaiveDate>,
+    on_select: EventHandler<Option<NaiveDate>>,
+    on_drag_target: EventHandler<Option<NaiveDate>>,
+    is_dark_mode: bool,
+) -> Element {
+    let motion_class = use_motion("transition-colors duration-200");
+
+    let drop_target_class = format!(
+        "ring-2 ring-blue-400 {}",
+        theme::highlight_class(is_dark_mode)
+    );
+
+    rsx! {
+        div {
+            class: "flex gap-2 mt-3 overflow-x-auto text-xs",
+            role: "group",
+            aria_label: "Plan by day",
+            for day in days {
+                {
+                    let date = day.date;
+                    let is_selected = selected == Some(date);
+                    let state_class = if drop_target == Some(date) {
+                        drop_target_class.as_str()
+                    } else {
+                        theme::pill_class(is_selected, is_dark_mode)
+                    };
+                    let drag_class = if dragging { "border-dashed" } else { "" };
+                    rsx! {
+                        button {
+                            key: "{date}",
+                            r#type: "button",
+                            class: "flex flex-col items-center min-w-[3.5rem] px-2 py-1 rounded-lg border {motion_class} {state_class} {drag_class}",
+                            title: if dragging { "Drop to schedule for {day.label}" } else { "Show todos due {day.label}" },
+                            aria_pressed: is_selected,
+                            onclick: move |_| on_select.call(if is_selected { None } else { Some(date) }),
+                            ondragenter: move |evt| {
+                                evt.prevent_default();
+                                on_drag_target.call(Some(date));
+                            },
+                            ondragover: move |evt| evt.prevent_default(),
+                            ondragleave: move |_| {
+                                if drop_target == Some(date) {
+                                    on_drag_target.call(None);
+                                }
+                            },
+                            ondrop: move |evt| evt.prevent_default(),
+                            // Children ignore the pointer so moving over them does not leave the chip
+                            span { class: "pointer-events-none font-medium", "{day.weekday}" }
+                            span { class: "pointer-events-none", "{day.label}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::dates::upcoming_days;
+    use dioxus::dioxus_core::{Mutation, Mutations};
+
+    #[test]
+    fn test_day_planner_rendering() {
+        let mut app = VirtualDom::new(|| {
+            let today = NaiveDate::from_ymd_opt(2025, 3, 30).unwrap();
+            rsx! {
+                DayPlanner {
+                    days: upcoming_days(today, 7),
+                    selected: None,
+                    dragging: false,
+                    drop_target: None,
+                    on_select: |_| {},
+                    on_drag_target: |_| {},
+                    is_dark_mode: false,
+                }
+            }
+        });
+
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        let texts: Vec<String> = mutations
+            .edits
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Mutation::CreateTextNode { value, .. } => Some(value),
+                _ => None,
+            })
+            .collect();
+        assert!(texts.contains(&"Mar 31".to_string()));
+        assert!(texts.contains(&"Apr 5".to_string()));
+    }
+}
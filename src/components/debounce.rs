@@ -0,0 +1,29 @@
+use crate::utils::timer;
+use dioxus::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Logic following a fast-changing value, such as text being typed, only
+/// once it stops changing
+///
+/// Returns the value as it was when it last stayed the same for `delay`,
+/// starting with the first value passed. Reading the result subscribes the
+/// component, so it renders again when the settled value changes.
+pub fn use_debounced<T: Clone + PartialEq + 'static>(value: T, delay: Duration) -> T {
+    let mut settled = use_signal(|| value.clone());
+    // The newest value seen, which a pending wait must still match to settle
+    let latest = use_hook(|| Rc::new(RefCell::new(value.clone())));
+
+    if *latest.borrow() != value {
+        latest.replace(value.clone());
+        let latest = latest.clone();
+        spawn(async move {
+            timer::sleep(delay).await;
+            if *latest.borrow() == value {
+                settled.set(value);
+            }
+        });
+    }
+    settled()
+}
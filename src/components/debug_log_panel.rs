@@ -0,0 +1,117 @@
+use crate::utils::debug_log::{self, LogEntry};
+use crate::utils::export;
+use crate::utils::theme;
+use chrono::Utc;
+use dioxus::prelude::*;
+use tracing::Level;
+
+/// Dialog showing the recent debug log, to attach to a bug report
+///
+/// Opened with Ctrl+Shift+L or from the settings. The entries are read when
+/// it opens and on "Refresh"; "Export" saves them as a text file.
+#[component]
+pub fn DebugLogPanel(on_close: EventHandler<()>, is_dark_mode: bool) -> Element {
+    let mut entries = use_signal(debug_log::entries);
+    let mut status = use_signal(|| None::<String>);
+
+    let card_class = theme::modal_panel_class(is_dark_mode);
+    let log_class = theme::inset_class(is_dark_mode);
+    let button_class = theme::secondary_button_class(is_dark_mode);
+    let level_class = |entry: &LogEntry| match entry.level {
+        Level::ERROR => "text-red-500",
+        Level::WARN => "text-amber-500",
+        _ => "",
+    };
+
+    let export_log = move |_| {
+        let file_name = format!("todo-debug-log-{}.txt", Utc::now().format("%Y-%m-%d"));
+        let message =
+            match export::save_export_file(&file_name, debug_log::export_text().as_bytes()) {
+                Ok(location) => location,
+                Err(e) => format!("Export failed: {:?}", e),
+            };
+        status.set(Some(message));
+    };
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-40 flex items-center justify-center bg-black/40 p-4",
+            onclick: move |_| on_close.call(()),
+            div {
+                class: "w-full max-w-3xl max-h-[80vh] flex flex-col p-5 rounded-lg border shadow-lg {card_class}",
+                role: "dialog",
+                aria_modal: "true",
+                aria_label: "Debug log",
+                onclick: move |evt| evt.stop_propagation(),
+                onkeydown: move |evt: Event<KeyboardData>| {
+                    if evt.key() == Key::Escape {
+                        on_close.call(());
+                    }
+                },
+                h2 { class: "text-lg font-semibold mb-1", "Debug log" }
+                p { class: "text-sm mb-3 opacity-75",
+                    "Recent activity, without the text of your todos. Attach an export to a bug report."
+                }
+                div { class: "flex-1 overflow-y-auto rounded p-2 font-mono text-xs whitespace-pre-wrap break-all {log_class}",
+                    if entries.read().is_empty() {
+                        p { class: "italic", "Nothing logged yet." }
+                    }
+                    for entry in entries.read().iter() {
+                        div { class: "{level_class(entry)}", "{entry}" }
+                    }
+                }
+                if let Some(message) = status() {
+                    p { class: "mt-2 text-sm", "{message}" }
+                }
+                div { class: "flex justify-end gap-2 mt-3",
+                    button {
+                        r#type: "button",
+                        class: "{button_class} text-sm",
+                        onclick: move |_| entries.set(debug_log::entries()),
+                        "Refresh"
+                    }
+                    button {
+                        r#type: "button",
+                        class: "{button_class} text-sm",
+                        onclick: export_log,
+                        "Export"
+                    }
+                    button {
+                        r#type: "button",
+                        class: "px-3 py-1 rounded bg-blue-500 text-white hover:bg-blue-600 text-sm",
+                        autofocus: true,
+                        onclick: move |_| on_close.call(()),
+                        "Close"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::{Mutation, Mutations};
+
+    #[test]
+    fn test_debug_log_panel_shows_entries() {
+        debug_log::record(LogEntry::new(
+            Level::INFO,
+            "todo::components::debug_log_panel",
+            "save key=todo/v1/todos bytes=42 result=ok",
+        ));
+
+        let mut app = VirtualDom::new(|| {
+            rsx! {
+                DebugLogPanel { on_close: |_| {}, is_dark_mode: false }
+            }
+        });
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        assert!(mutations.edits.iter().any(|edit| matches!(
+            edit,
+            Mutation::CreateTextNode { value, .. } if value.ends_with("save key=todo/v1/todos bytes=42 result=ok")
+        )));
+    }
+}
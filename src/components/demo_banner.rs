@@ -0,0 +1,36 @@
+use crate::components::theme_manager::use_motion;
+use crate::utils::theme;
+use dioxus::prelude::*;
+
+/// Component telling demo visitors that their changes are not saved
+#[component]
+pub fn DemoBanner(is_dark_mode: bool) -> Element {
+    let motion_class = use_motion("transition-colors");
+
+    let banner_class = theme::notice_class(theme::Tone::Warning, is_dark_mode);
+
+    rsx! {
+        div {
+            class: "mb-4 px-3 py-2 rounded-lg border text-sm text-center {banner_class} {motion_class}",
+            role: "status",
+            "Demo mode — changes are not saved"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::Mutations;
+
+    #[test]
+    fn test_demo_banner_rendering() {
+        let mut app = VirtualDom::new(|| {
+            rsx! {
+                DemoBanner { is_dark_mode: false }
+            }
+        });
+
+        app.rebuild(&mut Mutations::default());
+    }
+}
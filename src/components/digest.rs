@@ -0,0 +1,101 @@
+use crate::components::clock::use_clock;
+use crate::models::digest::{self, DigestSummary};
+use crate::models::quiet::QuietMode;
+use crate::models::{AppSettings, TodoList};
+use crate::utils::constants::app::APP_NAME;
+use crate::utils::constants::todo::DIGEST_CHECK_INTERVAL_SECS;
+use crate::utils::{self, keys, timer};
+use chrono::NaiveDate;
+use dioxus::prelude::*;
+use std::time::Duration;
+
+/// Shows a system notification, then reports whether it was clicked.
+const SHOW_NOTIFICATION: &str = r#"
+    const [title, body] = await dioxus.recv();
+    if (!("Notification" in window)) {
+        dioxus.send(false);
+        return;
+    }
+    if (Notification.permission === "default") {
+        await Notification.requestPermission();
+    }
+    if (Notification.permission !== "granted") {
+        dioxus.send(false);
+        return;
+    }
+    const notification = new Notification(title, { body });
+    notification.onclick = () => {
+        window.focus();
+        dioxus.send(true);
+        notification.close();
+    };
+    notification.onclose = () => dioxus.send(false);
+    await new Promise(() => {});
+"#;
+
+/// Shows the digest notification and calls `on_open` if it is clicked.
+async fn notify(summary: DigestSummary, on_open: Callback<()>) {
+    let mut eval = document::eval(SHOW_NOTIFICATION);
+    let _ = eval.send((APP_NAME, summary.message()));
+    if let Ok(true) = eval.recv::<bool>().await {
+        on_open.call(());
+    }
+}
+
+/// Logic for the daily digest notification
+///
+/// Once the todos are loaded, waits for the digest time from settings and
+/// shows a notification of the overdue todos and todos due today, unless
+/// there are none. The wait is cut short every few minutes and whenever the
+/// window regains focus, so a digest missed while the computer slept fires
+/// soon after it wakes. The day is stored so it fires once a day at most, and
+/// a digest due during "Do not disturb" is skipped for the day.
+pub fn use_daily_digest(
+    todo_list: Signal<TodoList>,
+    loaded: Signal<bool>,
+    settings: Signal<AppSettings>,
+    on_open: Callback<()>,
+) {
+    let clock = use_clock();
+    let quiet_mode = use_context::<Signal<QuietMode>>();
+
+    use_hook(move || {
+        spawn(async move {
+            let check_interval = Duration::from_secs(DIGEST_CHECK_INTERVAL_SECS);
+            loop {
+                let (enabled, at, skip_weekends) = {
+                    let settings = settings.peek();
+                    (
+                        settings.daily_digest,
+                        settings.digest_time,
+                        settings.digest_skip_weekends,
+                    )
+                };
+                if !enabled {
+                    timer::sleep_or_wake(check_interval).await;
+                    continue;
+                }
+                if !*loaded.peek() {
+                    timer::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+
+                let now = clock.now_local();
+                let last_fired = utils::load::<NaiveDate>(keys::DIGEST_FIRED).ok();
+                if digest::is_due(now, at, last_fired, skip_weekends) {
+                    let _ = utils::save(keys::DIGEST_FIRED, &now.date());
+                    let summary = digest::summarize(&todo_list.peek().all(), now.date());
+                    let allowed = quiet_mode.peek().notifications_allowed(clock.now_utc());
+                    if allowed && !summary.is_empty() {
+                        spawn(notify(summary, on_open));
+                    }
+                    continue;
+                }
+
+                let next = digest::next_fire(now, at, last_fired, skip_weekends);
+                let wait = (next - now).to_std().unwrap_or_default();
+                timer::sleep_or_wake(wait.min(check_interval)).await;
+            }
+        });
+    });
+}
@@ -0,0 +1,182 @@
+use crate::components::{Modal, ModalSize};
+use crate::models::duplicates::{DuplicateGroup, Merge};
+use crate::utils::theme;
+use chrono::Local;
+use dioxus::prelude::*;
+use std::collections::HashSet;
+
+/// Dialog listing open todos that repeat each other, merging the groups
+/// ticked
+///
+/// Every group has a checkbox, ticked to begin with, and lists its todos
+/// with their due dates and tags, marking the one a merge keeps. Merging
+/// keeps that todo with every tag of the group and the earliest due date,
+/// and moves the others to the trash.
+#[component]
+pub fn DuplicatesDialog(
+    groups: Vec<DuplicateGroup>,
+    on_merge: EventHandler<Vec<Merge>>,
+    on_close: EventHandler<()>,
+    is_dark_mode: bool,
+) -> Element {
+    // Positions of the groups left out
+    let mut skipped = use_signal(HashSet::<usize>::new);
+
+    let hint_class = theme::muted_text_class(is_dark_mode);
+    let merge_class = theme::primary_button_class(is_dark_mode);
+    let tag_class = theme::tag_pill_class(is_dark_mode);
+
+    let merges: Vec<Merge> = groups
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !skipped.read().contains(index))
+        .map(|(_, group)| group.merge())
+        .collect();
+    let count = merges.len();
+
+    rsx! {
+        Modal {
+            title: "Duplicates",
+            on_close,
+            size: ModalSize::Large,
+            is_dark_mode,
+            div { class: "max-h-96 overflow-y-auto text-sm space-y-3",
+                if groups.is_empty() {
+                    p { class: "{hint_class}", "No open todos repeat each other." }
+                }
+                for (index , group) in groups.iter().enumerate() {
+                    {
+                        let keep = group.merge().keep;
+                        let text = group.todos[0].text.clone();
+                        let checked = !skipped.read().contains(&index);
+                        rsx! {
+                            fieldset { key: "{index}",
+                                label { class: "flex items-center gap-2 font-medium",
+                                    input {
+                                        r#type: "checkbox",
+                                        checked,
+                                        aria_label: "Merge {text}",
+                                        onclick: move |_| {
+                                            let mut skipped = skipped.write();
+                                            if !skipped.remove(&index) {
+                                                skipped.insert(index);
+                                            }
+                                        },
+                                    }
+                                    span { class: "break-words", "{text} ×{group.todos.len()}" }
+                                    if !group.differs() {
+                                        span { class: "font-normal {hint_class}", "identical" }
+                                    }
+                                }
+                                ul { class: "ml-6 mt-1 space-y-0.5",
+                                    for todo in group.todos.iter() {
+                                        li { key: "{todo.id}", class: "flex flex-wrap items-center gap-1.5",
+                                            span { class: "{hint_class}",
+                                                match todo.due_date {
+                                                    Some(due) => format!("Due {}", due.with_timezone(&Local).format("%b %d, %Y")),
+                                                    None => "No due date".to_string(),
+                                                }
+                                            }
+                                            for tag in todo.tags.iter() {
+                                                span { key: "{tag}", class: "px-1.5 rounded-full text-xs {tag_class}", "{tag}" }
+                                            }
+                                            if todo.id == keep {
+                                                span { class: "text-xs font-medium", "kept" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            div { class: "flex justify-end space-x-2 mt-4",
+                button {
+                    r#type: "button",
+                    class: "px-3 py-1 rounded text-sm {hint_class} hover:underline",
+                    onclick: move |_| on_close.call(()),
+                    "Cancel"
+                }
+                button {
+                    r#type: "button",
+                    class: "{merge_class} text-sm disabled:opacity-50",
+                    disabled: count == 0,
+                    onclick: move |_| on_merge.call(merges.clone()),
+                    if count == 1 {
+                        "Merge 1 group"
+                    } else {
+                        "Merge {count} groups"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use crate::models::TodoList;
+    use crate::models::duplicates::find_duplicates;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Merged = Rc<RefCell<Vec<Vec<Merge>>>>;
+
+    /// Renders the dialog for two groups: "Renew domain" three times, one of
+    /// them tagged, and "Buy milk" twice.
+    fn dialog() -> (TestDom, Merged) {
+        let mut list = TodoList::new();
+        let first = list.add("Renew domain".to_string());
+        list.add("Buy milk".to_string());
+        let tagged = list.add("renew domain".to_string());
+        list.add_tag(tagged, "Work".to_string());
+        list.add("Buy  milk".to_string());
+        list.add("Renew Domain".to_string());
+        let groups = find_duplicates(&list);
+        assert_eq!(groups[0].todos[0].id, first);
+
+        let merged = Merged::default();
+        let dom = TestDom::with_props(
+            move |merged: Merged| {
+                rsx! {
+                    DuplicatesDialog {
+                        groups: groups.clone(),
+                        on_merge: move |merges| merged.borrow_mut().push(merges),
+                        on_close: |_| {},
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            merged.clone(),
+        );
+        (dom, merged)
+    }
+
+    #[test]
+    fn test_groups_show_their_differences() {
+        let (dom, _) = dialog();
+        assert!(dom.text().contains("Renew domain ×3"));
+        assert!(dom.text().contains("Buy milk ×2identical"));
+        assert!(dom.find("span", "Work").is_some());
+        assert_eq!(dom.find_all("span", "kept").len(), 2);
+    }
+
+    #[test]
+    fn test_merging_only_the_ticked_groups() {
+        let (mut dom, merged) = dialog();
+        let milk = dom
+            .find_by_attribute("input", "aria-label", "Merge Buy milk")
+            .unwrap();
+        dom.click(milk);
+        dom.click(dom.find("button", "Merge 1 group").unwrap());
+
+        let merges = merged.borrow();
+        assert_eq!(merges.len(), 1);
+        assert_eq!(merges[0].len(), 1);
+        assert_eq!(merges[0][0].tags, ["Work"]);
+        assert_eq!(merges[0][0].remove.len(), 2);
+    }
+}
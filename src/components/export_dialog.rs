@@ -0,0 +1,347 @@
+use crate::components::ImportPreview;
+use crate::components::clock::use_clock;
+use crate::models::import_plan::ImportPlan;
+use crate::models::{Todo, TodoList, WeekStart};
+use crate::utils::constants::export::{ENCRYPTED_EXTENSION, WEEKLY_REPORT_NAME};
+use crate::utils::export::{self, ExportError};
+use crate::utils::theme;
+use crate::utils::{clipboard, reports};
+use dioxus::prelude::*;
+
+/// Formats offered by the export dialog.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ExportFormat {
+    Json,
+    Encrypted,
+}
+
+/// Which todos the export dialog writes out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ExportScope {
+    Everything,
+    CurrentView,
+}
+
+/// Props for the ExportDialog component.
+#[derive(Props, PartialEq, Clone)]
+pub struct ExportDialogProps {
+    /// The todos to export
+    pub todos: Vec<Todo>,
+    /// Ids of the todos on screen, exported with the "Current view" scope
+    #[props(default)]
+    pub visible_ids: Vec<usize>,
+    /// Names of the filters applied to the list, put in the file name of
+    /// "Current view" exports
+    #[props(default)]
+    pub view_name: Vec<String>,
+    /// Callback when an import preview was applied, returning how many
+    /// todos were added and merged into
+    pub on_import: Callback<ImportPlan, (usize, usize)>,
+    /// First day of the week covered by the weekly report
+    #[props(default)]
+    pub week_start: WeekStart,
+    /// Whether dark mode is enabled
+    #[props(default = false)]
+    pub is_dark_mode: bool,
+}
+
+/// Turns an export error into a message for the user.
+pub fn describe_error(error: &ExportError) -> String {
+    match error {
+        ExportError::WrongPassphrase => "Wrong password.".to_string(),
+        ExportError::CorruptFile(reason) => format!("The file is corrupt: {}", reason),
+        ExportError::UnsupportedVersion(version) => format!(
+            "This backup was made by a newer version of the app (format {}).",
+            version
+        ),
+        ExportError::SerializeError(reason)
+        | ExportError::CryptoError(reason)
+        | ExportError::IoError(reason) => format!("Export failed: {}", reason),
+        ExportError::InvalidTodos(problems) => {
+            format!("Some todos cannot be imported: {}", problems.join("; "))
+        }
+    }
+}
+
+/// Dialog for exporting todos to a file and importing them back, and for
+/// the weekly report.
+#[component]
+pub fn ExportDialog(props: ExportDialogProps) -> Element {
+    let mut format = use_signal(|| ExportFormat::Json);
+    let mut scope = use_signal(|| ExportScope::Everything);
+    let mut include_history = use_signal(|| false);
+    let clock = use_clock();
+    let mut passphrase = use_signal(String::new);
+    let mut import_passphrase = use_signal(String::new);
+    let mut pending_bundle = use_signal(|| None::<Vec<u8>>);
+    let mut status = use_signal(|| None::<String>);
+    let mut preview = use_signal(|| None::<ImportPlan>);
+
+    // The weekly report covers every todo, whatever the scope
+    let weekly_report = {
+        let todos = props.todos.clone();
+        let week_start = props.week_start;
+        let clock = clock.clone();
+        move || {
+            reports::weekly_html(
+                &TodoList::from_todos(todos.clone()),
+                clock.today_local(),
+                week_start,
+            )
+        }
+    };
+    let save_report = {
+        let weekly_report = weekly_report.clone();
+        let clock = clock.clone();
+        move |_| {
+            let file_name =
+                export::export_file_name(&[WEEKLY_REPORT_NAME], clock.today_local(), "html");
+            let result = export::save_export_file(&file_name, weekly_report().as_bytes());
+            status.set(Some(result.unwrap_or_else(|e| describe_error(&e))));
+        }
+    };
+    let copy_report = move |_| {
+        clipboard::write_html(&weekly_report());
+        status.set(Some(
+            "Copied the weekly report; paste it into an email.".to_string(),
+        ));
+    };
+
+    let todos = props.todos.clone();
+    let visible_ids = props.visible_ids.clone();
+    let view_name = props.view_name.clone();
+    let handle_export = move |_| {
+        let mut list = TodoList::from_todos(todos.clone());
+        if !include_history() {
+            list = export::without_text_history(&list);
+        }
+        let today = clock.today_local();
+        // Everything exports the whole list; the current view only what is on screen
+        let (ids, name_parts) = match scope() {
+            ExportScope::Everything => (None, Vec::new()),
+            ExportScope::CurrentView => (Some(&visible_ids), view_name.clone()),
+        };
+        let result = match format() {
+            ExportFormat::Json => match ids {
+                Some(ids) => export::export_json_subset(&list, ids),
+                None => export::export_json(&list),
+            }
+            .and_then(|json| {
+                export::save_export_file(
+                    &export::export_file_name(&name_parts, today, "json"),
+                    json.as_bytes(),
+                )
+            }),
+            ExportFormat::Encrypted => {
+                if passphrase.read().is_empty() {
+                    status.set(Some("Enter a password for the backup.".to_string()));
+                    return;
+                }
+                match ids {
+                    Some(ids) => export::export_encrypted_subset(&list, ids, &passphrase.read()),
+                    None => export::export_encrypted(&list, &passphrase.read()),
+                }
+                .and_then(|bundle| {
+                    export::save_export_file(
+                        &export::export_file_name(&name_parts, today, ENCRYPTED_EXTENSION),
+                        &bundle,
+                    )
+                })
+            }
+        };
+        status.set(Some(result.unwrap_or_else(|e| describe_error(&e))));
+    };
+
+    // The file is only read here; nothing changes until the preview is applied
+    let existing = props.todos.clone();
+    let mut finish_import = move |result: Result<Vec<Todo>, ExportError>| match result {
+        Ok(todos) => {
+            preview.set(Some(ImportPlan::new(todos, &existing)));
+            pending_bundle.set(None);
+            import_passphrase.set(String::new());
+            status.set(None);
+        }
+        Err(e) => status.set(Some(describe_error(&e))),
+    };
+
+    let apply_import = move |plan: ImportPlan| {
+        let (added, merged) = props.on_import.call(plan);
+        preview.set(None);
+        status.set(Some(format!(
+            "Imported {} new todos and merged {} into existing ones.",
+            added, merged
+        )));
+    };
+
+    let handle_file = {
+        let finish_import = finish_import.clone();
+        move |evt: Event<FormData>| {
+            let mut finish_import = finish_import.clone();
+            async move {
+                let Some(files) = evt.files() else {
+                    return;
+                };
+                let Some(name) = files.files().into_iter().next() else {
+                    return;
+                };
+                let Some(bytes) = files.read_file(&name).await else {
+                    status.set(Some(format!("Could not read {}.", name)));
+                    return;
+                };
+
+                if export::is_encrypted(&bytes) {
+                    pending_bundle.set(Some(bytes));
+                    status.set(Some("Enter the backup password to import.".to_string()));
+                } else {
+                    let result = String::from_utf8(bytes)
+                        .map_err(|e| ExportError::CorruptFile(e.to_string()))
+                        .and_then(|json| export::read_json(&json));
+                    finish_import(result);
+                }
+            }
+        }
+    };
+
+    let handle_decrypt = move |evt: Event<FormData>| {
+        evt.prevent_default();
+        if let Some(bundle) = pending_bundle() {
+            finish_import(
+                export::decrypt_bundle(&bundle, &import_passphrase.read())
+                    .and_then(|json| export::read_json(&json)),
+            );
+        }
+    };
+
+    // Dynamic classes based on dark mode
+    let text_class = theme::secondary_text_class(props.is_dark_mode);
+    let input_class = theme::form_control_class(props.is_dark_mode);
+    let button_class = theme::secondary_button_class(props.is_dark_mode);
+
+    rsx! {
+      div { class: "flex flex-col space-y-2 text-sm {text_class}",
+        // Export
+        div { class: "flex flex-wrap items-center gap-2",
+          select {
+            class: "{input_class}",
+            aria_label: "Export scope",
+            onchange: move |evt| {
+                scope
+                    .set(
+                        if evt.value() == "view" {
+                            ExportScope::CurrentView
+                        } else {
+                            ExportScope::Everything
+                        },
+                    )
+            },
+            option {
+              value: "everything",
+              selected: scope() == ExportScope::Everything,
+              "Everything ({props.todos.len()})"
+            }
+            option {
+              value: "view",
+              selected: scope() == ExportScope::CurrentView,
+              "Current view ({props.visible_ids.len()})"
+            }
+          }
+          select {
+            class: "{input_class}",
+            aria_label: "Export format",
+            onchange: move |evt| {
+                format
+                    .set(
+                        if evt.value() == "encrypted" {
+                            ExportFormat::Encrypted
+                        } else {
+                            ExportFormat::Json
+                        },
+                    )
+            },
+            option { value: "json", selected: format() == ExportFormat::Json, "JSON" }
+            option {
+              value: "encrypted",
+              selected: format() == ExportFormat::Encrypted,
+              "Encrypted backup (.{ENCRYPTED_EXTENSION})"
+            }
+          }
+          if format() == ExportFormat::Encrypted {
+            input {
+              class: "{input_class}",
+              r#type: "password",
+              placeholder: "Backup password",
+              value: "{passphrase.read()}",
+              oninput: move |evt| passphrase.set(evt.value()),
+            }
+          }
+          label { class: "flex items-center gap-1",
+            input {
+              r#type: "checkbox",
+              checked: include_history(),
+              onchange: move |evt| include_history.set(evt.checked()),
+            }
+            "Include edit history"
+          }
+          button {
+            r#type: "button",
+            class: "{button_class}",
+            onclick: handle_export,
+            "Export"
+          }
+        }
+
+        // Weekly report
+        div { class: "flex flex-wrap items-center gap-2",
+          button {
+            r#type: "button",
+            class: "{button_class}",
+            onclick: save_report,
+            "Generate weekly report"
+          }
+          button {
+            r#type: "button",
+            class: "{button_class}",
+            onclick: copy_report,
+            "Copy for email"
+          }
+        }
+
+        // Import
+        div { class: "flex flex-wrap items-center gap-2",
+          label { "Import (previewed before anything changes):" }
+          input {
+            r#type: "file",
+            accept: ".json,.{ENCRYPTED_EXTENSION}",
+            onchange: handle_file,
+          }
+        }
+        if pending_bundle.read().is_some() {
+          form {
+            class: "flex flex-wrap items-center gap-2",
+            onsubmit: handle_decrypt,
+            input {
+              class: "{input_class}",
+              r#type: "password",
+              placeholder: "Backup password",
+              value: "{import_passphrase.read()}",
+              oninput: move |evt| import_passphrase.set(evt.value()),
+            }
+            button { r#type: "submit", class: "{button_class}", "Decrypt" }
+          }
+        }
+
+        if let Some(message) = status() {
+          p { "{message}" }
+        }
+
+        if let Some(plan) = preview() {
+          ImportPreview {
+            plan,
+            on_apply: apply_import,
+            on_cancel: move |_| preview.set(None),
+            is_dark_mode: props.is_dark_mode,
+          }
+        }
+      }
+    }
+}
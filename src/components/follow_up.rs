@@ -0,0 +1,143 @@
+use crate::components::clock::use_clock;
+use crate::components::theme_manager::use_motion;
+use crate::models::Todo;
+use crate::models::staleness::staleness;
+use crate::utils;
+use crate::utils::keys;
+use crate::utils::theme;
+use chrono::NaiveDate;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Logic for prompting about waiting todos at most once a day
+///
+/// Returns whether the follow-up banner is open. It opens the first time
+/// `pending` lists todos on a day it was not shown yet, and the day is
+/// stored so it stays closed until tomorrow once dismissed.
+pub fn use_follow_up_prompt(pending: Memo<Vec<usize>>) -> Signal<bool> {
+    let mut open = use_signal(|| false);
+    let clock = use_clock();
+
+    use_effect(move || {
+        if pending.read().is_empty() || *open.peek() {
+            return;
+        }
+        let today = clock.today_local();
+        if utils::load::<NaiveDate>(keys::FOLLOW_UP_SHOWN).ok() == Some(today) {
+            return;
+        }
+        let _ = utils::save(keys::FOLLOW_UP_SHOWN, &today);
+        open.set(true);
+    });
+
+    open
+}
+
+/// Banner listing waiting todos that are due a follow-up
+///
+/// "Bump" marks a todo as followed up so it waits again; "Done" completes it.
+#[component]
+pub fn FollowUpBanner(
+    todos: Vec<Rc<Todo>>,
+    on_bump: EventHandler<usize>,
+    on_done: EventHandler<usize>,
+    on_dismiss: EventHandler<()>,
+    is_dark_mode: bool,
+) -> Element {
+    let motion_class = use_motion("transition-colors");
+
+    let banner_class = theme::notice_class(theme::Tone::Info, is_dark_mode);
+    let button_class = format!(
+        "px-2 py-0.5 rounded border {}",
+        theme::outline_button_class(is_dark_mode)
+    );
+    let now = use_clock().now_utc();
+
+    rsx! {
+        div {
+            class: "mb-4 p-4 rounded-lg border text-sm {banner_class} {motion_class}",
+            role: "status",
+            div { class: "flex items-center justify-between mb-2",
+                p { class: "font-semibold", "Needs follow-up" }
+                button {
+                    r#type: "button",
+                    class: "hover:underline",
+                    onclick: move |_| on_dismiss.call(()),
+                    "Dismiss"
+                }
+            }
+            ul { class: "space-y-2",
+                for todo in todos {
+                    {
+                        let todo_id = todo.id;
+                        let days = staleness(&todo, now).map(|age| age.num_days()).unwrap_or_default();
+                        rsx! {
+                            li { key: "{todo_id}", class: "flex items-center gap-2",
+                                span { class: "flex-1 min-w-0 truncate", title: "{todo.text}", "{todo.text}" }
+                                span { class: "shrink-0 opacity-75", "waiting {days} days" }
+                                button {
+                                    r#type: "button",
+                                    class: "{button_class}",
+                                    title: "Mark as followed up",
+                                    onclick: move |_| on_bump.call(todo_id),
+                                    "Bump"
+                                }
+                                button {
+                                    r#type: "button",
+                                    class: "{button_class}",
+                                    onclick: move |_| on_done.call(todo_id),
+                                    "Done"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::clock::AppClock;
+    use crate::utils::clock::FixedClock;
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use dioxus::dioxus_core::{Mutation, Mutations};
+
+    /// The moment the banner is rendered at.
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 12, 9, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn test_follow_up_banner_lists_waiting_todos() {
+        let mut app = VirtualDom::new(|| {
+            let mut todo = Todo::new(3, "Quote from the plumber".to_string());
+            todo.updated_at = Some(now() - Duration::days(9));
+            rsx! {
+                FollowUpBanner {
+                    todos: vec![Rc::new(todo)],
+                    on_bump: |_| {},
+                    on_done: |_| {},
+                    on_dismiss: |_| {},
+                    is_dark_mode: false,
+                }
+            }
+        })
+        .with_root_context(AppClock(Rc::new(FixedClock::new(now()))));
+
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        let texts: Vec<String> = mutations
+            .edits
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Mutation::CreateTextNode { value, .. } => Some(value),
+                _ => None,
+            })
+            .collect();
+        assert!(texts.contains(&"Quote from the plumber".to_string()));
+        assert!(texts.contains(&"waiting 9 days".to_string()));
+    }
+}
@@ -0,0 +1,203 @@
+use crate::components::modal::{Modal, ModalSize};
+use crate::models::import_plan::{ImportPlan, ImportRow};
+use crate::utils::theme;
+use dioxus::prelude::*;
+use std::collections::HashSet;
+
+/// Dialog showing what an import would change before it is applied
+///
+/// New todos and duplicates each have a checkbox, all ticked to begin
+/// with; unticked rows are left out. Todos that cannot be imported are
+/// listed with the reason and are never applied.
+#[component]
+pub fn ImportPreview(
+    plan: ImportPlan,
+    on_apply: EventHandler<ImportPlan>,
+    on_cancel: EventHandler<()>,
+    is_dark_mode: bool,
+) -> Element {
+    let mut skipped = use_signal(HashSet::<ImportRow>::new);
+
+    let mut toggle = move |row: ImportRow| {
+        let mut skipped = skipped.write();
+        if !skipped.remove(&row) {
+            skipped.insert(row);
+        }
+    };
+
+    let selected = plan.without(&skipped.read());
+    let count = selected.additions.len() + selected.duplicates.len();
+    let hint_class = theme::secondary_text_class(is_dark_mode);
+    let danger_class = theme::danger_text_class(is_dark_mode);
+    let heading_class = "text-sm font-medium mt-3 mb-1";
+
+    let additions: Vec<(usize, String, bool)> = plan
+        .additions
+        .iter()
+        .enumerate()
+        .map(|(index, todo)| {
+            let checked = !skipped.read().contains(&ImportRow::Addition(index));
+            (index, todo.text.clone(), checked)
+        })
+        .collect();
+    let duplicates: Vec<(usize, String, String, bool)> = plan
+        .duplicates
+        .iter()
+        .enumerate()
+        .map(|(index, duplicate)| {
+            let checked = !skipped.read().contains(&ImportRow::Duplicate(index));
+            (
+                index,
+                duplicate.todo.text.clone(),
+                duplicate.existing_text.clone(),
+                checked,
+            )
+        })
+        .collect();
+
+    rsx! {
+        Modal {
+            title: "Import preview",
+            on_close: on_cancel,
+            size: ModalSize::Large,
+            is_dark_mode,
+            div { class: "max-h-96 overflow-y-auto text-sm",
+                if plan.is_empty() && plan.errors.is_empty() {
+                    p { class: "{hint_class}", "The file has no todos." }
+                }
+                if !additions.is_empty() {
+                    h3 { class: "{heading_class}", "New ({additions.len()})" }
+                    ul { class: "space-y-1",
+                        for (index , text , checked) in additions {
+                            li { key: "add-{index}",
+                                label { class: "flex items-center gap-2",
+                                    input {
+                                        r#type: "checkbox",
+                                        checked,
+                                        aria_label: "Import {text}",
+                                        onclick: move |_| toggle(ImportRow::Addition(index)),
+                                    }
+                                    span { class: "break-words", "{text}" }
+                                }
+                            }
+                        }
+                    }
+                }
+                if !duplicates.is_empty() {
+                    h3 { class: "{heading_class}", "Already in the list ({duplicates.len()})" }
+                    ul { class: "space-y-1",
+                        for (index , text , existing , checked) in duplicates {
+                            li { key: "merge-{index}",
+                                label { class: "flex items-center gap-2",
+                                    input {
+                                        r#type: "checkbox",
+                                        checked,
+                                        aria_label: "Merge {text}",
+                                        onclick: move |_| toggle(ImportRow::Duplicate(index)),
+                                    }
+                                    span { class: "break-words",
+                                        "{text} "
+                                        span { class: "{hint_class}", "merges with '{existing}'" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                if !plan.errors.is_empty() {
+                    h3 { class: "{heading_class} {danger_class}", "Cannot import ({plan.errors.len()})" }
+                    ul { class: "space-y-1",
+                        for problem in plan.errors.iter() {
+                            li { key: "error-{problem.position}",
+                                "Todo {problem.position} "
+                                span { class: "break-words", "'{problem.text}'" }
+                                span { class: "{hint_class}", ": {problem.reason}" }
+                            }
+                        }
+                    }
+                }
+            }
+            div { class: "flex justify-end space-x-2 mt-4",
+                button {
+                    r#type: "button",
+                    class: "px-3 py-1 rounded text-sm {hint_class} hover:underline",
+                    onclick: move |_| on_cancel.call(()),
+                    "Cancel"
+                }
+                button {
+                    r#type: "button",
+                    class: "px-3 py-1 rounded bg-blue-500 text-white hover:bg-blue-600 text-sm disabled:opacity-50",
+                    disabled: count == 0,
+                    onclick: move |_| on_apply.call(selected.clone()),
+                    "Import {count}"
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use crate::models::Todo;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Applied = Rc<RefCell<Vec<ImportPlan>>>;
+
+    /// Renders the preview of importing "Call mum", "buy milk" and a blank
+    /// todo into a list holding "Buy milk".
+    fn render(applied: Applied) -> TestDom {
+        let existing = [Todo::new(1, "Buy milk".to_string())];
+        let imported = vec![
+            Todo::new(5, "Call mum".to_string()),
+            Todo::new(6, "buy milk".to_string()),
+            Todo::new(7, " ".to_string()),
+        ];
+        let plan = ImportPlan::new(imported, &existing);
+        TestDom::with_props(
+            |(plan, applied): (ImportPlan, Applied)| {
+                rsx! {
+                    ImportPreview {
+                        plan,
+                        on_apply: move |plan| applied.borrow_mut().push(plan),
+                        on_cancel: |_| {},
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            (plan, applied),
+        )
+    }
+
+    #[test]
+    fn test_preview_lists_additions_merges_and_problems() {
+        let dom = render(Applied::default());
+        let text = dom.text();
+
+        assert!(text.contains("New (1)"));
+        assert!(text.contains("merges with 'Buy milk'"));
+        assert!(text.contains("Cannot import (1)"));
+        assert!(text.contains("Todo 3"));
+        assert!(dom.find("button", "Import 2").is_some());
+    }
+
+    #[test]
+    fn test_unticked_rows_are_left_out() {
+        let applied = Applied::default();
+        let mut dom = render(applied.clone());
+
+        let merge = dom
+            .find_by_attribute("input", "aria-label", "Merge buy milk")
+            .unwrap();
+        dom.click(merge);
+        let apply = dom.find("button", "Import 1").unwrap();
+        dom.click(apply);
+
+        let applied = applied.borrow();
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].additions[0].text, "Call mum");
+        assert!(applied[0].duplicates.is_empty());
+    }
+}
@@ -0,0 +1,208 @@
+use crate::models::{IssueLinkRule, IssueLinker};
+use crate::utils::theme;
+use dioxus::prelude::*;
+
+/// Props for the IssueLinkRules component.
+#[derive(Props, PartialEq, Clone)]
+pub struct IssueLinkRulesProps {
+    /// The saved rules
+    pub rules: Vec<IssueLinkRule>,
+    /// Callback when a rule is added, changed or removed
+    pub on_rules_change: EventHandler<Vec<IssueLinkRule>>,
+    /// Whether dark mode is enabled
+    #[props(default = false)]
+    pub is_dark_mode: bool,
+}
+
+/// Describes a rule in a single line, like `/[A-Z]+-\d+/ → https://…/{0}`.
+fn describe_rule(rule: &IssueLinkRule) -> String {
+    format!("/{}/ → {}", rule.pattern, rule.url_template)
+}
+
+/// Editor for the rules that link issue-tracker references in todo text
+#[component]
+pub fn IssueLinkRules(props: IssueLinkRulesProps) -> Element {
+    let mut draft = use_signal(IssueLinkRule::default);
+    let mut editing_index = use_signal(|| None::<usize>);
+    let mut error = use_signal(|| None::<String>);
+    let mut sample = use_signal(String::new);
+
+    // Regex and template errors are reported here, before the rule is stored
+    let save_rule = {
+        let rules = props.rules.clone();
+        move |evt: Event<FormData>| {
+            evt.prevent_default();
+            let rule = IssueLinkRule {
+                pattern: draft.read().pattern.trim().to_string(),
+                url_template: draft.read().url_template.trim().to_string(),
+            };
+            if let Err(e) = rule.validate() {
+                error.set(Some(e.to_string()));
+                return;
+            }
+
+            let mut rules = rules.clone();
+            match editing_index() {
+                Some(index) if index < rules.len() => rules[index] = rule,
+                _ => rules.push(rule),
+            }
+            props.on_rules_change.call(rules);
+            draft.set(IssueLinkRule::default());
+            editing_index.set(None);
+            error.set(None);
+        }
+    };
+
+    // Addresses the sample text would link to with the saved rules and the draft
+    let sample_links = {
+        let mut rules = props.rules.clone();
+        if draft.read().validate().is_ok() {
+            match editing_index() {
+                Some(index) if index < rules.len() => rules[index] = draft(),
+                _ => rules.push(draft()),
+            }
+        }
+        IssueLinker::new(&rules)
+            .find(&sample.read())
+            .into_iter()
+            .map(|link| link.url)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let text_class = theme::secondary_text_class(props.is_dark_mode);
+    let input_class = theme::form_control_class(props.is_dark_mode);
+    let button_class = theme::secondary_button_class(props.is_dark_mode);
+    let link_class = theme::link_class(props.is_dark_mode);
+
+    rsx! {
+      div { class: "flex flex-col space-y-2 text-sm {text_class}",
+        if props.rules.is_empty() {
+          p { "No rules yet. Rules turn references like ABC-123 into links to your issue tracker." }
+        }
+        ul { class: "space-y-1",
+          for (index, rule) in props.rules.iter().enumerate() {
+            li { key: "{index}", class: "flex items-center gap-2",
+              span { class: "flex-1 truncate font-mono", "{describe_rule(rule)}" }
+              button {
+                r#type: "button",
+                class: "text-xs {link_class}",
+                onclick: {
+                    let rule = rule.clone();
+                    move |_| {
+                        draft.set(rule.clone());
+                        editing_index.set(Some(index));
+                        error.set(None);
+                    }
+                },
+                "Edit"
+              }
+              button {
+                r#type: "button",
+                class: "text-xs {link_class}",
+                onclick: {
+                    let rules = props.rules.clone();
+                    move |_| {
+                        let mut rules = rules.clone();
+                        rules.remove(index);
+                        props.on_rules_change.call(rules);
+                        editing_index.set(None);
+                    }
+                },
+                "Delete"
+              }
+            }
+          }
+        }
+
+        form { class: "flex flex-wrap items-center gap-2", onsubmit: save_rule,
+          input {
+            class: "{input_class} w-32",
+            placeholder: r"[A-Z]+-\d+",
+            aria_label: "Pattern",
+            value: "{draft.read().pattern}",
+            oninput: move |evt| draft.write().pattern = evt.value(),
+          }
+          input {
+            class: "{input_class} flex-1 min-w-32",
+            placeholder: "https://jira.example.com/browse/{{0}}",
+            aria_label: "Link address",
+            value: "{draft.read().url_template}",
+            oninput: move |evt| draft.write().url_template = evt.value(),
+          }
+          button { r#type: "submit", class: button_class,
+            if editing_index().is_some() { "Update rule" } else { "Add rule" }
+          }
+          if editing_index().is_some() {
+            button {
+              r#type: "button",
+              class: "text-xs {link_class}",
+              onclick: move |_| {
+                  draft.set(IssueLinkRule::default());
+                  editing_index.set(None);
+                  error.set(None);
+              },
+              "Cancel"
+            }
+          }
+        }
+        if let Some(message) = error() {
+          p { class: "text-red-500", role: "alert", "{message}" }
+        }
+        p { class: "text-xs",
+          "In the address, {{0}} is the matched text and {{1}}, {{2}}, … are its groups."
+        }
+
+        div { class: "flex items-center gap-2",
+          input {
+            class: "{input_class} flex-1",
+            placeholder: "Try the rules on a sample todo",
+            aria_label: "Sample todo text",
+            value: "{sample.read()}",
+            oninput: move |evt| sample.set(evt.value()),
+          }
+          if !sample.read().is_empty() {
+            span { class: "truncate",
+              if sample_links.is_empty() { "No links" } else { "Links: {sample_links}" }
+            }
+          }
+        }
+      }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::Mutations;
+
+    #[test]
+    fn test_describe_rule() {
+        let rule = IssueLinkRule {
+            pattern: r"[A-Z]+-\d+".to_string(),
+            url_template: "https://jira.example.com/browse/{0}".to_string(),
+        };
+        assert_eq!(
+            describe_rule(&rule),
+            r"/[A-Z]+-\d+/ → https://jira.example.com/browse/{0}"
+        );
+    }
+
+    #[test]
+    fn test_issue_link_rules_rendering() {
+        let mut app = VirtualDom::new(|| {
+            rsx! {
+                IssueLinkRules {
+                    rules: vec![IssueLinkRule {
+                        pattern: r"#(\d+)".to_string(),
+                        url_template: "https://github.com/tyrchen/todo/issues/{1}".to_string(),
+                    }],
+                    on_rules_change: |_| {},
+                    is_dark_mode: true,
+                }
+            }
+        });
+
+        app.rebuild(&mut Mutations::default());
+    }
+}
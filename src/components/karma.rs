@@ -0,0 +1,114 @@
+use crate::components::theme_manager::use_motion;
+use crate::models::karma::{Karma, WeeklyProgress};
+use crate::utils::constants::todo::KARMA_POINTS_PER_LEVEL;
+use crate::utils::theme;
+use dioxus::prelude::*;
+use std::f64::consts::PI;
+
+/// Radius of the weekly progress ring (SVG units)
+const RING_RADIUS: f64 = 15.0;
+
+/// Card showing the karma level and score, with a ring filling up towards the weekly goal
+#[component]
+pub fn KarmaSummary(karma: Karma, progress: WeeklyProgress, is_dark_mode: bool) -> Element {
+    let motion_class = use_motion("transition-colors");
+    let ring_motion_class = use_motion("transition-all duration-500");
+
+    let card_class = format!(
+        "{} {}",
+        theme::surface_class(is_dark_mode),
+        theme::secondary_text_class(is_dark_mode)
+    );
+    let track_class = theme::track_class(is_dark_mode);
+    let ring_class = if progress.is_met() {
+        "text-green-500"
+    } else {
+        "text-blue-500"
+    };
+
+    let circumference = 2.0 * PI * RING_RADIUS;
+    let dash_offset = circumference * (1.0 - progress.fraction());
+    let goal_label = if progress.is_met() {
+        format!(
+            "Weekly goal reached: {} of {}",
+            progress.completed, progress.goal
+        )
+    } else {
+        format!("{} of {} this week", progress.completed, progress.goal)
+    };
+
+    rsx! {
+        div {
+            class: "flex items-center gap-3 p-3 mt-4 rounded-lg shadow text-sm {card_class} {motion_class}",
+            role: "status",
+            svg {
+                class: "w-10 h-10 shrink-0 -rotate-90",
+                view_box: "0 0 36 36",
+                "aria-hidden": "true",
+                circle {
+                    class: "{track_class}",
+                    cx: "18",
+                    cy: "18",
+                    r: "{RING_RADIUS}",
+                    fill: "none",
+                    stroke: "currentColor",
+                    stroke_width: "4",
+                }
+                circle {
+                    class: "{ring_class} {ring_motion_class}",
+                    cx: "18",
+                    cy: "18",
+                    r: "{RING_RADIUS}",
+                    fill: "none",
+                    stroke: "currentColor",
+                    stroke_width: "4",
+                    stroke_linecap: "round",
+                    stroke_dasharray: "{circumference}",
+                    stroke_dashoffset: "{dash_offset}",
+                }
+            }
+            div { class: "flex-1 min-w-0",
+                p { class: "font-semibold",
+                    "Level {karma.level} · {karma.score} karma"
+                }
+                p { class: "text-xs opacity-75",
+                    "{goal_label} · {KARMA_POINTS_PER_LEVEL - karma.level_points} points to the next level"
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::{Mutation, Mutations};
+
+    #[test]
+    fn test_karma_summary_shows_level_and_goal() {
+        let mut app = VirtualDom::new(|| {
+            rsx! {
+                KarmaSummary {
+                    karma: Karma { score: 31, level: 2, level_points: 6 },
+                    progress: WeeklyProgress { completed: 5, goal: 20 },
+                    is_dark_mode: false,
+                }
+            }
+        });
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        let texts: Vec<_> = mutations
+            .edits
+            .iter()
+            .filter_map(|edit| match edit {
+                Mutation::CreateTextNode { value, .. } => Some(value.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(texts.contains(&"Level 2 · 31 karma"), "{texts:?}");
+        assert!(
+            texts.contains(&"5 of 20 this week · 19 points to the next level"),
+            "{texts:?}"
+        );
+    }
+}
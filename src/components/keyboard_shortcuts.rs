@@ -1,15 +1,19 @@
+use crate::components::theme_manager::use_motion;
+use crate::utils::theme;
 use dioxus::prelude::*;
 
 /// Component for displaying keyboard shortcuts help
 #[component]
 pub fn KeyboardShortcuts(is_dark_mode: bool) -> Element {
-    let text_secondary_class = "text-gray-600 dark:text-gray-400";
+    let motion_class = use_motion("transition-colors");
+
+    let text_secondary_class = theme::secondary_text_class(is_dark_mode);
 
     rsx! {
         div {
-            class: "mt-6 text-xs {text_secondary_class} text-center transition-colors",
+            class: "mt-6 text-xs {text_secondary_class} text-center {motion_class}",
             p { "Keyboard shortcuts:" }
-            p { "Ctrl+A: All todos | Ctrl+C: Completed todos | Ctrl+V: Active todos | Ctrl+D: Toggle dark mode" }
+            p { "Ctrl+A: All todos | Ctrl+C: Completed todos | Ctrl+V: Active todos | Ctrl+T: Todos due today | Ctrl+D: Toggle dark mode | Ctrl+S: Save now | Ctrl+K: Quick switcher | Ctrl+Z / Ctrl+Shift+Z: Undo / Redo | Ctrl+↑/↓: Select the previous or next todo | Delete: Move the selected todo to the trash | 1-9: Toggle the Nth todo shown (hold Alt to see the numbers)" }
         }
     }
 }
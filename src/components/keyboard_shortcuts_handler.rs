@@ -1,16 +1,105 @@
 use crate::models::FilterState;
 use dioxus::prelude::*;
 
-pub fn use_keyboard_shortcuts<F, T>(
+/// Reports whether focus is in a text field now and after every focus change.
+const WATCH_TEXT_ENTRY: &str = r#"
+    const typing = (el) => !!el && (el.isContentEditable
+        || el.tagName === "TEXTAREA"
+        || el.tagName === "SELECT"
+        || (el.tagName === "INPUT" && !["checkbox", "radio", "button", "submit"].includes(el.type)));
+    const report = () => dioxus.send(typing(document.activeElement));
+    document.addEventListener("focusin", report);
+    document.addEventListener("focusout", () => setTimeout(report, 0));
+    report();
+    await new Promise(() => {});
+"#;
+
+/// Logic tracking whether the user is typing in a text field, where number
+/// keys must reach the field instead of toggling todos
+pub fn use_text_entry_focus() -> Signal<bool> {
+    let mut typing = use_signal(|| false);
+
+    use_hook(move || {
+        spawn(async move {
+            let mut eval = document::eval(WATCH_TEXT_ENTRY);
+            while let Ok(focused) = eval.recv::<bool>().await {
+                if *typing.peek() != focused {
+                    typing.set(focused);
+                }
+            }
+        });
+    });
+
+    typing
+}
+
+/// Gets the number 1 to 9 of a number key, on the main row or the keypad.
+fn number_key(code: Code) -> Option<usize> {
+    match code {
+        Code::Digit1 | Code::Numpad1 => Some(1),
+        Code::Digit2 | Code::Numpad2 => Some(2),
+        Code::Digit3 | Code::Numpad3 => Some(3),
+        Code::Digit4 | Code::Numpad4 => Some(4),
+        Code::Digit5 | Code::Numpad5 => Some(5),
+        Code::Digit6 | Code::Numpad6 => Some(6),
+        Code::Digit7 | Code::Numpad7 => Some(7),
+        Code::Digit8 | Code::Numpad8 => Some(8),
+        Code::Digit9 | Code::Numpad9 => Some(9),
+        _ => None,
+    }
+}
+
+/// Handles the app-wide shortcuts; `selection_mover` gets -1 for Ctrl+↑ and
+/// 1 for Ctrl+↓. Ctrl+Shift+L opens the debug log and is left out of the help.
+/// `number_picker` gets 1 to 9 for the number keys, pressed alone or with Alt.
+/// Ctrl+K opens the quick switcher through `palette_opener`. Delete and
+/// Backspace, pressed alone, call `deleter`. Ctrl+Z calls `undoer` and
+/// Ctrl+Shift+Z calls `redoer`, which return whether they handled the key;
+/// keys they leave alone keep their default, such as undoing typing.
+#[allow(clippy::too_many_arguments)]
+pub fn use_keyboard_shortcuts<F, T, S, M, D, N, P, X, U, R>(
     mut filter_setter: F,
     mut theme_toggler: T,
+    mut saver: S,
+    mut selection_mover: M,
+    mut debug_log_opener: D,
+    mut number_picker: N,
+    mut palette_opener: P,
+    mut deleter: X,
+    mut undoer: U,
+    mut redoer: R,
 ) -> impl FnMut(Event<KeyboardData>) + 'static
 where
     F: FnMut(FilterState) + 'static,
     T: FnMut(()) + 'static,
+    S: FnMut(()) + 'static,
+    M: FnMut(isize) + 'static,
+    D: FnMut(()) + 'static,
+    N: FnMut(usize) + 'static,
+    P: FnMut(()) + 'static,
+    X: FnMut(()) + 'static,
+    U: FnMut(()) -> bool + 'static,
+    R: FnMut(()) -> bool + 'static,
 {
     move |evt: Event<KeyboardData>| {
-        if evt.modifiers().ctrl() {
+        let modifiers = evt.modifiers();
+        if !modifiers.ctrl()
+            && !modifiers.meta()
+            && !modifiers.shift()
+            && let Some(number) = number_key(evt.code())
+        {
+            number_picker(number);
+            return;
+        }
+        if !modifiers.ctrl()
+            && !modifiers.meta()
+            && !modifiers.alt()
+            && matches!(evt.key(), Key::Delete | Key::Backspace)
+        {
+            deleter(());
+            return;
+        }
+        if modifiers.ctrl() {
             let key = evt.key().to_string();
             match key.as_str() {
                 "a" => {
@@ -25,10 +114,44 @@ where
                     filter_setter(FilterState::Active);
                     evt.prevent_default();
                 }
+                "t" => {
+                    filter_setter(FilterState::DueToday);
+                    evt.prevent_default();
+                }
                 "d" => {
                     theme_toggler(());
                     evt.prevent_default();
                 }
+                "s" => {
+                    saver(());
+                    evt.prevent_default();
+                }
+                "k" => {
+                    palette_opener(());
+                    evt.prevent_default();
+                }
+                "ArrowUp" => {
+                    selection_mover(-1);
+                    evt.prevent_default();
+                }
+                "ArrowDown" => {
+                    selection_mover(1);
+                    evt.prevent_default();
+                }
+                "Z" | "z" => {
+                    let handled = if modifiers.shift() {
+                        redoer(())
+                    } else {
+                        undoer(())
+                    };
+                    if handled {
+                        evt.prevent_default();
+                    }
+                }
+                "L" | "l" if evt.modifiers().shift() => {
+                    debug_log_opener(());
+                    evt.prevent_default();
+                }
                 _ => {}
             }
         }
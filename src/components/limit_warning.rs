@@ -0,0 +1,62 @@
+use crate::utils::theme;
+use dioxus::prelude::*;
+
+/// Component warning that the list has grown past the configured size
+#[component]
+pub fn LimitWarning(
+    count: usize,
+    limit: usize,
+    on_open_settings: EventHandler<()>,
+    on_dismiss: EventHandler<()>,
+    is_dark_mode: bool,
+) -> Element {
+    let toast_class = theme::notice_class(theme::Tone::Warning, is_dark_mode);
+
+    rsx! {
+        div {
+            class: "fixed bottom-4 right-4 z-20 max-w-sm p-4 rounded-lg border shadow-lg text-sm {toast_class}",
+            role: "alert",
+            p { class: "font-semibold mb-1", "Your list is getting long" }
+            p { class: "mb-3",
+                "You have {count} todos, more than the {limit} this app is tuned for. Export a backup and clear finished todos to keep things fast."
+            }
+            div { class: "flex justify-end gap-2",
+                button {
+                    r#type: "button",
+                    class: "px-3 py-1 rounded hover:underline",
+                    onclick: move |_| on_dismiss.call(()),
+                    "Dismiss"
+                }
+                button {
+                    r#type: "button",
+                    class: "px-3 py-1 rounded bg-amber-500 text-white hover:bg-amber-600",
+                    onclick: move |_| on_open_settings.call(()),
+                    "Export…"
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::Mutations;
+
+    #[test]
+    fn test_limit_warning_rendering() {
+        let mut app = VirtualDom::new(|| {
+            rsx! {
+                LimitWarning {
+                    count: 5001,
+                    limit: 5000,
+                    on_open_settings: |_| {},
+                    on_dismiss: |_| {},
+                    is_dark_mode: false,
+                }
+            }
+        });
+
+        app.rebuild(&mut Mutations::default());
+    }
+}
@@ -0,0 +1,141 @@
+use crate::components::theme_manager::use_motion;
+use crate::utils::export;
+use crate::utils::storage::LoadFailure;
+use crate::utils::theme;
+use chrono::Utc;
+use dioxus::prelude::*;
+
+/// Panel shown when the stored todos could not be loaded
+///
+/// Saving stays paused while it is shown. The user can try loading again,
+/// open the backups in settings, or export the stored text to recover it
+/// by hand when it could be read at all.
+#[component]
+pub fn LoadErrorPanel(
+    failure: LoadFailure,
+    on_retry: EventHandler<()>,
+    on_open_backups: EventHandler<()>,
+    is_dark_mode: bool,
+) -> Element {
+    let motion_class = use_motion("transition-colors");
+
+    let mut status = use_signal(|| None::<String>);
+
+    let panel_class = theme::notice_class(theme::Tone::Danger, is_dark_mode);
+    let button_class = format!(
+        "px-3 py-1 rounded border {}",
+        theme::outline_button_class(is_dark_mode)
+    );
+
+    let raw = failure.raw.clone();
+    let export_raw = move |_| {
+        let Some(raw) = raw.as_ref() else {
+            return;
+        };
+        let file_name = format!("todos-raw-{}.txt", Utc::now().format("%Y-%m-%d"));
+        let message = match export::save_export_file(&file_name, raw.as_bytes()) {
+            Ok(location) => location,
+            Err(e) => format!("Export failed: {:?}", e),
+        };
+        status.set(Some(message));
+    };
+
+    rsx! {
+        div {
+            class: "mb-4 p-4 rounded-lg border text-sm {panel_class} {motion_class}",
+            role: "alert",
+            p { class: "font-semibold mb-1", "Your todos could not be loaded" }
+            p { class: "mb-1", "{failure.error}" }
+            p { class: "mb-3",
+                "Saving is paused so the stored todos are not overwritten. Try again, restore a backup, or export the stored data to recover it by hand."
+            }
+            div { class: "flex flex-wrap gap-2",
+                button {
+                    r#type: "button",
+                    class: "{button_class}",
+                    onclick: move |_| on_retry.call(()),
+                    "Try again"
+                }
+                button {
+                    r#type: "button",
+                    class: "{button_class}",
+                    onclick: move |_| on_open_backups.call(()),
+                    "Restore a backup…"
+                }
+                if failure.raw.is_some() {
+                    button {
+                        r#type: "button",
+                        class: "{button_class}",
+                        onclick: export_raw,
+                        "Export stored data"
+                    }
+                }
+            }
+            if let Some(message) = status() {
+                p { class: "mt-2", "{message}" }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::{Mutation, Mutations};
+
+    /// Renders the panel and collects its edits.
+    fn render(failure: LoadFailure) -> Vec<Mutation> {
+        let mut app = VirtualDom::new_with_props(
+            |failure: LoadFailure| {
+                rsx! {
+                    LoadErrorPanel {
+                        failure,
+                        on_retry: |_| {},
+                        on_open_backups: |_| {},
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            failure,
+        );
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        mutations.edits
+    }
+
+    /// Counts the templates loaded, one more when the export button is shown.
+    fn loaded_templates(edits: &[Mutation]) -> usize {
+        edits
+            .iter()
+            .filter(|edit| matches!(edit, Mutation::LoadTemplate { .. }))
+            .count()
+    }
+
+    #[test]
+    fn test_load_error_panel_shows_error() {
+        let edits = render(LoadFailure {
+            error: "The storage could not be read: DbError(\"locked\")".to_string(),
+            raw: None,
+        });
+        assert!(edits.iter().any(|edit| matches!(
+            edit,
+            Mutation::CreateTextNode { value, .. } if value.contains("DbError")
+        )));
+    }
+
+    #[test]
+    fn test_load_error_panel_offers_export_only_with_raw_data() {
+        let unreadable = render(LoadFailure {
+            error: "The storage could not be read".to_string(),
+            raw: None,
+        });
+        let damaged = render(LoadFailure {
+            error: "The stored data is damaged".to_string(),
+            raw: Some("{not json".to_string()),
+        });
+        assert_eq!(
+            loaded_templates(&damaged),
+            loaded_templates(&unreadable) + 1
+        );
+    }
+}
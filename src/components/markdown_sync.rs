@@ -0,0 +1,338 @@
+use crate::models::settings::MarkdownSyncSettings;
+use crate::models::{AppSettings, Todo, TodoList};
+use crate::utils::constants::markdown_sync::{FILE_EVENT_SETTLE_MS, WRITE_DEBOUNCE_MS};
+use crate::utils::markdown_sync::{MarkdownDoc, apply_pull, plan_pull, write_back};
+use crate::utils::theme;
+use crate::utils::timer;
+use dioxus::prelude::*;
+use notify::{RecursiveMode, Watcher};
+use std::cell::{Cell, RefCell};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, SystemTime};
+
+/// Outcome of the last read or write of the file, shown in settings.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarkdownSyncStatus {
+    pub message: Option<String>,
+}
+
+/// Modification time of the file when the app last read or wrote it.
+type LastSeen = Rc<Cell<Option<SystemTime>>>;
+
+/// Gets the modification time of a file, `None` if it does not exist.
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Reads the file, `None` if it does not exist yet.
+fn read(path: &Path) -> Result<Option<String>, String> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(format!("Could not read {}: {}", path.display(), error)),
+    }
+}
+
+/// Gets the todos kept in the file, in list order.
+fn synced_todos(list: &TodoList, tag: &str) -> Vec<Todo> {
+    list.all_including_archived()
+        .into_iter()
+        .filter(|todo| todo.tags.iter().any(|t| t == tag))
+        .collect()
+}
+
+/// Notes checklist lines that could not be read, which are left alone.
+fn with_malformed(message: String, doc: &MarkdownDoc) -> String {
+    match doc.malformed_count() {
+        0 => message,
+        1 => format!("{}; 1 unreadable line kept as is", message),
+        count => format!("{}; {} unreadable lines kept as is", message, count),
+    }
+}
+
+/// Brings the synced todos in line with the file.
+fn pull(
+    mut todo_list: Signal<TodoList>,
+    config: &MarkdownSyncSettings,
+    last_seen: &LastSeen,
+) -> Result<String, String> {
+    let path = Path::new(&config.path);
+    let seen = modified(path);
+    let Some(text) = read(path)? else {
+        // A file that is not there yet is made from the todos rather than emptying them
+        last_seen.set(None);
+        let written = push(todo_list, config, last_seen)?;
+        return Ok(written.unwrap_or_else(|| "No todos to write yet".to_string()));
+    };
+    let doc = MarkdownDoc::parse(&text);
+    last_seen.set(seen);
+
+    // Applied to a copy, so the list and the write-back wake only on a change
+    let mut list = todo_list.peek().clone();
+    let synced = synced_todos(&list, &config.tag);
+    let plan = plan_pull(&doc, &synced.iter().collect::<Vec<_>>());
+    let changed = apply_pull(&mut list, &plan, &config.tag);
+    if changed > 0 {
+        todo_list.set(list);
+    }
+    let message = match changed {
+        0 => "Read the file: nothing changed".to_string(),
+        1 => "Read the file: 1 todo changed".to_string(),
+        count => format!("Read the file: {} todos changed", count),
+    };
+    Ok(with_malformed(message, &doc))
+}
+
+/// Writes the synced todos into the file.
+///
+/// When the file changed since the app last saw it, it is read instead and
+/// the edits made in the file win.
+fn push(
+    todo_list: Signal<TodoList>,
+    config: &MarkdownSyncSettings,
+    last_seen: &LastSeen,
+) -> Result<Option<String>, String> {
+    let path = Path::new(&config.path);
+    if modified(path) != last_seen.get() {
+        let message = pull(todo_list, config, last_seen)?;
+        return Ok(Some(format!(
+            "The file changed on disk as well, so its version was kept. {}",
+            message
+        )));
+    }
+
+    let text = read(path)?.unwrap_or_default();
+    let doc = MarkdownDoc::parse(&text);
+    let synced = synced_todos(&todo_list.peek(), &config.tag);
+    let written = write_back(&doc, &synced.iter().collect::<Vec<_>>(), &config.tag).to_text();
+    if written == text {
+        return Ok(None);
+    }
+    std::fs::write(path, written)
+        .map_err(|error| format!("Could not write {}: {}", path.display(), error))?;
+    last_seen.set(modified(path));
+    Ok(Some(with_malformed("Wrote the file".to_string(), &doc)))
+}
+
+/// Reads the file, then again whenever something else changes it.
+async fn watch(
+    todo_list: Signal<TodoList>,
+    config: MarkdownSyncSettings,
+    last_seen: LastSeen,
+    mut status: Signal<MarkdownSyncStatus>,
+) {
+    let mut report = move |result: Result<String, String>| {
+        status.set(MarkdownSyncStatus {
+            message: Some(result.unwrap_or_else(|error| error)),
+        });
+    };
+    report(pull(todo_list, &config, &last_seen));
+
+    let path = PathBuf::from(&config.path);
+    let Some(name) = path.file_name().map(|name| name.to_os_string()) else {
+        report(Err(format!("{} is not a file", path.display())));
+        return;
+    };
+    let (sender, mut events) = tokio::sync::mpsc::unbounded_channel();
+    let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event
+            && event
+                .paths
+                .iter()
+                .any(|path| path.file_name() == Some(&name))
+        {
+            let _ = sender.send(());
+        }
+    });
+    // The folder is watched, since editors often save by replacing the file
+    let folder = match path.parent() {
+        Some(folder) if !folder.as_os_str().is_empty() => folder,
+        _ => Path::new("."),
+    };
+    let _watcher = match watcher.and_then(|mut watcher| {
+        watcher.watch(folder, RecursiveMode::NonRecursive)?;
+        Ok(watcher)
+    }) {
+        Ok(watcher) => watcher,
+        Err(error) => {
+            report(Err(format!(
+                "Could not watch {}: {}",
+                folder.display(),
+                error
+            )));
+            return;
+        }
+    };
+
+    while events.recv().await.is_some() {
+        timer::sleep(Duration::from_millis(FILE_EVENT_SETTLE_MS)).await;
+        while events.try_recv().is_ok() {}
+        // The app's own writes leave the time it last saw
+        if modified(&path) != last_seen.get() {
+            report(pull(todo_list, &config, &last_seen));
+        }
+    }
+}
+
+/// Logic keeping the todos with the sync tag and a Markdown file in step
+///
+/// Once the todos have loaded, reads the file and watches it on a background
+/// task, starting again whenever the sync settings change. Changes to the
+/// todos are written back once they have settled for [`WRITE_DEBOUNCE_MS`].
+/// The status is provided as context for [`MarkdownSyncSettingsSection`].
+pub fn use_markdown_sync(todo_list: Signal<TodoList>, loaded: Signal<bool>) {
+    let settings = use_context::<Signal<AppSettings>>();
+    let mut status = use_context_provider(|| Signal::new(MarkdownSyncStatus::default()));
+    let config = use_memo(move || settings.read().markdown_sync.clone());
+    let last_seen: LastSeen = use_hook(Rc::default);
+    let watch_task = use_hook(|| Rc::new(RefCell::new(None::<Task>)));
+    let pending_writes = use_hook(|| Rc::new(Cell::new(0u64)));
+
+    {
+        let last_seen = last_seen.clone();
+        use_effect(move || {
+            let config = config();
+            if !loaded() {
+                return;
+            }
+            if let Some(task) = watch_task.borrow_mut().take() {
+                task.cancel();
+            }
+            if !config.is_active() {
+                status.set(MarkdownSyncStatus::default());
+                return;
+            }
+            let task = spawn(watch(todo_list, config, last_seen.clone(), status));
+            *watch_task.borrow_mut() = Some(task);
+        });
+    }
+
+    use_effect(move || {
+        let _ = todo_list.read();
+        let config = config.peek().clone();
+        if !loaded() || !config.is_active() {
+            return;
+        }
+        let write = pending_writes.get() + 1;
+        pending_writes.set(write);
+        let last_seen = last_seen.clone();
+        let pending_writes = pending_writes.clone();
+        spawn(async move {
+            timer::sleep(Duration::from_millis(WRITE_DEBOUNCE_MS)).await;
+            // Only the last of a run of changes writes
+            if pending_writes.get() != write {
+                return;
+            }
+            match push(todo_list, &config, &last_seen) {
+                Ok(Some(message)) => status.set(MarkdownSyncStatus {
+                    message: Some(message),
+                }),
+                Ok(None) => {}
+                Err(error) => status.set(MarkdownSyncStatus {
+                    message: Some(error),
+                }),
+            }
+        });
+    });
+}
+
+/// Settings section choosing the Markdown file to sync with
+///
+/// Must be rendered below [`use_markdown_sync`].
+#[component]
+pub fn MarkdownSyncSettingsSection(is_dark_mode: bool) -> Element {
+    let mut settings = use_context::<Signal<AppSettings>>();
+    let status = use_context::<Signal<MarkdownSyncStatus>>();
+    let markdown_sync = settings().markdown_sync;
+
+    let heading_class = theme::primary_text_class(is_dark_mode);
+    let text_class = theme::secondary_text_class(is_dark_mode);
+    let input_class = theme::form_control_class(is_dark_mode);
+
+    rsx! {
+      h3 { class: "text-sm font-medium mt-4 mb-2 {heading_class}", "Markdown file sync" }
+      div { class: "grid grid-cols-[auto_1fr] items-center gap-2 mb-2 text-sm {text_class}",
+        label { r#for: "markdown-sync-path", "File" }
+        input {
+          id: "markdown-sync-path",
+          class: "{input_class}",
+          placeholder: "/home/me/notes/TODO.md",
+          value: "{markdown_sync.path}",
+          onchange: move |evt: Event<FormData>| {
+              settings.write().markdown_sync.path = evt.value().trim().to_string()
+          },
+        }
+        label { r#for: "markdown-sync-tag", "Todos tagged" }
+        input {
+          id: "markdown-sync-tag",
+          class: "{input_class}",
+          value: "{markdown_sync.tag}",
+          onchange: move |evt: Event<FormData>| {
+              settings.write().markdown_sync.tag = evt.value().trim().to_string()
+          },
+        }
+        label { r#for: "markdown-sync-enabled", "Keep in sync" }
+        input {
+          id: "markdown-sync-enabled",
+          class: "justify-self-start",
+          r#type: "checkbox",
+          checked: markdown_sync.enabled,
+          onchange: move |evt: Event<FormData>| settings.write().markdown_sync.enabled = evt.checked(),
+        }
+      }
+      if let Some(message) = status().message {
+        p { class: "mt-2 text-sm {text_class}", aria_live: "polite", "{message}" }
+      }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+
+    #[component]
+    fn Harness() -> Element {
+        use_context_provider(|| Signal::new(AppSettings::default()));
+        use_context_provider(|| {
+            Signal::new(MarkdownSyncStatus {
+                message: Some("Wrote the file".to_string()),
+            })
+        });
+        let settings = use_context::<Signal<AppSettings>>();
+        let markdown_sync = settings().markdown_sync;
+        rsx! {
+            MarkdownSyncSettingsSection { is_dark_mode: false }
+            span { id: "state", "{markdown_sync.enabled} {markdown_sync.path}" }
+        }
+    }
+
+    #[test]
+    fn test_with_malformed() {
+        let doc = MarkdownDoc::parse("- [?] one\n- [ ] two (due: soon)\n");
+        assert_eq!(
+            with_malformed("Wrote the file".to_string(), &doc),
+            "Wrote the file; 2 unreadable lines kept as is"
+        );
+    }
+
+    #[test]
+    fn test_settings_section_edits_the_settings() {
+        let mut dom = TestDom::with_props(|()| rsx! { Harness {} }, ());
+        assert!(dom.find("p", "Wrote the file").is_some());
+
+        let path = dom
+            .find_by_attribute("input", "id", "markdown-sync-path")
+            .unwrap();
+        dom.change(path, " /tmp/TODO.md ");
+        let enabled = dom
+            .find_by_attribute("input", "id", "markdown-sync-enabled")
+            .unwrap();
+        dom.change(enabled, "true");
+        assert!(dom.find("span", "true /tmp/TODO.md").is_some());
+    }
+}
@@ -1,21 +1,109 @@
 pub mod app_header;
+pub mod auto_archive;
+pub mod auto_tag_rules;
+pub mod batch_tag;
+#[cfg(feature = "caldav")]
+pub mod caldav_sync;
+pub mod character_counter;
+pub mod clock;
+pub mod command_palette;
+pub mod date_picker;
+pub mod day_planner;
+pub mod debounce;
+pub mod debug_log_panel;
+pub mod demo_banner;
+pub mod digest;
+pub mod duplicates;
+pub mod export_dialog;
 pub mod filter_bar;
+pub mod follow_up;
+pub mod import_preview;
+pub mod issue_link_rules;
+pub mod karma;
 pub mod keyboard_shortcuts;
 pub mod keyboard_shortcuts_handler;
+pub mod limit_warning;
+pub mod load_error_panel;
+#[cfg(feature = "markdown-sync")]
+pub mod markdown_sync;
+pub mod modal;
+pub mod move_position;
+pub mod onboarding;
+pub mod postponed;
+pub mod quick_filters;
+pub mod quiet_mode;
+pub mod reminders;
 pub mod search_box;
+pub mod settings_manager;
+pub mod settings_panel;
+pub mod shift_dates;
+pub mod snapshot_view;
+pub mod tag_due_rules;
 pub mod tags_filter;
+#[cfg(test)]
+pub(crate) mod test_dom;
+pub mod text_stats;
 pub mod theme_manager;
+#[cfg(test)]
+mod theming_tests;
+pub mod timeline;
+pub mod toast;
 pub mod todo_app;
+pub mod todo_detail;
 pub mod todo_form;
 pub mod todo_item;
+pub mod todo_link;
 pub mod todo_list;
+pub mod todo_notes;
 pub mod todo_state;
+pub mod whats_new;
+pub mod window_size;
+pub mod your_data;
 
 pub use app_header::AppHeader;
+pub use auto_tag_rules::AutoTagRules;
+pub use batch_tag::{BatchTagDialog, BatchTagMode};
+#[cfg(feature = "caldav")]
+pub use caldav_sync::CalDavSyncSettings;
+pub use character_counter::CharacterCounter;
+pub use command_palette::CommandPalette;
+pub use date_picker::DatePicker;
+pub use day_planner::DayPlanner;
+pub use debug_log_panel::DebugLogPanel;
+pub use demo_banner::DemoBanner;
+pub use duplicates::DuplicatesDialog;
+pub use export_dialog::ExportDialog;
 pub use filter_bar::FilterBar;
+pub use follow_up::FollowUpBanner;
+pub use import_preview::ImportPreview;
+pub use issue_link_rules::IssueLinkRules;
+pub use karma::KarmaSummary;
 pub use keyboard_shortcuts::KeyboardShortcuts;
+pub use limit_warning::LimitWarning;
+pub use load_error_panel::LoadErrorPanel;
+#[cfg(feature = "markdown-sync")]
+pub use markdown_sync::MarkdownSyncSettingsSection;
+pub use modal::{Modal, ModalSize, use_modal};
+pub use move_position::MovePositionDialog;
+pub use onboarding::{OnboardingTour, WelcomePrompt};
+pub use postponed::MostPostponed;
+pub use quick_filters::QuickFilters;
+pub use quiet_mode::QuietModeMenu;
+pub use reminders::DueSoonBell;
 pub use search_box::SearchBox;
+pub use settings_panel::SettingsPanel;
+pub use shift_dates::ShiftDatesDialog;
+pub use snapshot_view::{ShareSnapshotButton, SnapshotView};
+pub use tag_due_rules::TagDueRules;
 pub use tags_filter::TagsFilter;
+pub use text_stats::TextStatsLine;
+pub use timeline::{Timeline, ViewSwitcher};
+pub use toast::{Toast, ToastAction, ToastMessage};
 pub use todo_app::TodoApp;
+pub use todo_detail::TodoDetail;
 pub use todo_form::TodoForm;
+pub use todo_link::{TodoLinkButton, TodoUrlField};
 pub use todo_list::TodoList;
+pub use todo_notes::TodoNotes;
+pub use whats_new::WhatsNewDialog;
+pub use your_data::YourData;
@@ -0,0 +1,339 @@
+use crate::components::theme_manager::use_motion;
+use crate::utils::theme;
+use dioxus::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Elements Tab moves between inside a dialog
+const FOCUSABLE: &str = "a[href], button:not([disabled]), input:not([disabled]), \
+    select:not([disabled]), textarea:not([disabled]), [tabindex]:not([tabindex='-1'])";
+
+/// Numbers the open dialogs, so each has an id of its own
+static NEXT_MODAL_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Width of a modal dialog.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ModalSize {
+    /// For a field or two, like a date
+    Small,
+    /// For forms and confirmations
+    #[default]
+    Medium,
+    /// For lists and longer text
+    Large,
+}
+
+impl ModalSize {
+    /// Gets the Tailwind class limiting the dialog's width.
+    pub fn max_width_class(self) -> &'static str {
+        match self {
+            ModalSize::Small => "max-w-sm",
+            ModalSize::Medium => "max-w-lg",
+            ModalSize::Large => "max-w-2xl",
+        }
+    }
+}
+
+/// Works out where Tab moves focus inside a dialog, wrapping at both ends.
+///
+/// # Arguments
+/// * `count` - Number of elements that can take focus
+/// * `current` - Position of the focused one, `None` if focus is elsewhere
+/// * `backwards` - Whether Shift is held
+///
+/// # Returns
+/// The position to focus, or `None` when nothing can take focus
+pub(crate) fn next_focus(count: usize, current: Option<usize>, backwards: bool) -> Option<usize> {
+    if count == 0 {
+        return None;
+    }
+    Some(match (current.filter(|at| *at < count), backwards) {
+        (None, false) => 0,
+        (None, true) => count - 1,
+        (Some(at), false) => (at + 1) % count,
+        (Some(at), true) => (at + count - 1) % count,
+    })
+}
+
+/// Moves focus to the next element inside a dialog, as Tab would.
+async fn move_focus(dialog_id: String, backwards: bool) {
+    let mut eval = document::eval(&format!(
+        r#"
+        const dialog = document.getElementById('{dialog_id}');
+        const items = dialog ? [...dialog.querySelectorAll("{FOCUSABLE}")] : [];
+        dioxus.send([items.length, items.indexOf(document.activeElement)]);
+        const next = await dioxus.recv();
+        if (next !== null) items[next]?.focus();
+        "#
+    ));
+    let Ok((count, current)) = eval.recv::<(usize, i64)>().await else {
+        return;
+    };
+    let _ = eval.send(next_focus(count, usize::try_from(current).ok(), backwards));
+}
+
+/// Controls of a dialog, from [`use_modal`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModalHandle {
+    open: Signal<bool>,
+}
+
+impl ModalHandle {
+    /// Checks if the dialog is shown.
+    pub fn is_open(&self) -> bool {
+        (self.open)()
+    }
+
+    /// Shows the dialog.
+    pub fn open(&self) {
+        let mut open = self.open;
+        open.set(true);
+    }
+
+    /// Hides the dialog.
+    pub fn close(&self) {
+        let mut open = self.open;
+        open.set(false);
+    }
+}
+
+/// Logic for showing and hiding a dialog
+///
+/// Returns controls that are `Copy`, so they can be moved into any number
+/// of event handlers. Render the [`Modal`] while
+/// [`is_open`](ModalHandle::is_open) and pass
+/// [`close`](ModalHandle::close) as its `on_close`.
+pub fn use_modal() -> ModalHandle {
+    ModalHandle {
+        open: use_signal(|| false),
+    }
+}
+
+/// Component showing its children in a dialog over the rest of the app
+///
+/// The dialog sits in a fixed overlay above everything else, wherever it
+/// is rendered. While it is open the page behind does not scroll and Tab
+/// cycles through the dialog's own controls; focus moves into the dialog
+/// when it opens and back where it was when it closes. Escape and clicks
+/// on the overlay call `on_close` unless turned off.
+#[component]
+pub fn Modal(
+    /// Heading of the dialog, also its accessible name
+    title: String,
+    on_close: EventHandler<()>,
+    #[props(default)] size: ModalSize,
+    #[props(default = true)] close_on_escape: bool,
+    #[props(default = true)] close_on_overlay: bool,
+    is_dark_mode: bool,
+    children: Element,
+) -> Element {
+    let motion_class = use_motion("transition-opacity duration-150");
+
+    let dialog_id = use_hook(|| format!("modal-{}", NEXT_MODAL_ID.fetch_add(1, Ordering::Relaxed)));
+
+    // Locks the page behind and moves focus in; nested dialogs share the lock
+    use_hook({
+        let dialog_id = dialog_id.clone();
+        move || {
+            let _ = document::eval(&format!(
+                r#"
+                const body = document.body;
+                body.dataset.openModals = Number(body.dataset.openModals || 0) + 1;
+                body.style.overflow = 'hidden';
+                (window.modalReturnFocus ??= []).push(document.activeElement);
+                setTimeout(() => {{
+                    const dialog = document.getElementById('{dialog_id}');
+                    if (dialog && !dialog.contains(document.activeElement)) {{
+                        (dialog.querySelector("{FOCUSABLE}") ?? dialog).focus();
+                    }}
+                }}, 0);
+                "#
+            ));
+        }
+    });
+    use_drop(|| {
+        let _ = document::eval(
+            r#"
+            const body = document.body;
+            const open = Math.max(Number(body.dataset.openModals || 0) - 1, 0);
+            body.dataset.openModals = open;
+            if (open === 0) body.style.overflow = '';
+            window.modalReturnFocus?.pop()?.focus?.();
+            "#,
+        );
+    });
+
+    let handle_key_down = {
+        let dialog_id = dialog_id.clone();
+        move |evt: Event<KeyboardData>| match evt.key() {
+            Key::Escape if close_on_escape => {
+                evt.stop_propagation();
+                on_close.call(());
+            }
+            Key::Tab => {
+                evt.prevent_default();
+                spawn(move_focus(dialog_id.clone(), evt.modifiers().shift()));
+            }
+            _ => {}
+        }
+    };
+
+    let panel_class = theme::modal_panel_class(is_dark_mode);
+    let close_class = theme::muted_text_class(is_dark_mode);
+    let close_hover_class = theme::hover_class(is_dark_mode);
+    let width_class = size.max_width_class();
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-50 flex items-center justify-center bg-black/40 p-4 {motion_class}",
+            onclick: move |_| {
+                if close_on_overlay {
+                    on_close.call(());
+                }
+            },
+            div {
+                id: "{dialog_id}",
+                class: "w-full {width_class} max-h-full overflow-y-auto p-5 rounded-lg border shadow-lg {panel_class}",
+                role: "dialog",
+                aria_modal: "true",
+                aria_label: "{title}",
+                tabindex: "-1",
+                onclick: move |evt| evt.stop_propagation(),
+                onkeydown: handle_key_down,
+                div { class: "flex items-center justify-between mb-3",
+                    h2 { class: "text-lg font-semibold", "{title}" }
+                    button {
+                        r#type: "button",
+                        class: "p-1 rounded {close_class} {close_hover_class}",
+                        aria_label: "Close",
+                        onclick: move |_| on_close.call(()),
+                        "✕"
+                    }
+                }
+                {children}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Renders a dialog with two fields, counting calls to `on_close`.
+    fn render(close_on_escape: bool, close_on_overlay: bool) -> (TestDom, Rc<Cell<usize>>) {
+        let closed = Rc::new(Cell::new(0));
+        let dom = TestDom::with_props(
+            |(closed, close_on_escape, close_on_overlay): (Rc<Cell<usize>>, bool, bool)| {
+                rsx! {
+                    Modal {
+                        title: "Rename",
+                        on_close: move |_| closed.set(closed.get() + 1),
+                        close_on_escape,
+                        close_on_overlay,
+                        is_dark_mode: false,
+                        input { value: "Call the plumber" }
+                        button { "Save" }
+                    }
+                }
+            },
+            (closed.clone(), close_on_escape, close_on_overlay),
+        );
+        (dom, closed)
+    }
+
+    fn dialog(dom: &TestDom) -> usize {
+        dom.find_by_attribute("div", "role", "dialog").unwrap()
+    }
+
+    #[test]
+    fn test_tab_cycles_forward_and_back() {
+        // Tab from the last control wraps to the first, Shift+Tab the other way
+        assert_eq!(next_focus(3, Some(0), false), Some(1));
+        assert_eq!(next_focus(3, Some(2), false), Some(0));
+        assert_eq!(next_focus(3, Some(0), true), Some(2));
+        assert_eq!(next_focus(3, Some(2), true), Some(1));
+    }
+
+    #[test]
+    fn test_tab_from_outside_enters_at_either_end() {
+        assert_eq!(next_focus(3, None, false), Some(0));
+        assert_eq!(next_focus(3, None, true), Some(2));
+        // A position that no longer exists counts as outside
+        assert_eq!(next_focus(2, Some(5), false), Some(0));
+        assert_eq!(next_focus(0, None, false), None);
+        assert_eq!(next_focus(1, Some(0), false), Some(0));
+    }
+
+    #[test]
+    fn test_dialog_is_labelled_and_holds_its_children() {
+        let (dom, _) = render(true, true);
+        let dialog = dialog(&dom);
+        assert_eq!(dom.attribute(dialog, "aria-modal"), Some("true"));
+        assert_eq!(dom.attribute(dialog, "aria-label"), Some("Rename"));
+        assert!(dom.find("h2", "Rename").is_some());
+        assert!(dom.find("button", "Save").is_some());
+    }
+
+    #[test]
+    fn test_escape_closes_unless_turned_off() {
+        let (mut dom, closed) = render(true, true);
+        dom.key_down(dialog(&dom), Key::Tab);
+        assert_eq!(closed.get(), 0);
+        dom.key_down(dialog(&dom), Key::Escape);
+        assert_eq!(closed.get(), 1);
+
+        let (mut dom, closed) = render(false, true);
+        dom.key_down(dialog(&dom), Key::Escape);
+        assert_eq!(closed.get(), 0);
+    }
+
+    #[test]
+    fn test_overlay_click_closes_unless_turned_off() {
+        let (mut dom, closed) = render(true, true);
+        dom.click(dom.find("button", "Save").unwrap());
+        assert_eq!(closed.get(), 0);
+        dom.click(dom.first("div").unwrap());
+        assert_eq!(closed.get(), 1);
+        dom.click(
+            dom.find_by_attribute("button", "aria-label", "Close")
+                .unwrap(),
+        );
+        assert_eq!(closed.get(), 2);
+
+        let (mut dom, closed) = render(true, false);
+        dom.click(dom.first("div").unwrap());
+        assert_eq!(closed.get(), 0);
+    }
+
+    #[test]
+    fn test_use_modal_opens_and_closes() {
+        #[component]
+        fn Harness() -> Element {
+            let modal = use_modal();
+            rsx! {
+                button { onclick: move |_| modal.open(), "Open" }
+                if modal.is_open() {
+                    Modal {
+                        title: "Details",
+                        on_close: move |_| modal.close(),
+                        size: ModalSize::Small,
+                        is_dark_mode: true,
+                        p { "Inside" }
+                    }
+                }
+            }
+        }
+
+        let mut dom = TestDom::with_props(|()| rsx! { Harness {} }, ());
+        assert!(dom.find("p", "Inside").is_none());
+        dom.click(dom.find("button", "Open").unwrap());
+        let dialog = dialog(&dom);
+        assert!(dom.attribute(dialog, "class").unwrap().contains("max-w-sm"));
+
+        dom.key_down(dialog, Key::Escape);
+        assert!(dom.find("p", "Inside").is_none());
+    }
+}
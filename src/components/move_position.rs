@@ -0,0 +1,174 @@
+use crate::components::{Modal, ModalSize};
+use crate::models::Todo;
+use crate::models::view::index_for_position;
+use crate::utils::theme;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Dialog moving a todo to a position typed in, for those who cannot drag
+///
+/// Positions count the rows on screen, so with filters active the todo takes
+/// the place of the one shown at that position. The todo there now is named
+/// before moving, and positions that are out of range or where the todo
+/// already is are explained instead.
+#[component]
+pub fn MovePositionDialog(
+    todo: Rc<Todo>,
+    /// The todos on screen, in screen order
+    shown: Vec<Rc<Todo>>,
+    /// Called with where the todo goes, counting from 0 in list order, and
+    /// the position typed in
+    on_move: EventHandler<(usize, usize)>,
+    on_close: EventHandler<()>,
+    is_dark_mode: bool,
+) -> Element {
+    let mut position_text = use_signal(String::new);
+
+    let input_class = theme::form_control_class(is_dark_mode);
+    let hint_class = theme::muted_text_class(is_dark_mode);
+    let error_class = theme::danger_text_class(is_dark_mode);
+    let apply_class = theme::primary_button_class(is_dark_mode);
+
+    let count = shown.len();
+    let current = shown
+        .iter()
+        .position(|row| row.id == todo.id)
+        .map(|index| index + 1);
+    let todo_id = todo.id;
+    let typed = position_text.read().trim().to_string();
+    let result = (!typed.is_empty()).then(|| index_for_position(&shown, todo_id, &typed));
+    let message = match &result {
+        None => None,
+        Some(Ok(index)) => shown
+            .iter()
+            .find(|row| row.order == index + 1)
+            .map(|row| Ok(format!("Moves it to where “{}” is now.", row.text))),
+        Some(Err(error)) => Some(Err(error.to_string())),
+    };
+    let target = result.and_then(Result::ok).zip(typed.parse::<usize>().ok());
+    let invalid = matches!(message, Some(Err(_)));
+
+    rsx! {
+        Modal {
+            title: "Move to position",
+            on_close,
+            size: ModalSize::Small,
+            is_dark_mode,
+            form {
+                onsubmit: move |evt| {
+                    evt.prevent_default();
+                    if let Some(target) = target {
+                        on_move.call(target);
+                    }
+                },
+                p { class: "mb-2 text-sm break-words [overflow-wrap:anywhere]", "“{todo.text}”" }
+                label { class: "block text-sm mb-1", r#for: "move-position",
+                    if let Some(current) = current {
+                        "Position (now {current} of {count})"
+                    } else {
+                        "Position (1 to {count})"
+                    }
+                }
+                input {
+                    id: "move-position",
+                    class: "{input_class}",
+                    r#type: "number",
+                    min: 1,
+                    max: count as i64,
+                    autofocus: true,
+                    aria_invalid: invalid,
+                    value: "{position_text}",
+                    oninput: move |evt| position_text.set(evt.value()),
+                }
+                match message {
+                    Some(Ok(message)) => rsx! {
+                        p { class: "mt-2 text-sm {hint_class}", aria_live: "polite", "{message}" }
+                    },
+                    Some(Err(message)) => rsx! {
+                        p { class: "mt-2 text-sm {error_class}", role: "alert", "{message}" }
+                    },
+                    None => rsx! {},
+                }
+                div { class: "flex justify-end space-x-2 mt-4",
+                    button {
+                        r#type: "button",
+                        class: "px-3 py-1 rounded text-sm {hint_class} hover:underline",
+                        onclick: move |_| on_close.call(()),
+                        "Cancel"
+                    }
+                    button {
+                        r#type: "submit",
+                        class: "{apply_class} text-sm",
+                        disabled: target.is_none(),
+                        "Move"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use crate::models::TodoList;
+    use std::cell::RefCell;
+
+    type Moved = Rc<RefCell<Vec<(usize, usize)>>>;
+
+    /// Gets the todos of a list of four with the second one hidden by a filter.
+    fn shown() -> Vec<Rc<Todo>> {
+        let mut list = TodoList::new();
+        for text in ["Buy milk", "Call the plumber", "Pay rent", "Book the venue"] {
+            list.add(text.to_string());
+        }
+        list.all()
+            .into_iter()
+            .filter(|todo| todo.id != 2)
+            .map(Rc::new)
+            .collect()
+    }
+
+    /// Renders the dialog moving the last todo shown, recording the moves.
+    fn dialog() -> (TestDom, Moved) {
+        let moved = Moved::default();
+        let dom = TestDom::with_props(
+            move |moved: Moved| {
+                let shown = shown();
+                rsx! {
+                    MovePositionDialog {
+                        todo: Rc::clone(&shown[2]),
+                        shown,
+                        on_move: move |index| moved.borrow_mut().push(index),
+                        on_close: |_| {},
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            moved.clone(),
+        );
+        (dom, moved)
+    }
+
+    #[test]
+    fn test_dialog_names_the_todo_there_and_moves() {
+        let (mut dom, moved) = dialog();
+        assert!(dom.text().contains("Position (now 3 of 3)"));
+        let form = dom.first("form").unwrap();
+        let input = dom.first("input").unwrap();
+
+        dom.input(input, "3");
+        assert!(dom.text().contains("already at this position"));
+        dom.submit(form);
+        dom.input(input, "7");
+        assert!(dom.text().contains("Enter a position from 1 to 3."));
+        dom.submit(form);
+        assert!(moved.borrow().is_empty());
+
+        dom.input(input, "2");
+        assert!(dom.text().contains("Moves it to where “Pay rent” is now."));
+        dom.submit(form);
+        assert_eq!(*moved.borrow(), [(2, 2)]);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::components::theme_manager::use_motion;
+use crate::utils;
+use crate::utils::constants::onboarding::TOUR_STEPS;
+use crate::utils::keys;
+use crate::utils::theme;
+use dioxus::prelude::*;
+
+/// Classes added to the element the current tour step points at
+const TOUR_HIGHLIGHT_CLASS: &str = "relative z-40 rounded-lg ring-2 ring-blue-500 ring-offset-2";
+
+/// Logic for tracking whether the first-run onboarding was completed
+///
+/// Returns the `onboarding_done` flag, persisted whenever it changes, and a
+/// signal holding the current tour step (`None` when the tour is not shown).
+pub fn use_onboarding() -> (Signal<bool>, Signal<Option<usize>>) {
+    // Demo visitors get the sample list straight away, so there is nothing to offer
+    let onboarding_done = use_signal(|| {
+        utils::demo::is_demo_mode()
+            || utils::load::<bool>(keys::ONBOARDING_DONE)
+                .unwrap_or_else(|_| !utils::onboarding::is_first_run())
+    });
+    let tour_step = use_signal(|| None::<usize>);
+
+    use_effect(move || {
+        if onboarding_done() {
+            let _ = utils::save(keys::ONBOARDING_DONE, &true);
+        }
+    });
+
+    (onboarding_done, tour_step)
+}
+
+/// Gets the classes for an element the tour can point at.
+///
+/// # Arguments
+/// * `tour_step` - The current tour step, if the tour is shown
+/// * `step` - The tour step that points at this element
+///
+/// # Returns
+/// The highlight classes when the tour is on this step, otherwise an empty string
+pub fn tour_highlight_class(tour_step: Option<usize>, step: usize) -> &'static str {
+    if tour_step == Some(step) {
+        TOUR_HIGHLIGHT_CLASS
+    } else {
+        ""
+    }
+}
+
+/// Component offering sample todos to a first-time user
+#[component]
+pub fn WelcomePrompt(
+    on_accept: EventHandler<()>,
+    on_decline: EventHandler<()>,
+    is_dark_mode: bool,
+) -> Element {
+    let motion_class = use_motion("transition-colors");
+
+    let card_class = theme::notice_class(theme::Tone::Info, is_dark_mode);
+    let secondary_button_class = theme::outline_button_class(is_dark_mode);
+
+    rsx! {
+        div { class: "mb-4 p-4 rounded-lg border {card_class} {motion_class}",
+            h2 { class: "font-semibold mb-1", "Welcome! 👋" }
+            p { class: "text-sm mb-3",
+                "Want a few sample todos to see tags, due dates and drag to reorder in action? You can delete them like any other todo."
+            }
+            div { class: "flex gap-2",
+                button {
+                    r#type: "button",
+                    class: "px-3 py-1 rounded bg-blue-500 text-white hover:bg-blue-600 text-sm",
+                    onclick: move |_| on_accept.call(()),
+                    "Add sample todos"
+                }
+                button {
+                    r#type: "button",
+                    class: "px-3 py-1 rounded {secondary_button_class} text-sm",
+                    onclick: move |_| on_decline.call(()),
+                    "Start empty"
+                }
+            }
+        }
+    }
+}
+
+/// Component showing the coach mark for the current step of the guided tour
+#[component]
+pub fn OnboardingTour(
+    step: usize,
+    on_step_change: EventHandler<Option<usize>>,
+    is_dark_mode: bool,
+) -> Element {
+    let Some((title, description)) = TOUR_STEPS.get(step) else {
+        return rsx! {};
+    };
+    let is_last_step = step + 1 == TOUR_STEPS.len();
+
+    let card_class = theme::modal_panel_class(is_dark_mode);
+    let text_secondary_class = theme::muted_text_class(is_dark_mode);
+
+    rsx! {
+        // Dim everything except the highlighted element
+        div {
+            class: "fixed inset-0 z-30 bg-black/30",
+            onclick: move |_| on_step_change.call(None),
+        }
+        div {
+            class: "fixed bottom-6 left-1/2 -translate-x-1/2 z-50 w-80 p-4 rounded-lg border shadow-lg {card_class}",
+            role: "dialog",
+            aria_label: "Guided tour",
+            p { class: "text-xs {text_secondary_class} mb-1",
+                "Step {step + 1} of {TOUR_STEPS.len()}"
+            }
+            h2 { class: "font-semibold mb-1", "{title}" }
+            p { class: "text-sm mb-3", "{description}" }
+            div { class: "flex justify-between items-center",
+                button {
+                    r#type: "button",
+                    class: "text-sm {text_secondary_class} hover:underline",
+                    onclick: move |_| on_step_change.call(None),
+                    "Skip tour"
+                }
+                div { class: "flex gap-2",
+                    if step > 0 {
+                        button {
+                            r#type: "button",
+                            class: "px-3 py-1 rounded text-sm {text_secondary_class}",
+                            onclick: move |_| on_step_change.call(Some(step - 1)),
+                            "Back"
+                        }
+                    }
+                    button {
+                        r#type: "button",
+                        class: "px-3 py-1 rounded bg-blue-500 text-white hover:bg-blue-600 text-sm",
+                        onclick: move |_| {
+                            on_step_change.call(if is_last_step { None } else { Some(step + 1) })
+                        },
+                        if is_last_step { "Done" } else { "Next" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::Mutations;
+
+    #[test]
+    fn test_tour_highlight_class() {
+        assert_eq!(tour_highlight_class(None, 0), "");
+        assert_eq!(tour_highlight_class(Some(1), 0), "");
+        assert_eq!(tour_highlight_class(Some(2), 2), TOUR_HIGHLIGHT_CLASS);
+    }
+
+    #[test]
+    fn test_onboarding_tour_rendering() {
+        let mut app = VirtualDom::new(|| {
+            rsx! {
+                OnboardingTour { step: 0, on_step_change: |_| {}, is_dark_mode: false }
+                WelcomePrompt { on_accept: |_| {}, on_decline: |_| {}, is_dark_mode: true }
+            }
+        });
+
+        app.rebuild(&mut Mutations::default());
+    }
+}
@@ -0,0 +1,79 @@
+use crate::components::theme_manager::use_motion;
+use crate::models::Todo;
+use crate::utils::theme;
+use dioxus::prelude::*;
+
+/// Card listing the open todos postponed most often, each opening its todo in the list
+#[component]
+pub fn MostPostponed(
+    todos: Vec<Todo>,
+    on_open_todo: EventHandler<usize>,
+    is_dark_mode: bool,
+) -> Element {
+    let motion_class = use_motion("transition-colors");
+
+    let card_class = format!(
+        "{} {}",
+        theme::surface_class(is_dark_mode),
+        theme::secondary_text_class(is_dark_mode)
+    );
+    let link_class = theme::accent_text_class(is_dark_mode);
+
+    rsx! {
+        div {
+            class: "p-3 mt-4 rounded-lg shadow text-sm {card_class} {motion_class}",
+            aria_label: "Most postponed",
+            p { class: "font-semibold", "Most postponed" }
+            p { class: "text-xs opacity-75 mb-1.5",
+                "These keep slipping. Split them up, hand them off or let them go?"
+            }
+            ul { class: "space-y-0.5",
+                for todo in todos {
+                    li { key: "{todo.id}", class: "flex items-baseline gap-2",
+                        button {
+                            r#type: "button",
+                            class: "flex-1 min-w-0 truncate text-left {link_class} hover:underline",
+                            title: "{todo.text}",
+                            onclick: move |_| on_open_todo.call(todo.id),
+                            "{todo.text}"
+                        }
+                        span { class: "shrink-0 text-xs tabular-nums opacity-75",
+                            "×{todo.postponed_count}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_most_postponed_lists_counts_and_opens_todos() {
+        let opened = Rc::new(RefCell::new(Vec::new()));
+        let mut dom = TestDom::with_props(
+            |opened: Rc<RefCell<Vec<usize>>>| {
+                let mut todo = Todo::new(4, "File taxes".to_string());
+                todo.postponed_count = 6;
+                rsx! {
+                    MostPostponed {
+                        todos: vec![todo],
+                        on_open_todo: move |id| opened.borrow_mut().push(id),
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            opened.clone(),
+        );
+
+        assert!(dom.text().contains("×6"));
+        dom.click(dom.find("button", "File taxes").unwrap());
+        assert_eq!(*opened.borrow(), [4]);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::components::theme_manager::use_motion;
+use crate::models::QuickFilter;
+use crate::utils::theme;
+use dioxus::prelude::*;
+
+/// Component showing date-scoped filter pills with the number of todos each matches
+///
+/// A "Stale" pill follows when `stale_count` is given. It stands for the
+/// `is:stale` search, so it is reported through `on_toggle_stale` rather
+/// than as a quick filter. Likewise an "Added this session" pill stands for
+/// the `is:new` search, shown once `new_count` is given.
+#[component]
+pub fn QuickFilters(
+    counts: Vec<(QuickFilter, usize)>,
+    active: Option<QuickFilter>,
+    on_change: EventHandler<Option<QuickFilter>>,
+    #[props(default)] stale_count: Option<usize>,
+    #[props(default)] stale_active: bool,
+    #[props(default)] on_toggle_stale: EventHandler<bool>,
+    #[props(default)] new_count: Option<usize>,
+    #[props(default)] new_active: bool,
+    #[props(default)] on_toggle_new: EventHandler<bool>,
+    is_dark_mode: bool,
+) -> Element {
+    let motion_class = use_motion("transition-colors duration-200");
+
+    let stale_class = theme::pill_class(stale_active, is_dark_mode);
+    let new_class = theme::pill_class(new_active, is_dark_mode);
+    let overdue_count_class = theme::danger_text_class(is_dark_mode);
+
+    rsx! {
+        div { class: "flex flex-wrap gap-2 mt-3 text-xs", role: "group", aria_label: "Quick filters",
+            for (quick_filter, count) in counts {
+                {
+                    let is_active = active == Some(quick_filter);
+                    let state_class = theme::pill_class(is_active, is_dark_mode);
+                    let count_class = if quick_filter == QuickFilter::Overdue && count > 0 && !is_active {
+                        overdue_count_class
+                    } else {
+                        ""
+                    };
+                    rsx! {
+                        span {
+                            key: "{quick_filter.label()}",
+                            class: "inline-flex items-center rounded-full border {motion_class} {state_class}",
+                            button {
+                                r#type: "button",
+                                class: "px-3 py-1",
+                                aria_pressed: is_active,
+                                onclick: move |_| on_change.call(if is_active { None } else { Some(quick_filter) }),
+                                "{quick_filter.label()} "
+                                span { class: "font-semibold {count_class}", "{count}" }
+                            }
+                            if is_active {
+                                button {
+                                    r#type: "button",
+                                    class: "pr-2 opacity-80 hover:opacity-100",
+                                    title: "Clear filter",
+                                    aria_label: "Clear {quick_filter.label()} filter",
+                                    onclick: move |_| on_change.call(None),
+                                    "×"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(count) = stale_count {
+                span {
+                    key: "stale",
+                    class: "inline-flex items-center rounded-full border {motion_class} {stale_class}",
+                    button {
+                        r#type: "button",
+                        class: "px-3 py-1",
+                        aria_pressed: stale_active,
+                        title: "Open todos left unchanged the longest, oldest first",
+                        onclick: move |_| on_toggle_stale.call(!stale_active),
+                        "Stale "
+                        span { class: "font-semibold", "{count}" }
+                    }
+                }
+            }
+            if let Some(count) = new_count {
+                span {
+                    key: "new",
+                    class: "inline-flex items-center rounded-full border {motion_class} {new_class}",
+                    button {
+                        r#type: "button",
+                        class: "px-3 py-1",
+                        aria_pressed: new_active,
+                        title: "Todos added since the app was started",
+                        onclick: move |_| on_toggle_new.call(!new_active),
+                        "Added this session "
+                        span { class: "font-semibold", "{count}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::{Mutation, Mutations};
+
+    #[test]
+    fn test_quick_filters_rendering() {
+        let mut app = VirtualDom::new(|| {
+            rsx! {
+                QuickFilters {
+                    counts: vec![
+                        (QuickFilter::Overdue, 3),
+                        (QuickFilter::Today, 5),
+                        (QuickFilter::ThisWeek, 9),
+                    ],
+                    active: Some(QuickFilter::Today),
+                    on_change: |_| {},
+                    is_dark_mode: false,
+                }
+            }
+        });
+
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        let texts: Vec<String> = mutations
+            .edits
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Mutation::CreateTextNode { value, .. } => Some(value),
+                _ => None,
+            })
+            .collect();
+        assert!(texts.contains(&"This week ".to_string()));
+        assert!(texts.contains(&"9".to_string()));
+        assert!(texts.contains(&"Overdue ".to_string()));
+    }
+
+    #[test]
+    fn test_session_pill_shows_only_with_a_count() {
+        let texts = |new_count: Option<usize>| {
+            let mut app = VirtualDom::new_with_props(
+                |new_count: Option<usize>| {
+                    rsx! {
+                        QuickFilters {
+                            counts: vec![],
+                            active: None,
+                            on_change: |_| {},
+                            new_count,
+                            is_dark_mode: false,
+                        }
+                    }
+                },
+                new_count,
+            );
+            let mut mutations = Mutations::default();
+            app.rebuild(&mut mutations);
+            mutations
+                .edits
+                .into_iter()
+                .filter_map(|edit| match edit {
+                    Mutation::CreateTextNode { value, .. } => Some(value),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(texts(Some(12)), ["12"]);
+        assert!(texts(None).is_empty());
+    }
+}
@@ -0,0 +1,235 @@
+use crate::components::clock::use_clock;
+use crate::components::theme_manager::use_motion;
+use crate::models::quiet::{QuietFor, QuietMode};
+use crate::utils;
+use crate::utils::keys;
+use crate::utils::theme;
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use dioxus::prelude::*;
+
+/// Logic for remembering when "Do not disturb" ends
+///
+/// Returns the quiet mode, persisted whenever it changes. It is also provided
+/// as context, so the features it silences can read it with
+/// `use_context::<Signal<QuietMode>>()`.
+pub fn use_quiet_mode() -> Signal<QuietMode> {
+    let quiet_mode = use_context_provider(|| {
+        Signal::new(utils::load::<QuietMode>(keys::QUIET_MODE).unwrap_or_default())
+    });
+
+    use_effect(move || {
+        let _ = utils::save(keys::QUIET_MODE, &*quiet_mode.read());
+    });
+
+    quiet_mode
+}
+
+/// Parses the value of a `datetime-local` field, like "2025-03-12T18:30".
+fn parse_local(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M").ok()
+}
+
+/// Formats the end of a quiet period, with the day only when it is not today.
+fn format_until(until: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let (until, now) = (until.with_timezone(&Local), now.with_timezone(&Local));
+    if until.date_naive() == now.date_naive() {
+        until.format("%H:%M").to_string()
+    } else {
+        until.format("%a %H:%M").to_string()
+    }
+}
+
+/// Component for the "Do not disturb until…" control in the header
+///
+/// A moon button opens a menu of quiet periods: an hour, until tomorrow
+/// morning, or until a picked time. While quiet mode is on the button is
+/// highlighted and its title says when it ends; the menu offers to end it.
+#[component]
+pub fn QuietModeMenu(
+    quiet_mode: QuietMode,
+    on_change: EventHandler<QuietMode>,
+    is_dark_mode: bool,
+) -> Element {
+    let motion_class = use_motion("transition-colors");
+
+    let clock = use_clock();
+    let mut show_menu = use_signal(|| false);
+    let mut custom_until = use_signal(String::new);
+
+    let now = clock.now_utc();
+    let active_until = quiet_mode.active_until(now);
+    let title = match active_until {
+        Some(until) => format!("Do not disturb until {}", format_until(until, now)),
+        None => "Do not disturb".to_string(),
+    };
+
+    let start = move |quiet_for: QuietFor| {
+        show_menu.set(false);
+        on_change.call(QuietMode::start(quiet_for, &*clock));
+    };
+    let custom = parse_local(&custom_until());
+
+    let state_class = if active_until.is_some() {
+        theme::tint_class(theme::Accent::Indigo, is_dark_mode).to_string()
+    } else {
+        format!(
+            "{} {}",
+            theme::secondary_text_class(is_dark_mode),
+            theme::hover_class(is_dark_mode)
+        )
+    };
+    let item_class = format!(
+        "block w-full text-left px-3 py-1.5 rounded {}",
+        theme::menu_item_class(is_dark_mode)
+    );
+    let menu_class = theme::card_class(is_dark_mode);
+    let input_class = theme::input_class(is_dark_mode);
+    let off_class = theme::danger_text_class(is_dark_mode);
+
+    rsx! {
+        div { class: "relative",
+            button {
+                r#type: "button",
+                class: "p-2 rounded-full {motion_class} {state_class}",
+                title: "{title}",
+                aria_label: "{title}",
+                aria_expanded: show_menu(),
+                onclick: move |_| show_menu.set(!show_menu()),
+                "☾"
+            }
+
+            if show_menu() {
+                div {
+                    class: "absolute right-0 top-full mt-1 z-20 w-60 p-2 rounded-lg border shadow-lg text-sm {menu_class}",
+                    role: "menu",
+                    p { class: "px-3 py-1 font-semibold", "Do not disturb" }
+                    button {
+                        r#type: "button",
+                        class: "{item_class}",
+                        role: "menuitem",
+                        onclick: {
+                            let mut start = start.clone();
+                            move |_| start(QuietFor::OneHour)
+                        },
+                        "For 1 hour"
+                    }
+                    button {
+                        r#type: "button",
+                        class: "{item_class}",
+                        role: "menuitem",
+                        onclick: {
+                            let mut start = start.clone();
+                            move |_| start(QuietFor::UntilTomorrow)
+                        },
+                        "Until tomorrow"
+                    }
+                    form {
+                        class: "flex items-center gap-1 px-3 py-1.5",
+                        onsubmit: {
+                            let mut start = start.clone();
+                            move |evt: Event<FormData>| {
+                                evt.prevent_default();
+                                if let Some(until) = custom {
+                                    start(QuietFor::Until(until));
+                                }
+                            }
+                        },
+                        input {
+                            r#type: "datetime-local",
+                            class: "flex-1 min-w-0 px-1 py-0.5 text-xs rounded border {input_class}",
+                            aria_label: "Quiet until",
+                            value: "{custom_until}",
+                            oninput: move |evt| custom_until.set(evt.value()),
+                        }
+                        button {
+                            r#type: "submit",
+                            class: "px-2 py-0.5 text-xs rounded bg-indigo-500 text-white disabled:opacity-50",
+                            disabled: custom.is_none(),
+                            "Set"
+                        }
+                    }
+                    if active_until.is_some() {
+                        button {
+                            r#type: "button",
+                            class: "{item_class} {off_class}",
+                            role: "menuitem",
+                            onclick: move |_| {
+                                show_menu.set(false);
+                                on_change.call(QuietMode::default());
+                            },
+                            "Turn off"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Changes = Rc<RefCell<Vec<QuietMode>>>;
+
+    fn render(quiet_mode: QuietMode, changes: Changes) -> TestDom {
+        TestDom::with_props(
+            |(quiet_mode, changes): (QuietMode, Changes)| {
+                rsx! {
+                    QuietModeMenu {
+                        quiet_mode,
+                        on_change: move |quiet| changes.borrow_mut().push(quiet),
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            (quiet_mode, changes),
+        )
+    }
+
+    #[test]
+    fn test_parse_local() {
+        assert_eq!(
+            parse_local("2025-03-12T18:30").map(|time| time.to_string()),
+            Some("2025-03-12 18:30:00".to_string())
+        );
+        assert_eq!(parse_local(""), None);
+    }
+
+    #[test]
+    fn test_picking_an_hour_starts_quiet_mode() {
+        let changes = Changes::default();
+        let mut dom = render(QuietMode::default(), changes.clone());
+
+        dom.click(dom.first("button").unwrap());
+        assert!(dom.find("button", "Turn off").is_none());
+        dom.click(dom.find("button", "For 1 hour").unwrap());
+
+        let until = changes.borrow()[0].until.unwrap();
+        let left = until - Utc::now();
+        assert!(left > chrono::Duration::minutes(59) && left <= chrono::Duration::hours(1));
+        assert!(dom.find("button", "For 1 hour").is_none());
+    }
+
+    #[test]
+    fn test_active_quiet_mode_can_be_turned_off() {
+        let changes = Changes::default();
+        let quiet = QuietMode {
+            until: Some(Utc::now() + chrono::Duration::hours(2)),
+        };
+        let mut dom = render(quiet, changes.clone());
+
+        let moon = dom.first("button").unwrap();
+        assert!(
+            dom.attribute(moon, "title")
+                .unwrap()
+                .starts_with("Do not disturb until ")
+        );
+        dom.click(moon);
+        dom.click(dom.find("button", "Turn off").unwrap());
+        assert_eq!(*changes.borrow(), vec![QuietMode::default()]);
+    }
+}
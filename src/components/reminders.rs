@@ -0,0 +1,238 @@
+use crate::components::clock::use_clock;
+use crate::components::theme_manager::use_motion;
+use crate::models::Todo;
+use crate::models::idle::{self, TickMonitor};
+use crate::utils;
+use crate::utils::constants::todo::{MAX_TICK_CREDIT_SECS, REMINDER_CHECK_INTERVAL_SECS};
+use crate::utils::keys;
+use crate::utils::{theme, timer};
+use chrono::{DateTime, Duration, Local, Utc};
+use dioxus::prelude::*;
+use std::collections::BTreeSet;
+
+/// Logic for remembering which due soon reminders were already seen
+///
+/// Returns the ids of the todos listed the last time the bell dropdown was
+/// opened, persisted whenever they change. The bell badge only counts todos
+/// that are not in this set.
+pub fn use_seen_reminders() -> Signal<BTreeSet<usize>> {
+    let seen =
+        use_signal(|| utils::load::<BTreeSet<usize>>(keys::SEEN_REMINDERS).unwrap_or_default());
+
+    use_effect(move || {
+        let _ = utils::save(keys::SEEN_REMINDERS, &*seen.read());
+    });
+
+    seen
+}
+
+/// Logic for checking reminders as time passes
+///
+/// Returns when reminders were last checked, moved on every
+/// [`REMINDER_CHECK_INTERVAL_SECS`] so todos entering the reminder window show
+/// up without anything else changing. Checks stop while the computer sleeps
+/// and run as soon as the window regains focus or becomes visible again; a
+/// long gap between checks is logged as time away rather than counted.
+pub fn use_reminder_checks() -> Signal<DateTime<Utc>> {
+    let clock = use_clock();
+    let mut checked = use_signal(|| clock.now_utc());
+
+    use_hook(move || {
+        spawn(async move {
+            let interval = std::time::Duration::from_secs(REMINDER_CHECK_INTERVAL_SECS);
+            let mut monitor =
+                TickMonitor::new(clock.now_utc(), Duration::seconds(MAX_TICK_CREDIT_SECS));
+            loop {
+                timer::sleep_or_wake(interval).await;
+                let now = clock.now_utc();
+                let tick = monitor.tick(now);
+                if tick.resumed() {
+                    tracing::debug!(
+                        "Reminder checks resumed after {} away",
+                        idle::format_away(tick.away.unwrap_or_default())
+                    );
+                }
+                checked.set(now);
+            }
+        });
+    });
+
+    checked
+}
+
+/// Scrolls the list to a todo and briefly highlights it.
+///
+/// Runs after a short delay so a filter reset made just before has rendered.
+pub fn scroll_to_todo(id: usize) {
+    let _ = document::eval(&format!(
+        r#"
+        setTimeout(() => {{
+            const item = document.getElementById('todo-{id}');
+            if (item) {{
+                item.scrollIntoView({{ behavior: 'smooth', block: 'center' }});
+                item.classList.add('ring-2', 'ring-amber-400');
+                setTimeout(() => item.classList.remove('ring-2', 'ring-amber-400'), 1500);
+            }}
+        }}, 50);
+        "#
+    ));
+}
+
+/// Formats when a todo is due relative to now, like "in 5h" or "overdue".
+fn due_label(due: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let hours = (due - now).num_hours();
+    if due <= now {
+        "overdue".to_string()
+    } else if hours < 1 {
+        "within the hour".to_string()
+    } else if hours < 48 {
+        format!("in {}h", hours)
+    } else {
+        format!("on {}", due.with_timezone(&Local).format("%b %d"))
+    }
+}
+
+/// Component showing a bell with the number of todos newly due soon, and a
+/// dropdown listing every todo due soon with quick actions
+#[component]
+pub fn DueSoonBell(
+    todos: Vec<Todo>,
+    unseen_count: usize,
+    on_open: EventHandler<()>,
+    on_complete: EventHandler<usize>,
+    on_snooze: EventHandler<usize>,
+    on_open_todo: EventHandler<usize>,
+    is_dark_mode: bool,
+) -> Element {
+    let motion_class = use_motion("transition-colors");
+
+    let mut show_dropdown = use_signal(|| false);
+    let now = use_clock().now_utc();
+
+    let dropdown_class = theme::card_class(is_dark_mode);
+    let text_secondary_class = theme::muted_text_class(is_dark_mode);
+    let action_class = theme::link_class(is_dark_mode);
+    let hover_class = theme::hover_class(is_dark_mode);
+
+    rsx! {
+        div { class: "relative",
+            button {
+                r#type: "button",
+                class: "relative p-2 rounded-full {hover_class} {motion_class}",
+                aria_label: "Due soon",
+                aria_expanded: show_dropdown(),
+                onclick: move |_| {
+                    let opening = !show_dropdown();
+                    show_dropdown.set(opening);
+                    if opening {
+                        on_open.call(());
+                    }
+                },
+                "🔔"
+                if unseen_count > 0 {
+                    span {
+                        class: "absolute -top-0.5 -right-0.5 min-w-4 h-4 px-1 rounded-full bg-red-500 text-white text-[10px] leading-4 text-center",
+                        "{unseen_count}"
+                    }
+                }
+            }
+
+            if show_dropdown() {
+                div {
+                    class: "absolute right-0 top-full mt-1 z-20 w-72 p-2 rounded-lg border shadow-lg text-sm {dropdown_class}",
+                    role: "menu",
+                    p { class: "px-2 py-1 font-semibold", "Due soon" }
+                    if todos.is_empty() {
+                        p { class: "px-2 py-1 {text_secondary_class}", "Nothing due soon" }
+                    }
+                    for todo in todos {
+                        div { key: "{todo.id}", class: "px-2 py-1.5 rounded",
+                            div { class: "flex justify-between gap-2",
+                                span { class: "truncate", "{todo.text}" }
+                                if let Some(due) = todo.due_date {
+                                    span { class: "shrink-0 text-xs {text_secondary_class}",
+                                        "{due_label(due, now)}"
+                                    }
+                                }
+                            }
+                            div { class: "flex gap-3 text-xs mt-0.5",
+                                button {
+                                    r#type: "button",
+                                    class: action_class,
+                                    onclick: move |_| on_complete.call(todo.id),
+                                    "Complete"
+                                }
+                                button {
+                                    r#type: "button",
+                                    class: action_class,
+                                    title: "Remind me again in an hour",
+                                    onclick: move |_| on_snooze.call(todo.id),
+                                    "Snooze 1h"
+                                }
+                                button {
+                                    r#type: "button",
+                                    class: action_class,
+                                    onclick: move |_| {
+                                        show_dropdown.set(false);
+                                        on_open_todo.call(todo.id);
+                                    },
+                                    "Open"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use dioxus::dioxus_core::{Mutation, Mutations};
+
+    #[test]
+    fn test_due_label() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 12, 23, 59, 0).unwrap();
+        assert_eq!(due_label(now - Duration::hours(1), now), "overdue");
+        assert_eq!(
+            due_label(now + Duration::minutes(30), now),
+            "within the hour"
+        );
+        assert_eq!(due_label(now + Duration::hours(5), now), "in 5h");
+    }
+
+    #[test]
+    fn test_due_soon_bell_badge() {
+        let mut app = VirtualDom::new_with_props(
+            |unseen_count: usize| {
+                rsx! {
+                    DueSoonBell {
+                        todos: Vec::new(),
+                        unseen_count,
+                        on_open: |_| {},
+                        on_complete: |_| {},
+                        on_snooze: |_| {},
+                        on_open_todo: |_| {},
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            3,
+        );
+
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        let texts: Vec<String> = mutations
+            .edits
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Mutation::CreateTextNode { value, .. } => Some(value),
+                _ => None,
+            })
+            .collect();
+        assert!(texts.contains(&"3".to_string()));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::components::theme_manager::use_motion;
+use crate::utils::theme;
 use dioxus::prelude::*;
 
 /// Props for the SearchBox component
@@ -15,40 +17,20 @@ pub struct SearchBoxProps {
 /// A component that renders a search input field
 #[component]
 pub fn SearchBox(props: SearchBoxProps) -> Element {
+    let motion_class = use_motion("transition-colors duration-300");
+    let icon_motion_class = use_motion("transition-colors duration-200");
+
     // Dynamic classes based on dark mode
-    let container_bg_class = if props.is_dark_mode {
-        "bg-gray-800"
-    } else {
-        "bg-white"
-    };
-    let text_class = if props.is_dark_mode {
-        "text-gray-100"
-    } else {
-        "text-gray-800"
-    };
-    let border_class = if props.is_dark_mode {
-        "border-gray-700"
-    } else {
-        "border-gray-200"
-    };
-    let placeholder_class = if props.is_dark_mode {
-        "placeholder-gray-500"
-    } else {
-        "placeholder-gray-400"
-    };
-    let focus_class = if props.is_dark_mode {
-        "focus:ring-indigo-500 focus:border-indigo-500"
-    } else {
-        "focus:ring-indigo-600 focus:border-indigo-600"
-    };
-    let icon_class = if props.is_dark_mode {
-        "text-gray-400"
-    } else {
-        "text-gray-500"
-    };
+    let container_bg_class = theme::surface_class(props.is_dark_mode);
+    let text_class = theme::primary_text_class(props.is_dark_mode);
+    let border_class = theme::border_class(props.is_dark_mode);
+    let placeholder_class = theme::placeholder_class(props.is_dark_mode);
+    let focus_class = theme::focus_ring_class(props.is_dark_mode);
+    let icon_class = theme::muted_text_class(props.is_dark_mode);
+    let clear_hover_class = theme::hover_text_class(theme::Accent::Gray, props.is_dark_mode);
 
     rsx! {
-        div { class: "mb-4 {container_bg_class} rounded-lg shadow-md overflow-hidden transition-colors duration-300 border {border_class}",
+        div { class: "mb-4 {container_bg_class} rounded-lg shadow-md overflow-hidden {motion_class} border {border_class}",
             div { class: "relative flex items-center",
                 // Search icon
                 div { class: "absolute inset-y-0 left-3 flex items-center pointer-events-none {icon_class}",
@@ -70,7 +52,7 @@ pub fn SearchBox(props: SearchBoxProps) -> Element {
 
                 // Search input
                 input {
-                    class: "block w-full py-3 pr-3 pl-10 {text_class} {placeholder_class} {container_bg_class} {focus_class} transition-colors duration-300 border-0 focus:ring-2 outline-none",
+                    class: "block w-full py-3 pr-3 pl-10 {text_class} {placeholder_class} {container_bg_class} {focus_class} {motion_class} border-0 focus:ring-2 outline-none",
                     "type": "search",
                     placeholder: "Search todos...",
                     autocomplete: "off",
@@ -82,7 +64,7 @@ pub fn SearchBox(props: SearchBoxProps) -> Element {
                 // Clear button (only shown when there is search text)
                 if !props.search_term.is_empty() {
                     button {
-                        class: "absolute right-3 {icon_class} hover:text-gray-700 dark:hover:text-gray-300 transition-colors duration-200",
+                        class: "absolute right-3 {icon_class} {clear_hover_class} {icon_motion_class}",
                         r#type: "button",
                         title: "Clear search",
                         onclick: move |_| props.on_search.call(String::new()),
@@ -108,3 +90,55 @@ pub fn SearchBox(props: SearchBoxProps) -> Element {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Renders the box with a search term, recording each search it reports.
+    fn render(term: &str) -> (TestDom, Rc<RefCell<Vec<String>>>) {
+        let searches = Rc::new(RefCell::new(Vec::new()));
+        let dom = TestDom::with_props(
+            |(term, searches): (String, Rc<RefCell<Vec<String>>>)| {
+                rsx! {
+                  SearchBox {
+                    search_term: term,
+                    on_search: move |term| searches.borrow_mut().push(term),
+                  }
+                }
+            },
+            (term.to_string(), searches.clone()),
+        );
+        (dom, searches)
+    }
+
+    #[test]
+    fn test_typing_reports_the_search() {
+        let (mut dom, searches) = render("");
+        dom.input(dom.first("input").unwrap(), "milk");
+
+        assert_eq!(*searches.borrow(), vec!["milk".to_string()]);
+    }
+
+    #[test]
+    fn test_clear_button_shows_with_a_term_and_clears_it() {
+        let (dom, _) = render("");
+        assert!(
+            dom.find_by_attribute("button", "title", "Clear search")
+                .is_none()
+        );
+
+        let (mut dom, searches) = render("milk");
+        let input = dom.first("input").unwrap();
+        assert_eq!(dom.attribute(input, "value"), Some("milk"));
+        dom.click(
+            dom.find_by_attribute("button", "title", "Clear search")
+                .unwrap(),
+        );
+
+        assert_eq!(*searches.borrow(), vec![String::new()]);
+    }
+}
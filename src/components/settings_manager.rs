@@ -0,0 +1,20 @@
+use crate::models::AppSettings;
+use crate::utils;
+use crate::utils::keys;
+use dioxus::prelude::*;
+
+/// Logic for loading the user settings and saving them whenever they change
+///
+/// The settings are also provided as context, so components further down the
+/// tree can read them with `use_context::<Signal<AppSettings>>()`.
+pub fn use_settings() -> Signal<AppSettings> {
+    let settings = use_context_provider(|| {
+        Signal::new(utils::load::<AppSettings>(keys::SETTINGS).unwrap_or_default())
+    });
+
+    use_effect(move || {
+        let _ = utils::save(keys::SETTINGS, &*settings.read());
+    });
+
+    settings
+}
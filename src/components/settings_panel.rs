@@ -0,0 +1,886 @@
+use crate::components::theme_manager::use_motion;
+use crate::components::your_data::format_bytes;
+use crate::components::{AutoTagRules, ExportDialog, IssueLinkRules, TagDueRules, YourData};
+use crate::models::import_plan::ImportPlan;
+use crate::models::{
+    Animations, AppSettings, AutosaveInterval, SplitView, Todo, TodoList, WeekStart,
+};
+use crate::utils::constants::profile::DEFAULT_PROFILE;
+use crate::utils::profile;
+use crate::utils::storage::{
+    self, DataHealth, HealthReport, MaintenanceReport, RepairOutcome, StorageError, StorageStats,
+};
+use crate::utils::theme;
+use chrono::{DateTime, NaiveTime, Utc};
+use dioxus::prelude::*;
+
+/// Props for the SettingsPanel component.
+#[derive(Props, PartialEq, Clone)]
+pub struct SettingsPanelProps {
+    /// The current todos, used for exports
+    pub todos: Vec<Todo>,
+    /// Ids of the todos on screen, for exports of the current view
+    #[props(default)]
+    pub visible_ids: Vec<usize>,
+    /// Names of the filters applied to the list, for export file names
+    #[props(default)]
+    pub view_name: Vec<String>,
+    /// Callback when a repair replaced the todo list
+    pub on_restore: EventHandler<TodoList>,
+    /// Callback applying an import, returning how many todos were added
+    /// and merged into
+    pub on_import: Callback<ImportPlan, (usize, usize)>,
+    /// The current user settings
+    #[props(default)]
+    pub settings: AppSettings,
+    /// Callback when a setting is changed
+    pub on_settings_change: EventHandler<AppSettings>,
+    /// Callback applying the auto-tag rules to existing todos, returning how many were tagged
+    pub on_apply_auto_tags: Callback<(), usize>,
+    /// Callback opening the release notes
+    pub on_show_whats_new: EventHandler<()>,
+    /// Callback opening the debug log
+    pub on_show_debug_log: EventHandler<()>,
+    /// When a save last succeeded, `None` if none did since launch
+    #[props(default)]
+    pub last_saved: Option<DateTime<Utc>>,
+    /// Callback deleting the todos past their retention and tidying the
+    /// storage, returning how many todos were deleted and the space freed
+    pub on_reclaim: Callback<(), Result<(usize, MaintenanceReport), StorageError>>,
+    /// Callback deleting every stored value and starting afresh
+    #[props(default)]
+    pub on_delete_all: EventHandler<()>,
+    /// The profile the app runs in, `None` for the default one
+    #[props(default)]
+    pub profile: Option<String>,
+    /// Callback restarting the app in another profile, `None` for the default one
+    #[props(default)]
+    pub on_switch_profile: EventHandler<Option<String>>,
+    /// Whether dark mode is enabled
+    #[props(default = false)]
+    pub is_dark_mode: bool,
+    /// Extra sections shown before export & import, such as sync
+    #[props(default)]
+    pub children: Element,
+}
+
+/// Describes a health report in a single line for the user.
+fn describe_report(report: &HealthReport) -> String {
+    let data = match &report.data {
+        DataHealth::Missing => "No todos stored yet.".to_string(),
+        DataHealth::Valid { todos } => format!("Stored todos are valid ({} todos).", todos),
+        DataHealth::Corrupt { error } => format!("Stored todos are corrupt: {}", error),
+    };
+
+    if report.is_healthy() {
+        format!("No problems found. {}", data)
+    } else if report.integrity_errors.is_empty() {
+        data
+    } else {
+        format!(
+            "{} Database problems: {}",
+            data,
+            report.integrity_errors.join("; ")
+        )
+    }
+}
+
+/// Describes what reclaiming space did in a single line for the user.
+fn describe_reclaimed(deleted: usize, report: MaintenanceReport) -> String {
+    let todos = if deleted == 1 { "todo" } else { "todos" };
+    match (deleted, report.bytes_freed) {
+        (0, 0) => "Nothing to reclaim.".to_string(),
+        (0, bytes) => format!("Freed {}.", format_bytes(bytes)),
+        (deleted, 0) => format!("Deleted {} {} past their retention.", deleted, todos),
+        (deleted, bytes) => format!(
+            "Deleted {} {} past their retention and freed {}.",
+            deleted,
+            todos,
+            format_bytes(bytes)
+        ),
+    }
+}
+
+/// Panel with application settings and maintenance actions.
+#[component]
+pub fn SettingsPanel(props: SettingsPanelProps) -> Element {
+    let motion_class = use_motion("transition-colors duration-300");
+
+    let mut report = use_signal(|| None::<HealthReport>);
+    let mut profile_name = use_signal(|| {
+        props
+            .profile
+            .clone()
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+    });
+    let mut status = use_signal(|| None::<String>);
+    let mut stats = use_signal(StorageStats::collect);
+
+    let check_integrity = move |_| match storage::health_check() {
+        Ok(result) => {
+            status.set(Some(describe_report(&result)));
+            report.set(Some(result));
+        }
+        Err(e) => {
+            status.set(Some(format!("Health check failed: {:?}", e)));
+            report.set(None);
+        }
+    };
+
+    let repair_data = move |_| match storage::repair() {
+        Ok((RepairOutcome::Salvaged { recovered }, Some(list))) => {
+            props.on_restore.call(list);
+            status.set(Some(format!("Repaired: recovered {} todos.", recovered)));
+            report.set(None);
+        }
+        Ok(_) => {
+            status.set(Some("Nothing to repair.".to_string()));
+            report.set(None);
+        }
+        Err(e) => status.set(Some(format!("Repair failed: {:?}", e))),
+    };
+
+    let reclaim_space = move |_| {
+        match props.on_reclaim.call(()) {
+            Ok((deleted, report)) => status.set(Some(describe_reclaimed(deleted, report))),
+            Err(e) => status.set(Some(format!("Reclaiming space failed: {:?}", e))),
+        }
+        stats.set(StorageStats::collect());
+    };
+
+    // The profile typed in, `Some(None)` for the default one and `None` for
+    // a name that cannot be used
+    let profile_target = {
+        let name = profile_name.read();
+        if name.trim().is_empty() || name.trim().eq_ignore_ascii_case(DEFAULT_PROFILE) {
+            Some(None)
+        } else {
+            profile::parse_name(&name).map(Some)
+        }
+    };
+    let can_switch_profile = profile_target
+        .as_ref()
+        .is_some_and(|target| *target != props.profile);
+
+    let needs_repair = report
+        .read()
+        .as_ref()
+        .is_some_and(|r| matches!(r.data, DataHealth::Corrupt { .. }));
+
+    // Dynamic classes based on dark mode
+    let container_bg_class = theme::surface_class(props.is_dark_mode);
+    let heading_class = theme::primary_text_class(props.is_dark_mode);
+    let text_class = theme::secondary_text_class(props.is_dark_mode);
+    let button_class = theme::secondary_button_class(props.is_dark_mode);
+    let select_class = theme::form_control_class(props.is_dark_mode);
+    let digest_time = props.settings.digest_time.format("%H:%M").to_string();
+    let repair_button_class = theme::danger_button_class(props.is_dark_mode);
+
+    rsx! {
+      div { class: "p-4 mb-6 {container_bg_class} rounded-lg shadow {motion_class}",
+        h2 { class: "text-lg font-semibold mb-3 {heading_class}", "Settings" }
+
+        // Preferences
+        div { class: "grid grid-cols-[auto_1fr] items-center gap-2 mb-4 text-sm {text_class}",
+          label { r#for: "week-start", "Week starts on" }
+          select {
+            id: "week-start",
+            class: "{select_class} justify-self-start",
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    if let Some(week_start) = WeekStart::parse(&evt.value()) {
+                        props.on_settings_change.call(AppSettings {
+                            week_start,
+                            ..settings.clone()
+                        });
+                    }
+                }
+            },
+            for week_start in WeekStart::ALL {
+              option {
+                value: week_start.label(),
+                selected: week_start == props.settings.week_start,
+                "{week_start.label()}"
+              }
+            }
+          }
+          label { r#for: "autosave", "Save changes" }
+          select {
+            id: "autosave",
+            class: "{select_class} justify-self-start",
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    if let Some(autosave) = AutosaveInterval::parse(&evt.value()) {
+                        props.on_settings_change.call(AppSettings {
+                            autosave,
+                            ..settings.clone()
+                        });
+                    }
+                }
+            },
+            for interval in AutosaveInterval::ALL {
+              option {
+                value: interval.label(),
+                selected: interval == props.settings.autosave,
+                "{interval.label()}"
+              }
+            }
+          }
+          label { r#for: "split-view", "Show details beside the list" }
+          select {
+            id: "split-view",
+            class: "{select_class} justify-self-start",
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    if let Some(split_view) = SplitView::parse(&evt.value()) {
+                        props.on_settings_change.call(AppSettings {
+                            split_view,
+                            ..settings.clone()
+                        });
+                    }
+                }
+            },
+            for split in SplitView::ALL {
+              option {
+                value: split.label(),
+                selected: split == props.settings.split_view,
+                "{split.label()}"
+              }
+            }
+          }
+          label { r#for: "animations", "Animations" }
+          select {
+            id: "animations",
+            class: "{select_class} justify-self-start",
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    if let Some(animations) = Animations::parse(&evt.value()) {
+                        props.on_settings_change.call(AppSettings {
+                            animations,
+                            ..settings.clone()
+                        });
+                    }
+                }
+            },
+            for animations in Animations::ALL {
+              option {
+                value: animations.label(),
+                selected: animations == props.settings.animations,
+                "{animations.label()}"
+              }
+            }
+          }
+          label { r#for: "todo-count-warning", "Warn above this many todos" }
+          input {
+            id: "todo-count-warning",
+            class: "{select_class} w-28 justify-self-start",
+            r#type: "number",
+            min: "1",
+            value: "{props.settings.todo_count_warning}",
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    if let Ok(todo_count_warning) = evt.value().trim().parse::<usize>()
+                        && todo_count_warning > 0
+                    {
+                        props.on_settings_change.call(AppSettings {
+                            todo_count_warning,
+                            ..settings.clone()
+                        });
+                    }
+                }
+            },
+          }
+          label { r#for: "reminder-lead-hours", "Remind me this many hours before due" }
+          input {
+            id: "reminder-lead-hours",
+            class: "{select_class} w-28 justify-self-start",
+            r#type: "number",
+            min: "0",
+            value: "{props.settings.reminder_lead_hours}",
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    if let Ok(reminder_lead_hours) = evt.value().trim().parse::<u32>() {
+                        props.on_settings_change.call(AppSettings {
+                            reminder_lead_hours,
+                            ..settings.clone()
+                        });
+                    }
+                }
+            },
+          }
+          label { r#for: "auto-archive-days", "Archive completed todos after (days)" }
+          input {
+            id: "auto-archive-days",
+            class: "{select_class} w-28 justify-self-start",
+            r#type: "number",
+            min: "1",
+            placeholder: "Off",
+            value: props.settings.auto_archive_after_days.map(|days| days.to_string()).unwrap_or_default(),
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    // An empty field turns the policy off
+                    let value = evt.value();
+                    let auto_archive_after_days = match value.trim() {
+                        "" => None,
+                        days => match days.parse::<u32>() {
+                            Ok(days) if days > 0 => Some(days),
+                            _ => return,
+                        },
+                    };
+                    props.on_settings_change.call(AppSettings {
+                        auto_archive_after_days,
+                        ..settings.clone()
+                    });
+                }
+            },
+          }
+          label { r#for: "trash-retention-days", "Delete trashed todos after (days)" }
+          input {
+            id: "trash-retention-days",
+            class: "{select_class} w-28 justify-self-start",
+            r#type: "number",
+            min: "1",
+            placeholder: "Never",
+            value: props.settings.trash_retention_days.map(|days| days.to_string()).unwrap_or_default(),
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    // An empty field keeps trashed todos until the trash is emptied
+                    let value = evt.value();
+                    let trash_retention_days = match value.trim() {
+                        "" => None,
+                        days => match days.parse::<u32>() {
+                            Ok(days) if days > 0 => Some(days),
+                            _ => return,
+                        },
+                    };
+                    props.on_settings_change.call(AppSettings {
+                        trash_retention_days,
+                        ..settings.clone()
+                    });
+                }
+            },
+          }
+          label { r#for: "archive-retention-days", "Delete archived todos after (days)" }
+          input {
+            id: "archive-retention-days",
+            class: "{select_class} w-28 justify-self-start",
+            r#type: "number",
+            min: "1",
+            placeholder: "Never",
+            value: props.settings.archive_retention_days.map(|days| days.to_string()).unwrap_or_default(),
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    // An empty field keeps archived todos forever
+                    let value = evt.value();
+                    let archive_retention_days = match value.trim() {
+                        "" => None,
+                        days => match days.parse::<u32>() {
+                            Ok(days) if days > 0 => Some(days),
+                            _ => return,
+                        },
+                    };
+                    props.on_settings_change.call(AppSettings {
+                        archive_retention_days,
+                        ..settings.clone()
+                    });
+                }
+            },
+          }
+          label { r#for: "escalate-overdue", "Mark overdue todos Urgent" }
+          input {
+            id: "escalate-overdue",
+            class: "justify-self-start",
+            r#type: "checkbox",
+            checked: props.settings.escalate_overdue,
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    props.on_settings_change.call(AppSettings {
+                        escalate_overdue: evt.checked(),
+                        ..settings.clone()
+                    });
+                }
+            },
+          }
+          label { r#for: "review-undated-days", "Tag undated todos \"review\" after (days)" }
+          input {
+            id: "review-undated-days",
+            class: "{select_class} w-28 justify-self-start",
+            r#type: "number",
+            min: "1",
+            placeholder: "Off",
+            value: props.settings.review_undated_after_days.map(|days| days.to_string()).unwrap_or_default(),
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    // An empty field turns the rule off
+                    let value = evt.value();
+                    let review_undated_after_days = match value.trim() {
+                        "" => None,
+                        days => match days.parse::<u32>() {
+                            Ok(days) if days > 0 => Some(days),
+                            _ => return,
+                        },
+                    };
+                    props.on_settings_change.call(AppSettings {
+                        review_undated_after_days,
+                        ..settings.clone()
+                    });
+                }
+            },
+          }
+          label { r#for: "expand-emoji", "Expand :shortcodes:" }
+          input {
+            id: "expand-emoji",
+            class: "justify-self-start",
+            r#type: "checkbox",
+            checked: props.settings.expand_emoji,
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    props.on_settings_change.call(AppSettings {
+                        expand_emoji: evt.checked(),
+                        ..settings.clone()
+                    });
+                }
+            },
+          }
+          label { r#for: "show-week-numbers", "Show week numbers on due dates" }
+          input {
+            id: "show-week-numbers",
+            class: "justify-self-start",
+            r#type: "checkbox",
+            checked: props.settings.show_week_numbers,
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    props.on_settings_change.call(AppSettings {
+                        show_week_numbers: evt.checked(),
+                        ..settings.clone()
+                    });
+                }
+            },
+          }
+          label { r#for: "show-minimap", "Show a due-date minimap beside long lists" }
+          input {
+            id: "show-minimap",
+            class: "justify-self-start",
+            r#type: "checkbox",
+            checked: props.settings.show_minimap,
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    props.on_settings_change.call(AppSettings {
+                        show_minimap: evt.checked(),
+                        ..settings.clone()
+                    });
+                }
+            },
+          }
+          label { r#for: "show-stale", "Mark todos unchanged for (days)" }
+          div { class: "flex items-center gap-2",
+            input {
+              id: "show-stale",
+              r#type: "checkbox",
+              checked: props.settings.show_stale,
+              onchange: {
+                  let settings = props.settings.clone();
+                  move |evt: Event<FormData>| {
+                      props.on_settings_change.call(AppSettings {
+                          show_stale: evt.checked(),
+                          ..settings.clone()
+                      });
+                  }
+              },
+            }
+            input {
+              class: "{select_class} w-20",
+              r#type: "number",
+              min: "1",
+              aria_label: "Days without changes before a todo is stale",
+              value: "{props.settings.stale_after_days}",
+              onchange: {
+                  let settings = props.settings.clone();
+                  move |evt: Event<FormData>| {
+                      if let Ok(stale_after_days @ 1..) = evt.value().trim().parse::<u32>() {
+                          props.on_settings_change.call(AppSettings {
+                              stale_after_days,
+                              ..settings.clone()
+                          });
+                      }
+                  }
+              },
+            }
+          }
+          label { r#for: "show-karma", "Show karma, with a weekly goal of" }
+          div { class: "flex items-center gap-2",
+            input {
+              id: "show-karma",
+              r#type: "checkbox",
+              checked: props.settings.show_karma,
+              onchange: {
+                  let settings = props.settings.clone();
+                  move |evt: Event<FormData>| {
+                      props.on_settings_change.call(AppSettings {
+                          show_karma: evt.checked(),
+                          ..settings.clone()
+                      });
+                  }
+              },
+            }
+            input {
+              class: "{select_class} w-20",
+              r#type: "number",
+              min: "1",
+              aria_label: "Todos to complete each week",
+              value: "{props.settings.weekly_goal}",
+              onchange: {
+                  let settings = props.settings.clone();
+                  move |evt: Event<FormData>| {
+                      if let Ok(weekly_goal @ 1..) = evt.value().trim().parse::<usize>() {
+                          props.on_settings_change.call(AppSettings {
+                              weekly_goal,
+                              ..settings.clone()
+                          });
+                      }
+                  }
+              },
+            }
+          }
+          if cfg!(feature = "desktop") {
+            label { r#for: "daily-digest", "Notify me of todos due today at" }
+            div { class: "flex flex-wrap items-center gap-2",
+              input {
+                id: "daily-digest",
+                r#type: "checkbox",
+                checked: props.settings.daily_digest,
+                onchange: {
+                    let settings = props.settings.clone();
+                    move |evt: Event<FormData>| {
+                        props.on_settings_change.call(AppSettings {
+                            daily_digest: evt.checked(),
+                            ..settings.clone()
+                        });
+                    }
+                },
+              }
+              input {
+                class: "{select_class} w-28",
+                r#type: "time",
+                aria_label: "Time of the daily digest",
+                value: "{digest_time}",
+                onchange: {
+                    let settings = props.settings.clone();
+                    move |evt: Event<FormData>| {
+                        if let Ok(digest_time) = NaiveTime::parse_from_str(evt.value().trim(), "%H:%M") {
+                            props.on_settings_change.call(AppSettings {
+                                digest_time,
+                                ..settings.clone()
+                            });
+                        }
+                    }
+                },
+              }
+              label { class: "flex items-center gap-1",
+                input {
+                  r#type: "checkbox",
+                  checked: props.settings.digest_skip_weekends,
+                  onchange: {
+                      let settings = props.settings.clone();
+                      move |evt: Event<FormData>| {
+                          props.on_settings_change.call(AppSettings {
+                              digest_skip_weekends: evt.checked(),
+                              ..settings.clone()
+                          });
+                      }
+                  },
+                }
+                "Skip weekends"
+              }
+            }
+          }
+          label { r#for: "follow-up-tag", "Follow up on todos tagged, after (days)" }
+          div { class: "flex items-center gap-2",
+            input {
+              id: "follow-up-tag",
+              class: "{select_class} w-28",
+              r#type: "text",
+              placeholder: "off",
+              value: "{props.settings.follow_up_tag}",
+              onchange: {
+                  let settings = props.settings.clone();
+                  move |evt: Event<FormData>| {
+                      props.on_settings_change.call(AppSettings {
+                          follow_up_tag: evt.value().trim().to_string(),
+                          ..settings.clone()
+                      });
+                  }
+              },
+            }
+            input {
+              class: "{select_class} w-20",
+              r#type: "number",
+              min: "1",
+              aria_label: "Days without changes before a waiting todo needs follow-up",
+              value: "{props.settings.follow_up_after_days}",
+              onchange: {
+                  let settings = props.settings.clone();
+                  move |evt: Event<FormData>| {
+                      if let Ok(follow_up_after_days @ 1..) = evt.value().trim().parse::<u32>() {
+                          props.on_settings_change.call(AppSettings {
+                              follow_up_after_days,
+                              ..settings.clone()
+                          });
+                      }
+                  }
+              },
+            }
+          }
+          label { r#for: "follow-up-note", "Note the date when bumping a todo" }
+          input {
+            id: "follow-up-note",
+            class: "justify-self-start",
+            r#type: "checkbox",
+            checked: props.settings.follow_up_note,
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    props.on_settings_change.call(AppSettings {
+                        follow_up_note: evt.checked(),
+                        ..settings.clone()
+                    });
+                }
+            },
+          }
+          label { r#for: "confirm-unlock", "Ask before unlocking a todo" }
+          input {
+            id: "confirm-unlock",
+            class: "justify-self-start",
+            r#type: "checkbox",
+            checked: props.settings.confirm_unlock,
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    props.on_settings_change.call(AppSettings {
+                        confirm_unlock: evt.checked(),
+                        ..settings.clone()
+                    });
+                }
+            },
+          }
+          label { r#for: "completion-sound", "Play a sound when completing a todo" }
+          input {
+            id: "completion-sound",
+            class: "justify-self-start",
+            r#type: "checkbox",
+            checked: props.settings.completion_sound,
+            onchange: {
+                let settings = props.settings.clone();
+                move |evt: Event<FormData>| {
+                    props.on_settings_change.call(AppSettings {
+                        completion_sound: evt.checked(),
+                        ..settings.clone()
+                    });
+                }
+            },
+          }
+        }
+
+        // Auto-tag rules
+        h3 { class: "text-sm font-medium mb-2 {heading_class}", "Auto-tag rules" }
+        div { class: "mb-4",
+          AutoTagRules {
+            rules: props.settings.auto_tag_rules.clone(),
+            on_rules_change: {
+                let settings = props.settings.clone();
+                move |auto_tag_rules| {
+                    props.on_settings_change.call(AppSettings {
+                        auto_tag_rules,
+                        ..settings.clone()
+                    });
+                }
+            },
+            on_apply_to_existing: props.on_apply_auto_tags,
+            is_dark_mode: props.is_dark_mode,
+          }
+        }
+
+        // Default due dates by tag
+        h3 { class: "text-sm font-medium mb-2 {heading_class}", "Due dates by tag" }
+        div { class: "mb-4",
+          TagDueRules {
+            rules: props.settings.tag_due_rules.clone(),
+            on_rules_change: {
+                let settings = props.settings.clone();
+                move |tag_due_rules| {
+                    props.on_settings_change.call(AppSettings {
+                        tag_due_rules,
+                        ..settings.clone()
+                    });
+                }
+            },
+            is_dark_mode: props.is_dark_mode,
+          }
+        }
+
+        // Issue links
+        h3 { class: "text-sm font-medium mb-2 {heading_class}", "Issue links" }
+        div { class: "mb-4",
+          IssueLinkRules {
+            rules: props.settings.issue_link_rules.clone(),
+            on_rules_change: {
+                let settings = props.settings.clone();
+                move |issue_link_rules| {
+                    props.on_settings_change.call(AppSettings {
+                        issue_link_rules,
+                        ..settings.clone()
+                    });
+                }
+            },
+            is_dark_mode: props.is_dark_mode,
+          }
+        }
+
+        // Tags no longer suggested
+        h3 { class: "text-sm font-medium mb-2 {heading_class}", "Tag suggestions" }
+        div { class: "mb-4 flex flex-col space-y-2 text-sm {text_class}",
+          if props.settings.suppressed_suggestions.is_empty() {
+            p { "Every tag can be suggested. Hover a suggestion and click ✕ to stop suggesting it." }
+          }
+          ul { class: "space-y-1",
+            for tag in props.settings.suppressed_suggestions.iter().cloned() {
+              li { key: "{tag}", class: "flex items-center gap-2",
+                span { class: "flex-1 truncate", "{tag}" }
+                button {
+                  r#type: "button",
+                  class: "{button_class}",
+                  onclick: {
+                      let settings = props.settings.clone();
+                      let tag = tag.clone();
+                      move |_| {
+                          let mut suppressed_suggestions = settings.suppressed_suggestions.clone();
+                          suppressed_suggestions.remove(&tag);
+                          props.on_settings_change.call(AppSettings {
+                              suppressed_suggestions,
+                              ..settings.clone()
+                          });
+                      }
+                  },
+                  "Suggest again"
+                }
+              }
+            }
+          }
+        }
+
+        // What is stored and where
+        h3 { class: "text-sm font-medium mb-2 {heading_class}", "Your data" }
+        match &*stats.read() {
+            Ok(stats) => rsx! {
+                YourData {
+                    stats: stats.clone(),
+                    last_saved: props.last_saved,
+                    can_open_folder: cfg!(all(feature = "sqlite", not(target_arch = "wasm32"))),
+                    on_open_folder: move |_| {
+                        #[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+                        if let Err(e) = storage::open_data_folder() {
+                            status.set(Some(format!("Could not open the data folder: {}", e)));
+                        }
+                    },
+                    on_reclaim: reclaim_space,
+                    on_delete_all: props.on_delete_all,
+                    is_dark_mode: props.is_dark_mode,
+                }
+            },
+            Err(e) => rsx! {
+                p { class: "mb-4 text-sm {text_class}", "Could not read the stored data: {e:?}" }
+            },
+        }
+
+        // Data integrity
+        div { class: "flex flex-col space-y-2 text-sm {text_class}",
+          div { class: "flex items-center space-x-2",
+            button {
+              r#type: "button",
+              class: "{button_class}",
+              onclick: check_integrity,
+              "Check data integrity"
+            }
+            button {
+              r#type: "button",
+              class: "{button_class}",
+              onclick: move |_| props.on_show_whats_new.call(()),
+              "What's new"
+            }
+            button {
+              r#type: "button",
+              class: "{button_class}",
+              onclick: move |_| props.on_show_debug_log.call(()),
+              "Debug log"
+            }
+            if needs_repair {
+              button {
+                r#type: "button",
+                class: "{repair_button_class}",
+                onclick: repair_data,
+                "Repair"
+              }
+            }
+          }
+          if let Some(message) = status() {
+            p { "{message}" }
+          }
+        }
+
+        {props.children}
+
+        // Profile, each with its own todos and settings
+        h3 { class: "text-sm font-medium mt-4 mb-2 {heading_class}", "Profile" }
+        form {
+          class: "flex flex-wrap items-center gap-2 text-sm {text_class}",
+          onsubmit: move |evt| {
+              evt.prevent_default();
+              if can_switch_profile && let Some(target) = profile_target.clone() {
+                  props.on_switch_profile.call(target);
+              }
+          },
+          input {
+            class: "{select_class} w-40",
+            aria_label: "Profile name",
+            placeholder: DEFAULT_PROFILE,
+            value: "{profile_name}",
+            oninput: move |evt| profile_name.set(evt.value()),
+          }
+          button {
+            r#type: "submit",
+            class: "{button_class}",
+            disabled: !can_switch_profile,
+            "Switch and restart"
+          }
+          if profile_target.is_none() {
+            span { "Use letters, digits, - and _ only." }
+          }
+        }
+
+        // Backup
+        h3 { class: "text-sm font-medium mt-4 mb-2 {heading_class}", "Export & import" }
+        ExportDialog {
+          todos: props.todos.clone(),
+          visible_ids: props.visible_ids.clone(),
+          view_name: props.view_name.clone(),
+          on_import: props.on_import,
+          week_start: props.settings.week_start,
+          is_dark_mode: props.is_dark_mode,
+        }
+      }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::utils::constants::todo::MAX_DATE_SHIFT_DAYS;
+use crate::utils::theme;
+use chrono::{DateTime, Duration, Local, Utc};
+use dioxus::prelude::*;
+
+/// Formats a due date the way the todo items show it.
+fn format_due(due: DateTime<Utc>) -> String {
+    due.with_timezone(&Local).format("%b %d, %Y").to_string()
+}
+
+/// Formats a range of due dates, collapsing it when it is a single day.
+fn format_range((first, last): (DateTime<Utc>, DateTime<Utc>)) -> String {
+    if first.date_naive() == last.date_naive() {
+        format_due(first)
+    } else {
+        format!("{} – {}", format_due(first), format_due(last))
+    }
+}
+
+/// Parses the entered day offset, accepting an explicit `+`.
+///
+/// # Returns
+/// The offset, or `None` if it is not a whole number within
+/// [`MAX_DATE_SHIFT_DAYS`] either way
+fn parse_offset(text: &str) -> Option<i64> {
+    let text = text.trim();
+    let days: i64 = text.strip_prefix('+').unwrap_or(text).parse().ok()?;
+    (days.abs() <= MAX_DATE_SHIFT_DAYS).then_some(days)
+}
+
+/// Dialog moving the due dates of the todos shown by a number of days
+///
+/// Shows the range the due dates span now and where it ends up before the
+/// shift is applied.
+#[component]
+pub fn ShiftDatesDialog(
+    count: usize,
+    range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    on_apply: EventHandler<i64>,
+    on_close: EventHandler<()>,
+    is_dark_mode: bool,
+) -> Element {
+    let mut offset_text = use_signal(|| "7".to_string());
+
+    let card_class = theme::modal_panel_class(is_dark_mode);
+    let input_class = theme::input_class(is_dark_mode);
+    let hint_class = theme::muted_text_class(is_dark_mode);
+
+    let offset = parse_offset(&offset_text());
+    let preview = match (range, offset) {
+        (None, _) => "None of the todos shown has a due date.".to_string(),
+        (Some(_), None) => format!(
+            "Enter a whole number of days, up to {} either way.",
+            MAX_DATE_SHIFT_DAYS
+        ),
+        (Some(range), Some(days)) => {
+            let shifted = (
+                range.0 + Duration::days(days),
+                range.1 + Duration::days(days),
+            );
+            format!(
+                "{} {} due {} → {}",
+                count,
+                if count == 1 { "todo" } else { "todos" },
+                format_range(range),
+                format_range(shifted)
+            )
+        }
+    };
+    let can_apply = range.is_some() && offset.is_some_and(|days| days != 0);
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-40 flex items-center justify-center bg-black/40 p-4",
+            onclick: move |_| on_close.call(()),
+            form {
+                class: "w-full max-w-sm p-5 rounded-lg border shadow-lg {card_class}",
+                role: "dialog",
+                aria_modal: "true",
+                aria_label: "Shift due dates",
+                onclick: move |evt| evt.stop_propagation(),
+                onkeydown: move |evt: Event<KeyboardData>| {
+                    if evt.key() == Key::Escape {
+                        on_close.call(());
+                    }
+                },
+                onsubmit: move |evt| {
+                    evt.prevent_default();
+                    if let (true, Some(days)) = (can_apply, offset) {
+                        on_apply.call(days);
+                    }
+                },
+                h2 { class: "text-lg font-semibold mb-3", "Shift due dates" }
+                label { class: "block text-sm mb-1", r#for: "shift-days", "Days to move (negative moves earlier)" }
+                input {
+                    id: "shift-days",
+                    class: "w-full px-2 py-1 rounded border {input_class}",
+                    r#type: "number",
+                    min: "-{MAX_DATE_SHIFT_DAYS}",
+                    max: "{MAX_DATE_SHIFT_DAYS}",
+                    step: "1",
+                    autofocus: true,
+                    value: "{offset_text}",
+                    oninput: move |evt| offset_text.set(evt.value()),
+                }
+                p { class: "mt-2 text-sm {hint_class}", aria_live: "polite", "{preview}" }
+                div { class: "flex justify-end space-x-2 mt-4",
+                    button {
+                        r#type: "button",
+                        class: "px-3 py-1 rounded text-sm {hint_class} hover:underline",
+                        onclick: move |_| on_close.call(()),
+                        "Cancel"
+                    }
+                    button {
+                        r#type: "submit",
+                        class: "px-3 py-1 rounded bg-blue-500 text-white hover:bg-blue-600 text-sm disabled:opacity-50",
+                        disabled: !can_apply,
+                        "Shift"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_offset() {
+        assert_eq!(parse_offset("7"), Some(7));
+        assert_eq!(parse_offset(" +14 "), Some(14));
+        assert_eq!(parse_offset("-3"), Some(-3));
+        assert_eq!(parse_offset("0"), Some(0));
+        assert_eq!(parse_offset("3650"), Some(3650));
+        assert_eq!(parse_offset("3651"), None);
+        assert_eq!(parse_offset("1.5"), None);
+        assert_eq!(parse_offset(""), None);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::models::Todo;
+use crate::utils::clipboard;
+use crate::utils::share::{self, SnapshotError};
+use crate::utils::theme;
+use chrono::Local;
+use dioxus::prelude::*;
+
+/// Removes the snapshot from the page URL without reloading the page.
+pub fn clear_snapshot_fragment() {
+    let _ = document::eval("history.replaceState(null, '', location.pathname + location.search);");
+}
+
+/// Read-only view of a list shared through a snapshot link
+///
+/// Nothing is written to storage while it is shown; the todos only join the
+/// visitor's own list when they choose to import them.
+#[component]
+pub fn SnapshotView(
+    snapshot: Result<Vec<Todo>, SnapshotError>,
+    on_import: EventHandler<Vec<Todo>>,
+    on_close: EventHandler<()>,
+    is_dark_mode: bool,
+) -> Element {
+    let banner_class = theme::notice_class(theme::Tone::Info, is_dark_mode);
+    let list_class = format!(
+        "{} {}",
+        theme::card_class(is_dark_mode),
+        theme::divide_class(is_dark_mode)
+    );
+    let secondary_text_class = theme::muted_text_class(is_dark_mode);
+    let tag_class = theme::tag_pill_class(is_dark_mode);
+    let primary_button_class = theme::primary_button_class(is_dark_mode);
+    let secondary_button_class = theme::outline_button_class(is_dark_mode);
+
+    let todos = match snapshot {
+        Ok(todos) => todos,
+        Err(error) => {
+            return rsx! {
+                div { class: "mb-4 p-4 rounded-lg border text-sm {banner_class}", role: "alert",
+                    p { class: "mb-3", "{error}" }
+                    button {
+                        r#type: "button",
+                        class: "px-3 py-1 rounded {secondary_button_class}",
+                        onclick: move |_| on_close.call(()),
+                        "Open my list"
+                    }
+                }
+            };
+        }
+    };
+    let count = todos.len();
+    let is_empty = todos.is_empty();
+    let import_all = {
+        let todos = todos.clone();
+        move |_| on_import.call(todos.clone())
+    };
+
+    rsx! {
+        div { class: "mb-4 p-4 rounded-lg border text-sm {banner_class}", role: "status",
+            p { class: "font-semibold mb-1", "Shared snapshot" }
+            p { class: "mb-3",
+                "You're viewing {count} shared todos. This view is read-only and nothing is saved unless you import them."
+            }
+            div { class: "flex gap-2",
+                button {
+                    r#type: "button",
+                    class: primary_button_class,
+                    disabled: is_empty,
+                    onclick: import_all,
+                    "Import into my list"
+                }
+                button {
+                    r#type: "button",
+                    class: "px-3 py-1 rounded {secondary_button_class}",
+                    onclick: move |_| on_close.call(()),
+                    "Open my list"
+                }
+            }
+        }
+        ul { class: "rounded-lg shadow divide-y {list_class}",
+            for todo in todos {
+                li { key: "{todo.id}", class: "flex items-start gap-3 p-4",
+                    span { aria_label: if todo.completed { "Completed" } else { "Open" },
+                        if todo.completed { "☑" } else { "☐" }
+                    }
+                    div { class: "flex-1 min-w-0",
+                        p { class: if todo.completed { "line-through {secondary_text_class} break-words" } else { "break-words" },
+                            "{todo.text}"
+                        }
+                        div { class: "flex flex-wrap items-center gap-1.5 mt-1 text-xs",
+                            if let Some(due) = todo.due_date {
+                                span { class: secondary_text_class,
+                                    "Due: {due.with_timezone(&Local).format(\"%b %d, %Y\")}"
+                                }
+                            }
+                            for tag in todo.tags.iter() {
+                                span { key: "{tag}", class: "px-2 py-0.5 rounded-full {tag_class}", "{tag}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Button copying a read-only snapshot link of the todos shown
+///
+/// `todos` gathers them when the button is clicked, so nothing is copied
+/// while the list merely renders.
+#[component]
+pub fn ShareSnapshotButton(todos: Callback<(), Vec<Todo>>, is_dark_mode: bool) -> Element {
+    let mut status = use_signal(|| None::<Result<String, String>>);
+
+    let share_link = move |_| {
+        let fragment = share::snapshot_fragment(&todos(()));
+        async move {
+            match fragment {
+                Ok(fragment) => {
+                    let base = document::eval("return location.href.split('#')[0];")
+                        .join::<String>()
+                        .await
+                        .unwrap_or_default();
+                    clipboard::write_text(&format!("{}{}", base, fragment));
+                    status.set(Some(Ok(
+                        "Link copied. Anyone with it sees these todos, read-only.".to_string(),
+                    )));
+                }
+                Err(e) => status.set(Some(Err(e.to_string()))),
+            }
+        }
+    };
+
+    let button_class = format!(
+        "{} {}",
+        theme::muted_text_class(is_dark_mode),
+        theme::hover_text_class(theme::Accent::Blue, is_dark_mode)
+    );
+
+    rsx! {
+        div { class: "mt-2 flex flex-wrap items-center justify-end gap-2 text-xs",
+            match status() {
+                Some(Ok(message)) => rsx! {
+                    span { class: "{button_class}", aria_live: "polite", "{message}" }
+                },
+                Some(Err(error)) => rsx! {
+                    span { class: "text-red-500", role: "alert", "{error}" }
+                },
+                None => rsx! {},
+            }
+            button {
+                r#type: "button",
+                class: "{button_class} hover:underline",
+                title: "Copy a read-only link to the todos shown above",
+                onclick: share_link,
+                "🔗 Share snapshot link"
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::Mutations;
+
+    #[test]
+    fn test_snapshot_view_rendering() {
+        let mut app = VirtualDom::new(|| {
+            let mut todo = Todo::new(1, "Shared 🎉".to_string());
+            todo.add_tag("Work".to_string());
+            rsx! {
+                SnapshotView {
+                    snapshot: Ok(vec![todo]),
+                    on_import: |_| {},
+                    on_close: |_| {},
+                    is_dark_mode: false,
+                }
+                SnapshotView {
+                    snapshot: Err(SnapshotError::Invalid("bad data".to_string())),
+                    on_import: |_| {},
+                    on_close: |_| {},
+                    is_dark_mode: true,
+                }
+                ShareSnapshotButton { todos: |_| Vec::new(), is_dark_mode: false }
+            }
+        });
+
+        app.rebuild(&mut Mutations::default());
+    }
+}
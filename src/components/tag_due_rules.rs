@@ -0,0 +1,268 @@
+use crate::models::tag_due::{RelativeDue, TagDueRule, weekday_name};
+use crate::utils::constants::todo::MAX_DATE_SHIFT_DAYS;
+use crate::utils::theme;
+use chrono::Weekday;
+use dioxus::prelude::*;
+
+/// Weekdays offered by the picker, in calendar order.
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// Props for the TagDueRules component.
+#[derive(Props, PartialEq, Clone)]
+pub struct TagDueRulesProps {
+    /// The saved rules
+    pub rules: Vec<TagDueRule>,
+    /// Callback when a rule is added, changed or removed
+    pub on_rules_change: EventHandler<Vec<TagDueRule>>,
+    /// Whether dark mode is enabled
+    #[props(default = false)]
+    pub is_dark_mode: bool,
+}
+
+/// Parses the picker's choice: a number of days, or a weekday like `Sat`.
+fn parse_due(choice: &str, days: &str) -> Option<RelativeDue> {
+    if choice == "days" {
+        let days: u32 = days.trim().parse().ok()?;
+        return (i64::from(days) <= MAX_DATE_SHIFT_DAYS).then_some(RelativeDue::InDays(days));
+    }
+    choice.parse().ok().map(RelativeDue::Weekday)
+}
+
+/// Editor for the rules giving tagged todos a default due date
+#[component]
+pub fn TagDueRules(props: TagDueRulesProps) -> Element {
+    let mut tag = use_signal(String::new);
+    let mut choice = use_signal(|| "days".to_string());
+    let mut days = use_signal(|| "1".to_string());
+    let mut editing_index = use_signal(|| None::<usize>);
+    let mut error = use_signal(|| None::<String>);
+
+    let mut reset = move || {
+        tag.set(String::new());
+        choice.set("days".to_string());
+        days.set("1".to_string());
+        editing_index.set(None);
+        error.set(None);
+    };
+
+    let save_rule = {
+        let rules = props.rules.clone();
+        move |evt: Event<FormData>| {
+            evt.prevent_default();
+            let new_tag = tag.read().trim().to_string();
+            if new_tag.is_empty() {
+                error.set(Some("Enter the tag the rule is for".to_string()));
+                return;
+            }
+            let Some(due) = parse_due(&choice.read(), &days.read()) else {
+                error.set(Some(format!(
+                    "Enter a whole number of days, up to {}",
+                    MAX_DATE_SHIFT_DAYS
+                )));
+                return;
+            };
+
+            let rule = TagDueRule { tag: new_tag, due };
+            let mut rules = rules.clone();
+            match editing_index() {
+                Some(index) if index < rules.len() => rules[index] = rule,
+                _ => rules.push(rule),
+            }
+            props.on_rules_change.call(rules);
+            reset();
+        }
+    };
+
+    let text_class = theme::secondary_text_class(props.is_dark_mode);
+    let input_class = theme::form_control_class(props.is_dark_mode);
+    let button_class = theme::secondary_button_class(props.is_dark_mode);
+    let link_class = theme::link_class(props.is_dark_mode);
+
+    rsx! {
+      div { class: "flex flex-col space-y-2 text-sm {text_class}",
+        if props.rules.is_empty() {
+          p { "No rules yet. Rules give a todo a due date when it is tagged and has none, like Urgent → tomorrow." }
+        }
+        ul { class: "space-y-1",
+          for (index, rule) in props.rules.iter().enumerate() {
+            li { key: "{index}", class: "flex items-center gap-2",
+              span { class: "flex-1 truncate", "{rule.tag} → {rule.due}" }
+              button {
+                r#type: "button",
+                class: "text-xs {link_class}",
+                onclick: {
+                    let rule = rule.clone();
+                    move |_| {
+                        tag.set(rule.tag.clone());
+                        match rule.due {
+                            RelativeDue::InDays(count) => {
+                                choice.set("days".to_string());
+                                days.set(count.to_string());
+                            }
+                            RelativeDue::Weekday(day) => choice.set(day.to_string()),
+                        }
+                        editing_index.set(Some(index));
+                        error.set(None);
+                    }
+                },
+                "Edit"
+              }
+              button {
+                r#type: "button",
+                class: "text-xs {link_class}",
+                onclick: {
+                    let rules = props.rules.clone();
+                    move |_| {
+                        let mut rules = rules.clone();
+                        rules.remove(index);
+                        props.on_rules_change.call(rules);
+                        editing_index.set(None);
+                    }
+                },
+                "Delete"
+              }
+            }
+          }
+        }
+
+        form { class: "flex flex-wrap items-center gap-2", onsubmit: save_rule,
+          input {
+            class: "{input_class} w-28",
+            placeholder: "Tag",
+            aria_label: "Tag",
+            value: "{tag.read()}",
+            oninput: move |evt| tag.set(evt.value()),
+          }
+          span { "due" }
+          select {
+            class: "{input_class}",
+            aria_label: "Due",
+            onchange: move |evt| choice.set(evt.value()),
+            option { value: "days", selected: *choice.read() == "days", "In days" }
+            for day in WEEKDAYS {
+              option {
+                value: "{day}",
+                selected: *choice.read() == day.to_string(),
+                "{weekday_name(day)}"
+              }
+            }
+          }
+          if *choice.read() == "days" {
+            input {
+              class: "{input_class} w-16",
+              r#type: "number",
+              min: "0",
+              max: "{MAX_DATE_SHIFT_DAYS}",
+              aria_label: "Days from today",
+              value: "{days.read()}",
+              oninput: move |evt| days.set(evt.value()),
+            }
+          }
+          button { r#type: "submit", class: button_class,
+            if editing_index().is_some() { "Update rule" } else { "Add rule" }
+          }
+          if editing_index().is_some() {
+            button {
+              r#type: "button",
+              class: "text-xs {link_class}",
+              onclick: move |_| reset(),
+              "Cancel"
+            }
+          }
+        }
+        if let Some(message) = error() {
+          p { class: "text-red-500", role: "alert", "{message}" }
+        }
+      }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Saved = Rc<RefCell<Vec<Vec<TagDueRule>>>>;
+
+    fn render(rules: Vec<TagDueRule>, saved: Saved) -> TestDom {
+        TestDom::with_props(
+            |(rules, saved): (Vec<TagDueRule>, Saved)| {
+                rsx! {
+                    TagDueRules {
+                        rules,
+                        on_rules_change: move |rules| saved.borrow_mut().push(rules),
+                    }
+                }
+            },
+            (rules, saved),
+        )
+    }
+
+    #[test]
+    fn test_parse_due() {
+        assert_eq!(parse_due("days", " 2 "), Some(RelativeDue::InDays(2)));
+        assert_eq!(parse_due("days", "0"), Some(RelativeDue::InDays(0)));
+        assert_eq!(parse_due("days", "-1"), None);
+        assert_eq!(parse_due("days", "3651"), None);
+        assert_eq!(
+            parse_due("Sat", ""),
+            Some(RelativeDue::Weekday(Weekday::Sat))
+        );
+        assert_eq!(parse_due("Someday", ""), None);
+    }
+
+    #[test]
+    fn test_adding_rules_for_days_and_weekdays() {
+        let saved = Saved::default();
+        let urgent = TagDueRule {
+            tag: "Urgent".to_string(),
+            due: RelativeDue::InDays(1),
+        };
+        let mut dom = render(vec![urgent.clone()], saved.clone());
+        assert!(dom.text().contains("Urgent → tomorrow"));
+
+        let tag = dom.find_by_attribute("input", "aria-label", "Tag").unwrap();
+        dom.input(tag, " Groceries ");
+        let due = dom
+            .find_by_attribute("select", "aria-label", "Due")
+            .unwrap();
+        dom.change(due, "Sat");
+        assert!(
+            dom.find_by_attribute("input", "aria-label", "Days from today")
+                .is_none()
+        );
+        dom.submit(dom.first("form").unwrap());
+
+        assert_eq!(
+            *saved.borrow(),
+            vec![vec![
+                urgent,
+                TagDueRule {
+                    tag: "Groceries".to_string(),
+                    due: RelativeDue::Weekday(Weekday::Sat),
+                },
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_rules_need_a_tag() {
+        let saved = Saved::default();
+        let mut dom = render(Vec::new(), saved.clone());
+
+        dom.submit(dom.first("form").unwrap());
+
+        assert!(saved.borrow().is_empty());
+        assert!(dom.text().contains("Enter the tag the rule is for"));
+    }
+}
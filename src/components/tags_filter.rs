@@ -1,15 +1,45 @@
+use crate::components::theme_manager::use_motion;
+use crate::models::{TagFilter, TodoList};
+use crate::utils::theme;
 use dioxus::prelude::*;
 
 /// Component for filtering todos by tags
+///
+/// Offers the tags in use together with `default_tags`, and "Untagged" for
+/// the todos without any. The tags are gathered
+/// when the filter first renders and again only when the todos change, not
+/// on every render of the app.
+///
+/// Tags some todo carries can be deleted from every todo with the ✕ next to
+/// them, which asks first since it may touch many todos. Deleting the
+/// selected tag selects "All" again.
 #[component]
 pub fn TagsFilter(
-    tags: Vec<String>,
-    selected_tag: Option<String>,
-    on_select_tag: EventHandler<Option<String>>,
+    todo_list: ReadOnlySignal<TodoList>,
+    default_tags: &'static [&'static str],
+    selected_tag: TagFilter,
+    on_select_tag: EventHandler<TagFilter>,
+    on_delete_tag: EventHandler<String>,
     is_dark_mode: bool,
 ) -> Element {
-    let text_secondary_class = "text-gray-600 dark:text-gray-400";
-    let border_class = "border-gray-200 dark:border-gray-700";
+    let text_secondary_class = theme::secondary_text_class(is_dark_mode);
+    let border_class = theme::border_class(is_dark_mode);
+    let selected_ring_class = format!(
+        "ring-2 ring-blue-500 ring-offset-1 {}",
+        theme::ring_offset_class(is_dark_mode)
+    );
+
+    let motion_class = use_motion("transition-colors");
+    let all_motion_class = use_motion("transition-all");
+    let tag_motion_class = use_motion("transition-opacity");
+
+    let tags = use_memo(move || todo_list.read().sorted_tags(default_tags));
+    let tags = tags();
+    let in_use = use_memo(move || todo_list.read().all_tags());
+    let in_use = in_use();
+    // The tag waiting for its deletion to be confirmed
+    let mut deleting = use_signal(|| None::<String>);
+    let delete_class = theme::hover_text_class(theme::Accent::Red, is_dark_mode);
 
     // Only render if there are tags
     if tags.is_empty() {
@@ -20,32 +50,51 @@ pub fn TagsFilter(
 
     rsx! {
         div { class: "mt-6 mb-4 flex flex-wrap items-center {border_class} border-b pb-4",
-            span { class: "mr-2 text-sm font-medium {text_secondary_class} transition-colors",
+            span { class: "mr-2 text-sm font-medium {text_secondary_class} {motion_class}",
                 "Filter by tag:"
             }
 
             // "All" tag option
             {
-                let all_base_class = "text-xs px-3 py-1 rounded-full mr-1.5 mb-1.5 hover:opacity-80 transition-all border";
-                let (all_bg_text, all_border) = if is_dark_mode {
-                    ("bg-gray-700 text-gray-200", "border-gray-600")
-                } else {
-                    ("bg-gray-200 text-gray-700", "border-gray-300")
-                };
-                let all_selected_class = if selected_tag.is_none() {
-                    " ring-2 ring-blue-500 ring-offset-1 dark:ring-offset-gray-900"
+                let all_base_class = "text-xs px-3 py-1 rounded-full mr-1.5 mb-1.5 hover:opacity-80 border";
+                let all_bg_text = theme::tint_class(theme::Accent::Gray, is_dark_mode);
+                let all_border = border_class;
+                let all_selected_class = if selected_tag == TagFilter::All {
+                    selected_ring_class.as_str()
                 } else {
                     ""
                 };
                 let final_all_class = format!(
-                    "{} {} {} {}",
+                    "{} {} {} {} {}",
                     all_base_class,
+                    all_motion_class,
                     all_bg_text,
                     all_border,
                     all_selected_class,
                 );
                 rsx! {
-                    button { class: "{final_all_class}", onclick: move |_| on_select_tag.call(None), "All" }
+                    button { class: "{final_all_class}", onclick: move |_| on_select_tag.call(TagFilter::All), "All" }
+                }
+            }
+
+            // "Untagged" option, for todos not sorted into any tag yet
+            {
+                let is_selected = selected_tag == TagFilter::Untagged;
+                let untagged_class = format!(
+                    "text-xs px-3 py-1 rounded-full mr-1.5 mb-1.5 hover:opacity-80 border italic {} {} {} {}",
+                    all_motion_class,
+                    theme::tint_class(theme::Accent::Gray, is_dark_mode),
+                    border_class,
+                    if is_selected { selected_ring_class.as_str() } else { "" },
+                );
+                rsx! {
+                    button {
+                        class: "{untagged_class}",
+                        onclick: move |_| {
+                            on_select_tag.call(if is_selected { TagFilter::All } else { TagFilter::Untagged })
+                        },
+                        "Untagged"
+                    }
                 }
             }
 
@@ -54,41 +103,161 @@ pub fn TagsFilter(
                 tags.iter()
                     .map(|tag| {
                         let tag_clone = tag.clone();
-                        let is_selected = selected_tag.as_ref() == Some(tag);
-                        let base_tag_class = "text-xs px-3 py-1 rounded-full mr-1.5 mb-1.5 hover:opacity-80 transition-opacity border";
-                        let (tag_bg_text, tag_border) = if is_dark_mode {
-                            ("bg-blue-900 text-blue-200", "border-blue-700")
-                        } else {
-                            ("bg-blue-100 text-blue-800", "border-blue-300")
-                        };
+                        let is_selected = selected_tag == TagFilter::Tag(tag.clone());
+                        // The chip's wrapper spaces it, with its delete button
+                        let base_tag_class = "text-xs px-3 py-1 rounded-full hover:opacity-80 border";
+                        let tag_bg_text = theme::tint_class(theme::Accent::Blue, is_dark_mode);
+                        let tag_border = border_class;
                         let selected_class = if is_selected {
-                            " ring-2 ring-blue-500 ring-offset-1 dark:ring-offset-gray-900"
+                            selected_ring_class.as_str()
                         } else {
                             ""
                         };
                         let final_tag_class = format!(
-                            "{} {} {} {}",
+                            "{} {} {} {} {}",
                             base_tag_class,
+                            tag_motion_class,
                             tag_bg_text,
                             tag_border,
                             selected_class,
                         );
+                        let deletable = in_use.contains(tag);
+                        let tag_to_delete = tag.clone();
                         rsx! {
-                            button {
-                                key: "{tag_clone}", // Use the tag itself as key
-                                class: "{final_tag_class}",
-                                onclick: move |_| {
-                                    if is_selected {
-                                        on_select_tag.call(None);
-                                    } else {
-                                        on_select_tag.call(Some(tag_clone.clone()));
+                            span { key: "{tag}", class: "inline-flex items-center mr-1.5 mb-1.5",
+                                button {
+                                    class: "{final_tag_class}",
+                                    onclick: move |_| {
+                                        if is_selected {
+                                            on_select_tag.call(TagFilter::All);
+                                        } else {
+                                            on_select_tag.call(TagFilter::Tag(tag_clone.clone()));
+                                        }
+                                    },
+                                    "{tag}"
+                                }
+                                if deletable {
+                                    button {
+                                        r#type: "button",
+                                        class: "ml-0.5 px-1 text-xs {text_secondary_class} {delete_class}",
+                                        aria_label: "Delete tag {tag}",
+                                        title: "Remove this tag from every todo",
+                                        onclick: move |_| deleting.set(Some(tag_to_delete.clone())),
+                                        "✕"
                                     }
-                                },
-                                "{tag}"
+                                }
                             }
                         }
                     })
             }
+
+            // Deleting a tag strips it from every todo, so it asks first
+            if let Some(tag) = deleting() {
+                div { class: "w-full flex items-center gap-2 mt-1 text-sm {text_secondary_class}",
+                    span { "Remove “{tag}” from every todo? The todos stay." }
+                    button {
+                        r#type: "button",
+                        class: "font-medium {delete_class}",
+                        onclick: move |_| {
+                            deleting.set(None);
+                            if selected_tag == TagFilter::Tag(tag.clone()) {
+                                on_select_tag.call(TagFilter::All);
+                            }
+                            on_delete_tag.call(tag.clone());
+                        },
+                        "Delete tag"
+                    }
+                    button {
+                        r#type: "button",
+                        class: "hover:underline",
+                        onclick: move |_| deleting.set(None),
+                        "Cancel"
+                    }
+                }
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::{NodeRef, TestDom};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Deleted = Rc<RefCell<Vec<String>>>;
+    type Selected = Rc<RefCell<Vec<TagFilter>>>;
+
+    /// Renders the filter for a todo tagged "home" and "Work", with "Work"
+    /// and "Personal" as default tags, recording the tags selected and
+    /// deleted.
+    fn filter(selected_tag: TagFilter) -> (TestDom, Selected, Deleted) {
+        let selected = Selected::default();
+        let deleted = Deleted::default();
+        let dom = TestDom::with_props(
+            move |(selected_tag, selected, deleted): (TagFilter, Selected, Deleted)| {
+                let todo_list = use_signal(|| {
+                    let mut list = TodoList::new();
+                    let id = list.add("Call the plumber".to_string());
+                    list.add_tag(id, "home".to_string());
+                    list.add_tag(id, "Work".to_string());
+                    list
+                });
+                rsx! {
+                    TagsFilter {
+                        todo_list,
+                        default_tags: &["Work", "Personal"],
+                        selected_tag,
+                        on_select_tag: move |tag| selected.borrow_mut().push(tag),
+                        on_delete_tag: move |tag| deleted.borrow_mut().push(tag),
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            (selected_tag, selected.clone(), deleted.clone()),
+        );
+        (dom, selected, deleted)
+    }
+
+    /// Finds the ✕ deleting a tag.
+    fn delete_button(dom: &TestDom, tag: &str) -> Option<NodeRef> {
+        dom.find_by_attribute("button", "aria-label", &format!("Delete tag {tag}"))
+    }
+
+    #[test]
+    fn test_deleting_a_tag_asks_first() {
+        let (mut dom, selected, deleted) = filter(TagFilter::All);
+        assert!(
+            delete_button(&dom, "Work").is_some(),
+            "a default tag in use"
+        );
+        assert!(delete_button(&dom, "Personal").is_none(), "no todo has it");
+
+        dom.click(delete_button(&dom, "home").unwrap());
+        assert!(dom.text().contains("Remove “home” from every todo?"));
+        dom.click(dom.find("button", "Cancel").unwrap());
+        assert!(!dom.text().contains("from every todo?"));
+        assert!(deleted.borrow().is_empty());
+
+        dom.click(delete_button(&dom, "home").unwrap());
+        dom.click(dom.find("button", "Delete tag").unwrap());
+        assert_eq!(*deleted.borrow(), ["home"]);
+        assert!(selected.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_deleting_the_selected_tag_selects_all() {
+        let (mut dom, selected, deleted) = filter(TagFilter::Tag("home".to_string()));
+
+        // Another tag leaves the selection alone
+        dom.click(delete_button(&dom, "Work").unwrap());
+        dom.click(dom.find("button", "Delete tag").unwrap());
+        assert!(selected.borrow().is_empty());
+
+        dom.click(delete_button(&dom, "home").unwrap());
+        dom.click(dom.find("button", "Delete tag").unwrap());
+        assert_eq!(*selected.borrow(), [TagFilter::All]);
+        assert_eq!(*deleted.borrow(), ["Work", "home"]);
+    }
+}
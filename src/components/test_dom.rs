@@ -0,0 +1,415 @@
+//! A small in-memory DOM for component tests
+//!
+//! [`TestDom`] applies the edits a `VirtualDom` writes to a plain tree of
+//! elements and text, so a test can look up what is on screen by tag, text or
+//! attribute and fire events at it the way a browser would. Events go through
+//! the `VirtualDom` like real ones, and the tree is updated with the
+//! resulting edits before the call returns.
+
+use dioxus::dioxus_core::{
+    AttributeValue, ElementId, Template, TemplateAttribute, TemplateNode, WriteMutations,
+};
+use dioxus::html::{
+    PlatformEventData, SerializedFormData, SerializedHtmlEventConverter, SerializedKeyboardData,
+    SerializedMouseData, set_event_converter,
+};
+use dioxus::prelude::*;
+use std::any::Any;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Index of a node in the tree.
+pub(crate) type NodeRef = usize;
+
+#[derive(Debug)]
+enum NodeKind {
+    Element {
+        tag: &'static str,
+        attributes: Vec<(String, String)>,
+    },
+    Text(String),
+    Placeholder,
+}
+
+#[derive(Debug)]
+struct Node {
+    kind: NodeKind,
+    parent: Option<NodeRef>,
+    children: Vec<NodeRef>,
+    id: Option<ElementId>,
+}
+
+/// The tree the edits are written to.
+#[derive(Default)]
+struct Tree {
+    nodes: Vec<Node>,
+    ids: HashMap<ElementId, NodeRef>,
+    stack: Vec<NodeRef>,
+}
+
+impl Tree {
+    fn new() -> Self {
+        let mut tree = Self::default();
+        let root = tree.add(NodeKind::Element {
+            tag: "body",
+            attributes: Vec::new(),
+        });
+        tree.set_id(root, ElementId(0));
+        tree
+    }
+
+    fn add(&mut self, kind: NodeKind) -> NodeRef {
+        self.nodes.push(Node {
+            kind,
+            parent: None,
+            children: Vec::new(),
+            id: None,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn set_id(&mut self, node: NodeRef, id: ElementId) {
+        self.nodes[node].id = Some(id);
+        self.ids.insert(id, node);
+    }
+
+    fn node(&self, id: ElementId) -> NodeRef {
+        self.ids[&id]
+    }
+
+    fn build(&mut self, template: &TemplateNode) -> NodeRef {
+        match template {
+            TemplateNode::Element {
+                tag,
+                attrs,
+                children,
+                ..
+            } => {
+                let attributes = attrs
+                    .iter()
+                    .filter_map(|attr| match attr {
+                        TemplateAttribute::Static { name, value, .. } => {
+                            Some((name.to_string(), value.to_string()))
+                        }
+                        TemplateAttribute::Dynamic { .. } => None,
+                    })
+                    .collect();
+                let element = self.add(NodeKind::Element { tag, attributes });
+                for child in children.iter() {
+                    let child = self.build(child);
+                    self.append(element, child);
+                }
+                element
+            }
+            TemplateNode::Text { text } => self.add(NodeKind::Text(text.to_string())),
+            TemplateNode::Dynamic { .. } => self.add(NodeKind::Placeholder),
+        }
+    }
+
+    fn detach(&mut self, node: NodeRef) {
+        if let Some(parent) = self.nodes[node].parent.take() {
+            self.nodes[parent].children.retain(|child| *child != node);
+        }
+    }
+
+    fn append(&mut self, parent: NodeRef, child: NodeRef) {
+        self.detach(child);
+        self.nodes[child].parent = Some(parent);
+        self.nodes[parent].children.push(child);
+    }
+
+    /// Puts `nodes` where `target` is (offset 0), or just before or after it.
+    fn insert_at(&mut self, target: NodeRef, nodes: Vec<NodeRef>, after: bool) {
+        let parent = self.nodes[target].parent.expect("node is in the tree");
+        for node in &nodes {
+            self.detach(*node);
+            self.nodes[*node].parent = Some(parent);
+        }
+        let index = self.nodes[parent]
+            .children
+            .iter()
+            .position(|child| *child == target)
+            .expect("node is a child of its parent");
+        let index = if after { index + 1 } else { index };
+        self.nodes[parent].children.splice(index..index, nodes);
+    }
+
+    fn replace(&mut self, target: NodeRef, nodes: Vec<NodeRef>) {
+        self.insert_at(target, nodes, false);
+        self.detach(target);
+    }
+
+    fn pop(&mut self, count: usize) -> Vec<NodeRef> {
+        let start = self.stack.len() - count;
+        self.stack.split_off(start)
+    }
+
+    fn at_path(&self, path: &[u8]) -> NodeRef {
+        let mut node = *self.stack.last().expect("a node is on the stack");
+        for index in path {
+            node = self.nodes[node].children[*index as usize];
+        }
+        node
+    }
+
+    fn attributes_mut(&mut self, node: NodeRef) -> &mut Vec<(String, String)> {
+        match &mut self.nodes[node].kind {
+            NodeKind::Element { attributes, .. } => attributes,
+            _ => panic!("only elements have attributes"),
+        }
+    }
+}
+
+impl WriteMutations for Tree {
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        let parent = self.node(id);
+        for child in self.pop(m) {
+            self.append(parent, child);
+        }
+    }
+
+    fn assign_node_id(&mut self, path: &'static [u8], id: ElementId) {
+        let node = self.at_path(path);
+        self.set_id(node, id);
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        let node = self.add(NodeKind::Placeholder);
+        self.set_id(node, id);
+        self.stack.push(node);
+    }
+
+    fn create_text_node(&mut self, value: &str, id: ElementId) {
+        let node = self.add(NodeKind::Text(value.to_string()));
+        self.set_id(node, id);
+        self.stack.push(node);
+    }
+
+    fn load_template(&mut self, template: Template, index: usize, id: ElementId) {
+        let node = self.build(&template.roots[index]);
+        self.set_id(node, id);
+        self.stack.push(node);
+    }
+
+    fn replace_node_with(&mut self, id: ElementId, m: usize) {
+        let target = self.node(id);
+        let nodes = self.pop(m);
+        self.replace(target, nodes);
+    }
+
+    fn replace_placeholder_with_nodes(&mut self, path: &'static [u8], m: usize) {
+        let nodes = self.pop(m);
+        let target = self.at_path(path);
+        self.replace(target, nodes);
+    }
+
+    fn insert_nodes_after(&mut self, id: ElementId, m: usize) {
+        let target = self.node(id);
+        let nodes = self.pop(m);
+        self.insert_at(target, nodes, true);
+    }
+
+    fn insert_nodes_before(&mut self, id: ElementId, m: usize) {
+        let target = self.node(id);
+        let nodes = self.pop(m);
+        self.insert_at(target, nodes, false);
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &'static str,
+        _ns: Option<&'static str>,
+        value: &AttributeValue,
+        id: ElementId,
+    ) {
+        let node = self.node(id);
+        let value = match value {
+            AttributeValue::Text(text) => Some(text.clone()),
+            AttributeValue::Float(number) => Some(number.to_string()),
+            AttributeValue::Int(number) => Some(number.to_string()),
+            AttributeValue::Bool(flag) => Some(flag.to_string()),
+            _ => None,
+        };
+        let attributes = self.attributes_mut(node);
+        attributes.retain(|(existing, _)| existing != name);
+        if let Some(value) = value {
+            attributes.push((name.to_string(), value));
+        }
+    }
+
+    fn set_node_text(&mut self, value: &str, id: ElementId) {
+        let node = self.node(id);
+        self.nodes[node].kind = NodeKind::Text(value.to_string());
+    }
+
+    fn create_event_listener(&mut self, _name: &'static str, _id: ElementId) {}
+
+    fn remove_event_listener(&mut self, _name: &'static str, _id: ElementId) {}
+
+    fn remove_node(&mut self, id: ElementId) {
+        let node = self.node(id);
+        self.detach(node);
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        let node = self.node(id);
+        self.stack.push(node);
+    }
+}
+
+/// A rendered component that events can be fired at.
+pub(crate) struct TestDom {
+    vdom: VirtualDom,
+    tree: Tree,
+}
+
+impl TestDom {
+    /// Renders a component with props.
+    pub(crate) fn with_props<P: Clone + 'static, M: 'static>(
+        app: impl dioxus::dioxus_core::ComponentFunction<P, M>,
+        props: P,
+    ) -> Self {
+        Self::from_vdom(VirtualDom::new_with_props(app, props))
+    }
+
+    fn from_vdom(mut vdom: VirtualDom) -> Self {
+        set_event_converter(Box::new(SerializedHtmlEventConverter));
+        let mut tree = Tree::new();
+        vdom.rebuild(&mut tree);
+        Self { vdom, tree }
+    }
+
+    /// Gets the text shown, with the text of each element run together.
+    pub(crate) fn text(&self) -> String {
+        self.text_of(0)
+    }
+
+    /// Gets the text shown inside a node.
+    pub(crate) fn text_of(&self, node: NodeRef) -> String {
+        match &self.tree.nodes[node].kind {
+            NodeKind::Text(text) => text.clone(),
+            NodeKind::Placeholder => String::new(),
+            NodeKind::Element { .. } => self.tree.nodes[node]
+                .children
+                .iter()
+                .map(|child| self.text_of(*child))
+                .collect(),
+        }
+    }
+
+    /// Gets an attribute of an element, if it is set.
+    pub(crate) fn attribute(&self, node: NodeRef, name: &str) -> Option<&str> {
+        match &self.tree.nodes[node].kind {
+            NodeKind::Element { attributes, .. } => attributes
+                .iter()
+                .find(|(existing, _)| existing == name)
+                .map(|(_, value)| value.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Gets the class attribute of every element on screen that has one.
+    pub(crate) fn classes(&self) -> Vec<&str> {
+        let mut order = Vec::new();
+        self.walk(0, &mut order);
+        order
+            .into_iter()
+            .filter_map(|node| self.attribute(node, "class"))
+            .collect()
+    }
+
+    /// Finds every element on screen with a tag whose text, trimmed, is `text`.
+    pub(crate) fn find_all(&self, tag: &str, text: &str) -> Vec<NodeRef> {
+        self.elements(tag)
+            .filter(|node| self.text_of(*node).trim() == text)
+            .collect()
+    }
+
+    /// Finds the element on screen with a tag whose text, trimmed, is `text`.
+    pub(crate) fn find(&self, tag: &str, text: &str) -> Option<NodeRef> {
+        self.find_all(tag, text).into_iter().next()
+    }
+
+    /// Finds the first element on screen with a tag and an attribute set to `value`.
+    pub(crate) fn find_by_attribute(&self, tag: &str, name: &str, value: &str) -> Option<NodeRef> {
+        self.elements(tag)
+            .find(|node| self.attribute(*node, name) == Some(value))
+    }
+
+    /// Finds the first element on screen with a tag.
+    pub(crate) fn first(&self, tag: &str) -> Option<NodeRef> {
+        self.elements(tag).next()
+    }
+
+    /// Elements on screen with a tag, in document order.
+    fn elements<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = NodeRef> + 'a {
+        let mut order = Vec::new();
+        self.walk(0, &mut order);
+        order.into_iter().filter(move |node| {
+            matches!(self.tree.nodes[*node].kind, NodeKind::Element { tag: found, .. } if found == tag)
+        })
+    }
+
+    fn walk(&self, node: NodeRef, order: &mut Vec<NodeRef>) {
+        order.push(node);
+        for child in &self.tree.nodes[node].children {
+            self.walk(*child, order);
+        }
+    }
+
+    /// Clicks an element.
+    pub(crate) fn click(&mut self, node: NodeRef) {
+        self.fire(node, "click", SerializedMouseData::default());
+    }
+
+    /// Types a new value into an input, firing `input`.
+    pub(crate) fn input(&mut self, node: NodeRef, value: &str) {
+        self.fire(node, "input", form_data(value));
+    }
+
+    /// Commits a new value to an input, firing `change`.
+    pub(crate) fn change(&mut self, node: NodeRef, value: &str) {
+        self.fire(node, "change", form_data(value));
+    }
+
+    /// Submits a form.
+    pub(crate) fn submit(&mut self, node: NodeRef) {
+        self.fire(node, "submit", form_data(""));
+    }
+
+    /// Presses a key on an element.
+    pub(crate) fn key_down(&mut self, node: NodeRef, key: Key) {
+        let data = SerializedKeyboardData::new(
+            key,
+            Code::Unidentified,
+            Location::Standard,
+            false,
+            Modifiers::empty(),
+            false,
+        );
+        self.fire(node, "keydown", data);
+    }
+
+    /// Fires an event at the nearest element with an id, bubbling up from
+    /// there, and applies the edits it causes.
+    fn fire(&mut self, node: NodeRef, name: &str, data: impl Any) {
+        let mut target = node;
+        let id = loop {
+            if let Some(id) = self.tree.nodes[target].id {
+                break id;
+            }
+            target = self.tree.nodes[target]
+                .parent
+                .expect("an ancestor has an id");
+        };
+        let event: Rc<dyn Any> = Rc::new(PlatformEventData::new(Box::new(data)));
+        self.vdom
+            .runtime()
+            .handle_event(name, Event::new(event, true), id);
+        self.vdom.render_immediate(&mut self.tree);
+    }
+}
+
+fn form_data(value: &str) -> SerializedFormData {
+    SerializedFormData::new(value.to_string(), HashMap::new())
+}
@@ -0,0 +1,40 @@
+use crate::components::debounce::use_debounced;
+use crate::utils::constants::ui::TEXT_STATS_DEBOUNCE_MS;
+use crate::utils::text;
+use crate::utils::theme;
+use dioxus::prelude::*;
+use std::time::Duration;
+
+/// Line of live character and word counts below an editor
+///
+/// The counts follow the text once typing pauses, and long text also shows
+/// an estimated reading time.
+#[component]
+pub fn TextStatsLine(text: String, is_dark_mode: bool) -> Element {
+    let settled = use_debounced(text, Duration::from_millis(TEXT_STATS_DEBOUNCE_MS));
+    let stats = text::text_stats(&settled);
+    let text_class = theme::secondary_text_class(is_dark_mode);
+
+    rsx! {
+      span { class: "text-xs tabular-nums {text_class}", aria_live: "polite", "{stats}" }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+
+    #[test]
+    fn test_shows_the_counts_of_the_text() {
+        let dom = TestDom::with_props(
+            |text: String| {
+                rsx! {
+                    TextStatsLine { text, is_dark_mode: false }
+                }
+            },
+            "Call the plumber 🔧".to_string(),
+        );
+        assert_eq!(dom.text(), "18 characters · 3 words");
+    }
+}
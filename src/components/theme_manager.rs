@@ -1,8 +1,7 @@
 use crate::utils;
+use crate::utils::keys;
 use dioxus::prelude::*;
 
-const THEME_STORAGE_KEY: &str = "dioxus-todo-app-theme";
-
 #[cfg(target_arch = "wasm32")]
 use web_sys::window;
 
@@ -10,7 +9,7 @@ use web_sys::window;
 pub fn use_theme_manager() -> (Signal<bool>, impl FnMut(()) + Clone) {
     let mut is_dark_mode = use_signal(|| {
         // Try to load from localStorage first
-        if let Ok(theme) = utils::load::<String>(THEME_STORAGE_KEY) {
+        if let Ok(theme) = utils::load::<String>(keys::THEME) {
             return theme == "dark";
         }
 
@@ -30,7 +29,7 @@ pub fn use_theme_manager() -> (Signal<bool>, impl FnMut(()) + Clone) {
     // Save theme preference whenever it changes and update HTML class
     use_effect(move || {
         let theme = if is_dark_mode() { "dark" } else { "light" };
-        let _ = utils::save(THEME_STORAGE_KEY, &theme);
+        let _ = utils::save(keys::THEME, &theme);
 
         #[cfg(target_arch = "wasm32")]
         // Also update the html class for Tailwind dark mode selector
@@ -53,3 +52,57 @@ pub fn use_theme_manager() -> (Signal<bool>, impl FnMut(()) + Clone) {
 
     (is_dark_mode, toggle_theme)
 }
+
+/// Theme state shared with every component as context
+#[derive(Clone, Copy)]
+pub struct ThemeContext {
+    /// Whether transitions and animations play
+    pub animations_enabled: Memo<bool>,
+}
+
+/// Reports whether the system asks for reduced motion, now and on every change.
+const WATCH_REDUCED_MOTION: &str = r#"
+    const query = window.matchMedia("(prefers-reduced-motion: reduce)");
+    query.addEventListener("change", () => dioxus.send(query.matches));
+    dioxus.send(query.matches);
+    await new Promise(() => {});
+"#;
+
+/// Logic tracking the system's `prefers-reduced-motion` setting
+///
+/// Listens to the media query in the webview, so it works the same on web and
+/// desktop. Stays `false` where there is no webview, such as in tests.
+pub fn use_prefers_reduced_motion() -> Signal<bool> {
+    let mut reduced = use_signal(|| false);
+
+    use_hook(move || {
+        spawn(async move {
+            let mut eval = document::eval(WATCH_REDUCED_MOTION);
+            while let Ok(matches) = eval.recv::<bool>().await {
+                if *reduced.peek() != matches {
+                    reduced.set(matches);
+                }
+            }
+        });
+    });
+
+    reduced
+}
+
+/// Logic for reading whether animations play
+///
+/// Returns the flag from the [`ThemeContext`] provided by an ancestor, or
+/// `true` without one. Components take their transition classes from
+/// [`use_motion`].
+pub fn use_animations() -> bool {
+    let theme = use_hook(try_consume_context::<ThemeContext>);
+    theme.is_none_or(|theme| (theme.animations_enabled)())
+}
+
+/// Logic for the transition or animation classes of a component
+///
+/// Returns `classes` through [`theme::motion`](crate::utils::theme::motion),
+/// so the animating ones are left out when animations are off.
+pub fn use_motion(classes: &str) -> String {
+    crate::utils::theme::motion(classes, use_animations())
+}
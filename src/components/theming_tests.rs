@@ -0,0 +1,192 @@
+//! Checks that components take their colors from the theme tokens
+//!
+//! Tailwind's `dark:` variants follow the system setting rather than the
+//! app's theme toggle, so a component using them looks wrong whenever the two
+//! disagree. Each component here is rendered in both themes and its classes
+//! are checked for leftover `dark:` variants.
+
+use crate::components::test_dom::TestDom;
+use crate::components::todo_item::TodoItem;
+use crate::components::{
+    AppHeader, FilterBar, Modal, QuietModeMenu, SearchBox, TagsFilter, TodoForm,
+};
+use crate::models::quiet::QuietMode;
+use crate::models::{TagFilter, Todo, TodoList};
+use crate::utils::autosave::SaveStatus;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Asserts no class on screen uses a `dark:` variant.
+fn assert_themed(name: &str, dom: &TestDom) {
+    let leftovers: Vec<&str> = dom
+        .classes()
+        .into_iter()
+        .filter(|class| class.split_whitespace().any(|c| c.contains("dark:")))
+        .collect();
+    assert!(
+        leftovers.is_empty(),
+        "{name} still uses dark: classes: {leftovers:?}"
+    );
+}
+
+/// Asserts a component renders with no `dark:` classes and differs between themes.
+fn check(name: &str, render: fn(bool) -> TestDom) {
+    let (light, dark) = (render(false), render(true));
+    assert_themed(name, &light);
+    assert_themed(name, &dark);
+    assert_ne!(
+        light.classes(),
+        dark.classes(),
+        "{name} looks the same in both themes"
+    );
+}
+
+#[test]
+fn test_header_follows_the_theme() {
+    check("AppHeader", |is_dark_mode| {
+        let mut dom = TestDom::with_props(
+            |is_dark_mode: bool| {
+                rsx! {
+                    AppHeader {
+                        title: "Todos",
+                        is_dark_mode,
+                        on_toggle_theme: |_| {},
+                        on_toggle_settings: |_| {},
+                        save_status: SaveStatus::Unsaved,
+                        on_save: |_| {},
+                        profile: Some("Work".to_string()),
+                        QuietModeMenu {
+                            quiet_mode: QuietMode::default(),
+                            on_change: |_| {},
+                            is_dark_mode,
+                        }
+                    }
+                }
+            },
+            is_dark_mode,
+        );
+        // Open the quiet mode menu so its entries are checked too
+        dom.click(dom.find("button", "☾").unwrap());
+        dom
+    });
+}
+
+#[test]
+fn test_todo_row_follows_the_theme() {
+    check("TodoItem", |is_dark_mode| {
+        let mut todo = Todo::new(1, "Call the plumber".to_string());
+        todo.tags = vec!["home".to_string(), "urgent".to_string()];
+        TestDom::with_props(
+            |(todo, is_dark_mode): (Todo, bool)| {
+                rsx! {
+                    TodoItem {
+                        todo: Rc::new(todo),
+                        on_toggle: |_| {},
+                        on_delete: |_| {},
+                        on_restore: |_| {},
+                        on_update: |_| {},
+                        on_due_date_change: |_| {},
+                        on_tag_add: |_| {},
+                        on_tag_remove: |_| {},
+                        on_notes_change: |_| {},
+                        on_extract: |_| {},
+                        on_reveal: |_| {},
+                        on_lock_change: |_| {},
+                        is_dark_mode,
+                    }
+                }
+            },
+            (todo, is_dark_mode),
+        )
+    });
+}
+
+#[test]
+fn test_form_and_search_follow_the_theme() {
+    check("TodoForm", |is_dark_mode| {
+        TestDom::with_props(
+            |is_dark_mode: bool| {
+                rsx! {
+                    TodoForm { on_add: |_| {}, is_dark_mode }
+                }
+            },
+            is_dark_mode,
+        )
+    });
+    check("SearchBox", |is_dark_mode| {
+        TestDom::with_props(
+            |is_dark_mode: bool| {
+                rsx! {
+                    SearchBox { search_term: "milk", on_search: |_| {}, is_dark_mode }
+                }
+            },
+            is_dark_mode,
+        )
+    });
+}
+
+#[test]
+fn test_filters_follow_the_theme() {
+    check("FilterBar", |is_dark_mode| {
+        TestDom::with_props(
+            |is_dark_mode: bool| {
+                rsx! {
+                    FilterBar {
+                        filter: Default::default(),
+                        active_count: 2,
+                        completed_count: 1,
+                        visible_completed_count: 1,
+                        on_filter_change: |_| {},
+                        on_clear_completed: |_| {},
+                        on_clear_all_completed: |_| {},
+                        on_archive_completed: |_| {},
+                        on_shift_dates: |_| {},
+                        trash_count: 1,
+                        on_empty_trash: |_| {},
+                        is_dark_mode,
+                    }
+                }
+            },
+            is_dark_mode,
+        )
+    });
+    check("TagsFilter", |is_dark_mode| {
+        TestDom::with_props(
+            |is_dark_mode: bool| {
+                let todo_list = use_signal(|| {
+                    let mut list = TodoList::new();
+                    let id = list.add("Buy milk".to_string());
+                    list.add_tag(id, "shopping".to_string());
+                    list
+                });
+                rsx! {
+                    TagsFilter {
+                        todo_list,
+                        default_tags: &["Work"],
+                        selected_tag: TagFilter::Tag("shopping".to_string()),
+                        on_select_tag: |_| {},
+                        on_delete_tag: |_| {},
+                        is_dark_mode,
+                    }
+                }
+            },
+            is_dark_mode,
+        )
+    });
+}
+
+#[test]
+fn test_dialogs_follow_the_theme() {
+    check("Modal", |is_dark_mode| {
+        TestDom::with_props(
+            |is_dark_mode: bool| {
+                rsx! {
+                    Modal { title: "Rename", on_close: |_| {}, is_dark_mode,
+                        p { "Inside" }
+                    }
+                }
+            },
+            is_dark_mode,
+        )
+    });
+}
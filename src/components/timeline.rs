@@ -0,0 +1,278 @@
+use crate::components::theme_manager::use_motion;
+use crate::models::Todo;
+use crate::models::timeline::{self, TimelineAxis};
+use crate::utils::constants::ui::{TIMELINE_DAY_WIDTH, TIMELINE_MIN_DAYS};
+use crate::utils::{dates, theme};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Height of the row of day labels (pixels)
+const HEADER_HEIGHT: f64 = 36.0;
+/// Height of a todo's row (pixels)
+const ROW_HEIGHT: f64 = 28.0;
+
+/// A diamond being dragged along the axis.
+#[derive(Clone, Copy, PartialEq)]
+struct TimelineDrag {
+    id: usize,
+    due: NaiveDate,
+    /// Where the pointer went down (client pixels)
+    origin_x: f64,
+    /// How far the pointer has moved since (pixels)
+    dx: f64,
+}
+
+/// Horizontal timeline of the todos on screen that have a due date
+///
+/// Each todo is a diamond on its due date, with today marked by a line.
+/// Dragging a diamond, or pressing ←/→ while it has focus, moves the due date
+/// by whole days.
+#[component]
+pub fn Timeline(
+    todos: Vec<Rc<Todo>>,
+    today: NaiveDate,
+    on_due_date_change: EventHandler<(usize, Option<DateTime<Utc>>)>,
+    is_dark_mode: bool,
+) -> Element {
+    let mut drag = use_signal(|| None::<TimelineDrag>);
+
+    let motion_class = use_motion("transition-[left] duration-100");
+
+    let rows = timeline::rows(todos);
+    let axis = TimelineAxis::spanning(
+        rows.iter()
+            .filter_map(|todo| todo.due_date.map(dates::due_day)),
+        today,
+        TIMELINE_MIN_DAYS,
+        TIMELINE_DAY_WIDTH,
+    );
+    let height = HEADER_HEIGHT + rows.len() as f64 * ROW_HEIGHT + 8.0;
+
+    let move_due = move |id: usize, due: NaiveDate| {
+        on_due_date_change.call((id, Some(dates::due_date_on(due))));
+    };
+    let mut finish_drag = move || {
+        if let Some(drag) = drag.take() {
+            let moved = axis.dragged(drag.due, drag.dx);
+            if moved != drag.due {
+                move_due(drag.id, moved);
+            }
+        }
+    };
+
+    let border_class = theme::border_class(is_dark_mode);
+    let label_class = theme::muted_text_class(is_dark_mode);
+    let weekend_class = theme::subtle_surface_class(is_dark_mode);
+    let text_class = theme::primary_text_class(is_dark_mode);
+
+    if rows.is_empty() {
+        return rsx! {
+          p { class: "py-6 text-center text-sm {label_class}",
+            "No todos with a due date to show on the timeline."
+          }
+        };
+    }
+
+    rsx! {
+      div {
+        class: "overflow-x-auto rounded border {border_class}",
+        role: "region",
+        aria_label: "Timeline",
+        div {
+          class: "relative select-none",
+          style: "width: {axis.width()}px; height: {height}px;",
+          onmousemove: move |evt| {
+              if let Some(current) = drag() {
+                  let dx = evt.client_coordinates().x - current.origin_x;
+                  drag.set(Some(TimelineDrag { dx, ..current }));
+              }
+          },
+          onmouseup: move |_| finish_drag(),
+          onmouseleave: move |_| drag.set(None),
+
+          // Day columns, with the month at its first day and at the start
+          for (index, date) in axis.dates().enumerate() {
+            div {
+              key: "{date}",
+              class: "absolute top-0 bottom-0 border-l {border_class} text-[10px] leading-tight text-center {label_class}",
+              class: if dates::is_weekend(date.weekday()) { "{weekend_class}" },
+              style: "left: {axis.x_of(date)}px; width: {TIMELINE_DAY_WIDTH}px;",
+              div { class: "h-3 font-medium",
+                if index == 0 || date.day() == 1 { "{date.format(\"%b\")}" }
+              }
+              div { "{date.day()}" }
+            }
+          }
+
+          // Today
+          div {
+            class: "absolute top-0 bottom-0 w-0.5 bg-red-400 pointer-events-none",
+            style: "left: {axis.center_of(today)}px;",
+            title: "Today",
+          }
+
+          for (index, todo) in rows.iter().enumerate() {
+            {
+                let id = todo.id;
+                let due = todo.due_date.map(dates::due_day).unwrap_or(today);
+                let shown = match drag() {
+                    Some(current) if current.id == id => axis.dragged(due, current.dx),
+                    _ => due,
+                };
+                let top = HEADER_HEIGHT + index as f64 * ROW_HEIGHT;
+                let center = axis.center_of(shown);
+                let diamond_class = if todo.completed {
+                    "bg-gray-400"
+                } else if due < today {
+                    "bg-red-500"
+                } else {
+                    "bg-blue-500"
+                };
+                let done_class = if todo.completed { "line-through opacity-60" } else { "" };
+                rsx! {
+                  div {
+                    key: "{id}",
+                    class: "absolute w-3 h-3 rotate-45 cursor-grab {diamond_class} {motion_class} focus:outline-none focus:ring-2 focus:ring-blue-400",
+                    style: "left: {center - 6.0}px; top: {top + 8.0}px;",
+                    tabindex: 0,
+                    role: "button",
+                    title: "{todo.text}: due {shown.format(\"%b %d\")}",
+                    aria_label: "Due date of {todo.text}, {shown.format(\"%b %d\")}. Drag or use arrow keys to move it",
+                    onmousedown: move |evt| {
+                        evt.prevent_default();
+                        drag.set(Some(TimelineDrag {
+                            id,
+                            due,
+                            origin_x: evt.client_coordinates().x,
+                            dx: 0.0,
+                        }));
+                    },
+                    onkeydown: move |evt| {
+                        let days = match evt.key() {
+                            Key::ArrowLeft => -1,
+                            Key::ArrowRight => 1,
+                            _ => return,
+                        };
+                        evt.prevent_default();
+                        move_due(id, due + Duration::days(days));
+                    },
+                  }
+                  span {
+                    class: "absolute text-xs truncate max-w-64 pointer-events-none {text_class} {done_class}",
+                    style: "left: {center + 10.0}px; top: {top + 5.0}px;",
+                    "{todo.text}"
+                  }
+                }
+            }
+          }
+        }
+      }
+    }
+}
+
+/// Buttons switching the main view between the list and the timeline
+#[component]
+pub fn ViewSwitcher(timeline: bool, on_change: EventHandler<bool>, is_dark_mode: bool) -> Element {
+    let list_class = theme::segment_class(!timeline, is_dark_mode);
+    let timeline_class = theme::segment_class(timeline, is_dark_mode);
+
+    rsx! {
+      div {
+        class: "flex justify-end gap-1 text-xs",
+        role: "group",
+        aria_label: "View",
+        button {
+          r#type: "button",
+          class: "px-2 py-0.5 rounded {list_class}",
+          aria_pressed: !timeline,
+          onclick: move |_| on_change.call(false),
+          "List"
+        }
+        button {
+          r#type: "button",
+          class: "px-2 py-0.5 rounded {timeline_class}",
+          aria_pressed: timeline,
+          onclick: move |_| on_change.call(true),
+          "Timeline"
+        }
+      }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use dioxus::dioxus_core::{AttributeValue, Mutation, Mutations};
+    use std::cell::RefCell;
+
+    type Moves = Rc<RefCell<Vec<(usize, Option<DateTime<Utc>>)>>>;
+
+    #[test]
+    fn test_timeline_places_diamonds_on_due_dates() {
+        let mut app = VirtualDom::new(|| {
+            let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+            let mut due = Todo::new(1, "Ship release".to_string());
+            due.due_date = Some(dates::due_date_on(today + Duration::days(3)));
+            let undated = Todo::new(2, "Someday".to_string());
+            rsx! {
+                Timeline {
+                    todos: vec![Rc::new(due), Rc::new(undated)],
+                    today,
+                    on_due_date_change: |_| {},
+                    is_dark_mode: false,
+                }
+            }
+        });
+
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        let titles: Vec<String> = mutations
+            .edits
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Mutation::SetAttribute {
+                    name: "title",
+                    value: AttributeValue::Text(value),
+                    ..
+                } => Some(value),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(titles, vec!["Ship release: due Mar 15"]);
+    }
+
+    #[test]
+    fn test_arrow_keys_move_the_due_date() {
+        let moved = Rc::new(RefCell::new(Vec::new()));
+        let mut dom = TestDom::with_props(
+            |moved: Moves| {
+                let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+                let mut due = Todo::new(1, "Ship release".to_string());
+                due.due_date = Some(dates::due_date_on(today));
+                rsx! {
+                    Timeline {
+                        todos: vec![Rc::new(due)],
+                        today,
+                        on_due_date_change: move |change| moved.borrow_mut().push(change),
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            moved.clone(),
+        );
+
+        let diamond = dom.find_by_attribute("div", "role", "button").unwrap();
+        dom.key_down(diamond, Key::ArrowRight);
+        dom.key_down(diamond, Key::ArrowLeft);
+        dom.key_down(diamond, Key::Enter);
+
+        let day = |d| {
+            Some(dates::due_date_on(
+                NaiveDate::from_ymd_opt(2025, 3, d).unwrap(),
+            ))
+        };
+        assert_eq!(*moved.borrow(), vec![(1, day(13)), (1, day(11))]);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::models::Todo;
+use crate::utils::constants::ui::TOAST_DURATION_SECS;
+use crate::utils::theme;
+use crate::utils::timer;
+use dioxus::prelude::*;
+use std::time::Duration;
+
+/// Something the user can do from a toast.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ToastAction {
+    /// Take these todos back out of the trash
+    RestoreFromTrash(Vec<usize>),
+    /// Put cleared completed todos back in the list
+    RestoreCleared(Vec<Todo>),
+    /// Bring these archived todos back into the list
+    Unarchive(Vec<usize>),
+    /// Move these todos' due dates by this many days
+    ShiftDueDates(Vec<usize>, i64),
+    /// Put the todos back in this order, as ids and orders
+    RestoreOrder(Vec<(usize, usize)>),
+    /// Add this tag to these todos
+    AddTag(Vec<usize>, String),
+    /// Remove this tag from these todos
+    RemoveTag(Vec<usize>, String),
+    /// Undo the last recorded change, which the toast reports
+    UndoChange,
+}
+
+impl ToastAction {
+    /// Gets the label of the action button.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ToastAction::RestoreFromTrash(_)
+            | ToastAction::RestoreCleared(_)
+            | ToastAction::Unarchive(_)
+            | ToastAction::ShiftDueDates(..)
+            | ToastAction::RestoreOrder(_)
+            | ToastAction::AddTag(..)
+            | ToastAction::RemoveTag(..)
+            | ToastAction::UndoChange => "Undo",
+        }
+    }
+}
+
+/// A message shown in a toast, with an optional action.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToastMessage {
+    /// The text shown
+    pub text: String,
+    /// The action offered next to the text
+    pub action: Option<ToastAction>,
+}
+
+impl ToastMessage {
+    /// Creates a message without an action.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            action: None,
+        }
+    }
+}
+
+/// Logic for short messages shown at the bottom of the screen
+///
+/// Returns the current message and a function showing a new one. A message
+/// hides itself after [`TOAST_DURATION_SECS`] unless a newer one replaced it.
+pub fn use_toast() -> (
+    Signal<Option<ToastMessage>>,
+    impl FnMut(ToastMessage) + Copy,
+) {
+    let mut message = use_signal(|| None::<ToastMessage>);
+    let mut shown_count = use_signal(|| 0u64);
+
+    let show = move |toast: ToastMessage| {
+        message.set(Some(toast));
+        let shown = shown_count() + 1;
+        shown_count.set(shown);
+        spawn(async move {
+            timer::sleep(Duration::from_secs(TOAST_DURATION_SECS)).await;
+            if shown_count() == shown {
+                message.set(None);
+            }
+        });
+    };
+
+    (message, show)
+}
+
+/// Component showing a short message that can be dismissed
+#[component]
+pub fn Toast(
+    message: ToastMessage,
+    on_action: EventHandler<ToastAction>,
+    on_dismiss: EventHandler<()>,
+    is_dark_mode: bool,
+) -> Element {
+    let toast_class = theme::toast_class(is_dark_mode);
+
+    rsx! {
+        div {
+            class: "fixed bottom-4 left-1/2 -translate-x-1/2 z-20 max-w-sm flex items-center gap-3 px-4 py-2 rounded-lg border shadow-lg text-sm {toast_class}",
+            role: "status",
+            span { "{message.text}" }
+            if let Some(action) = message.action {
+                button {
+                    r#type: "button",
+                    class: "font-semibold text-amber-300 hover:underline",
+                    onclick: {
+                        let action = action.clone();
+                        move |_| {
+                            on_action.call(action.clone());
+                            on_dismiss.call(());
+                        }
+                    },
+                    "{action.label()}"
+                }
+            }
+            button {
+                r#type: "button",
+                class: "opacity-70 hover:opacity-100",
+                aria_label: "Dismiss",
+                onclick: move |_| on_dismiss.call(()),
+                "✕"
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::{Mutation, Mutations};
+
+    #[test]
+    fn test_toast_rendering() {
+        let mut app = VirtualDom::new(|| {
+            rsx! {
+                Toast {
+                    message: ToastMessage {
+                        text: "Archived 2 old todos".to_string(),
+                        action: Some(ToastAction::RestoreFromTrash(vec![1, 2])),
+                    },
+                    on_action: |_| {},
+                    on_dismiss: |_| {},
+                    is_dark_mode: false,
+                }
+            }
+        });
+
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        let texts: Vec<String> = mutations
+            .edits
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Mutation::CreateTextNode { value, .. } => Some(value),
+                _ => None,
+            })
+            .collect();
+        assert!(texts.contains(&"Archived 2 old todos".to_string()));
+        assert!(texts.contains(&"Undo".to_string()));
+    }
+}
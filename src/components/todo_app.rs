@@ -1,14 +1,81 @@
-use crate::components::keyboard_shortcuts_handler::use_keyboard_shortcuts;
-use crate::components::theme_manager::use_theme_manager;
+use crate::components::auto_archive::use_auto_archive;
+#[cfg(feature = "caldav")]
+use crate::components::caldav_sync::use_caldav_sync;
+use crate::components::clock::{use_clock, use_session_start};
+use crate::components::digest::use_daily_digest;
+use crate::components::export_dialog::describe_error;
+use crate::components::follow_up::use_follow_up_prompt;
+use crate::components::keyboard_shortcuts_handler::{use_keyboard_shortcuts, use_text_entry_focus};
+#[cfg(feature = "markdown-sync")]
+use crate::components::markdown_sync::use_markdown_sync;
+use crate::components::onboarding::{tour_highlight_class, use_onboarding};
+use crate::components::quiet_mode::use_quiet_mode;
+use crate::components::reminders::{scroll_to_todo, use_reminder_checks, use_seen_reminders};
+use crate::components::settings_manager::use_settings;
+use crate::components::snapshot_view::clear_snapshot_fragment;
+use crate::components::theme_manager::{
+    ThemeContext, use_prefers_reduced_motion, use_theme_manager,
+};
+use crate::components::toast::use_toast;
+use crate::components::todo_detail::use_selected_todo;
 use crate::components::todo_state::use_todo_state;
+use crate::components::whats_new::use_whats_new;
+use crate::components::window_size::use_window_size;
 use crate::components::{
-    AppHeader, FilterBar, KeyboardShortcuts, SearchBox, TagsFilter, TodoForm,
-    TodoList as TodoListComponent,
+    AppHeader, BatchTagDialog, BatchTagMode, CommandPalette, DayPlanner, DebugLogPanel, DemoBanner,
+    DueSoonBell, DuplicatesDialog, FilterBar, FollowUpBanner, KarmaSummary, KeyboardShortcuts,
+    LimitWarning, LoadErrorPanel, MostPostponed, MovePositionDialog, OnboardingTour, QuickFilters,
+    QuietModeMenu, SearchBox, SettingsPanel, ShareSnapshotButton, ShiftDatesDialog, SnapshotView,
+    TagsFilter, Timeline, Toast, ToastAction, ToastMessage, TodoDetail, TodoForm,
+    TodoList as TodoListComponent, ViewSwitcher, WelcomePrompt, WhatsNewDialog,
+};
+use crate::models::duplicates::{DuplicateGroup, find_duplicates};
+use crate::models::follow_up::select_follow_ups;
+use crate::models::karma;
+use crate::models::palette::{Command, CommandRegistry};
+use crate::models::postponed;
+use crate::models::quiet::{QuietFor, QuietMode};
+use crate::models::session;
+use crate::models::staleness;
+use crate::models::tag_suggestion;
+use crate::models::view::{row_for_number, selection_after_removal, step_selection};
+use crate::models::{
+    FilterCriteria, FilterState, IssueLinker, ListView, QuickFilter, SearchQuery, SortMode,
+    TagFilter, Todo, TodoList,
 };
-use crate::models::FilterState;
+use crate::utils::constants::app::RELEASE_NOTES;
 use crate::utils::constants::todo::DEFAULT_TAGS;
-use crate::utils::theme;
+use crate::utils::constants::ui::{COMPACT_ROW_MAX_WIDTH, PLANNER_DAYS, TOAST_TODO_TEXT_CHARS};
+use crate::utils::startup::{self, Milestone};
+use crate::utils::{autosave, dates, demo, export, keys, profile, share, storage, text, theme};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use dioxus::prelude::*;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Gets a todo's text for a toast, shortened to [`TOAST_TODO_TEXT_CHARS`].
+fn toast_todo_text(list: &TodoList, id: usize) -> String {
+    list.get(id)
+        .map(
+            |todo| match text::truncate_chars(&todo.text, TOAST_TODO_TEXT_CHARS) {
+                Some(start) => format!("{start}…"),
+                None => todo.text.clone(),
+            },
+        )
+        .unwrap_or_default()
+}
+
+/// Picks the todos with the given ids out of `todos`, in the order of `ids`,
+/// leaving out ids not among them.
+fn todos_by_ids(todos: &[Rc<Todo>], ids: &[usize]) -> Vec<Rc<Todo>> {
+    if ids.is_empty() {
+        return Vec::new();
+    }
+    let by_id: HashMap<usize, &Rc<Todo>> = todos.iter().map(|todo| (todo.id, todo)).collect();
+    ids.iter()
+        .filter_map(|id| by_id.get(id).map(|todo| Rc::clone(todo)))
+        .collect()
+}
 
 /// Main component for the Todo application.
 #[component]
@@ -16,114 +83,1340 @@ pub fn TodoApp() -> Element {
     // Theme management
     let (is_dark_mode, toggle_theme) = use_theme_manager();
 
+    // User settings and settings panel visibility
+    let mut settings = use_settings();
+    let clock = use_clock();
+    let session_start = use_session_start();
+    let mut show_settings = use_signal(|| false);
+    // "Do not disturb", silencing reminders, notifications, sounds and badges
+    let mut quiet_mode = use_quiet_mode();
+
+    // Animations, unless turned off in settings or the system asks for reduced motion
+    let prefers_reduced_motion = use_prefers_reduced_motion();
+    let animations_enabled = use_memo(move || {
+        settings
+            .read()
+            .animations
+            .is_enabled(prefers_reduced_motion())
+    });
+    use_context_provider(|| ThemeContext { animations_enabled });
+
     // Todo state management
-    let (todo_list, mut filter, mut selected_tag, operations, sorted_tags) =
-        use_todo_state(&DEFAULT_TAGS);
+    let (
+        todo_list,
+        mut criteria,
+        mut sort_mode,
+        operations,
+        mut save_tracker,
+        loaded,
+        load_failure,
+        mut operation_error,
+    ) = use_todo_state();
+    use_effect(|| startup::record(Milestone::Shell));
+
+    // A snapshot shared through the page URL, shown read-only instead of the list
+    let mut shared_snapshot = use_signal(share::snapshot_from_location);
+
+    // Release notes, shown once after an upgrade and on request from settings
+    let mut whats_new = use_whats_new(settings);
+
+    // Todo being dragged, and the planner day it is over
+    let mut dragged_todo = use_signal(|| None::<usize>);
+    let mut drop_day = use_signal(|| None::<NaiveDate>);
+    // Whether the todos on screen are drawn on a timeline instead of listed
+    let mut show_timeline = use_signal(|| false);
 
-    // Search state
-    let mut search_text = use_signal(String::new);
+    // Large list warning, dismissed for the rest of the session
+    let mut limit_warning_dismissed = use_signal(|| false);
+
+    // Dialog shifting the due dates of the todos shown
+    let mut show_shift_dates = use_signal(|| false);
+    // Dialog adding a tag to the todos shown or removing one from them
+    let mut batch_tag = use_signal(|| None::<BatchTagMode>);
+    // Dialog moving a todo to a typed position, given the todo
+    let mut moving_todo = use_signal(|| None::<usize>);
+    // Dialog listing the open todos that repeat each other
+    let mut duplicate_groups = use_signal(|| None::<Vec<DuplicateGroup>>);
+    // Debug log dialog, opened with Ctrl+Shift+L or from the settings
+    let mut show_debug_log = use_signal(|| false);
+    // Quick switcher, opened with Ctrl+K
+    let mut show_palette = use_signal(|| false);
+    // Whether Alt is held, numbering the rows the number keys toggle
+    let mut show_row_numbers = use_signal(|| false);
+    let typing_text = use_text_entry_focus();
+
+    // Due soon reminders already seen in the bell dropdown
+    let mut seen_reminders = use_seen_reminders();
+    let reminder_checks = use_reminder_checks();
+
+    // First-run onboarding
+    let (mut onboarding_done, mut tour_step) = use_onboarding();
+
+    // Short messages, such as why a change was refused
+    let (mut toast, mut show_toast) = use_toast();
+
+    // Detail panel docked beside the list on wide windows, and the todo it shows
+    let window_size = use_window_size();
+    let split_view = use_memo(move || {
+        settings
+            .read()
+            .split_view
+            .is_split(window_size().map(|size| size.width))
+    });
+    let mut selected_todo = use_selected_todo();
+    // Rows put their action buttons in a menu on narrow windows
+    let compact_rows = window_size().is_some_and(|size| size.width < COMPACT_ROW_MAX_WIDTH);
 
     // Extract operations
     let add_todo = operations.add_todo;
-    let toggle_todo = operations.toggle_todo;
-    let delete_todo = operations.delete_todo;
-    let update_todo = operations.update_todo;
-    let set_due_date = operations.set_due_date;
+    let add_shared_todo = operations.add_shared_todo;
+    let add_linked_todo = operations.add_linked_todo;
+    let toggle_todo = use_callback(operations.toggle_todo);
+    let toggle_all = use_callback(operations.toggle_all);
+    let move_to_trash = use_callback(operations.delete_todo);
+    let restore_from_trash = operations.restore_from_trash;
+    let mut restore_many_from_trash = operations.restore_many_from_trash;
+    let purge_from_trash = operations.purge_from_trash;
+    let mut empty_trash = operations.empty_trash;
+    let mut update_todo = operations.update_todo;
+    let mut restore_text = operations.restore_text;
+    let set_locked = operations.set_locked;
+    let set_notes = use_callback(operations.set_notes);
+    let set_url = use_callback(operations.set_url);
+    let add_subtask = use_callback(operations.add_subtask);
+    let toggle_subtask = use_callback(operations.toggle_subtask);
+    let remove_subtask = use_callback(operations.remove_subtask);
+    let extract_from_notes = use_callback(operations.extract_from_notes);
+    let set_due_date = use_callback(operations.set_due_date);
+    let shift_due_dates = use_callback(operations.shift_due_dates);
     let add_tag_to_todo = operations.add_tag_to_todo;
     let remove_tag_from_todo = operations.remove_tag_from_todo;
-    let mut clear_completed = operations.clear_completed;
-    let reorder_todo = operations.reorder_todo;
+    let add_tag_many = use_callback(operations.add_tag_many);
+    let remove_tag_many = use_callback(operations.remove_tag_many);
+    let mut remove_tag_everywhere = operations.remove_tag_everywhere;
+    let merge_duplicates = use_callback(operations.merge_duplicates);
+    let clear_completed = use_callback(operations.clear_completed);
+    let mut restore_cleared = operations.restore_cleared;
+    let archive_completed = use_callback(operations.archive_completed);
+    let unarchive_todos = use_callback(operations.unarchive_todos);
+    let mut reorder_todo = operations.reorder_todo;
+    let mut reorder_to_index = operations.reorder_to_index;
+    let mut restore_order = operations.restore_order;
+    let restore_todos = operations.restore_todos;
+    let mut import_todos = operations.import_todos;
+    let mut add_sample_todos = operations.add_sample_todos;
+    let save_now = use_callback(operations.save_now);
+    let mut retry_load = operations.retry_load;
+    let apply_auto_tags = use_callback(operations.apply_auto_tags);
+    let apply_import = use_callback(operations.apply_import);
+    let mut auto_archive = operations.auto_archive;
+    let mut escalate = operations.escalate;
+    let mut enforce_retention = operations.enforce_retention;
+    let mut reclaim_space = operations.reclaim_space;
+    let bump_todo = operations.bump_todo;
+    let snooze_reminder = use_callback(operations.snooze_reminder);
+    let cancel_snooze = operations.cancel_snooze;
+    let mut undo = operations.undo;
+    let mut redo = operations.redo;
+
+    // Operations on todos that are gone, for example after a sync, say so
+    use_effect(move || {
+        if let Some(error) = operation_error() {
+            show_toast(ToastMessage::new(error.to_string()));
+            operation_error.set(None);
+        }
+    });
+
+    // Locked todos refuse edits and deletion; tell the user why nothing happened
+    let delete_todo = move |id: usize| {
+        if let Err(error) = move_to_trash(id) {
+            show_toast(ToastMessage::new(error.to_string()));
+        }
+    };
+    let update_todo = move |change: (usize, String)| {
+        if let Err(error) = update_todo(change) {
+            show_toast(ToastMessage::new(error.to_string()));
+        }
+    };
+    let restore_text = move |version: (usize, usize)| {
+        if let Err(error) = restore_text(version) {
+            show_toast(ToastMessage::new(error.to_string()));
+        }
+    };
+    // Restarts in another profile once pending changes are written, since the
+    // new process or page loads the todos from storage
+    let switch_profile = move |target: Option<String>| {
+        if loaded() && save_tracker.peek().is_dirty() {
+            let flushed = storage::get_storage().and_then(|storage| {
+                autosave::flush_with(
+                    &mut save_tracker.write(),
+                    storage.as_ref(),
+                    keys::TODOS,
+                    &*todo_list.peek(),
+                )
+            });
+            if let Err(error) = flushed {
+                show_toast(ToastMessage::new(format!(
+                    "Could not save before switching profiles: {:?}",
+                    error
+                )));
+                return;
+            }
+        }
+        profile::switch_to(target.as_deref());
+    };
+    // Deletes everything stored and restarts, with saves blocked first so the
+    // todos on screen are not written back
+    let delete_all_data = move |_| {
+        save_tracker.write().block_persistence();
+        match storage::delete_all() {
+            Ok(_) => profile::switch_to(profile::active()),
+            Err(error) => {
+                save_tracker.write().unblock_persistence();
+                show_toast(ToastMessage::new(format!(
+                    "Could not delete the data: {:?}",
+                    error
+                )));
+            }
+        }
+    };
+    // Clears the given visible todos, or all with `None`, offering an undo
+    let clear_completed = use_callback(move |visible_ids: Option<Vec<usize>>| {
+        let cleared = clear_completed(visible_ids);
+        if cleared.removed.is_empty() && cleared.skipped_locked == 0 {
+            return;
+        }
+        let mut text = format!(
+            "Cleared {} completed {}",
+            cleared.removed.len(),
+            if cleared.removed.len() == 1 {
+                "todo"
+            } else {
+                "todos"
+            }
+        );
+        if cleared.skipped_locked > 0 {
+            text.push_str(&format!("; kept {} locked", cleared.skipped_locked));
+        }
+        show_toast(ToastMessage {
+            text,
+            action: (!cleared.removed.is_empty())
+                .then_some(ToastAction::RestoreCleared(cleared.removed)),
+        });
+    });
+    // Archives the completed todos among the visible ones, offering an undo
+    let archive_completed = use_callback(move |visible_ids: Vec<usize>| {
+        let archived = archive_completed(visible_ids);
+        if archived.is_empty() {
+            return;
+        }
+        show_toast(ToastMessage {
+            text: format!(
+                "Archived {} completed {}",
+                archived.len(),
+                if archived.len() == 1 { "todo" } else { "todos" }
+            ),
+            action: Some(ToastAction::Unarchive(archived)),
+        });
+    });
+    // Moves the due dates of the given todos, offering an undo
+    let mut shift_dates = move |(ids, days): (Vec<usize>, i64)| {
+        let moved = shift_due_dates((ids, days));
+        let shifted = moved.shifted.len();
+        let mut text = format!(
+            "Moved {} due {} {} {} {}",
+            shifted,
+            if shifted == 1 { "date" } else { "dates" },
+            days.abs(),
+            if days.abs() == 1 { "day" } else { "days" },
+            if days < 0 { "earlier" } else { "later" }
+        );
+        if !moved.refused.is_empty() {
+            text.push_str(&format!(
+                "; {} would fall outside the allowed dates",
+                moved.refused.len()
+            ));
+        }
+        show_toast(ToastMessage {
+            text,
+            action: (shifted > 0).then_some(ToastAction::ShiftDueDates(moved.shifted, -days)),
+        });
+    };
+
+    // Adds a tag to the given todos or removes it, offering an undo of just
+    // the todos that changed
+    let mut tag_todos = move |(mode, ids, tag): (BatchTagMode, Vec<usize>, String)| {
+        let removing = mode == BatchTagMode::Remove;
+        let changing: Vec<usize> = {
+            let list = todo_list.peek();
+            ids.iter()
+                .copied()
+                .filter(|id| {
+                    list.get(*id)
+                        .is_some_and(|todo| todo.tags.contains(&tag) == removing)
+                })
+                .collect()
+        };
+        let (changed, verb, undo) = match mode {
+            BatchTagMode::Add => (
+                add_tag_many((ids, tag.clone())),
+                "Tagged",
+                ToastAction::RemoveTag(changing, tag.clone()),
+            ),
+            BatchTagMode::Remove => (
+                remove_tag_many((ids, tag.clone())),
+                "Untagged",
+                ToastAction::AddTag(changing, tag.clone()),
+            ),
+        };
+        show_toast(ToastMessage {
+            text: format!(
+                "{verb} {changed} {} {} '{tag}'",
+                if changed == 1 { "todo" } else { "todos" },
+                if removing { "from" } else { "with" }
+            ),
+            action: (changed > 0).then_some(undo),
+        });
+    };
+
+    // Moves a dragged todo, offering an undo
+    let reorder_todo = move |(source_id, target_id): (usize, usize)| {
+        let Some(previous) = reorder_todo((source_id, target_id)) else {
+            return;
+        };
+        let todo_text = toast_todo_text(&todo_list.peek(), source_id);
+        show_toast(ToastMessage {
+            text: format!("Moved '{todo_text}'"),
+            action: Some(ToastAction::RestoreOrder(previous)),
+        });
+    };
+
+    // Moves a todo to a typed position, announcing where it went
+    let mut move_to_index = move |(id, index, position): (usize, usize, usize)| {
+        let Some(previous) = reorder_to_index((id, index)) else {
+            return;
+        };
+        let todo_text = toast_todo_text(&todo_list.peek(), id);
+        show_toast(ToastMessage {
+            text: format!("Moved '{todo_text}' to position {position}"),
+            action: Some(ToastAction::RestoreOrder(previous)),
+        });
+    };
+
+    // Writes every todo to a JSON file, as "Everything" in the export dialog does
+    let export_json = use_callback({
+        let clock = clock.clone();
+        move |_| {
+            let list = export::without_text_history(&todo_list.peek());
+            let file_name = export::export_file_name::<&str>(&[], clock.today_local(), "json");
+            let result = export::export_json(&list)
+                .and_then(|json| export::save_export_file(&file_name, json.as_bytes()));
+            show_toast(ToastMessage::new(
+                result.unwrap_or_else(|error| describe_error(&error)),
+            ));
+        }
+    });
+    let toggle_theme_command = use_callback(toggle_theme.clone());
+
+    // The escalation rules run first on load and daily, then todos past the
+    // trash and archive retention are deleted, then completed todos past the
+    // auto-archive age leave the list
+    let scheduled_rules = use_callback(move |now: DateTime<Utc>| {
+        escalate(now);
+        enforce_retention(now);
+        auto_archive(now)
+    });
+    // "Reclaim space" in the settings
+    let reclaim_space = use_callback({
+        let clock = clock.clone();
+        move |_| reclaim_space(clock.now_utc())
+    });
+    use_auto_archive(loaded, scheduled_rules, move |archived: Vec<usize>| {
+        show_toast(ToastMessage {
+            text: format!(
+                "Archived {} old {} to the trash",
+                archived.len(),
+                if archived.len() == 1 { "todo" } else { "todos" }
+            ),
+            action: Some(ToastAction::RestoreFromTrash(archived)),
+        });
+    });
+
+    // Two-way sync with a CalDAV task collection, on native builds
+    #[cfg(feature = "caldav")]
+    use_caldav_sync(todo_list, loaded);
+    #[cfg(feature = "caldav")]
+    let caldav_settings = rsx! {
+        crate::components::CalDavSyncSettings { todo_list, is_dark_mode: is_dark_mode() }
+    };
+    #[cfg(not(feature = "caldav"))]
+    let caldav_settings = rsx! {};
+
+    // Two-way sync with a Markdown checklist file, on native builds
+    #[cfg(feature = "markdown-sync")]
+    use_markdown_sync(todo_list, loaded);
+    #[cfg(feature = "markdown-sync")]
+    let markdown_sync_settings = rsx! {
+        crate::components::MarkdownSyncSettingsSection { is_dark_mode: is_dark_mode() }
+    };
+    #[cfg(not(feature = "markdown-sync"))]
+    let markdown_sync_settings = rsx! {};
 
     // Set filter handler
     let change_filter = move |new_filter: FilterState| {
-        filter.set(new_filter);
+        criteria.write().filter = new_filter;
     };
 
     // Selected tag handler
-    let select_tag = move |tag: Option<String>| {
-        selected_tag.set(tag);
+    let select_tag = move |tag: TagFilter| {
+        criteria.write().tag = tag;
+    };
+
+    // Deletes a tag from every todo; the tags filter stops filtering by it
+    let delete_tag = move |tag: String| {
+        let removed = remove_tag_everywhere(tag.clone());
+        show_toast(ToastMessage {
+            text: format!(
+                "Deleted tag '{tag}' from {removed} {}",
+                if removed == 1 { "todo" } else { "todos" }
+            ),
+            action: (removed > 0).then_some(ToastAction::UndoChange),
+        });
     };
 
     // Search handler
     let on_search = move |text: String| {
-        search_text.set(text);
+        criteria.write().search_text = text;
     };
 
-    // Keyboard shortcut handler
-    let handle_key_down = use_keyboard_shortcuts(change_filter, toggle_theme.clone());
+    // Shows a todo in the list, clearing filters that would hide it
+    let reveal_todo = use_callback(move |id: usize| {
+        criteria.set(FilterCriteria::default());
+        scroll_to_todo(id);
+    });
+
+    // Ctrl+Z and Ctrl+Shift+Z, saying so when there is nothing to step over
+    let undo_change = use_callback(move |_| {
+        if !undo(()) {
+            show_toast(ToastMessage::new("Nothing to undo"));
+        }
+    });
+    let redo_change = use_callback(move |_| {
+        if !redo(()) {
+            show_toast(ToastMessage::new("Nothing to redo"));
+        }
+    });
+
+    // Morning notification of overdue todos and todos due today, opening the Today view
+    let open_today = use_callback(move |_| {
+        criteria.set(FilterCriteria {
+            quick_filter: Some(QuickFilter::Today),
+            ..FilterCriteria::default()
+        });
+        #[cfg(feature = "desktop")]
+        {
+            let window = dioxus_desktop::window();
+            window.set_minimized(false);
+            window.set_focus();
+        }
+    });
+    if cfg!(feature = "desktop") {
+        use_daily_digest(todo_list, loaded, settings, open_today);
+    }
 
-    // Get current todos as vector
-    let todos = todo_list.read().all();
+    // Get current todos as vector in the chosen order, shared with the list items
+    let todos: Vec<Rc<_>> = todo_list
+        .read()
+        .sorted(sort_mode())
+        .into_iter()
+        .map(Rc::new)
+        .collect();
+    // The Trash filter, or a `deleted:any` search, lists the trash instead
+    let current = criteria();
+    let searching_trash =
+        current.filter == FilterState::Trash || SearchQuery::parse(&current.search_text).deleted;
+    let listed_todos: Vec<Rc<_>> = if searching_trash {
+        todo_list
+            .read()
+            .trashed()
+            .into_iter()
+            .map(Rc::new)
+            .collect()
+    } else if current.filter == FilterState::Archived {
+        // The status filter leaves only the archived ones
+        let mut all = todo_list.read().all_including_archived();
+        sort_mode().sort(&mut all);
+        all.into_iter().map(Rc::new).collect()
+    } else {
+        todos.clone()
+    };
     let active_count = todo_list.read().active_count();
+    let trash_count = todo_list.read().trash_count();
     let completed_count = todo_list.read().completed_count();
+    let overdue_count = todo_list.read().overdue_count(clock.today_local());
+
+    let todo_count_warning = settings.read().todo_count_warning;
+    let todo_count = todos.len();
+    let show_limit_warning = !limit_warning_dismissed() && todo_count > todo_count_warning;
+
+    // Karma and progress towards the weekly goal, unless turned off in settings
+    // or the todos have not loaded yet
+    let karma_summary = (loaded() && settings.read().show_karma).then(|| {
+        let settings = settings.read();
+        (
+            karma::karma(todos.iter().map(|todo| &**todo)),
+            karma::weekly_progress(
+                todos.iter().map(|todo| &**todo),
+                clock.now_utc(),
+                settings.week_start,
+                settings.weekly_goal,
+            ),
+        )
+    });
+
+    // Todos whose due date keeps slipping, shown with the karma summary
+    let most_postponed: Vec<Todo> = if karma_summary.is_some() {
+        postponed::most_postponed(todos.iter().map(|todo| &**todo))
+            .into_iter()
+            .cloned()
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    // Tag suggestions ranked by use; the counts are redone only when the todos change
+    let tag_usage = use_memo(move || tag_suggestion::tag_usage(&todo_list.read().all()));
+    let suggest_tags = use_callback(move |on_todo: Vec<String>| {
+        tag_suggestion::suggest_tags(
+            &DEFAULT_TAGS,
+            &tag_usage.read(),
+            &settings.read().suppressed_suggestions,
+            &on_todo,
+        )
+    });
+    let suppress_tag = move |tag: String| {
+        settings.write().suppressed_suggestions.insert(tag);
+    };
+
+    // Issue-link rules, compiled once when they change rather than for every row
+    let issue_linker =
+        use_memo(move || Rc::new(IssueLinker::new(&settings.read().issue_link_rules)));
+
+    // Waiting todos due a follow-up, offered in a banner once a day
+    let follow_up_ids = use_memo({
+        let clock = clock.clone();
+        move || {
+            if !loaded() {
+                return Vec::new();
+            }
+            let settings = settings.read();
+            select_follow_ups(
+                &todo_list.read(),
+                &settings.follow_up_tag,
+                clock.now_utc(),
+                settings.follow_up_after_days,
+            )
+        }
+    });
+    let mut follow_up_open = use_follow_up_prompt(follow_up_ids);
+    let follow_up_todos = todos_by_ids(&todos, &follow_up_ids.read());
+
+    // Todos entering the reminder window, and how many of them are new.
+    // Reading the last check renders again on every check, which also
+    // notices the end of a quiet period
+    let _ = reminder_checks();
+    let now = clock.now_utc();
+    let notifications_allowed = quiet_mode.read().notifications_allowed(now);
+    let reminder_lead = Duration::hours(settings.read().reminder_lead_hours.into());
+    let due_soon: Vec<_> = todo_list
+        .read()
+        .due_soon(now, reminder_lead)
+        .into_iter()
+        .cloned()
+        .collect();
+    // No badge while quiet
+    let unseen_reminders = if notifications_allowed {
+        due_soon
+            .iter()
+            .filter(|todo| !seen_reminders.read().contains(&todo.id))
+            .count()
+    } else {
+        0
+    };
+    let due_soon_ids: Vec<usize> = due_soon.iter().map(|todo| todo.id).collect();
+
+    // Todos each quick filter pill would show with the current status filter
+    let today = clock.today_local();
+    let week_start = settings.read().week_start;
+    let quick_filter_counts: Vec<_> = QuickFilter::ALL
+        .into_iter()
+        .map(|pill| {
+            let count = todos
+                .iter()
+                .filter(|todo| {
+                    current.filter.matches(todo, today, week_start)
+                        && pill.matches(todo, today, week_start)
+                })
+                .count();
+            (pill, count)
+        })
+        .collect();
+
+    // Open todos left unchanged too long, counted for the "Stale" pill when
+    // the aging indicator is on
+    let show_stale = settings.read().show_stale;
+    let stale_after_days = settings.read().stale_after_days;
+    let stale_count = show_stale.then(|| {
+        todos
+            .iter()
+            .filter(|todo| staleness::is_stale(todo, now, stale_after_days))
+            .count()
+    });
+
+    // Todos added since launch, counted for the "Added this session" pill
+    // from the first one on
+    let new_count = todos
+        .iter()
+        .filter(|todo| session::is_added_since(todo, session_start))
+        .count();
+
+    // Filters narrowing the list to the todos on screen
+    let list_view = ListView {
+        week_start,
+        stale_after_days,
+        session_start: Some(session_start),
+        ..current.view()
+    };
+    let stale_active = list_view.query.stale;
+    let new_active = list_view.query.added_this_session;
+
+    // Todos on screen in screen order, which Ctrl+↑/↓ step through; the
+    // number keys toggle and "Current view" exports them, trashed ones aside
+    let row_ids = list_view.visible_ids(listed_todos.iter().map(|todo| &**todo), now, today);
+    let shown_ids = if searching_trash {
+        Vec::new()
+    } else {
+        row_ids.clone()
+    };
+    let selectable_ids = if split_view() { row_ids } else { Vec::new() };
+    let deletable_ids = if split_view() {
+        shown_ids.clone()
+    } else {
+        Vec::new()
+    };
+    let numbered_ids = shown_ids.clone();
+    // The todo being moved with the todos on screen in screen order, which
+    // "Move to position…" counts; gathered only while its dialog is open
+    let moving = moving_todo().and_then(|id| {
+        let shown = todos_by_ids(&listed_todos, &shown_ids);
+        let todo = shown.iter().find(|todo| todo.id == id).cloned()?;
+        Some((todo, shown))
+    });
+    let selected_detail = selected_todo().and_then(|id| {
+        listed_todos
+            .iter()
+            .find(|todo| todo.id == id)
+            .map(Rc::clone)
+    });
+    let selected_snooze = selected_todo().and_then(|id| todo_list.read().snoozed_until(id));
+
+    // Keyboard shortcut handler
+    let mut handle_key_down = use_keyboard_shortcuts(
+        change_filter,
+        toggle_theme.clone(),
+        move |_| save_now(()),
+        move |step| {
+            if let Some(id) = step_selection(&selectable_ids, selected_todo(), step) {
+                selected_todo.set(Some(id));
+                scroll_to_todo(id);
+            }
+        },
+        move |_| show_debug_log.set(true),
+        move |number| {
+            // Digits typed into a field belong to the field
+            if typing_text() {
+                return;
+            }
+            if let Some(id) = row_for_number(&numbered_ids, number) {
+                toggle_todo(id);
+            }
+        },
+        move |_| show_palette.set(true),
+        // Delete moves the selected todo to the trash and selects the row
+        // taking its place; keys typed into a field belong to the field
+        move |_| {
+            if typing_text() {
+                return;
+            }
+            let Some(id) = selected_todo().filter(|id| deletable_ids.contains(id)) else {
+                return;
+            };
+            let todo_text = toast_todo_text(&todo_list.peek(), id);
+            match move_to_trash(id) {
+                Ok(()) => {
+                    let next = selection_after_removal(&deletable_ids, &[id]);
+                    selected_todo.set(next);
+                    if let Some(next) = next {
+                        scroll_to_todo(next);
+                    }
+                    show_toast(ToastMessage {
+                        text: format!("Moved '{todo_text}' to the trash"),
+                        action: Some(ToastAction::RestoreFromTrash(vec![id])),
+                    });
+                }
+                Err(error) => show_toast(ToastMessage::new(error.to_string())),
+            }
+        },
+        // Undo in a text field undoes the typing instead, so the key is
+        // left to the field
+        move |_| {
+            if typing_text() {
+                return false;
+            }
+            undo_change(());
+            true
+        },
+        move |_| {
+            if typing_text() {
+                return false;
+            }
+            redo_change(());
+            true
+        },
+    );
+    // Completed todos on screen, which "Clear completed" removes
+    let visible_completed_ids = if searching_trash {
+        Vec::new()
+    } else {
+        list_view.visible_ids(
+            todos
+                .iter()
+                .filter(|todo| todo.completed)
+                .map(|todo| &**todo),
+            now,
+            today,
+        )
+    };
+    // The same todos, which "Archive completed" keeps out of sight instead
+    let archivable_ids = visible_completed_ids.clone();
+
+    // Todos on screen with a due date, which "Shift dates…" moves
+    let visible_dated_ids = if searching_trash {
+        Vec::new()
+    } else {
+        list_view.visible_ids(
+            todos
+                .iter()
+                .filter(|todo| todo.due_date.is_some())
+                .map(|todo| &**todo),
+            now,
+            today,
+        )
+    };
+
+    // Todos on screen, laid out by due date on the timeline
+    let timeline_todos: Vec<Rc<Todo>> = if show_timeline() && !searching_trash {
+        listed_todos
+            .iter()
+            .filter(|todo| list_view.matches(todo, now, today))
+            .map(Rc::clone)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    // Commands offered in the quick switcher; features add theirs here
+    let mut commands = CommandRegistry::new();
+    commands.register(
+        Command::new("theme.toggle", "Toggle dark mode", move || {
+            toggle_theme_command(())
+        })
+        .with_keywords(&["theme", "light", "night"]),
+    );
+    commands.register(
+        Command::new("export.json", "Export JSON", move || export_json(())).with_keywords(&[
+            "backup",
+            "download",
+            "save file",
+        ]),
+    );
+    commands.register({
+        let ids = visible_completed_ids.clone();
+        Command::new("todos.clear_completed", "Clear completed", move || {
+            clear_completed(Some(ids.clone()))
+        })
+        .with_keywords(&["remove done", "delete finished"])
+    });
+    commands.register({
+        let ids = visible_completed_ids.clone();
+        Command::new("todos.archive_completed", "Archive completed", move || {
+            archive_completed(ids.clone())
+        })
+        .with_keywords(&["hide done", "keep finished"])
+    });
+    commands.register(
+        Command::new("view.today", "Switch to Today view", move || open_today(()))
+            .with_keywords(&["due today", "agenda"]),
+    );
+    commands.register({
+        let clock = clock.clone();
+        Command::new("quiet.one_hour", "Do not disturb for 1 hour", move || {
+            let mut quiet_mode = quiet_mode;
+            quiet_mode.set(QuietMode::start(QuietFor::OneHour, &*clock))
+        })
+        .with_keywords(&["quiet", "mute", "silence"])
+    });
+    commands.register(
+        Command::new("edit.undo", "Undo", move || undo_change(()))
+            .with_keywords(&["revert", "take back"]),
+    );
+    commands.register(
+        Command::new("edit.redo", "Redo", move || redo_change(())).with_keywords(&["again"]),
+    );
+    commands.register(
+        Command::new("settings.open", "Open settings", move || {
+            let mut show_settings = show_settings;
+            show_settings.set(true)
+        })
+        .with_keywords(&["preferences", "options"]),
+    );
+
+    // Offer sample todos until the user answers or adds a todo of their own
+    let show_welcome = loaded() && !onboarding_done() && todos.is_empty();
+    // The shell is drawn at once; the list waits for the todos to arrive
+    let loading = !loaded() && load_failure().is_none();
+    let loading_text_class = theme::secondary_text_class(is_dark_mode());
+
+    // Todos currently shown, gathered for a snapshot link on the web only
+    // when one is asked for
+    let snapshot_todos = {
+        let ids = shown_ids.clone();
+        move |_| {
+            let list = todo_list.peek();
+            ids.iter()
+                .filter_map(|id| list.get(*id).cloned())
+                .collect::<Vec<_>>()
+        }
+    };
 
     // Get container class from theme utilities
-    let container_class = theme::container_class(is_dark_mode());
+    let container_class = theme::container_class(is_dark_mode(), animations_enabled());
+    let list_motion_class = theme::motion("transition-all duration-300", animations_enabled());
+
+    if let Some(snapshot) = shared_snapshot() {
+        return rsx! {
+            div { class: "h-full {container_class} py-8 px-4",
+                div { class: "max-w-2xl mx-auto sm:px-6 lg:px-8",
+                    SnapshotView {
+                        snapshot,
+                        on_import: move |todos| {
+                            import_todos(todos);
+                            clear_snapshot_fragment();
+                            shared_snapshot.set(None);
+                        },
+                        on_close: move |_| {
+                            clear_snapshot_fragment();
+                            shared_snapshot.set(None);
+                        },
+                        is_dark_mode: is_dark_mode(),
+                    }
+                }
+            }
+        };
+    }
 
     rsx! {
         div {
             class: "h-full {container_class} py-8 px-4",
             tabindex: "0",
-            onkeydown: handle_key_down,
+            // Holding Alt shows which number key toggles which row
+            onkeydown: move |evt: Event<KeyboardData>| {
+                if evt.key() == Key::Alt && !typing_text() {
+                    show_row_numbers.set(true);
+                }
+                handle_key_down(evt);
+            },
+            onkeyup: move |evt: Event<KeyboardData>| {
+                if !evt.modifiers().alt() && show_row_numbers() {
+                    show_row_numbers.set(false);
+                }
+            },
 
-            div { class: "max-w-2xl mx-auto sm:px-6 lg:px-8",
+            div {
+                class: if split_view() { "max-w-7xl mx-auto sm:px-6 lg:px-8 grid grid-cols-[minmax(0,1fr)_minmax(20rem,28rem)] gap-6 items-start" } else { "max-w-2xl mx-auto sm:px-6 lg:px-8" },
 
-                // App header
-                AppHeader {
-                    title: "Dioxus Todo App",
-                    is_dark_mode: is_dark_mode(),
-                    on_toggle_theme: toggle_theme,
-                }
+                div { class: "min-w-0",
+
+                    // App header
+                    AppHeader {
+                        title: "Dioxus Todo App",
+                        is_dark_mode: is_dark_mode(),
+                        on_toggle_theme: toggle_theme,
+                        on_toggle_settings: move |_| show_settings.set(!show_settings()),
+                        save_status: save_tracker.read().status(),
+                        on_save: move |_| save_now(()),
+                        profile: profile::active().map(String::from),
+                        QuietModeMenu {
+                            quiet_mode: quiet_mode(),
+                            on_change: move |quiet| quiet_mode.set(quiet),
+                            is_dark_mode: is_dark_mode(),
+                        }
+                        DueSoonBell {
+                            todos: due_soon,
+                            unseen_count: unseen_reminders,
+                            // Remember only what is listed now, so todos that left and re-enter count again
+                            on_open: move |_| seen_reminders.set(due_soon_ids.iter().copied().collect()),
+                            on_complete: toggle_todo,
+                            // Counted again when the snooze runs out
+                            on_snooze: move |id| {
+                                seen_reminders.write().remove(&id);
+                                snooze_reminder(id);
+                            },
+                            on_open_todo: reveal_todo,
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
 
-                // Todo form
-                TodoForm { on_add: add_todo, is_dark_mode: is_dark_mode() }
+                    // Demo mode notice
+                    if demo::is_demo_mode() {
+                        DemoBanner { is_dark_mode: is_dark_mode() }
+                    }
 
-                // Search box
-                SearchBox {
-                    search_term: search_text(),
-                    on_search,
-                    is_dark_mode: is_dark_mode(),
-                }
+                    // Stored todos that could not be loaded, with ways to recover them
+                    if let Some(failure) = load_failure() {
+                        LoadErrorPanel {
+                            failure,
+                            on_retry: move |_| retry_load(()),
+                            on_open_backups: move |_| show_settings.set(true),
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
 
-                // Tags filter
-                TagsFilter {
-                    tags: sorted_tags.clone(),
-                    selected_tag: selected_tag(),
-                    on_select_tag: select_tag,
-                    is_dark_mode: is_dark_mode(),
-                }
+                    // Waiting todos due a follow-up
+                    if follow_up_open() && notifications_allowed && !follow_up_todos.is_empty() {
+                        FollowUpBanner {
+                            todos: follow_up_todos,
+                            on_bump: bump_todo,
+                            on_done: toggle_todo,
+                            on_dismiss: move |_| follow_up_open.set(false),
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+
+                    // Settings panel
+                    if show_settings() {
+                        SettingsPanel {
+                            todos: todos.iter().map(|todo| (**todo).clone()).collect(),
+                            visible_ids: shown_ids.clone(),
+                            view_name: list_view.name_parts(),
+                            on_restore: restore_todos,
+                            on_import: apply_import,
+                            settings: settings(),
+                            on_settings_change: move |new_settings| settings.set(new_settings),
+                            on_apply_auto_tags: apply_auto_tags,
+                            on_show_whats_new: move |_| whats_new.set(RELEASE_NOTES.to_vec()),
+                            on_show_debug_log: move |_| show_debug_log.set(true),
+                            last_saved: save_tracker.read().last_saved(),
+                            on_reclaim: reclaim_space,
+                            on_delete_all: delete_all_data,
+                            profile: profile::active().map(String::from),
+                            on_switch_profile: switch_profile,
+                            is_dark_mode: is_dark_mode(),
+                            {caldav_settings}
+                            {markdown_sync_settings}
+                        }
+                    }
+
+                    // First-run welcome
+                    if show_welcome {
+                        WelcomePrompt {
+                            on_accept: move |_| {
+                                add_sample_todos(());
+                                onboarding_done.set(true);
+                                tour_step.set(Some(0));
+                            },
+                            on_decline: move |_| {
+                                onboarding_done.set(true);
+                                tour_step.set(Some(0));
+                            },
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+
+                    // Todo form
+                    div { class: tour_highlight_class(tour_step(), 0),
+                        TodoForm {
+                            on_add: add_todo,
+                            on_paste: add_shared_todo,
+                            on_add_linked: add_linked_todo,
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
 
-                // Todo list
-                div { class: "transition-all duration-300 mt-4",
-                    TodoListComponent {
-                        todos,
-                        filter: filter(),
-                        search_text: search_text(),
-                        on_toggle: toggle_todo,
-                        on_delete: delete_todo,
-                        on_update: update_todo,
-                        on_due_date_change: set_due_date,
-                        on_tag_add: add_tag_to_todo,
-                        on_tag_remove: remove_tag_from_todo,
-                        on_reorder: reorder_todo,
-                        selected_tag: selected_tag(),
+                    // Next days, to drop todos on or filter by
+                    DayPlanner {
+                        days: dates::upcoming_days(today, PLANNER_DAYS),
+                        selected: current.due_on,
+                        dragging: dragged_todo().is_some(),
+                        drop_target: drop_day(),
+                        on_select: move |day| criteria.write().due_on = day,
+                        on_drag_target: move |day| drop_day.set(day),
                         is_dark_mode: is_dark_mode(),
-                        default_tags: Some(DEFAULT_TAGS.iter().map(|s| s.to_string()).collect()),
                     }
-                }
 
-                // Filter bar
-                FilterBar {
-                    filter: filter(),
-                    on_filter_change: change_filter,
-                    active_count,
-                    completed_count,
-                    on_clear_completed: move |_| clear_completed(()),
-                    is_dark_mode: is_dark_mode(),
+                    // Search box
+                    SearchBox {
+                        search_term: current.search_text.clone(),
+                        on_search,
+                        is_dark_mode: is_dark_mode(),
+                    }
+
+                    // Tags filter
+                    div { class: tour_highlight_class(tour_step(), 2),
+                        TagsFilter {
+                            todo_list,
+                            default_tags: &DEFAULT_TAGS,
+                            selected_tag: current.tag.clone(),
+                            on_select_tag: select_tag,
+                            on_delete_tag: delete_tag,
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+
+                    // Quick filter pills
+                    if !searching_trash {
+                        QuickFilters {
+                            counts: quick_filter_counts,
+                            active: current.quick_filter,
+                            on_change: move |pill| criteria.write().quick_filter = pill,
+                            stale_count,
+                            stale_active,
+                            // The stale view is the `is:stale` search
+                            on_toggle_stale: move |on: bool| criteria.write().search_text = if on { "is:stale" } else { "" }.to_string(),
+                            new_count: (new_count > 0 || new_active).then_some(new_count),
+                            new_active,
+                            // So is the view of todos added since launch
+                            on_toggle_new: move |on: bool| criteria.write().search_text = if on { "is:new" } else { "" }.to_string(),
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+
+                    // List or timeline
+                    if !searching_trash {
+                        div { class: "mt-3",
+                            ViewSwitcher {
+                                timeline: show_timeline(),
+                                on_change: move |on| show_timeline.set(on),
+                                is_dark_mode: is_dark_mode(),
+                            }
+                        }
+                    }
+
+                    // Todo list
+                    if loading {
+                        p {
+                            class: "mt-4 py-8 text-center text-sm {loading_text_class}",
+                            role: "status",
+                            "Loading todos…"
+                        }
+                    } else if show_timeline() && !searching_trash {
+                        div { class: "mt-2",
+                            Timeline {
+                                todos: timeline_todos,
+                                today,
+                                on_due_date_change: set_due_date,
+                                is_dark_mode: is_dark_mode(),
+                            }
+                        }
+                    } else {
+                    div { class: "{list_motion_class} mt-4",
+                        TodoListComponent {
+                            todos: listed_todos,
+                            in_trash: searching_trash,
+                            // The pills and the planner day do not apply to the trash
+                            criteria: if searching_trash {
+                                FilterCriteria { quick_filter: None, due_on: None, ..current.clone() }
+                            } else {
+                                current.clone()
+                            },
+                            week_start,
+                            on_toggle: toggle_todo,
+                            on_toggle_all: move |ids| {
+                                toggle_all(Some(ids));
+                            },
+                            on_delete: delete_todo,
+                            on_restore: restore_from_trash,
+                            on_purge: purge_from_trash,
+                            on_unarchive: move |id| unarchive_todos(vec![id]),
+                            on_update: update_todo,
+                            on_due_date_change: set_due_date,
+                            on_tag_add: add_tag_to_todo,
+                            on_tag_remove: remove_tag_from_todo,
+                            on_notes_change: set_notes,
+                            on_url_change: set_url,
+                            on_subtask_add: add_subtask,
+                            on_subtask_toggle: toggle_subtask,
+                            on_subtask_remove: remove_subtask,
+                            on_extract: extract_from_notes,
+                            on_reveal: reveal_todo,
+                            on_lock_change: set_locked,
+                            confirm_unlock: settings.read().confirm_unlock,
+                            on_reorder: reorder_todo,
+                            reorderable: sort_mode() == SortMode::ManualOrder,
+                            on_move_to_position: move |id| moving_todo.set(Some(id)),
+                            on_drag_start: move |id| dragged_todo.set(Some(id)),
+                            // A drop on a planner day schedules the todo instead of moving it
+                            on_drag_end: move |id| {
+                                dragged_todo.set(None);
+                                let Some(day) = drop_day() else {
+                                    return false;
+                                };
+                                drop_day.set(None);
+                                set_due_date((id, Some(dates::due_date_on(day))));
+                                true
+                            },
+                            selected_id: if split_view() { selected_todo() } else { None },
+                            on_select: move |id| selected_todo.set(Some(id)),
+                            show_week_numbers: settings.read().show_week_numbers,
+                            show_minimap: settings.read().show_minimap,
+                            compact: compact_rows,
+                            issue_linker: issue_linker(),
+                            show_stale,
+                            stale_after_days,
+                            session_start: Some(session_start),
+                            show_row_numbers: show_row_numbers() && !searching_trash,
+                            is_dark_mode: is_dark_mode(),
+                            suggest_tags,
+                            on_suppress_tag: suppress_tag,
+                        }
+                    }
+                    }
+
+                    // Filter bar
+                    div { class: tour_highlight_class(tour_step(), 1),
+                        FilterBar {
+                            filter: current.filter,
+                            on_filter_change: change_filter,
+                            active_count,
+                            completed_count,
+                            overdue_count,
+                            visible_completed_count: visible_completed_ids.len(),
+                            on_clear_completed: move |_| clear_completed(Some(visible_completed_ids.clone())),
+                            on_clear_all_completed: move |_| clear_completed(None),
+                            on_archive_completed: move |_| archive_completed(archivable_ids.clone()),
+                            on_shift_dates: move |_| show_shift_dates.set(true),
+                            sort_mode: sort_mode(),
+                            on_sort_change: move |mode| sort_mode.set(mode),
+                            on_tag_visible: move |_| batch_tag.set(Some(BatchTagMode::Add)),
+                            on_untag_visible: move |_| batch_tag.set(Some(BatchTagMode::Remove)),
+                            on_find_duplicates: move |_| duplicate_groups.set(Some(find_duplicates(&todo_list.read()))),
+                            trash_count,
+                            on_empty_trash: move |_| {
+                                let emptied = empty_trash(());
+                                show_toast(ToastMessage {
+                                    text: format!(
+                                        "Deleted {} {} for good",
+                                        emptied,
+                                        if emptied == 1 { "todo" } else { "todos" }
+                                    ),
+                                    action: None,
+                                });
+                            },
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+
+                    // Karma and weekly goal
+                    if let Some((karma, progress)) = karma_summary {
+                        KarmaSummary { karma, progress, is_dark_mode: is_dark_mode() }
+                    }
+                    if !most_postponed.is_empty() {
+                        MostPostponed {
+                            todos: most_postponed,
+                            on_open_todo: reveal_todo,
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+
+                    // Read-only link to the todos shown
+                    if cfg!(feature = "web") && !searching_trash {
+                        ShareSnapshotButton { todos: snapshot_todos, is_dark_mode: is_dark_mode() }
+                    }
+
+                    // Keyboard shortcuts help
+                    div { class: tour_highlight_class(tour_step(), 3),
+                        KeyboardShortcuts { is_dark_mode: is_dark_mode() }
+                    }
+
+                    // Large list warning
+                    if show_limit_warning {
+                        LimitWarning {
+                            count: todo_count,
+                            limit: todo_count_warning,
+                            on_open_settings: move |_| {
+                                show_settings.set(true);
+                                limit_warning_dismissed.set(true);
+                            },
+                            on_dismiss: move |_| limit_warning_dismissed.set(true),
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+
+                    if let Some(message) = toast() {
+                        Toast {
+                            message,
+                            on_action: move |action| match action {
+                                ToastAction::RestoreFromTrash(ids) => restore_many_from_trash(ids),
+                                ToastAction::RestoreCleared(todos) => restore_cleared(todos),
+                                ToastAction::Unarchive(ids) => unarchive_todos(ids),
+                                ToastAction::ShiftDueDates(ids, days) => {
+                                    shift_due_dates((ids, days));
+                                }
+                                ToastAction::RestoreOrder(snapshot) => restore_order(snapshot),
+                                ToastAction::AddTag(ids, tag) => {
+                                    add_tag_many((ids, tag));
+                                }
+                                ToastAction::RemoveTag(ids, tag) => {
+                                    remove_tag_many((ids, tag));
+                                }
+                                ToastAction::UndoChange => undo_change(()),
+                            },
+                            on_dismiss: move |_| toast.set(None),
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+
+                    // Shift due dates
+                    if show_shift_dates() {
+                        ShiftDatesDialog {
+                            count: visible_dated_ids.len(),
+                            range: todo_list.read().due_date_range(&visible_dated_ids),
+                            on_apply: {
+                                let ids = visible_dated_ids.clone();
+                                move |days| {
+                                    show_shift_dates.set(false);
+                                    shift_dates((ids.clone(), days));
+                                }
+                            },
+                            on_close: move |_| show_shift_dates.set(false),
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+
+                    // Tag or untag all visible
+                    if let Some(mode) = batch_tag() {
+                        BatchTagDialog {
+                            mode,
+                            count: shown_ids.len(),
+                            tags: match mode {
+                                BatchTagMode::Add => todo_list.read().sorted_tags(&DEFAULT_TAGS),
+                                BatchTagMode::Remove => todo_list.read().tags_of(&shown_ids),
+                            },
+                            on_apply: {
+                                let ids = shown_ids.clone();
+                                move |tag| {
+                                    batch_tag.set(None);
+                                    tag_todos((mode, ids.clone(), tag));
+                                }
+                            },
+                            on_close: move |_| batch_tag.set(None),
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+
+                    // Move a todo to a typed position
+                    if let Some((todo, shown)) = moving {
+                        MovePositionDialog {
+                            todo,
+                            shown,
+                            on_move: move |(index, position)| {
+                                if let Some(id) = moving_todo() {
+                                    moving_todo.set(None);
+                                    move_to_index((id, index, position));
+                                }
+                            },
+                            on_close: move |_| moving_todo.set(None),
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+
+                    // Merge duplicates
+                    if let Some(groups) = duplicate_groups() {
+                        DuplicatesDialog {
+                            groups,
+                            on_merge: move |merges| {
+                                duplicate_groups.set(None);
+                                let removed = merge_duplicates(merges);
+                                show_toast(ToastMessage {
+                                    text: format!(
+                                        "Merged {} {}",
+                                        removed,
+                                        if removed == 1 { "duplicate" } else { "duplicates" }
+                                    ),
+                                    action: (removed > 0).then_some(ToastAction::UndoChange),
+                                });
+                            },
+                            on_close: move |_| duplicate_groups.set(None),
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+
+                    // Release notes
+                    if !whats_new.read().is_empty() {
+                        WhatsNewDialog {
+                            notes: whats_new(),
+                            on_close: move |_| whats_new.set(Vec::new()),
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+
+                    // Quick switcher
+                    if show_palette() {
+                        CommandPalette {
+                            registry: commands,
+                            tags: todo_list.read().sorted_tags(&DEFAULT_TAGS),
+                            todos: todos.clone(),
+                            on_select_tag: move |tag| criteria.write().tag = TagFilter::Tag(tag),
+                            on_reveal_todo: reveal_todo,
+                            on_close: move |_| show_palette.set(false),
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+
+                    // Debug log
+                    if show_debug_log() {
+                        DebugLogPanel {
+                            on_close: move |_| show_debug_log.set(false),
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+
+                    // Guided tour
+                    if let Some(step) = tour_step() {
+                        OnboardingTour {
+                            step,
+                            on_step_change: move |step| tour_step.set(step),
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
                 }
 
-                // Keyboard shortcuts help
-                KeyboardShortcuts { is_dark_mode: is_dark_mode() }
+                // Details of the selected todo, docked beside the list
+                if split_view() {
+                    div { class: "sticky top-8",
+                        TodoDetail {
+                            todo: selected_detail,
+                            on_toggle: toggle_todo,
+                            on_notes_change: set_notes,
+                            on_url_change: set_url,
+                            on_extract: extract_from_notes,
+                            on_restore_text: restore_text,
+                            snoozed_until: selected_snooze,
+                            on_cancel_snooze: cancel_snooze,
+                            is_dark_mode: is_dark_mode(),
+                        }
+                    }
+                }
             }
         }
     }
@@ -0,0 +1,275 @@
+use crate::components::{TodoLinkButton, TodoNotes, TodoUrlField};
+use crate::models::Todo;
+use crate::utils;
+use crate::utils::keys;
+use crate::utils::theme;
+use chrono::{DateTime, Local, Utc};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Logic for remembering the todo shown in the docked detail panel
+///
+/// Returns the selected todo id, `None` when nothing is selected, persisted
+/// whenever it changes so the panel shows the same todo after a restart.
+pub fn use_selected_todo() -> Signal<Option<usize>> {
+    let selected =
+        use_signal(|| utils::load::<Option<usize>>(keys::SELECTED_TODO).unwrap_or_default());
+
+    use_effect(move || {
+        let _ = utils::save(keys::SELECTED_TODO, &*selected.read());
+    });
+
+    selected
+}
+
+/// Formats a time for the detail panel, like "Mar 18, 2025 14:05".
+fn format_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local)
+        .format("%b %d, %Y %H:%M")
+        .to_string()
+}
+
+/// Formats a time of day for the detail panel, like "15:30".
+fn format_clock_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local).format("%H:%M").to_string()
+}
+
+/// Panel docked beside the list showing everything about the selected todo
+///
+/// Shows a hint while no todo is selected, or when the selected todo no
+/// longer exists.
+#[component]
+pub fn TodoDetail(
+    todo: Option<Rc<Todo>>,
+    on_toggle: EventHandler<usize>,
+    on_notes_change: EventHandler<(usize, String)>,
+    #[props(default)] on_url_change: EventHandler<(usize, Option<String>)>,
+    on_extract: EventHandler<(usize, usize, bool)>,
+    /// Called with the todo's id and the position of an earlier text to bring back
+    #[props(default)]
+    on_restore_text: EventHandler<(usize, usize)>,
+    /// When the todo's snoozed reminder comes back, if it is snoozed
+    #[props(default)]
+    snoozed_until: Option<DateTime<Utc>>,
+    #[props(default)] on_cancel_snooze: EventHandler<usize>,
+    is_dark_mode: bool,
+) -> Element {
+    let panel_class = theme::card_class(is_dark_mode);
+    let label_class = theme::muted_text_class(is_dark_mode);
+    let tag_class = theme::tint_class(theme::Accent::Blue, is_dark_mode);
+    let button_class = theme::secondary_button_class(is_dark_mode);
+
+    let Some(todo) = todo else {
+        return rsx! {
+            aside { class: "p-6 rounded-lg shadow-md border text-sm italic {panel_class} {label_class}",
+                "Select a todo to see its details. Ctrl+↑ and Ctrl+↓ move the selection."
+            }
+        };
+    };
+
+    let todo_id = todo.id;
+    let facts = [
+        ("Due", todo.due_date.map(format_time)),
+        ("Added", todo.created_at.map(format_time)),
+        ("Changed", todo.updated_at.map(format_time)),
+        ("Completed", todo.completed_at.map(format_time)),
+    ];
+
+    rsx! {
+        aside {
+            class: "p-6 rounded-lg shadow-md border text-sm space-y-4 {panel_class}",
+            aria_label: "Todo details",
+            h2 { class: "text-lg font-semibold break-words [overflow-wrap:anywhere]",
+                if todo.locked {
+                    span { class: "mr-1", title: "Locked", "🔒" }
+                }
+                "{todo.text}"
+            }
+            div { class: "flex items-center gap-2",
+                span { class: "{label_class}",
+                    if todo.completed { "Completed" } else { "Open" }
+                }
+                button {
+                    r#type: "button",
+                    class: "{button_class}",
+                    onclick: move |_| on_toggle.call(todo_id),
+                    if todo.completed { "Reopen" } else { "Mark done" }
+                }
+            }
+            dl { class: "grid grid-cols-[auto_1fr] gap-x-4 gap-y-1",
+                for (label, value) in facts {
+                    if let Some(value) = value {
+                        dt { class: "{label_class}", "{label}" }
+                        dd { "{value}" }
+                    }
+                }
+            }
+            if let Some(until) = snoozed_until {
+                p { class: "flex items-center gap-2 text-xs {label_class}",
+                    span { "snoozed until {format_clock_time(until)}" }
+                    button {
+                        r#type: "button",
+                        class: "{button_class}",
+                        title: "Remind me again now",
+                        onclick: move |_| on_cancel_snooze.call(todo_id),
+                        "Cancel"
+                    }
+                }
+            }
+            if todo.postponed_count > 0 {
+                p {
+                    class: "text-xs {label_class}",
+                    title: "Times the due date was moved later",
+                    "postponed ×{todo.postponed_count}"
+                }
+            }
+            if !todo.tags.is_empty() {
+                div { class: "flex flex-wrap gap-1.5",
+                    for tag in todo.tags.iter() {
+                        span { class: "text-xs px-2 py-0.5 rounded-full {tag_class}", "{tag}" }
+                    }
+                }
+            }
+            div { class: "flex items-center gap-2",
+                div { class: "flex-1 min-w-0",
+                    TodoUrlField {
+                        key: "{todo_id}",
+                        todo_id,
+                        url: todo.url.clone(),
+                        on_url_change,
+                        is_dark_mode,
+                    }
+                }
+                if let Some(url) = todo.url.clone() {
+                    TodoLinkButton { url, class: "{button_class}" }
+                }
+            }
+            div {
+                h3 { class: "mb-1 {label_class}", "Notes" }
+                TodoNotes {
+                    key: "{todo_id}",
+                    todo_id,
+                    notes: todo.notes.clone(),
+                    on_notes_change,
+                    on_extract,
+                    is_dark_mode,
+                }
+            }
+            if !todo.text_history.is_empty() {
+                div {
+                    h3 { class: "mb-1 {label_class}", "History" }
+                    // Newest first, each shown with when it was replaced
+                    ul { class: "space-y-1",
+                        for (index, (replaced_at, text)) in todo.text_history.iter().enumerate().rev() {
+                            li { key: "{index}", class: "flex items-start gap-2",
+                                span { class: "flex-1 min-w-0 break-words [overflow-wrap:anywhere]",
+                                    "{text}"
+                                }
+                                span { class: "shrink-0 text-xs {label_class}",
+                                    "{format_time(*replaced_at)}"
+                                }
+                                button {
+                                    r#type: "button",
+                                    class: "shrink-0 text-xs {button_class}",
+                                    disabled: todo.locked,
+                                    onclick: move |_| on_restore_text.call((todo_id, index)),
+                                    "Restore"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TodoList;
+    use chrono::TimeZone;
+    use dioxus::dioxus_core::{Mutation, Mutations};
+
+    /// Renders the panel and collects the text of its dynamic text nodes.
+    fn rendered_texts(todo: Option<Rc<Todo>>) -> Vec<String> {
+        rendered_snoozed_texts(todo, None)
+    }
+
+    /// Renders the panel for a todo whose reminder may be snoozed.
+    fn rendered_snoozed_texts(
+        todo: Option<Rc<Todo>>,
+        snoozed_until: Option<DateTime<Utc>>,
+    ) -> Vec<String> {
+        let mut app = VirtualDom::new_with_props(
+            |(todo, snoozed_until): (Option<Rc<Todo>>, Option<DateTime<Utc>>)| {
+                rsx! {
+                    TodoDetail {
+                        todo,
+                        on_toggle: |_| {},
+                        on_notes_change: |_| {},
+                        on_extract: |_| {},
+                        snoozed_until,
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            (todo, snoozed_until),
+        );
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        mutations
+            .edits
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Mutation::CreateTextNode { value, .. } => Some(value),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_todo_detail_shows_selected_todo() {
+        let mut todo = Todo::new(1, "Renew passport".to_string());
+        todo.tags.push("Personal".to_string());
+        let texts = rendered_texts(Some(Rc::new(todo.clone())));
+        assert!(texts.contains(&"Renew passport".to_string()));
+        assert!(texts.contains(&"Personal".to_string()));
+        assert!(!texts.iter().any(|text| text.starts_with("postponed")));
+
+        todo.postponed_count = 4;
+        let texts = rendered_texts(Some(Rc::new(todo)));
+        assert!(texts.contains(&"postponed ×4".to_string()), "{texts:?}");
+    }
+
+    #[test]
+    fn test_todo_detail_lists_earlier_texts() {
+        let mut list = TodoList::new();
+        let id = list.add("Renew pasport".to_string());
+        list.update_text(id, "Renew passport".to_string()).unwrap();
+        list.update_text(id, "Renew passport by May".to_string())
+            .unwrap();
+
+        let texts = rendered_texts(list.get(id).cloned().map(Rc::new));
+        for text in ["Renew passport by May", "Renew passport", "Renew pasport"] {
+            assert!(texts.contains(&text.to_string()), "{texts:?}");
+        }
+    }
+
+    #[test]
+    fn test_todo_detail_shows_snooze() {
+        let todo = Rc::new(Todo::new(1, "Send the invoice".to_string()));
+        let texts = rendered_snoozed_texts(Some(todo.clone()), None);
+        assert!(!texts.iter().any(|text| text.starts_with("snoozed")));
+
+        let until = Local
+            .with_ymd_and_hms(2025, 3, 12, 15, 30, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        let texts = rendered_snoozed_texts(Some(todo), Some(until));
+        assert!(
+            texts.contains(&"snoozed until 15:30".to_string()),
+            "{texts:?}"
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::components::clock::use_clock;
+use crate::components::{DatePicker, Modal, ModalSize, TodoUrlField};
+use crate::models::due_limits::DueDateLimits;
+use crate::utils::{dates, theme};
+use chrono::{DateTime, NaiveDate, Utc};
+use dioxus::prelude::*;
+
+/// Editor for a todo's due date and link, in a dialog
+///
+/// Picking a date, or clearing it, saves it and closes the editor. A date
+/// outside the [`DueDateLimits`] is not saved; the editor stays open and
+/// says what is wrong with it.
+#[component]
+pub(super) fn DueDateEditor(
+    todo_id: usize,
+    due_date: Option<DateTime<Utc>>,
+    url: Option<String>,
+    on_due_date_change: EventHandler<(usize, Option<DateTime<Utc>>)>,
+    on_url_change: EventHandler<(usize, Option<String>)>,
+    on_close: EventHandler<()>,
+    is_dark_mode: bool,
+) -> Element {
+    let clock = use_clock();
+    let mut date_error = use_signal(|| None::<String>);
+
+    let handle_date_change = move |date: Option<NaiveDate>| {
+        if let Some(date) = date
+            && let Err(e) = DueDateLimits::default().check_day(date, clock.today_local())
+        {
+            date_error.set(Some(e.to_string()));
+            return;
+        }
+        let new_due_date = date.map(dates::due_date_on);
+        if new_due_date != due_date {
+            on_due_date_change.call((todo_id, new_due_date));
+        }
+        on_close.call(());
+    };
+
+    let date_text_class = theme::secondary_text_class(is_dark_mode);
+    let error_class = theme::danger_text_class(is_dark_mode);
+
+    rsx! {
+      Modal {
+        title: "Due date and link",
+        on_close,
+        size: ModalSize::Small,
+        is_dark_mode,
+        div { class: "flex items-center space-x-2",
+          label { class: "text-xs font-medium {date_text_class}", "Due:" }
+          DatePicker {
+            value: due_date.map(|dt| dt.date_naive()),
+            on_change: handle_date_change,
+            label: "Due date",
+            is_dark_mode,
+          }
+        }
+        if let Some(error) = date_error() {
+          p { class: "mt-1 text-xs {error_class}", role: "alert", "{error}" }
+        }
+        div { class: "mt-3",
+          TodoUrlField { todo_id, url, on_url_change, is_dark_mode }
+        }
+      }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use crate::utils::dates;
+    use chrono::NaiveDate;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Calls = Rc<RefCell<Vec<Option<Option<DateTime<Utc>>>>>>;
+
+    fn march(day: u32) -> DateTime<Utc> {
+        dates::due_date_on(NaiveDate::from_ymd_opt(2025, 3, day).unwrap())
+    }
+
+    /// Picks `value` in the editor of a todo due March 12, recording changes
+    /// as `Some` and closing as `None`.
+    fn pick(value: &str) -> Vec<Option<Option<DateTime<Utc>>>> {
+        let calls = Calls::default();
+        let mut dom = TestDom::with_props(
+            |calls: Calls| {
+                let closed = calls.clone();
+                rsx! {
+                    DueDateEditor {
+                        todo_id: 1,
+                        due_date: Some(march(12)),
+                        url: None,
+                        on_due_date_change: move |(_, due)| calls.borrow_mut().push(Some(due)),
+                        on_url_change: |_| {},
+                        on_close: move |_| closed.borrow_mut().push(None),
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            calls.clone(),
+        );
+
+        let picker = dom
+            .find_by_attribute("input", "aria-label", "Due date")
+            .unwrap();
+        assert_eq!(dom.attribute(picker, "value"), Some("2025-03-12"));
+        dom.change(picker, value);
+        calls.take()
+    }
+
+    #[test]
+    fn test_picking_a_date_saves_it_and_closes() {
+        assert_eq!(pick("2025-03-15"), vec![Some(Some(march(15))), None]);
+        assert_eq!(pick(""), vec![Some(None), None]);
+    }
+
+    #[test]
+    fn test_picking_the_same_date_only_closes() {
+        assert_eq!(pick("2025-03-12"), vec![None]);
+    }
+
+    #[test]
+    fn test_dates_outside_the_limits_are_not_saved() {
+        assert_eq!(pick("0200-03-12"), vec![]);
+        assert_eq!(pick("9999-03-12"), vec![]);
+    }
+
+    #[test]
+    fn test_a_date_outside_the_limits_is_explained() {
+        let mut dom = TestDom::with_props(
+            |_: ()| {
+                rsx! {
+                    DueDateEditor {
+                        todo_id: 1,
+                        due_date: None,
+                        url: None,
+                        on_due_date_change: |_| unreachable_pick(),
+                        on_url_change: |_| {},
+                        on_close: |_| unreachable_pick(),
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            (),
+        );
+        let picker = dom
+            .find_by_attribute("input", "aria-label", "Due date")
+            .unwrap();
+        dom.change(picker, "0200-03-12");
+
+        let alert = dom.find_by_attribute("p", "role", "alert").unwrap();
+        assert_eq!(
+            dom.text_of(alert),
+            "Due date 0200-03-12 is before 1970-01-01, the earliest allowed"
+        );
+    }
+
+    fn unreachable_pick() {
+        panic!("nothing was picked");
+    }
+
+    #[test]
+    fn test_escape_closes_without_saving() {
+        let closed = Rc::new(RefCell::new(0));
+        let mut dom = TestDom::with_props(
+            |closed: Rc<RefCell<usize>>| {
+                rsx! {
+                    DueDateEditor {
+                        todo_id: 1,
+                        due_date: None,
+                        url: None,
+                        on_due_date_change: |_| unreachable_pick(),
+                        on_url_change: |_| {},
+                        on_close: move |_| *closed.borrow_mut() += 1,
+                        is_dark_mode: true,
+                    }
+                }
+            },
+            closed.clone(),
+        );
+
+        let dialog = dom.find_by_attribute("div", "role", "dialog").unwrap();
+        dom.key_down(dialog, Key::Escape);
+        assert_eq!(*closed.borrow(), 1);
+    }
+}
@@ -0,0 +1,83 @@
+use super::TodoAction;
+use crate::utils::theme;
+use dioxus::prelude::*;
+
+/// Menu opened by right-clicking a todo, or from its "⋯" button on narrow windows
+///
+/// Offers locking and unlocking, moving to a typed position when the list
+/// can be reordered, and with `show_actions` also the actions of the row's
+/// buttons, which do not fit beside the text then.
+#[component]
+pub(super) fn TodoItemMenu(
+    locked: bool,
+    confirming_unlock: bool,
+    show_actions: bool,
+    notes_open: bool,
+    subtasks_open: bool,
+    movable: bool,
+    on_action: EventHandler<TodoAction>,
+    is_dark_mode: bool,
+) -> Element {
+    let menu_class = theme::menu_class(is_dark_mode);
+    let menu_item_class = theme::menu_item_class(is_dark_mode);
+    let item = move |action: TodoAction, label: &'static str| {
+        rsx! {
+          button {
+            r#type: "button",
+            class: "w-full text-left px-2 py-1 rounded {menu_item_class}",
+            role: "menuitem",
+            onclick: move |_| on_action.call(action),
+            "{label}"
+          }
+        }
+    };
+
+    rsx! {
+      div {
+        class: "absolute right-4 top-10 z-40 w-56 p-1 rounded-lg border shadow-lg text-sm {menu_class}",
+        role: "menu",
+        if confirming_unlock {
+          p { class: "px-2 py-1", "Unlock this todo? It can then be edited and deleted." }
+          div { class: "flex justify-end gap-1 p-1",
+            button {
+              r#type: "button",
+              class: "px-2 py-1 rounded {menu_item_class}",
+              onclick: move |_| on_action.call(TodoAction::Close),
+              "Cancel"
+            }
+            button {
+              r#type: "button",
+              class: "px-2 py-1 rounded bg-amber-500 text-white hover:bg-amber-600",
+              onclick: move |_| on_action.call(TodoAction::ToggleLock),
+              "Unlock"
+            }
+          }
+        } else {
+          if show_actions {
+            if !locked {
+              {item(TodoAction::Edit, "✏️ Edit")}
+            }
+            {item(TodoAction::DueDate, "📅 Due date")}
+            {item(TodoAction::Tags, "🏷️ Tags")}
+            {item(TodoAction::Notes, if notes_open { "📝 Hide notes" } else { "📝 Notes" })}
+            {item(TodoAction::Subtasks, if subtasks_open { "☑️ Hide subtasks" } else { "☑️ Subtasks" })}
+            {item(TodoAction::Copy, "📋 Copy as text")}
+            if !locked {
+              {item(TodoAction::Delete, "🗑️ Delete")}
+            }
+          }
+          if movable {
+            {item(TodoAction::MoveToPosition, "↕️ Move to position…")}
+          }
+          button {
+            r#type: "button",
+            class: "w-full text-left px-2 py-1 rounded {menu_item_class}",
+            role: "menuitem",
+            autofocus: true,
+            onclick: move |_| on_action.call(TodoAction::ToggleLock),
+            if locked { "🔓 Unlock" } else { "🔒 Lock" }
+          }
+        }
+      }
+    }
+}
@@ -0,0 +1,924 @@
+//! A single todo in the list
+//!
+//! [`TodoItem`] holds the row's state, which editors are open and the text
+//! being edited, and lays out its parts: the [`row::TodoRow`] with the text
+//! and actions, the menu, the notes, the subtasks, and the due date and tag
+//! editors below.
+
+mod due_date_editor;
+mod menu;
+mod row;
+mod subtasks;
+mod tag_editor;
+mod tag_pills;
+
+use crate::components::theme_manager::use_motion;
+use crate::components::{TodoNotes, use_modal};
+use crate::models::{IssueLinker, Todo};
+use crate::utils::dates::IsoWeek;
+use crate::utils::text::TextError;
+use crate::utils::{clipboard, dates, share, text, theme};
+use chrono::{DateTime, Datelike, Local, Utc};
+use dioxus::prelude::*;
+use due_date_editor::DueDateEditor;
+use menu::TodoItemMenu;
+use row::TodoRow;
+use std::rc::Rc;
+use subtasks::SubtaskList;
+use tag_editor::TagEditor;
+
+/// Props for the TodoItem component.
+#[derive(Props, PartialEq, Clone)]
+pub struct TodoItemProps {
+    /// The todo item to display, shared with the list to avoid cloning it per render
+    pub todo: Rc<Todo>,
+    /// Callback when the todo is toggled
+    pub on_toggle: EventHandler<usize>,
+    /// Callback when the todo is deleted
+    pub on_delete: EventHandler<usize>,
+    /// Whether the todo is in the trash, which replaces the actions with a restore button
+    #[props(default = false)]
+    pub in_trash: bool,
+    /// Callback when the todo is restored from the trash
+    pub on_restore: EventHandler<usize>,
+    /// Callback when the todo in the trash is deleted for good
+    #[props(default)]
+    pub on_purge: EventHandler<usize>,
+    /// Callback when the archived todo is brought back into the list
+    #[props(default)]
+    pub on_unarchive: EventHandler<usize>,
+    /// Callback when the todo text is updated
+    pub on_update: EventHandler<(usize, String)>,
+    /// Callback when the due date is updated
+    pub on_due_date_change: EventHandler<(usize, Option<DateTime<Utc>>)>,
+    /// Callback when a tag is added
+    pub on_tag_add: EventHandler<(usize, String)>,
+    /// Callback when a tag is removed
+    pub on_tag_remove: EventHandler<(usize, String)>,
+    /// Callback when the notes are edited
+    pub on_notes_change: EventHandler<(usize, String)>,
+    /// Callback when the link is set or cleared
+    #[props(default)]
+    pub on_url_change: EventHandler<(usize, Option<String>)>,
+    /// Callback when a subtask is added
+    #[props(default)]
+    pub on_subtask_add: EventHandler<(usize, String)>,
+    /// Callback when a subtask is checked off or reopened, given the todo and subtask ids
+    #[props(default)]
+    pub on_subtask_toggle: EventHandler<(usize, usize)>,
+    /// Callback when a subtask is deleted, given the todo and subtask ids
+    #[props(default)]
+    pub on_subtask_remove: EventHandler<(usize, usize)>,
+    /// Callback to create a todo from a line of the notes
+    pub on_extract: EventHandler<(usize, usize, bool)>,
+    /// Text of the todo this one was extracted from, if it still exists
+    #[props(default)]
+    pub parent_text: Option<String>,
+    /// Callback to bring another todo into view
+    pub on_reveal: EventHandler<usize>,
+    /// Callback when the todo is locked or unlocked
+    pub on_lock_change: EventHandler<(usize, bool)>,
+    /// Whether the list can be reordered, offering "Move to position…" in the menu
+    #[props(default = false)]
+    pub movable: bool,
+    /// Callback to pick a position to move the todo to
+    #[props(default)]
+    pub on_move_to_position: EventHandler<usize>,
+    /// Whether unlocking asks for confirmation first
+    #[props(default = false)]
+    pub confirm_unlock: bool,
+    /// Whether the due date shows its ISO week number
+    #[props(default = false)]
+    pub show_week_number: bool,
+    /// Days the todo has gone unchanged, shown as an aging clock when it is stale
+    #[props(default)]
+    pub stale_days: Option<i64>,
+    /// Whether the row is laid out for a narrow window, with the action
+    /// buttons in a "⋯" menu and the tags on a row of their own
+    #[props(default = false)]
+    pub compact: bool,
+    /// Rules linking issue-tracker references in the text, shared by every item in the list
+    #[props(default)]
+    pub issue_linker: Rc<IssueLinker>,
+    /// Whether dark mode is enabled
+    #[props(default = false)]
+    pub is_dark_mode: bool,
+    /// Gets the tags to suggest from the tags a todo already has
+    #[props(default)]
+    pub suggest_tags: Callback<Vec<String>, Vec<String>>,
+    /// Callback when a tag should no longer be suggested
+    #[props(default)]
+    pub on_suppress_tag: EventHandler<String>,
+}
+
+/// Renders a single todo item with toggle, edit, and delete functionality.
+#[component]
+pub fn TodoItem(props: TodoItemProps) -> Element {
+    let row_motion_class = use_motion("transition-all duration-200 ease-in-out");
+    let motion_class = use_motion("transition-colors duration-200");
+
+    // Closures share the todo through the Rc; text is only copied when an edit starts or is reset
+    let todo = props.todo.clone();
+    let todo_id = todo.id;
+    let todo_due_date = todo.due_date;
+    let todo_locked = todo.locked;
+
+    let mut editing = use_signal(|| false);
+    let mut edit_text = use_signal(|| todo.text.clone());
+    let date_editor = use_modal();
+    let mut tag_editing = use_signal(|| false);
+    let mut show_notes = use_signal(|| false);
+    let mut show_subtasks = use_signal(|| false);
+    let mut menu_open = use_signal(|| false);
+    let mut confirming_unlock = use_signal(|| false);
+
+    let mut toggle_editing = {
+        let todo = todo.clone();
+        let in_trash = props.in_trash;
+        move || {
+            if in_trash || todo_locked {
+                return;
+            }
+            let current_editing = editing();
+            editing.set(!current_editing);
+            if !current_editing {
+                edit_text.set(todo.text.clone());
+                tag_editing.set(false);
+                date_editor.close();
+            }
+        }
+    };
+
+    // Submitting and leaving the editor follow the same rules: valid changes are
+    // saved, empty text reverts, and overlong text keeps the editor open
+    let commit_edit = {
+        let todo = todo.clone();
+        move |_: ()| {
+            let validated = text::validate_todo_text(&edit_text.read());
+            match validated {
+                Ok(valid) if valid != todo.text => {
+                    props.on_update.call((todo_id, valid));
+                    editing.set(false);
+                }
+                Err(TextError::TooLong { .. }) => {}
+                Ok(_) | Err(TextError::Empty) => {
+                    editing.set(false);
+                    edit_text.set(todo.text.clone());
+                }
+            }
+        }
+    };
+
+    let mut toggle_date_editing = move || {
+        if date_editor.is_open() {
+            date_editor.close();
+        } else {
+            date_editor.open();
+            editing.set(false);
+            tag_editing.set(false);
+        }
+    };
+
+    let mut toggle_tag_editing = move || {
+        let is_editing = !tag_editing();
+        tag_editing.set(is_editing);
+        if is_editing {
+            editing.set(false);
+            date_editor.close();
+        }
+    };
+
+    let handle_key_press = {
+        let todo = todo.clone();
+        move |evt: Event<KeyboardData>| {
+            if evt.key().to_string() == "Escape" {
+                if editing() {
+                    editing.set(false);
+                    edit_text.set(todo.text.clone());
+                }
+                if date_editor.is_open() {
+                    date_editor.close();
+                }
+                if tag_editing() {
+                    tag_editing.set(false);
+                }
+            }
+        }
+    };
+
+    // Locked todos only unlock through the menu, after confirming when the setting asks for it
+    let open_menu = {
+        let in_trash = props.in_trash;
+        move |evt: Event<MouseData>| {
+            if in_trash {
+                return;
+            }
+            evt.prevent_default();
+            confirming_unlock.set(false);
+            menu_open.set(true);
+        }
+    };
+
+    let mut toggle_lock = move || {
+        if todo_locked && props.confirm_unlock && !confirming_unlock() {
+            confirming_unlock.set(true);
+            return;
+        }
+        props.on_lock_change.call((todo_id, !todo_locked));
+        menu_open.set(false);
+        confirming_unlock.set(false);
+        if !todo_locked {
+            editing.set(false);
+        }
+    };
+
+    let copy_as_text = {
+        let todo = todo.clone();
+        move || clipboard::write_text(&share::format_todo(&todo))
+    };
+
+    // Actions picked from the row's buttons or its menu
+    let handle_action = move |action: TodoAction| {
+        if action != TodoAction::ToggleLock {
+            menu_open.set(false);
+        }
+        match action {
+            TodoAction::Edit => toggle_editing(),
+            TodoAction::DueDate => toggle_date_editing(),
+            TodoAction::Tags => toggle_tag_editing(),
+            TodoAction::Notes => show_notes.set(!show_notes()),
+            TodoAction::Subtasks => show_subtasks.set(!show_subtasks()),
+            TodoAction::Copy => copy_as_text(),
+            TodoAction::Delete => props.on_delete.call(todo_id),
+            TodoAction::MoveToPosition => props.on_move_to_position.call(todo_id),
+            TodoAction::ToggleLock => toggle_lock(),
+            TodoAction::Close => {}
+        }
+    };
+
+    let bg_class = theme::todo_row_class(todo.completed, props.is_dark_mode);
+    let border_class = theme::border_class(props.is_dark_mode);
+    let date_text_class = theme::secondary_text_class(props.is_dark_mode);
+    let date_icon_class = theme::accent_text_class(props.is_dark_mode);
+    let text_toggle_class = theme::accent_text_class(props.is_dark_mode);
+    let weekend_badge_class = theme::badge_class(props.is_dark_mode);
+
+    // The due day, read the way the due filters read it
+    let due_day = todo_due_date.map(dates::due_day);
+    let due_date_display = due_day.map(|day| {
+        let date = day.format("%b %d, %Y").to_string();
+        if props.show_week_number {
+            format!("{} ({})", date, IsoWeek::of(day).label())
+        } else {
+            date
+        }
+    });
+    let due_on_weekend = due_day.is_some_and(|day| dates::is_weekend(day.weekday()));
+    let completed_display = todo
+        .completed_at
+        .filter(|_| todo.completed)
+        .map(|at| at.with_timezone(&Local).format("%b %-d").to_string());
+
+    rsx! {
+      li {
+        id: "todo-{todo_id}",
+        class: "group relative flex flex-col p-4 border-b {border_class} {bg_class} {row_motion_class}",
+        onkeydown: handle_key_press,
+        oncontextmenu: open_menu,
+
+        TodoRow {
+          todo: todo.clone(),
+          editing: editing(),
+          edit_text: edit_text(),
+          on_edit_input: move |value| edit_text.set(value),
+          on_edit_commit: commit_edit,
+          tag_editing: tag_editing(),
+          notes_open: show_notes(),
+          subtasks_open: show_subtasks(),
+          menu_open: menu_open(),
+          in_trash: props.in_trash,
+          compact: props.compact,
+          stale_days: props.stale_days,
+          issue_linker: props.issue_linker.clone(),
+          on_toggle: props.on_toggle,
+          on_restore: props.on_restore,
+          on_purge: props.on_purge,
+          on_unarchive: props.on_unarchive,
+          on_action: handle_action.clone(),
+          on_open_menu: move |_| {
+              confirming_unlock.set(false);
+              menu_open.set(true);
+          },
+          is_dark_mode: props.is_dark_mode,
+        }
+
+        if menu_open() {
+          div {
+            class: "fixed inset-0 z-30",
+            onclick: move |_| menu_open.set(false),
+            oncontextmenu: move |evt| {
+                evt.prevent_default();
+                menu_open.set(false);
+            },
+          }
+          TodoItemMenu {
+            locked: todo_locked,
+            confirming_unlock: confirming_unlock(),
+            show_actions: props.compact,
+            notes_open: show_notes(),
+            subtasks_open: show_subtasks(),
+            movable: props.movable,
+            on_action: handle_action,
+            is_dark_mode: props.is_dark_mode,
+          }
+        }
+
+        if let (Some(parent_id), Some(parent_text)) = (todo.derived_from, props.parent_text.clone()) {
+          div { class: "mt-1 text-xs {date_text_class}",
+            "↳ Extracted from "
+            button {
+              r#type: "button",
+              class: "{text_toggle_class} hover:underline",
+              title: "Show the original todo",
+              onclick: move |_| props.on_reveal.call(parent_id),
+              "“{parent_text}”"
+            }
+          }
+        }
+
+        if show_notes() && !props.in_trash {
+          TodoNotes {
+            todo_id,
+            notes: todo.notes.clone(),
+            on_notes_change: props.on_notes_change,
+            on_extract: props.on_extract,
+            is_dark_mode: props.is_dark_mode,
+          }
+        }
+
+        if show_subtasks() && !props.in_trash {
+          SubtaskList {
+            todo_id,
+            subtasks: todo.subtasks.clone(),
+            on_add: props.on_subtask_add,
+            on_toggle: props.on_subtask_toggle,
+            on_remove: props.on_subtask_remove,
+            is_dark_mode: props.is_dark_mode,
+          }
+        }
+
+        if date_editor.is_open() {
+          DueDateEditor {
+            todo_id,
+            due_date: todo_due_date,
+            url: todo.url.clone(),
+            on_due_date_change: props.on_due_date_change,
+            on_url_change: props.on_url_change,
+            on_close: move |_| date_editor.close(),
+            is_dark_mode: props.is_dark_mode,
+          }
+        } else if let Some(date_str) = due_date_display {
+          div { class: "mt-2 text-xs flex items-center {date_text_class} {motion_class}",
+            span { class: "{date_icon_class} mr-1.5",
+              svg {
+                xmlns: "http://www.w3.org/2000/svg",
+                fill: "none",
+                view_box: "0 0 24 24",
+                stroke_width: "1.5",
+                stroke: "currentColor",
+                class: "w-3.5 h-3.5",
+                path {
+                  stroke_linecap: "round",
+                  stroke_linejoin: "round",
+                  d: "M6.75 3v2.25M17.25 3v2.25M3 18.75V7.5a2.25 2.25 0 012.25-2.25h13.5A2.25 2.25 0 0121 7.5v11.25m-18 0A2.25 2.25 0 005.25 21h13.5A2.25 2.25 0 0021 18.75m-18 0v-7.5A2.25 2.25 0 015.25 9h13.5A2.25 2.25 0 0121 11.25v7.5",
+                }
+              }
+            }
+            span { "Due: {date_str}" }
+            if due_on_weekend {
+              span { class: "ml-1.5 px-1.5 rounded {weekend_badge_class}", "weekend" }
+            }
+          }
+        }
+
+        if let Some(completed_str) = completed_display {
+          div { class: "mt-2 text-xs flex items-center {date_text_class} {motion_class}",
+            span { "Completed {completed_str}" }
+          }
+        }
+
+        if tag_editing() {
+          TagEditor {
+            todo_id,
+            tags: todo.tags.clone(),
+            suggestions: props.suggest_tags.call(todo.tags.clone()),
+            on_tag_add: props.on_tag_add,
+            on_tag_remove: props.on_tag_remove,
+            on_suppress: props.on_suppress_tag,
+            on_close: move |_| tag_editing.set(false),
+            is_dark_mode: props.is_dark_mode,
+          }
+        }
+      }
+    }
+}
+
+/// Actions on a todo, asked for by its row's buttons or picked from its menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TodoAction {
+    Edit,
+    DueDate,
+    Tags,
+    Notes,
+    Subtasks,
+    Copy,
+    Delete,
+    MoveToPosition,
+    ToggleLock,
+    Close,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use crate::utils::constants::todo::{TRUNCATE_TEXT_AT, URL_DISPLAY_CHARS};
+    use chrono::TimeZone;
+    use dioxus::dioxus_core::{AttributeValue, Mutation, Mutations};
+    use std::cell::RefCell;
+
+    /// Renders a single todo item and collects its mutations.
+    fn render(text: String) -> Vec<Mutation> {
+        let mut app = VirtualDom::new_with_props(
+            |text: String| {
+                rsx! {
+                    TodoItem {
+                        todo: Rc::new(Todo::new(1, text)),
+                        on_toggle: |_| {},
+                        on_delete: |_| {},
+                        on_restore: |_| {},
+                        on_update: |_| {},
+                        on_due_date_change: |_| {},
+                        on_tag_add: |_| {},
+                        on_tag_remove: |_| {},
+                        on_notes_change: |_| {},
+                        on_extract: |_| {},
+                        on_reveal: |_| {},
+                        on_lock_change: |_| {},
+                    }
+                }
+            },
+            text,
+        );
+
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        mutations.edits
+    }
+
+    /// Renders a single todo item and collects the text of its dynamic text nodes.
+    fn rendered_texts(text: String) -> Vec<String> {
+        render(text)
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Mutation::CreateTextNode { value, .. } => Some(value),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Renders a single todo item and collects the values of one dynamic attribute.
+    fn rendered_attributes(text: String, attribute: &str) -> Vec<String> {
+        render(text)
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Mutation::SetAttribute {
+                    name,
+                    value: AttributeValue::Text(value),
+                    ..
+                } if name == attribute => Some(value),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_todo_item_truncates_long_text() {
+        let long_text = "a".repeat(TRUNCATE_TEXT_AT + 100);
+        let texts = rendered_texts(long_text.clone());
+
+        let expected = format!("{}…", "a".repeat(TRUNCATE_TEXT_AT));
+        assert!(texts.contains(&expected));
+        assert!(!texts.contains(&long_text));
+    }
+
+    #[test]
+    fn test_todo_item_renders_short_text_in_full() {
+        let short_text = "a".repeat(TRUNCATE_TEXT_AT);
+        let texts = rendered_texts(short_text.clone());
+
+        assert!(texts.contains(&short_text));
+        assert!(!texts.iter().any(|text| text.ends_with('…')));
+    }
+
+    #[test]
+    fn test_todo_item_long_unbroken_text_keeps_row_width() {
+        let url = format!("https://example.com/{}", "a".repeat(200));
+        let pathological = format!("See {} {}", url, "x".repeat(300));
+
+        let row_class = |classes: Vec<String>| {
+            classes
+                .into_iter()
+                .find(|class| class.starts_with("group relative"))
+                .unwrap()
+        };
+        assert_eq!(
+            row_class(rendered_attributes(pathological.clone(), "class")),
+            row_class(rendered_attributes("Buy milk".to_string(), "class"))
+        );
+
+        // The text wraps anywhere, and the address is shortened for display
+        let classes = rendered_attributes(pathological.clone(), "class");
+        assert!(
+            classes
+                .iter()
+                .any(|class| class.contains("break-words [overflow-wrap:anywhere]"))
+        );
+        let texts = rendered_texts(pathological.clone());
+        let shown = text::truncate_middle(&url, URL_DISPLAY_CHARS).unwrap();
+        assert!(texts.contains(&shown));
+        assert!(!texts.iter().any(|text| text.contains(&url)));
+        // The full address stays in the tooltip
+        assert!(rendered_attributes(pathological, "title").contains(&url));
+    }
+
+    #[test]
+    fn test_todo_item_shows_stale_clock() {
+        let mut app = VirtualDom::new(|| {
+            rsx! {
+                TodoItem {
+                    todo: Rc::new(Todo::new(1, "Call the plumber".to_string())),
+                    on_toggle: |_| {},
+                    on_delete: |_| {},
+                    on_restore: |_| {},
+                    on_update: |_| {},
+                    on_due_date_change: |_| {},
+                    on_tag_add: |_| {},
+                    on_tag_remove: |_| {},
+                    on_notes_change: |_| {},
+                    on_extract: |_| {},
+                    on_reveal: |_| {},
+                    on_lock_change: |_| {},
+                    stale_days: Some(23),
+                }
+            }
+        });
+
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        assert!(mutations.edits.iter().any(|edit| matches!(
+            edit,
+            Mutation::SetAttribute {
+                name: "title",
+                value: AttributeValue::Text(value),
+                ..
+            } if value == "Stale for 23 days"
+        )));
+    }
+
+    #[test]
+    fn test_completed_todo_shows_when_it_was_done() {
+        let render = |completed: bool| {
+            let mut todo = Todo::new(1, "File taxes".to_string());
+            todo.completed = completed;
+            todo.completed_at = Some(Utc.with_ymd_and_hms(2025, 3, 5, 12, 0, 0).unwrap());
+            TestDom::with_props(
+                |todo: Todo| {
+                    rsx! {
+                        TodoItem {
+                            todo: Rc::new(todo),
+                            on_toggle: |_| {},
+                            on_delete: |_| {},
+                            on_restore: |_| {},
+                            on_update: |_| {},
+                            on_due_date_change: |_| {},
+                            on_tag_add: |_| {},
+                            on_tag_remove: |_| {},
+                            on_notes_change: |_| {},
+                            on_extract: |_| {},
+                            on_reveal: |_| {},
+                            on_lock_change: |_| {},
+                        }
+                    }
+                },
+                todo,
+            )
+        };
+
+        assert!(render(true).text().contains("Completed Mar "));
+        assert!(!render(false).text().contains("Completed"));
+    }
+
+    #[test]
+    fn test_due_date_shows_the_due_day_in_any_time_zone() {
+        let render = |day: u32| {
+            let mut todo = Todo::new(1, "File taxes".to_string());
+            todo.due_date = Some(dates::due_date_on(
+                chrono::NaiveDate::from_ymd_opt(2025, 3, day).unwrap(),
+            ));
+            TestDom::with_props(
+                |todo: Todo| {
+                    rsx! {
+                        TodoItem {
+                            todo: Rc::new(todo),
+                            on_toggle: |_| {},
+                            on_delete: |_| {},
+                            on_restore: |_| {},
+                            on_update: |_| {},
+                            on_due_date_change: |_| {},
+                            on_tag_add: |_| {},
+                            on_tag_remove: |_| {},
+                            on_notes_change: |_| {},
+                            on_extract: |_| {},
+                            on_reveal: |_| {},
+                            on_lock_change: |_| {},
+                            show_week_number: true,
+                        }
+                    }
+                },
+                todo,
+            )
+        };
+
+        // Stored at midnight UTC, which is the day before west of UTC
+        let monday = render(17).text();
+        assert!(monday.contains("Mar 17, 2025 (W12)"));
+        assert!(!monday.contains("weekend"));
+        let sunday = render(16).text();
+        assert!(sunday.contains("Mar 16, 2025 (W11)"));
+        assert!(sunday.contains("weekend"));
+    }
+
+    /// Renders the row menu and collects the labels of its items.
+    fn menu_labels(locked: bool, show_actions: bool, movable: bool) -> Vec<String> {
+        let mut app = VirtualDom::new_with_props(
+            |(locked, show_actions, movable): (bool, bool, bool)| {
+                rsx! {
+                    TodoItemMenu {
+                        locked,
+                        confirming_unlock: false,
+                        show_actions,
+                        notes_open: false,
+                        subtasks_open: false,
+                        movable,
+                        on_action: |_| {},
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            (locked, show_actions, movable),
+        );
+
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        mutations
+            .edits
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Mutation::CreateTextNode { value, .. } => Some(value),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_overflow_menu_holds_the_row_actions() {
+        let labels = menu_labels(false, true, false);
+        for action in [
+            "✏️ Edit",
+            "📅 Due date",
+            "🏷️ Tags",
+            "☑️ Subtasks",
+            "🗑️ Delete",
+        ] {
+            assert!(labels.iter().any(|label| label == action), "{labels:?}");
+        }
+
+        // Locked todos cannot be edited or deleted from the menu either
+        let labels = menu_labels(true, true, false);
+        assert!(
+            !labels
+                .iter()
+                .any(|label| label == "✏️ Edit" || label == "🗑️ Delete")
+        );
+        assert!(labels.iter().any(|label| label == "📅 Due date"));
+
+        // The right-click menu on wide windows only locks
+        assert!(
+            !menu_labels(false, false, false)
+                .iter()
+                .any(|label| label == "✏️ Edit")
+        );
+    }
+
+    #[test]
+    fn test_menu_offers_move_to_position_when_reorderable() {
+        let move_label = "↕️ Move to position…";
+        assert!(
+            menu_labels(false, false, true)
+                .iter()
+                .any(|label| label == move_label)
+        );
+        assert!(
+            !menu_labels(false, true, false)
+                .iter()
+                .any(|label| label == move_label)
+        );
+    }
+
+    #[test]
+    fn test_compact_row_puts_tags_on_their_own_row() {
+        let tag_row_class = |compact: bool| {
+            let mut app = VirtualDom::new_with_props(
+                |compact: bool| {
+                    let mut todo = Todo::new(1, "Pack for the trip".to_string());
+                    todo.tags = vec!["Travel".to_string()];
+                    rsx! {
+                        TodoItem {
+                            todo: Rc::new(todo),
+                            on_toggle: |_| {},
+                            on_delete: |_| {},
+                            on_restore: |_| {},
+                            on_update: |_| {},
+                            on_due_date_change: |_| {},
+                            on_tag_add: |_| {},
+                            on_tag_remove: |_| {},
+                            on_notes_change: |_| {},
+                            on_extract: |_| {},
+                            on_reveal: |_| {},
+                            on_lock_change: |_| {},
+                            compact,
+                        }
+                    }
+                },
+                compact,
+            );
+
+            let mut mutations = Mutations::default();
+            app.rebuild(&mut mutations);
+            mutations.edits.into_iter().find_map(|edit| match edit {
+                Mutation::SetAttribute {
+                    name: "class",
+                    value: AttributeValue::Text(value),
+                    ..
+                } if value.starts_with("flex flex-wrap items-center gap-1.5") => Some(value),
+                _ => None,
+            })
+        };
+
+        assert!(tag_row_class(true).unwrap().contains("basis-full"));
+        assert!(!tag_row_class(false).unwrap().contains("basis-full"));
+    }
+
+    #[test]
+    fn test_toggle_and_delete_report_the_todo() {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let mut dom = TestDom::with_props(
+            |calls: Rc<RefCell<Vec<(&'static str, usize)>>>| {
+                let (toggled, deleted) = (calls.clone(), calls.clone());
+                rsx! {
+                    TodoItem {
+                        todo: Rc::new(Todo::new(7, "Water the plants".to_string())),
+                        on_toggle: move |id| toggled.borrow_mut().push(("toggle", id)),
+                        on_delete: move |id| deleted.borrow_mut().push(("delete", id)),
+                        on_restore: |_| {},
+                        on_update: |_| {},
+                        on_due_date_change: |_| {},
+                        on_tag_add: |_| {},
+                        on_tag_remove: |_| {},
+                        on_notes_change: |_| {},
+                        on_extract: |_| {},
+                        on_reveal: |_| {},
+                        on_lock_change: |_| {},
+                    }
+                }
+            },
+            calls.clone(),
+        );
+
+        let checkbox = dom
+            .find_by_attribute("input", "aria-label", "Toggle todo completion")
+            .unwrap();
+        dom.click(checkbox);
+        dom.click(
+            dom.find_by_attribute("button", "title", "Delete task")
+                .unwrap(),
+        );
+
+        assert_eq!(*calls.borrow(), vec![("toggle", 7), ("delete", 7)]);
+    }
+
+    #[test]
+    fn test_issue_references_link_to_the_tracker() {
+        let mut app = VirtualDom::new(|| {
+            let linker = IssueLinker::new(&[crate::models::IssueLinkRule {
+                pattern: r"[A-Z]+-\d+".to_string(),
+                url_template: "https://jira.example.com/browse/{0}".to_string(),
+            }]);
+            rsx! {
+                TodoItem {
+                    todo: Rc::new(Todo::new(1, "Fix login bug ABC-1234".to_string())),
+                    on_toggle: |_| {},
+                    on_delete: |_| {},
+                    on_restore: |_| {},
+                    on_update: |_| {},
+                    on_due_date_change: |_| {},
+                    on_tag_add: |_| {},
+                    on_tag_remove: |_| {},
+                    on_notes_change: |_| {},
+                    on_extract: |_| {},
+                    on_reveal: |_| {},
+                    on_lock_change: |_| {},
+                    issue_linker: Rc::new(linker),
+                }
+            }
+        });
+
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        let hrefs: Vec<String> = mutations
+            .edits
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Mutation::SetAttribute {
+                    name: "href",
+                    value: AttributeValue::Text(value),
+                    ..
+                } => Some(value),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(hrefs, vec!["https://jira.example.com/browse/ABC-1234"]);
+    }
+
+    #[test]
+    fn test_editors_open_from_the_row_and_close_on_escape() {
+        let updates = Rc::new(RefCell::new(Vec::new()));
+        let mut dom = TestDom::with_props(
+            |updates: Rc<RefCell<Vec<(usize, String)>>>| {
+                rsx! {
+                    TodoItem {
+                        todo: Rc::new(Todo::new(7, "Water the plants".to_string())),
+                        on_toggle: |_| {},
+                        on_delete: |_| {},
+                        on_restore: |_| {},
+                        on_update: move |update| updates.borrow_mut().push(update),
+                        on_due_date_change: |_| {},
+                        on_tag_add: |_| {},
+                        on_tag_remove: |_| {},
+                        on_notes_change: |_| {},
+                        on_extract: |_| {},
+                        on_reveal: |_| {},
+                        on_lock_change: |_| {},
+                    }
+                }
+            },
+            updates.clone(),
+        );
+        let new_tag_field =
+            |dom: &TestDom| dom.find_by_attribute("input", "placeholder", "New tag...");
+
+        dom.click(
+            dom.find_by_attribute("button", "title", "Edit tags")
+                .unwrap(),
+        );
+        assert!(new_tag_field(&dom).is_some());
+        dom.key_down(dom.first("li").unwrap(), Key::Escape);
+        assert!(new_tag_field(&dom).is_none());
+
+        // Opening the text editor closes the others, and submitting it saves the text
+        dom.click(
+            dom.find_by_attribute("button", "title", "Edit due date")
+                .unwrap(),
+        );
+        assert!(
+            dom.find_by_attribute("input", "aria-label", "Due date")
+                .is_some()
+        );
+        dom.click(
+            dom.find_by_attribute("button", "title", "Edit task text")
+                .unwrap(),
+        );
+        assert!(
+            dom.find_by_attribute("input", "aria-label", "Due date")
+                .is_none()
+        );
+        let editor = dom
+            .find_by_attribute("input", "value", "Water the plants")
+            .unwrap();
+        dom.input(editor, "Water the ferns");
+        dom.submit(dom.first("form").unwrap());
+
+        assert_eq!(*updates.borrow(), vec![(7, "Water the ferns".to_string())]);
+    }
+}
@@ -0,0 +1,648 @@
+use super::TodoAction;
+use super::tag_pills::TagPills;
+use crate::components::theme_manager::use_motion;
+use crate::components::{CharacterCounter, TextStatsLine, TodoLinkButton};
+use crate::models::{IssueLinker, Todo};
+use crate::utils::constants::todo::{TRUNCATE_TEXT_AT, URL_DISPLAY_CHARS};
+use crate::utils::text::DisplaySegment;
+use crate::utils::theme::Accent;
+use crate::utils::{text, theme};
+use chrono::Local;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Props for the TodoRow component.
+#[derive(Props, PartialEq, Clone)]
+pub(super) struct TodoRowProps {
+    pub todo: Rc<Todo>,
+    /// Whether the text editor is open instead of the text
+    pub editing: bool,
+    /// Text in the editor
+    pub edit_text: String,
+    /// Callback when the text in the editor changes
+    pub on_edit_input: EventHandler<String>,
+    /// Callback when the editor is submitted or loses focus
+    pub on_edit_commit: EventHandler<()>,
+    /// Whether the tag editor is open, which hides the tags beside the text
+    pub tag_editing: bool,
+    /// Whether the notes are open below the row
+    pub notes_open: bool,
+    /// Whether the subtasks are open below the row
+    pub subtasks_open: bool,
+    /// Whether the row's menu is open
+    pub menu_open: bool,
+    pub in_trash: bool,
+    /// Whether the action buttons go in a "⋯" menu and the tags on a row of their own
+    pub compact: bool,
+    pub stale_days: Option<i64>,
+    pub issue_linker: Rc<IssueLinker>,
+    pub on_toggle: EventHandler<usize>,
+    pub on_restore: EventHandler<usize>,
+    /// Callback when a todo in the trash is deleted for good
+    #[props(default)]
+    pub on_purge: EventHandler<usize>,
+    /// Callback when an archived todo is brought back into the list
+    #[props(default)]
+    pub on_unarchive: EventHandler<usize>,
+    /// Callback when one of the action buttons, or the text, asks for an action
+    pub on_action: EventHandler<TodoAction>,
+    /// Callback when the "⋯" button is pressed
+    pub on_open_menu: EventHandler<()>,
+    pub is_dark_mode: bool,
+}
+
+/// The main line of a todo: its checkbox, its text or the text editor, and
+/// the action buttons
+///
+/// Which editors are open is up to the item; the row reports what was asked
+/// for through `on_action`.
+#[component]
+pub(super) fn TodoRow(props: TodoRowProps) -> Element {
+    let TodoRowProps {
+        todo,
+        editing,
+        edit_text,
+        on_edit_input,
+        on_edit_commit,
+        tag_editing,
+        notes_open,
+        subtasks_open,
+        menu_open,
+        in_trash,
+        compact,
+        stale_days,
+        issue_linker,
+        on_toggle,
+        on_restore,
+        on_purge,
+        on_unarchive,
+        on_action,
+        on_open_menu,
+        is_dark_mode,
+    } = props;
+
+    let reveal_motion_class = use_motion("transition-opacity duration-150");
+    let motion_class = use_motion("transition-colors duration-200");
+    let button_motion_class = use_motion("transition-colors duration-150");
+
+    let todo_id = todo.id;
+    let mut show_full_text = use_signal(|| false);
+    let mut tags_collapsed = use_signal(|| todo.tags.len() > 3);
+
+    let stale_title = stale_days.map(|days| {
+        format!(
+            "Stale for {} {}",
+            days,
+            if days == 1 { "day" } else { "days" }
+        )
+    });
+
+    // Dated like the due date, in local time
+    let created_title = todo
+        .created_at
+        .map(|at| format!("Created {}", at.with_timezone(&Local).format("%b %d, %Y")));
+
+    // Very long text is cut short so a single todo cannot blow up the layout
+    let short_text = text::truncate_chars(&todo.text, TRUNCATE_TEXT_AT);
+    let is_long_text = short_text.is_some();
+    let display_text = match short_text {
+        Some(short_text) if !show_full_text() => format!("{}…", short_text),
+        _ => todo.text.clone(),
+    };
+    // Long web addresses are shortened in the middle; the full one is in the tooltip.
+    // Issue-tracker references in the rest of the text become links
+    let display_segments =
+        issue_linker.link_segments(text::display_segments(&display_text, URL_DISPLAY_CHARS));
+
+    let text_class = theme::todo_text_class(todo.completed, is_dark_mode);
+    let border_class = theme::border_class(is_dark_mode);
+    let input_bg_class = theme::field_class(is_dark_mode);
+    let button_text_class = theme::muted_text_class(is_dark_mode);
+    let text_toggle_class = theme::accent_text_class(is_dark_mode);
+    let trash_text_class = theme::danger_text_class(is_dark_mode);
+    let badge_class = theme::badge_class(is_dark_mode);
+    let checkbox_class = theme::checkbox_class(is_dark_mode);
+    let focus_class = theme::focus_ring_class(is_dark_mode);
+    let hover_class = theme::hover_class(is_dark_mode);
+    let blue_hover_class = theme::hover_text_class(Accent::Blue, is_dark_mode);
+    let green_hover_class = theme::hover_text_class(Accent::Green, is_dark_mode);
+    let purple_hover_class = theme::hover_text_class(Accent::Purple, is_dark_mode);
+    let amber_hover_class = theme::hover_text_class(Accent::Amber, is_dark_mode);
+    let red_hover_class = theme::hover_text_class(Accent::Red, is_dark_mode);
+    let (subtasks_done, subtasks_total) = todo.subtask_progress();
+
+    rsx! {
+      div { class: "flex items-center w-full",
+        div { class: "flex-shrink-0 mr-4",
+          input {
+            r#type: "checkbox",
+            class: "w-5 h-5 rounded focus:ring-offset-0 focus:ring-2 {checkbox_class}",
+            checked: todo.completed,
+            disabled: in_trash,
+            onclick: move |_| on_toggle.call(todo_id),
+            aria_label: "Toggle todo completion",
+          }
+        }
+
+        // `min-w-0` lets the text shrink and wrap instead of widening the row
+        div { class: "flex-1 min-w-0 flex flex-wrap items-center gap-1.5",
+          if editing {
+            form {
+              class: "flex-1 mr-2",
+              onsubmit: move |evt| {
+                  evt.prevent_default();
+                  on_edit_commit.call(());
+              },
+              input {
+                class: "w-full px-3 py-1.5 border {border_class} {input_bg_class} rounded shadow-sm focus:outline-none focus:ring-2 {focus_class} {motion_class} text-sm",
+                value: "{edit_text}",
+                oninput: move |evt| on_edit_input.call(evt.value()),
+                autofocus: true,
+                onblur: move |_| on_edit_commit.call(()),
+              }
+              div { class: "mt-0.5 flex justify-between gap-2",
+                TextStatsLine { text: edit_text.clone(), is_dark_mode }
+                CharacterCounter { text: edit_text.clone(), is_dark_mode }
+              }
+            }
+          } else {
+            div {
+              class: "cursor-pointer mr-2 {text_class} {motion_class} text-sm min-w-0 max-w-full break-words [overflow-wrap:anywhere]",
+              title: created_title,
+              ondoubleclick: move |_| on_action.call(TodoAction::Edit),
+              if todo.locked {
+                span {
+                  class: "mr-1 text-xs",
+                  title: "Locked: right-click to unlock",
+                  aria_label: "Locked",
+                  "🔒"
+                }
+              }
+              if let Some(stale_title) = stale_title {
+                span {
+                  class: "mr-1 text-xs opacity-60",
+                  title: "{stale_title}",
+                  aria_label: "{stale_title}",
+                  "🕓"
+                }
+              }
+              span {
+                for segment in display_segments {
+                  match segment {
+                      DisplaySegment::Text(plain) => rsx! { "{plain}" },
+                      DisplaySegment::Url { full, shown } => rsx! {
+                        span { class: "underline decoration-dotted", title: "{full}", "{shown}" }
+                      },
+                      DisplaySegment::Issue { text, url } => rsx! {
+                        a {
+                          class: "underline {text_toggle_class}",
+                          href: "{url}",
+                          target: "_blank",
+                          rel: "noopener noreferrer",
+                          title: "Open {url}",
+                          onclick: move |evt| evt.stop_propagation(),
+                          ondoubleclick: move |evt| evt.stop_propagation(),
+                          "{text}"
+                        }
+                      },
+                  }
+                }
+              }
+              if is_long_text {
+                button {
+                  r#type: "button",
+                  class: "ml-1.5 text-xs {text_toggle_class} hover:underline",
+                  onclick: move |_| show_full_text.set(!show_full_text()),
+                  if show_full_text() { "Show less" } else { "Show full text" }
+                }
+              }
+            }
+
+            if let Some(url) = todo.url.clone() {
+              TodoLinkButton {
+                url,
+                class: "p-0.5 rounded {button_text_class} {blue_hover_class}",
+              }
+            }
+
+            if !todo.notes.is_empty() && !notes_open {
+              button {
+                r#type: "button",
+                class: "text-xs opacity-70 hover:opacity-100",
+                title: "Show notes",
+                onclick: move |_| on_action.call(TodoAction::Notes),
+                "📝"
+              }
+            }
+
+            if !todo.subtasks.is_empty() {
+              button {
+                r#type: "button",
+                class: "px-1.5 rounded text-xs tabular-nums {badge_class} hover:opacity-80",
+                title: "{subtasks_done} of {subtasks_total} subtasks done",
+                aria_expanded: subtasks_open,
+                onclick: move |_| on_action.call(TodoAction::Subtasks),
+                "☑ {subtasks_done}/{subtasks_total}"
+              }
+            }
+
+            // Show tags inline with todo text
+            if !todo.tags.is_empty() && !tag_editing {
+              TagPills {
+                tags: todo.tags.clone(),
+                collapsed: tags_collapsed(),
+                on_collapse: move |collapsed| tags_collapsed.set(collapsed),
+                compact,
+                is_dark_mode,
+              }
+            }
+          }
+        }
+
+        if in_trash {
+          div { class: "flex flex-shrink-0 items-center gap-1.5 text-xs {trash_text_class}",
+            span { "In trash —" }
+            button {
+              r#type: "button",
+              class: "font-medium {text_toggle_class} hover:underline",
+              title: "Restore this todo to the list",
+              onclick: move |_| on_restore.call(todo_id),
+              "Restore"
+            }
+            button {
+              r#type: "button",
+              class: "font-medium {text_toggle_class} hover:underline",
+              title: "Delete this todo for good",
+              onclick: move |_| on_purge.call(todo_id),
+              "Delete forever"
+            }
+          }
+        } else if todo.archived {
+          div { class: "flex flex-shrink-0 items-center gap-1.5 text-xs {trash_text_class}",
+            span { "Archived —" }
+            button {
+              r#type: "button",
+              class: "font-medium {text_toggle_class} hover:underline",
+              title: "Bring this todo back into the list",
+              onclick: move |_| on_unarchive.call(todo_id),
+              "Unarchive"
+            }
+          }
+        }
+
+        if compact {
+          if !editing && !in_trash {
+            button {
+              r#type: "button",
+              class: "flex-shrink-0 px-2 py-0.5 rounded text-lg leading-none {button_text_class} {hover_class} {button_motion_class}",
+              title: "Actions",
+              aria_haspopup: "menu",
+              aria_expanded: menu_open,
+              onclick: move |_| on_open_menu.call(()),
+              "⋯"
+            }
+          }
+        } else {
+          div { class: "flex flex-shrink-0 space-x-1.5 opacity-0 group-hover:opacity-100 focus-within:opacity-100 {reveal_motion_class}",
+            if !editing && !in_trash {
+              if !todo.locked {
+                button {
+                  r#type: "button",
+                  class: "p-1.5 rounded {button_text_class} {hover_class} {blue_hover_class} {button_motion_class}",
+                  title: "Edit task text",
+                  onclick: move |_| on_action.call(TodoAction::Edit),
+                  svg {
+                    xmlns: "http://www.w3.org/2000/svg",
+                    fill: "none",
+                    view_box: "0 0 24 24",
+                    stroke_width: "1.5",
+                    stroke: "currentColor",
+                    class: "w-4 h-4",
+                    path {
+                      stroke_linecap: "round",
+                      stroke_linejoin: "round",
+                      d: "M16.862 4.487l1.687-1.688a1.875 1.875 0 112.652 2.652L10.582 16.07a4.5 4.5 0 01-1.897 1.13L6 18l.8-2.685a4.5 4.5 0 011.13-1.897l8.932-8.931zm0 0L19.5 7.125M18 14v4.75A2.25 2.25 0 0115.75 21H5.25A2.25 2.25 0 013 18.75V8.25A2.25 2.25 0 015.25 6H10",
+                    }
+                  }
+                }
+              }
+              button {
+                r#type: "button",
+                class: "p-1.5 rounded {button_text_class} {hover_class} {green_hover_class} {button_motion_class}",
+                title: "Edit due date",
+                onclick: move |_| on_action.call(TodoAction::DueDate),
+                svg {
+                  xmlns: "http://www.w3.org/2000/svg",
+                  fill: "none",
+                  view_box: "0 0 24 24",
+                  stroke_width: "1.5",
+                  stroke: "currentColor",
+                  class: "w-4 h-4",
+                  path {
+                    stroke_linecap: "round",
+                    stroke_linejoin: "round",
+                    d: "M6.75 3v2.25M17.25 3v2.25M3 18.75V7.5a2.25 2.25 0 012.25-2.25h13.5A2.25 2.25 0 0121 7.5v11.25m-18 0A2.25 2.25 0 005.25 21h13.5A2.25 2.25 0 0021 18.75m-18 0v-7.5A2.25 2.25 0 015.25 9h13.5A2.25 2.25 0 0121 11.25v7.5",
+                  }
+                }
+              }
+              button {
+                r#type: "button",
+                class: "p-1.5 rounded {button_text_class} {hover_class} {purple_hover_class} {button_motion_class}",
+                title: "Edit tags",
+                onclick: move |_| on_action.call(TodoAction::Tags),
+                svg {
+                  xmlns: "http://www.w3.org/2000/svg",
+                  fill: "none",
+                  view_box: "0 0 24 24",
+                  stroke_width: "1.5",
+                  stroke: "currentColor",
+                  class: "w-4 h-4",
+                  path {
+                    stroke_linecap: "round",
+                    stroke_linejoin: "round",
+                    d: "M9.568 3H5.25A2.25 2.25 0 003 5.25v4.318c0 .597.237 1.17.659 1.591l9.581 9.581c.699.699 1.78.872 2.607.33a18.095 18.095 0 005.223-5.223c.542-.827.369-1.908-.33-2.607L11.16 3.66A2.25 2.25 0 009.568 3z",
+                  }
+                  path {
+                    stroke_linecap: "round",
+                    stroke_linejoin: "round",
+                    d: "M6 6h.008v.008H6V6z",
+                  }
+                }
+              }
+              button {
+                r#type: "button",
+                class: "p-1.5 rounded {button_text_class} {hover_class} {blue_hover_class} {button_motion_class} text-sm leading-4",
+                title: if notes_open { "Hide notes" } else { "Notes" },
+                aria_expanded: notes_open,
+                onclick: move |_| on_action.call(TodoAction::Notes),
+                "📝"
+              }
+              button {
+                r#type: "button",
+                class: "p-1.5 rounded {button_text_class} {hover_class} {blue_hover_class} {button_motion_class} text-sm leading-4",
+                title: if subtasks_open { "Hide subtasks" } else { "Subtasks" },
+                aria_expanded: subtasks_open,
+                onclick: move |_| on_action.call(TodoAction::Subtasks),
+                "☑"
+              }
+              button {
+                r#type: "button",
+                class: "p-1.5 rounded {button_text_class} {hover_class} {amber_hover_class} {button_motion_class}",
+                title: "Copy as shareable text",
+                onclick: move |_| on_action.call(TodoAction::Copy),
+                svg {
+                  xmlns: "http://www.w3.org/2000/svg",
+                  fill: "none",
+                  view_box: "0 0 24 24",
+                  stroke_width: "1.5",
+                  stroke: "currentColor",
+                  class: "w-4 h-4",
+                  path {
+                    stroke_linecap: "round",
+                    stroke_linejoin: "round",
+                    d: "M15.666 3.888A2.25 2.25 0 0013.5 2.25h-3c-1.03 0-1.9.693-2.166 1.638m7.332 0c.055.194.084.4.084.612v0a.75.75 0 01-.75.75H9a.75.75 0 01-.75-.75v0c0-.212.03-.418.084-.612m7.332 0c.646.049 1.288.11 1.927.184 1.1.128 1.907 1.077 1.907 2.185V19.5a2.25 2.25 0 01-2.25 2.25H6.75A2.25 2.25 0 014.5 19.5V6.257c0-1.108.806-2.057 1.907-2.185a48.208 48.208 0 011.927-.184",
+                  }
+                }
+              }
+            }
+            if !todo.locked {
+              button {
+                r#type: "button",
+                class: "p-1.5 rounded {button_text_class} {hover_class} {red_hover_class} {button_motion_class}",
+                title: "Delete task",
+                onclick: move |_| on_action.call(TodoAction::Delete),
+                svg {
+                  xmlns: "http://www.w3.org/2000/svg",
+                  fill: "none",
+                  view_box: "0 0 24 24",
+                  stroke_width: "1.5",
+                  stroke: "currentColor",
+                  class: "w-4 h-4",
+                  path {
+                    stroke_linecap: "round",
+                    stroke_linejoin: "round",
+                    d: "M14.74 9l-.346 9m-4.788 0L9.26 9m9.968-3.21c.342.052.682.107 1.022.166m-1.022-.165L18.16 19.673a2.25 2.25 0 01-2.244 2.077H8.084a2.25 2.25 0 01-2.244-2.077L4.772 5.79m14.456 0a48.108 48.108 0 00-3.478-.397m-12 .562c.34-.059.68-.114 1.022-.165m0 0a48.11 48.11 0 013.478-.397m7.5 0v-.916c0-1.18-.91-2.164-2.09-2.201a51.964 51.964 0 00-3.32 0c-1.18.037-2.09 1.022-2.09 2.201v.916m7.5 0a48.667 48.667 0 00-7.5 0",
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use crate::models::Subtask;
+    use chrono::{TimeZone, Utc};
+    use std::cell::RefCell;
+
+    type Actions = Rc<RefCell<Vec<TodoAction>>>;
+
+    /// Renders the row of a todo, recording the actions it asks for.
+    fn render(todo: Todo, compact: bool, actions: Actions) -> TestDom {
+        TestDom::with_props(
+            |(todo, compact, actions): (Rc<Todo>, bool, Actions)| {
+                let opened = actions.clone();
+                rsx! {
+                    TodoRow {
+                        todo,
+                        editing: false,
+                        edit_text: String::new(),
+                        on_edit_input: |_| {},
+                        on_edit_commit: |_| {},
+                        tag_editing: false,
+                        notes_open: false,
+                        subtasks_open: false,
+                        menu_open: false,
+                        in_trash: false,
+                        compact,
+                        stale_days: None,
+                        issue_linker: Rc::new(IssueLinker::default()),
+                        on_toggle: |_| {},
+                        on_restore: |_| {},
+                        on_action: move |action| actions.borrow_mut().push(action),
+                        on_open_menu: move |_| opened.borrow_mut().push(TodoAction::Close),
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            (Rc::new(todo), compact, actions),
+        )
+    }
+
+    #[test]
+    fn test_action_buttons_ask_for_their_actions() {
+        let actions = Actions::default();
+        let mut dom = render(
+            Todo::new(1, "Plan the week".to_string()),
+            false,
+            actions.clone(),
+        );
+
+        for title in [
+            "Edit task text",
+            "Edit due date",
+            "Edit tags",
+            "Notes",
+            "Subtasks",
+            "Copy as shareable text",
+            "Delete task",
+        ] {
+            dom.click(dom.find_by_attribute("button", "title", title).unwrap());
+        }
+
+        assert_eq!(
+            *actions.borrow(),
+            vec![
+                TodoAction::Edit,
+                TodoAction::DueDate,
+                TodoAction::Tags,
+                TodoAction::Notes,
+                TodoAction::Subtasks,
+                TodoAction::Copy,
+                TodoAction::Delete,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_subtask_progress_opens_the_subtasks() {
+        let mut todo = Todo::new(1, "Move house".to_string());
+        for (id, completed) in [(1, true), (2, true), (3, false)] {
+            todo.subtasks.push(Subtask {
+                id,
+                text: format!("Step {id}"),
+                completed,
+            });
+        }
+        let actions = Actions::default();
+        let mut dom = render(todo, false, actions.clone());
+
+        let progress = dom
+            .find_by_attribute("button", "title", "2 of 3 subtasks done")
+            .unwrap();
+        assert_eq!(dom.text_of(progress), "☑ 2/3");
+        dom.click(progress);
+        assert_eq!(*actions.borrow(), vec![TodoAction::Subtasks]);
+
+        // Todos without subtasks show no progress
+        let dom = render(Todo::new(2, "Relax".to_string()), false, Actions::default());
+        assert!(!dom.text().contains("☑ "));
+    }
+
+    #[test]
+    fn test_text_tooltip_shows_when_the_todo_was_created() {
+        let mut todo = Todo::new(1, "Renew passport".to_string());
+        todo.created_at = Some(Utc.with_ymd_and_hms(2024, 3, 3, 12, 0, 0).unwrap());
+        let dom = render(todo, false, Actions::default());
+        assert!(
+            dom.find_by_attribute("div", "title", "Created Mar 03, 2024")
+                .is_some()
+        );
+
+        // Todos from before creation times were recorded have no tooltip
+        let mut legacy = Todo::new(2, "Water plants".to_string());
+        legacy.created_at = None;
+        let dom = render(legacy, false, Actions::default());
+        assert!(
+            !dom.find_all("div", "Water plants")
+                .into_iter()
+                .any(|node| dom.attribute(node, "title").is_some())
+        );
+    }
+
+    #[test]
+    fn test_archived_rows_offer_to_unarchive() {
+        let mut todo = Todo::new(1, "Old project".to_string());
+        let dom = render(todo.clone(), false, Actions::default());
+        assert!(dom.find("button", "Unarchive").is_none());
+
+        todo.archived = true;
+        let dom = render(todo, false, Actions::default());
+        assert!(dom.text().contains("Archived —"));
+        assert!(dom.find("button", "Unarchive").is_some());
+    }
+
+    #[test]
+    fn test_trashed_rows_offer_restore_and_delete_forever() {
+        let purged = Rc::new(RefCell::new(Vec::new()));
+        let mut dom = TestDom::with_props(
+            |purged: Rc<RefCell<Vec<usize>>>| {
+                rsx! {
+                    TodoRow {
+                        todo: Rc::new(Todo::new(3, "Old idea".to_string())),
+                        editing: false,
+                        edit_text: String::new(),
+                        on_edit_input: |_| {},
+                        on_edit_commit: |_| {},
+                        tag_editing: false,
+                        notes_open: false,
+                        subtasks_open: false,
+                        menu_open: false,
+                        in_trash: true,
+                        compact: false,
+                        stale_days: None,
+                        issue_linker: Rc::new(IssueLinker::default()),
+                        on_toggle: |_| {},
+                        on_restore: |_| {},
+                        on_purge: move |id| purged.borrow_mut().push(id),
+                        on_action: |_| {},
+                        on_open_menu: |_| {},
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            purged.clone(),
+        );
+
+        assert!(dom.text().contains("In trash —"));
+        assert!(dom.find("button", "Restore").is_some());
+        dom.click(dom.find("button", "Delete forever").unwrap());
+        assert_eq!(*purged.borrow(), [3]);
+    }
+
+    #[test]
+    fn test_locked_rows_cannot_be_edited_or_deleted() {
+        let mut todo = Todo::new(1, "Pay rent".to_string());
+        todo.locked = true;
+        let dom = render(todo, false, Actions::default());
+
+        assert!(
+            dom.find_by_attribute("span", "aria-label", "Locked")
+                .is_some()
+        );
+        assert!(
+            dom.find_by_attribute("button", "title", "Edit task text")
+                .is_none()
+        );
+        assert!(
+            dom.find_by_attribute("button", "title", "Delete task")
+                .is_none()
+        );
+        assert!(
+            dom.find_by_attribute("button", "title", "Edit due date")
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_compact_rows_keep_the_actions_in_a_menu() {
+        let actions = Actions::default();
+        let mut dom = render(
+            Todo::new(1, "Plan the week".to_string()),
+            true,
+            actions.clone(),
+        );
+
+        assert!(
+            dom.find_by_attribute("button", "title", "Edit tags")
+                .is_none()
+        );
+        dom.click(dom.find_by_attribute("button", "title", "Actions").unwrap());
+        assert_eq!(*actions.borrow(), vec![TodoAction::Close]);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::components::theme_manager::use_motion;
+use crate::models::Subtask;
+use crate::utils::theme;
+use dioxus::prelude::*;
+
+/// A todo's subtasks, below its row
+///
+/// Each subtask has a checkbox and a delete button, and a field at the end
+/// adds another. Checking off every subtask leaves the todo itself open.
+#[component]
+pub(super) fn SubtaskList(
+    todo_id: usize,
+    subtasks: Vec<Subtask>,
+    on_add: EventHandler<(usize, String)>,
+    on_toggle: EventHandler<(usize, usize)>,
+    on_remove: EventHandler<(usize, usize)>,
+    is_dark_mode: bool,
+) -> Element {
+    let motion_class = use_motion("transition-colors duration-200");
+    let button_motion_class = use_motion("transition-colors duration-150");
+
+    let mut new_subtask = use_signal(String::new);
+
+    let handle_add = move |evt: Event<FormData>| {
+        evt.prevent_default();
+        let text = new_subtask.read().trim().to_string();
+        if !text.is_empty() {
+            on_add.call((todo_id, text));
+            new_subtask.set(String::new());
+        }
+    };
+
+    let add_button_class = theme::add_button_class(is_dark_mode);
+    let border_class = theme::border_class(is_dark_mode);
+    let input_bg_class = theme::field_class(is_dark_mode);
+    let button_text_class = theme::muted_text_class(is_dark_mode);
+    let delete_hover_class = theme::hover_text_class(theme::Accent::Red, is_dark_mode);
+    let checkbox_class = theme::checkbox_class(is_dark_mode);
+    let rows: Vec<(Subtask, &str)> = subtasks
+        .into_iter()
+        .map(|subtask| {
+            let text_class = theme::todo_text_class(subtask.completed, is_dark_mode);
+            (subtask, text_class)
+        })
+        .collect();
+
+    rsx! {
+      div { class: "mt-3 ml-9 text-sm",
+        ul { class: "space-y-1",
+          for (subtask, text_class) in rows {
+            li {
+              key: "subtask-{subtask.id}",
+              class: "group/subtask flex items-center gap-2",
+              input {
+                r#type: "checkbox",
+                class: "w-4 h-4 rounded {checkbox_class}",
+                checked: subtask.completed,
+                aria_label: "Complete {subtask.text}",
+                onclick: move |_| on_toggle.call((todo_id, subtask.id)),
+              }
+              span { class: "flex-1 min-w-0 break-words {text_class} {motion_class}",
+                "{subtask.text}"
+              }
+              button {
+                r#type: "button",
+                class: "p-0.5 rounded opacity-0 group-hover/subtask:opacity-100 focus:opacity-100 {button_text_class} {delete_hover_class} {button_motion_class}",
+                title: "Delete subtask",
+                aria_label: "Delete subtask {subtask.text}",
+                onclick: move |_| on_remove.call((todo_id, subtask.id)),
+                "✕"
+              }
+            }
+          }
+        }
+
+        form { class: "flex items-center mt-1.5", onsubmit: handle_add,
+          input {
+            class: "text-xs px-2 py-1 border {border_class} {input_bg_class} rounded-l focus:outline-none focus:ring-1 focus:ring-blue-400 w-48 {motion_class}",
+            placeholder: "New subtask...",
+            value: "{new_subtask.read()}",
+            oninput: move |evt| new_subtask.set(evt.value()),
+          }
+          button {
+            r#type: "submit",
+            class: "px-2 py-1 {add_button_class} text-white text-xs rounded-r focus:outline-none focus:ring-1 focus:ring-green-400 {motion_class}",
+            "Add"
+          }
+        }
+      }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Calls = Rc<RefCell<Vec<String>>>;
+
+    /// Renders the subtasks of todo 3: "Pack boxes", done, and "Book van".
+    fn render(calls: Calls) -> TestDom {
+        TestDom::with_props(
+            |calls: Calls| {
+                let (added, toggled, removed) = (calls.clone(), calls.clone(), calls.clone());
+                rsx! {
+                    SubtaskList {
+                        todo_id: 3,
+                        subtasks: vec![
+                            Subtask { id: 1, text: "Pack boxes".to_string(), completed: true },
+                            Subtask { id: 2, text: "Book van".to_string(), completed: false },
+                        ],
+                        on_add: move |(id, text): (usize, String)| added.borrow_mut().push(format!("add {id} {text}")),
+                        on_toggle: move |(id, subtask_id): (usize, usize)| toggled.borrow_mut().push(format!("toggle {id} {subtask_id}")),
+                        on_remove: move |(id, subtask_id): (usize, usize)| removed.borrow_mut().push(format!("remove {id} {subtask_id}")),
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            calls,
+        )
+    }
+
+    #[test]
+    fn test_subtasks_toggle_and_delete() {
+        let calls = Calls::default();
+        let mut dom = render(calls.clone());
+
+        let book_van = dom
+            .find_by_attribute("input", "aria-label", "Complete Book van")
+            .unwrap();
+        dom.click(book_van);
+        let delete = dom
+            .find_by_attribute("button", "aria-label", "Delete subtask Pack boxes")
+            .unwrap();
+        dom.click(delete);
+
+        assert_eq!(*calls.borrow(), ["toggle 3 2", "remove 3 1"]);
+    }
+
+    #[test]
+    fn test_new_subtasks_are_trimmed_and_blank_ones_skipped() {
+        let calls = Calls::default();
+        let mut dom = render(calls.clone());
+
+        let field = dom
+            .find_by_attribute("input", "placeholder", "New subtask...")
+            .unwrap();
+        dom.input(field, "  Label boxes ");
+        dom.submit(dom.first("form").unwrap());
+        assert_eq!(dom.attribute(field, "value"), Some(""));
+
+        dom.input(field, "  ");
+        dom.submit(dom.first("form").unwrap());
+        assert_eq!(*calls.borrow(), ["add 3 Label boxes"]);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::components::theme_manager::use_motion;
+use crate::utils::theme;
+use dioxus::prelude::*;
+
+/// Editor for a todo's tags, below its row
+///
+/// Lists the tags with remove buttons, offers suggested tags, and takes a new
+/// tag. A suggestion's ✕ asks for it not to be suggested again. Closes when
+/// it loses focus.
+#[component]
+pub(super) fn TagEditor(
+    todo_id: usize,
+    tags: Vec<String>,
+    suggestions: Vec<String>,
+    on_tag_add: EventHandler<(usize, String)>,
+    on_tag_remove: EventHandler<(usize, String)>,
+    on_suppress: EventHandler<String>,
+    on_close: EventHandler<()>,
+    is_dark_mode: bool,
+) -> Element {
+    let motion_class = use_motion("transition-colors duration-200");
+    let button_motion_class = use_motion("transition-colors duration-150");
+
+    let mut new_tag = use_signal(String::new);
+
+    let handle_tag_add = move |evt: Event<FormData>| {
+        evt.prevent_default();
+        let tag = new_tag.read().trim().to_string();
+        if !tag.is_empty() {
+            on_tag_add.call((todo_id, tag));
+            new_tag.set(String::new());
+        }
+    };
+
+    let tag_class = theme::tag_pill_class(is_dark_mode);
+    let tag_suggestion_button_class = theme::tag_suggestion_class(is_dark_mode);
+    let add_tag_button_class = theme::add_button_class(is_dark_mode);
+    let border_class = theme::border_class(is_dark_mode);
+    let input_bg_class = theme::field_class(is_dark_mode);
+    let button_text_class = theme::muted_text_class(is_dark_mode);
+    let chip_class = theme::chip_class(is_dark_mode);
+    let hover_class = theme::hover_class(is_dark_mode);
+    let remove_hover_class = theme::hover_text_class(theme::Accent::Red, is_dark_mode);
+
+    rsx! {
+      div {
+        class: "mt-3 flex flex-wrap items-center gap-1.5",
+        tabindex: "0",
+        onblur: move |_| on_close.call(()),
+
+        for tag in tags.iter().cloned() {
+          span {
+            key: "tag-{tag}",
+            class: "{tag_class} text-xs px-2.5 py-0.5 rounded-full flex items-center {motion_class}",
+            span { class: "mr-1", "{tag}" }
+            button {
+              class: "opacity-70 hover:opacity-100 focus:outline-none",
+              onclick: {
+                  let tag = tag.clone();
+                  move |_| on_tag_remove.call((todo_id, tag.clone()))
+              },
+              svg {
+                xmlns: "http://www.w3.org/2000/svg",
+                fill: "none",
+                view_box: "0 0 24 24",
+                stroke_width: "2.5",
+                stroke: "currentColor",
+                class: "w-3 h-3",
+                path {
+                  stroke_linecap: "round",
+                  stroke_linejoin: "round",
+                  d: "M6 18L18 6M6 6l12 12",
+                }
+              }
+            }
+          }
+        }
+
+        for suggestion in suggestions {
+          span { key: "suggestion-{suggestion}", class: "group relative",
+            button {
+              r#type: "button",
+              class: "{tag_suggestion_button_class}",
+              onclick: {
+                  let suggestion = suggestion.clone();
+                  move |_| on_tag_add.call((todo_id, suggestion.clone()))
+              },
+              "+ {suggestion}"
+            }
+            button {
+              r#type: "button",
+              class: "absolute -top-1.5 -right-1.5 hidden group-hover:flex group-focus-within:flex items-center justify-center w-3.5 h-3.5 rounded-full text-[9px] leading-none {chip_class} {remove_hover_class}",
+              title: "Don't suggest \"{suggestion}\"",
+              aria_label: "Don't suggest {suggestion}",
+              onclick: {
+                  let suggestion = suggestion.clone();
+                  move |_| on_suppress.call(suggestion.clone())
+              },
+              "✕"
+            }
+          }
+        }
+
+        form { class: "flex items-center", onsubmit: handle_tag_add,
+          input {
+            class: "text-xs px-2 py-1 border {border_class} {input_bg_class} rounded-l focus:outline-none focus:ring-1 focus:ring-blue-400 w-24 {motion_class}",
+            placeholder: "New tag...",
+            value: "{new_tag.read()}",
+            oninput: move |evt| new_tag.set(evt.value()),
+          }
+          button {
+            r#type: "submit",
+            class: "px-2 py-1 {add_tag_button_class} text-white text-xs rounded-r focus:outline-none focus:ring-1 focus:ring-green-400 {motion_class}",
+            "Add"
+          }
+        }
+        button {
+          r#type: "button",
+          class: "p-1 rounded {button_text_class} {hover_class} {remove_hover_class} {button_motion_class}",
+          onclick: move |_| on_close.call(()),
+          svg {
+            xmlns: "http://www.w3.org/2000/svg",
+            fill: "none",
+            view_box: "0 0 24 24",
+            stroke_width: "1.5",
+            stroke: "currentColor",
+            class: "w-4 h-4",
+            path {
+              stroke_linecap: "round",
+              stroke_linejoin: "round",
+              d: "M6 18L18 6M6 6l12 12",
+            }
+          }
+        }
+      }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Calls = Rc<RefCell<Vec<(&'static str, String)>>>;
+
+    /// Renders the editor for a todo tagged "Work", suggesting "Home".
+    fn render(calls: Calls) -> TestDom {
+        TestDom::with_props(
+            |calls: Calls| {
+                let (added, removed, suppressed) = (calls.clone(), calls.clone(), calls.clone());
+                rsx! {
+                    TagEditor {
+                        todo_id: 3,
+                        tags: vec!["Work".to_string()],
+                        suggestions: vec!["Home".to_string()],
+                        on_tag_add: move |(id, tag): (usize, String)| {
+                            assert_eq!(id, 3);
+                            added.borrow_mut().push(("add", tag));
+                        },
+                        on_tag_remove: move |(_, tag): (usize, String)| removed.borrow_mut().push(("remove", tag)),
+                        on_suppress: move |tag| suppressed.borrow_mut().push(("suppress", tag)),
+                        on_close: |_| {},
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            calls,
+        )
+    }
+
+    #[test]
+    fn test_new_tags_are_trimmed_and_the_field_cleared() {
+        let calls = Calls::default();
+        let mut dom = render(calls.clone());
+
+        let field = dom
+            .find_by_attribute("input", "placeholder", "New tag...")
+            .unwrap();
+        dom.input(field, "  Errands ");
+        dom.submit(dom.first("form").unwrap());
+        assert_eq!(dom.attribute(field, "value"), Some(""));
+
+        // Blank tags are not added
+        dom.input(field, "   ");
+        dom.submit(dom.first("form").unwrap());
+        assert_eq!(*calls.borrow(), vec![("add", "Errands".to_string())]);
+    }
+
+    #[test]
+    fn test_suggestions_add_and_tags_remove() {
+        let calls = Calls::default();
+        let mut dom = render(calls.clone());
+
+        dom.click(dom.find("button", "+ Home").unwrap());
+        let remove = dom
+            .find_by_attribute(
+                "button",
+                "class",
+                "opacity-70 hover:opacity-100 focus:outline-none",
+            )
+            .unwrap();
+        dom.click(remove);
+
+        assert_eq!(
+            *calls.borrow(),
+            vec![("add", "Home".to_string()), ("remove", "Work".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_suppressing_a_suggestion_leaves_the_todo_alone() {
+        let calls = Calls::default();
+        let mut dom = render(calls.clone());
+
+        let suppress = dom
+            .find_by_attribute("button", "aria-label", "Don't suggest Home")
+            .unwrap();
+        dom.click(suppress);
+
+        assert_eq!(*calls.borrow(), vec![("suppress", "Home".to_string())]);
+    }
+}
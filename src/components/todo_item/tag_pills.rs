@@ -0,0 +1,113 @@
+use crate::components::theme_manager::use_motion;
+use crate::utils::theme;
+use dioxus::prelude::*;
+
+/// A todo's tags shown beside its text
+///
+/// More than three tags start collapsed to the first two, with a button
+/// showing the rest.
+#[component]
+pub(super) fn TagPills(
+    tags: Vec<String>,
+    collapsed: bool,
+    on_collapse: EventHandler<bool>,
+    compact: bool,
+    is_dark_mode: bool,
+) -> Element {
+    let motion_class = use_motion("transition-colors duration-200");
+    let button_motion_class = use_motion("transition-colors duration-150");
+
+    let tag_class = theme::tag_pill_class(is_dark_mode);
+    let chip_class = theme::chip_class(is_dark_mode);
+    let visible_tags = if collapsed {
+        &tags[..tags.len().min(2)]
+    } else {
+        &tags[..]
+    };
+
+    rsx! {
+      div { class: if compact { "flex flex-wrap items-center gap-1.5 basis-full min-w-0 max-w-full" } else { "flex flex-wrap items-center gap-1.5 ml-2 min-w-0 max-w-full" },
+        for tag in visible_tags {
+          span {
+            key: "tag-{tag}",
+            class: "{tag_class} text-xs px-2 py-0.5 rounded-full flex items-center max-w-full {motion_class}",
+            span { class: "truncate", title: "{tag}", "{tag}" }
+          }
+        }
+
+        // Show tag count indicator if collapsed
+        if collapsed && tags.len() > 2 {
+          button {
+            class: "text-xs px-2 py-0.5 rounded-full {chip_class} {button_motion_class}",
+            onclick: move |_| on_collapse.call(false),
+            "+{tags.len() - 2} more"
+          }
+        } else if !collapsed && tags.len() > 3 {
+          button {
+            class: "text-xs px-2 py-0.5 rounded-full {chip_class} {button_motion_class}",
+            onclick: move |_| on_collapse.call(true),
+            "Show less"
+          }
+        }
+      }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_collapsed_pills_show_two_tags_and_a_count() {
+        let expanded = Rc::new(RefCell::new(Vec::new()));
+        let mut dom = TestDom::with_props(
+            |expanded: Rc<RefCell<Vec<bool>>>| {
+                rsx! {
+                    TagPills {
+                        tags: ["Home", "Work", "Errands", "Later"].map(String::from).to_vec(),
+                        collapsed: true,
+                        on_collapse: move |collapsed| expanded.borrow_mut().push(collapsed),
+                        compact: false,
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            expanded.clone(),
+        );
+
+        assert!(dom.find("span", "Work").is_some());
+        assert!(dom.find("span", "Errands").is_none());
+        dom.click(dom.find("button", "+2 more").unwrap());
+        assert_eq!(*expanded.borrow(), vec![false]);
+    }
+
+    /// Renders `count` expanded tags.
+    fn expanded(count: usize) -> TestDom {
+        TestDom::with_props(
+            |count: usize| {
+                rsx! {
+                    TagPills {
+                        tags: (0..count).map(|n| format!("tag{n}")).collect::<Vec<_>>(),
+                        collapsed: false,
+                        on_collapse: |_| {},
+                        compact: false,
+                        is_dark_mode: false,
+                    }
+                }
+            },
+            count,
+        )
+    }
+
+    #[test]
+    fn test_expanded_pills_offer_to_collapse_past_three_tags() {
+        let dom = expanded(4);
+        assert!(dom.find("span", "tag3").is_some());
+        assert!(dom.find("button", "Show less").is_some());
+
+        assert!(expanded(3).first("button").is_none());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::components::theme_manager::use_motion;
+use crate::utils::text::{self, UrlError};
+use crate::utils::theme;
+use dioxus::prelude::*;
+
+/// Button opening a todo's link
+///
+/// A plain link with `target="_blank"`: the web build opens a new tab and the
+/// desktop webview hands it to the system browser.
+#[component]
+pub fn TodoLinkButton(url: String, class: String) -> Element {
+    rsx! {
+        a {
+            class: "inline-flex items-center {class}",
+            href: "{url}",
+            target: "_blank",
+            rel: "noopener noreferrer",
+            title: "Open {url}",
+            aria_label: "Open link",
+            svg {
+                xmlns: "http://www.w3.org/2000/svg",
+                fill: "none",
+                view_box: "0 0 24 24",
+                stroke_width: "1.5",
+                stroke: "currentColor",
+                class: "w-4 h-4",
+                path {
+                    stroke_linecap: "round",
+                    stroke_linejoin: "round",
+                    d: "M13.19 8.688a4.5 4.5 0 011.242 7.244l-4.5 4.5a4.5 4.5 0 01-6.364-6.364l1.757-1.757m13.35-.622l1.757-1.757a4.5 4.5 0 00-6.364-6.364l-4.5 4.5a4.5 4.5 0 001.242 7.244",
+                }
+            }
+        }
+    }
+}
+
+/// Input for setting or clearing a todo's link
+///
+/// The link is saved on Enter or when the input loses focus, if it is a valid
+/// http(s) address; an empty input removes it.
+#[component]
+pub fn TodoUrlField(
+    todo_id: usize,
+    url: Option<String>,
+    on_url_change: EventHandler<(usize, Option<String>)>,
+    is_dark_mode: bool,
+) -> Element {
+    let motion_class = use_motion("transition-colors");
+
+    let mut draft = use_signal(|| url.clone().unwrap_or_default());
+    let mut error = use_signal(|| None::<UrlError>);
+
+    let input_class = theme::input_class(is_dark_mode);
+    let label_class = theme::muted_text_class(is_dark_mode);
+
+    let mut save = {
+        let url = url.clone();
+        move || {
+            let input = draft.read().trim().to_string();
+            let validated = if input.is_empty() {
+                Ok(None)
+            } else {
+                text::validate_url(&input).map(Some)
+            };
+            match validated {
+                Ok(next) => {
+                    error.set(None);
+                    if next != url {
+                        on_url_change.call((todo_id, next));
+                    }
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        }
+    };
+    let mut save_on_blur = save.clone();
+
+    rsx! {
+        form {
+            class: "flex flex-wrap items-center gap-2 text-xs",
+            onsubmit: move |evt| {
+                evt.prevent_default();
+                save();
+            },
+            label { class: "font-medium {label_class}", r#for: "todo-url-{todo_id}", "Link:" }
+            input {
+                id: "todo-url-{todo_id}",
+                r#type: "url",
+                class: "flex-1 min-w-0 px-2 py-1 border rounded shadow-sm focus:outline-none focus:ring-1 focus:ring-blue-400 {motion_class} {input_class}",
+                placeholder: "https://…",
+                value: "{draft}",
+                aria_invalid: error.read().is_some(),
+                oninput: move |evt| {
+                    draft.set(evt.value());
+                    error.set(None);
+                },
+                onblur: move |_| save_on_blur(),
+            }
+            if let Some(e) = error() {
+                p { class: "w-full text-red-500", role: "alert", "The {e}." }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::{AttributeValue, Mutation, Mutations};
+
+    #[test]
+    fn test_todo_url_field_shows_the_link() {
+        let mut app = VirtualDom::new(|| {
+            rsx! {
+                TodoUrlField {
+                    todo_id: 1,
+                    url: Some("https://example.com/invoice".to_string()),
+                    on_url_change: |_| {},
+                    is_dark_mode: false,
+                }
+            }
+        });
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        assert!(mutations.edits.iter().any(|edit| matches!(
+            edit,
+            Mutation::SetAttribute { name: "value", value: AttributeValue::Text(value), .. }
+                if value == "https://example.com/invoice"
+        )));
+    }
+}
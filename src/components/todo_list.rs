@@ -1,22 +1,67 @@
+use super::clock::use_clock;
+use super::theme_manager::use_motion;
 use super::todo_item::TodoItem;
-use crate::models::{FilterState, Todo};
+use crate::models::minimap::{self, MinimapMark};
+use crate::models::view::ListViewModel;
+use crate::models::{FilterCriteria, IssueLinker, ListView, Todo, WeekStart};
+use crate::utils::constants::todo::DEFAULT_STALE_AFTER_DAYS;
+use crate::utils::constants::ui::MINIMAP_MIN_ROWS;
+use crate::utils::theme;
 use chrono::{DateTime, Utc};
 use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Id of the scrolling list, which the minimap scrolls
+const SCROLL_ID: &str = "todo-list-scroll";
+
+/// Id of the minimap strip
+const MINIMAP_ID: &str = "todo-list-minimap";
+
+/// Listens for Escape until the drag ends, then reports whether it was pressed.
+const WATCH_DRAG_ESCAPE: &str = r#"
+    const stop = (escaped) => {
+        window.removeEventListener("keydown", onKeyDown, true);
+        document.removeEventListener("dragend", onDragEnd, true);
+        dioxus.send(escaped);
+    };
+    const onKeyDown = (event) => {
+        if (event.key === "Escape") {
+            stop(true);
+        }
+    };
+    const onDragEnd = () => stop(false);
+    window.addEventListener("keydown", onKeyDown, true);
+    document.addEventListener("dragend", onDragEnd, true);
+"#;
 
 /// Props for the TodoList component.
 #[derive(Props, PartialEq, Clone)]
 pub struct TodoListProps {
-    /// The list of todos to display
-    pub todos: Vec<Todo>,
-    /// The current filter state
-    pub filter: FilterState,
-    /// Search text to filter todos by
-    #[props(default = String::new())]
-    pub search_text: String,
+    /// The list of todos to display, shared with the items so rendering does not clone them
+    pub todos: Vec<Rc<Todo>>,
+    /// Whether the todos come from the trash
+    #[props(default = false)]
+    pub in_trash: bool,
+    /// The filters narrowing the todos down to those shown
+    pub criteria: FilterCriteria,
+    /// First day of the week, which decides where "this week" ends
+    #[props(default)]
+    pub week_start: WeekStart,
     /// Callback when a todo is toggled
     pub on_toggle: EventHandler<usize>,
+    /// Callback to complete the given todos, or reopen them when all are done
+    #[props(default)]
+    pub on_toggle_all: EventHandler<Vec<usize>>,
     /// Callback when a todo is deleted
     pub on_delete: EventHandler<usize>,
+    /// Callback when a todo is restored from the trash
+    pub on_restore: EventHandler<usize>,
+    /// Callback when a todo in the trash is deleted for good
+    #[props(default)]
+    pub on_purge: EventHandler<usize>,
+    /// Callback when an archived todo is brought back into the list
+    #[props(default)]
+    pub on_unarchive: EventHandler<usize>,
     /// Callback when a todo is updated
     pub on_update: EventHandler<(usize, String)>,
     /// Callback when a todo's due date is changed
@@ -25,148 +70,316 @@ pub struct TodoListProps {
     pub on_tag_add: EventHandler<(usize, String)>,
     /// Callback when a tag is removed from a todo
     pub on_tag_remove: EventHandler<(usize, String)>,
+    /// Callback when a todo's notes are edited
+    pub on_notes_change: EventHandler<(usize, String)>,
+    /// Callback when a todo's link is set or cleared
+    #[props(default)]
+    pub on_url_change: EventHandler<(usize, Option<String>)>,
+    /// Callback when a subtask is added to a todo
+    #[props(default)]
+    pub on_subtask_add: EventHandler<(usize, String)>,
+    /// Callback when a subtask is checked off or reopened, given the todo and subtask ids
+    #[props(default)]
+    pub on_subtask_toggle: EventHandler<(usize, usize)>,
+    /// Callback when a subtask is deleted, given the todo and subtask ids
+    #[props(default)]
+    pub on_subtask_remove: EventHandler<(usize, usize)>,
+    /// Callback to create a todo from a line of another todo's notes
+    pub on_extract: EventHandler<(usize, usize, bool)>,
+    /// Callback to bring a todo into view, clearing filters that hide it
+    pub on_reveal: EventHandler<usize>,
+    /// Callback when a todo is locked or unlocked
+    pub on_lock_change: EventHandler<(usize, bool)>,
+    /// Whether unlocking a todo asks for confirmation first
+    #[props(default = false)]
+    pub confirm_unlock: bool,
     /// Callback when a todo is reordered via drag and drop
     pub on_reorder: EventHandler<(usize, usize)>,
-    /// Optional selected tag for filtering
-    pub selected_tag: Option<String>,
+    /// Whether todos can be dragged into another order, which only the
+    /// manual sort order keeps
+    #[props(default = true)]
+    pub reorderable: bool,
+    /// Callback to pick a position to move a todo to, for those who cannot drag
+    #[props(default)]
+    pub on_move_to_position: EventHandler<usize>,
+    /// Callback when dragging a todo starts
+    #[props(default)]
+    pub on_drag_start: EventHandler<usize>,
+    /// Callback when dragging a todo ends, returning whether a drop target
+    /// outside the list took it, in which case the list is not reordered
+    #[props(default)]
+    pub on_drag_end: Callback<usize, bool>,
+    /// The todo shown in the docked detail panel, highlighted in the list
+    #[props(default)]
+    pub selected_id: Option<usize>,
+    /// Callback when a row is clicked, selecting its todo for the detail panel
+    #[props(default)]
+    pub on_select: EventHandler<usize>,
+    /// Whether due dates show their ISO week number
+    #[props(default = false)]
+    pub show_week_numbers: bool,
+    /// Whether stale todos show an aging clock
+    #[props(default = false)]
+    pub show_stale: bool,
+    /// Days without changes after which an open todo is stale
+    #[props(default = DEFAULT_STALE_AFTER_DAYS)]
+    pub stale_after_days: u32,
+    /// When the app was started, from which `is:new` lists todos
+    #[props(default)]
+    pub session_start: Option<DateTime<Utc>>,
+    /// Whether the first nine rows show the number key that toggles them
+    #[props(default = false)]
+    pub show_row_numbers: bool,
+    /// Whether long lists show a strip of due-date marks beside them
+    #[props(default = false)]
+    pub show_minimap: bool,
+    /// Whether rows are laid out for a narrow window
+    #[props(default = false)]
+    pub compact: bool,
+    /// Rules linking issue-tracker references in todo text
+    #[props(default)]
+    pub issue_linker: Rc<IssueLinker>,
     /// Whether dark mode is enabled
     #[props(default = false)]
     pub is_dark_mode: bool,
-    /// List of default tags to suggest
-    pub default_tags: Option<Vec<String>>,
+    /// Gets the tags to suggest from the tags a todo already has
+    #[props(default)]
+    pub suggest_tags: Callback<Vec<String>, Vec<String>>,
+    /// Callback when a tag should no longer be suggested
+    #[props(default)]
+    pub on_suppress_tag: EventHandler<String>,
 }
 
 /// Component that renders a list of TodoItems.
 #[component]
 pub fn TodoList(props: TodoListProps) -> Element {
+    let motion_class = use_motion("transition-colors duration-300");
+    let row_motion_class = use_motion("transition-colors duration-200");
+
     // State to track drag and drop
     let mut drag_item = use_signal(|| None::<usize>);
     let mut drag_over_item = use_signal(|| None::<usize>);
+    let clock = use_clock();
 
     // Dynamic classes based on dark mode
-    let container_bg_class = if props.is_dark_mode {
-        "bg-gray-800"
+    let container_bg_class = theme::surface_class(props.is_dark_mode);
+    let text_class = theme::muted_text_class(props.is_dark_mode);
+    let border_class = format!(
+        "{} {}",
+        theme::divide_class(props.is_dark_mode),
+        theme::border_class(props.is_dark_mode)
+    );
+
+    // Filter todos based on the current filter state, selected tag, and search text
+    #[cfg(debug_assertions)]
+    let filter_started = chrono::Utc::now();
+
+    // Gather the filters once, parsing the search text once rather than for every todo
+    let view = ListView {
+        week_start: props.week_start,
+        stale_after_days: props.stale_after_days,
+        session_start: props.session_start,
+        ..props.criteria.view()
+    };
+    let now = clock.now_utc();
+    let today = clock.today_local();
+    let model = ListViewModel::compute(
+        &props.todos,
+        &view,
+        &props.criteria.search_text,
+        props.in_trash,
+        now,
+        today,
+    );
+
+    #[cfg(debug_assertions)]
+    tracing::debug!(
+        "Filtered {} of {} todos in {}µs",
+        model.rows.len(),
+        props.todos.len(),
+        (chrono::Utc::now() - filter_started)
+            .num_microseconds()
+            .unwrap_or_default()
+    );
+
+    // One mark per row on screen, left out for short lists
+    let marks = if props.show_minimap {
+        minimap::marks(
+            model.rows.iter().map(|row| &**row.todo),
+            today,
+            MINIMAP_MIN_ROWS,
+        )
     } else {
-        "bg-white"
+        None
     };
-    let text_class = if props.is_dark_mode {
-        "text-gray-400"
+    let row_count = model.rows.len();
+
+    // The todos the "mark all" toggle changes: those on screen, archived ones aside
+    let toggle_ids: Vec<usize> = if props.in_trash {
+        Vec::new()
     } else {
-        "text-gray-500"
+        model
+            .rows
+            .iter()
+            .filter(|row| !row.todo.archived)
+            .map(|row| row.todo.id)
+            .collect()
     };
-    let border_class = if props.is_dark_mode {
-        "divide-gray-700 border-gray-700"
+    let all_completed = model
+        .rows
+        .iter()
+        .filter(|row| !row.todo.archived)
+        .all(|row| row.todo.completed);
+    let toggle_all_label = if all_completed {
+        "Mark all as active"
     } else {
-        "divide-gray-200 border-gray-200"
+        "Mark all as complete"
     };
 
-    // Filter todos based on the current filter state, selected tag, and search text
-    let filtered_todos = props
-        .todos
-        .iter()
-        .filter(|todo| {
-            // Filter state match
-            let filter_match = props.filter.matches(todo);
-
-            // Tag match
-            let tag_match = match &props.selected_tag {
-                Some(tag) => todo.tags.contains(tag),
-                None => true,
-            };
-
-            // Search text match
-            let search_match = if props.search_text.is_empty() {
-                true
-            } else {
-                // Case-insensitive search
-                let search_term = props.search_text.to_lowercase();
-                let todo_text = todo.text.to_lowercase();
-
-                // Search in todo text
-                let text_match = todo_text.contains(&search_term);
-
-                // Search in tags
-                let tags_match = todo
-                    .tags
-                    .iter()
-                    .any(|tag| tag.to_lowercase().contains(&search_term));
-
-                text_match || tags_match
-            };
-
-            filter_match && tag_match && search_match
-        })
-        .cloned()
-        .collect::<Vec<_>>();
-
-    // Provide an empty Vec if default_tags is None
-    let default_tags_list = props.default_tags.clone().unwrap_or_default();
+    // Scrolls the list so the row under a click on the minimap is in the middle
+    let jump_to = move |y: f64| async move {
+        let Ok(Some((strip_height, scroll_height, client_height))) = document::eval(&format!(
+            r#"
+            const strip = document.getElementById('{MINIMAP_ID}');
+            const list = document.getElementById('{SCROLL_ID}');
+            return strip && list ? [strip.clientHeight, list.scrollHeight, list.clientHeight] : null;
+            "#
+        ))
+        .join::<Option<(f64, f64, f64)>>()
+        .await
+        else {
+            return;
+        };
+        let Some(row) = minimap::row_at(y, strip_height, row_count) else {
+            return;
+        };
+        let scroll_top = minimap::scroll_top_for(row, row_count, scroll_height, client_height);
+        let eval = document::eval(&format!(
+            r#"
+            const top = await dioxus.recv();
+            document.getElementById('{SCROLL_ID}')?.scrollTo({{ top }});
+            "#
+        ));
+        let _ = eval.send(scroll_top);
+    };
+
+    let selected_class = theme::highlight_class(props.is_dark_mode);
+    let checkbox_class = theme::checkbox_class(props.is_dark_mode);
 
     // Drag handlers
     let on_reorder = props.on_reorder;
-
-    // Determine empty state message
-    let empty_state_message = if props.todos.is_empty() {
-        "Add your first todo above! ✨".to_string()
-    } else if !props.search_text.is_empty() {
-        format!("No todos match your search: '{}'", props.search_text)
-    } else if props.selected_tag.is_some() {
-        "No todos found with the selected tag.".to_string()
-    } else {
-        match props.filter {
-            FilterState::Active => "All tasks done! 🎉".to_string(),
-            FilterState::Completed => "No completed tasks yet.".to_string(),
-            FilterState::All => "No tasks match the current filter.".to_string(),
-        }
-    };
+    let on_drag_start = props.on_drag_start;
+    let on_drag_end = props.on_drag_end;
+    // Rows in the trash, or sorted by anything but the manual order, stay put
+    let draggable = props.reorderable && !props.in_trash;
+    let drag_class = if draggable { "cursor-move" } else { "" };
 
     rsx! {
-        div { class: "{container_bg_class} rounded-lg shadow-md overflow-hidden transition-colors duration-300 border {border_class} h-[400px] overflow-y-auto",
+        div { class: "{container_bg_class} rounded-lg shadow-md overflow-hidden {motion_class} border {border_class} h-[400px] flex",
+            div { id: SCROLL_ID, class: "flex-1 min-w-0 overflow-y-auto",
 
-            if filtered_todos.is_empty() {
-                div { class: "p-8 text-center {text_class} transition-colors duration-300 text-lg italic",
-                    "{empty_state_message}"
-                }
-            } else {
-                ul { class: "divide-y {border_class} transition-colors duration-300 h-max ",
-                    for todo in filtered_todos {
-                        {
-                            let todo_id = todo.id;
-                            rsx! {
-                                li {
-                                    key: "todo-{todo_id}",
-                                    class: "relative transition-colors duration-200 cursor-move",
-                                    draggable: "true",
-                                    ondragstart: move |_| {
-                                        drag_item.set(Some(todo_id));
-                                    },
-                                    ondragenter: move |_| {
-                                        drag_over_item.set(Some(todo_id));
-                                    },
-                                    ondragend: move |_: Event<DragData>| {
-                                        if let (Some(source_id), Some(target_id)) = (drag_item(), drag_over_item()) {
-                                            if source_id != target_id {
+                if let Some(empty_state) = &model.empty_state {
+                    div { class: "p-8 text-center {text_class} {motion_class} text-lg italic",
+                        "{empty_state}"
+                    }
+                } else {
+                    if !toggle_ids.is_empty() {
+                        div { class: "sticky top-0 z-10 flex items-center gap-3 px-4 py-2 border-b text-sm {container_bg_class} {border_class} {text_class}",
+                            input {
+                                r#type: "checkbox",
+                                id: "toggle-all",
+                                class: "w-5 h-5 rounded {checkbox_class}",
+                                checked: all_completed,
+                                onchange: move |_| props.on_toggle_all.call(toggle_ids.clone()),
+                            }
+                            label { r#for: "toggle-all", "{toggle_all_label}" }
+                        }
+                    }
+                    ul { class: "divide-y {border_class} {motion_class} h-max ",
+                        for (index, row) in model.rows.into_iter().enumerate() {
+                            {
+                                let todo = row.todo;
+                                let number = (props.show_row_numbers && index < 9).then_some(index + 1);
+                                let todo_id = todo.id;
+                                let is_selected = props.selected_id == Some(todo_id);
+                                rsx! {
+                                    li {
+                                        key: "todo-{todo_id}",
+                                        class: if props.in_trash { "relative" } else if is_selected { "relative {row_motion_class} {drag_class} {selected_class}" } else { "relative {row_motion_class} {drag_class}" },
+                                        aria_current: if is_selected { "true" } else { "false" },
+                                        onclick: move |_| props.on_select.call(todo_id),
+                                        draggable: if draggable { "true" } else { "false" },
+                                        ondragstart: move |_| {
+                                            drag_item.set(Some(todo_id));
+                                            on_drag_start.call(todo_id);
+                                            // Escape cancels the drag, leaving the order alone
+                                            spawn(async move {
+                                                let mut eval = document::eval(WATCH_DRAG_ESCAPE);
+                                                if let Ok(true) = eval.recv::<bool>().await {
+                                                    drag_item.set(None);
+                                                    drag_over_item.set(None);
+                                                }
+                                            });
+                                        },
+                                        ondragenter: move |_| {
+                                            drag_over_item.set(Some(todo_id));
+                                        },
+                                        ondragend: move |_: Event<DragData>| {
+                                            let dropped_outside = on_drag_end.call(todo_id);
+                                            if let (Some(source_id), Some(target_id)) = (drag_item(), drag_over_item())
+                                                && source_id != target_id
+                                                && !dropped_outside
+                                            {
                                                 on_reorder.call((source_id, target_id));
                                             }
+                                            drag_item.set(None);
+                                            drag_over_item.set(None);
+                                        },
+                                        ondragover: move |evt| evt.prevent_default(),
+
+                                        // Add subtle highlight when dragging over this item
+                                        style: if drag_over_item() == Some(todo_id) && drag_item() != Some(todo_id) { "box-shadow: inset 0 -2px 0 0 rgba(79, 70, 229, 0.5); background-color: rgba(79, 70, 229, 0.1);" } else { "" },
+
+                                        if let Some(number) = number {
+                                            span {
+                                                class: "absolute left-0.5 top-0.5 z-10 px-1 rounded text-[10px] font-mono bg-blue-500 text-white pointer-events-none",
+                                                aria_hidden: "true",
+                                                "{number}"
+                                            }
+                                        }
+
+                                        TodoItem {
+                                            todo: Rc::clone(todo),
+                                            on_toggle: props.on_toggle,
+                                            on_delete: props.on_delete,
+                                            in_trash: props.in_trash,
+                                            on_restore: props.on_restore,
+                                            on_purge: props.on_purge,
+                                            on_unarchive: props.on_unarchive,
+                                            on_update: props.on_update,
+                                            on_due_date_change: props.on_due_date_change,
+                                            on_tag_add: props.on_tag_add,
+                                            on_tag_remove: props.on_tag_remove,
+                                            on_notes_change: props.on_notes_change,
+                                            on_url_change: props.on_url_change,
+                                            on_subtask_add: props.on_subtask_add,
+                                            on_subtask_toggle: props.on_subtask_toggle,
+                                            on_subtask_remove: props.on_subtask_remove,
+                                            on_extract: props.on_extract,
+                                            parent_text: row.parent_text.map(str::to_string),
+                                            on_reveal: props.on_reveal,
+                                            on_lock_change: props.on_lock_change,
+                                            confirm_unlock: props.confirm_unlock,
+                                            movable: draggable,
+                                            on_move_to_position: props.on_move_to_position,
+                                            show_week_number: props.show_week_numbers,
+                                            stale_days: row.stale_days.filter(|_| props.show_stale),
+                                            compact: props.compact,
+                                            issue_linker: props.issue_linker.clone(),
+                                            is_dark_mode: props.is_dark_mode,
+                                            suggest_tags: props.suggest_tags,
+                                            on_suppress_tag: props.on_suppress_tag,
                                         }
-                                        drag_item.set(None);
-                                        drag_over_item.set(None);
-                                    },
-                                    ondragover: move |evt| evt.prevent_default(),
-
-                                    // Add subtle highlight when dragging over this item
-                                    style: if drag_over_item() == Some(todo_id) && drag_item() != Some(todo_id) { "box-shadow: inset 0 -2px 0 0 rgba(79, 70, 229, 0.5); background-color: rgba(79, 70, 229, 0.1);" } else { "" },
-
-                                    TodoItem {
-                                        todo: todo.clone(),
-                                        on_toggle: props.on_toggle,
-                                        on_delete: props.on_delete,
-                                        on_update: props.on_update,
-                                        on_due_date_change: props.on_due_date_change,
-                                        on_tag_add: props.on_tag_add,
-                                        on_tag_remove: props.on_tag_remove,
-                                        is_dark_mode: props.is_dark_mode,
-                                        default_tags: default_tags_list.clone(),
                                     }
                                 }
                             }
@@ -174,6 +387,31 @@ pub fn TodoList(props: TodoListProps) -> Element {
                     }
                 }
             }
+
+            if let Some(marks) = marks {
+                div {
+                    id: MINIMAP_ID,
+                    class: "w-2 shrink-0 flex flex-col border-l {border_class} cursor-pointer",
+                    title: "Jump to this part of the list",
+                    aria_hidden: "true",
+                    onclick: move |evt| jump_to(evt.element_coordinates().y),
+                    for mark in marks {
+                        div { class: "flex-1 min-h-0 pointer-events-none {mark_class(mark, props.is_dark_mode)}" }
+                    }
+                }
+            }
         }
     }
 }
+
+/// Gets the color of a row in the minimap.
+fn mark_class(mark: MinimapMark, is_dark_mode: bool) -> &'static str {
+    match (mark, is_dark_mode) {
+        (MinimapMark::Overdue, _) => "bg-red-500",
+        (MinimapMark::DueToday, _) => "bg-amber-400",
+        (MinimapMark::Completed, false) => "bg-gray-400",
+        (MinimapMark::Completed, true) => "bg-gray-500",
+        (MinimapMark::Other, false) => "bg-gray-100",
+        (MinimapMark::Other, true) => "bg-gray-700",
+    }
+}
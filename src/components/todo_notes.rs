@@ -0,0 +1,201 @@
+use crate::components::TextStatsLine;
+use crate::utils::notes;
+use crate::utils::theme;
+use dioxus::prelude::*;
+
+/// Props for the TodoNotes component.
+#[derive(Props, PartialEq, Clone)]
+pub struct TodoNotesProps {
+    /// The id of the todo the notes belong to
+    pub todo_id: usize,
+    /// The saved notes
+    pub notes: String,
+    /// Callback when the notes are edited
+    pub on_notes_change: EventHandler<(usize, String)>,
+    /// Callback to create a todo from a line: the todo id, the line index and
+    /// whether to strike the line through
+    pub on_extract: EventHandler<(usize, usize, bool)>,
+    /// Whether dark mode is enabled
+    #[props(default = false)]
+    pub is_dark_mode: bool,
+}
+
+/// Viewer and editor for a todo's notes, with actions turning lines into todos
+#[component]
+pub fn TodoNotes(props: TodoNotesProps) -> Element {
+    let todo_id = props.todo_id;
+    let textarea_id = format!("notes-{}", todo_id);
+    let mut editing = use_signal(|| props.notes.is_empty());
+    let mut draft = use_signal(|| props.notes.clone());
+    let mut strike = use_signal(|| true);
+
+    // Saves the draft if it differs from the stored notes
+    let save_draft = {
+        let saved = props.notes.clone();
+        move || {
+            let text = draft();
+            if text != saved {
+                props.on_notes_change.call((todo_id, text));
+            }
+        }
+    };
+
+    // Extracts the line holding the cursor, saving pending edits first
+    let extract_selected = {
+        let textarea_id = textarea_id.clone();
+        let save_draft = save_draft.clone();
+        move |_| {
+            let textarea_id = textarea_id.clone();
+            let save_draft = save_draft.clone();
+            async move {
+                let cursor = document::eval(&format!(
+                    "return document.getElementById('{textarea_id}')?.selectionStart ?? null;"
+                ))
+                .join::<Option<usize>>()
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+
+                save_draft();
+                let line_index = notes::line_at(&draft.read(), cursor);
+                if strike() {
+                    let struck = notes::strike_line(&draft.read(), line_index);
+                    draft.set(struck);
+                }
+                props.on_extract.call((todo_id, line_index, strike()));
+            }
+        }
+    };
+
+    // Struck lines are shown with a line-through instead of their ~~ markers
+    let lines: Vec<(usize, String, bool, bool)> = props
+        .notes
+        .split('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            (
+                index,
+                line.replace("~~", ""),
+                notes::is_struck(line),
+                notes::action_item(line).is_some(),
+            )
+        })
+        .collect();
+
+    let panel_class = theme::inset_class(props.is_dark_mode);
+    let input_class = theme::input_class(props.is_dark_mode);
+    let link_class = theme::link_class(props.is_dark_mode);
+
+    rsx! {
+      div { class: "mt-3 p-3 rounded border text-sm {panel_class}",
+        if editing() {
+          textarea {
+            id: "{textarea_id}",
+            class: "w-full min-h-24 px-2 py-1 rounded border font-mono text-xs {input_class}",
+            placeholder: "Notes. Lines starting with - are action items.",
+            value: "{draft.read()}",
+            oninput: move |evt| draft.set(evt.value()),
+          }
+          TextStatsLine { text: draft(), is_dark_mode: props.is_dark_mode }
+          div { class: "flex flex-wrap items-center gap-3 mt-1",
+            button {
+              r#type: "button",
+              class: "text-xs {link_class}",
+              onclick: {
+                  let save_draft = save_draft.clone();
+                  move |_| {
+                      save_draft();
+                      editing.set(false);
+                  }
+              },
+              "Done"
+            }
+            button {
+              r#type: "button",
+              class: "text-xs {link_class}",
+              title: "Create a todo from the line with the cursor",
+              onclick: extract_selected,
+              "Extract line to todo"
+            }
+            label { class: "flex items-center gap-1 text-xs",
+              input {
+                r#type: "checkbox",
+                checked: strike(),
+                onchange: move |evt| strike.set(evt.checked()),
+              }
+              "Strike through extracted lines"
+            }
+          }
+        } else {
+          ul { class: "space-y-0.5",
+            for (index, line, struck, is_action_item) in lines {
+              li { key: "{index}", class: "group/line flex items-center gap-2 min-h-5",
+                span {
+                  class: if struck { "flex-1 whitespace-pre-wrap break-words line-through opacity-60" } else { "flex-1 whitespace-pre-wrap break-words" },
+                  "{line}"
+                }
+                if is_action_item {
+                  button {
+                    r#type: "button",
+                    class: "text-xs {link_class} opacity-0 group-hover/line:opacity-100 focus:opacity-100",
+                    title: "Create a todo from this line",
+                    onclick: move |_| props.on_extract.call((todo_id, index, strike())),
+                    "→ Todo"
+                  }
+                }
+              }
+            }
+          }
+          button {
+            r#type: "button",
+            class: "text-xs {link_class} mt-1",
+            onclick: {
+                let notes = props.notes.clone();
+                move |_| {
+                    draft.set(notes.clone());
+                    editing.set(true);
+                }
+            },
+            "Edit notes"
+          }
+        }
+      }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::{Mutation, Mutations};
+
+    #[test]
+    fn test_todo_notes_viewer() {
+        let mut app = VirtualDom::new_with_props(
+            |notes: String| {
+                rsx! {
+                    TodoNotes {
+                        todo_id: 1,
+                        notes,
+                        on_notes_change: |_| {},
+                        on_extract: |_| {},
+                    }
+                }
+            },
+            "Context\n- Email Bob\n- ~~Book room~~".to_string(),
+        );
+
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        let texts: Vec<String> = mutations
+            .edits
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Mutation::CreateTextNode { value, .. } => Some(value),
+                _ => None,
+            })
+            .collect();
+        assert!(texts.contains(&"- Email Bob".to_string()));
+        assert!(texts.contains(&"- Book room".to_string()));
+    }
+}
@@ -1,124 +1,1141 @@
-use crate::models::{FilterState, TodoList};
+use crate::components::clock::use_clock;
+use crate::components::debounce::use_debounced;
+use crate::models::auto_archive::select_auto_archivable;
+use crate::models::duplicates::Merge;
+use crate::models::escalation::plan_escalations;
+use crate::models::import_plan::ImportPlan;
+use crate::models::quiet::QuietMode;
+use crate::models::reminder;
+use crate::models::retention::{RetentionPolicy, select_purgeable};
+use crate::models::{
+    AppSettings, AutoTagger, ClearedTodos, FilterCriteria, ShiftedDueDates, SortMode, Todo,
+    TodoError, TodoHistory, TodoList,
+};
 use crate::utils;
-use crate::utils::constants::storage::TODO_STORAGE_KEY;
+use crate::utils::autosave::SaveTracker;
+use crate::utils::constants::ui::FILTERS_SAVE_DEBOUNCE_MS;
+use crate::utils::keys;
+use crate::utils::share::SharedTodo;
+use crate::utils::sound::SoundDebouncer;
+use crate::utils::startup::{self, Milestone};
+use crate::utils::storage::{InitialLoad, LoadFailure, MaintenanceReport, StorageError};
+use crate::utils::text::RepeatGuard;
 use chrono::{DateTime, Utc};
 use dioxus::prelude::*;
+use std::cell::Cell;
 use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
 
 // Type definition for the due date callback
 pub type DueDateCallback = Box<dyn FnMut((usize, Option<DateTime<Utc>>)) + 'static>;
 
+// Type definition for the text update callback, which locked todos refuse
+pub type UpdateTextCallback = Box<dyn FnMut((usize, String)) -> Result<(), TodoError> + 'static>;
+
+// Type definition for the callback restoring an earlier text, given the todo
+// id and the position of the version in its history
+pub type RestoreTextCallback = Box<dyn FnMut((usize, usize)) -> Result<(), TodoError> + 'static>;
+
+// Type definition for the clear completed callback, given the ids of the
+// visible todos or `None` to clear every completed todo
+pub type ClearCompletedCallback = Box<dyn FnMut(Option<Vec<usize>>) -> ClearedTodos + 'static>;
+
+// Type definition for the reorder callbacks, given the todo and the todo or
+// the index to move it to, returning the order before the move when a todo
+// moved, so it can be undone
+pub type ReorderCallback = Box<dyn FnMut((usize, usize)) -> Option<Vec<(usize, usize)>> + 'static>;
+
+// Type definition for the callback putting todos back in an earlier order
+pub type RestoreOrderCallback = Box<dyn FnMut(Vec<(usize, usize)>) + 'static>;
+
+// Type definition for the callback deleting todos past their retention and
+// tidying the storage, returning how many todos were deleted
+pub type ReclaimSpaceCallback =
+    Box<dyn FnMut(DateTime<Utc>) -> Result<(usize, MaintenanceReport), StorageError> + 'static>;
+
 pub struct TodoOperations {
     pub add_todo: Box<dyn FnMut(String) + 'static>,
+    pub add_shared_todo: Box<dyn FnMut(SharedTodo) + 'static>,
+    pub add_linked_todo: Box<dyn FnMut((String, String)) + 'static>,
     pub toggle_todo: Box<dyn FnMut(usize) + 'static>,
-    pub delete_todo: Box<dyn FnMut(usize) + 'static>,
-    pub update_todo: Box<dyn FnMut((usize, String)) + 'static>,
+    pub toggle_all: Box<dyn FnMut(Option<Vec<usize>>) -> usize + 'static>,
+    pub delete_todo: Box<dyn FnMut(usize) -> Result<(), TodoError> + 'static>,
+    pub restore_from_trash: Box<dyn FnMut(usize) + 'static>,
+    pub restore_many_from_trash: Box<dyn FnMut(Vec<usize>) + 'static>,
+    pub purge_from_trash: Box<dyn FnMut(usize) + 'static>,
+    pub empty_trash: Box<dyn FnMut(()) -> usize + 'static>,
+    pub update_todo: UpdateTextCallback,
+    pub restore_text: RestoreTextCallback,
+    pub set_locked: Box<dyn FnMut((usize, bool)) + 'static>,
+    pub set_notes: Box<dyn FnMut((usize, String)) + 'static>,
+    pub set_url: Box<dyn FnMut((usize, Option<String>)) + 'static>,
+    pub add_subtask: Box<dyn FnMut((usize, String)) + 'static>,
+    pub toggle_subtask: Box<dyn FnMut((usize, usize)) + 'static>,
+    pub remove_subtask: Box<dyn FnMut((usize, usize)) + 'static>,
+    pub extract_from_notes: Box<dyn FnMut((usize, usize, bool)) + 'static>,
     pub set_due_date: DueDateCallback,
+    pub shift_due_dates: Box<dyn FnMut((Vec<usize>, i64)) -> ShiftedDueDates + 'static>,
     pub add_tag_to_todo: Box<dyn FnMut((usize, String)) + 'static>,
     pub remove_tag_from_todo: Box<dyn FnMut((usize, String)) + 'static>,
-    pub clear_completed: Box<dyn FnMut(()) + 'static>,
-    pub reorder_todo: Box<dyn FnMut((usize, usize)) + 'static>,
+    pub add_tag_many: Box<dyn FnMut((Vec<usize>, String)) -> usize + 'static>,
+    pub remove_tag_many: Box<dyn FnMut((Vec<usize>, String)) -> usize + 'static>,
+    pub remove_tag_everywhere: Box<dyn FnMut(String) -> usize + 'static>,
+    pub merge_duplicates: Box<dyn FnMut(Vec<Merge>) -> usize + 'static>,
+    pub clear_completed: ClearCompletedCallback,
+    pub restore_cleared: Box<dyn FnMut(Vec<Todo>) + 'static>,
+    pub archive_completed: Box<dyn FnMut(Vec<usize>) -> Vec<usize> + 'static>,
+    pub unarchive_todos: Box<dyn FnMut(Vec<usize>) + 'static>,
+    pub reorder_todo: ReorderCallback,
+    pub reorder_to_index: ReorderCallback,
+    pub restore_order: RestoreOrderCallback,
+    pub restore_todos: Box<dyn FnMut(TodoList) + 'static>,
+    pub import_todos: Box<dyn FnMut(Vec<Todo>) + 'static>,
+    pub apply_import: Box<dyn FnMut(ImportPlan) -> (usize, usize) + 'static>,
+    pub add_sample_todos: Box<dyn FnMut(()) + 'static>,
+    pub save_now: Box<dyn FnMut(()) + 'static>,
+    pub retry_load: Box<dyn FnMut(()) + 'static>,
+    pub apply_auto_tags: Box<dyn FnMut(()) -> usize + 'static>,
+    pub auto_archive: Box<dyn FnMut(DateTime<Utc>) -> Vec<usize> + 'static>,
+    pub escalate: Box<dyn FnMut(DateTime<Utc>) -> usize + 'static>,
+    pub enforce_retention: Box<dyn FnMut(DateTime<Utc>) -> usize + 'static>,
+    pub reclaim_space: ReclaimSpaceCallback,
+    pub bump_todo: Box<dyn FnMut(usize) + 'static>,
+    pub snooze_reminder: Box<dyn FnMut(usize) + 'static>,
+    pub cancel_snooze: Box<dyn FnMut(usize) + 'static>,
+    pub undo: Box<dyn FnMut(()) -> bool + 'static>,
+    pub redo: Box<dyn FnMut(()) -> bool + 'static>,
 }
 
 // Type definition for the return value of use_todo_state
 pub type TodoStateReturn = (
     Signal<TodoList>,
-    Signal<FilterState>,
-    Signal<Option<String>>,
+    Signal<FilterCriteria>,
+    Signal<SortMode>,
     TodoOperations,
-    Vec<String>,
+    Signal<SaveTracker>,
+    Signal<bool>,
+    Signal<Option<LoadFailure>>,
+    Signal<Option<TodoError>>,
 );
 
-pub fn use_todo_state(default_tags: &[&str]) -> TodoStateReturn {
+/// Checks that an operation found its todo.
+fn found(found: bool, id: usize) -> Result<(), TodoError> {
+    if found {
+        Ok(())
+    } else {
+        Err(TodoError::NotFound(id))
+    }
+}
+
+/// Logic for the todo list, its filters, sort order and persistence
+///
+/// Operations that return nothing report a todo that is gone, for example
+/// after a sync removed it, in the returned error signal instead of doing
+/// nothing silently.
+///
+/// Every change made through the operations can be undone and redone, up to
+/// the last `UNDO_HISTORY_LIMIT` changes. Loading, restoring, importing and
+/// syncing replace todos from outside, so the history starts afresh after
+/// them.
+///
+/// Must be called below `use_settings`, whose autosave interval decides when
+/// changes are written to storage, and `use_quiet_mode`, which silences the
+/// completion sound.
+pub fn use_todo_state() -> TodoStateReturn {
     // State
     let mut todo_list = use_signal(TodoList::default);
-    let filter = use_signal(|| FilterState::All);
-    let mut selected_tag = use_signal(|| None::<String>);
+    let criteria = use_signal(|| utils::load::<FilterCriteria>(keys::FILTERS).unwrap_or_default());
+    let sort_mode = use_signal(|| utils::load::<SortMode>(keys::SORT_MODE).unwrap_or_default());
+    let mut loaded = use_signal(|| false);
+    let mut save_tracker = use_signal(SaveTracker::default);
+    let mut load_failure = use_signal(|| None::<LoadFailure>);
+    let settings = use_context::<Signal<AppSettings>>();
+    let quiet_mode = use_context::<Signal<QuietMode>>();
+    let clock = use_clock();
+    let mut sound_debouncer = use_signal(SoundDebouncer::default);
+    let mut repeat_guard = use_signal(RepeatGuard::default);
+    let mut operation_error = use_signal(|| None::<TodoError>);
+    let mut history = use_signal(TodoHistory::default);
+
+    // Bumped on every change so a delayed save can tell it was superseded
+    let change_count = use_hook(|| Rc::new(Cell::new(0u64)));
+    // Set once loading finishes, so the loaded data is not reported as a change
+    let skip_next_change = use_hook(|| Rc::new(Cell::new(false)));
+
+    // Loads todos from storage. Demo mode runs on in-memory storage, which
+    // starts out empty, so it is seeded with the sample todos. Data that is
+    // there but cannot be read blocks saving, so the empty list on screen
+    // cannot overwrite it before it is recovered. Todos that have sat in the
+    // trash too long are deleted on the way in; they are written out with the
+    // next change, and deleted again on every load until then. Archived todos
+    // past their retention go the same way, and so do snoozes that ran out
+    // while the app was closed.
+    let loaded_marker = skip_next_change.clone();
+    let load_clock = clock.clone();
+    let load = move || {
+        let loaded_marker = loaded_marker.clone();
+        let clock = load_clock.clone();
+        spawn(async move {
+            match utils::storage::initial_load_async::<TodoList>(keys::TODOS).await {
+                InitialLoad::Loaded(mut loaded_todos) => {
+                    let policy = RetentionPolicy::from_settings(&settings.peek());
+                    select_purgeable(&loaded_todos, policy, clock.now_utc())
+                        .apply(&mut loaded_todos);
+                    loaded_todos.prune_snoozes(clock.now_utc());
+                    history.write().clear();
+                    todo_list.set(loaded_todos)
+                }
+                InitialLoad::Empty if utils::demo::is_demo_mode() => {
+                    todo_list.set(utils::onboarding::sample_todo_list())
+                }
+                InitialLoad::Empty => {}
+                InitialLoad::Failed(failure) => {
+                    save_tracker.write().block_persistence();
+                    load_failure.set(Some(failure));
+                    return;
+                }
+            }
+            save_tracker.write().unblock_persistence();
+            load_failure.set(None);
+            loaded_marker.set(true);
+            loaded.set(true);
+            startup::record(Milestone::Interactive);
+        });
+    };
+
+    // Load on component mount
+    use_effect(load.clone());
 
-    // Load todos from localStorage on component mount
+    // Remember the filters once they settle, so a search is written once
+    // rather than on every keystroke, and the sort order whenever it changes
+    let settled_criteria =
+        use_debounced(criteria(), Duration::from_millis(FILTERS_SAVE_DEBOUNCE_MS));
+    use_effect(use_reactive!(|settled_criteria| {
+        let _ = utils::save(keys::FILTERS, &settled_criteria);
+    }));
     use_effect(move || {
-        if let Ok(loaded_todos) = utils::load::<TodoList>(TODO_STORAGE_KEY) {
-            todo_list.set(loaded_todos);
-        }
+        let _ = utils::save(keys::SORT_MODE, &*sort_mode.read());
     });
 
-    // Save todos to localStorage whenever they change
+    // Writes the current todos to storage, recording the outcome for the indicator
+    let flush = move || {
+        if save_tracker.peek().is_persistence_blocked() {
+            return;
+        }
+        spawn(async move {
+            let snapshot = todo_list.peek().clone();
+            save_tracker.write().begin_save();
+            let result = utils::save_async(keys::TODOS, &snapshot).await;
+            save_tracker.write().finish_save(&result);
+        });
+    };
+
+    // Save todos after each change, once the initial load is done, waiting for
+    // the autosave interval so a burst of changes is written only once
+    let pending_changes = change_count.clone();
     use_effect(move || {
-        let _ = utils::save(TODO_STORAGE_KEY, &todo_list.read() as &TodoList);
+        let _ = todo_list.read();
+        if !loaded() || skip_next_change.replace(false) {
+            return;
+        }
+        if save_tracker.peek().is_persistence_blocked() {
+            return;
+        }
+        save_tracker.write().mark_dirty();
+        let change = pending_changes.get() + 1;
+        pending_changes.set(change);
+
+        let Some(delay) = settings.peek().autosave.delay() else {
+            return;
+        };
+        let pending_changes = pending_changes.clone();
+        spawn(async move {
+            if !delay.is_zero() {
+                utils::timer::sleep(delay).await;
+            }
+            if pending_changes.get() == change {
+                flush();
+            }
+        });
     });
 
+    // Records why an operation did nothing, for the toast
+    let mut report = move |result: Result<(), TodoError>| {
+        if let Err(error) = result {
+            operation_error.set(Some(error));
+        }
+    };
+
+    // Keeps a change for undo, given the list from before it
+    let mut record = move |before: TodoList, after: &TodoList| {
+        history.write().record(before, after);
+    };
+
+    // Expands emoji shortcodes in entered text when the setting is on
+    let prepare_text = move |text: String| {
+        if settings.peek().expand_emoji {
+            utils::emoji::expand(&text)
+        } else {
+            text
+        }
+    };
+
+    // Compiles the auto-tag rules from the settings
+    let auto_tagger = move || AutoTagger::new(&settings.peek().auto_tag_rules);
+
+    // Gives a todo that just gained tags the due date their rules set
+    let apply_due_rules = {
+        let clock = clock.clone();
+        move |list: &mut TodoList, id: usize| {
+            list.apply_tag_due_rules(id, &settings.peek().tag_due_rules, clock.today_local())
+        }
+    };
+
+    // Drops an add repeating the one before it within a moment, as holding
+    // Enter does; dropped adds leave the list alone, so they are not saved
+    let mut is_repeated_add = {
+        let clock = clock.clone();
+        move |text: &str| !repeat_guard.write().try_enter(text, clock.now_utc())
+    };
+
     // Event handlers
-    let add_todo = Box::new(move |text: String| {
-        let list = &mut todo_list.write();
-        list.add(text);
+    let add_todo = Box::new({
+        let apply_due_rules = apply_due_rules.clone();
+        let mut is_repeated_add = is_repeated_add.clone();
+        move |text: String| {
+            if is_repeated_add(&text) {
+                return;
+            }
+            let before = todo_list.peek().clone();
+            let list = &mut todo_list.write();
+            let id = list.add(prepare_text(text));
+            list.apply_auto_tags(id, &auto_tagger());
+            apply_due_rules(list, id);
+            record(before, list);
+        }
     });
 
-    let toggle_todo = Box::new(move |id: usize| {
+    let add_shared_todo = Box::new({
+        let apply_due_rules = apply_due_rules.clone();
+        move |shared: SharedTodo| {
+            let before = todo_list.peek().clone();
+            let list = &mut todo_list.write();
+            let id = list.add(prepare_text(shared.text));
+            list.apply_auto_tags(id, &auto_tagger());
+            if shared.completed {
+                list.toggle(id);
+            }
+            report(list.set_due_date(id, shared.due_date));
+            for tag in shared.tags {
+                list.add_tag(id, tag);
+            }
+            apply_due_rules(list, id);
+            record(before, list);
+        }
+    });
+
+    // Adds a todo whose link was split off the typed text
+    let add_linked_todo = Box::new({
+        let apply_due_rules = apply_due_rules.clone();
+        move |(text, url): (String, String)| {
+            if is_repeated_add(&format!("{text} {url}")) {
+                return;
+            }
+            let before = todo_list.peek().clone();
+            let list = &mut todo_list.write();
+            let id = list.add(prepare_text(text));
+            list.apply_auto_tags(id, &auto_tagger());
+            apply_due_rules(list, id);
+            list.set_url(id, Some(url));
+            record(before, list);
+        }
+    });
+
+    let toggle_todo = Box::new({
+        let clock = clock.clone();
+        move |id: usize| {
+            let completed = {
+                let before = todo_list.peek().clone();
+                let list = &mut todo_list.write();
+                let toggled = list.toggle(id);
+                report(found(toggled, id));
+                record(before, list);
+                list.get(id).is_some_and(|todo| todo.completed)
+            };
+            // Only completing plays the tick, at most once per debounce interval,
+            // and not while quiet
+            if completed
+                && settings.peek().completion_sound
+                && quiet_mode.peek().notifications_allowed(clock.now_utc())
+                && sound_debouncer.write().try_play(clock.now_utc())
+            {
+                utils::sound::play_completion_sound();
+            }
+        }
+    });
+
+    // Completes or reopens the given todos together, or every todo for `None`
+    let toggle_all = Box::new(move |ids: Option<Vec<usize>>| {
+        let before = todo_list.peek().clone();
         let list = &mut todo_list.write();
-        list.toggle(id);
+        let changed = list.toggle_all(ids.as_deref());
+        record(before, list);
+        changed
     });
 
     let delete_todo = Box::new(move |id: usize| {
+        let before = todo_list.peek().clone();
         let list = &mut todo_list.write();
-        list.remove(id);
+        list.move_to_trash(id)?;
+        record(before, list);
+        Ok(())
     });
 
-    let update_todo = Box::new(move |(id, text): (usize, String)| {
+    let restore_from_trash = Box::new(move |id: usize| {
+        let before = todo_list.peek().clone();
         let list = &mut todo_list.write();
-        list.update_text(id, text);
+        let restored = list.restore(id);
+        report(found(restored, id));
+        record(before, list);
+    });
+
+    let restore_many_from_trash = Box::new(move |ids: Vec<usize>| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        list.restore_many(&ids);
+        record(before, list);
+        if let Some(missing) = ids.iter().find(|id| list.get(**id).is_none()) {
+            report(Err(TodoError::NotFound(*missing)));
+        }
+    });
+
+    let purge_from_trash = Box::new(move |id: usize| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let purged = list.purge(id);
+        report(found(purged, id));
+        record(before, list);
+    });
+
+    let empty_trash = Box::new(move |_| {
+        if todo_list.peek().trash_count() == 0 {
+            return 0;
+        }
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let emptied = list.empty_trash();
+        record(before, list);
+        emptied
+    });
+
+    let update_todo = Box::new({
+        let apply_due_rules = apply_due_rules.clone();
+        move |(id, text): (usize, String)| {
+            let before = todo_list.peek().clone();
+            let list = &mut todo_list.write();
+            list.update_text(id, prepare_text(text))?;
+            if list.apply_auto_tags(id, &auto_tagger()) {
+                apply_due_rules(list, id);
+            }
+            record(before, list);
+            Ok(())
+        }
+    });
+
+    let restore_text = Box::new({
+        let apply_due_rules = apply_due_rules.clone();
+        move |(id, index): (usize, usize)| {
+            let before = todo_list.peek().clone();
+            let list = &mut todo_list.write();
+            list.restore_text(id, index)?;
+            if list.apply_auto_tags(id, &auto_tagger()) {
+                apply_due_rules(list, id);
+            }
+            record(before, list);
+            Ok(())
+        }
+    });
+
+    let set_locked = Box::new(move |(id, locked): (usize, bool)| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let changed = list.set_locked(id, locked);
+        report(found(changed, id));
+        record(before, list);
+    });
+
+    let set_notes = Box::new(move |(id, notes): (usize, String)| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let changed = list.set_notes(id, notes);
+        report(found(changed, id));
+        record(before, list);
+    });
+
+    let set_url = Box::new(move |(id, url): (usize, Option<String>)| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let changed = list.set_url(id, url);
+        report(found(changed, id));
+        record(before, list);
+    });
+
+    let add_subtask = Box::new(move |(id, text): (usize, String)| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let added = list.add_subtask(id, prepare_text(text));
+        report(found(added.is_some(), id));
+        record(before, list);
+    });
+
+    // A subtask already gone from a todo that still exists is not an error
+    let toggle_subtask = Box::new(move |(id, subtask_id): (usize, usize)| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        if !list.toggle_subtask(id, subtask_id) {
+            report(found(list.get(id).is_some(), id));
+        }
+        record(before, list);
+    });
+
+    let remove_subtask = Box::new(move |(id, subtask_id): (usize, usize)| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        if !list.remove_subtask(id, subtask_id) {
+            report(found(list.get(id).is_some(), id));
+        }
+        record(before, list);
+    });
+
+    let extract_from_notes = Box::new({
+        let apply_due_rules = apply_due_rules.clone();
+        move |(id, line, strike): (usize, usize, bool)| {
+            let before = todo_list.peek().clone();
+            let list = &mut todo_list.write();
+            match list.extract_from_notes(id, line, strike) {
+                Some(new_id) => {
+                    list.apply_auto_tags(new_id, &auto_tagger());
+                    apply_due_rules(list, new_id);
+                }
+                None if list.get(id).is_none() => report(Err(TodoError::NotFound(id))),
+                None => report(Err(TodoError::NoteLineUnavailable(id))),
+            }
+            record(before, list);
+        }
     });
 
     let set_due_date = Box::new(move |(id, date): (usize, Option<DateTime<Utc>>)| {
+        let before = todo_list.peek().clone();
         let list = &mut todo_list.write();
-        list.set_due_date(id, date);
+        report(list.set_due_date(id, date));
+        record(before, list);
     });
 
-    let add_tag_to_todo = Box::new(move |(id, tag): (usize, String)| {
+    let shift_due_dates = Box::new(move |(ids, days): (Vec<usize>, i64)| {
+        let before = todo_list.peek().clone();
         let list = &mut todo_list.write();
-        list.add_tag(id, tag);
+        let moved = list.shift_due_dates(&ids, days);
+        record(before, list);
+        moved
+    });
+
+    let add_tag_to_todo = Box::new({
+        let apply_due_rules = apply_due_rules.clone();
+        move |(id, tag): (usize, String)| {
+            let before = todo_list.peek().clone();
+            let list = &mut todo_list.write();
+            let changed = list.add_tag(id, tag);
+            report(found(changed, id));
+            apply_due_rules(list, id);
+            record(before, list);
+        }
     });
 
     let remove_tag_from_todo = Box::new(move |(id, tag): (usize, String)| {
+        let before = todo_list.peek().clone();
         let list = &mut todo_list.write();
-        list.remove_tag(id, &tag);
+        let changed = list.remove_tag(id, &tag);
+        report(found(changed, id));
+        record(before, list);
     });
 
-    let clear_completed = Box::new(move |_| {
+    // Tags the given todos together, as one change
+    let add_tag_many = Box::new(move |(ids, tag): (Vec<usize>, String)| {
+        let before = todo_list.peek().clone();
         let list = &mut todo_list.write();
-        list.clear_completed();
+        let added = list.add_tag_many(&ids, &tag);
+        for id in ids {
+            apply_due_rules(list, id);
+        }
+        record(before, list);
+        added
+    });
+
+    let remove_tag_many = Box::new(move |(ids, tag): (Vec<usize>, String)| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let removed = list.remove_tag_many(&ids, &tag);
+        record(before, list);
+        removed
+    });
+
+    let remove_tag_everywhere = Box::new(move |tag: String| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let removed = list.remove_tag_everywhere(&tag);
+        record(before, list);
+        removed
+    });
+
+    let merge_duplicates = Box::new(move |merges: Vec<Merge>| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let removed = merges.iter().map(|merge| merge.apply(list)).sum();
+        record(before, list);
+        removed
+    });
+
+    let clear_completed = Box::new(move |visible_ids: Option<Vec<usize>>| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let cleared = match visible_ids {
+            Some(ids) => list.clear_completed_filtered(&ids),
+            None => list.clear_completed(),
+        };
+        record(before, list);
+        cleared
+    });
+
+    let restore_cleared = Box::new(move |todos: Vec<Todo>| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        list.restore_cleared(todos);
+        record(before, list);
+    });
+
+    // Archives the completed todos among the visible ones
+    let archive_completed = Box::new(move |visible_ids: Vec<usize>| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let archived = list.archive_completed(&visible_ids);
+        record(before, list);
+        archived
+    });
+
+    let unarchive_todos = Box::new(move |ids: Vec<usize>| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        list.unarchive_many(&ids);
+        record(before, list);
     });
 
     let reorder_todo = Box::new(move |(source_id, target_id): (usize, usize)| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let previous = list.order_snapshot();
+        if list.reorder(source_id, target_id) {
+            record(before, list);
+            return Some(previous);
+        }
+        // Dropping a todo on itself is not a failure
+        if let Some(missing) = [source_id, target_id]
+            .into_iter()
+            .find(|id| list.get(*id).is_none())
+        {
+            report(Err(TodoError::NotFound(missing)));
+        }
+        None
+    });
+
+    // Moves a todo to a position picked without dragging
+    let reorder_to_index = Box::new(move |(id, index): (usize, usize)| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let previous = list.order_snapshot();
+        if list.reorder_to_index(id, index) {
+            record(before, list);
+            return Some(previous);
+        }
+        report(found(list.get(id).is_some(), id));
+        None
+    });
+
+    let restore_order = Box::new(move |snapshot: Vec<(usize, usize)>| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        list.set_order_snapshot(snapshot);
+        record(before, list);
+    });
+
+    // A restored or repaired list replaces whatever could not be loaded
+    let restore_todos = Box::new(move |list: TodoList| {
+        save_tracker.write().unblock_persistence();
+        load_failure.set(None);
+        todo_list.set(list);
+        loaded.set(true);
+    });
+
+    let import_todos = Box::new(move |todos: Vec<Todo>| {
+        let list = &mut todo_list.write();
+        list.add_copies(&todos);
+    });
+
+    // Adds and merges as one change; a list that could not be loaded is
+    // started afresh from the import, as a restore would
+    let apply_import = Box::new(move |plan: ImportPlan| {
+        if load_failure.peek().is_some() {
+            save_tracker.write().unblock_persistence();
+            load_failure.set(None);
+            loaded.set(true);
+        }
         let list = &mut todo_list.write();
-        list.reorder(source_id, target_id);
+        let known: HashSet<usize> = list
+            .all_including_archived()
+            .iter()
+            .map(|todo| todo.id)
+            .collect();
+        let counts = plan.apply(list);
+        let tagger = auto_tagger();
+        let new_ids: Vec<usize> = list
+            .all_including_archived()
+            .iter()
+            .map(|todo| todo.id)
+            .filter(|id| !known.contains(id))
+            .collect();
+        for id in new_ids {
+            list.apply_auto_tags(id, &tagger);
+        }
+        counts
+    });
+
+    let add_sample_todos = Box::new(move |_| {
+        todo_list.set(utils::onboarding::sample_todo_list());
+        loaded.set(true);
+    });
+
+    let save_now = Box::new(move |_| {
+        if loaded() && save_tracker.peek().is_dirty() {
+            flush();
+        }
+    });
+
+    let retry_load = Box::new(move |_| load());
+
+    let apply_auto_tags = Box::new(move |_| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let tagged = list.apply_auto_tags_to_all(&auto_tagger());
+        record(before, list);
+        tagged
+    });
+
+    // Moves completed todos past the policy's age to the trash in a single
+    // change, so they are saved in one write
+    let auto_archive = Box::new(move |now: DateTime<Utc>| {
+        let Some(days) = settings.peek().auto_archive_after_days else {
+            return Vec::new();
+        };
+        let ids = select_auto_archivable(&todo_list.peek(), now, days);
+        if !ids.is_empty() {
+            let before = todo_list.peek().clone();
+            let list = &mut todo_list.write();
+            list.move_many_to_trash(&ids);
+            record(before, list);
+        }
+        ids
+    });
+
+    // Applies the escalation rules in a single change, so they are saved in
+    // one write, and notes each change in the log
+    let escalate = Box::new({
+        let clock = clock.clone();
+        move |now: DateTime<Utc>| {
+            let escalations = plan_escalations(
+                &todo_list.peek(),
+                &settings.peek(),
+                now,
+                clock.today_local(),
+            );
+            if escalations.is_empty() {
+                return 0;
+            }
+            let before = todo_list.peek().clone();
+            let list = &mut todo_list.write();
+            let escalated = escalations
+                .into_iter()
+                .filter(|escalation| escalation.apply(list))
+                .inspect(|escalation| tracing::info!("Escalated: {}", escalation))
+                .count();
+            record(before, list);
+            escalated
+        }
     });
 
-    let _select_tag = move |tag: Option<String>| {
-        selected_tag.set(tag);
+    // Deletes the todos in the trash and the archive past their retention in
+    // a single change, so they are saved in one write
+    let mut enforce_retention = move |now: DateTime<Utc>| {
+        let policy = RetentionPolicy::from_settings(&settings.peek());
+        let purgeable = select_purgeable(&todo_list.peek(), policy, now);
+        if purgeable.is_empty() {
+            return 0;
+        }
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let deleted = purgeable.apply(list);
+        record(before, list);
+        tracing::info!("Deleted {} todos past their retention", deleted);
+        deleted
     };
 
-    // Combine default and user tags, ensuring uniqueness and sorting
-    let all_current_tags = todo_list.read().all_tags();
-    let mut combined_tags = default_tags
-        .iter()
-        .map(|&s| s.to_string())
-        .collect::<HashSet<_>>();
+    // Deletes what the retention policy allows, saves at once so the storage
+    // no longer holds the deleted todos, then lets the backend give the space
+    // back
+    let reclaim_space = Box::new(move |now: DateTime<Utc>| {
+        let deleted = enforce_retention(now);
+        if deleted > 0 && !save_tracker.peek().is_persistence_blocked() {
+            let snapshot = todo_list.peek().clone();
+            save_tracker.write().begin_save();
+            let result = utils::save(keys::TODOS, &snapshot);
+            save_tracker.write().finish_save(&result);
+            result?;
+        }
+        let report = utils::storage::maintenance()?;
+        Ok((deleted, report))
+    });
+    let enforce_retention = Box::new(enforce_retention);
+
+    // Marks a waiting todo as followed up, noting the day if the settings ask for it
+    let bump_todo = Box::new({
+        let clock = clock.clone();
+        move |id: usize| {
+            let note_date = settings.peek().follow_up_note.then(|| clock.today_local());
+            let before = todo_list.peek().clone();
+            let list = &mut todo_list.write();
+            let bumped = list.bump(id, note_date);
+            report(found(bumped, id));
+            record(before, list);
+        }
+    });
 
-    combined_tags.extend(all_current_tags);
-    let mut sorted_tags = combined_tags.into_iter().collect::<Vec<_>>();
-    sorted_tags.sort();
+    // Puts a todo's reminder off for a while; snoozes are kept with the todos,
+    // so they last across restarts
+    let snooze_reminder = Box::new(move |id: usize| {
+        let until = reminder::snooze_end(clock.now_utc());
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        let snoozed = list.snooze(id, until);
+        report(found(snoozed, id));
+        record(before, list);
+    });
+
+    let cancel_snooze = Box::new(move |id: usize| {
+        let before = todo_list.peek().clone();
+        let list = &mut todo_list.write();
+        list.cancel_snooze(id);
+        record(before, list);
+    });
+
+    // Steps back over the last recorded change. Changes made elsewhere since,
+    // such as by a sync, clear the history instead of being undone with it.
+    let undo = Box::new(move |_| {
+        if !history.peek().can_undo() {
+            return false;
+        }
+        let mut list = todo_list.peek().clone();
+        let undone = history.write().undo(&mut list);
+        if undone {
+            todo_list.set(list);
+        }
+        undone
+    });
+
+    let redo = Box::new(move |_| {
+        if !history.peek().can_redo() {
+            return false;
+        }
+        let mut list = todo_list.peek().clone();
+        let redone = history.write().redo(&mut list);
+        if redone {
+            todo_list.set(list);
+        }
+        redone
+    });
 
     let operations = TodoOperations {
         add_todo,
+        add_shared_todo,
+        add_linked_todo,
         toggle_todo,
+        toggle_all,
         delete_todo,
+        restore_from_trash,
+        restore_many_from_trash,
+        purge_from_trash,
+        empty_trash,
         update_todo,
+        restore_text,
+        set_locked,
+        set_notes,
+        set_url,
+        add_subtask,
+        toggle_subtask,
+        remove_subtask,
+        extract_from_notes,
         set_due_date,
+        shift_due_dates,
         add_tag_to_todo,
         remove_tag_from_todo,
+        add_tag_many,
+        remove_tag_many,
+        remove_tag_everywhere,
+        merge_duplicates,
         clear_completed,
+        restore_cleared,
+        archive_completed,
+        unarchive_todos,
         reorder_todo,
+        reorder_to_index,
+        restore_order,
+        restore_todos,
+        import_todos,
+        apply_import,
+        add_sample_todos,
+        save_now,
+        retry_load,
+        apply_auto_tags,
+        auto_archive,
+        escalate,
+        enforce_retention,
+        reclaim_space,
+        bump_todo,
+        snooze_reminder,
+        cancel_snooze,
+        undo,
+        redo,
     };
 
-    (todo_list, filter, selected_tag, operations, sorted_tags)
+    (
+        todo_list,
+        criteria,
+        sort_mode,
+        operations,
+        save_tracker,
+        loaded,
+        load_failure,
+        operation_error,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use crate::models::duplicates::find_duplicates;
+
+    /// Id of a todo that was never in the list
+    const MISSING: usize = 999;
+
+    /// Runs the todo state on its own, with buttons calling the operations.
+    ///
+    /// Storage is only touched by spawned tasks, which are not run here, so
+    /// the list lives in memory.
+    #[component]
+    fn Harness() -> Element {
+        use_context_provider(|| Signal::new(AppSettings::default()));
+        use_context_provider(|| Signal::new(QuietMode::default()));
+        let (todo_list, _, _, operations, _, _, _, operation_error) = use_todo_state();
+        let TodoOperations {
+            mut add_todo,
+            mut toggle_todo,
+            mut delete_todo,
+            mut add_tag_to_todo,
+            mut add_tag_many,
+            mut merge_duplicates,
+            mut reorder_todo,
+            mut set_notes,
+            restore_todos,
+            mut undo,
+            mut redo,
+            ..
+        } = operations;
+        let restore_todos = use_callback(restore_todos);
+        let first = todo_list
+            .read()
+            .all()
+            .first()
+            .map_or(MISSING, |todo| todo.id);
+        let all_ids: Vec<usize> = todo_list.read().all().iter().map(|todo| todo.id).collect();
+        let tagged = todo_list
+            .read()
+            .all()
+            .iter()
+            .filter(|todo| todo.tags.iter().any(|tag| tag == "home"))
+            .count();
+        let noted = todo_list
+            .read()
+            .all()
+            .iter()
+            .filter(|todo| !todo.notes.is_empty())
+            .count();
+
+        rsx! {
+            button {
+                onclick: move |_| {
+                    let mut list = TodoList::new();
+                    list.add("Call the plumber".to_string());
+                    list.add("Renew the passport".to_string());
+                    restore_todos(list);
+                },
+                "Load"
+            }
+            button {
+                onclick: move |_| {
+                    for _ in 0..5 {
+                        add_todo("Buy milk".to_string());
+                    }
+                    add_todo("Buy bread".to_string());
+                },
+                "Add five times"
+            }
+            button { onclick: move |_| toggle_todo(MISSING), "Toggle missing" }
+            button {
+                onclick: move |_| {
+                    let _ = delete_todo(first);
+                    add_tag_to_todo((first, "home".to_string()));
+                },
+                "Tag after deleting"
+            }
+            button { onclick: move |_| {
+                    add_tag_many((all_ids.clone(), "home".to_string()));
+                }, "Tag all" }
+            button {
+                onclick: move |_| {
+                    let mut list = TodoList::new();
+                    for text in ["Renew domain", "renew domain", "Renew  Domain"] {
+                        list.add(text.to_string());
+                    }
+                    restore_todos(list);
+                },
+                "Load duplicates"
+            }
+            button { onclick: move |_| {
+                    let merges = find_duplicates(&todo_list.read()).iter().map(|group| group.merge()).collect();
+                    merge_duplicates(merges);
+                }, "Merge duplicates" }
+            button { onclick: move |_| {
+                    reorder_todo((first, first));
+                }, "Drop on itself" }
+            button { onclick: move |_| {
+                    set_notes((first, "Ask about the kitchen tap".to_string()));
+                }, "Edit notes" }
+            button { onclick: move |_| {
+                    undo(());
+                }, "Undo" }
+            button { onclick: move |_| {
+                    redo(());
+                }, "Redo" }
+            span { "Todos: {todo_list.read().all().len()}" }
+            span { "Tagged: {tagged}" }
+            span { "Noted: {noted}" }
+            if let Some(error) = operation_error() {
+                p { "{error}" }
+            }
+        }
+    }
+
+    fn render() -> TestDom {
+        let mut dom = TestDom::with_props(|()| rsx! { Harness {} }, ());
+        dom.click(dom.find("button", "Load").unwrap());
+        assert!(dom.find("span", "Todos: 2").is_some());
+        dom
+    }
+
+    #[test]
+    fn test_operating_on_a_missing_todo_reports_an_error() {
+        let mut dom = render();
+        assert!(dom.first("p").is_none());
+
+        dom.click(dom.find("button", "Toggle missing").unwrap());
+        assert!(dom.find("p", "Todo 999 no longer exists").is_some());
+    }
+
+    #[test]
+    fn test_stale_id_after_deleting_reports_an_error() {
+        let mut dom = render();
+        dom.click(dom.find("button", "Tag after deleting").unwrap());
+
+        assert!(dom.find("span", "Todos: 1").is_some());
+        let error = dom.text_of(dom.first("p").unwrap());
+        assert!(error.ends_with("no longer exists"), "{}", error);
+    }
+
+    #[test]
+    fn test_undo_and_redo_a_deletion() {
+        let mut dom = render();
+        dom.click(dom.find("button", "Tag after deleting").unwrap());
+        assert!(dom.find("span", "Todos: 1").is_some());
+
+        dom.click(dom.find("button", "Undo").unwrap());
+        assert!(dom.find("span", "Todos: 2").is_some());
+        dom.click(dom.find("button", "Redo").unwrap());
+        assert!(dom.find("span", "Todos: 1").is_some());
+    }
+
+    #[test]
+    fn test_dropping_a_todo_on_itself_is_not_an_error() {
+        let mut dom = render();
+        dom.click(dom.find("button", "Drop on itself").unwrap());
+        assert!(dom.first("p").is_none());
+    }
+
+    #[test]
+    fn test_tagging_many_is_undone_as_one_change() {
+        let mut dom = render();
+        dom.click(dom.find("button", "Tag all").unwrap());
+        assert!(dom.find("span", "Tagged: 2").is_some());
+
+        dom.click(dom.find("button", "Undo").unwrap());
+        assert!(dom.find("span", "Tagged: 0").is_some());
+        assert!(dom.find("span", "Todos: 2").is_some());
+        dom.click(dom.find("button", "Redo").unwrap());
+        assert!(dom.find("span", "Tagged: 2").is_some());
+    }
+
+    #[test]
+    fn test_editing_notes_keeps_earlier_changes_undoable() {
+        let mut dom = render();
+        dom.click(dom.find("button", "Tag all").unwrap());
+        dom.click(dom.find("button", "Edit notes").unwrap());
+        assert!(dom.find("span", "Noted: 1").is_some());
+
+        dom.click(dom.find("button", "Undo").unwrap());
+        assert!(dom.find("span", "Noted: 0").is_some());
+        assert!(dom.find("span", "Tagged: 2").is_some());
+        dom.click(dom.find("button", "Undo").unwrap());
+        assert!(dom.find("span", "Tagged: 0").is_some());
+    }
+
+    #[test]
+    fn test_merging_duplicates_is_undone_as_one_change() {
+        let mut dom = render();
+        dom.click(dom.find("button", "Load duplicates").unwrap());
+        dom.click(dom.find("button", "Merge duplicates").unwrap());
+        assert!(dom.find("span", "Todos: 1").is_some());
+
+        dom.click(dom.find("button", "Undo").unwrap());
+        assert!(dom.find("span", "Todos: 3").is_some());
+    }
+
+    #[test]
+    fn test_rapid_identical_adds_add_one_todo() {
+        let mut dom = render();
+        dom.click(dom.find("button", "Add five times").unwrap());
+        assert!(dom.find("span", "Todos: 4").is_some());
+
+        // The adds were one change each, the repeats none
+        dom.click(dom.find("button", "Undo").unwrap());
+        dom.click(dom.find("button", "Undo").unwrap());
+        assert!(dom.find("span", "Todos: 2").is_some());
+    }
 }
@@ -0,0 +1,102 @@
+use crate::components::{Modal, ModalSize};
+use crate::models::AppSettings;
+use crate::utils::constants::app::APP_VERSION;
+use crate::utils::theme;
+use crate::utils::version;
+use dioxus::prelude::*;
+
+/// Release notes of one version: the version and its bullet points
+pub type ReleaseNotes = (&'static str, &'static [&'static str]);
+
+/// Logic for showing what changed since the last visit
+///
+/// Returns the release notes to show, empty while the dialog is closed. They
+/// start out filled only after an upgrade. The running version is then
+/// stored in the settings so the notes are shown once.
+pub fn use_whats_new(mut settings: Signal<AppSettings>) -> Signal<Vec<ReleaseNotes>> {
+    let whats_new = use_signal(|| {
+        let last_seen = settings.peek().last_seen_version.clone();
+        if version::should_show_whats_new(last_seen.as_deref(), APP_VERSION) {
+            version::notes_since(last_seen.as_deref(), APP_VERSION)
+        } else {
+            Vec::new()
+        }
+    });
+
+    use_effect(move || {
+        if settings.peek().last_seen_version.as_deref() != Some(APP_VERSION) {
+            settings.write().last_seen_version = Some(APP_VERSION.to_string());
+        }
+    });
+
+    whats_new
+}
+
+/// Component showing release notes in a dismissible dialog
+#[component]
+pub fn WhatsNewDialog(
+    notes: Vec<ReleaseNotes>,
+    on_close: EventHandler<()>,
+    is_dark_mode: bool,
+) -> Element {
+    let version_class = theme::muted_text_class(is_dark_mode);
+
+    rsx! {
+        Modal {
+            title: "What's new ✨",
+            on_close,
+            size: ModalSize::Large,
+            is_dark_mode,
+            for (version , items) in notes {
+                section { key: "{version}", class: "mb-3",
+                    h3 { class: "text-sm font-medium mb-1 {version_class}", "Version {version}" }
+                    ul { class: "list-disc pl-5 space-y-1 text-sm",
+                        for item in items.iter() {
+                            li { "{item}" }
+                        }
+                    }
+                }
+            }
+            div { class: "flex justify-end",
+                button {
+                    r#type: "button",
+                    class: "px-3 py-1 rounded bg-blue-500 text-white hover:bg-blue-600 text-sm",
+                    onclick: move |_| on_close.call(()),
+                    "Got it"
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::{Mutation, Mutations};
+
+    #[test]
+    fn test_whats_new_dialog_rendering() {
+        let mut app = VirtualDom::new(|| {
+            rsx! {
+                WhatsNewDialog {
+                    notes: vec![("1.1.0", &["Faster search"][..]), ("1.0.0", &["First release"][..])],
+                    on_close: |_| {},
+                    is_dark_mode: true,
+                }
+            }
+        });
+
+        let mut mutations = Mutations::default();
+        app.rebuild(&mut mutations);
+        let texts: Vec<String> = mutations
+            .edits
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Mutation::CreateTextNode { value, .. } => Some(value),
+                _ => None,
+            })
+            .collect();
+        assert!(texts.contains(&"Version 1.1.0".to_string()));
+        assert!(texts.contains(&"First release".to_string()));
+    }
+}
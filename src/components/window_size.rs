@@ -0,0 +1,47 @@
+use dioxus::prelude::*;
+
+/// Size of the app window's viewport in CSS pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WindowSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Reports the viewport size now and after every resize, at most once per frame.
+const WATCH_SIZE: &str = r#"
+    let frame = null;
+    const report = () => {
+        frame = null;
+        dioxus.send([window.innerWidth, window.innerHeight]);
+    };
+    window.addEventListener("resize", () => {
+        if (frame === null) {
+            frame = requestAnimationFrame(report);
+        }
+    });
+    report();
+    await new Promise(() => {});
+"#;
+
+/// Logic tracking the size of the app window
+///
+/// Works the same on web and desktop by listening to `resize` in the
+/// webview. The size is `None` until the webview first reports it, and
+/// stays `None` where there is no webview, such as in tests.
+pub fn use_window_size() -> Signal<Option<WindowSize>> {
+    let mut size = use_signal(|| None::<WindowSize>);
+
+    use_hook(move || {
+        spawn(async move {
+            let mut eval = document::eval(WATCH_SIZE);
+            while let Ok((width, height)) = eval.recv::<(f64, f64)>().await {
+                let reported = Some(WindowSize { width, height });
+                if *size.peek() != reported {
+                    size.set(reported);
+                }
+            }
+        });
+    });
+
+    size
+}
@@ -0,0 +1,219 @@
+use crate::utils::storage::StorageStats;
+use crate::utils::theme;
+use chrono::{DateTime, Local, Utc};
+use dioxus::prelude::*;
+
+/// Props for the YourData component.
+#[derive(Props, PartialEq, Clone)]
+pub struct YourDataProps {
+    /// The figures to show
+    pub stats: StorageStats,
+    /// When a save last succeeded, `None` if none did since launch
+    #[props(default)]
+    pub last_saved: Option<DateTime<Utc>>,
+    /// Whether the data folder can be opened, only on desktop
+    #[props(default = false)]
+    pub can_open_folder: bool,
+    /// Callback opening the data folder
+    #[props(default)]
+    pub on_open_folder: EventHandler<()>,
+    /// Callback deleting the todos past their retention and compacting the
+    /// database
+    pub on_reclaim: EventHandler<()>,
+    /// Callback deleting every stored value, after two confirmations
+    pub on_delete_all: EventHandler<()>,
+    /// Whether dark mode is enabled
+    #[props(default = false)]
+    pub is_dark_mode: bool,
+}
+
+/// Formats a size in bytes, like "512 B" or "1.5 KB".
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Figures about the stored data, with maintenance actions
+#[component]
+pub fn YourData(props: YourDataProps) -> Element {
+    // 0 until "Delete all data" is clicked, then one step per confirmation
+    let mut delete_step = use_signal(|| 0u8);
+
+    let stats = &props.stats;
+    let last_saved = props.last_saved.map_or_else(
+        || "Not since the app started".to_string(),
+        |at| {
+            at.with_timezone(&Local)
+                .format("%b %d, %Y %H:%M")
+                .to_string()
+        },
+    );
+    let figures = [
+        ("Active todos", stats.active.to_string()),
+        ("Completed todos", stats.completed.to_string()),
+        ("Archived todos", stats.archived.to_string()),
+        ("In the trash", stats.trashed.to_string()),
+        ("Tags", stats.tags.to_string()),
+        ("Todo document", format_bytes(stats.document_bytes)),
+        (
+            "Everything stored",
+            format!(
+                "{} in {} values",
+                format_bytes(stats.total_bytes),
+                stats.keys
+            ),
+        ),
+        ("Last saved", last_saved),
+        ("Stored in", stats.location.clone()),
+    ];
+
+    let text_class = theme::secondary_text_class(props.is_dark_mode);
+    let button_class = theme::secondary_button_class(props.is_dark_mode);
+    let danger_button_class = theme::danger_button_class(props.is_dark_mode);
+
+    rsx! {
+      div { class: "mb-4 flex flex-col space-y-2 text-sm {text_class}",
+        dl { class: "grid grid-cols-2 gap-x-4 gap-y-1",
+          for (label, value) in figures {
+            dt { key: "{label}", "{label}" }
+            dd { class: "truncate", title: "{value}", "{value}" }
+          }
+        }
+        div { class: "flex flex-wrap items-center gap-2",
+          if props.can_open_folder {
+            button {
+              r#type: "button",
+              class: button_class,
+              onclick: move |_| props.on_open_folder.call(()),
+              "Open data folder"
+            }
+          }
+          button {
+            r#type: "button",
+            class: button_class,
+            title: "Delete todos past their retention and compact the database",
+            onclick: move |_| props.on_reclaim.call(()),
+            "Reclaim space"
+          }
+          if delete_step() == 0 {
+            button {
+              r#type: "button",
+              class: danger_button_class,
+              onclick: move |_| delete_step.set(1),
+              "Delete all data"
+            }
+          }
+        }
+        if delete_step() > 0 {
+          div { class: "flex flex-wrap items-center gap-2", role: "alert",
+            if delete_step() == 1 {
+              span { "This deletes every todo, the trash and all settings of this profile." }
+              button {
+                r#type: "button",
+                class: danger_button_class,
+                onclick: move |_| delete_step.set(2),
+                "Continue"
+              }
+            } else {
+              span { "There is no undo. Export a backup first if you may want the todos back." }
+              button {
+                r#type: "button",
+                class: danger_button_class,
+                onclick: move |_| {
+                    delete_step.set(0);
+                    props.on_delete_all.call(());
+                },
+                "Delete everything"
+              }
+            }
+            button {
+              r#type: "button",
+              class: button_class,
+              onclick: move |_| delete_step.set(0),
+              "Cancel"
+            }
+          }
+        }
+      }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::test_dom::TestDom;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    fn render(deleted: Rc<Cell<usize>>) -> TestDom {
+        TestDom::with_props(
+            |deleted: Rc<Cell<usize>>| {
+                rsx! {
+                    YourData {
+                        stats: StorageStats {
+                            active: 3,
+                            completed: 2,
+                            archived: 6,
+                            trashed: 1,
+                            tags: 4,
+                            document_bytes: 1536,
+                            total_bytes: 2048,
+                            keys: 5,
+                            location: "In memory, nothing is saved".to_string(),
+                        },
+                        on_reclaim: move |_| {},
+                        on_delete_all: move |_| deleted.set(deleted.get() + 1),
+                    }
+                }
+            },
+            deleted,
+        )
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MB");
+    }
+
+    #[test]
+    fn test_shows_the_figures() {
+        let dom = render(Rc::default());
+        let text = dom.text();
+        assert!(text.contains("Active todos3"));
+        assert!(text.contains("Archived todos6"));
+        assert!(text.contains("Todo document1.5 KB"));
+        assert!(text.contains("2.0 KB in 5 values"));
+        assert!(text.contains("Not since the app started"));
+        assert!(dom.find("button", "Open data folder").is_none());
+    }
+
+    #[test]
+    fn test_delete_all_asks_twice() {
+        let deleted = Rc::new(Cell::new(0));
+        let mut dom = render(deleted.clone());
+
+        dom.click(dom.find("button", "Delete all data").unwrap());
+        dom.click(dom.find("button", "Continue").unwrap());
+        dom.click(dom.find("button", "Cancel").unwrap());
+        assert_eq!(deleted.get(), 0);
+
+        dom.click(dom.find("button", "Delete all data").unwrap());
+        dom.click(dom.find("button", "Continue").unwrap());
+        assert_eq!(deleted.get(), 0);
+        dom.click(dom.find("button", "Delete everything").unwrap());
+        assert_eq!(deleted.get(), 1);
+        assert!(dom.find("button", "Delete all data").is_some());
+    }
+}
@@ -0,0 +1,118 @@
+//! Headless todo engine
+//!
+//! Everything needed to work with todos from a plain Rust program, without
+//! Dioxus: the [`TodoList`] model, search queries, import and export, and a
+//! [`Store`] that keeps a list in sync with a storage backend. Build with
+//! `default-features = false` (plus `sqlite` for the SQLite backend) to leave
+//! the UI out entirely.
+//!
+//! # Example
+//! ```
+//! use todo::engine::{MemoryStorage, Store};
+//!
+//! let storage = MemoryStorage::new();
+//! let mut store = Store::load(Box::new(storage.clone())).unwrap();
+//! store.mutate(|list| list.add("Buy milk".to_string()));
+//! store.flush().unwrap();
+//!
+//! let reopened = Store::load(Box::new(storage)).unwrap();
+//! assert_eq!(reopened.list().all()[0].text, "Buy milk");
+//! ```
+
+pub use crate::models::query::parse_duration;
+pub use crate::models::{FilterState, SearchQuery, Todo, TodoList};
+pub use crate::utils::autosave::{SaveStatus, SaveTracker};
+pub use crate::utils::export::{
+    ExportDocument, ExportError, export_encrypted, export_json, import_encrypted, import_json,
+};
+pub use crate::utils::keys;
+pub use crate::utils::storage::{
+    MemoryStorage, RawStorage, StorageBackend, StorageError, open_storage,
+};
+
+use crate::utils::autosave;
+use crate::utils::storage::load_with;
+
+/// A todo list backed by a storage backend.
+///
+/// Changes made through [`Store::mutate`] stay in memory until
+/// [`Store::flush`] writes them, so a batch of changes is saved once.
+pub struct Store {
+    list: TodoList,
+    storage: Box<dyn RawStorage>,
+    key: String,
+    tracker: SaveTracker,
+}
+
+impl Store {
+    /// Loads the todo list kept under the app's key, or starts an empty one.
+    ///
+    /// # Arguments
+    /// * `storage` - The backend to read from and write to
+    ///
+    /// # Returns
+    /// The store, or the error if stored data exists but cannot be read
+    pub fn load(storage: Box<dyn RawStorage>) -> Result<Self, StorageError> {
+        Self::load_from(storage, keys::TODOS)
+    }
+
+    /// Loads the todo list kept under the given key, or starts an empty one.
+    ///
+    /// # Arguments
+    /// * `storage` - The backend to read from and write to
+    /// * `key` - The key the list is stored under
+    pub fn load_from(storage: Box<dyn RawStorage>, key: &str) -> Result<Self, StorageError> {
+        let list = match load_with(storage.as_ref(), key) {
+            Ok(list) => list,
+            Err(StorageError::NotFound(_)) => TodoList::new(),
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self {
+            list,
+            storage,
+            key: key.to_string(),
+            tracker: SaveTracker::default(),
+        })
+    }
+
+    /// Gets the todo list.
+    pub fn list(&self) -> &TodoList {
+        &self.list
+    }
+
+    /// Changes the todo list, marking the store as having unsaved changes.
+    ///
+    /// # Arguments
+    /// * `change` - Closure applying the change
+    ///
+    /// # Returns
+    /// Whatever the closure returns, such as the id of an added todo
+    pub fn mutate<R>(&mut self, change: impl FnOnce(&mut TodoList) -> R) -> R {
+        self.tracker.mark_dirty();
+        change(&mut self.list)
+    }
+
+    /// Gets whether everything has been saved, or why not.
+    pub fn status(&self) -> SaveStatus {
+        self.tracker.status()
+    }
+
+    /// Writes the todo list to storage if it has unsaved changes.
+    pub fn flush(&mut self) -> Result<(), StorageError> {
+        if !self.tracker.is_dirty() {
+            return Ok(());
+        }
+        autosave::flush_with(
+            &mut self.tracker,
+            self.storage.as_ref(),
+            &self.key,
+            &self.list,
+        )
+    }
+
+    /// Gives up the store, returning the todo list without saving it.
+    pub fn into_list(self) -> TodoList {
+        self.list
+    }
+}
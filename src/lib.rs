@@ -1,3 +1,6 @@
+#[cfg(feature = "ui")]
 pub mod components;
+pub mod engine;
 pub mod models;
+pub mod sync;
 pub mod utils;
@@ -2,10 +2,12 @@ use dioxus::prelude::*;
 
 mod components;
 mod models;
+#[cfg(feature = "caldav")]
+mod sync;
 mod utils;
 
 use components::TodoApp;
-use dioxus_logger::tracing::Level;
+use tracing::Level;
 use utils::constants::app::APP_NAME;
 use utils::constants::ui::window::{DEFAULT_HEIGHT, DEFAULT_WIDTH};
 
@@ -13,7 +15,8 @@ const FAVICON: Asset = asset!("/assets/favicon.ico");
 const TAILWIND_CSS: Asset = asset!("/assets/tailwind.css");
 
 fn main() {
-    dioxus_logger::init(Level::INFO).expect("failed to init logger");
+    utils::startup::mark_launch();
+    utils::debug_log::init(Level::INFO).expect("failed to init logger");
 
     #[cfg(feature = "desktop")]
     dioxus::LaunchBuilder::desktop()
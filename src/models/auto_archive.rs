@@ -0,0 +1,46 @@
+//! Policy moving old completed todos out of the list
+//!
+//! When the setting is on, completed todos are archived once their
+//! completion is more than the configured number of days old. Locked todos
+//! and todos completed before completion times were recorded are kept.
+
+use super::TodoList;
+use chrono::{DateTime, Duration, Utc};
+
+/// Picks the todos the auto-archive policy would move out of the list.
+///
+/// # Arguments
+/// * `list` - The todos to look at
+/// * `now` - The current time
+/// * `days` - How many days after completion a todo is archived
+///
+/// # Returns
+/// The ids of the matching todos, in list order
+///
+/// # Example
+/// ```
+/// use chrono::{Duration, Utc};
+/// use todo::models::TodoList;
+/// use todo::models::auto_archive::select_auto_archivable;
+///
+/// let mut list = TodoList::new();
+/// let id = list.add("Old report".to_string());
+/// list.toggle(id);
+/// let later = Utc::now() + Duration::days(31);
+/// assert_eq!(select_auto_archivable(&list, later, 30), vec![id]);
+/// assert!(select_auto_archivable(&list, later, 60).is_empty());
+/// ```
+pub fn select_auto_archivable(list: &TodoList, now: DateTime<Utc>, days: u32) -> Vec<usize> {
+    let cutoff = now - Duration::days(days.into());
+    list.all()
+        .into_iter()
+        .filter(|todo| {
+            todo.completed
+                && !todo.locked
+                && todo
+                    .completed_at
+                    .is_some_and(|completed_at| completed_at < cutoff)
+        })
+        .map(|todo| todo.id)
+        .collect()
+}
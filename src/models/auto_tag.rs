@@ -0,0 +1,164 @@
+//! Rules that tag todos automatically based on their text
+//!
+//! A rule pairs a pattern with a tag. Plain patterns are comma-separated
+//! words or phrases, matched as whole words ignoring case, so `buy, order`
+//! tags "Buy milk" and "Order pizza" but not "Disorder". Regex patterns are
+//! matched anywhere in the text, also ignoring case.
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A rule adding a tag to todos whose text matches a pattern.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AutoTagRule {
+    /// Comma-separated words, or a regular expression when `is_regex` is set
+    pub pattern: String,
+    /// Whether the pattern is a regular expression
+    #[serde(default)]
+    pub is_regex: bool,
+    /// The tag added to matching todos
+    pub tag: String,
+}
+
+/// Reasons a rule cannot be saved.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AutoTagError {
+    /// The pattern has no words to match
+    EmptyPattern,
+    /// The tag is empty
+    EmptyTag,
+    /// The regular expression does not compile
+    InvalidRegex(String),
+}
+
+impl fmt::Display for AutoTagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AutoTagError::EmptyPattern => write!(f, "Enter a word or pattern to match"),
+            AutoTagError::EmptyTag => write!(f, "Enter the tag to add"),
+            AutoTagError::InvalidRegex(reason) => {
+                write!(f, "Invalid regular expression: {}", reason)
+            }
+        }
+    }
+}
+
+impl AutoTagRule {
+    /// Checks that the rule can be applied.
+    ///
+    /// Called when a rule is saved, so a broken regular expression is
+    /// reported to the user instead of being silently skipped later.
+    pub fn validate(&self) -> Result<(), AutoTagError> {
+        self.compile().map(|_| ())
+    }
+
+    /// Compiles the rule into a matcher.
+    fn compile(&self) -> Result<Matcher, AutoTagError> {
+        if self.tag.trim().is_empty() {
+            return Err(AutoTagError::EmptyTag);
+        }
+
+        if self.is_regex {
+            if self.pattern.is_empty() {
+                return Err(AutoTagError::EmptyPattern);
+            }
+            RegexBuilder::new(&self.pattern)
+                .case_insensitive(true)
+                .build()
+                .map(Matcher::Regex)
+                .map_err(|e| AutoTagError::InvalidRegex(e.to_string()))
+        } else {
+            let words: Vec<String> = self
+                .pattern
+                .split(',')
+                .map(|word| word.trim().to_lowercase())
+                .filter(|word| !word.is_empty())
+                .collect();
+            if words.is_empty() {
+                Err(AutoTagError::EmptyPattern)
+            } else {
+                Ok(Matcher::Words(words))
+            }
+        }
+    }
+}
+
+/// How a compiled rule matches text.
+#[derive(Clone, Debug)]
+enum Matcher {
+    /// Any of these lowercased words or phrases, as whole words
+    Words(Vec<String>),
+    Regex(Regex),
+}
+
+impl Matcher {
+    fn matches(&self, text: &str, lowercase_text: &str) -> bool {
+        match self {
+            Matcher::Words(words) => words
+                .iter()
+                .any(|word| contains_whole_word(lowercase_text, word)),
+            Matcher::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+/// Checks if `text` contains `word` with no letter or digit directly around it.
+fn contains_whole_word(text: &str, word: &str) -> bool {
+    text.match_indices(word).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+/// A set of rules compiled once and applied to many todos.
+#[derive(Clone, Debug, Default)]
+pub struct AutoTagger {
+    rules: Vec<(Matcher, String)>,
+}
+
+impl AutoTagger {
+    /// Compiles the rules, skipping any that are not valid.
+    pub fn new(rules: &[AutoTagRule]) -> Self {
+        Self {
+            rules: rules
+                .iter()
+                .filter_map(|rule| {
+                    let matcher = rule.compile().ok()?;
+                    Some((matcher, rule.tag.trim().to_string()))
+                })
+                .collect(),
+        }
+    }
+
+    /// Checks if there are no rules to apply.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Gets the tags the rules add to a todo with this text, without duplicates.
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::{AutoTagRule, AutoTagger};
+    ///
+    /// let tagger = AutoTagger::new(&[AutoTagRule {
+    ///     pattern: "buy, order".to_string(),
+    ///     is_regex: false,
+    ///     tag: "Shopping".to_string(),
+    /// }]);
+    /// assert_eq!(tagger.tags_for("Buy milk"), vec!["Shopping"]);
+    /// assert!(tagger.tags_for("Fix the disorder").is_empty());
+    /// ```
+    pub fn tags_for(&self, text: &str) -> Vec<&str> {
+        let lowercase_text = text.to_lowercase();
+        let mut tags: Vec<&str> = Vec::new();
+        for (matcher, tag) in &self.rules {
+            if !tags.contains(&tag.as_str()) && matcher.matches(text, &lowercase_text) {
+                tags.push(tag);
+            }
+        }
+        tags
+    }
+}
@@ -0,0 +1,122 @@
+//! The morning summary of overdue todos and todos due today
+//!
+//! The desktop app shows one notification a day at the time set in settings,
+//! like "3 overdue, 5 due today". The day it last fired is stored, so
+//! starting the app again later that day does not repeat it, and a day missed
+//! while the app was closed or the computer asleep is caught up on as soon as
+//! it runs again that day.
+
+use super::Todo;
+use crate::utils::dates;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+
+/// Counts of the open todos needing attention today.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DigestSummary {
+    /// Open todos due before today
+    pub overdue: usize,
+    /// Open todos due today
+    pub due_today: usize,
+}
+
+impl DigestSummary {
+    /// Checks if there is nothing to report.
+    pub fn is_empty(&self) -> bool {
+        self.overdue == 0 && self.due_today == 0
+    }
+
+    /// Gets the notification text.
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::digest::DigestSummary;
+    ///
+    /// let summary = DigestSummary { overdue: 3, due_today: 5 };
+    /// assert_eq!(summary.message(), "3 overdue, 5 due today");
+    /// assert_eq!(DigestSummary { overdue: 0, due_today: 1 }.message(), "1 due today");
+    /// ```
+    pub fn message(&self) -> String {
+        match (self.overdue, self.due_today) {
+            (0, 0) => "Nothing due today".to_string(),
+            (overdue, 0) => format!("{} overdue", overdue),
+            (0, due_today) => format!("{} due today", due_today),
+            (overdue, due_today) => format!("{} overdue, {} due today", overdue, due_today),
+        }
+    }
+}
+
+/// Counts the open todos that are overdue or due today.
+///
+/// # Arguments
+/// * `todos` - The todos to look at
+/// * `today` - The current local date
+pub fn summarize<'a>(todos: impl IntoIterator<Item = &'a Todo>, today: NaiveDate) -> DigestSummary {
+    todos
+        .into_iter()
+        .filter(|todo| !todo.completed && todo.deleted_at.is_none())
+        .filter_map(|todo| todo.due_date)
+        .fold(DigestSummary::default(), |mut summary, due| {
+            let due = dates::due_day(due);
+            if due < today {
+                summary.overdue += 1;
+            } else if due == today {
+                summary.due_today += 1;
+            }
+            summary
+        })
+}
+
+/// Gets when the digest fires next.
+///
+/// The digest fires once a day at `at`, skipping weekends when asked, and not
+/// again on a day it already fired. A firing time already passed today is
+/// returned as is, meaning the digest is due now.
+///
+/// # Arguments
+/// * `now` - The current local time
+/// * `at` - The local time of day the digest fires
+/// * `last_fired` - The day the digest last fired, if ever
+/// * `skip_weekends` - Whether Saturdays and Sundays are quiet days
+///
+/// # Example
+/// ```
+/// use chrono::{NaiveDate, NaiveTime};
+/// use todo::models::digest::next_fire;
+///
+/// let at = NaiveTime::from_hms_opt(8, 30, 0).unwrap();
+/// let tuesday = NaiveDate::from_ymd_opt(2025, 3, 11).unwrap();
+/// let nine = tuesday.and_hms_opt(9, 0, 0).unwrap();
+///
+/// // Launched at 09:00 without a digest yet today: due now
+/// assert_eq!(next_fire(nine, at, None, false), tuesday.and_time(at));
+/// // Already fired today: tomorrow morning
+/// assert_eq!(next_fire(nine, at, Some(tuesday), false), tuesday.succ_opt().unwrap().and_time(at));
+/// ```
+pub fn next_fire(
+    now: NaiveDateTime,
+    at: NaiveTime,
+    last_fired: Option<NaiveDate>,
+    skip_weekends: bool,
+) -> NaiveDateTime {
+    let mut day = now.date();
+    if last_fired.is_some_and(|last| last >= day) {
+        day += Duration::days(1);
+    }
+    while skip_weekends && dates::is_weekend(day.weekday()) {
+        day += Duration::days(1);
+    }
+    day.and_time(at)
+}
+
+/// Checks if the digest should fire now.
+///
+/// # Arguments
+/// Same as [`next_fire`]
+pub fn is_due(
+    now: NaiveDateTime,
+    at: NaiveTime,
+    last_fired: Option<NaiveDate>,
+    skip_weekends: bool,
+) -> bool {
+    next_fire(now, at, last_fired, skip_weekends) <= now
+}
@@ -0,0 +1,123 @@
+//! Sanity limits for due dates
+//!
+//! A date typed with a slip, like the year 0200 for 2020, parses fine but is
+//! almost certainly wrong, and so is a date decades in the past. Due dates
+//! must fall within a window, from 1970 to 50 years from today by default.
+//! Clearing a due date is always allowed.
+
+use crate::utils::constants::todo::{EARLIEST_DUE_YEAR, MAX_DUE_YEARS_AHEAD};
+use chrono::{DateTime, Months, NaiveDate, Utc};
+use std::fmt;
+
+/// Why a due date is refused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DueDateError {
+    /// The date is before the earliest allowed one
+    TooEarly {
+        /// The refused date
+        due: NaiveDate,
+        /// The earliest allowed date
+        earliest: NaiveDate,
+    },
+    /// The date is after the latest allowed one
+    TooLate {
+        /// The refused date
+        due: NaiveDate,
+        /// The latest allowed date
+        latest: NaiveDate,
+    },
+}
+
+impl fmt::Display for DueDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DueDateError::TooEarly { due, earliest } => write!(
+                f,
+                "Due date {} is before {}, the earliest allowed",
+                due.format("%Y-%m-%d"),
+                earliest.format("%Y-%m-%d")
+            ),
+            DueDateError::TooLate { due, latest } => write!(
+                f,
+                "Due date {} is after {}, the latest allowed",
+                due.format("%Y-%m-%d"),
+                latest.format("%Y-%m-%d")
+            ),
+        }
+    }
+}
+
+/// The window due dates must fall within.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DueDateLimits {
+    /// The earliest day a todo can be due
+    pub earliest: NaiveDate,
+    /// How many years ahead of today a todo can be due at the latest
+    pub years_ahead: u32,
+}
+
+impl Default for DueDateLimits {
+    fn default() -> Self {
+        Self {
+            earliest: NaiveDate::from_ymd_opt(EARLIEST_DUE_YEAR, 1, 1).unwrap_or(NaiveDate::MIN),
+            years_ahead: MAX_DUE_YEARS_AHEAD,
+        }
+    }
+}
+
+impl DueDateLimits {
+    /// Gets the latest day a todo can be due, counted from `today`.
+    pub fn latest(&self, today: NaiveDate) -> NaiveDate {
+        today
+            .checked_add_months(Months::new(self.years_ahead.saturating_mul(12)))
+            .unwrap_or(NaiveDate::MAX)
+    }
+
+    /// Checks that a todo may be due on a day.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use todo::models::due_limits::DueDateLimits;
+    ///
+    /// let limits = DueDateLimits::default();
+    /// let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+    /// assert!(limits.check_day(NaiveDate::from_ymd_opt(2025, 4, 1).unwrap(), today).is_ok());
+    ///
+    /// let typo = NaiveDate::from_ymd_opt(200, 4, 1).unwrap();
+    /// assert_eq!(
+    ///     limits.check_day(typo, today).unwrap_err().to_string(),
+    ///     "Due date 0200-04-01 is before 1970-01-01, the earliest allowed"
+    /// );
+    /// ```
+    pub fn check_day(&self, due: NaiveDate, today: NaiveDate) -> Result<(), DueDateError> {
+        let latest = self.latest(today);
+        if due < self.earliest {
+            Err(DueDateError::TooEarly {
+                due,
+                earliest: self.earliest,
+            })
+        } else if due > latest {
+            Err(DueDateError::TooLate { due, latest })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Checks that a todo may have a due date, where `None` clears it and is
+    /// always allowed.
+    ///
+    /// # Arguments
+    /// * `due` - The due date to check
+    /// * `now` - The current time, from which the latest allowed date counts
+    pub fn check(
+        &self,
+        due: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Result<(), DueDateError> {
+        match due {
+            Some(due) => self.check_day(due.date_naive(), now.date_naive()),
+            None => Ok(()),
+        }
+    }
+}
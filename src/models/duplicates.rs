@@ -0,0 +1,152 @@
+//! Open todos that repeat each other within the list
+//!
+//! Two todos repeat each other when their texts match ignoring case and runs
+//! of whitespace, the same rule imports use. Merging a group keeps one todo,
+//! gives it every tag of the group and the earliest due date, and moves the
+//! others to the trash.
+//!
+//! Working out a merge is kept apart from applying it, so what a merge would
+//! do can be shown before the list is touched.
+
+use super::import_plan::match_key;
+use super::{Todo, TodoList};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Open todos with the same text, in list order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DuplicateGroup {
+    /// The todos, at least two
+    pub todos: Vec<Todo>,
+}
+
+impl DuplicateGroup {
+    /// Works out what merging the group would do.
+    ///
+    /// The first locked todo is kept, since locked todos cannot be deleted,
+    /// or else the first todo in list order. Its tags come first, followed
+    /// by the tags only the others have.
+    pub fn merge(&self) -> Merge {
+        let keep = self
+            .todos
+            .iter()
+            .find(|todo| todo.locked)
+            .or(self.todos.first())
+            .map_or(0, |todo| todo.id);
+
+        let mut tags: Vec<String> = Vec::new();
+        let kept = self.todos.iter().filter(|todo| todo.id == keep);
+        for todo in kept.chain(self.todos.iter().filter(|todo| todo.id != keep)) {
+            for tag in &todo.tags {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
+            }
+        }
+
+        Merge {
+            keep,
+            tags,
+            due_date: self.todos.iter().filter_map(|todo| todo.due_date).min(),
+            remove: self
+                .todos
+                .iter()
+                .filter(|todo| todo.id != keep)
+                .map(|todo| todo.id)
+                .collect(),
+        }
+    }
+
+    /// Checks if the todos differ in their tags or due dates, which merging
+    /// brings together.
+    pub fn differs(&self) -> bool {
+        self.todos.windows(2).any(|pair| {
+            let (a, b) = (&pair[0], &pair[1]);
+            a.due_date != b.due_date || {
+                let mut a_tags = a.tags.clone();
+                let mut b_tags = b.tags.clone();
+                a_tags.sort();
+                b_tags.sort();
+                a_tags != b_tags
+            }
+        })
+    }
+}
+
+/// What merging a group of duplicates does.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Merge {
+    /// The id of the todo kept
+    pub keep: usize,
+    /// Every tag of the group, which the kept todo ends up with
+    pub tags: Vec<String>,
+    /// The earliest due date of the group, if any has one
+    pub due_date: Option<DateTime<Utc>>,
+    /// The ids of the todos moved to the trash
+    pub remove: Vec<usize>,
+}
+
+impl Merge {
+    /// Applies the merge to the list.
+    ///
+    /// Locked todos among those to remove stay in the list, and nothing
+    /// happens if the kept todo is gone.
+    ///
+    /// # Returns
+    /// The number of todos moved to the trash
+    pub fn apply(&self, list: &mut TodoList) -> usize {
+        if list.get(self.keep).is_none() {
+            return 0;
+        }
+        for tag in &self.tags {
+            list.add_tag(self.keep, tag.clone());
+        }
+        if self.due_date.is_some()
+            && list.get(self.keep).map(|todo| todo.due_date) != Some(self.due_date)
+        {
+            // The date is already on a todo of the list, so it is not checked again
+            let _ = list.set_due_date(self.keep, self.due_date);
+        }
+        self.remove
+            .iter()
+            .filter(|id| list.move_to_trash(**id).is_ok())
+            .count()
+    }
+}
+
+/// Groups the open todos of the list that repeat each other.
+///
+/// # Returns
+/// The groups in the list order of their first todo, leaving out todos that
+/// repeat no other
+///
+/// # Example
+/// ```
+/// use todo::models::TodoList;
+/// use todo::models::duplicates::find_duplicates;
+///
+/// let mut list = TodoList::new();
+/// let first = list.add("Renew domain".to_string());
+/// list.add("Buy milk".to_string());
+/// let second = list.add("renew  DOMAIN".to_string());
+///
+/// let groups = find_duplicates(&list);
+/// assert_eq!(groups.len(), 1);
+/// assert_eq!(groups[0].todos.iter().map(|todo| todo.id).collect::<Vec<_>>(), [first, second]);
+/// ```
+pub fn find_duplicates(list: &TodoList) -> Vec<DuplicateGroup> {
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut groups: Vec<DuplicateGroup> = Vec::new();
+    for todo in list.all().into_iter().filter(|todo| !todo.completed) {
+        let key = match_key(&todo.text);
+        match positions.get(&key) {
+            Some(&position) => groups[position].todos.push(todo),
+            None => {
+                positions.insert(key, groups.len());
+                groups.push(DuplicateGroup { todos: vec![todo] });
+            }
+        }
+    }
+    groups.retain(|group| group.todos.len() > 1);
+    groups
+}
@@ -0,0 +1,117 @@
+//! Rules escalating open todos as they age
+//!
+//! Two rules, each turned on in the settings: an open todo whose due date
+//! has passed is made high priority, once, and an open todo without a due
+//! date is tagged for review once it has waited the configured number of
+//! days since it was added. Completed, archived and locked todos are left
+//! alone, as are todos added before creation times were recorded.
+//!
+//! Planning is kept apart from applying, so the rules can be checked
+//! against any date without touching the list.
+
+use super::{AppSettings, TodoList};
+use crate::utils::constants::todo::{HIGH_PRIORITY_TAG, REVIEW_TAG};
+use crate::utils::dates;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::fmt;
+
+/// A change the escalation rules would make to a todo.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Escalation {
+    /// Make an overdue todo high priority
+    Urgent(usize),
+    /// Tag a todo without a due date for review
+    Review(usize),
+}
+
+impl Escalation {
+    /// Makes the change.
+    ///
+    /// # Returns
+    /// `true` if the todo was found and changed
+    pub fn apply(self, list: &mut TodoList) -> bool {
+        match self {
+            Escalation::Urgent(id) => list.escalate(id),
+            Escalation::Review(id) => {
+                let tagged = list
+                    .get(id)
+                    .is_some_and(|todo| has_tag(&todo.tags, REVIEW_TAG));
+                !tagged && list.add_tag(id, REVIEW_TAG.to_string())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Escalation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Escalation::Urgent(id) => {
+                write!(f, "todo {} is overdue, tagged {}", id, HIGH_PRIORITY_TAG)
+            }
+            Escalation::Review(id) => write!(
+                f,
+                "todo {} has waited without a due date, tagged {}",
+                id, REVIEW_TAG
+            ),
+        }
+    }
+}
+
+fn has_tag(tags: &[String], tag: &str) -> bool {
+    tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+}
+
+/// Works out the changes the escalation rules would make, without making them.
+///
+/// # Arguments
+/// * `list` - The todos to look at
+/// * `settings` - Which rules are on, and after how many days
+/// * `now` - The current time
+/// * `today` - The current local date, which decides what is overdue
+///
+/// # Returns
+/// The changes, in list order
+///
+/// # Example
+/// ```
+/// use chrono::{Duration, Utc};
+/// use todo::models::escalation::{Escalation, plan_escalations};
+/// use todo::models::{AppSettings, TodoList};
+///
+/// let mut list = TodoList::new();
+/// let id = list.add("Renew passport".to_string());
+/// let now = Utc::now();
+/// list.set_due_date(id, Some(now - Duration::days(2))).unwrap();
+///
+/// let settings = AppSettings { escalate_overdue: true, ..AppSettings::default() };
+/// let today = now.date_naive();
+/// assert_eq!(plan_escalations(&list, &settings, now, today), [Escalation::Urgent(id)]);
+/// assert!(plan_escalations(&list, &AppSettings::default(), now, today).is_empty());
+/// ```
+pub fn plan_escalations(
+    list: &TodoList,
+    settings: &AppSettings,
+    now: DateTime<Utc>,
+    today: NaiveDate,
+) -> Vec<Escalation> {
+    let review_cutoff = settings
+        .review_undated_after_days
+        .map(|days| now - Duration::days(days.into()));
+    list.all()
+        .into_iter()
+        .filter(|todo| !todo.completed && !todo.locked)
+        .filter_map(|todo| match todo.due_date {
+            Some(due) => (settings.escalate_overdue
+                && dates::due_day(due) < today
+                && !todo.escalated
+                && !has_tag(&todo.tags, HIGH_PRIORITY_TAG))
+            .then_some(Escalation::Urgent(todo.id)),
+            None => {
+                let waited = review_cutoff
+                    .zip(todo.created_at)
+                    .is_some_and(|(cutoff, created_at)| created_at <= cutoff);
+                (waited && !has_tag(&todo.tags, REVIEW_TAG)).then_some(Escalation::Review(todo.id))
+            }
+        })
+        .collect()
+}
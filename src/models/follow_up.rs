@@ -0,0 +1,76 @@
+//! Todos handed to someone else that are due a reminder
+//!
+//! Todos carrying the follow-up tag from settings, "waiting" by default, are
+//! listed for follow-up once they have gone unchanged for the configured
+//! number of days. Bumping one marks it changed again, so it drops off the
+//! list until it has waited that long once more.
+
+use super::{Todo, TodoList, staleness};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::cmp::Reverse;
+
+/// Picks the open todos with the follow-up tag that have waited too long.
+///
+/// # Arguments
+/// * `list` - The todos to look at
+/// * `tag` - The follow-up tag, matched ignoring case; empty turns the rule off
+/// * `now` - The current time
+/// * `days` - How many days without changes make a todo due for follow-up
+///
+/// # Returns
+/// The ids of the matching todos, the longest waiting first
+///
+/// # Example
+/// ```
+/// use chrono::{Duration, Utc};
+/// use todo::models::TodoList;
+/// use todo::models::follow_up::select_follow_ups;
+///
+/// let mut list = TodoList::new();
+/// let id = list.add("Quote from the plumber".to_string());
+/// list.add_tag(id, "Waiting".to_string());
+/// let later = Utc::now() + Duration::days(8);
+/// assert_eq!(select_follow_ups(&list, "waiting", later, 7), vec![id]);
+/// assert!(select_follow_ups(&list, "waiting", later, 10).is_empty());
+/// ```
+pub fn select_follow_ups(list: &TodoList, tag: &str, now: DateTime<Utc>, days: u32) -> Vec<usize> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Vec::new();
+    }
+    let mut waiting: Vec<Todo> = list
+        .all()
+        .into_iter()
+        .filter(|todo| has_tag(todo, tag) && staleness::is_stale(todo, now, days))
+        .collect();
+    waiting.sort_by_key(|todo| Reverse(staleness::staleness(todo, now)));
+    waiting.into_iter().map(|todo| todo.id).collect()
+}
+
+/// Checks if a todo carries the tag, ignoring case.
+fn has_tag(todo: &Todo, tag: &str) -> bool {
+    todo.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+}
+
+/// Appends a dated follow-up line to a todo's notes.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use todo::models::follow_up::append_follow_up_note;
+///
+/// let today = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+/// assert_eq!(append_follow_up_note("", today), "Followed up on 2025-03-03");
+/// assert_eq!(
+///     append_follow_up_note("Sent the form", today),
+///     "Sent the form\nFollowed up on 2025-03-03"
+/// );
+/// ```
+pub fn append_follow_up_note(notes: &str, today: NaiveDate) -> String {
+    let line = format!("Followed up on {}", today.format("%Y-%m-%d"));
+    if notes.trim().is_empty() {
+        line
+    } else {
+        format!("{}\n{}", notes.trim_end(), line)
+    }
+}
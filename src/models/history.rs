@@ -0,0 +1,155 @@
+//! Undo and redo for changes to the todo list
+//!
+//! Each change is kept as a patch holding the todos it touched as they were
+//! before it, which puts back the exact order, trash and text of every todo
+//! on undo. Lists share unchanged todos, so a change to one todo of a long
+//! list keeps that one todo rather than a copy of the list. Only the last
+//! [`UNDO_HISTORY_LIMIT`] changes are kept, and making a new change after an
+//! undo forgets what could have been redone.
+//!
+//! Changes made without going through the history, such as a sync or a
+//! restored backup, would be lost by stepping back over them. The history
+//! notices the list no longer looks as it left it and starts afresh instead.
+
+use super::{ListPatch, TodoList};
+use crate::utils::constants::todo::UNDO_HISTORY_LIMIT;
+use std::collections::VecDeque;
+
+/// Changes to a todo list that can be undone and redone.
+#[derive(Clone, Debug)]
+pub struct TodoHistory {
+    /// Patches undoing each change, oldest first
+    undo: VecDeque<ListPatch>,
+    /// Patches making each undone change again, most recent last
+    redo: Vec<ListPatch>,
+    /// The list as the last change, undo or redo left it
+    expected: Option<TodoList>,
+    limit: usize,
+}
+
+impl Default for TodoHistory {
+    fn default() -> Self {
+        Self::with_limit(UNDO_HISTORY_LIMIT)
+    }
+}
+
+impl TodoHistory {
+    /// Creates an empty history keeping at most `limit` changes.
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            expected: None,
+            limit,
+        }
+    }
+
+    /// Records a change, given the list before and after it.
+    ///
+    /// A change that left the list as it was is not recorded.
+    ///
+    /// # Returns
+    /// `true` if the change was recorded
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::{TodoHistory, TodoList};
+    ///
+    /// let mut history = TodoHistory::default();
+    /// let mut list = TodoList::new();
+    /// let before = list.clone();
+    /// list.add("Call the plumber".to_string());
+    ///
+    /// assert!(history.record(before, &list));
+    /// assert!(history.undo(&mut list));
+    /// assert!(list.all().is_empty());
+    /// assert!(history.redo(&mut list));
+    /// assert_eq!(list.all()[0].text, "Call the plumber");
+    /// ```
+    pub fn record(&mut self, before: TodoList, after: &TodoList) -> bool {
+        let patch = after.patch_back_to(&before);
+        if patch.is_empty() {
+            return false;
+        }
+        if self
+            .expected
+            .as_ref()
+            .is_some_and(|expected| *expected != before)
+        {
+            self.clear();
+        }
+        self.undo.push_back(patch);
+        while self.undo.len() > self.limit {
+            self.undo.pop_front();
+        }
+        self.redo.clear();
+        self.expected = Some(after.clone());
+        true
+    }
+
+    /// Puts the list back as it was before the last recorded change.
+    ///
+    /// # Returns
+    /// `true` if a change was undone; `false` when there is none, or when the
+    /// list was changed elsewhere since and the history was cleared
+    pub fn undo(&mut self, list: &mut TodoList) -> bool {
+        if !self.matches(list) {
+            return false;
+        }
+        let Some(patch) = self.undo.pop_back() else {
+            return false;
+        };
+        let redo = list.apply_patch(patch);
+        self.redo.push(redo);
+        self.expected = Some(list.clone());
+        true
+    }
+
+    /// Makes the last undone change again.
+    ///
+    /// # Returns
+    /// `true` if a change was redone; `false` when there is none, or when the
+    /// list was changed elsewhere since and the history was cleared
+    pub fn redo(&mut self, list: &mut TodoList) -> bool {
+        if !self.matches(list) {
+            return false;
+        }
+        let Some(patch) = self.redo.pop() else {
+            return false;
+        };
+        let undo = list.apply_patch(patch);
+        self.undo.push_back(undo);
+        self.expected = Some(list.clone());
+        true
+    }
+
+    /// Whether there is a change to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether there is an undone change to make again.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forgets every change.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.expected = None;
+    }
+
+    /// Checks the list is as the history left it, clearing the history if not.
+    fn matches(&mut self, list: &TodoList) -> bool {
+        if self
+            .expected
+            .as_ref()
+            .is_some_and(|expected| expected != list)
+        {
+            self.clear();
+            return false;
+        }
+        true
+    }
+}
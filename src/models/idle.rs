@@ -0,0 +1,106 @@
+//! Telling time spent in the app from time spent away
+//!
+//! Periodic checks expect their ticks a set interval apart. While the
+//! computer sleeps, or the browser tab is hidden, ticks stop or slow down, so
+//! the next one arrives hours late. [`TickMonitor`] compares the time that
+//! actually passed with what a tick may account for: anything beyond the cap
+//! is reported as time away instead of being counted, so eight hours of sleep
+//! are not tracked as eight hours of work.
+
+use chrono::{DateTime, Duration, Utc};
+
+/// What passed between two ticks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tick {
+    /// Time the tick accounts for, at most the monitor's cap
+    pub credited: Duration,
+    /// Time beyond the cap, when the gap was long enough to mean the user was away
+    pub away: Option<Duration>,
+}
+
+impl Tick {
+    /// Checks if the tick came after a gap, so anything waiting on time
+    /// should catch up now.
+    pub fn resumed(&self) -> bool {
+        self.away.is_some()
+    }
+}
+
+/// Watches the gaps between the ticks of a periodic check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TickMonitor {
+    last: DateTime<Utc>,
+    max_credit: Duration,
+}
+
+impl TickMonitor {
+    /// Creates a monitor counting from `started`.
+    ///
+    /// # Arguments
+    /// * `started` - When the check started ticking
+    /// * `max_credit` - Most time one tick may account for; set it to a
+    ///   couple of intervals so a late tick is not mistaken for time away
+    pub fn new(started: DateTime<Utc>, max_credit: Duration) -> Self {
+        Self {
+            last: started,
+            max_credit,
+        }
+    }
+
+    /// Records a tick and reports the time since the previous one.
+    ///
+    /// A clock set back counts for nothing, and the next tick is measured
+    /// from the new time.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, TimeZone, Utc};
+    /// use todo::models::idle::TickMonitor;
+    ///
+    /// let start = Utc.with_ymd_and_hms(2025, 3, 12, 9, 0, 0).unwrap();
+    /// let mut monitor = TickMonitor::new(start, Duration::minutes(2));
+    ///
+    /// let tick = monitor.tick(start + Duration::minutes(1));
+    /// assert_eq!(tick.credited, Duration::minutes(1));
+    /// assert!(!tick.resumed());
+    ///
+    /// // Back after the night: two minutes count, the rest was time away
+    /// let tick = monitor.tick(start + Duration::hours(8) + Duration::minutes(1));
+    /// assert_eq!(tick.credited, Duration::minutes(2));
+    /// assert_eq!(tick.away, Some(Duration::hours(8) - Duration::minutes(2)));
+    /// ```
+    pub fn tick(&mut self, now: DateTime<Utc>) -> Tick {
+        let elapsed = (now - self.last).max(Duration::zero());
+        self.last = now;
+        if elapsed > self.max_credit {
+            Tick {
+                credited: self.max_credit,
+                away: Some(elapsed - self.max_credit),
+            }
+        } else {
+            Tick {
+                credited: elapsed,
+                away: None,
+            }
+        }
+    }
+}
+
+/// Formats a stretch of time away, like "3h 12m" or "45m".
+///
+/// # Example
+/// ```
+/// use chrono::Duration;
+/// use todo::models::idle::format_away;
+///
+/// assert_eq!(format_away(Duration::minutes(192)), "3h 12m");
+/// assert_eq!(format_away(Duration::minutes(45)), "45m");
+/// assert_eq!(format_away(Duration::hours(2)), "2h 0m");
+/// ```
+pub fn format_away(away: Duration) -> String {
+    let minutes = away.num_minutes().max(0);
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
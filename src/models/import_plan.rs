@@ -0,0 +1,218 @@
+//! What an import would change, worked out before anything is changed
+//!
+//! Imported todos are sorted into three groups: new todos to add, todos
+//! that repeat one already in the list, and todos that cannot be imported.
+//! A todo repeats another when their texts match ignoring case and runs of
+//! whitespace. Applying a plan adds the new todos and merges each repeat
+//! into the todo it matches; see [`ImportPlan::apply`].
+
+use super::due_limits::DueDateLimits;
+use super::{Todo, TodoList};
+use crate::utils::text;
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+
+/// An imported todo that repeats one already in the list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportDuplicate {
+    /// The imported todo, with its text validated
+    pub todo: Todo,
+    /// The id of the todo it would merge into
+    pub existing_id: usize,
+    /// The text of the todo it would merge into
+    pub existing_text: String,
+}
+
+/// An imported todo that cannot be imported, and why.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportProblem {
+    /// 1-based position of the todo in the imported file
+    pub position: usize,
+    /// The todo's text as it is in the file
+    pub text: String,
+    /// Why it cannot be imported
+    pub reason: String,
+}
+
+/// A row of an import plan that can be left out before applying it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ImportRow {
+    /// A todo in [`ImportPlan::additions`], by position
+    Addition(usize),
+    /// A todo in [`ImportPlan::duplicates`], by position
+    Duplicate(usize),
+}
+
+/// The changes an import would make to the list.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportPlan {
+    /// Todos not yet in the list, in file order
+    pub additions: Vec<Todo>,
+    /// Todos already in the list, in file order
+    pub duplicates: Vec<ImportDuplicate>,
+    /// Todos that cannot be imported, in file order
+    pub errors: Vec<ImportProblem>,
+}
+
+/// Reduces a text to what decides whether two todos are the same.
+pub(crate) fn match_key(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+impl ImportPlan {
+    /// Sorts imported todos against the todos already in the list.
+    ///
+    /// Texts and links go through the same validation as typed ones, and due
+    /// dates must fall within the default [`DueDateLimits`]. A todo repeated
+    /// within the imported file is added once, and its later copies are
+    /// reported as problems.
+    ///
+    /// # Arguments
+    /// * `imported` - The todos read from the file, in file order
+    /// * `existing` - The todos in the list, in list order
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::Todo;
+    /// use todo::models::import_plan::ImportPlan;
+    ///
+    /// let existing = [Todo::new(1, "Buy milk".to_string())];
+    /// let imported = vec![
+    ///     Todo::new(7, "buy  MILK".to_string()),
+    ///     Todo::new(8, "Call mum".to_string()),
+    ///     Todo::new(9, "   ".to_string()),
+    /// ];
+    /// let plan = ImportPlan::new(imported, &existing);
+    /// assert_eq!(plan.additions[0].text, "Call mum");
+    /// assert_eq!(plan.duplicates[0].existing_id, 1);
+    /// assert_eq!(plan.errors[0].position, 3);
+    /// ```
+    pub fn new(imported: Vec<Todo>, existing: &[Todo]) -> Self {
+        let mut known: HashMap<String, &Todo> = HashMap::new();
+        for todo in existing {
+            known.entry(match_key(&todo.text)).or_insert(todo);
+        }
+        let mut added: HashMap<String, usize> = HashMap::new();
+        let limits = DueDateLimits::default();
+        let now = Utc::now();
+
+        let mut plan = Self::default();
+        for (index, mut todo) in imported.into_iter().enumerate() {
+            let mut reasons = Vec::new();
+            match text::validate_todo_text(&todo.text) {
+                Ok(valid) => todo.text = valid,
+                Err(e) => reasons.push(e.to_string()),
+            }
+            if let Some(url) = &mut todo.url {
+                match text::validate_url(url) {
+                    Ok(valid) => *url = valid,
+                    Err(e) => reasons.push(e.to_string()),
+                }
+            }
+            if let Err(e) = limits.check(todo.due_date, now) {
+                reasons.push(e.to_string());
+            }
+            if !reasons.is_empty() {
+                plan.errors.push(ImportProblem {
+                    position: index + 1,
+                    text: todo.text,
+                    reason: reasons.join("; "),
+                });
+                continue;
+            }
+
+            let key = match_key(&todo.text);
+            if let Some(existing) = known.get(&key) {
+                plan.duplicates.push(ImportDuplicate {
+                    existing_id: existing.id,
+                    existing_text: existing.text.clone(),
+                    todo,
+                });
+            } else if let Some(first) = added.get(&key) {
+                plan.errors.push(ImportProblem {
+                    position: index + 1,
+                    text: todo.text,
+                    reason: format!("repeats todo {first} of the file"),
+                });
+            } else {
+                added.insert(key, index + 1);
+                plan.additions.push(todo);
+            }
+        }
+        plan
+    }
+
+    /// Checks if applying the plan would change nothing.
+    pub fn is_empty(&self) -> bool {
+        self.additions.is_empty() && self.duplicates.is_empty()
+    }
+
+    /// Copies the plan without the given rows.
+    pub fn without(&self, skipped: &HashSet<ImportRow>) -> Self {
+        Self {
+            additions: self
+                .additions
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !skipped.contains(&ImportRow::Addition(*index)))
+                .map(|(_, todo)| todo.clone())
+                .collect(),
+            duplicates: self
+                .duplicates
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !skipped.contains(&ImportRow::Duplicate(*index)))
+                .map(|(_, duplicate)| duplicate.clone())
+                .collect(),
+            errors: self.errors.clone(),
+        }
+    }
+
+    /// Applies the plan to a list in one go.
+    ///
+    /// New todos are added as copies at the end of the list. A duplicate
+    /// gives the todo it matches the tags it lacks, and its due date, notes
+    /// and link where the todo has none; the text and completion are kept.
+    /// Duplicates of todos no longer in the list are skipped.
+    ///
+    /// # Returns
+    /// The numbers of todos added and merged into
+    pub fn apply(&self, list: &mut TodoList) -> (usize, usize) {
+        let added = list.add_copies(&self.additions);
+        let mut merged = 0;
+        for duplicate in &self.duplicates {
+            let Some(existing) = list.get(duplicate.existing_id) else {
+                continue;
+            };
+            let source = &duplicate.todo;
+            let new_tags: Vec<String> = source
+                .tags
+                .iter()
+                .filter(|tag| !existing.tags.contains(tag))
+                .cloned()
+                .collect();
+            let fill_due = existing.due_date.is_none() && source.due_date.is_some();
+            let fill_notes = existing.notes.is_empty() && !source.notes.is_empty();
+            let fill_url = existing.url.is_none() && source.url.is_some();
+
+            let id = duplicate.existing_id;
+            for tag in new_tags {
+                list.add_tag(id, tag);
+            }
+            if fill_due {
+                let _ = list.set_due_date(id, source.due_date);
+            }
+            if fill_notes {
+                list.set_notes(id, source.notes.clone());
+            }
+            if fill_url {
+                list.set_url(id, source.url.clone());
+            }
+            merged += 1;
+        }
+        (added, merged)
+    }
+}
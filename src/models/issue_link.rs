@@ -0,0 +1,248 @@
+//! Rules turning issue-tracker references in todo text into links
+//!
+//! A rule pairs a regular expression with a URL template, such as
+//! `[A-Z]+-\d+` with `https://jira.example.com/browse/{0}`. In the template,
+//! `{0}` stands for the whole match and `{1}`, `{2}`, … for its groups. When
+//! rules match overlapping text, the match starting first wins, then the
+//! longer one, then the rule listed first. Text inside markdown links such as
+//! `[notes](https://example.com/ABC-1)` is left alone.
+
+use crate::utils::text::{self, DisplaySegment, UrlError};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+/// Markdown links, `[label](target)`, whose text is never linked again
+static MARKDOWN_LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[[^\]]*\]\([^)\s]*\)").expect("valid markdown link pattern"));
+
+/// A rule linking text that matches a pattern to a web address.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct IssueLinkRule {
+    /// Regular expression matching a reference, such as `[A-Z]+-\d+`
+    pub pattern: String,
+    /// Address of the reference, with `{0}` for the match and `{1}`, `{2}`, …
+    /// for its groups
+    pub url_template: String,
+}
+
+/// Reasons a rule cannot be saved.
+#[derive(Clone, Debug, PartialEq)]
+pub enum IssueLinkError {
+    /// The pattern is empty
+    EmptyPattern,
+    /// The regular expression does not compile
+    InvalidRegex(String),
+    /// The pattern matches empty text, which cannot be a link
+    MatchesEmpty,
+    /// The template does not give a web address
+    InvalidTemplate(UrlError),
+}
+
+impl fmt::Display for IssueLinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssueLinkError::EmptyPattern => write!(f, "Enter a pattern to match"),
+            IssueLinkError::InvalidRegex(reason) => {
+                write!(f, "Invalid regular expression: {}", reason)
+            }
+            IssueLinkError::MatchesEmpty => write!(f, "The pattern must not match empty text"),
+            IssueLinkError::InvalidTemplate(reason) => write!(f, "The {}", reason),
+        }
+    }
+}
+
+impl IssueLinkRule {
+    /// Checks that the rule can be applied.
+    ///
+    /// Called when a rule is saved, so a broken regular expression is
+    /// reported to the user instead of being silently skipped later.
+    pub fn validate(&self) -> Result<(), IssueLinkError> {
+        self.compile().map(|_| ())
+    }
+
+    /// Compiles the pattern.
+    fn compile(&self) -> Result<Regex, IssueLinkError> {
+        if self.pattern.is_empty() {
+            return Err(IssueLinkError::EmptyPattern);
+        }
+        let regex =
+            Regex::new(&self.pattern).map_err(|e| IssueLinkError::InvalidRegex(e.to_string()))?;
+        if regex.is_match("") {
+            return Err(IssueLinkError::MatchesEmpty);
+        }
+        let sample = fill_template(&self.url_template, &["ABC-1"]);
+        text::validate_url(&sample).map_err(IssueLinkError::InvalidTemplate)?;
+        Ok(regex)
+    }
+}
+
+/// Puts the matched text into a URL template, percent-encoding it.
+///
+/// Placeholders past the last group become empty.
+fn fill_template(template: &str, groups: &[&str]) -> String {
+    let mut url = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        url.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let index = after
+            .find('}')
+            .and_then(|close| Some((after[..close].parse::<usize>().ok()?, close)));
+        match index {
+            Some((index, close)) => {
+                url.push_str(&encode(groups.get(index).copied().unwrap_or_default()));
+                rest = &after[close + 1..];
+            }
+            None => {
+                url.push('{');
+                rest = after;
+            }
+        }
+    }
+    url.push_str(rest);
+    url
+}
+
+/// Percent-encodes everything but unreserved URL characters.
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// A reference found in todo text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IssueLink {
+    /// Byte range of the reference in the text
+    pub range: Range<usize>,
+    /// Address the reference links to
+    pub url: String,
+}
+
+/// A set of rules compiled once and applied to many todos.
+///
+/// Linkers compare equal when they were built from the same rules.
+#[derive(Clone, Debug, Default)]
+pub struct IssueLinker {
+    rules: Vec<IssueLinkRule>,
+    compiled: Vec<(Regex, String)>,
+}
+
+impl PartialEq for IssueLinker {
+    fn eq(&self, other: &Self) -> bool {
+        self.rules == other.rules
+    }
+}
+
+impl IssueLinker {
+    /// Compiles the rules, skipping any that are not valid.
+    pub fn new(rules: &[IssueLinkRule]) -> Self {
+        Self {
+            rules: rules.to_vec(),
+            compiled: rules
+                .iter()
+                .filter_map(|rule| Some((rule.compile().ok()?, rule.url_template.clone())))
+                .collect(),
+        }
+    }
+
+    /// Finds the references in text, in order and without overlaps.
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::issue_link::{IssueLinkRule, IssueLinker};
+    ///
+    /// let linker = IssueLinker::new(&[IssueLinkRule {
+    ///     pattern: r"[A-Z]+-\d+".to_string(),
+    ///     url_template: "https://jira.example.com/browse/{0}".to_string(),
+    /// }]);
+    /// let links = linker.find("Fix login bug ABC-1234");
+    /// assert_eq!(links[0].range, 14..22);
+    /// assert_eq!(links[0].url, "https://jira.example.com/browse/ABC-1234");
+    /// ```
+    pub fn find(&self, text: &str) -> Vec<IssueLink> {
+        if self.compiled.is_empty() {
+            return Vec::new();
+        }
+        let markdown_links: Vec<Range<usize>> = MARKDOWN_LINK
+            .find_iter(text)
+            .map(|found| found.range())
+            .collect();
+
+        // Every match of every rule, first by start, then longest, then rule order
+        let mut candidates: Vec<(usize, IssueLink)> = Vec::new();
+        for (rule_index, (regex, template)) in self.compiled.iter().enumerate() {
+            for captures in regex.captures_iter(text) {
+                let Some(whole) = captures.get(0) else {
+                    continue;
+                };
+                let groups: Vec<&str> = captures
+                    .iter()
+                    .map(|group| group.map_or("", |group| group.as_str()))
+                    .collect();
+                candidates.push((
+                    rule_index,
+                    IssueLink {
+                        range: whole.range(),
+                        url: fill_template(template, &groups),
+                    },
+                ));
+            }
+        }
+        candidates.sort_by_key(|(rule_index, link)| {
+            (link.range.start, usize::MAX - link.range.end, *rule_index)
+        });
+
+        let mut links: Vec<IssueLink> = Vec::new();
+        for (_, link) in candidates {
+            let overlaps =
+                |other: &Range<usize>| link.range.start < other.end && other.start < link.range.end;
+            let taken = links.last().is_some_and(|last| overlaps(&last.range));
+            if !taken && !markdown_links.iter().any(overlaps) {
+                links.push(link);
+            }
+        }
+        links
+    }
+
+    /// Splits the plain runs of display segments around the references in them.
+    ///
+    /// Web addresses are kept as they are, so references inside them are not
+    /// linked.
+    pub fn link_segments<'a>(&self, segments: Vec<DisplaySegment<'a>>) -> Vec<DisplaySegment<'a>> {
+        if self.compiled.is_empty() {
+            return segments;
+        }
+        let mut linked = Vec::with_capacity(segments.len());
+        for segment in segments {
+            let DisplaySegment::Text(plain) = segment else {
+                linked.push(segment);
+                continue;
+            };
+            let mut plain_start = 0;
+            for link in self.find(plain) {
+                if plain_start < link.range.start {
+                    linked.push(DisplaySegment::Text(&plain[plain_start..link.range.start]));
+                }
+                linked.push(DisplaySegment::Issue {
+                    text: &plain[link.range.clone()],
+                    url: link.url,
+                });
+                plain_start = link.range.end;
+            }
+            if plain_start < plain.len() {
+                linked.push(DisplaySegment::Text(&plain[plain_start..]));
+            }
+        }
+        linked
+    }
+}
@@ -0,0 +1,150 @@
+//! Points and levels for completed todos
+//!
+//! Every completed todo earns karma: a point for finishing it, more for
+//! high-priority todos (tagged [`HIGH_PRIORITY_TAG`]) and for clearing one
+//! that was overdue. The score, level and weekly goal are worked out from the
+//! todos' `completed_at` times whenever they are shown, so nothing extra is
+//! stored. Days are taken in UTC, like due dates.
+
+use super::{Todo, WeekStart};
+use crate::utils::constants::todo::{
+    HIGH_PRIORITY_TAG, KARMA_COMPLETION_POINTS, KARMA_OVERDUE_BONUS, KARMA_POINTS_PER_LEVEL,
+    KARMA_PRIORITY_BONUS,
+};
+use crate::utils::dates;
+use chrono::{DateTime, Utc};
+
+/// Score and level from all completed todos.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Karma {
+    /// Total points
+    pub score: u32,
+    /// Level reached, starting at 1
+    pub level: u32,
+    /// Points earned within the current level
+    pub level_points: u32,
+}
+
+/// Todos completed this week against the weekly goal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WeeklyProgress {
+    /// Todos completed since the start of the week
+    pub completed: usize,
+    /// Todos to complete each week
+    pub goal: usize,
+}
+
+impl WeeklyProgress {
+    /// Gets how much of the goal is reached, from 0.0 to 1.0.
+    ///
+    /// A goal of zero counts as reached.
+    pub fn fraction(&self) -> f64 {
+        if self.goal == 0 {
+            return 1.0;
+        }
+        (self.completed as f64 / self.goal as f64).min(1.0)
+    }
+
+    /// Checks if the goal is reached.
+    pub fn is_met(&self) -> bool {
+        self.completed >= self.goal
+    }
+}
+
+/// Gets the points a todo earned.
+///
+/// # Returns
+/// The points, or 0 for todos that are open or have no completion time
+///
+/// # Example
+/// ```
+/// use chrono::{Duration, Utc};
+/// use todo::models::Todo;
+/// use todo::models::karma::score_for;
+///
+/// let mut todo = Todo::new(1, "Renew the passport".to_string());
+/// assert_eq!(score_for(&todo), 0);
+///
+/// todo.completed = true;
+/// todo.completed_at = Some(Utc::now());
+/// assert_eq!(score_for(&todo), 1);
+///
+/// // Urgent and finished two days late
+/// todo.tags.push("urgent".to_string());
+/// todo.due_date = Some(Utc::now() - Duration::days(2));
+/// assert_eq!(score_for(&todo), 4);
+/// ```
+pub fn score_for(todo: &Todo) -> u32 {
+    let Some(completed_at) = todo.completed_at.filter(|_| todo.completed) else {
+        return 0;
+    };
+    let mut points = KARMA_COMPLETION_POINTS;
+    if todo
+        .tags
+        .iter()
+        .any(|tag| tag.eq_ignore_ascii_case(HIGH_PRIORITY_TAG))
+    {
+        points += KARMA_PRIORITY_BONUS;
+    }
+    if todo
+        .due_date
+        .is_some_and(|due| completed_at.date_naive() > due.date_naive())
+    {
+        points += KARMA_OVERDUE_BONUS;
+    }
+    points
+}
+
+/// Gets the score and level from all todos.
+///
+/// # Example
+/// ```
+/// use todo::models::karma::{Karma, karma};
+///
+/// assert_eq!(karma([]), Karma { score: 0, level: 1, level_points: 0 });
+/// ```
+pub fn karma<'a>(todos: impl IntoIterator<Item = &'a Todo>) -> Karma {
+    let score = todos.into_iter().map(score_for).sum();
+    Karma {
+        score,
+        level: score / KARMA_POINTS_PER_LEVEL + 1,
+        level_points: score % KARMA_POINTS_PER_LEVEL,
+    }
+}
+
+/// Counts the todos completed since the start of the current week.
+///
+/// # Arguments
+/// * `todos` - The todos to look at
+/// * `now` - The current time
+/// * `week_start` - First day of the week
+/// * `goal` - Todos to complete each week
+///
+/// # Example
+/// ```
+/// use chrono::Utc;
+/// use todo::models::karma::weekly_progress;
+/// use todo::models::{Todo, WeekStart};
+///
+/// let mut todo = Todo::new(1, "Mow the lawn".to_string());
+/// todo.completed = true;
+/// todo.completed_at = Some(Utc::now());
+///
+/// let progress = weekly_progress([&todo], Utc::now(), WeekStart::Monday, 4);
+/// assert_eq!((progress.completed, progress.fraction()), (1, 0.25));
+/// ```
+pub fn weekly_progress<'a>(
+    todos: impl IntoIterator<Item = &'a Todo>,
+    now: DateTime<Utc>,
+    week_start: WeekStart,
+    goal: usize,
+) -> WeeklyProgress {
+    let (first, last) = dates::week_range(now.date_naive(), week_start);
+    let completed = todos
+        .into_iter()
+        .filter(|todo| todo.completed)
+        .filter_map(|todo| todo.completed_at)
+        .filter(|completed_at| (first..=last).contains(&completed_at.date_naive()))
+        .count();
+    WeeklyProgress { completed, goal }
+}
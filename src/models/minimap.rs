@@ -0,0 +1,126 @@
+//! The due-date strip beside long lists
+//!
+//! Each row on screen gets one mark in a slim strip along the list, colored by
+//! how urgent it is, so clusters of overdue todos can be spotted and jumped to
+//! in a long list. The strip spans the list's visible height; clicking it
+//! scrolls the list to the same proportion of its content.
+
+use super::Todo;
+use crate::utils::dates;
+use chrono::NaiveDate;
+
+/// How a row is shown in the strip.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MinimapMark {
+    /// Open and due before today
+    Overdue,
+    /// Open and due today
+    DueToday,
+    /// Completed
+    Completed,
+    /// Anything else
+    Other,
+}
+
+impl MinimapMark {
+    /// Gets the mark of a todo.
+    ///
+    /// # Arguments
+    /// * `todo` - The todo of the row
+    /// * `today` - The current local date
+    pub fn of(todo: &Todo, today: NaiveDate) -> Self {
+        if todo.completed {
+            return MinimapMark::Completed;
+        }
+        match todo.due_date.map(dates::due_day) {
+            Some(due) if due < today => MinimapMark::Overdue,
+            Some(due) if due == today => MinimapMark::DueToday,
+            _ => MinimapMark::Other,
+        }
+    }
+}
+
+/// Gets the strip's marks for the rows on screen, or `None` when there are
+/// fewer rows than `min_rows`, where the list is short enough to scan.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use todo::models::Todo;
+/// use todo::models::minimap::{MinimapMark, marks};
+///
+/// let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+/// let mut done = Todo::new(1, "Done".to_string());
+/// done.completed = true;
+/// let open = Todo::new(2, "Open".to_string());
+///
+/// assert_eq!(marks([&done, &open], today, 2), Some(vec![MinimapMark::Completed, MinimapMark::Other]));
+/// assert_eq!(marks([&done, &open], today, 3), None);
+/// ```
+pub fn marks<'a>(
+    rows: impl IntoIterator<Item = &'a Todo>,
+    today: NaiveDate,
+    min_rows: usize,
+) -> Option<Vec<MinimapMark>> {
+    let marks: Vec<_> = rows
+        .into_iter()
+        .map(|todo| MinimapMark::of(todo, today))
+        .collect();
+    (marks.len() >= min_rows).then_some(marks)
+}
+
+/// Gets the row under a click on the strip.
+///
+/// # Arguments
+/// * `y` - Distance of the click from the top of the strip (pixels)
+/// * `strip_height` - Height of the strip (pixels)
+/// * `row_count` - Number of rows the strip stands for
+///
+/// # Returns
+/// The index of the row, or `None` if there are no rows or the strip has no height
+///
+/// # Example
+/// ```
+/// use todo::models::minimap::row_at;
+///
+/// assert_eq!(row_at(0.0, 400.0, 100), Some(0));
+/// assert_eq!(row_at(200.0, 400.0, 100), Some(50));
+/// assert_eq!(row_at(400.0, 400.0, 100), Some(99));
+/// ```
+pub fn row_at(y: f64, strip_height: f64, row_count: usize) -> Option<usize> {
+    if row_count == 0 || strip_height <= 0.0 || !y.is_finite() {
+        return None;
+    }
+    let fraction = (y / strip_height).clamp(0.0, 1.0);
+    Some(((fraction * row_count as f64) as usize).min(row_count - 1))
+}
+
+/// Gets the scroll position that brings a row to the middle of the list.
+///
+/// Rows are taken to be of equal height, so the row's share of the rows is
+/// its share of the content.
+///
+/// # Arguments
+/// * `row` - Index of the row
+/// * `row_count` - Number of rows in the list
+/// * `scroll_height` - Height of the list's content (pixels)
+/// * `client_height` - Height of the list's visible area (pixels)
+///
+/// # Example
+/// ```
+/// use todo::models::minimap::scroll_top_for;
+///
+/// // 100 rows of 40px shown in 400px: row 50 starts at 2000px and ends up centered
+/// assert_eq!(scroll_top_for(50, 100, 4000.0, 400.0), 1820.0);
+/// assert_eq!(scroll_top_for(0, 100, 4000.0, 400.0), 0.0);
+/// assert_eq!(scroll_top_for(99, 100, 4000.0, 400.0), 3600.0);
+/// ```
+pub fn scroll_top_for(row: usize, row_count: usize, scroll_height: f64, client_height: f64) -> f64 {
+    if row_count == 0 {
+        return 0.0;
+    }
+    let row_height = scroll_height / row_count as f64;
+    let row_middle = (row as f64 + 0.5) * row_height;
+    let max_scroll = (scroll_height - client_height).max(0.0);
+    (row_middle - client_height / 2.0).clamp(0.0, max_scroll)
+}
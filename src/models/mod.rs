@@ -1,3 +1,38 @@
+pub mod auto_archive;
+pub mod auto_tag;
+pub mod digest;
+pub mod due_limits;
+pub mod duplicates;
+pub mod escalation;
+pub mod follow_up;
+pub mod history;
+pub mod idle;
+pub mod import_plan;
+pub mod issue_link;
+pub mod karma;
+pub mod minimap;
+pub mod palette;
+pub mod postponed;
+pub mod query;
+pub mod quiet;
+pub mod reminder;
+pub mod retention;
+pub mod session;
+pub mod settings;
+pub mod staleness;
+pub mod tag_due;
+pub mod tag_suggestion;
+pub mod timeline;
 pub mod todo;
+pub mod view;
 
-pub use todo::{FilterState, Todo, TodoList};
+pub use auto_tag::{AutoTagRule, AutoTagger};
+pub use history::TodoHistory;
+pub use issue_link::{IssueLinkRule, IssueLinker};
+pub use query::SearchQuery;
+pub use settings::{Animations, AppSettings, AutosaveInterval, SplitView, WeekStart};
+pub use todo::{
+    ClearedTodos, FilterState, ListPatch, QuickFilter, ShiftedDueDates, SortMode, Subtask, Todo,
+    TodoError, TodoList,
+};
+pub use view::{FilterCriteria, ListView, TagFilter};
@@ -0,0 +1,217 @@
+//! Commands and search results of the quick switcher
+//!
+//! The quick switcher (Ctrl+K) searches todos, tags and commands at once.
+//! Commands come from a [`CommandRegistry`] that features add their actions
+//! to, so anything the app can do is one search away. [`rank`] scores every
+//! candidate with the same fuzzy matcher and interleaves the kinds by score.
+
+use crate::models::Todo;
+use crate::utils::text::fuzzy_score;
+use std::fmt;
+use std::rc::Rc;
+
+/// An action offered in the quick switcher.
+#[derive(Clone)]
+pub struct Command {
+    /// Stable name of the command, like `theme.toggle`
+    pub id: &'static str,
+    /// Title shown in the list and searched
+    pub title: String,
+    /// Other words the command is found by
+    pub keywords: Vec<String>,
+    /// Runs the command
+    pub handler: Rc<dyn Fn()>,
+}
+
+impl Command {
+    /// Creates a command without keywords.
+    pub fn new(id: &'static str, title: impl Into<String>, handler: impl Fn() + 'static) -> Self {
+        Self {
+            id,
+            title: title.into(),
+            keywords: Vec::new(),
+            handler: Rc::new(handler),
+        }
+    }
+
+    /// Adds words the command is also found by.
+    pub fn with_keywords(mut self, keywords: &[&str]) -> Self {
+        self.keywords
+            .extend(keywords.iter().map(|keyword| keyword.to_string()));
+        self
+    }
+
+    /// Runs the command.
+    pub fn run(&self) {
+        (self.handler)();
+    }
+
+    /// Scores the command against a query, taking the best of its title and
+    /// keywords; the title wins ties.
+    fn score(&self, query: &str) -> Option<i64> {
+        let title = fuzzy_score(query, &self.title);
+        // Keywords count a little less than the title
+        let keywords = self
+            .keywords
+            .iter()
+            .filter_map(|keyword| fuzzy_score(query, keyword))
+            .max()
+            .map(|score| score - 1);
+        title.max(keywords)
+    }
+}
+
+impl fmt::Debug for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Command")
+            .field("id", &self.id)
+            .field("title", &self.title)
+            .field("keywords", &self.keywords)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Command {
+    /// Commands are equal only when they run the same handler, so a registry
+    /// rebuilt with fresh handlers is seen as changed.
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && self.title == other.title
+            && self.keywords == other.keywords
+            && Rc::ptr_eq(&self.handler, &other.handler)
+    }
+}
+
+/// The commands offered in the quick switcher, in the order they were added.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CommandRegistry {
+    commands: Vec<Command>,
+}
+
+impl CommandRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a command, replacing one registered before with the same id.
+    pub fn register(&mut self, command: Command) {
+        match self.commands.iter_mut().find(|c| c.id == command.id) {
+            Some(existing) => *existing = command,
+            None => self.commands.push(command),
+        }
+    }
+
+    /// Gets a command by id.
+    pub fn get(&self, id: &str) -> Option<&Command> {
+        self.commands.iter().find(|command| command.id == id)
+    }
+
+    /// Runs a command by id.
+    ///
+    /// # Returns
+    /// Whether a command with that id was found
+    pub fn run(&self, id: &str) -> bool {
+        self.get(id).map(Command::run).is_some()
+    }
+
+    /// Lists the commands in the order they were added.
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+}
+
+/// A result of the quick switcher.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PaletteItem {
+    /// Runs the command with this id
+    Command(&'static str),
+    /// Filters the list by this tag
+    Tag(String),
+    /// Shows the todo with this id in the list
+    Todo(usize),
+}
+
+/// A result with its title and score.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaletteMatch {
+    pub item: PaletteItem,
+    /// Text shown for the result
+    pub title: String,
+    pub score: i64,
+}
+
+/// Scores the commands, tags and open todos against a query, best first.
+///
+/// Results with equal scores list commands first, then tags, then todos. An
+/// empty query lists the commands and tags but no todos, which would only
+/// repeat the list. Completed todos are left out.
+///
+/// # Arguments
+/// * `query` - Text typed in the switcher
+/// * `registry` - Commands on offer
+/// * `tags` - Tags to filter by
+/// * `todos` - Todos to jump to
+/// * `limit` - Most results returned
+pub fn rank<'a>(
+    query: &str,
+    registry: &CommandRegistry,
+    tags: &[String],
+    todos: impl IntoIterator<Item = &'a Todo>,
+    limit: usize,
+) -> Vec<PaletteMatch> {
+    let query = query.trim();
+    // The kind breaks ties, in the order results of equal score are listed
+    let mut matches: Vec<(PaletteMatch, u8)> = Vec::new();
+
+    for command in registry.commands() {
+        if let Some(score) = command.score(query) {
+            let item = PaletteItem::Command(command.id);
+            let title = command.title.clone();
+            matches.push((PaletteMatch { item, title, score }, 0));
+        }
+    }
+    for tag in tags {
+        // "#" may be typed before a tag
+        let tag_query = query.strip_prefix('#').unwrap_or(query);
+        if let Some(score) = fuzzy_score(tag_query, tag) {
+            let item = PaletteItem::Tag(tag.clone());
+            let title = format!("#{}", tag);
+            matches.push((PaletteMatch { item, title, score }, 1));
+        }
+    }
+    if !query.is_empty() {
+        for todo in todos.into_iter().filter(|todo| !todo.completed) {
+            if let Some(score) = fuzzy_score(query, &todo.text) {
+                let item = PaletteItem::Todo(todo.id);
+                let title = todo.text.clone();
+                matches.push((PaletteMatch { item, title, score }, 2));
+            }
+        }
+    }
+
+    // Stable, so commands keep their registration order among equals
+    matches.sort_by(|(a, a_kind), (b, b_kind)| b.score.cmp(&a.score).then(a_kind.cmp(b_kind)));
+    matches
+        .into_iter()
+        .take(limit)
+        .map(|(found, _)| found)
+        .collect()
+}
+
+/// Moves the highlighted result up or down, wrapping around the ends.
+///
+/// # Example
+/// ```
+/// use todo::models::palette::step_highlight;
+///
+/// assert_eq!(step_highlight(0, -1, 3), 2);
+/// assert_eq!(step_highlight(2, 1, 3), 0);
+/// assert_eq!(step_highlight(0, 1, 0), 0);
+/// ```
+pub fn step_highlight(highlighted: usize, step: isize, count: usize) -> usize {
+    if count == 0 {
+        return 0;
+    }
+    (highlighted as isize + step).rem_euclid(count as isize) as usize
+}
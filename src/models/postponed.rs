@@ -0,0 +1,47 @@
+//! Open todos whose due date keeps being pushed back
+//!
+//! Each time a todo's due date moves later its postponed count goes up; see
+//! [`Todo::set_due_date`]. The todos postponed most often are worth a second
+//! look: they may need splitting up, handing off or dropping.
+
+use super::Todo;
+use crate::utils::constants::todo::MOST_POSTPONED_LIMIT;
+
+/// Gets the open todos postponed most often, most postponed first.
+///
+/// Todos never postponed are left out, as are completed todos and todos in
+/// the trash. Todos postponed equally often keep their list order. At most
+/// [`MOST_POSTPONED_LIMIT`] are returned.
+///
+/// # Example
+/// ```
+/// use chrono::{Duration, Utc};
+/// use todo::models::TodoList;
+/// use todo::models::postponed::most_postponed;
+///
+/// let mut list = TodoList::new();
+/// let id = list.add("File taxes".to_string());
+/// list.add("Water plants".to_string());
+/// let due = Utc::now();
+/// for days in 0..3 {
+///     list.set_due_date(id, Some(due + Duration::days(days))).unwrap();
+/// }
+///
+/// let todos = list.all();
+/// let postponed = most_postponed(&todos);
+/// assert_eq!(postponed.len(), 1);
+/// assert_eq!((postponed[0].id, postponed[0].postponed_count), (id, 2));
+/// ```
+pub fn most_postponed<'a>(todos: impl IntoIterator<Item = &'a Todo>) -> Vec<&'a Todo> {
+    let mut postponed: Vec<&Todo> = todos
+        .into_iter()
+        .filter(|todo| todo.postponed_count > 0 && !todo.completed && todo.deleted_at.is_none())
+        .collect();
+    postponed.sort_by(|a, b| {
+        b.postponed_count
+            .cmp(&a.postponed_count)
+            .then_with(|| a.order.cmp(&b.order))
+    });
+    postponed.truncate(MOST_POSTPONED_LIMIT);
+    postponed
+}
@@ -0,0 +1,140 @@
+//! Structured search queries typed into the search box
+//!
+//! A query is free text mixed with `operator:value` tokens. Supported
+//! operators:
+//!
+//! * `completed:<7d` - completed within the last 7 days (`d` days, `w` weeks,
+//!   `m` months of 30 days)
+//! * `deleted:any` - search the trash instead of the list
+//! * `is:new` - todos added since the app was started
+//! * `is:stale` - open todos left unchanged for the stale threshold in settings
+//! * `week:2025-W12` - due in that ISO week, Monday to Sunday
+//!
+//! Tokens that don't form a valid operator are searched for as plain text,
+//! so something like `note:` or `completed:soon` still finds matching todos.
+
+use super::Todo;
+use crate::utils::dates::{self, IsoWeek};
+use chrono::{DateTime, Duration, Utc};
+
+/// A parsed search query.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchQuery {
+    /// Free text to look for in the text, tags and notes, lowercased
+    pub text: String,
+    /// Only match todos completed within this long before now
+    pub completed_within: Option<Duration>,
+    /// Whether the query searches the trash instead of the list
+    pub deleted: bool,
+    /// Only match todos due in this ISO week
+    pub due_week: Option<IsoWeek>,
+    /// Only match stale todos; the threshold comes from settings, so
+    /// [`ListView`](super::ListView) checks it
+    pub stale: bool,
+    /// Only match todos added this session; the session start is known to
+    /// the app, so [`ListView`](super::ListView) checks it
+    pub added_this_session: bool,
+}
+
+impl SearchQuery {
+    /// Parses the text of the search box.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::Duration;
+    /// use todo::models::SearchQuery;
+    ///
+    /// let query = SearchQuery::parse("report completed:<2w");
+    /// assert_eq!(query.text, "report");
+    /// assert_eq!(query.completed_within, Some(Duration::weeks(2)));
+    /// assert!(!query.deleted);
+    /// ```
+    pub fn parse(input: &str) -> Self {
+        let mut query = Self::default();
+        let mut words = Vec::new();
+
+        for token in input.split_whitespace() {
+            let operator = token.split_once(':').and_then(|(name, value)| {
+                match name.to_ascii_lowercase().as_str() {
+                    "completed" => value
+                        .strip_prefix('<')
+                        .and_then(parse_duration)
+                        .map(QueryOperator::CompletedWithin),
+                    "deleted" if value.eq_ignore_ascii_case("any") => Some(QueryOperator::Deleted),
+                    "week" => IsoWeek::parse(value).map(QueryOperator::DueWeek),
+                    "is" if value.eq_ignore_ascii_case("stale") => Some(QueryOperator::Stale),
+                    "is" if value.eq_ignore_ascii_case("new") => {
+                        Some(QueryOperator::AddedThisSession)
+                    }
+                    _ => None,
+                }
+            });
+
+            match operator {
+                Some(QueryOperator::CompletedWithin(duration)) => {
+                    query.completed_within = Some(duration)
+                }
+                Some(QueryOperator::Deleted) => query.deleted = true,
+                Some(QueryOperator::DueWeek(week)) => query.due_week = Some(week),
+                Some(QueryOperator::Stale) => query.stale = true,
+                Some(QueryOperator::AddedThisSession) => query.added_this_session = true,
+                None => words.push(token.to_lowercase()),
+            }
+        }
+
+        query.text = words.join(" ");
+        query
+    }
+
+    /// Checks if a todo matches the query at the given time.
+    ///
+    /// Whether the todo is in the trash is up to the caller, which picks the
+    /// todos to search based on [`SearchQuery::deleted`].
+    pub fn matches(&self, todo: &Todo, now: DateTime<Utc>) -> bool {
+        let completed_match = match self.completed_within {
+            Some(duration) => todo
+                .completed_at
+                .is_some_and(|completed_at| todo.completed && now - completed_at <= duration),
+            None => true,
+        };
+        let week_match = match self.due_week {
+            Some(week) => todo
+                .due_date
+                .is_some_and(|due| week.contains(dates::due_day(due))),
+            None => true,
+        };
+
+        completed_match && week_match && todo.matches_search(&self.text)
+    }
+}
+
+/// An operator token recognized in a query.
+enum QueryOperator {
+    CompletedWithin(Duration),
+    Deleted,
+    DueWeek(IsoWeek),
+    Stale,
+    AddedThisSession,
+}
+
+/// Parses a duration such as `7d`, `2w` or `3m`.
+///
+/// # Arguments
+/// * `value` - A whole number followed by `d` (days), `w` (weeks) or `m` (months of 30 days)
+///
+/// # Returns
+/// The duration, or `None` if the value is not in that form
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let unit = value.chars().last()?;
+    let amount: i64 = value[..value.len() - unit.len_utf8()].parse().ok()?;
+    if amount < 0 {
+        return None;
+    }
+
+    match unit.to_ascii_lowercase() {
+        'd' => Duration::try_days(amount),
+        'w' => Duration::try_weeks(amount),
+        'm' => Duration::try_days(amount.checked_mul(30)?),
+        _ => None,
+    }
+}
@@ -0,0 +1,101 @@
+//! Do not disturb
+//!
+//! While quiet mode is on, the app keeps to itself: no reminder banners,
+//! system notifications, daily digest, completion sounds or badge counts.
+//! Features ask [`QuietMode::notifications_allowed`] instead of checking
+//! their own settings for it. The end of the quiet period is stored as an
+//! instant, so it survives restarts and simply runs out.
+
+use crate::utils::clock::Clock;
+use crate::utils::constants::todo::QUIET_UNTIL_TOMORROW_HOUR;
+use chrono::{DateTime, Duration, NaiveDateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How long to stay quiet, as picked in the header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuietFor {
+    /// One hour from now
+    OneHour,
+    /// Until [`QUIET_UNTIL_TOMORROW_HOUR`] tomorrow, local time
+    UntilTomorrow,
+    /// Until a local wall-clock time
+    Until(NaiveDateTime),
+}
+
+impl QuietFor {
+    /// Works out when the quiet period ends.
+    ///
+    /// # Returns
+    /// The end, or `None` for a custom time that has already passed
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, NaiveDate};
+    /// use todo::models::quiet::QuietFor;
+    /// use todo::utils::clock::{Clock, FixedClock};
+    ///
+    /// // 22:30 in a time zone two hours east of UTC
+    /// let clock = FixedClock::at_local(NaiveDate::from_ymd_opt(2025, 3, 12).unwrap(), 22, 30, 2);
+    /// assert_eq!(QuietFor::OneHour.end(&clock), Some(clock.now_utc() + Duration::hours(1)));
+    ///
+    /// // 8:00 local the next morning is 6:00 UTC
+    /// let end = QuietFor::UntilTomorrow.end(&clock).unwrap();
+    /// assert_eq!(end.to_rfc3339(), "2025-03-13T06:00:00+00:00");
+    /// ```
+    pub fn end(self, clock: &dyn Clock) -> Option<DateTime<Utc>> {
+        let now = clock.now_utc();
+        // Local times are turned into instants with the clock's current offset
+        let offset = clock.now_local() - now.naive_utc();
+        let local_end = match self {
+            QuietFor::OneHour => return Some(now + Duration::hours(1)),
+            QuietFor::UntilTomorrow => {
+                let tomorrow = clock.today_local().succ_opt()?;
+                tomorrow.and_time(NaiveTime::from_hms_opt(QUIET_UNTIL_TOMORROW_HOUR, 0, 0)?)
+            }
+            QuietFor::Until(local) => local,
+        };
+        let end = (local_end - offset).and_utc();
+        (end > now).then_some(end)
+    }
+}
+
+/// Whether quiet mode is on, and until when.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietMode {
+    /// End of the quiet period, `None` when it was never turned on or was turned off
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl QuietMode {
+    /// Starts a quiet period.
+    ///
+    /// # Returns
+    /// Quiet mode until the end of the period, or off if it would end in the past
+    pub fn start(quiet_for: QuietFor, clock: &dyn Clock) -> Self {
+        Self {
+            until: quiet_for.end(clock),
+        }
+    }
+
+    /// Gets the end of the quiet period, if it is still running.
+    pub fn active_until(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.until.filter(|until| now < *until)
+    }
+
+    /// Checks if reminders, notifications, sounds and badges may be shown.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use todo::models::quiet::QuietMode;
+    ///
+    /// let now = Utc::now();
+    /// let quiet = QuietMode { until: Some(now + Duration::minutes(5)) };
+    /// assert!(!quiet.notifications_allowed(now));
+    /// assert!(quiet.notifications_allowed(now + Duration::minutes(5)));
+    /// assert!(QuietMode::default().notifications_allowed(now));
+    /// ```
+    pub fn notifications_allowed(&self, now: DateTime<Utc>) -> bool {
+        self.active_until(now).is_none()
+    }
+}
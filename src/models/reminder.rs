@@ -0,0 +1,53 @@
+//! When a due todo is next reminded of
+//!
+//! A todo is reminded of the configured lead time before it is due. Snoozing
+//! its reminder puts it off until a later time, kept with the todos so it
+//! survives a restart; a snooze that has run out changes nothing, and one set
+//! before the reminder would come anyway is simply overtaken by it.
+
+use super::Todo;
+use crate::utils::constants::todo::REMINDER_SNOOZE_MINUTES;
+use chrono::{DateTime, Duration, Utc};
+
+/// Gets when a todo is next reminded of.
+///
+/// # Arguments
+/// * `todo` - The todo
+/// * `snoozed_until` - When its reminder was snoozed until, if it was
+/// * `lead` - How long before the due date a todo should be reminded of
+///
+/// # Returns
+/// The reminder time, the later of the lead time before the due date and the
+/// end of the snooze, or `None` for todos that are done, archived or undated
+///
+/// # Example
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use todo::models::Todo;
+/// use todo::models::reminder::next_reminder;
+///
+/// let due = Utc.with_ymd_and_hms(2025, 3, 12, 17, 0, 0).unwrap();
+/// let mut todo = Todo::new(1, "Send the invoice".to_string());
+/// todo.due_date = Some(due);
+///
+/// let lead = Duration::hours(2);
+/// assert_eq!(next_reminder(&todo, None, lead), Some(due - lead));
+/// assert_eq!(next_reminder(&todo, Some(due), lead), Some(due));
+/// ```
+pub fn next_reminder(
+    todo: &Todo,
+    snoozed_until: Option<DateTime<Utc>>,
+    lead: Duration,
+) -> Option<DateTime<Utc>> {
+    if todo.completed || todo.archived {
+        return None;
+    }
+    let remind_at = todo.due_date? - lead;
+    Some(snoozed_until.map_or(remind_at, |until| until.max(remind_at)))
+}
+
+/// Gets when a reminder snoozed now comes back, [`REMINDER_SNOOZE_MINUTES`]
+/// later.
+pub fn snooze_end(now: DateTime<Utc>) -> DateTime<Utc> {
+    now + Duration::minutes(REMINDER_SNOOZE_MINUTES)
+}
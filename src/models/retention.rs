@@ -0,0 +1,114 @@
+//! Policies deleting old todos from the trash and the archive for good
+//!
+//! Todos in the trash are deleted once they have been there longer than the
+//! trash retention, and archived todos once they have been archived longer
+//! than the archive retention; either can keep todos forever. Locked todos
+//! and todos trashed or archived before those times were recorded are kept.
+//!
+//! Selecting is kept apart from deleting, so the policies can be checked
+//! against any date without touching the list.
+
+use super::{AppSettings, TodoList};
+use chrono::{DateTime, Duration, Utc};
+
+/// How long the trash and the archive keep todos.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Days a todo stays in the trash; `None` keeps it forever
+    pub trash_days: Option<u32>,
+    /// Days a todo stays archived; `None` keeps it forever
+    pub archive_days: Option<u32>,
+}
+
+impl RetentionPolicy {
+    /// Gets the policy set in the settings.
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            trash_days: settings.trash_retention_days,
+            archive_days: settings.archive_retention_days,
+        }
+    }
+}
+
+/// Todos past their retention, to be deleted for good.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Purgeable {
+    /// Ids of todos in the trash
+    pub trashed: Vec<usize>,
+    /// Ids of archived todos
+    pub archived: Vec<usize>,
+}
+
+impl Purgeable {
+    /// Whether no todo is past its retention.
+    pub fn is_empty(&self) -> bool {
+        self.trashed.is_empty() && self.archived.is_empty()
+    }
+
+    /// Deletes the todos for good.
+    ///
+    /// # Returns
+    /// The number of todos deleted
+    pub fn apply(&self, list: &mut TodoList) -> usize {
+        let trashed = self.trashed.iter().filter(|id| list.purge(**id)).count();
+        trashed + list.purge_archived(&self.archived)
+    }
+}
+
+/// Picks the todos the retention policy would delete.
+///
+/// # Arguments
+/// * `list` - The todos to look at
+/// * `policy` - How long the trash and the archive keep todos
+/// * `now` - The current time
+///
+/// # Returns
+/// The ids of the todos in the trash, oldest deletion first, and of the
+/// archived todos, in list order
+///
+/// # Example
+/// ```
+/// use chrono::{Duration, Utc};
+/// use todo::models::TodoList;
+/// use todo::models::retention::{RetentionPolicy, select_purgeable};
+///
+/// let mut list = TodoList::new();
+/// let id = list.add("Old idea".to_string());
+/// list.move_to_trash(id).unwrap();
+/// let policy = RetentionPolicy { trash_days: Some(30), archive_days: None };
+///
+/// let later = Utc::now() + Duration::days(31);
+/// assert_eq!(select_purgeable(&list, policy, later).trashed, vec![id]);
+/// assert!(select_purgeable(&list, policy, Utc::now()).is_empty());
+/// ```
+pub fn select_purgeable(list: &TodoList, policy: RetentionPolicy, now: DateTime<Utc>) -> Purgeable {
+    let cutoff = |days: u32| now - Duration::days(days.into());
+
+    let trashed = policy.trash_days.map_or_else(Vec::new, |days| {
+        let cutoff = cutoff(days);
+        list.trashed()
+            .into_iter()
+            .rev()
+            .filter(|todo| {
+                todo.deleted_at
+                    .is_some_and(|deleted_at| deleted_at < cutoff)
+            })
+            .map(|todo| todo.id)
+            .collect()
+    });
+    let archived = policy.archive_days.map_or_else(Vec::new, |days| {
+        let cutoff = cutoff(days);
+        list.archived()
+            .into_iter()
+            .filter(|todo| {
+                !todo.locked
+                    && todo
+                        .archived_at
+                        .is_some_and(|archived_at| archived_at < cutoff)
+            })
+            .map(|todo| todo.id)
+            .collect()
+    });
+
+    Purgeable { trashed, archived }
+}
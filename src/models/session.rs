@@ -0,0 +1,34 @@
+//! Todos added since the app was started
+//!
+//! Each launch of the app starts a session at the time the clock gave on the
+//! first render. Todos whose `created_at` falls at or after that moment were
+//! added this session, which lets a brain dump be triaged right after it was
+//! typed in. Nothing about the session is stored, so a restart ends it.
+
+use super::Todo;
+use chrono::{DateTime, Utc};
+
+/// Checks if a todo was added this session.
+///
+/// Todos added before creation times were recorded never count as new.
+///
+/// # Arguments
+/// * `todo` - The todo to check
+/// * `session_start` - When the app was started
+///
+/// # Example
+/// ```
+/// use chrono::{Duration, Utc};
+/// use todo::models::TodoList;
+/// use todo::models::session::is_added_since;
+///
+/// let started = Utc::now();
+/// let mut list = TodoList::new();
+/// let id = list.add("Sort the inbox".to_string());
+/// assert!(is_added_since(list.get(id).unwrap(), started));
+/// assert!(!is_added_since(list.get(id).unwrap(), Utc::now() + Duration::minutes(1)));
+/// ```
+pub fn is_added_since(todo: &Todo, session_start: DateTime<Utc>) -> bool {
+    todo.created_at
+        .is_some_and(|created_at| created_at >= session_start)
+}
@@ -0,0 +1,381 @@
+use super::tag_due::TagDueRule;
+use super::{AutoTagRule, IssueLinkRule};
+use crate::utils::constants::markdown_sync::DEFAULT_SYNC_TAG;
+use crate::utils::constants::todo::{
+    DEFAULT_DIGEST_TIME, DEFAULT_FOLLOW_UP_AFTER_DAYS, DEFAULT_FOLLOW_UP_TAG,
+    DEFAULT_REMINDER_LEAD_HOURS, DEFAULT_STALE_AFTER_DAYS, DEFAULT_TODO_COUNT_WARNING,
+    DEFAULT_TRASH_RETENTION_DAYS, DEFAULT_WEEKLY_GOAL,
+};
+use crate::utils::constants::ui::SPLIT_VIEW_MIN_WIDTH;
+use chrono::{DateTime, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+/// First day of the week used for week ranges and calendars.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WeekStart {
+    #[default]
+    Monday,
+    Sunday,
+    Saturday,
+}
+
+impl WeekStart {
+    /// All supported week starts, in the order they are offered in settings.
+    pub const ALL: [WeekStart; 3] = [WeekStart::Monday, WeekStart::Sunday, WeekStart::Saturday];
+
+    /// Gets the weekday the week starts on.
+    #[allow(dead_code)]
+    pub fn weekday(self) -> Weekday {
+        match self {
+            WeekStart::Monday => Weekday::Mon,
+            WeekStart::Sunday => Weekday::Sun,
+            WeekStart::Saturday => Weekday::Sat,
+        }
+    }
+
+    /// Gets the name shown in settings.
+    pub fn label(self) -> &'static str {
+        match self {
+            WeekStart::Monday => "Monday",
+            WeekStart::Sunday => "Sunday",
+            WeekStart::Saturday => "Saturday",
+        }
+    }
+
+    /// Parses a name as returned by [`WeekStart::label`], ignoring case.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|start| start.label().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// How soon changes to the todos are written to storage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AutosaveInterval {
+    #[default]
+    Immediate,
+    OneSecond,
+    FiveSeconds,
+    /// Only saved with "Save now" or Ctrl+S
+    Manual,
+}
+
+impl AutosaveInterval {
+    /// All supported intervals, in the order they are offered in settings.
+    pub const ALL: [AutosaveInterval; 4] = [
+        AutosaveInterval::Immediate,
+        AutosaveInterval::OneSecond,
+        AutosaveInterval::FiveSeconds,
+        AutosaveInterval::Manual,
+    ];
+
+    /// Gets how long to wait after the last change before saving.
+    ///
+    /// # Returns
+    /// The delay, or `None` when changes are only saved manually
+    pub fn delay(self) -> Option<Duration> {
+        match self {
+            AutosaveInterval::Immediate => Some(Duration::ZERO),
+            AutosaveInterval::OneSecond => Some(Duration::from_secs(1)),
+            AutosaveInterval::FiveSeconds => Some(Duration::from_secs(5)),
+            AutosaveInterval::Manual => None,
+        }
+    }
+
+    /// Gets the name shown in settings.
+    pub fn label(self) -> &'static str {
+        match self {
+            AutosaveInterval::Immediate => "Immediately",
+            AutosaveInterval::OneSecond => "After 1 second",
+            AutosaveInterval::FiveSeconds => "After 5 seconds",
+            AutosaveInterval::Manual => "Manually",
+        }
+    }
+
+    /// Parses a name as returned by [`AutosaveInterval::label`], ignoring case.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|interval| interval.label().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// When the list and the detail panel are shown side by side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SplitView {
+    /// When the window is at least [`SPLIT_VIEW_MIN_WIDTH`] wide
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl SplitView {
+    /// All supported choices, in the order they are offered in settings.
+    pub const ALL: [SplitView; 3] = [SplitView::Auto, SplitView::Always, SplitView::Never];
+
+    /// Checks if the detail panel is docked beside the list.
+    ///
+    /// # Arguments
+    /// * `window_width` - The window width in CSS pixels, `None` until it is known
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::SplitView;
+    ///
+    /// assert!(SplitView::Auto.is_split(Some(1440.0)));
+    /// assert!(!SplitView::Auto.is_split(Some(800.0)));
+    /// assert!(SplitView::Always.is_split(None));
+    /// ```
+    pub fn is_split(self, window_width: Option<f64>) -> bool {
+        match self {
+            SplitView::Auto => window_width.is_some_and(|width| width >= SPLIT_VIEW_MIN_WIDTH),
+            SplitView::Always => true,
+            SplitView::Never => false,
+        }
+    }
+
+    /// Gets the name shown in settings.
+    pub fn label(self) -> &'static str {
+        match self {
+            SplitView::Auto => "On wide windows",
+            SplitView::Always => "Always",
+            SplitView::Never => "Never",
+        }
+    }
+
+    /// Parses a name as returned by [`SplitView::label`], ignoring case.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|split| split.label().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// Whether transitions and animations play.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Animations {
+    /// Unless the system asks for reduced motion
+    #[default]
+    System,
+    On,
+    Off,
+}
+
+impl Animations {
+    /// All supported choices, in the order they are offered in settings.
+    pub const ALL: [Animations; 3] = [Animations::System, Animations::On, Animations::Off];
+
+    /// Checks if animations play.
+    ///
+    /// # Arguments
+    /// * `prefers_reduced_motion` - Whether the system asks for reduced motion
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::Animations;
+    ///
+    /// assert!(Animations::System.is_enabled(false));
+    /// assert!(!Animations::System.is_enabled(true));
+    /// assert!(Animations::On.is_enabled(true));
+    /// ```
+    pub fn is_enabled(self, prefers_reduced_motion: bool) -> bool {
+        match self {
+            Animations::System => !prefers_reduced_motion,
+            Animations::On => true,
+            Animations::Off => false,
+        }
+    }
+
+    /// Gets the name shown in settings.
+    pub fn label(self) -> &'static str {
+        match self {
+            Animations::System => "Follow the system",
+            Animations::On => "On",
+            Animations::Off => "Off",
+        }
+    }
+
+    /// Parses a name as returned by [`Animations::label`], ignoring case.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|animations| animations.label().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// Connection to a CalDAV task collection the todos are synced with.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalDavSettings {
+    /// Whether the todos are synced in the background
+    pub enabled: bool,
+    /// URL of the task collection, like `https://cloud.example.com/remote.php/dav/calendars/me/tasks/`
+    pub url: String,
+    /// Account name on the server
+    pub username: String,
+    /// App password created for this app on the server, not the account password
+    pub app_password: String,
+    /// When the last sync finished; `None` until the first sync, which is previewed
+    pub last_synced_at: Option<DateTime<Utc>>,
+}
+
+impl CalDavSettings {
+    /// Checks if a URL and username are filled in.
+    #[allow(dead_code)]
+    pub fn is_configured(&self) -> bool {
+        !self.url.trim().is_empty() && !self.username.trim().is_empty()
+    }
+}
+
+/// A Markdown checklist file the todos are synced with.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarkdownSyncSettings {
+    /// Whether the file is watched and written
+    pub enabled: bool,
+    /// Full path of the file, like `/home/me/notes/TODO.md`
+    pub path: String,
+    /// Tag marking the todos that are kept in the file
+    pub tag: String,
+}
+
+impl Default for MarkdownSyncSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: String::new(),
+            tag: DEFAULT_SYNC_TAG.to_string(),
+        }
+    }
+}
+
+impl MarkdownSyncSettings {
+    /// Checks if sync is on with a file and tag filled in.
+    #[allow(dead_code)]
+    pub fn is_active(&self) -> bool {
+        self.enabled && !self.path.trim().is_empty() && !self.tag.trim().is_empty()
+    }
+}
+
+/// User preferences, stored separately from the todos.
+///
+/// Missing fields fall back to their defaults, so settings saved by older
+/// versions keep loading as new options are added.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// First day of the week
+    pub week_start: WeekStart,
+    /// Number of todos above which a warning suggests archiving or exporting
+    pub todo_count_warning: usize,
+    /// How soon changes are saved
+    pub autosave: AutosaveInterval,
+    /// Whether shortcodes like `:rocket:` become emoji when a todo is saved
+    pub expand_emoji: bool,
+    /// How many hours before its due date a todo is listed as due soon
+    pub reminder_lead_hours: u32,
+    /// Rules tagging todos automatically when they are added or edited
+    pub auto_tag_rules: Vec<AutoTagRule>,
+    /// Rules giving todos a due date when a tag is added, if they have none
+    pub tag_due_rules: Vec<TagDueRule>,
+    /// Rules linking issue-tracker references in todo text, like `ABC-123`
+    pub issue_link_rules: Vec<IssueLinkRule>,
+    /// Whether due dates show their ISO week number, like "Mar 18 (W12)"
+    pub show_week_numbers: bool,
+    /// Whether long lists show a strip of due-date marks beside them
+    pub show_minimap: bool,
+    /// Whether unlocking a todo asks for confirmation first
+    pub confirm_unlock: bool,
+    /// Days after completion that completed todos are archived; `None` keeps them
+    pub auto_archive_after_days: Option<u32>,
+    /// Whether open todos past their due date are made high priority, once
+    pub escalate_overdue: bool,
+    /// Days an open todo without a due date waits before it is tagged for
+    /// review; `None` never tags it
+    pub review_undated_after_days: Option<u32>,
+    /// Days todos stay in the trash before they are deleted for good; `None`
+    /// keeps them
+    pub trash_retention_days: Option<u32>,
+    /// Days archived todos are kept before they are deleted for good; `None`
+    /// keeps them
+    pub archive_retention_days: Option<u32>,
+    /// App version at the last visit, used to show what is new after an upgrade
+    pub last_seen_version: Option<String>,
+    /// Whether completing a todo plays a short tick sound
+    pub completion_sound: bool,
+    /// Whether open todos left unchanged for a while show an aging clock
+    pub show_stale: bool,
+    /// Days without changes after which an open todo counts as stale
+    pub stale_after_days: u32,
+    /// When the detail panel is docked beside the list
+    pub split_view: SplitView,
+    /// Whether transitions and animations play
+    pub animations: Animations,
+    /// Tag of todos waiting on someone else; empty turns follow-up prompts off
+    pub follow_up_tag: String,
+    /// Days a waiting todo goes unchanged before it is listed for follow-up
+    pub follow_up_after_days: u32,
+    /// Whether bumping a waiting todo adds a dated line to its notes
+    pub follow_up_note: bool,
+    /// Whether karma points and the weekly goal are shown
+    pub show_karma: bool,
+    /// Todos to complete each week
+    pub weekly_goal: usize,
+    /// Whether the desktop app shows a notification of overdue todos and todos due today every morning
+    pub daily_digest: bool,
+    /// Local time of the daily digest
+    pub digest_time: NaiveTime,
+    /// Whether the daily digest is skipped on Saturdays and Sundays
+    pub digest_skip_weekends: bool,
+    /// Sync with a CalDAV task collection
+    pub caldav: CalDavSettings,
+    /// Sync with a Markdown checklist file
+    pub markdown_sync: MarkdownSyncSettings,
+    /// Tags no longer offered when tagging a todo, though todos keep them
+    pub suppressed_suggestions: BTreeSet<String>,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            week_start: WeekStart::default(),
+            todo_count_warning: DEFAULT_TODO_COUNT_WARNING,
+            autosave: AutosaveInterval::default(),
+            expand_emoji: true,
+            reminder_lead_hours: DEFAULT_REMINDER_LEAD_HOURS,
+            auto_tag_rules: Vec::new(),
+            tag_due_rules: Vec::new(),
+            issue_link_rules: Vec::new(),
+            show_week_numbers: false,
+            show_minimap: true,
+            confirm_unlock: true,
+            auto_archive_after_days: None,
+            escalate_overdue: false,
+            review_undated_after_days: None,
+            trash_retention_days: Some(DEFAULT_TRASH_RETENTION_DAYS),
+            archive_retention_days: None,
+            last_seen_version: None,
+            completion_sound: false,
+            show_stale: false,
+            stale_after_days: DEFAULT_STALE_AFTER_DAYS,
+            split_view: SplitView::default(),
+            animations: Animations::default(),
+            follow_up_tag: DEFAULT_FOLLOW_UP_TAG.to_string(),
+            follow_up_after_days: DEFAULT_FOLLOW_UP_AFTER_DAYS,
+            follow_up_note: true,
+            show_karma: true,
+            weekly_goal: DEFAULT_WEEKLY_GOAL,
+            daily_digest: true,
+            digest_time: NaiveTime::from_hms_opt(DEFAULT_DIGEST_TIME.0, DEFAULT_DIGEST_TIME.1, 0)
+                .expect("the default digest time is valid"),
+            digest_skip_weekends: false,
+            caldav: CalDavSettings::default(),
+            markdown_sync: MarkdownSyncSettings::default(),
+            suppressed_suggestions: BTreeSet::new(),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Open todos that have sat untouched for a while
+//!
+//! A todo's age counts from the last change to its text, completion, due
+//! date or tags, or from when it was added if it never changed. Completed
+//! todos, todos in the trash and todos added before these times were
+//! recorded have no age and are never stale.
+
+use super::Todo;
+use chrono::{DateTime, Duration, Utc};
+
+/// Gets how long an open todo has gone without changes.
+///
+/// # Arguments
+/// * `todo` - The todo to look at
+/// * `now` - The current time
+///
+/// # Returns
+/// The time since the todo last changed, or `None` if it is completed, in
+/// the trash or its age is unknown
+///
+/// # Example
+/// ```
+/// use chrono::{Duration, Utc};
+/// use todo::models::TodoList;
+/// use todo::models::staleness::staleness;
+///
+/// let mut list = TodoList::new();
+/// let id = list.add("Call the plumber".to_string());
+/// let later = Utc::now() + Duration::days(23);
+/// assert_eq!(staleness(list.get(id).unwrap(), later).unwrap().num_days(), 23);
+///
+/// list.toggle(id);
+/// assert_eq!(staleness(list.get(id).unwrap(), later), None);
+/// ```
+pub fn staleness(todo: &Todo, now: DateTime<Utc>) -> Option<Duration> {
+    if todo.completed || todo.deleted_at.is_some() {
+        return None;
+    }
+    let since = todo.updated_at.or(todo.created_at)?;
+    Some((now - since).max(Duration::zero()))
+}
+
+/// Checks if an open todo has gone unchanged for at least the given number of days.
+///
+/// # Arguments
+/// * `todo` - The todo to look at
+/// * `now` - The current time
+/// * `days` - How many days without changes make a todo stale
+pub fn is_stale(todo: &Todo, now: DateTime<Utc>, days: u32) -> bool {
+    staleness(todo, now).is_some_and(|age| age >= Duration::days(days.into()))
+}
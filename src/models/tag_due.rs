@@ -0,0 +1,135 @@
+//! Default due dates for tagged todos
+//!
+//! A rule pairs a tag with a due date relative to the day the tag is added,
+//! like "Urgent → tomorrow" or "Groceries → Saturday". Rules only fill in a
+//! missing due date; a date the todo already has is never replaced. When
+//! several of a todo's tags have rules, the earliest date wins.
+
+use super::Todo;
+use crate::utils::dates;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A due date relative to today.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RelativeDue {
+    /// This many days from today; 0 is today
+    InDays(u32),
+    /// The next day that is this weekday, today included
+    Weekday(Weekday),
+}
+
+impl Default for RelativeDue {
+    fn default() -> Self {
+        RelativeDue::InDays(1)
+    }
+}
+
+impl RelativeDue {
+    /// Gets the day this resolves to.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{NaiveDate, Weekday};
+    /// use todo::models::tag_due::RelativeDue;
+    ///
+    /// // Wednesday 12 March 2025
+    /// let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+    /// assert_eq!(RelativeDue::InDays(1).resolve(today).to_string(), "2025-03-13");
+    /// assert_eq!(RelativeDue::Weekday(Weekday::Sat).resolve(today).to_string(), "2025-03-15");
+    /// assert_eq!(RelativeDue::Weekday(Weekday::Wed).resolve(today), today);
+    /// ```
+    pub fn resolve(self, today: NaiveDate) -> NaiveDate {
+        match self {
+            RelativeDue::InDays(days) => today + Duration::days(i64::from(days)),
+            RelativeDue::Weekday(day) => {
+                let ahead =
+                    (7 + day.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+                today + Duration::days(i64::from(ahead))
+            }
+        }
+    }
+}
+
+impl fmt::Display for RelativeDue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelativeDue::InDays(0) => write!(f, "today"),
+            RelativeDue::InDays(1) => write!(f, "tomorrow"),
+            RelativeDue::InDays(days) => write!(f, "in {} days", days),
+            RelativeDue::Weekday(day) => write!(f, "{}", weekday_name(*day)),
+        }
+    }
+}
+
+/// Gets the full English name of a weekday, like "Saturday".
+pub fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+/// A rule giving todos with a tag a default due date.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagDueRule {
+    /// The tag, matched ignoring case
+    pub tag: String,
+    /// When todos with the tag are due
+    pub due: RelativeDue,
+}
+
+impl TagDueRule {
+    /// Checks if the rule is for a tag, ignoring case and surrounding spaces.
+    fn matches(&self, tag: &str) -> bool {
+        let own = self.tag.trim();
+        !own.is_empty() && own.to_lowercase() == tag.trim().to_lowercase()
+    }
+}
+
+/// Works out the due date the rules give a todo.
+///
+/// # Arguments
+/// * `todo` - The todo, with the tag already added
+/// * `rules` - The rules from the settings
+/// * `today` - The local day the tag is added
+///
+/// # Returns
+/// The earliest date among the rules for the todo's tags, or `None` if the
+/// todo already has a due date or none of its tags has a rule
+///
+/// # Example
+/// ```
+/// use chrono::{NaiveDate, Weekday};
+/// use todo::models::Todo;
+/// use todo::models::tag_due::{RelativeDue, TagDueRule, apply_tag_rules};
+///
+/// let rules = [TagDueRule { tag: "Groceries".to_string(), due: RelativeDue::Weekday(Weekday::Sat) }];
+/// let mut todo = Todo::new(1, "Buy eggs".to_string());
+/// todo.tags.push("groceries".to_string());
+///
+/// let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+/// let due = apply_tag_rules(&todo, &rules, today).unwrap();
+/// assert_eq!(due.date_naive().to_string(), "2025-03-15");
+/// ```
+pub fn apply_tag_rules(
+    todo: &Todo,
+    rules: &[TagDueRule],
+    today: NaiveDate,
+) -> Option<DateTime<Utc>> {
+    if todo.due_date.is_some() {
+        return None;
+    }
+    rules
+        .iter()
+        .filter(|rule| todo.tags.iter().any(|tag| rule.matches(tag)))
+        .map(|rule| rule.due.resolve(today))
+        .min()
+        .map(dates::due_date_on)
+}
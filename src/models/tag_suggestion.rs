@@ -0,0 +1,77 @@
+//! Tags offered when tagging a todo
+//!
+//! Suggestions are the default tags together with the user's own tags that
+//! are on a few todos already, most used first. Tags the user asked not to
+//! have suggested are left out, though todos keep them.
+
+use super::Todo;
+use crate::utils::constants::todo::{FREQUENT_TAG_MIN_USES, MAX_TAG_SUGGESTIONS};
+use std::collections::{BTreeSet, HashMap};
+
+/// Counts how many todos carry each tag.
+pub fn tag_usage<'a>(todos: impl IntoIterator<Item = &'a Todo>) -> HashMap<String, usize> {
+    let mut usage = HashMap::new();
+    for todo in todos {
+        for tag in &todo.tags {
+            *usage.entry(tag.clone()).or_insert(0) += 1;
+        }
+    }
+    usage
+}
+
+/// Gets the tags to suggest for a todo, most used first.
+///
+/// Default tags are always candidates; the user's own tags are once they
+/// are on [`FREQUENT_TAG_MIN_USES`] todos. Tags used equally often keep the
+/// order of the defaults, then come alphabetically. At most
+/// [`MAX_TAG_SUGGESTIONS`] are returned.
+///
+/// # Arguments
+/// * `defaults` - Tags suggested even when unused
+/// * `usage` - Number of todos carrying each tag, from [`tag_usage`]
+/// * `suppressed` - Tags the user does not want suggested
+/// * `on_todo` - Tags the todo already has
+///
+/// # Example
+/// ```
+/// use std::collections::{BTreeSet, HashMap};
+/// use todo::models::tag_suggestion::suggest_tags;
+///
+/// let usage = HashMap::from([("Work".to_string(), 1), ("errands".to_string(), 4)]);
+/// let suppressed = BTreeSet::from(["Shopping".to_string()]);
+/// assert_eq!(
+///     suggest_tags(&["Work", "Personal", "Shopping"], &usage, &suppressed, &[]),
+///     ["errands", "Work", "Personal"]
+/// );
+/// ```
+pub fn suggest_tags(
+    defaults: &[&str],
+    usage: &HashMap<String, usize>,
+    suppressed: &BTreeSet<String>,
+    on_todo: &[String],
+) -> Vec<String> {
+    let uses = |tag: &str| usage.get(tag).copied().unwrap_or(0);
+    let default_rank = |tag: &str| {
+        defaults
+            .iter()
+            .position(|default| *default == tag)
+            .unwrap_or(defaults.len())
+    };
+
+    let frequent = usage
+        .iter()
+        .filter(|(_, count)| **count >= FREQUENT_TAG_MIN_USES)
+        .map(|(tag, _)| tag.as_str());
+    let mut tags: Vec<&str> = defaults.iter().copied().chain(frequent).collect();
+    tags.sort_unstable();
+    tags.dedup();
+    tags.retain(|tag| !suppressed.contains(*tag) && !on_todo.iter().any(|on| on == tag));
+    tags.sort_by(|a, b| {
+        uses(b)
+            .cmp(&uses(a))
+            .then_with(|| default_rank(a).cmp(&default_rank(b)))
+            .then_with(|| a.cmp(b))
+    });
+    tags.truncate(MAX_TAG_SUGGESTIONS);
+    tags.into_iter().map(str::to_string).collect()
+}
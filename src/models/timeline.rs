@@ -0,0 +1,128 @@
+//! The timeline of due-dated todos
+//!
+//! Each todo with a due date gets a row, with a diamond on its due date along
+//! a horizontal date axis. The axis spans the due dates on screen and today,
+//! padded by a day on either side, and is at least a couple of weeks long.
+//! Dragging a diamond moves the due date by whole days.
+
+use super::Todo;
+use crate::utils::dates;
+use chrono::{Duration, NaiveDate};
+use std::borrow::Borrow;
+
+/// The date axis of the timeline, one column per day.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimelineAxis {
+    /// First day on the axis
+    pub start: NaiveDate,
+    /// Number of days on the axis
+    pub days: u32,
+    /// Width of a day (pixels)
+    pub px_per_day: f64,
+}
+
+impl TimelineAxis {
+    /// Creates an axis spanning `dates` and `today`, with a day to spare on
+    /// either side, at least `min_days` long.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use todo::models::timeline::TimelineAxis;
+    ///
+    /// let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+    /// let due = NaiveDate::from_ymd_opt(2025, 3, 20).unwrap();
+    /// let axis = TimelineAxis::spanning([due], today, 7, 30.0);
+    /// assert_eq!(axis.start, NaiveDate::from_ymd_opt(2025, 3, 11).unwrap());
+    /// assert_eq!(axis.days, 11);
+    /// assert_eq!(axis.width(), 330.0);
+    /// ```
+    pub fn spanning(
+        dates: impl IntoIterator<Item = NaiveDate>,
+        today: NaiveDate,
+        min_days: u32,
+        px_per_day: f64,
+    ) -> Self {
+        let (first, last) = dates
+            .into_iter()
+            .fold((today, today), |(first, last), date| {
+                (first.min(date), last.max(date))
+            });
+        let start = first - Duration::days(1);
+        let days = ((last - start).num_days() + 2) as u32;
+        Self {
+            start,
+            days: days.max(min_days),
+            px_per_day,
+        }
+    }
+
+    /// Gets the width of the axis (pixels).
+    pub fn width(&self) -> f64 {
+        self.days as f64 * self.px_per_day
+    }
+
+    /// Gets the days on the axis, in order.
+    pub fn dates(&self) -> impl Iterator<Item = NaiveDate> {
+        self.start.iter_days().take(self.days as usize)
+    }
+
+    /// Gets the distance of a day's column from the start of the axis (pixels).
+    pub fn x_of(&self, date: NaiveDate) -> f64 {
+        (date - self.start).num_days() as f64 * self.px_per_day
+    }
+
+    /// Gets the distance of the middle of a day's column from the start of
+    /// the axis (pixels), where its diamond is drawn.
+    pub fn center_of(&self, date: NaiveDate) -> f64 {
+        self.x_of(date) + self.px_per_day / 2.0
+    }
+
+    /// Gets the day whose column is under a point, clamped to the axis.
+    ///
+    /// # Arguments
+    /// * `x` - Distance of the point from the start of the axis (pixels)
+    pub fn date_at(&self, x: f64) -> NaiveDate {
+        if self.days == 0 || self.px_per_day <= 0.0 {
+            return self.start;
+        }
+        let day = (x / self.px_per_day)
+            .floor()
+            .clamp(0.0, (self.days - 1) as f64);
+        self.start + Duration::days(day as i64)
+    }
+
+    /// Gets the day a date lands on after being dragged, snapped to whole days
+    /// and clamped to the axis.
+    ///
+    /// # Arguments
+    /// * `date` - The day being dragged
+    /// * `dx` - How far it has been dragged (pixels, negative to the left)
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use todo::models::timeline::TimelineAxis;
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+    /// let axis = TimelineAxis { start, days: 31, px_per_day: 20.0 };
+    /// let due = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+    /// assert_eq!(axis.dragged(due, 29.0), NaiveDate::from_ymd_opt(2025, 3, 11).unwrap());
+    /// assert_eq!(axis.dragged(due, 9.0), due);
+    /// ```
+    pub fn dragged(&self, date: NaiveDate, dx: f64) -> NaiveDate {
+        self.date_at(self.center_of(date) + dx)
+    }
+}
+
+/// Gets the todos shown on the timeline, those with a due date, earliest first.
+///
+/// Todos due the same day keep their order in `todos`.
+pub fn rows<T: Borrow<Todo>>(todos: impl IntoIterator<Item = T>) -> Vec<T> {
+    let mut rows: Vec<T> = todos
+        .into_iter()
+        .filter(|todo| todo.borrow().due_date.is_some())
+        .collect();
+    rows.sort_by_key(|todo| todo.borrow().due_date.map(dates::due_day));
+    rows
+}
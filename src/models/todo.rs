@@ -1,20 +1,115 @@
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use super::AutoTagger;
+use super::WeekStart;
+use super::due_limits::{DueDateError, DueDateLimits};
+use super::follow_up;
+use super::reminder;
+use super::tag_due::{TagDueRule, apply_tag_rules};
+use crate::utils::constants::todo::{HIGH_PRIORITY_TAG, TEXT_HISTORY_LIMIT};
+use crate::utils::dates::{self, DueBucket};
+use crate::utils::{notes, text};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Arc;
+use tracing::field::Empty;
+use tracing::{Span, instrument};
 
 /// Represents a single todo item.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Todo {
     pub id: usize,
     pub text: String,
     pub completed: bool,
     pub due_date: Option<DateTime<Utc>>,
     pub tags: Vec<String>,
+    /// 1-based position in the list; the sort key for display, storage and exports.
+    /// Todos written without it load in id order.
+    #[serde(default)]
     pub order: usize,
+    /// When the todo was added; `None` for todos added before this was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    /// When the todo was last marked completed; `None` while open.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+    /// When the todo was moved to the trash; `None` unless it is in the trash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Free-form notes; lines starting with `- ` are action items.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+    /// A web page the todo is about, opened from the row's link button.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The todo whose notes this one was extracted from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derived_from: Option<usize>,
+    /// Whether the todo is protected from text edits and deletion.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub locked: bool,
+    /// Whether the todo is archived: kept, but out of the list and counts.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+    /// When the todo was archived; `None` unless it is archived, or if it was
+    /// archived before this was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Whether the todo was made high priority for passing its due date, so
+    /// it is not escalated again after the user lowers it.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub escalated: bool,
+    /// When the text, completion, due date or tags last changed; `None` if
+    /// they have not changed since the todo was added or last synced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Earlier texts, oldest first, each with when it was replaced; only the
+    /// last [`TEXT_HISTORY_LIMIT`] are kept.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub text_history: Vec<(DateTime<Utc>, String)>,
+    /// The copy of the todo on a sync server, if it is synced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<RemoteLink>,
+    /// How many times the due date was moved later.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub postponed_count: u32,
+    /// Smaller steps of the todo, in the order they were added.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subtasks: Vec<Subtask>,
+}
+
+/// A step of a todo, checked off on its own.
+///
+/// Completing every subtask leaves the todo itself open.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subtask {
+    /// Identifies the subtask within its todo
+    pub id: usize,
+    pub text: String,
+    #[serde(default)]
+    pub completed: bool,
+}
+
+/// Where a synced todo lives on the server and what was last seen there.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteLink {
+    /// The iCalendar UID, which identifies the todo on both sides
+    pub uid: String,
+    /// The URL of the remote resource, relative to the server
+    pub href: String,
+    /// The entity tag the server gave the resource at the last sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// The iCalendar SEQUENCE at the last sync
+    #[serde(default)]
+    pub sequence: u32,
+    /// When the todo was last synced
+    pub synced_at: DateTime<Utc>,
 }
 
 impl Todo {
-    /// Creates a new Todo item with the given text.
+    /// Creates a new Todo item with the given text, created just now.
     pub fn new(id: usize, text: String) -> Self {
         Self {
             id,
@@ -23,59 +118,557 @@ impl Todo {
             due_date: None,
             tags: Vec::new(),
             order: id,
+            created_at: Some(Utc::now()),
+            completed_at: None,
+            deleted_at: None,
+            notes: String::new(),
+            url: None,
+            derived_from: None,
+            locked: false,
+            archived: false,
+            archived_at: None,
+            escalated: false,
+            updated_at: None,
+            text_history: Vec::new(),
+            remote: None,
+            postponed_count: 0,
+            subtasks: Vec::new(),
+        }
+    }
+
+    /// Replaces the text, keeping the old one in the text history.
+    ///
+    /// The oldest versions are dropped once there are more than
+    /// [`TEXT_HISTORY_LIMIT`].
+    pub fn replace_text(&mut self, text: String) {
+        if self.text == text {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.text, text);
+        self.text_history.push((Utc::now(), previous));
+        let excess = self.text_history.len().saturating_sub(TEXT_HISTORY_LIMIT);
+        self.text_history.drain(..excess);
+        self.touch();
+    }
+
+    /// Records that the text, completion, due date or tags changed just now.
+    ///
+    /// Every change to those fields goes through a method that calls this,
+    /// so sync can rely on `updated_at`.
+    fn touch(&mut self) {
+        self.updated_at = Some(Utc::now());
+    }
+
+    /// Checks if the text, completion, due date or tags changed since the last sync.
+    ///
+    /// Todos that were never synced count as changed.
+    #[allow(dead_code)]
+    pub fn changed_since_sync(&self) -> bool {
+        match (&self.remote, self.updated_at) {
+            (None, _) => true,
+            (Some(remote), Some(updated_at)) => updated_at > remote.synced_at,
+            (Some(_), None) => false,
         }
     }
 
-    /// Toggles the completed status of the todo.
+    /// Toggles the completed status of the todo, recording when it was completed.
     pub fn toggle(&mut self) {
         self.completed = !self.completed;
+        self.completed_at = self.completed.then(Utc::now);
+        self.touch();
     }
 
     /// Sets the due date for the todo
+    ///
+    /// Moving an existing due date later counts as postponing the todo;
+    /// setting a first date, clearing it or moving it earlier does not.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use todo::models::Todo;
+    ///
+    /// let mut todo = Todo::new(1, "File taxes".to_string());
+    /// let due = Utc::now();
+    /// todo.set_due_date(Some(due));
+    /// todo.set_due_date(Some(due + Duration::days(3)));
+    /// todo.set_due_date(Some(due + Duration::days(1)));
+    /// assert_eq!(todo.postponed_count, 1);
+    /// ```
     pub fn set_due_date(&mut self, date: Option<DateTime<Utc>>) {
-        self.due_date = date;
+        if let (Some(old), Some(new)) = (self.due_date, date)
+            && new > old
+        {
+            self.postponed_count += 1;
+        }
+        if self.due_date != date {
+            self.due_date = date;
+            self.touch();
+        }
     }
 
     /// Adds a tag to the todo
     pub fn add_tag(&mut self, tag: String) {
         if !self.tags.contains(&tag) {
             self.tags.push(tag);
+            self.touch();
         }
     }
 
     /// Removes a tag from the todo
     pub fn remove_tag(&mut self, tag: &str) {
+        let before = self.tags.len();
         self.tags.retain(|t| t != tag);
+        if self.tags.len() != before {
+            self.touch();
+        }
+    }
+
+    /// Counts the completed subtasks and all subtasks, for showing "2/5".
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::TodoList;
+    ///
+    /// let mut list = TodoList::new();
+    /// let id = list.add("Move house".to_string());
+    /// let boxes = list.add_subtask(id, "Pack boxes".to_string()).unwrap();
+    /// list.add_subtask(id, "Book van".to_string());
+    /// list.toggle_subtask(id, boxes);
+    /// assert_eq!(list.get(id).unwrap().subtask_progress(), (1, 2));
+    /// ```
+    pub fn subtask_progress(&self) -> (usize, usize) {
+        let done = self
+            .subtasks
+            .iter()
+            .filter(|subtask| subtask.completed)
+            .count();
+        (done, self.subtasks.len())
+    }
+
+    /// Checks if the todo is open and was due before today.
+    ///
+    /// The due day is the one it was stored for, see [`dates::due_day`], and
+    /// `today` is the local date. A todo due any time today is not overdue
+    /// yet.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use todo::models::Todo;
+    /// use todo::utils::dates::due_date_on;
+    ///
+    /// let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+    /// let mut todo = Todo::new(1, "File taxes".to_string());
+    /// todo.set_due_date(Some(due_date_on(today)));
+    /// assert!(!todo.is_overdue(today));
+    /// assert!(todo.is_overdue(today.succ_opt().unwrap()));
+    /// todo.toggle();
+    /// assert!(!todo.is_overdue(today.succ_opt().unwrap()));
+    /// ```
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        !self.completed && self.due_date.is_some_and(|due| dates::due_day(due) < today)
+    }
+
+    /// Checks if the text, any tag or the notes contain the search term,
+    /// ignoring case.
+    ///
+    /// The term must already be lowercased so callers can do that once per
+    /// search rather than once per todo. An empty term matches everything.
+    pub fn matches_search(&self, lowercase_term: &str) -> bool {
+        lowercase_term.is_empty()
+            || contains_ignore_case(&self.text, lowercase_term)
+            || self
+                .tags
+                .iter()
+                .any(|tag| contains_ignore_case(tag, lowercase_term))
+            || contains_ignore_case(&self.notes, lowercase_term)
+    }
+}
+
+/// Checks if a count is zero, so serde can leave it out.
+fn is_zero(count: &u32) -> bool {
+    *count == 0
+}
+
+/// Checks if `haystack` contains the already lowercased `needle`, ignoring case.
+///
+/// ASCII text is scanned in place, jumping between occurrences of the first
+/// needle byte; only non-ASCII text pays for a lowercased copy.
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    if !haystack.is_ascii() || !needle.is_ascii() {
+        return haystack.to_lowercase().contains(needle);
+    }
+
+    let (haystack, needle) = (haystack.as_bytes(), needle.as_bytes());
+    let Some(&first) = needle.first() else {
+        return true;
+    };
+    if haystack.len() < needle.len() {
+        return false;
+    }
+
+    let candidates = &haystack[..=haystack.len() - needle.len()];
+    memchr::memchr2_iter(first, first.to_ascii_uppercase(), candidates)
+        .any(|start| haystack[start..start + needle.len()].eq_ignore_ascii_case(needle))
+}
+
+/// Errors changing a single todo in a [`TodoList`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TodoError {
+    /// No todo with this id is in the list
+    NotFound(usize),
+    /// The todo is locked, so its text cannot change and it cannot be deleted
+    Locked(usize),
+    /// The todo has no earlier text at the requested position
+    VersionNotFound(usize),
+    /// The line of the todo's notes is gone, already extracted or has no valid text
+    NoteLineUnavailable(usize),
+    /// The due date is outside the allowed window
+    InvalidDueDate(usize, DueDateError),
+}
+
+impl fmt::Display for TodoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TodoError::NotFound(id) => write!(f, "Todo {} no longer exists", id),
+            TodoError::Locked(_) => write!(
+                f,
+                "This todo is locked. Unlock it first to edit or delete it."
+            ),
+            TodoError::VersionNotFound(_) => {
+                write!(f, "That version of the todo is no longer in its history")
+            }
+            TodoError::NoteLineUnavailable(_) => {
+                write!(f, "That line of the notes cannot become a todo")
+            }
+            TodoError::InvalidDueDate(_, error) => write!(f, "{}", error),
+        }
     }
 }
 
+/// Outcome of an operation applied to many todos at once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BulkOutcome {
+    /// Number of todos the operation changed
+    pub changed: usize,
+    /// Number of locked todos left untouched
+    pub skipped_locked: usize,
+}
+
+/// Todos removed by clearing completed ones.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClearedTodos {
+    /// The removed todos in list order, kept so the clear can be undone
+    pub removed: Vec<Todo>,
+    /// Number of locked todos left untouched
+    pub skipped_locked: usize,
+}
+
+/// Todos whose due dates were moved by some days.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShiftedDueDates {
+    /// The todos whose due date moved, so the shift can be undone
+    pub shifted: Vec<usize>,
+    /// The todos left alone because their date would leave the allowed window
+    pub refused: Vec<usize>,
+}
+
 /// Filter options for displaying todos.
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
+///
+/// Archived todos only show with [`FilterState::Archived`], and todos in
+/// the trash only with [`FilterState::Trash`].
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
 pub enum FilterState {
     #[default]
     All,
     Active,
     Completed,
+    Archived,
+    Trash,
+    /// Todos due today
+    DueToday,
+    /// Todos due this week
+    DueThisWeek,
+    /// Open todos due before today
+    Overdue,
 }
 
 impl FilterState {
     /// Checks if a todo should be visible based on the current filter.
-    pub fn matches(&self, todo: &Todo) -> bool {
+    ///
+    /// # Arguments
+    /// * `todo` - The todo to check; todos without a due date never match
+    ///   the due filters
+    /// * `today` - The current local date, from the clock, which decides
+    ///   what is due today and this week; due days are read with
+    ///   [`dates::due_day`]
+    /// * `week_start` - The first day of the week
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use todo::models::{FilterState, Todo, WeekStart};
+    /// use todo::utils::dates::due_date_on;
+    ///
+    /// let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+    /// let mut todo = Todo::new(1, "Pay rent".to_string());
+    /// assert!(!FilterState::DueToday.matches(&todo, today, WeekStart::Monday));
+    /// todo.set_due_date(Some(due_date_on(today)));
+    /// assert!(FilterState::DueToday.matches(&todo, today, WeekStart::Monday));
+    /// assert!(!FilterState::DueToday.matches(&todo, today.succ_opt().unwrap(), WeekStart::Monday));
+    /// ```
+    pub fn matches(&self, todo: &Todo, today: NaiveDate, week_start: WeekStart) -> bool {
+        let due_day = || todo.due_date.map(dates::due_day);
+        match self {
+            FilterState::All => !todo.archived,
+            FilterState::Active => !todo.archived && !todo.completed,
+            FilterState::Completed => !todo.archived && todo.completed,
+            FilterState::Archived => todo.archived,
+            FilterState::Trash => todo.deleted_at.is_some(),
+            FilterState::DueToday => !todo.archived && due_day() == Some(today),
+            FilterState::DueThisWeek => {
+                let (first, last) = dates::week_range(today, week_start);
+                !todo.archived && due_day().is_some_and(|day| (first..=last).contains(&day))
+            }
+            FilterState::Overdue => !todo.archived && todo.is_overdue(today),
+        }
+    }
+}
+
+/// Date-scoped filters applied on top of a [`FilterState`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuickFilter {
+    /// Open todos due before today
+    Overdue,
+    /// Todos due today
+    Today,
+    /// Todos due from today to the end of this week
+    ThisWeek,
+}
+
+impl QuickFilter {
+    /// All quick filters, in the order they are shown.
+    pub const ALL: [QuickFilter; 3] = [
+        QuickFilter::Overdue,
+        QuickFilter::Today,
+        QuickFilter::ThisWeek,
+    ];
+
+    /// Gets the name shown on the pill.
+    pub fn label(self) -> &'static str {
+        match self {
+            QuickFilter::Overdue => "Overdue",
+            QuickFilter::Today => "Today",
+            QuickFilter::ThisWeek => "This week",
+        }
+    }
+
+    /// Checks if a todo's due date falls in the filter's range.
+    ///
+    /// # Arguments
+    /// * `todo` - The todo to check; todos without a due date never match
+    /// * `today` - The current date
+    /// * `week_start` - The first day of the week, which decides where this week ends
+    pub fn matches(self, todo: &Todo, today: NaiveDate, week_start: WeekStart) -> bool {
+        let Some(due) = todo.due_date else {
+            return false;
+        };
+        match (self, dates::bucket(dates::due_day(due), today, week_start)) {
+            (QuickFilter::Overdue, DueBucket::Overdue) => todo.is_overdue(today),
+            (QuickFilter::Today, DueBucket::Today) => true,
+            (QuickFilter::ThisWeek, DueBucket::Today | DueBucket::ThisWeek) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Orders in which the list can show todos.
+///
+/// Every mode falls back to the manual order for todos it ranks the same,
+/// so the list never shuffles between renders.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortMode {
+    /// The order set by dragging todos
+    #[default]
+    ManualOrder,
+    /// Earliest due date first, todos without one last
+    DueDateAsc,
+    /// By text, ignoring case
+    Alphabetical,
+    /// Newest first, todos from before creation times were recorded last
+    CreatedDesc,
+    /// Todos tagged [`HIGH_PRIORITY_TAG`] first, then by due date
+    Priority,
+}
+
+impl SortMode {
+    /// All sort modes, in the order they are offered.
+    pub const ALL: [SortMode; 5] = [
+        SortMode::ManualOrder,
+        SortMode::DueDateAsc,
+        SortMode::Alphabetical,
+        SortMode::CreatedDesc,
+        SortMode::Priority,
+    ];
+
+    /// Gets the name shown in the sort menu.
+    pub fn label(self) -> &'static str {
         match self {
-            FilterState::All => true,
-            FilterState::Active => !todo.completed,
-            FilterState::Completed => todo.completed,
+            SortMode::ManualOrder => "Manual order",
+            SortMode::DueDateAsc => "Due date",
+            SortMode::Alphabetical => "Alphabetical",
+            SortMode::CreatedDesc => "Newest first",
+            SortMode::Priority => "Priority",
         }
     }
+
+    /// Parses a name as returned by [`SortMode::label`], ignoring case.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.label().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Sorts todos in this mode.
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::{SortMode, Todo};
+    ///
+    /// let mut todos = vec![
+    ///     Todo::new(1, "water the plants".to_string()),
+    ///     Todo::new(2, "Buy milk".to_string()),
+    /// ];
+    /// SortMode::Alphabetical.sort(&mut todos);
+    /// assert_eq!(todos[0].text, "Buy milk");
+    /// ```
+    pub fn sort(self, todos: &mut [Todo]) {
+        let urgent = |todo: &Todo| {
+            todo.tags
+                .iter()
+                .any(|tag| tag.eq_ignore_ascii_case(HIGH_PRIORITY_TAG))
+        };
+        // Todos without a due date sort after every date
+        let due = |todo: &Todo| (todo.due_date.is_none(), todo.due_date);
+        todos.sort_by(|a, b| {
+            let ranked = match self {
+                SortMode::ManualOrder => Ordering::Equal,
+                SortMode::DueDateAsc => due(a).cmp(&due(b)),
+                SortMode::Alphabetical => a.text.to_lowercase().cmp(&b.text.to_lowercase()),
+                SortMode::CreatedDesc => b.created_at.cmp(&a.created_at),
+                SortMode::Priority => urgent(b).cmp(&urgent(a)).then(due(a).cmp(&due(b))),
+            };
+            ranked.then(a.order.cmp(&b.order))
+        });
+    }
 }
 
 /// Manages the collection of todos in the application.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+///
+/// Equality compares the logical content: the todos by id, the trash, the
+/// snoozed reminders and `next_id`. Todos are kept in a HashMap internally but
+/// serialized as a Vec sorted by their order, so saved files and exports are
+/// byte-stable across runs.
+#[derive(Clone, Debug, PartialEq)]
 pub struct TodoList {
-    todos: HashMap<usize, Todo>,
+    todos: HashMap<usize, Arc<Todo>>,
+    /// Deleted todos that can still be restored, oldest deletion first
+    trash: Vec<Arc<Todo>>,
+    /// When the snoozed reminders of open todos come back, by todo id
+    snoozes: HashMap<usize, DateTime<Utc>>,
     next_id: usize,
 }
 
+/// The parts of a todo list one change touched, as they were on one side
+/// of it, kept for undo and redo.
+#[derive(Clone, Debug)]
+pub struct ListPatch {
+    /// Todos by id, `None` for todos not in the list
+    todos: Vec<(usize, Option<Arc<Todo>>)>,
+    /// The whole trash, if it changed
+    trash: Option<Vec<Arc<Todo>>>,
+    /// Every snooze, if any changed
+    snoozes: Option<HashMap<usize, DateTime<Utc>>>,
+}
+
+impl ListPatch {
+    /// Whether the patch changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.todos.is_empty() && self.trash.is_none() && self.snoozes.is_none()
+    }
+}
+
+/// Serialized form of a TodoList.
+#[derive(Serialize)]
+struct TodoListRepr<'a> {
+    todos: Vec<&'a Todo>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    trash: Vec<&'a Todo>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    snoozes: BTreeMap<usize, DateTime<Utc>>,
+    next_id: usize,
+}
+
+/// Todos as found in stored data: the current ordered list, or the map keyed
+/// by id written by earlier versions.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredTodos {
+    List(Vec<Todo>),
+    Map(HashMap<String, Todo>),
+}
+
+/// Deserialized form of a TodoList.
+#[derive(Deserialize)]
+struct StoredTodoList {
+    todos: StoredTodos,
+    #[serde(default)]
+    trash: Vec<Todo>,
+    #[serde(default)]
+    snoozes: HashMap<usize, DateTime<Utc>>,
+    next_id: usize,
+}
+
+impl Serialize for TodoList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut todos: Vec<&Todo> = self.todos.values().map(Arc::as_ref).collect();
+        todos.sort_by_key(|todo| (todo.order, todo.id));
+        TodoListRepr {
+            todos,
+            trash: self.trash.iter().map(Arc::as_ref).collect(),
+            snoozes: self
+                .snoozes
+                .iter()
+                .map(|(id, until)| (*id, *until))
+                .collect(),
+            next_id: self.next_id,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TodoList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let stored = StoredTodoList::deserialize(deserializer)?;
+        let todos = match stored.todos {
+            StoredTodos::List(todos) => todos,
+            StoredTodos::Map(todos) => todos.into_values().collect(),
+        };
+        let mut list = Self {
+            todos: todos
+                .into_iter()
+                .map(|todo| (todo.id, Arc::new(todo)))
+                .collect(),
+            trash: stored.trash.into_iter().map(Arc::new).collect(),
+            snoozes: stored.snoozes,
+            next_id: stored.next_id,
+        };
+        list.renumber_orders();
+        Ok(list)
+    }
+}
+
 impl Default for TodoList {
     fn default() -> Self {
         Self::new()
@@ -87,21 +680,253 @@ impl TodoList {
     pub fn new() -> Self {
         Self {
             todos: HashMap::new(),
+            trash: Vec::new(),
+            snoozes: HashMap::new(),
             next_id: 1,
         }
     }
 
-    /// Adds a new todo with the given text.
+    /// Builds a TodoList from existing todos, keeping their ids.
+    ///
+    /// Later duplicates of the same id replace earlier ones, `next_id`
+    /// continues after the highest id present, and orders are renumbered to
+    /// a dense sequence keeping their relative positions.
+    pub fn from_todos(todos: Vec<Todo>) -> Self {
+        let next_id = todos.iter().map(|todo| todo.id).max().unwrap_or(0) + 1;
+        let mut list = Self {
+            todos: todos
+                .into_iter()
+                .map(|todo| (todo.id, Arc::new(todo)))
+                .collect(),
+            trash: Vec::new(),
+            snoozes: HashMap::new(),
+            next_id,
+        };
+        list.renumber_orders();
+        list
+    }
+
+    /// Adds a new todo with the given text at the end of the list.
+    #[instrument(level = "debug", skip_all, fields(len = text.chars().count(), id = Empty))]
     pub fn add(&mut self, text: String) -> usize {
         let id = self.next_id;
-        self.todos.insert(id, Todo::new(id, text));
+        let mut todo = Todo::new(id, text);
+        todo.order = self.todos.len() + 1;
+        self.todos.insert(id, Arc::new(todo));
         self.next_id += 1;
+        Span::current().record("id", id);
         id
     }
 
     /// Removes a todo by its ID.
-    pub fn remove(&mut self, id: usize) -> Option<Todo> {
-        self.todos.remove(&id)
+    ///
+    /// # Returns
+    /// The removed todo, or an error if it does not exist or is locked
+    #[instrument(level = "debug", skip(self))]
+    pub fn remove(&mut self, id: usize) -> Result<Todo, TodoError> {
+        match self.todos.get(&id) {
+            None => return Err(TodoError::NotFound(id)),
+            Some(todo) if todo.locked => return Err(TodoError::Locked(id)),
+            Some(_) => {}
+        }
+        let removed = self.todos.remove(&id).ok_or(TodoError::NotFound(id))?;
+        self.snoozes.remove(&id);
+        for todo in self.todos.values_mut() {
+            if todo.order > removed.order {
+                Arc::make_mut(todo).order -= 1;
+            }
+        }
+        Ok(Arc::unwrap_or_clone(removed))
+    }
+
+    /// Moves a todo to the trash, from where it can be restored.
+    ///
+    /// # Returns
+    /// An error if no todo with the given id exists or it is locked
+    #[instrument(level = "debug", skip(self))]
+    pub fn move_to_trash(&mut self, id: usize) -> Result<(), TodoError> {
+        let mut todo = self.remove(id)?;
+        todo.deleted_at = Some(Utc::now());
+        self.trash.push(Arc::new(todo));
+        Ok(())
+    }
+
+    /// Moves several todos to the trash at once, keeping locked ones.
+    ///
+    /// # Returns
+    /// How many todos were moved and how many locked ones were kept
+    #[instrument(level = "debug", skip_all, fields(count = ids.len()))]
+    pub fn move_many_to_trash(&mut self, ids: &[usize]) -> BulkOutcome {
+        let mut outcome = BulkOutcome::default();
+        for &id in ids {
+            match self.move_to_trash(id) {
+                Ok(()) => outcome.changed += 1,
+                Err(TodoError::Locked(_)) => outcome.skipped_locked += 1,
+                Err(
+                    TodoError::NotFound(_)
+                    | TodoError::VersionNotFound(_)
+                    | TodoError::NoteLineUnavailable(_)
+                    | TodoError::InvalidDueDate(..),
+                ) => {}
+            }
+        }
+        outcome
+    }
+
+    /// Restores a todo from the trash to the end of the list.
+    ///
+    /// # Returns
+    /// * `true` if the todo was found in the trash and restored
+    /// * `false` if the trash holds no todo with the given id
+    #[instrument(level = "debug", skip(self))]
+    pub fn restore(&mut self, id: usize) -> bool {
+        let Some(position) = self.trash.iter().position(|todo| todo.id == id) else {
+            return false;
+        };
+        let mut todo = self.trash.remove(position);
+        let restored = Arc::make_mut(&mut todo);
+        restored.deleted_at = None;
+        restored.order = self.todos.len() + 1;
+        self.todos.insert(id, todo);
+        true
+    }
+
+    /// Restores several todos from the trash, in the given order.
+    ///
+    /// # Returns
+    /// The number of todos restored
+    #[instrument(level = "debug", skip_all, fields(count = ids.len()))]
+    pub fn restore_many(&mut self, ids: &[usize]) -> usize {
+        ids.iter().filter(|id| self.restore(**id)).count()
+    }
+
+    /// Gets the todos in the trash, most recently deleted first.
+    pub fn trashed(&self) -> Vec<Todo> {
+        self.trash
+            .iter()
+            .rev()
+            .map(|todo| Todo::clone(todo))
+            .collect()
+    }
+
+    /// Counts the todos in the trash.
+    pub fn trash_count(&self) -> usize {
+        self.trash.len()
+    }
+
+    /// Deletes a todo in the trash for good.
+    ///
+    /// # Returns
+    /// * `true` if the todo was found in the trash and deleted
+    /// * `false` if the trash holds no todo with the given id
+    #[instrument(level = "debug", skip(self))]
+    pub fn purge(&mut self, id: usize) -> bool {
+        let before = self.trash.len();
+        self.trash.retain(|todo| todo.id != id);
+        self.trash.len() != before
+    }
+
+    /// Deletes the todos that have been in the trash longer than the given
+    /// number of days. Todos without a deletion time are kept.
+    ///
+    /// # Returns
+    /// The number of todos deleted
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use todo::models::TodoList;
+    ///
+    /// let mut list = TodoList::new();
+    /// let id = list.add("Old idea".to_string());
+    /// list.move_to_trash(id).unwrap();
+    ///
+    /// assert_eq!(list.purge_older_than(Utc::now() + Duration::days(29), 30), 0);
+    /// assert_eq!(list.purge_older_than(Utc::now() + Duration::days(31), 30), 1);
+    /// assert!(list.trashed().is_empty());
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn purge_older_than(&mut self, now: DateTime<Utc>, days: u32) -> usize {
+        let cutoff = now - Duration::days(days.into());
+        let before = self.trash.len();
+        self.trash.retain(|todo| {
+            todo.deleted_at
+                .is_none_or(|deleted_at| deleted_at >= cutoff)
+        });
+        before - self.trash.len()
+    }
+
+    /// Deletes every todo in the trash for good.
+    ///
+    /// # Returns
+    /// The number of todos deleted
+    #[instrument(level = "debug", skip(self))]
+    pub fn empty_trash(&mut self) -> usize {
+        std::mem::take(&mut self.trash).len()
+    }
+
+    /// Renumbers orders to 1..=n, keeping the current relative positions.
+    fn renumber_orders(&mut self) {
+        let mut ids: Vec<(usize, usize)> = self
+            .todos
+            .values()
+            .map(|todo| (todo.order, todo.id))
+            .collect();
+        ids.sort();
+        for (position, (_, id)) in ids.into_iter().enumerate() {
+            if let Some(todo) = self.todos.get_mut(&id)
+                && todo.order != position + 1
+            {
+                Arc::make_mut(todo).order = position + 1;
+            }
+        }
+    }
+
+    /// Validates the internal consistency of the list.
+    ///
+    /// Checks that every todo is stored under its own id, that `next_id` is
+    /// above every id in use including the trash, that trashed ids are not
+    /// reused, and that the order values are exactly `1..=n`.
+    ///
+    /// # Returns
+    /// * `Ok(())` if all invariants hold
+    /// * `Err(String)` describing the first violation found
+    #[allow(dead_code)]
+    pub fn check_invariants(&self) -> Result<(), String> {
+        for (key, todo) in &self.todos {
+            if *key != todo.id {
+                return Err(format!("todo {} is stored under id {}", todo.id, key));
+            }
+            if todo.id >= self.next_id {
+                return Err(format!(
+                    "todo id {} is not below next_id {}",
+                    todo.id, self.next_id
+                ));
+            }
+        }
+
+        for todo in &self.trash {
+            if todo.id >= self.next_id || self.todos.contains_key(&todo.id) {
+                return Err(format!(
+                    "trashed todo id {} is still in use or not below next_id {}",
+                    todo.id, self.next_id
+                ));
+            }
+        }
+
+        let mut orders: Vec<usize> = self.todos.values().map(|todo| todo.order).collect();
+        orders.sort_unstable();
+        for (position, order) in orders.into_iter().enumerate() {
+            if order != position + 1 {
+                return Err(format!(
+                    "order values are not a dense permutation: expected {}, found {}",
+                    position + 1,
+                    order
+                ));
+            }
+        }
+
+        Ok(())
     }
 
     /// Toggles the completion status of a todo.
@@ -122,9 +947,13 @@ impl TodoList {
     /// list.toggle_completion(id);
     /// assert!(list.all()[0].completed);
     /// ```
+    #[instrument(level = "debug", skip(self))]
     pub fn toggle_completion(&mut self, todo_id: usize) -> bool {
-        if let Some(todo) = self.todos.get_mut(&todo_id) {
+        if let Some(todo) = self.todos.get_mut(&todo_id).map(Arc::make_mut) {
             todo.toggle();
+            if todo.completed {
+                self.snoozes.remove(&todo_id);
+            }
             true
         } else {
             false
@@ -139,10 +968,119 @@ impl TodoList {
         self.toggle_completion(id)
     }
 
+    /// Completes every todo if any is still active, otherwise reopens them
+    /// all, like the "mark all as complete" toggle of TodoMVC.
+    ///
+    /// # Arguments
+    /// * `ids` - The todos to toggle, such as those on screen, or `None` for
+    ///   every todo; archived and unknown ids are skipped
+    ///
+    /// # Returns
+    /// The number of todos changed
+    ///
+    /// # Example
+    /// ```
+    /// # use todo::models::TodoList;
+    /// let mut list = TodoList::new();
+    /// let milk = list.add("Buy milk".to_string());
+    /// list.add("Call the plumber".to_string());
+    /// list.toggle(milk);
+    ///
+    /// assert_eq!(list.toggle_all(None), 1);
+    /// assert_eq!(list.completed_count(), 2);
+    /// assert_eq!(list.toggle_all(Some(&[milk])), 1);
+    /// assert_eq!(list.active_count(), 1);
+    /// ```
+    #[instrument(level = "debug", skip(self, ids))]
+    pub fn toggle_all(&mut self, ids: Option<&[usize]>) -> usize {
+        let ids: Vec<usize> = match ids {
+            Some(ids) => ids
+                .iter()
+                .copied()
+                .filter(|id| self.todos.get(id).is_some_and(|todo| !todo.archived))
+                .collect(),
+            None => self
+                .todos
+                .values()
+                .filter(|todo| !todo.archived)
+                .map(|todo| todo.id)
+                .collect(),
+        };
+        let complete = ids.iter().any(|id| !self.todos[id].completed);
+
+        let mut changed = 0;
+        for id in ids {
+            if let Some(todo) = self.todos.get_mut(&id)
+                && todo.completed != complete
+            {
+                Arc::make_mut(todo).toggle();
+                changed += 1;
+            }
+        }
+        if complete {
+            self.snoozes
+                .retain(|id, _| self.todos.get(id).is_some_and(|todo| !todo.completed));
+        }
+        changed
+    }
+
     /// Updates the text of a todo.
-    pub fn update_text(&mut self, id: usize, text: String) -> bool {
-        if let Some(todo) = self.todos.get_mut(&id) {
-            todo.text = text;
+    ///
+    /// # Returns
+    /// An error if no todo with the given id exists or it is locked
+    #[instrument(level = "debug", skip(self, text), fields(len = text.chars().count()))]
+    pub fn update_text(&mut self, id: usize, text: String) -> Result<(), TodoError> {
+        let todo = self
+            .todos
+            .get_mut(&id)
+            .map(Arc::make_mut)
+            .ok_or(TodoError::NotFound(id))?;
+        if todo.locked {
+            return Err(TodoError::Locked(id));
+        }
+        todo.replace_text(text);
+        Ok(())
+    }
+
+    /// Gets the earlier texts of a todo, oldest first, each with when it was
+    /// replaced; empty if no todo with the given id exists.
+    pub fn text_history(&self, id: usize) -> &[(DateTime<Utc>, String)] {
+        self.todos
+            .get(&id)
+            .map_or(&[], |todo| todo.text_history.as_slice())
+    }
+
+    /// Brings back an earlier text of a todo.
+    ///
+    /// Restoring is an edit like any other: the current text joins the
+    /// history, so the restore can itself be undone from there.
+    ///
+    /// # Arguments
+    /// * `id` - The todo's id
+    /// * `index` - Position of the version in [`text_history`](Self::text_history)
+    ///
+    /// # Returns
+    /// An error if no todo with the given id exists, it is locked, or it has
+    /// no version at `index`
+    #[instrument(level = "debug", skip(self))]
+    pub fn restore_text(&mut self, id: usize, index: usize) -> Result<(), TodoError> {
+        let text = self
+            .text_history(id)
+            .get(index)
+            .map(|(_, text)| text.clone())
+            .ok_or(TodoError::VersionNotFound(id))?;
+        self.update_text(id, text)
+    }
+
+    /// Locks or unlocks a todo.
+    ///
+    /// # Returns
+    /// * `true` if the todo was found
+    /// * `false` if no todo with the given id exists
+    #[instrument(level = "debug", skip(self))]
+    pub fn set_locked(&mut self, id: usize, locked: bool) -> bool {
+        if let Some(todo) = self.todos.get_mut(&id).map(Arc::make_mut) {
+            todo.locked = locked;
             true
         } else {
             false
@@ -185,7 +1123,7 @@ impl TodoList {
     fn reorder_todos_moving_down(&mut self, source_order: usize, target_order: usize) {
         for (_, todo) in self.todos.iter_mut() {
             if todo.order > source_order && todo.order <= target_order {
-                todo.order -= 1;
+                Arc::make_mut(todo).order -= 1;
             }
         }
     }
@@ -204,7 +1142,7 @@ impl TodoList {
     fn reorder_todos_moving_up(&mut self, source_order: usize, target_order: usize) {
         for (_, todo) in self.todos.iter_mut() {
             if todo.order >= target_order && todo.order < source_order {
-                todo.order += 1;
+                Arc::make_mut(todo).order += 1;
             }
         }
     }
@@ -219,7 +1157,7 @@ impl TodoList {
     /// * `true` if the update was successful
     /// * `false` if the todo was not found
     fn update_source_todo_order(&mut self, source_id: usize, target_order: usize) -> bool {
-        if let Some(todo) = self.todos.get_mut(&source_id) {
+        if let Some(todo) = self.todos.get_mut(&source_id).map(Arc::make_mut) {
             todo.order = target_order;
             true
         } else {
@@ -236,6 +1174,7 @@ impl TodoList {
     /// # Returns
     /// * `true` if the reorder was successful
     /// * `false` if the operation was invalid
+    #[instrument(level = "debug", skip(self))]
     pub fn reorder(&mut self, source_id: usize, target_id: usize) -> bool {
         if !self.validate_reorder_request(source_id, target_id) {
             return false;
@@ -259,55 +1198,618 @@ impl TodoList {
         true
     }
 
-    /// Gets all todos as a vector, sorted by their order field.
-    pub fn all(&self) -> Vec<Todo> {
-        let mut todos: Vec<Todo> = self.todos.values().cloned().collect();
-        todos.sort_by_key(|todo| todo.order);
-        todos
-    }
-
-    /// Gets filtered todos based on the given filter state.
+    /// Moves a todo to a position in the list, shifting the todos between
+    /// its old and new place by one, as dropping it there would.
     ///
-    /// This is a utility method that could be used in the future for more
-    /// advanced filtering capabilities.
-    #[allow(dead_code)]
-    pub fn filtered(&self, filter: FilterState) -> Vec<Todo> {
-        self.todos
+    /// # Arguments
+    /// * `id` - The ID of the todo to move
+    /// * `index` - Where to put it, counting from 0 in list order
+    ///
+    /// # Returns
+    /// * `true` if the todo moved
+    /// * `false` if it does not exist, is already there or the index is past
+    ///   the end of the list
+    ///
+    /// # Example
+    /// ```
+    /// # use todo::models::TodoList;
+    /// let mut list = TodoList::new();
+    /// let milk = list.add("Buy milk".to_string());
+    /// let plumber = list.add("Call the plumber".to_string());
+    /// let rent = list.add("Pay rent".to_string());
+    ///
+    /// assert!(list.reorder_to_index(rent, 0));
+    /// let ids: Vec<usize> = list.all().iter().map(|todo| todo.id).collect();
+    /// assert_eq!(ids, [rent, milk, plumber]);
+    /// assert!(!list.reorder_to_index(rent, 0));
+    /// assert!(!list.reorder_to_index(rent, 3));
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn reorder_to_index(&mut self, id: usize, index: usize) -> bool {
+        let Some(target_id) = self
+            .todos
             .values()
-            .filter(|todo| filter.matches(todo))
-            .cloned()
-            .collect()
+            .find(|todo| todo.order == index + 1)
+            .map(|todo| todo.id)
+        else {
+            return false;
+        };
+        self.reorder(id, target_id)
     }
 
-    /// Clears all completed todos.
-    pub fn clear_completed(&mut self) -> usize {
-        let completed_ids: Vec<_> = self
+    /// Gets every todo's id and order, in list order, so a reorder can be
+    /// undone with [`set_order_snapshot`](Self::set_order_snapshot).
+    pub fn order_snapshot(&self) -> Vec<(usize, usize)> {
+        let mut snapshot: Vec<(usize, usize)> = self
             .todos
+            .values()
+            .map(|todo| (todo.id, todo.order))
+            .collect();
+        snapshot.sort_unstable_by_key(|(_, order)| *order);
+        snapshot
+    }
+
+    /// Puts the todos back in the order of an earlier snapshot.
+    ///
+    /// Todos removed since are skipped, and todos added since stay at the
+    /// end. Orders are renumbered afterwards, so they remain `1..=n`.
+    ///
+    /// # Arguments
+    /// * `snapshot` - Ids and orders from [`order_snapshot`](Self::order_snapshot)
+    ///
+    /// # Returns
+    /// The number of todos that changed position
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::TodoList;
+    ///
+    /// let mut list = TodoList::new();
+    /// let first = list.add("Call the plumber".to_string());
+    /// let second = list.add("Pay rent".to_string());
+    /// let before = list.order_snapshot();
+    ///
+    /// list.reorder(second, first);
+    /// assert_eq!(list.set_order_snapshot(before), 2);
+    /// assert_eq!(list.all()[0].id, first);
+    /// ```
+    #[instrument(level = "debug", skip_all, fields(count = snapshot.len()))]
+    pub fn set_order_snapshot(&mut self, snapshot: Vec<(usize, usize)>) -> usize {
+        let before = self.order_snapshot();
+        let last = snapshot.iter().map(|(_, order)| *order).max().unwrap_or(0);
+        let restored: HashMap<usize, usize> = snapshot.into_iter().collect();
+        for todo in self.todos.values_mut() {
+            let order = match restored.get(&todo.id) {
+                Some(order) => *order,
+                None => last + todo.order,
+            };
+            if todo.order != order {
+                Arc::make_mut(todo).order = order;
+            }
+        }
+        self.renumber_orders();
+
+        self.order_snapshot()
             .iter()
-            .filter(|(_, todo)| todo.completed)
-            .map(|(id, _)| *id)
+            .zip(&before)
+            .filter(|((now, _), (was, _))| now != was)
+            .count()
+    }
+
+    /// Gets what differs from an earlier copy of the list, such as one taken
+    /// before a change, as a patch putting the earlier copy back.
+    ///
+    /// Todos are shared between copies until one is changed, so only the
+    /// todos the change touched are kept, and unchanged ones are told apart
+    /// without comparing their contents.
+    pub fn patch_back_to(&self, earlier: &TodoList) -> ListPatch {
+        let mut todos: Vec<(usize, Option<Arc<Todo>>)> = earlier
+            .todos
+            .iter()
+            .filter(|(id, todo)| self.todos.get(id) != Some(todo))
+            .map(|(id, todo)| (*id, Some(Arc::clone(todo))))
+            .collect();
+        todos.extend(
+            self.todos
+                .keys()
+                .filter(|id| !earlier.todos.contains_key(id))
+                .map(|id| (*id, None)),
+        );
+        ListPatch {
+            todos,
+            trash: (self.trash != earlier.trash).then(|| earlier.trash.clone()),
+            snoozes: (self.snoozes != earlier.snoozes).then(|| earlier.snoozes.clone()),
+        }
+    }
+
+    /// Applies a patch from [`patch_back_to`](Self::patch_back_to).
+    ///
+    /// Ids handed out since the patch was taken are not handed out again, so
+    /// a todo added after an undo cannot be mistaken for the one undone.
+    ///
+    /// # Returns
+    /// The patch undoing this one
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::TodoList;
+    ///
+    /// let mut list = TodoList::new();
+    /// let before = list.clone();
+    /// let first = list.add("Call the plumber".to_string());
+    ///
+    /// let redo = list.apply_patch(list.patch_back_to(&before));
+    /// assert!(list.all().is_empty());
+    /// list.apply_patch(redo);
+    /// assert_eq!(list.all()[0].id, first);
+    /// assert!(list.add("Pay rent".to_string()) > first);
+    /// ```
+    pub fn apply_patch(&mut self, patch: ListPatch) -> ListPatch {
+        let todos = patch
+            .todos
+            .into_iter()
+            .map(|(id, todo)| {
+                let replaced = match todo {
+                    Some(todo) => self.todos.insert(id, todo),
+                    None => self.todos.remove(&id),
+                };
+                (id, replaced)
+            })
+            .collect();
+        ListPatch {
+            todos,
+            trash: patch
+                .trash
+                .map(|trash| std::mem::replace(&mut self.trash, trash)),
+            snoozes: patch
+                .snoozes
+                .map(|snoozes| std::mem::replace(&mut self.snoozes, snoozes)),
+        }
+    }
+
+    /// Gets all todos as a vector, sorted by their order field, leaving
+    /// archived ones out.
+    pub fn all(&self) -> Vec<Todo> {
+        self.sorted_by_order(|todo| !todo.archived)
+    }
+
+    /// Gets all todos except archived ones, sorted in the given mode.
+    ///
+    /// Todos the mode ranks the same keep their manual order.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::Utc;
+    /// use todo::models::{SortMode, TodoList};
+    ///
+    /// let mut list = TodoList::new();
+    /// list.add("Someday".to_string());
+    /// let taxes = list.add("File taxes".to_string());
+    /// list.set_due_date(taxes, Some(Utc::now())).unwrap();
+    ///
+    /// let texts: Vec<String> = list
+    ///     .sorted(SortMode::DueDateAsc)
+    ///     .into_iter()
+    ///     .map(|todo| todo.text)
+    ///     .collect();
+    /// assert_eq!(texts, ["File taxes", "Someday"]);
+    /// ```
+    pub fn sorted(&self, mode: SortMode) -> Vec<Todo> {
+        let mut todos = self.all();
+        mode.sort(&mut todos);
+        todos
+    }
+
+    /// Gets all todos, archived ones included, sorted by their order field.
+    ///
+    /// Exports and sync use this so archiving never loses a todo.
+    pub fn all_including_archived(&self) -> Vec<Todo> {
+        self.sorted_by_order(|_| true)
+    }
+
+    /// Gets the archived todos, sorted by their order field.
+    #[allow(dead_code)]
+    pub fn archived(&self) -> Vec<Todo> {
+        self.sorted_by_order(|todo| todo.archived)
+    }
+
+    /// Archives a todo, taking it out of the list and counts while keeping it.
+    ///
+    /// # Returns
+    /// `true` if the todo was found and not archived yet
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::TodoList;
+    ///
+    /// let mut list = TodoList::new();
+    /// let id = list.add("Old project".to_string());
+    /// assert!(list.archive(id));
+    /// assert!(list.all().is_empty());
+    /// assert_eq!(list.archived()[0].id, id);
+    ///
+    /// assert!(list.unarchive(id));
+    /// assert_eq!(list.all().len(), 1);
+    /// ```
+    #[allow(dead_code)]
+    pub fn archive(&mut self, id: usize) -> bool {
+        self.set_archived(id, true)
+    }
+
+    /// Brings an archived todo back into the list.
+    ///
+    /// # Returns
+    /// `true` if the todo was found and archived
+    pub fn unarchive(&mut self, id: usize) -> bool {
+        self.set_archived(id, false)
+    }
+
+    /// Brings several archived todos back into the list.
+    ///
+    /// # Returns
+    /// The number of todos brought back
+    pub fn unarchive_many(&mut self, ids: &[usize]) -> usize {
+        ids.iter().filter(|id| self.unarchive(**id)).count()
+    }
+
+    /// Deletes archived todos for good, keeping locked ones. Ids of todos in
+    /// the list, the trash or unknown are ignored.
+    ///
+    /// # Returns
+    /// The number of todos deleted
+    #[instrument(level = "debug", skip_all, fields(count = ids.len()))]
+    pub fn purge_archived(&mut self, ids: &[usize]) -> usize {
+        ids.iter()
+            .filter(|id| {
+                self.todos
+                    .get(id)
+                    .is_some_and(|todo| todo.archived && !todo.locked)
+                    && self.remove(**id).is_ok()
+            })
+            .count()
+    }
+
+    fn set_archived(&mut self, id: usize, archived: bool) -> bool {
+        match self.todos.get_mut(&id) {
+            Some(todo) if todo.archived != archived => {
+                let todo = Arc::make_mut(todo);
+                todo.archived = archived;
+                todo.archived_at = archived.then(Utc::now);
+                if archived {
+                    self.snoozes.remove(&id);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Archives the completed todos among the given ones.
+    ///
+    /// Unlike clearing, locked todos are archived too, since nothing is
+    /// deleted. Ids of active, archived or unknown todos are ignored.
+    ///
+    /// # Returns
+    /// The ids of the archived todos, in list order
+    #[instrument(level = "debug", skip_all, fields(count = ids.len()))]
+    pub fn archive_completed(&mut self, ids: &[usize]) -> Vec<usize> {
+        let mut archived: Vec<(usize, usize)> = Vec::new();
+        for id in ids {
+            if let Some(todo) = self.todos.get_mut(id)
+                && todo.completed
+                && !todo.archived
+            {
+                let todo = Arc::make_mut(todo);
+                todo.archived = true;
+                todo.archived_at = Some(Utc::now());
+                archived.push((todo.order, todo.id));
+            }
+        }
+        archived.sort_unstable();
+        archived.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Gets all todos oldest first.
+    ///
+    /// Todos from before creation times were recorded come first, in list
+    /// order, as do todos created at the same moment.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use todo::models::{Todo, TodoList};
+    ///
+    /// let mut older = Todo::new(1, "Book flights".to_string());
+    /// older.created_at = Some(Utc::now() - Duration::days(2));
+    /// let newer = Todo::new(2, "Pack bags".to_string());
+    /// let list = TodoList::from_todos(vec![newer, older]);
+    ///
+    /// let texts: Vec<String> = list.sorted_by_created().into_iter().map(|todo| todo.text).collect();
+    /// assert_eq!(texts, ["Book flights", "Pack bags"]);
+    /// ```
+    #[allow(dead_code)]
+    pub fn sorted_by_created(&self) -> Vec<Todo> {
+        let mut todos = self.all();
+        todos.sort_by_key(|todo| todo.created_at);
+        todos
+    }
+
+    /// Gets the todos whose text, completion, due date or tags changed at or
+    /// after a moment, most recently changed first.
+    ///
+    /// Todos unchanged since they were added or last synced are left out.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use todo::models::TodoList;
+    ///
+    /// let mut list = TodoList::new();
+    /// let id = list.add("Book flights".to_string());
+    /// list.add("Pack bags".to_string());
+    /// let since = Utc::now() - Duration::seconds(1);
+    /// list.toggle_completion(id);
+    ///
+    /// let changed: Vec<usize> = list.recently_modified(since).iter().map(|todo| todo.id).collect();
+    /// assert_eq!(changed, [id]);
+    /// ```
+    #[allow(dead_code)]
+    pub fn recently_modified(&self, since: DateTime<Utc>) -> Vec<Todo> {
+        let mut todos: Vec<Todo> =
+            self.sorted_by_order(|todo| todo.updated_at.is_some_and(|at| at >= since));
+        todos.sort_by_key(|todo| Reverse(todo.updated_at));
+        todos
+    }
+
+    /// Gets the completed todos finished in a time range, including `start`
+    /// and excluding `end`, in the order they were completed.
+    ///
+    /// Todos completed before completion times were recorded are left out.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use todo::models::TodoList;
+    ///
+    /// let mut list = TodoList::new();
+    /// let id = list.add("Book flights".to_string());
+    /// list.add("Pack bags".to_string());
+    /// list.toggle_completion(id);
+    ///
+    /// let now = Utc::now();
+    /// let done = list.completed_between(now - Duration::hours(1), now + Duration::hours(1));
+    /// assert_eq!(done.len(), 1);
+    /// assert!(list.completed_between(now - Duration::days(2), now - Duration::days(1)).is_empty());
+    /// ```
+    #[allow(dead_code)]
+    pub fn completed_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<Todo> {
+        let mut todos: Vec<Todo> = self.sorted_by_order(|todo| {
+            todo.completed && todo.completed_at.is_some_and(|at| start <= at && at < end)
+        });
+        todos.sort_by_key(|todo| todo.completed_at);
+        todos
+    }
+
+    /// Gets filtered todos based on the given filter state, sorted by their
+    /// order field.
+    ///
+    /// This is a utility method that could be used in the future for more
+    /// advanced filtering capabilities. `today` is the local date the due
+    /// filters compare against, with weeks starting on Monday.
+    #[allow(dead_code)]
+    pub fn filtered(&self, filter: FilterState, today: NaiveDate) -> Vec<Todo> {
+        self.sorted_by_order(|todo| filter.matches(todo, today, WeekStart::default()))
+    }
+
+    /// Gets the todos whose text, tags or notes contain the query, ignoring case,
+    /// sorted by their order field.
+    #[allow(dead_code)]
+    pub fn search(&self, query: &str) -> Vec<Todo> {
+        let term = query.to_lowercase();
+        self.sorted_by_order(|todo| !todo.archived && todo.matches_search(&term))
+    }
+
+    /// Clones the todos matching the predicate, sorted by their order field.
+    ///
+    /// Sorts references first so only the matching todos are cloned, once.
+    fn sorted_by_order(&self, predicate: impl Fn(&Todo) -> bool) -> Vec<Todo> {
+        let mut todos: Vec<&Todo> = self
+            .todos
+            .values()
+            .map(Arc::as_ref)
+            .filter(|todo| predicate(todo))
+            .collect();
+        todos.sort_unstable_by_key(|todo| todo.order);
+        todos.into_iter().cloned().collect()
+    }
+
+    /// Gets the open todos that are due within the reminder lead time.
+    ///
+    /// A todo stays in the window once it has entered it, so overdue todos
+    /// are included until they are completed or their due date moves out.
+    /// A snoozed todo is left out until its snooze runs out.
+    ///
+    /// # Arguments
+    /// * `now` - The current time
+    /// * `lead` - How long before the due date a todo should be reminded of
+    ///
+    /// # Returns
+    /// The matching todos, soonest due first
+    pub fn due_soon(&self, now: DateTime<Utc>, lead: Duration) -> Vec<&Todo> {
+        let mut todos: Vec<&Todo> = self
+            .todos
+            .values()
+            .map(Arc::as_ref)
+            .filter(|todo| {
+                reminder::next_reminder(todo, self.snoozed_until(todo.id), lead)
+                    .is_some_and(|remind_at| remind_at <= now)
+            })
             .collect();
+        todos.sort_unstable_by_key(|todo| (todo.due_date, todo.order));
+        todos
+    }
 
-        let count = completed_ids.len();
+    /// Snoozes the reminder of an open todo until the given time, replacing
+    /// an earlier snooze.
+    ///
+    /// # Returns
+    /// `true` if the todo was found open and snoozed
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use todo::models::TodoList;
+    ///
+    /// let mut list = TodoList::new();
+    /// let id = list.add("Send the invoice".to_string());
+    /// let now = Utc::now();
+    /// list.set_due_date(id, Some(now + Duration::hours(1))).unwrap();
+    /// assert_eq!(list.due_soon(now, Duration::hours(2)).len(), 1);
+    ///
+    /// assert!(list.snooze(id, now + Duration::minutes(30)));
+    /// assert!(list.due_soon(now, Duration::hours(2)).is_empty());
+    /// assert_eq!(list.due_soon(now + Duration::minutes(30), Duration::hours(2)).len(), 1);
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn snooze(&mut self, id: usize, until: DateTime<Utc>) -> bool {
+        if !self
+            .todos
+            .get(&id)
+            .is_some_and(|todo| !todo.completed && !todo.archived)
+        {
+            return false;
+        }
+        self.snoozes.insert(id, until);
+        true
+    }
 
-        for id in completed_ids {
-            self.todos.remove(&id);
+    /// Brings a snoozed reminder back right away.
+    ///
+    /// # Returns
+    /// `true` if the todo's reminder was snoozed
+    #[instrument(level = "debug", skip(self))]
+    pub fn cancel_snooze(&mut self, id: usize) -> bool {
+        self.snoozes.remove(&id).is_some()
+    }
+
+    /// Gets when a todo's snoozed reminder comes back, `None` if it is not
+    /// snoozed.
+    pub fn snoozed_until(&self, id: usize) -> Option<DateTime<Utc>> {
+        self.snoozes.get(&id).copied()
+    }
+
+    /// Forgets snoozes that have run out or whose todo is no longer open,
+    /// such as one completed or deleted by a sync or in an older version.
+    ///
+    /// # Returns
+    /// The number of snoozes forgotten
+    pub fn prune_snoozes(&mut self, now: DateTime<Utc>) -> usize {
+        let before = self.snoozes.len();
+        let todos = &self.todos;
+        self.snoozes.retain(|id, until| {
+            *until > now
+                && todos
+                    .get(id)
+                    .is_some_and(|todo| !todo.completed && !todo.archived)
+        });
+        before - self.snoozes.len()
+    }
+
+    /// Clears all completed todos, keeping locked ones.
+    ///
+    /// # Returns
+    /// The cleared todos and how many locked ones were kept
+    #[instrument(level = "debug", skip(self))]
+    pub fn clear_completed(&mut self) -> ClearedTodos {
+        let ids: Vec<usize> = self.todos.keys().copied().collect();
+        self.clear_completed_filtered(&ids)
+    }
+
+    /// Clears the completed todos among the given ones, keeping locked ones.
+    ///
+    /// Ids of active, archived or unknown todos are ignored, so the ids of
+    /// every todo on screen can be passed.
+    ///
+    /// # Returns
+    /// The cleared todos and how many locked ones were kept
+    #[instrument(level = "debug", skip_all, fields(count = ids.len()))]
+    pub fn clear_completed_filtered(&mut self, ids: &[usize]) -> ClearedTodos {
+        let mut cleared = ClearedTodos::default();
+        for id in ids {
+            match self.todos.get(id) {
+                Some(todo) if !todo.completed || todo.archived => {}
+                Some(todo) if todo.locked => cleared.skipped_locked += 1,
+                Some(_) => {
+                    cleared
+                        .removed
+                        .extend(self.todos.remove(id).map(Arc::unwrap_or_clone));
+                }
+                _ => {}
+            }
+        }
+        cleared.removed.sort_unstable_by_key(|todo| todo.order);
+        self.renumber_orders();
+
+        cleared
+    }
+
+    /// Puts cleared todos back where they were, undoing a clear.
+    ///
+    /// Todos whose id is in use again are skipped.
+    ///
+    /// # Returns
+    /// The number of todos restored
+    #[instrument(level = "debug", skip_all, fields(count = todos.len()))]
+    pub fn restore_cleared(&mut self, todos: Vec<Todo>) -> usize {
+        let mut todos = todos;
+        todos.sort_unstable_by_key(|todo| todo.order);
+
+        let mut restored = 0;
+        for todo in todos {
+            if self.todos.contains_key(&todo.id) {
+                continue;
+            }
+            // Make room at the old position; later todos move down by one
+            for other in self.todos.values_mut() {
+                if other.order >= todo.order {
+                    Arc::make_mut(other).order += 1;
+                }
+            }
+            self.next_id = self.next_id.max(todo.id + 1);
+            self.todos.insert(todo.id, Arc::new(todo));
+            restored += 1;
         }
+        self.renumber_orders();
 
-        count
+        restored
     }
 
-    /// Returns the count of active (not completed) todos.
+    /// Returns the count of active (not completed) todos, archived ones aside.
     pub fn active_count(&self) -> usize {
-        self.todos.values().filter(|todo| !todo.completed).count()
+        self.todos
+            .values()
+            .filter(|todo| !todo.archived && !todo.completed)
+            .count()
     }
 
-    /// Returns the count of completed todos.
+    /// Returns the count of completed todos, archived ones aside.
     pub fn completed_count(&self) -> usize {
-        self.todos.values().filter(|todo| todo.completed).count()
+        self.todos
+            .values()
+            .filter(|todo| !todo.archived && todo.completed)
+            .count()
+    }
+
+    /// Returns the count of overdue todos on the given local date, archived
+    /// ones aside.
+    pub fn overdue_count(&self, today: NaiveDate) -> usize {
+        self.todos
+            .values()
+            .filter(|todo| !todo.archived && todo.is_overdue(today))
+            .count()
     }
 
-    /// Returns the total number of todos.
+    /// Returns the total number of todos, archived ones included.
     ///
     /// This could be used in the future for statistics or pagination.
     #[allow(dead_code)]
@@ -315,19 +1817,367 @@ impl TodoList {
         self.todos.len()
     }
 
-    /// Sets a due date for a todo.
-    pub fn set_due_date(&mut self, id: usize, date: Option<DateTime<Utc>>) -> bool {
-        if let Some(todo) = self.todos.get_mut(&id) {
-            todo.set_due_date(date);
+    /// Adds copies of todos from elsewhere, such as a shared snapshot.
+    ///
+    /// The copies get new ids at the end of the list and keep their text,
+    /// completion, due date, tags and notes. Todos whose text is not valid
+    /// are skipped, and links to other todos are dropped since their ids
+    /// mean nothing in this list.
+    ///
+    /// # Returns
+    /// The number of todos added
+    #[instrument(level = "debug", skip_all, fields(count = todos.len()))]
+    pub fn add_copies(&mut self, todos: &[Todo]) -> usize {
+        let mut added = 0;
+        for source in todos {
+            let Ok(text) = text::validate_todo_text(&source.text) else {
+                continue;
+            };
+            let id = self.add(text);
+            if let Some(todo) = self.todos.get_mut(&id).map(Arc::make_mut) {
+                todo.completed = source.completed;
+                todo.completed_at = source.completed_at;
+                todo.due_date = source.due_date;
+                todo.tags = source.tags.clone();
+                todo.notes = source.notes.clone();
+            }
+            added += 1;
+        }
+        added
+    }
+
+    /// Replaces the synced fields of a todo with those of its copy on a sync server.
+    ///
+    /// The text, completion, due date, tags and remote link are taken from
+    /// `source`; the id, position, notes and lock are kept. Locked todos keep
+    /// their text.
+    ///
+    /// # Returns
+    /// `true` if the todo was found
+    #[allow(dead_code)]
+    #[instrument(level = "debug", skip(self, source))]
+    pub fn update_from_remote(&mut self, id: usize, source: &Todo) -> bool {
+        let Some(todo) = self.todos.get_mut(&id).map(Arc::make_mut) else {
+            return false;
+        };
+        if !todo.locked {
+            todo.text = source.text.clone();
+        }
+        todo.completed = source.completed;
+        todo.completed_at = source.completed_at;
+        todo.due_date = source.due_date;
+        todo.tags = source.tags.clone();
+        todo.remote = source.remote.clone();
+        todo.updated_at = None;
+        true
+    }
+
+    /// Links a todo to its copy on a sync server, or unlinks it with `None`.
+    ///
+    /// # Returns
+    /// `true` if the todo was found
+    #[allow(dead_code)]
+    #[instrument(level = "debug", skip(self, link))]
+    pub fn set_remote(&mut self, id: usize, link: Option<RemoteLink>) -> bool {
+        match self.todos.get_mut(&id).map(Arc::make_mut) {
+            Some(todo) => {
+                todo.remote = link;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Gets a todo by its ID.
+    #[allow(dead_code)]
+    pub fn get(&self, id: usize) -> Option<&Todo> {
+        self.todos.get(&id).map(Arc::as_ref)
+    }
+
+    /// Replaces the notes of a todo.
+    #[instrument(level = "debug", skip(self, notes), fields(len = notes.chars().count()))]
+    pub fn set_notes(&mut self, id: usize, notes: String) -> bool {
+        if let Some(todo) = self.todos.get_mut(&id).map(Arc::make_mut) {
+            todo.notes = notes;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Sets or clears the link of a todo.
+    ///
+    /// The link is stored as given; callers validate it with
+    /// [`validate_url`](crate::utils::text::validate_url) first.
+    #[instrument(level = "debug", skip(self, url), fields(set = url.is_some()))]
+    pub fn set_url(&mut self, id: usize, url: Option<String>) -> bool {
+        if let Some(todo) = self.todos.get_mut(&id).map(Arc::make_mut) {
+            todo.url = url;
             true
         } else {
             false
         }
     }
 
+    /// Adds a subtask to the end of a todo's subtasks.
+    ///
+    /// # Returns
+    /// * `Some(id)` with the id of the new subtask
+    /// * `None` if no todo with the given id exists
+    #[instrument(level = "debug", skip(self, text), fields(len = text.chars().count()))]
+    pub fn add_subtask(&mut self, todo_id: usize, text: String) -> Option<usize> {
+        let todo = self.todos.get_mut(&todo_id).map(Arc::make_mut)?;
+        let id = todo
+            .subtasks
+            .iter()
+            .map(|subtask| subtask.id)
+            .max()
+            .map_or(1, |id| id + 1);
+        todo.subtasks.push(Subtask {
+            id,
+            text,
+            completed: false,
+        });
+        Some(id)
+    }
+
+    /// Toggles whether a subtask is completed; the todo itself is left as it is.
+    ///
+    /// # Returns
+    /// * `true` if the subtask was toggled
+    /// * `false` if the todo or the subtask does not exist
+    #[instrument(level = "debug", skip(self))]
+    pub fn toggle_subtask(&mut self, todo_id: usize, subtask_id: usize) -> bool {
+        let subtask = self
+            .todos
+            .get_mut(&todo_id)
+            .map(Arc::make_mut)
+            .and_then(|todo| {
+                todo.subtasks
+                    .iter_mut()
+                    .find(|subtask| subtask.id == subtask_id)
+            });
+        match subtask {
+            Some(subtask) => {
+                subtask.completed = !subtask.completed;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes a subtask from a todo.
+    ///
+    /// # Returns
+    /// * `true` if the subtask was removed
+    /// * `false` if the todo or the subtask does not exist
+    #[instrument(level = "debug", skip(self))]
+    pub fn remove_subtask(&mut self, todo_id: usize, subtask_id: usize) -> bool {
+        let Some(todo) = self.todos.get_mut(&todo_id).map(Arc::make_mut) else {
+            return false;
+        };
+        let before = todo.subtasks.len();
+        todo.subtasks.retain(|subtask| subtask.id != subtask_id);
+        todo.subtasks.len() != before
+    }
+
+    /// Marks a todo as followed up, counting as a change so it stops waiting.
+    ///
+    /// # Arguments
+    /// * `id` - The todo to bump
+    /// * `note_date` - When given, a dated follow-up line is added to the notes
+    ///
+    /// # Returns
+    /// * `true` if the todo was found
+    /// * `false` if no todo with the given id exists
+    #[instrument(level = "debug", skip(self))]
+    pub fn bump(&mut self, id: usize, note_date: Option<NaiveDate>) -> bool {
+        let Some(todo) = self.todos.get_mut(&id).map(Arc::make_mut) else {
+            return false;
+        };
+        if let Some(date) = note_date {
+            todo.notes = follow_up::append_follow_up_note(&todo.notes, date);
+        }
+        todo.touch();
+        true
+    }
+
+    /// Creates a todo from a line of another todo's notes.
+    ///
+    /// The new todo gets the line's text without its bullet, the parent's
+    /// tags and a `derived_from` link back to the parent. It is added at the
+    /// end of the list.
+    ///
+    /// # Arguments
+    /// * `parent_id` - The todo whose notes hold the line
+    /// * `line_index` - The 0-based index of the line in the notes
+    /// * `strike` - Whether to strike the line through in the parent's notes
+    ///
+    /// # Returns
+    /// The id of the new todo, or `None` if the parent or line does not
+    /// exist, the line was already extracted, or its text is not valid
+    #[instrument(level = "debug", skip(self))]
+    pub fn extract_from_notes(
+        &mut self,
+        parent_id: usize,
+        line_index: usize,
+        strike: bool,
+    ) -> Option<usize> {
+        let parent = self.todos.get(&parent_id)?;
+        let line = parent.notes.split('\n').nth(line_index)?;
+        if notes::is_struck(line) {
+            return None;
+        }
+        let todo_text = text::validate_todo_text(notes::line_text(line)).ok()?;
+        let tags = parent.tags.clone();
+
+        if strike {
+            let struck = notes::strike_line(&parent.notes, line_index);
+            self.set_notes(parent_id, struck);
+        }
+        let id = self.add(todo_text);
+        if let Some(todo) = self.todos.get_mut(&id).map(Arc::make_mut) {
+            todo.tags = tags;
+            todo.derived_from = Some(parent_id);
+        }
+        Some(id)
+    }
+
+    /// Sets a due date for a todo, or clears it with `None`.
+    ///
+    /// Dates outside the default [`DueDateLimits`] are refused; see
+    /// [`TodoList::set_due_date_within`].
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use todo::models::{TodoError, TodoList};
+    ///
+    /// let mut list = TodoList::new();
+    /// let id = list.add("File taxes".to_string());
+    /// assert!(list.set_due_date(id, Some(Utc::now() + Duration::days(30))).is_ok());
+    /// assert!(matches!(
+    ///     list.set_due_date(id, Some(Utc::now() + Duration::days(365 * 60))),
+    ///     Err(TodoError::InvalidDueDate(..))
+    /// ));
+    /// assert!(list.set_due_date(id, None).is_ok());
+    /// assert_eq!(list.set_due_date(99, None), Err(TodoError::NotFound(99)));
+    /// ```
+    pub fn set_due_date(
+        &mut self,
+        id: usize,
+        date: Option<DateTime<Utc>>,
+    ) -> Result<(), TodoError> {
+        self.set_due_date_within(id, date, DueDateLimits::default(), Utc::now())
+    }
+
+    /// Sets a due date for a todo if it falls within the given window.
+    ///
+    /// # Arguments
+    /// * `id` - The todo to change
+    /// * `date` - The due date, or `None` to clear it, which is always allowed
+    /// * `limits` - The window the due date must fall within
+    /// * `now` - The current time, from which the window's end counts
+    #[instrument(level = "debug", skip(self))]
+    pub fn set_due_date_within(
+        &mut self,
+        id: usize,
+        date: Option<DateTime<Utc>>,
+        limits: DueDateLimits,
+        now: DateTime<Utc>,
+    ) -> Result<(), TodoError> {
+        let todo = self.todos.get_mut(&id).ok_or(TodoError::NotFound(id))?;
+        limits
+            .check(date, now)
+            .map_err(|error| TodoError::InvalidDueDate(id, error))?;
+        if todo.due_date != date {
+            Arc::make_mut(todo).set_due_date(date);
+        }
+        Ok(())
+    }
+
+    /// Moves the due dates of several todos by a number of days, within
+    /// the default limits.
+    ///
+    /// Todos without a due date and unknown ids are skipped. Moving a date
+    /// later counts as postponing the todo. Shifting the shifted ids back by
+    /// `-days` undoes the change.
+    ///
+    /// # Arguments
+    /// * `ids` - The todos to shift
+    /// * `days` - The number of days, negative to move dates earlier
+    ///
+    /// # Returns
+    /// The todos whose due date moved, and those refused because the new
+    /// date would leave the allowed window
+    pub fn shift_due_dates(&mut self, ids: &[usize], days: i64) -> ShiftedDueDates {
+        self.shift_due_dates_within(ids, days, DueDateLimits::default(), Utc::now())
+    }
+
+    /// Moves the due dates of several todos by a number of days, as
+    /// [`TodoList::shift_due_dates`] does, if the new dates fall within the
+    /// given window.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use todo::models::TodoList;
+    /// use todo::models::due_limits::DueDateLimits;
+    ///
+    /// let now = Utc.with_ymd_and_hms(2025, 3, 12, 9, 0, 0).unwrap();
+    /// let mut list = TodoList::new();
+    /// let taxes = list.add("File taxes".to_string());
+    /// let party = list.add("Plan the party".to_string());
+    /// list.set_due_date(taxes, Some(now)).unwrap();
+    /// list.set_due_date(party, Some(Utc.with_ymd_and_hms(1970, 1, 2, 0, 0, 0).unwrap())).unwrap();
+    ///
+    /// let moved = list.shift_due_dates_within(&[taxes, party], -7, DueDateLimits::default(), now);
+    /// assert_eq!(moved.shifted, [taxes]);
+    /// assert_eq!(moved.refused, [party]);
+    /// ```
+    #[instrument(level = "debug", skip(self, ids), fields(count = ids.len()))]
+    pub fn shift_due_dates_within(
+        &mut self,
+        ids: &[usize],
+        days: i64,
+        limits: DueDateLimits,
+        now: DateTime<Utc>,
+    ) -> ShiftedDueDates {
+        let offset = Duration::try_days(days);
+        let mut outcome = ShiftedDueDates::default();
+        for &id in ids {
+            let Some(due) = self.todos.get(&id).and_then(|todo| todo.due_date) else {
+                continue;
+            };
+            let moved = offset
+                .and_then(|offset| due.checked_add_signed(offset))
+                .map(|moved| self.set_due_date_within(id, Some(moved), limits, now));
+            match moved {
+                Some(Ok(())) => outcome.shifted.push(id),
+                _ => outcome.refused.push(id),
+            }
+        }
+        outcome
+    }
+
+    /// Gets the earliest and latest due date among the given todos.
+    ///
+    /// # Returns
+    /// The inclusive range `(earliest, latest)`, or `None` if none of the
+    /// todos has a due date
+    pub fn due_date_range(&self, ids: &[usize]) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        ids.iter()
+            .filter_map(|id| self.todos.get(id)?.due_date)
+            .fold(None, |range, due| match range {
+                None => Some((due, due)),
+                Some((first, last)) => Some((first.min(due), last.max(due))),
+            })
+    }
+
     /// Adds a tag to a todo.
+    #[instrument(level = "debug", skip(self, tag), fields(tag_len = tag.chars().count()))]
     pub fn add_tag(&mut self, id: usize, tag: String) -> bool {
-        if let Some(todo) = self.todos.get_mut(&id) {
+        if let Some(todo) = self.todos.get_mut(&id).map(Arc::make_mut) {
             todo.add_tag(tag);
             true
         } else {
@@ -335,9 +2185,28 @@ impl TodoList {
         }
     }
 
+    /// Makes an overdue todo high priority, once.
+    ///
+    /// # Returns
+    /// * `true` if the todo was escalated
+    /// * `false` if it was escalated before or no todo with the given id exists
+    #[instrument(level = "debug", skip(self))]
+    pub fn escalate(&mut self, id: usize) -> bool {
+        match self.todos.get_mut(&id) {
+            Some(todo) if !todo.escalated => {
+                let todo = Arc::make_mut(todo);
+                todo.escalated = true;
+                todo.add_tag(HIGH_PRIORITY_TAG.to_string());
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Removes a tag from a todo.
+    #[instrument(level = "debug", skip(self, tag), fields(tag_len = tag.chars().count()))]
     pub fn remove_tag(&mut self, id: usize, tag: &str) -> bool {
-        if let Some(todo) = self.todos.get_mut(&id) {
+        if let Some(todo) = self.todos.get_mut(&id).map(Arc::make_mut) {
             todo.remove_tag(tag);
             true
         } else {
@@ -345,6 +2214,160 @@ impl TodoList {
         }
     }
 
+    /// Adds a tag to several todos at once.
+    ///
+    /// Todos that already have the tag are left as they are, and unknown ids
+    /// are skipped. Removing the tag from the todos that changed undoes it.
+    ///
+    /// # Returns
+    /// The number of todos that got the tag
+    ///
+    /// # Example
+    /// ```
+    /// # use todo::models::TodoList;
+    /// let mut list = TodoList::new();
+    /// let milk = list.add("Buy milk".to_string());
+    /// let bread = list.add("Buy bread".to_string());
+    /// list.add_tag(milk, "shopping".to_string());
+    ///
+    /// assert_eq!(list.add_tag_many(&[milk, bread], "shopping"), 1);
+    /// assert_eq!(list.get(milk).unwrap().tags, ["shopping"]);
+    /// assert_eq!(list.remove_tag_many(&[milk, bread], "shopping"), 2);
+    /// ```
+    #[instrument(level = "debug", skip(self, ids, tag), fields(count = ids.len()))]
+    pub fn add_tag_many(&mut self, ids: &[usize], tag: &str) -> usize {
+        let mut added = 0;
+        for id in ids {
+            if let Some(todo) = self.todos.get_mut(id)
+                && !todo.tags.iter().any(|t| t == tag)
+            {
+                Arc::make_mut(todo).add_tag(tag.to_string());
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Removes a tag from several todos at once.
+    ///
+    /// Todos without the tag and unknown ids are skipped.
+    ///
+    /// # Returns
+    /// The number of todos that lost the tag
+    #[instrument(level = "debug", skip(self, ids, tag), fields(count = ids.len()))]
+    pub fn remove_tag_many(&mut self, ids: &[usize], tag: &str) -> usize {
+        let mut removed = 0;
+        for id in ids {
+            if let Some(todo) = self.todos.get_mut(id)
+                && todo.tags.iter().any(|t| t == tag)
+            {
+                Arc::make_mut(todo).remove_tag(tag);
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Removes a tag from every todo that has it, those in the trash included.
+    ///
+    /// The todos themselves stay, only the tag goes, so it is no longer
+    /// listed by [`all_tags`](Self::all_tags).
+    ///
+    /// # Returns
+    /// The number of todos that lost the tag
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::TodoList;
+    ///
+    /// let mut list = TodoList::new();
+    /// let milk = list.add("Buy milk".to_string());
+    /// list.add_tag(milk, "errands".to_string());
+    /// assert_eq!(list.remove_tag_everywhere("errands"), 1);
+    /// assert!(list.all_tags().is_empty());
+    /// assert_eq!(list.all().len(), 1);
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn remove_tag_everywhere(&mut self, tag: &str) -> usize {
+        let mut removed = 0;
+        for todo in self.todos.values_mut().chain(self.trash.iter_mut()) {
+            if todo.tags.iter().any(|t| t == tag) {
+                Arc::make_mut(todo).remove_tag(tag);
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Adds the tags the auto-tag rules give a todo's text.
+    ///
+    /// Tags the todo already has are not added again, so applying the rules
+    /// repeatedly changes nothing.
+    ///
+    /// # Returns
+    /// * `true` if at least one tag was added
+    /// * `false` if nothing changed or no todo with the given id exists
+    #[instrument(level = "debug", skip(self, tagger))]
+    pub fn apply_auto_tags(&mut self, id: usize, tagger: &AutoTagger) -> bool {
+        let Some(todo) = self.todos.get_mut(&id) else {
+            return false;
+        };
+        let new_tags: Vec<String> = tagger
+            .tags_for(&todo.text)
+            .into_iter()
+            .filter(|tag| !todo.tags.iter().any(|t| t == tag))
+            .map(str::to_string)
+            .collect();
+        if new_tags.is_empty() {
+            return false;
+        }
+        let todo = Arc::make_mut(todo);
+        for tag in new_tags {
+            todo.add_tag(tag);
+        }
+        true
+    }
+
+    /// Gives a todo the due date its tags' rules set, if it has none.
+    ///
+    /// Called after tags are added; see [`apply_tag_rules`].
+    ///
+    /// # Returns
+    /// * `true` if a due date was set
+    /// * `false` if the todo already had one, no rule matched, or no todo
+    ///   with the given id exists
+    #[instrument(level = "debug", skip(self, rules))]
+    pub fn apply_tag_due_rules(
+        &mut self,
+        id: usize,
+        rules: &[TagDueRule],
+        today: NaiveDate,
+    ) -> bool {
+        let Some(todo) = self.todos.get_mut(&id) else {
+            return false;
+        };
+        let Some(due) = apply_tag_rules(todo, rules, today) else {
+            return false;
+        };
+        Arc::make_mut(todo).set_due_date(Some(due));
+        true
+    }
+
+    /// Applies the auto-tag rules to every todo.
+    ///
+    /// # Returns
+    /// The number of todos that gained at least one tag
+    #[instrument(level = "debug", skip_all)]
+    pub fn apply_auto_tags_to_all(&mut self, tagger: &AutoTagger) -> usize {
+        if tagger.is_empty() {
+            return 0;
+        }
+        let ids: Vec<usize> = self.todos.keys().copied().collect();
+        ids.into_iter()
+            .filter(|id| self.apply_auto_tags(*id, tagger))
+            .count()
+    }
+
     /// Gets all unique tags across all todos.
     pub fn all_tags(&self) -> Vec<String> {
         let mut tags = std::collections::HashSet::new();
@@ -355,6 +2378,38 @@ impl TodoList {
         }
         tags.into_iter().collect()
     }
+
+    /// Gets the tags in use together with some defaults, sorted and without repeats.
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::TodoList;
+    ///
+    /// let mut list = TodoList::new();
+    /// let id = list.add("Call the plumber".to_string());
+    /// list.add_tag(id, "home".to_string());
+    /// list.add_tag(id, "urgent".to_string());
+    /// assert_eq!(list.sorted_tags(&["work", "home"]), ["home", "urgent", "work"]);
+    /// ```
+    pub fn sorted_tags(&self, defaults: &[&str]) -> Vec<String> {
+        let mut tags = self.all_tags();
+        tags.extend(defaults.iter().map(|tag| tag.to_string()));
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+
+    /// Gets the tags the given todos carry, sorted and without repeats.
+    pub fn tags_of(&self, ids: &[usize]) -> Vec<String> {
+        let mut tags: Vec<String> = ids
+            .iter()
+            .filter_map(|id| self.todos.get(id))
+            .flat_map(|todo| todo.tags.iter().cloned())
+            .collect();
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
 }
 
 #[cfg(test)]
@@ -363,30 +2418,29 @@ mod tests {
 
     #[test]
     fn test_filtered() {
-        let mut todo_list = TodoList {
-            todos: HashMap::new(),
-            next_id: 1,
-        };
+        let mut todo_list = TodoList::new();
 
         todo_list
             .todos
-            .insert(1, Todo::new(1, "Active todo".to_string()));
+            .insert(1, Arc::new(Todo::new(1, "Active todo".to_string())));
         todo_list
             .todos
-            .insert(2, Todo::new(2, "Completed todo".to_string()));
-        todo_list.todos.get_mut(&2).unwrap().toggle();
+            .insert(2, Arc::new(Todo::new(2, "Completed todo".to_string())));
+        Arc::make_mut(todo_list.todos.get_mut(&2).unwrap()).toggle();
+
+        let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
 
         // Test All filter
-        let filtered = todo_list.filtered(FilterState::All);
+        let filtered = todo_list.filtered(FilterState::All, today);
         assert_eq!(filtered.len(), 2);
 
         // Test Active filter
-        let filtered = todo_list.filtered(FilterState::Active);
+        let filtered = todo_list.filtered(FilterState::Active, today);
         assert_eq!(filtered.len(), 1);
         assert_eq!(filtered[0].id, 1);
 
         // Test Completed filter
-        let filtered = todo_list.filtered(FilterState::Completed);
+        let filtered = todo_list.filtered(FilterState::Completed, today);
         assert_eq!(filtered.len(), 1);
         assert_eq!(filtered[0].id, 2);
     }
@@ -397,14 +2451,14 @@ mod tests {
 
         todo_list
             .todos
-            .insert(1, Todo::new(1, "Active todo".to_string()));
+            .insert(1, Arc::new(Todo::new(1, "Active todo".to_string())));
         todo_list
             .todos
-            .insert(2, Todo::new(2, "Completed todo".to_string()));
-        todo_list.todos.get_mut(&2).unwrap().toggle();
+            .insert(2, Arc::new(Todo::new(2, "Completed todo".to_string())));
+        Arc::make_mut(todo_list.todos.get_mut(&2).unwrap()).toggle();
 
-        let cleared_count = todo_list.clear_completed();
-        assert_eq!(cleared_count, 1);
+        let cleared = todo_list.clear_completed();
+        assert_eq!(cleared.removed.len(), 1);
         assert_eq!(todo_list.todos.len(), 1);
         assert!(todo_list.todos.contains_key(&1));
         assert!(!todo_list.todos.contains_key(&2));
@@ -416,11 +2470,11 @@ mod tests {
 
         todo_list
             .todos
-            .insert(1, Todo::new(1, "Active todo".to_string()));
+            .insert(1, Arc::new(Todo::new(1, "Active todo".to_string())));
         todo_list
             .todos
-            .insert(2, Todo::new(2, "Completed todo".to_string()));
-        todo_list.todos.get_mut(&2).unwrap().toggle();
+            .insert(2, Arc::new(Todo::new(2, "Completed todo".to_string())));
+        Arc::make_mut(todo_list.todos.get_mut(&2).unwrap()).toggle();
 
         assert_eq!(todo_list.active_count(), 1);
     }
@@ -431,11 +2485,11 @@ mod tests {
 
         todo_list
             .todos
-            .insert(1, Todo::new(1, "Active todo".to_string()));
+            .insert(1, Arc::new(Todo::new(1, "Active todo".to_string())));
         todo_list
             .todos
-            .insert(2, Todo::new(2, "Completed todo".to_string()));
-        todo_list.todos.get_mut(&2).unwrap().toggle();
+            .insert(2, Arc::new(Todo::new(2, "Completed todo".to_string())));
+        Arc::make_mut(todo_list.todos.get_mut(&2).unwrap()).toggle();
 
         assert_eq!(todo_list.completed_count(), 1);
     }
@@ -446,10 +2500,10 @@ mod tests {
 
         todo_list
             .todos
-            .insert(1, Todo::new(1, "Todo 1".to_string()));
+            .insert(1, Arc::new(Todo::new(1, "Todo 1".to_string())));
         todo_list
             .todos
-            .insert(2, Todo::new(2, "Todo 2".to_string()));
+            .insert(2, Arc::new(Todo::new(2, "Todo 2".to_string())));
 
         assert_eq!(todo_list.total_count(), 2);
     }
@@ -502,4 +2556,34 @@ mod tests {
         let result = list.reorder(id1, 999);
         assert!(!result);
     }
+
+    #[test]
+    fn test_patch_keeps_only_the_todos_a_change_touched() {
+        let mut list = TodoList::new();
+        for n in 0..1000 {
+            list.add(format!("Todo {n}"));
+        }
+        let before = list.clone();
+        assert!(list.set_notes(500, "Ask about the tap".to_string()));
+
+        let patch = list.patch_back_to(&before);
+        assert_eq!(patch.todos.len(), 1);
+        assert!(patch.trash.is_none() && patch.snoozes.is_none());
+        assert!(
+            list.todos
+                .iter()
+                .filter(|(id, _)| **id != 500)
+                .all(|(id, todo)| Arc::ptr_eq(todo, &before.todos[id])),
+            "unchanged todos are shared, not copied"
+        );
+
+        // Moving a todo to the trash touches it, the orders after it and the trash
+        let before = list.clone();
+        list.move_to_trash(999).unwrap();
+        let patch = list.patch_back_to(&before);
+        assert_eq!(patch.todos.len(), 2);
+        assert!(patch.trash.is_some());
+        list.apply_patch(patch);
+        assert_eq!(list, before);
+    }
 }
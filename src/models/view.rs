@@ -0,0 +1,593 @@
+//! The part of the list shown on screen
+//!
+//! The status filter, the quick filter pill, the day picked in the planner
+//! strip, the selected tag and the search query each narrow the list down. A
+//! todo is visible only if it passes all of them, so list-wide actions such
+//! as "Clear completed" can be limited to what the user is looking at. The
+//! filters the user picks are kept together in [`FilterCriteria`], and
+//! [`ListViewModel`] turns them into the rows the list component renders, or
+//! the reason it shows none.
+
+use super::{FilterState, QuickFilter, SearchQuery, Todo, WeekStart};
+use super::{session, staleness};
+use crate::utils::dates;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Which tags a todo needs to be shown.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TagFilter {
+    /// Todos with any tags or none
+    #[default]
+    All,
+    /// Todos with this tag
+    Tag(String),
+    /// Todos without tags
+    Untagged,
+}
+
+impl TagFilter {
+    /// Checks if a todo has the tags this filter asks for.
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::{TagFilter, Todo};
+    ///
+    /// let mut todo = Todo::new(1, "Buy milk".to_string());
+    /// assert!(TagFilter::Untagged.matches(&todo));
+    /// todo.tags.push("shopping".to_string());
+    /// assert!(!TagFilter::Untagged.matches(&todo));
+    /// assert!(TagFilter::Tag("shopping".to_string()).matches(&todo));
+    /// assert!(TagFilter::All.matches(&todo));
+    /// ```
+    pub fn matches(&self, todo: &Todo) -> bool {
+        match self {
+            TagFilter::All => true,
+            TagFilter::Tag(tag) => todo.tags.contains(tag),
+            TagFilter::Untagged => todo.tags.is_empty(),
+        }
+    }
+}
+
+/// Tag filter as found in stored data: the current form, or the single tag,
+/// if any, written by earlier versions.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredTagFilter {
+    Current(TagFilter),
+    Single(Option<String>),
+}
+
+/// Reads a stored tag filter in either form.
+fn deserialize_tag_filter<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<TagFilter, D::Error> {
+    Ok(match StoredTagFilter::deserialize(deserializer)? {
+        StoredTagFilter::Current(tag) => tag,
+        StoredTagFilter::Single(tag) => tag.map_or(TagFilter::All, TagFilter::Tag),
+    })
+}
+
+/// The filters the user picked, edited together and remembered across
+/// restarts.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilterCriteria {
+    /// The All/Active/Completed selection, or a due filter
+    pub filter: FilterState,
+    /// The selected tag, or untagged todos only
+    #[serde(deserialize_with = "deserialize_tag_filter")]
+    pub tag: TagFilter,
+    /// The text typed into the search box
+    pub search_text: String,
+    /// The selected quick filter pill, if any
+    pub quick_filter: Option<QuickFilter>,
+    /// The day picked in the planner strip, if any
+    pub due_on: Option<NaiveDate>,
+}
+
+impl FilterCriteria {
+    /// Checks if a todo passes every filter at the given time, with weeks
+    /// starting on Monday.
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::{FilterCriteria, FilterState, Todo};
+    /// use todo::utils::clock::{Clock, SystemClock};
+    ///
+    /// let (now, today) = (SystemClock.now_utc(), SystemClock.today_local());
+    /// let criteria = FilterCriteria {
+    ///     filter: FilterState::Active,
+    ///     search_text: "milk".to_string(),
+    ///     ..FilterCriteria::default()
+    /// };
+    /// assert!(criteria.matches(&Todo::new(1, "Buy milk".to_string()), now, today));
+    /// assert!(!criteria.matches(&Todo::new(2, "Buy bread".to_string()), now, today));
+    /// ```
+    #[allow(dead_code)]
+    pub fn matches(&self, todo: &Todo, now: DateTime<Utc>, today: NaiveDate) -> bool {
+        self.view().matches(todo, now, today)
+    }
+
+    /// Gets the view these filters give, with the default week start, stale
+    /// age and no session start; set those with struct update syntax.
+    pub fn view(&self) -> ListView {
+        ListView {
+            filter: self.filter,
+            quick_filter: self.quick_filter,
+            due_on: self.due_on,
+            tag: self.tag.clone(),
+            query: SearchQuery::parse(&self.search_text),
+            ..ListView::default()
+        }
+    }
+}
+
+/// The filters currently applied to the list.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ListView {
+    /// The All/Active/Completed selection, or a due filter
+    pub filter: FilterState,
+    /// The selected quick filter pill, if any
+    pub quick_filter: Option<QuickFilter>,
+    /// The day picked in the planner strip, showing only todos due that day
+    pub due_on: Option<NaiveDate>,
+    /// The selected tag, or untagged todos only
+    pub tag: TagFilter,
+    /// The parsed search text
+    pub query: SearchQuery,
+    /// The first day of the week, which decides where "This week" ends
+    pub week_start: WeekStart,
+    /// Days without changes after which `is:stale` lists an open todo
+    pub stale_after_days: u32,
+    /// When the app was started, from which `is:new` lists todos; without it
+    /// no todo counts as added this session
+    pub session_start: Option<DateTime<Utc>>,
+}
+
+impl ListView {
+    /// Checks if a todo is visible with these filters.
+    ///
+    /// # Arguments
+    /// * `todo` - The todo to check
+    /// * `now` - The current time, for the due filters and relative search
+    ///   operators
+    /// * `today` - The current local date, for the quick filters
+    pub fn matches(&self, todo: &Todo, now: DateTime<Utc>, today: NaiveDate) -> bool {
+        self.filter.matches(todo, today, self.week_start)
+            && self
+                .quick_filter
+                .is_none_or(|pill| pill.matches(todo, today, self.week_start))
+            && self
+                .due_on
+                .is_none_or(|day| todo.due_date.is_some_and(|due| dates::due_day(due) == day))
+            && self.tag.matches(todo)
+            && self.query.matches(todo, now)
+            && (!self.query.stale || staleness::is_stale(todo, now, self.stale_after_days))
+            && (!self.query.added_this_session
+                || self
+                    .session_start
+                    .is_some_and(|start| session::is_added_since(todo, start)))
+    }
+
+    /// Names the filters applied, for file names of exports of this view.
+    ///
+    /// # Example
+    /// ```
+    /// use todo::models::{FilterState, ListView, TagFilter};
+    ///
+    /// let view = ListView {
+    ///     filter: FilterState::Active,
+    ///     tag: TagFilter::Tag("Work".to_string()),
+    ///     ..ListView::default()
+    /// };
+    /// assert_eq!(view.name_parts(), ["active", "Work"]);
+    /// assert!(ListView::default().name_parts().is_empty());
+    /// ```
+    pub fn name_parts(&self) -> Vec<String> {
+        let mut parts = Vec::new();
+        match self.filter {
+            FilterState::All => {}
+            FilterState::Active => parts.push("active".to_string()),
+            FilterState::Completed => parts.push("completed".to_string()),
+            FilterState::Archived => parts.push("archived".to_string()),
+            FilterState::Trash => parts.push("trash".to_string()),
+            FilterState::DueToday => parts.push("due-today".to_string()),
+            FilterState::DueThisWeek => parts.push("due-this-week".to_string()),
+            FilterState::Overdue => parts.push("overdue".to_string()),
+        }
+        if let Some(pill) = self.quick_filter {
+            parts.push(pill.label().to_lowercase());
+        }
+        if let Some(day) = self.due_on {
+            parts.push(format!("due-{}", day.format("%Y-%m-%d")));
+        }
+        match &self.tag {
+            TagFilter::All => {}
+            TagFilter::Tag(tag) => parts.push(tag.clone()),
+            TagFilter::Untagged => parts.push("untagged".to_string()),
+        }
+        if self.query != SearchQuery::default() {
+            parts.push("search".to_string());
+        }
+        parts
+    }
+
+    /// Gets the ids of the visible todos among the given ones, in the same
+    /// order, or the longest untouched first for an `is:stale` search.
+    pub fn visible_ids<'a>(
+        &self,
+        todos: impl IntoIterator<Item = &'a Todo>,
+        now: DateTime<Utc>,
+        today: NaiveDate,
+    ) -> Vec<usize> {
+        self.visible(todos, now, today)
+            .into_iter()
+            .map(|todo| todo.id)
+            .collect()
+    }
+
+    /// Keeps the visible todos in screen order.
+    fn visible<'a, T: Borrow<Todo> + 'a>(
+        &self,
+        todos: impl IntoIterator<Item = &'a T>,
+        now: DateTime<Utc>,
+        today: NaiveDate,
+    ) -> Vec<&'a T> {
+        let mut visible: Vec<&T> = todos
+            .into_iter()
+            .filter(|todo| self.matches((*todo).borrow(), now, today))
+            .collect();
+        if self.query.stale {
+            visible.sort_by_key(|todo| Reverse(staleness::staleness((*todo).borrow(), now)));
+        }
+        visible
+    }
+}
+
+/// Why the list shows no rows, deciding the message shown instead.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EmptyState {
+    /// Nothing in the trash matches the search
+    Trash,
+    /// The trash is empty
+    TrashEmpty,
+    /// There are no todos at all
+    NoTodos,
+    /// Nothing matches the search text
+    Search(String),
+    /// Nothing matches the quick filter pill
+    QuickFilter(QuickFilter),
+    /// Nothing is due on the day picked in the planner strip
+    DueOn(NaiveDate),
+    /// Nothing has the selected tag
+    Tag,
+    /// Every todo that matches has tags and only untagged ones are shown
+    Untagged,
+    /// Every todo is completed and only active ones are shown
+    AllDone,
+    /// No todo is completed yet and only completed ones are shown
+    NoneCompleted,
+    /// No todo is archived and only archived ones are shown
+    NoneArchived,
+    /// No open todo is overdue and only overdue ones are shown
+    NoneOverdue,
+    /// Nothing matches the status filter
+    NoMatch,
+}
+
+impl fmt::Display for EmptyState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmptyState::Trash => write!(f, "No deleted todos match your search."),
+            EmptyState::TrashEmpty => write!(f, "The trash is empty."),
+            EmptyState::NoTodos => write!(f, "Add your first todo above! ✨"),
+            EmptyState::Search(text) => write!(f, "No todos match your search: '{}'", text),
+            EmptyState::QuickFilter(pill) => {
+                write!(f, "No todos match the {} filter.", pill.label())
+            }
+            EmptyState::DueOn(day) => write!(f, "No todos due on {}.", day.format("%b %-d")),
+            EmptyState::Tag => write!(f, "No todos found with the selected tag."),
+            EmptyState::Untagged => write!(f, "No untagged todos."),
+            EmptyState::AllDone => write!(f, "All tasks done! 🎉"),
+            EmptyState::NoneCompleted => write!(f, "No completed tasks yet."),
+            EmptyState::NoneArchived => write!(f, "No archived tasks."),
+            EmptyState::NoneOverdue => write!(f, "Nothing overdue 🎉"),
+            EmptyState::NoMatch => write!(f, "No tasks match the current filter."),
+        }
+    }
+}
+
+/// One row of the list as shown on screen.
+#[derive(Debug, PartialEq)]
+pub struct ListRow<'a, T> {
+    /// The todo in the row
+    pub todo: &'a T,
+    /// Text of the todo it was extracted from, for its breadcrumb
+    pub parent_text: Option<&'a str>,
+    /// Days it has gone unchanged, when that makes it stale
+    pub stale_days: Option<i64>,
+}
+
+/// What the list shows: its rows in screen order, or why there are none.
+#[derive(Debug, PartialEq)]
+pub struct ListViewModel<'a, T> {
+    /// The visible todos in screen order
+    pub rows: Vec<ListRow<'a, T>>,
+    /// Why nothing is shown, set only when there are no rows
+    pub empty_state: Option<EmptyState>,
+}
+
+impl<'a, T: Borrow<Todo>> ListViewModel<'a, T> {
+    /// Works out the rows of the list and, if there are none, why.
+    ///
+    /// # Arguments
+    /// * `todos` - All todos of the list, or of the trash, in list order
+    /// * `view` - The filters applied to the list
+    /// * `search_text` - The search text as typed, quoted when nothing matches it
+    /// * `in_trash` - Whether the todos come from the trash
+    /// * `now` - The current time, for relative search operators and staleness
+    /// * `today` - The current local date, for the quick filters
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Local, Utc};
+    /// use todo::models::view::{EmptyState, ListViewModel};
+    /// use todo::models::{FilterState, ListView, TodoList};
+    ///
+    /// let mut list = TodoList::new();
+    /// let id = list.add("Water the plants".to_string());
+    /// list.toggle(id);
+    /// let todos = list.all();
+    /// let view = ListView { filter: FilterState::Active, ..ListView::default() };
+    ///
+    /// let model = ListViewModel::compute(&todos, &view, "", false, Utc::now(), Local::now().date_naive());
+    /// assert!(model.rows.is_empty());
+    /// assert_eq!(model.empty_state, Some(EmptyState::AllDone));
+    /// ```
+    pub fn compute(
+        todos: &'a [T],
+        view: &ListView,
+        search_text: &str,
+        in_trash: bool,
+        now: DateTime<Utc>,
+        today: NaiveDate,
+    ) -> Self {
+        // Texts of the todos others were extracted from, for their breadcrumbs
+        let parent_ids: HashSet<usize> = todos
+            .iter()
+            .filter_map(|todo| todo.borrow().derived_from)
+            .collect();
+        let parent_texts: HashMap<usize, &'a str> = todos
+            .iter()
+            .map(|todo| -> &'a Todo { todo.borrow() })
+            .filter(|todo| parent_ids.contains(&todo.id))
+            .map(|parent| (parent.id, parent.text.as_str()))
+            .collect();
+
+        let rows: Vec<ListRow<'a, T>> = view
+            .visible(todos, now, today)
+            .into_iter()
+            .map(|todo| {
+                let item: &Todo = todo.borrow();
+                ListRow {
+                    todo,
+                    parent_text: item
+                        .derived_from
+                        .and_then(|parent_id| parent_texts.get(&parent_id).copied()),
+                    stale_days: staleness::staleness(item, now)
+                        .filter(|_| staleness::is_stale(item, now, view.stale_after_days))
+                        .map(|age| age.num_days()),
+                }
+            })
+            .collect();
+
+        let empty_state = rows
+            .is_empty()
+            .then(|| empty_state(todos.is_empty(), view, search_text, in_trash));
+        Self { rows, empty_state }
+    }
+}
+
+/// Picks why the list is empty, from the most specific reason to the least.
+fn empty_state(no_todos: bool, view: &ListView, search_text: &str, in_trash: bool) -> EmptyState {
+    if in_trash && no_todos {
+        EmptyState::TrashEmpty
+    } else if in_trash {
+        EmptyState::Trash
+    } else if no_todos {
+        EmptyState::NoTodos
+    } else if !search_text.is_empty() {
+        EmptyState::Search(search_text.to_string())
+    } else if let Some(pill) = view.quick_filter {
+        EmptyState::QuickFilter(pill)
+    } else if let Some(day) = view.due_on {
+        EmptyState::DueOn(day)
+    } else if view.tag == TagFilter::Untagged {
+        EmptyState::Untagged
+    } else if view.tag != TagFilter::All {
+        EmptyState::Tag
+    } else {
+        match view.filter {
+            FilterState::Active => EmptyState::AllDone,
+            FilterState::Completed => EmptyState::NoneCompleted,
+            FilterState::Archived => EmptyState::NoneArchived,
+            FilterState::Trash => EmptyState::TrashEmpty,
+            FilterState::Overdue => EmptyState::NoneOverdue,
+            FilterState::All | FilterState::DueToday | FilterState::DueThisWeek => {
+                EmptyState::NoMatch
+            }
+        }
+    }
+}
+
+/// Moves a selection through the visible todos.
+///
+/// # Arguments
+/// * `visible_ids` - The ids of the todos on screen, in screen order
+/// * `selected` - The selected todo, if any
+/// * `step` - How many rows to move, negative to move up
+///
+/// # Returns
+/// The newly selected id, staying at the first or last row rather than
+/// wrapping. Without a visible selection, moving down picks the first row
+/// and moving up the last.
+///
+/// # Example
+/// ```
+/// use todo::models::view::step_selection;
+///
+/// assert_eq!(step_selection(&[4, 2, 7], Some(2), 1), Some(7));
+/// assert_eq!(step_selection(&[4, 2, 7], Some(7), 1), Some(7));
+/// assert_eq!(step_selection(&[4, 2, 7], None, -1), Some(7));
+/// assert_eq!(step_selection(&[], Some(2), 1), None);
+/// ```
+pub fn step_selection(
+    visible_ids: &[usize],
+    selected: Option<usize>,
+    step: isize,
+) -> Option<usize> {
+    let last = visible_ids.len().checked_sub(1)?;
+    let position = match selected.and_then(|id| visible_ids.iter().position(|&v| v == id)) {
+        Some(position) => position.saturating_add_signed(step).min(last),
+        None if step < 0 => last,
+        None => 0,
+    };
+    Some(visible_ids[position])
+}
+
+/// Gets the todo a number key picks, 1 for the first row on screen up to 9.
+///
+/// # Arguments
+/// * `visible_ids` - The ids of the todos on screen, in screen order
+/// * `number` - The number pressed
+///
+/// # Example
+/// ```
+/// use todo::models::view::row_for_number;
+///
+/// assert_eq!(row_for_number(&[4, 2, 7], 1), Some(4));
+/// assert_eq!(row_for_number(&[4, 2, 7], 3), Some(7));
+/// assert_eq!(row_for_number(&[4, 2, 7], 4), None);
+/// assert_eq!(row_for_number(&[4, 2, 7], 0), None);
+/// ```
+pub fn row_for_number(visible_ids: &[usize], number: usize) -> Option<usize> {
+    if !(1..=9).contains(&number) {
+        return None;
+    }
+    visible_ids.get(number - 1).copied()
+}
+
+/// Gets the todo to select after removing some of the visible todos.
+///
+/// The row that moves up into the place of the first removed one is picked,
+/// so the selection stays at the same height; when the removed rows were at
+/// the bottom, the row above them is picked instead.
+///
+/// # Arguments
+/// * `visible_ids` - The ids of the todos on screen, in screen order, before the removal
+/// * `removed` - The ids of the removed todos
+///
+/// # Returns
+/// The id to select, or `None` if no visible todo is left
+///
+/// # Example
+/// ```
+/// use todo::models::view::selection_after_removal;
+///
+/// assert_eq!(selection_after_removal(&[4, 2, 7], &[2]), Some(7));
+/// assert_eq!(selection_after_removal(&[4, 2, 7], &[7]), Some(2));
+/// assert_eq!(selection_after_removal(&[4], &[4]), None);
+/// ```
+pub fn selection_after_removal(visible_ids: &[usize], removed: &[usize]) -> Option<usize> {
+    let remaining: Vec<usize> = visible_ids
+        .iter()
+        .copied()
+        .filter(|id| !removed.contains(id))
+        .collect();
+    let first_removed = visible_ids
+        .iter()
+        .position(|id| removed.contains(id))
+        .unwrap_or(remaining.len());
+    remaining.get(first_removed).or(remaining.last()).copied()
+}
+
+/// Why a todo cannot be moved to the position typed in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PositionError {
+    /// The text is not a whole number
+    NotANumber,
+    /// The number is not between 1 and the number of rows on screen
+    OutOfRange(usize),
+    /// The todo is already at that position
+    SamePosition,
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionError::NotANumber => write!(f, "Enter a position as a number."),
+            PositionError::OutOfRange(count) => {
+                write!(f, "Enter a position from 1 to {}.", count)
+            }
+            PositionError::SamePosition => write!(f, "The todo is already at this position."),
+        }
+    }
+}
+
+/// Gets where in the whole list a todo goes when moved to a position among
+/// the rows on screen.
+///
+/// With filters active the rows on screen are only part of the list, so the
+/// todo takes the place of the one shown at that position, leaving the
+/// hidden todos between them in their order.
+///
+/// # Arguments
+/// * `shown` - The todos on screen, in screen order
+/// * `id` - The todo to move
+/// * `position` - The position typed in, from 1 for the first row
+///
+/// # Returns
+/// The index to pass to [`TodoList::reorder_to_index`](super::TodoList::reorder_to_index),
+/// counting from 0 in list order
+///
+/// # Example
+/// ```
+/// use todo::models::TodoList;
+/// use todo::models::view::{PositionError, index_for_position};
+///
+/// let mut list = TodoList::new();
+/// for text in ["Buy milk", "Call the plumber", "Pay rent"] {
+///     list.add(text.to_string());
+/// }
+/// // The plumber is hidden by a filter
+/// let shown: Vec<_> = list.all().into_iter().filter(|todo| todo.id != 2).collect();
+///
+/// assert_eq!(index_for_position(&shown, 3, "1"), Ok(0));
+/// assert_eq!(index_for_position(&shown, 1, " 2 "), Ok(2));
+/// assert_eq!(index_for_position(&shown, 1, "3"), Err(PositionError::OutOfRange(2)));
+/// assert_eq!(index_for_position(&shown, 1, "1"), Err(PositionError::SamePosition));
+/// ```
+pub fn index_for_position<T: Borrow<Todo>>(
+    shown: &[T],
+    id: usize,
+    position: &str,
+) -> Result<usize, PositionError> {
+    let position: usize = position
+        .trim()
+        .parse()
+        .map_err(|_| PositionError::NotANumber)?;
+    let target = position
+        .checked_sub(1)
+        .and_then(|index| shown.get(index))
+        .map(Borrow::borrow)
+        .ok_or(PositionError::OutOfRange(shown.len()))?;
+    if target.id == id {
+        return Err(PositionError::SamePosition);
+    }
+    Ok(target.order - 1)
+}
@@ -0,0 +1,245 @@
+//! HTTP requests to the CalDAV server.
+
+use super::CalDavError;
+use super::ical;
+use super::plan::{self, SyncAction, SyncPlan};
+use super::xml::{self, CalendarResource};
+use crate::models::TodoList;
+use crate::models::settings::CalDavSettings;
+use crate::models::todo::RemoteLink;
+use crate::utils::constants::caldav::REQUEST_TIMEOUT_SECS;
+use chrono::{DateTime, Utc};
+use reqwest::header::{CONTENT_TYPE, ETAG, IF_MATCH, IF_NONE_MATCH};
+use reqwest::{Method, RequestBuilder, Response, StatusCode, Url};
+use std::time::Duration;
+
+/// What pushing the server side of a plan did.
+#[derive(Debug, Default)]
+pub struct PushOutcome {
+    /// Links of the todos uploaded or updated, to record locally
+    pub links: Vec<(usize, RemoteLink)>,
+    /// Number of todos deleted from the server
+    pub deleted: usize,
+    /// Changes the server refused, left for the next sync to plan again
+    pub failed: Vec<CalDavError>,
+}
+
+/// Client for one CalDAV task collection.
+pub struct CalDavClient {
+    http: reqwest::Client,
+    collection: Url,
+    username: String,
+    password: String,
+}
+
+impl CalDavClient {
+    /// Creates a client for the collection in the settings.
+    ///
+    /// # Returns
+    /// The client, or [`CalDavError::InvalidUrl`] if the URL is not http(s)
+    pub fn new(settings: &CalDavSettings) -> Result<Self, CalDavError> {
+        let url = settings.url.trim();
+        // Resource names are joined onto the collection, which needs a trailing slash
+        let collection = Url::parse(&format!("{}/", url.trim_end_matches('/')))
+            .ok()
+            .filter(|url| matches!(url.scheme(), "http" | "https"))
+            .ok_or_else(|| CalDavError::InvalidUrl(url.to_string()))?;
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| CalDavError::Network(e.to_string()))?;
+        Ok(Self {
+            http,
+            collection,
+            username: settings.username.trim().to_string(),
+            password: settings.app_password.clone(),
+        })
+    }
+
+    fn request(&self, method: Method, url: Url) -> RequestBuilder {
+        self.http
+            .request(method, url)
+            .basic_auth(&self.username, Some(&self.password))
+    }
+
+    /// Resolves an href from the server against the collection URL.
+    fn resolve(&self, href: &str) -> Result<Url, CalDavError> {
+        self.collection
+            .join(href)
+            .map_err(|_| CalDavError::InvalidUrl(href.to_string()))
+    }
+
+    /// Sends a request and turns failures into errors.
+    async fn send(&self, request: RequestBuilder, url: &Url) -> Result<Response, CalDavError> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| CalDavError::Network(e.to_string()))?;
+        match response.status() {
+            status if status.is_success() => Ok(response),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(CalDavError::Unauthorized),
+            status => Err(CalDavError::Status {
+                code: status.as_u16(),
+                url: url.to_string(),
+            }),
+        }
+    }
+
+    /// Fetches every todo in the collection.
+    pub async fn fetch(&self) -> Result<Vec<CalendarResource>, CalDavError> {
+        let report = Method::from_bytes(b"REPORT").expect("REPORT is a valid method");
+        let url = self.collection.clone();
+        let request = self
+            .request(report, url.clone())
+            .header("Depth", "1")
+            .header(CONTENT_TYPE, "application/xml; charset=utf-8")
+            .body(xml::VTODO_QUERY);
+        let body = self
+            .send(request, &url)
+            .await?
+            .text()
+            .await
+            .map_err(|e| CalDavError::Network(e.to_string()))?;
+        xml::parse_multistatus(&body)
+    }
+
+    /// Writes a calendar object, only if it is still the version last seen.
+    ///
+    /// # Returns
+    /// The resource's new entity tag, if the server sent one
+    async fn put(
+        &self,
+        url: &Url,
+        data: String,
+        etag: Option<&str>,
+    ) -> Result<Option<String>, CalDavError> {
+        let request = self
+            .request(Method::PUT, url.clone())
+            .header(CONTENT_TYPE, "text/calendar; charset=utf-8")
+            .body(data);
+        let request = match etag {
+            Some(etag) => request.header(IF_MATCH, etag),
+            None => request.header(IF_NONE_MATCH, "*"),
+        };
+        let response = self.send(request, url).await?;
+        Ok(response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string))
+    }
+
+    /// Makes the server side of a plan: uploads, updates and deletions.
+    ///
+    /// Each change is made on its own; one the server refuses, for example
+    /// because the todo changed there meanwhile, is reported in
+    /// [`PushOutcome::failed`] and planned again by the next sync.
+    ///
+    /// # Arguments
+    /// * `plan` - The planned changes
+    /// * `list` - The todos the plan was made from
+    /// * `synced_at` - When the list was read, recorded in the new links
+    pub async fn push(
+        &self,
+        plan: &SyncPlan,
+        list: &TodoList,
+        synced_at: DateTime<Utc>,
+    ) -> PushOutcome {
+        let mut outcome = PushOutcome::default();
+        for action in &plan.actions {
+            let result = match action {
+                SyncAction::Upload { id } => self.upload(list, *id, synced_at).await,
+                SyncAction::UpdateRemote { id, remote, .. } => {
+                    self.update(list, *id, remote, synced_at).await
+                }
+                SyncAction::DeleteRemote { remote, .. } => {
+                    match self.delete(&remote.href, remote.etag.as_deref()).await {
+                        Ok(()) => {
+                            outcome.deleted += 1;
+                            Ok(None)
+                        }
+                        Err(error) => Err(error),
+                    }
+                }
+                _ => Ok(None),
+            };
+            match result {
+                Ok(Some(link)) => outcome.links.push(link),
+                Ok(None) => {}
+                Err(error) => outcome.failed.push(error),
+            }
+        }
+        outcome
+    }
+
+    async fn upload(
+        &self,
+        list: &TodoList,
+        id: usize,
+        synced_at: DateTime<Utc>,
+    ) -> Result<Option<(usize, RemoteLink)>, CalDavError> {
+        let Some(todo) = list.get(id) else {
+            return Ok(None);
+        };
+        // A todo deleted on the server but changed here keeps its UID
+        let (uid, sequence) = match &todo.remote {
+            Some(link) => (link.uid.clone(), link.sequence + 1),
+            None => (plan::new_uid(), 0),
+        };
+        let url = self.resolve(&plan::resource_name(&uid))?;
+        let vtodo = plan::to_vtodo(todo, uid.clone(), sequence);
+        let etag = self
+            .put(&url, ical::write_vtodo(&vtodo, None, Utc::now()), None)
+            .await?;
+        Ok(Some((
+            id,
+            RemoteLink {
+                uid,
+                href: url.path().to_string(),
+                etag,
+                sequence,
+                synced_at,
+            },
+        )))
+    }
+
+    async fn update(
+        &self,
+        list: &TodoList,
+        id: usize,
+        remote: &plan::RemoteTodo,
+        synced_at: DateTime<Utc>,
+    ) -> Result<Option<(usize, RemoteLink)>, CalDavError> {
+        let Some(todo) = list.get(id) else {
+            return Ok(None);
+        };
+        let sequence = remote
+            .vtodo
+            .sequence
+            .max(todo.remote.as_ref().map_or(0, |link| link.sequence))
+            + 1;
+        let vtodo = plan::to_vtodo(todo, remote.vtodo.uid.clone(), sequence);
+        let url = self.resolve(&remote.href)?;
+        let data = ical::write_vtodo(&vtodo, Some(&remote.data), Utc::now());
+        let etag = self.put(&url, data, remote.etag.as_deref()).await?;
+        Ok(Some((
+            id,
+            RemoteLink {
+                uid: vtodo.uid,
+                href: remote.href.clone(),
+                etag,
+                sequence,
+                synced_at,
+            },
+        )))
+    }
+
+    async fn delete(&self, href: &str, etag: Option<&str>) -> Result<(), CalDavError> {
+        let url = self.resolve(href)?;
+        let mut request = self.request(Method::DELETE, url.clone());
+        if let Some(etag) = etag {
+            request = request.header(IF_MATCH, etag);
+        }
+        self.send(request, &url).await.map(|_| ())
+    }
+}
@@ -0,0 +1,354 @@
+//! Reading and writing the VTODO components of iCalendar data (RFC 5545).
+//!
+//! Only the properties the app syncs are interpreted. When a todo that came
+//! from the server is written back, every other line of the original resource
+//! (alarms, priorities, time zones, properties of other clients) is kept.
+
+use super::CalDavError;
+use crate::utils::constants::caldav::{ICAL_LINE_LIMIT, PRODUCT_ID};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+/// The synced fields of a VTODO.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VTodo {
+    /// Unique identifier, shared by every copy of the todo
+    pub uid: String,
+    /// The todo text
+    pub summary: String,
+    /// When the todo is due
+    pub due: Option<DateTime<Utc>>,
+    /// Whether the todo is done (STATUS COMPLETED or CANCELLED)
+    pub completed: bool,
+    /// When the todo was completed
+    pub completed_at: Option<DateTime<Utc>>,
+    /// The categories, which become tags
+    pub categories: Vec<String>,
+    /// The revision number, raised by each client that changes the todo
+    pub sequence: u32,
+    /// When the todo was last changed on the server
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+/// One unfolded content line: `NAME;PARAM=VALUE:value`, parameters dropped.
+struct ContentLine<'a> {
+    name: String,
+    value: &'a str,
+}
+
+/// Joins folded lines (continuations start with a space or tab).
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Splits a content line at the first colon outside a quoted parameter value.
+fn split_line(line: &str) -> Option<ContentLine<'_>> {
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(i),
+        _ => None,
+    })?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let name = head.split_once(';').map_or(head, |(name, _)| name);
+    Some(ContentLine {
+        name: name.to_ascii_uppercase(),
+        value,
+    })
+}
+
+/// Undoes TEXT escaping (`\\`, `\;`, `\,`, `\n`).
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Escapes a value for a TEXT property.
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Splits a list of TEXT values at the commas that are not escaped.
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut escaped = false;
+    for c in value.chars() {
+        match c {
+            ',' if !escaped => items.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    items.push(current);
+    items
+        .iter()
+        .map(|item| unescape(item).trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Parses a DATE or DATE-TIME value.
+///
+/// Dates become midnight UTC, like due dates picked in the app. Times with a
+/// `TZID` or no zone at all are read as UTC, which is at most a few hours off
+/// and never moves a date-only due date.
+pub fn parse_date_time(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Some(utc) = value.strip_suffix('Z') {
+        let time = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some(Utc.from_utc_datetime(&time));
+    }
+    if let Ok(time) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
+        return Some(Utc.from_utc_datetime(&time));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+    Some(Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?))
+}
+
+/// Formats a UTC DATE-TIME value.
+fn format_date_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Formats a due date, as a DATE when it is midnight UTC.
+fn format_due(due: DateTime<Utc>) -> String {
+    if due.time() == chrono::NaiveTime::MIN {
+        format!("DUE;VALUE=DATE:{}", due.format("%Y%m%d"))
+    } else {
+        format!("DUE:{}", format_date_time(due))
+    }
+}
+
+/// Parses every VTODO in a calendar object.
+///
+/// # Returns
+/// The todos in order, or [`CalDavError::Parse`] if a VTODO has no UID or is
+/// not closed
+pub fn parse_vtodos(ics: &str) -> Result<Vec<VTodo>, CalDavError> {
+    let mut todos = Vec::new();
+    let mut current: Option<(VTodo, bool)> = None;
+    // Depth of components nested in the VTODO, such as VALARM
+    let mut nested = 0usize;
+
+    for line in unfold(ics) {
+        let Some(line) = split_line(&line) else {
+            continue;
+        };
+        let Some((todo, has_uid)) = current.as_mut() else {
+            if line.name == "BEGIN" && line.value.eq_ignore_ascii_case("VTODO") {
+                current = Some((VTodo::default(), false));
+            }
+            continue;
+        };
+        match line.name.as_str() {
+            "BEGIN" => nested += 1,
+            "END" if nested > 0 => nested -= 1,
+            "END" => {
+                if let Some((todo, has_uid)) = current.take() {
+                    if !has_uid {
+                        return Err(CalDavError::Parse("a VTODO has no UID".to_string()));
+                    }
+                    todos.push(todo);
+                }
+            }
+            _ if nested > 0 => {}
+            "UID" => {
+                todo.uid = line.value.trim().to_string();
+                *has_uid = !todo.uid.is_empty();
+            }
+            "SUMMARY" => todo.summary = unescape(line.value),
+            "DUE" => todo.due = parse_date_time(line.value),
+            "STATUS" => {
+                let status = line.value.trim().to_ascii_uppercase();
+                todo.completed = status == "COMPLETED" || status == "CANCELLED";
+            }
+            "COMPLETED" => todo.completed_at = parse_date_time(line.value),
+            "CATEGORIES" => todo.categories.extend(split_list(line.value)),
+            "SEQUENCE" => todo.sequence = line.value.trim().parse().unwrap_or(0),
+            "LAST-MODIFIED" => todo.last_modified = parse_date_time(line.value),
+            _ => {}
+        }
+    }
+
+    if current.is_some() {
+        return Err(CalDavError::Parse("a VTODO is not closed".to_string()));
+    }
+    Ok(todos)
+}
+
+/// Properties written from the todo, replaced when a resource is updated.
+const SYNCED_PROPERTIES: [&str; 9] = [
+    "SUMMARY",
+    "DUE",
+    "STATUS",
+    "COMPLETED",
+    "PERCENT-COMPLETE",
+    "CATEGORIES",
+    "SEQUENCE",
+    "LAST-MODIFIED",
+    "DTSTAMP",
+];
+
+/// Builds the content lines for the synced properties of a todo.
+fn synced_lines(todo: &VTodo, now: DateTime<Utc>) -> Vec<String> {
+    let stamp = format_date_time(now);
+    let mut lines = vec![
+        format!("DTSTAMP:{}", stamp),
+        format!("LAST-MODIFIED:{}", stamp),
+        format!("SEQUENCE:{}", todo.sequence),
+        format!("SUMMARY:{}", escape(&todo.summary)),
+    ];
+    if let Some(due) = todo.due {
+        lines.push(format_due(due));
+    }
+    if todo.completed {
+        lines.push("STATUS:COMPLETED".to_string());
+        lines.push("PERCENT-COMPLETE:100".to_string());
+        lines.push(format!(
+            "COMPLETED:{}",
+            format_date_time(todo.completed_at.unwrap_or(now))
+        ));
+    } else {
+        lines.push("STATUS:NEEDS-ACTION".to_string());
+    }
+    if !todo.categories.is_empty() {
+        let categories: Vec<String> = todo.categories.iter().map(|c| escape(c)).collect();
+        lines.push(format!("CATEGORIES:{}", categories.join(",")));
+    }
+    lines
+}
+
+/// Writes a todo as iCalendar data.
+///
+/// # Arguments
+/// * `todo` - The fields to write
+/// * `original` - The resource as last fetched from the server; only its VTODO
+///   with the same UID is changed, and only in the synced properties
+/// * `now` - The time recorded as the last change
+///
+/// # Returns
+/// The calendar object with CRLF line endings and long lines folded
+pub fn write_vtodo(todo: &VTodo, original: Option<&str>, now: DateTime<Utc>) -> String {
+    let lines = match original {
+        Some(original) => patch_lines(unfold(original), todo, now),
+        None => {
+            let mut lines = vec![
+                "BEGIN:VCALENDAR".to_string(),
+                "VERSION:2.0".to_string(),
+                format!("PRODID:{}", PRODUCT_ID),
+                "BEGIN:VTODO".to_string(),
+                format!("UID:{}", todo.uid),
+            ];
+            lines.extend(synced_lines(todo, now));
+            lines.push("END:VTODO".to_string());
+            lines.push("END:VCALENDAR".to_string());
+            lines
+        }
+    };
+
+    let mut out = String::new();
+    for line in lines.iter().filter(|line| !line.is_empty()) {
+        fold_into(&mut out, line);
+    }
+    out
+}
+
+/// Replaces the synced properties of the VTODO with the todo's UID.
+fn patch_lines(lines: Vec<String>, todo: &VTodo, now: DateTime<Utc>) -> Vec<String> {
+    // Find the VTODO block by UID, falling back to the first one
+    let mut blocks = Vec::new();
+    let mut start = None;
+    let mut nested = 0usize;
+    let mut uid_matches = false;
+    for (i, line) in lines.iter().enumerate() {
+        let Some(line) = split_line(line) else {
+            continue;
+        };
+        match (start, line.name.as_str()) {
+            (None, "BEGIN") if line.value.eq_ignore_ascii_case("VTODO") => {
+                start = Some(i);
+                uid_matches = false;
+            }
+            (Some(_), "BEGIN") => nested += 1,
+            (Some(_), "END") if nested > 0 => nested -= 1,
+            (Some(begin), "END") => {
+                blocks.push((begin, i, uid_matches));
+                start = None;
+            }
+            (Some(_), "UID") if nested == 0 => uid_matches = line.value.trim() == todo.uid,
+            _ => {}
+        }
+    }
+    let Some(&(begin, end, _)) = blocks
+        .iter()
+        .find(|(_, _, matches)| *matches)
+        .or(blocks.first())
+    else {
+        return lines;
+    };
+
+    let mut patched: Vec<String> = lines[..=begin].to_vec();
+    let mut nested = 0usize;
+    for line in &lines[begin + 1..end] {
+        let name = split_line(line).map(|line| line.name).unwrap_or_default();
+        match name.as_str() {
+            "BEGIN" => nested += 1,
+            "END" => nested = nested.saturating_sub(1),
+            _ if nested == 0 && SYNCED_PROPERTIES.contains(&name.as_str()) => continue,
+            _ => {}
+        }
+        patched.push(line.clone());
+    }
+    patched.extend(synced_lines(todo, now));
+    patched.extend_from_slice(&lines[end..]);
+    patched
+}
+
+/// Appends a content line, folded at [`ICAL_LINE_LIMIT`] octets.
+fn fold_into(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > ICAL_LINE_LIMIT {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
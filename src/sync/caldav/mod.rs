@@ -0,0 +1,57 @@
+//! Two-way sync with a CalDAV task collection (RFC 4791).
+//!
+//! Parsing and planning are plain functions over fetched data, so they work
+//! and are tested without a network; [`CalDavClient`] does the requests and
+//! needs the `caldav` feature.
+
+#[cfg(feature = "caldav")]
+mod client;
+pub mod ical;
+pub mod plan;
+pub mod xml;
+
+#[cfg(feature = "caldav")]
+pub use client::CalDavClient;
+pub use plan::plan_sync;
+
+use std::fmt;
+
+/// Errors from talking to a CalDAV server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalDavError {
+    /// The collection URL is not an http(s) URL
+    InvalidUrl(String),
+    /// The server refused the username or app password
+    Unauthorized,
+    /// The server answered with an unexpected status
+    Status {
+        /// HTTP status code
+        code: u16,
+        /// The URL requested
+        url: String,
+    },
+    /// The server could not be reached
+    Network(String),
+    /// A response could not be read
+    Parse(String),
+}
+
+impl fmt::Display for CalDavError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalDavError::InvalidUrl(url) => write!(f, "\"{}\" is not a calendar URL", url),
+            CalDavError::Unauthorized => {
+                write!(f, "The server did not accept the username or app password.")
+            }
+            CalDavError::Status { code, url } => {
+                write!(f, "The server answered {} for {}", code, url)
+            }
+            CalDavError::Network(reason) => write!(f, "Could not reach the server: {}", reason),
+            CalDavError::Parse(reason) => {
+                write!(f, "Could not read the server's answer: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CalDavError {}
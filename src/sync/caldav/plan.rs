@@ -0,0 +1,393 @@
+//! Working out what a two-way sync changes on each side.
+//!
+//! Todos are matched by the iCalendar UID kept in their [`RemoteLink`]. A
+//! side counts as changed when it differs from what the link recorded at the
+//! last sync: the server's entity tag (or SEQUENCE, when the server sends no
+//! tags) and the todo's `updated_at`. When both sides changed, the later of
+//! the server's LAST-MODIFIED and the local `updated_at` wins, and without
+//! LAST-MODIFIED a higher SEQUENCE than last seen lets the server win.
+
+use super::ical::{self, VTodo};
+use super::xml::CalendarResource;
+use crate::models::todo::RemoteLink;
+use crate::models::{Todo, TodoList};
+use crate::utils::constants::caldav::UID_DOMAIN;
+use crate::utils::text;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// A todo as stored on the server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteTodo {
+    /// The resource URL, as given by the server
+    pub href: String,
+    /// The entity tag, quotes included
+    pub etag: Option<String>,
+    /// The synced fields of the first VTODO in the resource
+    pub vtodo: VTodo,
+    /// The full iCalendar data, patched rather than replaced when pushing
+    pub data: String,
+}
+
+impl RemoteTodo {
+    /// Builds the local copy of the todo, linked to this resource.
+    ///
+    /// The returned todo has id 0; it is meant for [`TodoList::update_from_remote`].
+    pub fn to_local(&self, synced_at: DateTime<Utc>) -> Todo {
+        let mut todo = Todo::new(0, self.vtodo.summary.trim().to_string());
+        todo.completed = self.vtodo.completed;
+        todo.completed_at = match (self.vtodo.completed, self.vtodo.completed_at) {
+            (false, _) => None,
+            (true, at) => Some(at.or(self.vtodo.last_modified).unwrap_or(synced_at)),
+        };
+        todo.due_date = self.vtodo.due;
+        for category in &self.vtodo.categories {
+            todo.add_tag(category.clone());
+        }
+        todo.remote = Some(RemoteLink {
+            uid: self.vtodo.uid.clone(),
+            href: self.href.clone(),
+            etag: self.etag.clone(),
+            sequence: self.vtodo.sequence,
+            synced_at,
+        });
+        todo
+    }
+}
+
+/// Builds the VTODO fields written for a local todo.
+pub fn to_vtodo(todo: &Todo, uid: String, sequence: u32) -> VTodo {
+    VTodo {
+        uid,
+        summary: todo.text.clone(),
+        due: todo.due_date,
+        completed: todo.completed,
+        completed_at: todo.completed_at,
+        categories: todo.tags.clone(),
+        sequence,
+        last_modified: None,
+    }
+}
+
+/// Generates a UID for a todo uploaded for the first time.
+pub fn new_uid() -> String {
+    let mut bytes = [0u8; 16];
+    // A failure leaves zeros; the time keeps the UID unique enough
+    let _ = getrandom::getrandom(&mut bytes);
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}@{}",
+        Utc::now().format("%Y%m%dT%H%M%S"),
+        hex,
+        UID_DOMAIN
+    )
+}
+
+/// Gets the file name of the resource a todo is uploaded to.
+///
+/// Characters that need escaping in a URL are replaced, so UIDs made by
+/// other clients still give a plain name.
+pub fn resource_name(uid: &str) -> String {
+    let name: String = uid
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || "-_.@".contains(c) {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("{}.ics", name)
+}
+
+/// One change made by a sync.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SyncAction {
+    /// A todo only on the server is added here
+    Import(RemoteTodo),
+    /// The server's copy replaces the local todo
+    UpdateLocal {
+        id: usize,
+        remote: RemoteTodo,
+        /// Whether the local todo had changed too
+        conflict: bool,
+    },
+    /// A todo not on the server is uploaded
+    Upload { id: usize },
+    /// The local todo replaces the server's copy
+    UpdateRemote {
+        id: usize,
+        remote: RemoteTodo,
+        /// Whether the server's copy had changed too
+        conflict: bool,
+    },
+    /// A todo deleted on the server, and unchanged here, moves to the trash
+    TrashLocal { id: usize },
+    /// A todo in the trash here, and unchanged on the server, is deleted there
+    DeleteRemote { id: usize, remote: RemoteTodo },
+}
+
+/// Everything a sync would change, worked out before anything is changed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SyncPlan {
+    /// The changes, local todos in list order first
+    pub actions: Vec<SyncAction>,
+    /// Server resources that could not be read or imported, with the reason
+    pub skipped: Vec<(String, String)>,
+}
+
+/// Checks if the server's copy changed since the link was recorded.
+fn remote_changed(link: &RemoteLink, remote: &RemoteTodo) -> bool {
+    match (&link.etag, &remote.etag) {
+        (Some(seen), Some(now)) => seen != now,
+        _ => remote.vtodo.sequence != link.sequence,
+    }
+}
+
+/// Decides whether the server's copy wins when both sides changed.
+fn remote_wins(todo: &Todo, link: &RemoteLink, remote: &RemoteTodo) -> bool {
+    match (remote.vtodo.last_modified, todo.updated_at) {
+        (Some(remote_at), Some(local_at)) => remote_at >= local_at,
+        _ => remote.vtodo.sequence > link.sequence,
+    }
+}
+
+/// Plans a sync between the list and the resources fetched from the server.
+///
+/// # Arguments
+/// * `list` - The todos, including the trash
+/// * `resources` - Every calendar object in the collection
+///
+/// # Returns
+/// The changes to make on both sides; resources without a readable VTODO
+/// are listed in [`SyncPlan::skipped`]
+pub fn plan_sync(list: &TodoList, resources: Vec<CalendarResource>) -> SyncPlan {
+    let mut plan = SyncPlan::default();
+
+    let mut remote_by_uid: HashMap<String, RemoteTodo> = HashMap::new();
+    let mut remote_order = Vec::new();
+    for resource in resources {
+        let vtodo = match ical::parse_vtodos(&resource.calendar_data) {
+            Ok(vtodos) => vtodos.into_iter().next(),
+            Err(error) => {
+                plan.skipped.push((resource.href, error.to_string()));
+                continue;
+            }
+        };
+        // Resources with only events or journals are not todos
+        let Some(vtodo) = vtodo else {
+            continue;
+        };
+        remote_order.push(vtodo.uid.clone());
+        remote_by_uid.insert(
+            vtodo.uid.clone(),
+            RemoteTodo {
+                href: resource.href,
+                etag: resource.etag,
+                vtodo,
+                data: resource.calendar_data,
+            },
+        );
+    }
+
+    for todo in list.all_including_archived() {
+        let Some(link) = &todo.remote else {
+            plan.actions.push(SyncAction::Upload { id: todo.id });
+            continue;
+        };
+        let local_changed = todo.changed_since_sync();
+        let Some(remote) = remote_by_uid.remove(&link.uid) else {
+            plan.actions.push(if local_changed {
+                SyncAction::Upload { id: todo.id }
+            } else {
+                SyncAction::TrashLocal { id: todo.id }
+            });
+            continue;
+        };
+        let id = todo.id;
+        match (local_changed, remote_changed(link, &remote)) {
+            (false, false) => {}
+            (false, true) => plan.actions.push(SyncAction::UpdateLocal {
+                id,
+                remote,
+                conflict: false,
+            }),
+            (true, false) => plan.actions.push(SyncAction::UpdateRemote {
+                id,
+                remote,
+                conflict: false,
+            }),
+            (true, true) if remote_wins(&todo, link, &remote) => {
+                plan.actions.push(SyncAction::UpdateLocal {
+                    id,
+                    remote,
+                    conflict: true,
+                })
+            }
+            (true, true) => plan.actions.push(SyncAction::UpdateRemote {
+                id,
+                remote,
+                conflict: true,
+            }),
+        }
+    }
+
+    for todo in list.trashed() {
+        let Some(link) = &todo.remote else {
+            continue;
+        };
+        // A todo deleted here but edited on the server is left alone there
+        if let Some(remote) = remote_by_uid.remove(&link.uid)
+            && !remote_changed(link, &remote)
+        {
+            plan.actions.push(SyncAction::DeleteRemote {
+                id: todo.id,
+                remote,
+            });
+        }
+    }
+
+    for uid in remote_order {
+        let Some(remote) = remote_by_uid.remove(&uid) else {
+            continue;
+        };
+        match text::validate_todo_text(&remote.vtodo.summary) {
+            Ok(_) => plan.actions.push(SyncAction::Import(remote)),
+            Err(error) => plan.skipped.push((remote.href, error.to_string())),
+        }
+    }
+    plan
+}
+
+/// Quotes a todo text for the preview, shortened to keep lines readable.
+fn quoted(text: &str) -> String {
+    const PREVIEW_CHARS: usize = 60;
+    if text.chars().count() > PREVIEW_CHARS {
+        let short: String = text.chars().take(PREVIEW_CHARS - 1).collect();
+        format!("\"{}…\"", short)
+    } else {
+        format!("\"{}\"", text)
+    }
+}
+
+impl SyncPlan {
+    /// Checks if the sync would change nothing on either side.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Describes each change, one line per action then per skipped resource,
+    /// for the preview shown before the first sync.
+    pub fn describe(&self, list: &TodoList) -> Vec<String> {
+        let local_text = |id: usize| {
+            list.get(id)
+                .map(|todo| todo.text.clone())
+                .unwrap_or_default()
+        };
+        let mut lines: Vec<String> = self
+            .actions
+            .iter()
+            .map(|action| match action {
+                SyncAction::Import(remote) => {
+                    format!("Add {} from the server", quoted(&remote.vtodo.summary))
+                }
+                SyncAction::UpdateLocal { id, conflict, .. } => format!(
+                    "Update {} from the server{}",
+                    quoted(&local_text(*id)),
+                    if *conflict {
+                        " (changed on both sides; the server's copy is newer)"
+                    } else {
+                        ""
+                    }
+                ),
+                SyncAction::Upload { id } => format!("Upload {}", quoted(&local_text(*id))),
+                SyncAction::UpdateRemote { id, conflict, .. } => format!(
+                    "Update {} on the server{}",
+                    quoted(&local_text(*id)),
+                    if *conflict {
+                        " (changed on both sides; this copy is newer)"
+                    } else {
+                        ""
+                    }
+                ),
+                SyncAction::TrashLocal { id } => format!(
+                    "Move {} to the trash (deleted on the server)",
+                    quoted(&local_text(*id))
+                ),
+                SyncAction::DeleteRemote { remote, .. } => format!(
+                    "Delete {} from the server (deleted here)",
+                    quoted(&remote.vtodo.summary)
+                ),
+            })
+            .collect();
+        lines.extend(
+            self.skipped
+                .iter()
+                .map(|(href, reason)| format!("Skip {}: {}", href, reason)),
+        );
+        lines
+    }
+
+    /// Applies the local side of the plan once the server side is done.
+    ///
+    /// Todos edited here after `synced_at` are not overwritten by the server's
+    /// copy; they are pushed by the next sync instead. Locked todos deleted on
+    /// the server are kept and unlinked, so the next sync uploads them again.
+    ///
+    /// # Arguments
+    /// * `list` - The todos to change
+    /// * `pushed` - The links of the todos uploaded or updated on the server
+    /// * `synced_at` - When the list was read for this sync
+    ///
+    /// # Returns
+    /// The number of todos changed by the server's side
+    pub fn apply(
+        &self,
+        list: &mut TodoList,
+        pushed: &[(usize, RemoteLink)],
+        synced_at: DateTime<Utc>,
+    ) -> usize {
+        let edited_since = |list: &TodoList, id: usize| {
+            list.get(id)
+                .and_then(|todo| todo.updated_at)
+                .is_some_and(|updated_at| updated_at > synced_at)
+        };
+
+        let mut changed = 0;
+        for action in &self.actions {
+            match action {
+                SyncAction::Import(remote) => {
+                    let id = list.add(remote.vtodo.summary.trim().to_string());
+                    list.update_from_remote(id, &remote.to_local(synced_at));
+                    changed += 1;
+                }
+                SyncAction::UpdateLocal { id, remote, .. } => {
+                    if !edited_since(list, *id)
+                        && list.update_from_remote(*id, &remote.to_local(synced_at))
+                    {
+                        changed += 1;
+                    }
+                }
+                SyncAction::TrashLocal { id } => {
+                    if edited_since(list, *id) {
+                        continue;
+                    }
+                    if list.move_to_trash(*id).is_ok() {
+                        changed += 1;
+                    } else {
+                        list.set_remote(*id, None);
+                    }
+                }
+                SyncAction::Upload { .. }
+                | SyncAction::UpdateRemote { .. }
+                | SyncAction::DeleteRemote { .. } => {}
+            }
+        }
+
+        for (id, link) in pushed {
+            list.set_remote(*id, Some(link.clone()));
+        }
+        changed
+    }
+}
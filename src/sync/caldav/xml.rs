@@ -0,0 +1,108 @@
+//! WebDAV multistatus responses (RFC 4918) and the CalDAV query sent for them.
+
+use super::CalDavError;
+
+const DAV: &str = "DAV:";
+const CALDAV: &str = "urn:ietf:params:xml:ns:caldav";
+
+/// REPORT body asking for the entity tag and data of every VTODO in a collection.
+pub const VTODO_QUERY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop>
+    <d:getetag/>
+    <c:calendar-data/>
+  </d:prop>
+  <c:filter>
+    <c:comp-filter name="VCALENDAR">
+      <c:comp-filter name="VTODO"/>
+    </c:comp-filter>
+  </c:filter>
+</c:calendar-query>"#;
+
+/// A calendar object listed in a multistatus response.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalendarResource {
+    /// The resource URL, as given by the server (usually an absolute path)
+    pub href: String,
+    /// The entity tag, quotes included
+    pub etag: Option<String>,
+    /// The iCalendar data
+    pub calendar_data: String,
+}
+
+/// Checks if a `DAV:status` line reports success, like `HTTP/1.1 200 OK`.
+fn is_success(status: &str) -> bool {
+    status
+        .split_whitespace()
+        .nth(1)
+        .is_some_and(|code| code.starts_with('2'))
+}
+
+/// Parses the calendar objects out of a multistatus response.
+///
+/// Responses without calendar data, such as the collection itself, and
+/// properties reported with a non-2xx status are skipped.
+///
+/// # Returns
+/// The resources in document order, or [`CalDavError::Parse`] if the body is
+/// not a multistatus document
+pub fn parse_multistatus(body: &str) -> Result<Vec<CalendarResource>, CalDavError> {
+    let document = roxmltree::Document::parse(body)
+        .map_err(|e| CalDavError::Parse(format!("invalid XML: {}", e)))?;
+    let root = document.root_element();
+    if !root.has_tag_name((DAV, "multistatus")) {
+        return Err(CalDavError::Parse(format!(
+            "expected a multistatus response, got <{}>",
+            root.tag_name().name()
+        )));
+    }
+
+    let mut resources = Vec::new();
+    for response in root
+        .children()
+        .filter(|n| n.has_tag_name((DAV, "response")))
+    {
+        let Some(href) = response
+            .children()
+            .find(|n| n.has_tag_name((DAV, "href")))
+            .and_then(|n| n.text())
+        else {
+            continue;
+        };
+
+        let mut etag = None;
+        let mut calendar_data = None;
+        for propstat in response
+            .children()
+            .filter(|n| n.has_tag_name((DAV, "propstat")))
+        {
+            let ok = propstat
+                .children()
+                .find(|n| n.has_tag_name((DAV, "status")))
+                .and_then(|n| n.text())
+                .is_some_and(is_success);
+            let Some(prop) = propstat.children().find(|n| n.has_tag_name((DAV, "prop"))) else {
+                continue;
+            };
+            if !ok {
+                continue;
+            }
+            for property in prop.children().filter(|n| n.is_element()) {
+                if property.has_tag_name((DAV, "getetag")) {
+                    etag = property.text().map(|text| text.trim().to_string());
+                } else if property.has_tag_name((CALDAV, "calendar-data")) {
+                    calendar_data = property.text().map(str::to_string);
+                }
+            }
+        }
+
+        if let Some(calendar_data) = calendar_data.filter(|data| !data.trim().is_empty()) {
+            resources.push(CalendarResource {
+                href: href.trim().to_string(),
+                etag,
+                calendar_data,
+            });
+        }
+    }
+    Ok(resources)
+}
@@ -0,0 +1,3 @@
+//! Keeping the todos in step with other apps through a server.
+
+pub mod caldav;
@@ -0,0 +1,150 @@
+//! Tracking whether the todos on screen have reached storage
+//!
+//! [`SaveTracker`] is a small state machine fed by the save loop in
+//! `use_todo_state`: changes mark it dirty, and each save reports when it
+//! starts and how it ended. The header shows the resulting [`SaveStatus`].
+//! When the stored data could not be loaded, the tracker blocks persistence
+//! so the empty list on screen never overwrites it.
+
+use crate::utils::storage::{self, RawStorage, StorageError};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// What the save-status indicator shows.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SaveStatus {
+    /// Everything on screen is in storage
+    Saved,
+    /// A save is in progress
+    Saving,
+    /// There are changes that have not been saved yet
+    Unsaved,
+    /// The last save failed, with the error message
+    Failed(String),
+    /// Saving is paused until the stored data is recovered
+    Blocked,
+}
+
+impl SaveStatus {
+    /// Gets the text shown in the indicator.
+    pub fn label(&self) -> &'static str {
+        match self {
+            SaveStatus::Saved => "Saved",
+            SaveStatus::Saving => "Saving…",
+            SaveStatus::Unsaved => "Unsaved changes",
+            SaveStatus::Failed(_) => "Save failed",
+            SaveStatus::Blocked => "Saving paused",
+        }
+    }
+}
+
+/// Dirty flag and outcome of the last save.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SaveTracker {
+    dirty: bool,
+    saving: bool,
+    error: Option<String>,
+    persistence_blocked: bool,
+    last_saved: Option<DateTime<Utc>>,
+}
+
+impl SaveTracker {
+    /// Records a change that still has to be saved.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Records that a save of the current data started.
+    ///
+    /// Changes made while the save runs mark the tracker dirty again.
+    pub fn begin_save(&mut self) {
+        self.dirty = false;
+        self.saving = true;
+    }
+
+    /// Records the result of the save started last.
+    ///
+    /// A failed save leaves the data unsaved until the next successful one.
+    pub fn finish_save(&mut self, result: &Result<(), StorageError>) {
+        self.saving = false;
+        match result {
+            Ok(()) => {
+                self.error = None;
+                self.last_saved = Some(Utc::now());
+            }
+            Err(e) => {
+                self.dirty = true;
+                self.error = Some(format!("{:?}", e));
+            }
+        }
+    }
+
+    /// Stops saves until [`SaveTracker::unblock_persistence`] is called.
+    pub fn block_persistence(&mut self) {
+        self.persistence_blocked = true;
+    }
+
+    /// Allows saves again, for example after a backup was restored.
+    pub fn unblock_persistence(&mut self) {
+        self.persistence_blocked = false;
+    }
+
+    /// Checks if saves are blocked.
+    pub fn is_persistence_blocked(&self) -> bool {
+        self.persistence_blocked
+    }
+
+    /// Checks if there are changes that have not reached storage.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Gets when a save last succeeded, `None` if none did since launch.
+    pub fn last_saved(&self) -> Option<DateTime<Utc>> {
+        self.last_saved
+    }
+
+    /// Gets the status to show, where blocked saves win over everything, a
+    /// save in progress wins over an earlier failure and a failure wins over
+    /// plain unsaved changes.
+    pub fn status(&self) -> SaveStatus {
+        if self.persistence_blocked {
+            SaveStatus::Blocked
+        } else if self.saving {
+            SaveStatus::Saving
+        } else if let Some(error) = &self.error {
+            SaveStatus::Failed(error.clone())
+        } else if self.dirty {
+            SaveStatus::Unsaved
+        } else {
+            SaveStatus::Saved
+        }
+    }
+}
+
+/// Saves data to the given backend, recording the save on the tracker.
+///
+/// # Arguments
+/// * `tracker` - The tracker to update
+/// * `storage` - The backend to write to
+/// * `key` - The key under which to store the data
+/// * `data` - The data to store
+///
+/// # Returns
+/// The result of the save, also recorded on the tracker, or
+/// [`StorageError::Blocked`] without writing if saves are blocked
+#[allow(dead_code)]
+pub fn flush_with<T: Serialize>(
+    tracker: &mut SaveTracker,
+    storage: &dyn RawStorage,
+    key: &str,
+    data: &T,
+) -> Result<(), StorageError> {
+    if tracker.is_persistence_blocked() {
+        return Err(StorageError::Blocked);
+    }
+    tracker.begin_save();
+    let result = storage::save_with(storage, key, data);
+    tracker.finish_save(&result);
+    result
+}
@@ -0,0 +1,53 @@
+//! Clipboard access through the webview
+//!
+//! Both the web and desktop builds render into a webview, so the clipboard is
+//! reached with the browser `navigator.clipboard` API through `document::eval`.
+
+use dioxus::prelude::*;
+use tracing::warn;
+
+/// Copies text to the clipboard.
+///
+/// # Arguments
+/// * `text` - The text to copy
+pub fn write_text(text: &str) {
+    let eval = document::eval("await navigator.clipboard.writeText(await dioxus.recv());");
+    if let Err(e) = eval.send(text) {
+        warn!("Failed to copy to the clipboard: {:?}", e);
+    }
+}
+
+/// Copies an HTML page to the clipboard, so pasting it into an email keeps
+/// its formatting. Apps that only take plain text get the HTML source.
+///
+/// # Arguments
+/// * `html` - The page to copy
+pub fn write_html(html: &str) {
+    let eval = document::eval(
+        r#"
+        const html = await dioxus.recv();
+        await navigator.clipboard.write([
+            new ClipboardItem({
+                "text/html": new Blob([html], { type: "text/html" }),
+                "text/plain": new Blob([html], { type: "text/plain" }),
+            }),
+        ]);
+        "#,
+    );
+    if let Err(e) = eval.send(html) {
+        warn!("Failed to copy to the clipboard: {:?}", e);
+    }
+}
+
+/// Reads text from the clipboard.
+///
+/// # Returns
+/// The clipboard text, or `None` if the clipboard is empty or cannot be read
+pub async fn read_text() -> Option<String> {
+    document::eval("return await navigator.clipboard.readText();")
+        .join::<String>()
+        .await
+        .inspect_err(|e| warn!("Failed to read the clipboard: {:?}", e))
+        .ok()
+        .filter(|text| !text.trim().is_empty())
+}
@@ -0,0 +1,110 @@
+//! The current time, behind a trait so tests can fix it
+//!
+//! Code that decides things by the date (overdue and stale todos, reminders,
+//! auto-archiving, follow-ups, the planner strip) reads the time from a
+//! [`Clock`] rather than calling `Utc::now()` itself. The app runs on
+//! [`SystemClock`]; tests use [`FixedClock`], which also fixes the local time
+//! zone, so "today" does not depend on when or where they run.
+
+use chrono::{
+    DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+};
+
+/// Source of the current time.
+pub trait Clock {
+    /// Gets the current instant.
+    fn now_utc(&self) -> DateTime<Utc>;
+
+    /// Gets the current date in the user's time zone.
+    fn today_local(&self) -> NaiveDate;
+
+    /// Gets the current wall-clock time in the user's time zone.
+    fn now_local(&self) -> NaiveDateTime;
+}
+
+/// The computer's clock and time zone.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_utc(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn today_local(&self) -> NaiveDate {
+        Local::now().date_naive()
+    }
+
+    fn now_local(&self) -> NaiveDateTime {
+        Local::now().naive_local()
+    }
+}
+
+/// A clock standing still at a chosen instant, in a chosen time zone.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FixedClock {
+    now: DateTime<Utc>,
+    offset: FixedOffset,
+}
+
+#[allow(dead_code)]
+impl FixedClock {
+    /// Creates a clock stopped at the given instant, in UTC.
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now,
+            offset: FixedOffset::east_opt(0).expect("UTC is a valid offset"),
+        }
+    }
+
+    /// Creates a clock stopped at a local wall-clock time.
+    ///
+    /// # Arguments
+    /// * `date` - The local date
+    /// * `hour`, `minute` - The local time
+    /// * `utc_offset_hours` - The time zone, as hours east of UTC
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use todo::utils::clock::{Clock, FixedClock};
+    ///
+    /// // Just after midnight in Berlin in summer, it is still the day before in UTC
+    /// let clock = FixedClock::at_local(NaiveDate::from_ymd_opt(2025, 3, 4).unwrap(), 0, 1, 2);
+    /// assert_eq!(clock.today_local(), NaiveDate::from_ymd_opt(2025, 3, 4).unwrap());
+    /// assert_eq!(clock.now_utc().date_naive(), NaiveDate::from_ymd_opt(2025, 3, 3).unwrap());
+    /// ```
+    pub fn at_local(date: NaiveDate, hour: u32, minute: u32, utc_offset_hours: i32) -> Self {
+        let offset =
+            FixedOffset::east_opt(utc_offset_hours * 60 * 60).expect("the offset is within a day");
+        let time = NaiveTime::from_hms_opt(hour, minute, 0).expect("the time is valid");
+        let local = offset
+            .from_local_datetime(&date.and_time(time))
+            .single()
+            .expect("a fixed offset maps every local time once");
+        Self {
+            now: local.with_timezone(&Utc),
+            offset,
+        }
+    }
+
+    /// Moves the clock forward, or back for a negative duration.
+    pub fn advance(&mut self, by: Duration) {
+        self.now += by;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now_utc(&self) -> DateTime<Utc> {
+        self.now
+    }
+
+    fn today_local(&self) -> NaiveDate {
+        self.now_local().date()
+    }
+
+    fn now_local(&self) -> NaiveDateTime {
+        self.now.with_timezone(&self.offset).naive_local()
+    }
+}
@@ -5,8 +5,207 @@
 
 /// Storage-related constants
 pub mod storage {
-    /// Key used for storing todo data in local storage or database
-    pub const TODO_STORAGE_KEY: &str = "dioxus-todo-app";
+    /// Environment variable used to select the storage backend at runtime
+    pub const STORAGE_BACKEND_ENV: &str = "TODO_STORAGE_BACKEND";
+
+    /// Suffix of the key a corrupt value is copied to before it is repaired
+    pub const CORRUPT_BACKUP_SUFFIX: &str = ".corrupt";
+}
+
+/// Profile constants
+#[allow(dead_code)]
+pub mod profile {
+    /// Name of the profile used when none is given
+    pub const DEFAULT_PROFILE: &str = "default";
+
+    /// Command-line argument picking the profile in native builds (`--profile work`)
+    pub const PROFILE_ARG: &str = "--profile";
+
+    /// Environment variable picking the profile in native builds
+    pub const PROFILE_ENV: &str = "TODO_PROFILE";
+
+    /// Query parameter picking the profile in the web build (`?profile=work`)
+    pub const PROFILE_QUERY_PARAM: &str = "profile";
+
+    /// Maximum number of characters in a profile name
+    pub const MAX_PROFILE_NAME_LENGTH: usize = 32;
+}
+
+/// Data directory constants
+#[allow(dead_code)]
+pub mod data_dir {
+    /// Command-line argument putting the data in a folder of choice (`--data-dir E:/todo`)
+    pub const DATA_DIR_ARG: &str = "--data-dir";
+
+    /// Environment variable putting the data in a folder of choice
+    pub const DATA_DIR_ENV: &str = "TODO_DATA_DIR";
+
+    /// Command-line argument keeping the data next to the executable
+    pub const PORTABLE_ARG: &str = "--portable";
+
+    /// File next to the executable that keeps the data next to it
+    pub const PORTABLE_FLAG_FILE: &str = "portable.flag";
+}
+
+/// Demo mode constants
+#[allow(dead_code)]
+pub mod demo {
+    /// Query parameter that turns on demo mode in the web build (`?demo=1`)
+    pub const DEMO_QUERY_PARAM: &str = "demo";
+
+    /// Environment variable that turns on demo mode in native builds
+    pub const DEMO_MODE_ENV: &str = "TODO_DEMO_MODE";
+}
+
+/// Export-related constants
+pub mod export {
+    /// Version of the JSON export document
+    pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+    /// Magic header of encrypted backup bundles
+    pub const ENCRYPTED_MAGIC: &[u8] = b"TODOENC";
+
+    /// Format version byte of encrypted backup bundles
+    pub const ENCRYPTED_VERSION: u8 = 1;
+
+    /// File extension of encrypted backup bundles
+    pub const ENCRYPTED_EXTENSION: &str = "todoenc";
+
+    /// Name put in the file name of weekly reports
+    pub const WEEKLY_REPORT_NAME: &str = "weekly report";
+
+    /// Length of the key derivation salt (bytes)
+    pub const SALT_LEN: usize = 16;
+
+    /// Length of the stored key check value (bytes)
+    pub const KEY_CHECK_LEN: usize = 16;
+
+    /// Length of the AEAD nonce (bytes)
+    pub const NONCE_LEN: usize = 12;
+}
+
+/// Sharing-related constants
+#[allow(dead_code)]
+pub mod share {
+    /// Name of the URL fragment parameter holding a shared snapshot
+    pub const SNAPSHOT_FRAGMENT_PARAM: &str = "share";
+
+    /// Version of the snapshot document inside a share link; version 2 holds
+    /// only the shown part of each todo
+    pub const SNAPSHOT_FORMAT_VERSION: u32 = 2;
+
+    /// Largest encoded snapshot put in a link (bytes), so links stay shareable
+    pub const MAX_SNAPSHOT_BYTES: usize = 8 * 1024;
+
+    /// Largest decompressed snapshot accepted from a link (bytes)
+    pub const MAX_SNAPSHOT_JSON_BYTES: usize = 1024 * 1024;
+
+    /// Compression level used for snapshots (0-10)
+    pub const SNAPSHOT_COMPRESSION_LEVEL: u8 = 9;
+}
+
+/// Sound-related constants
+#[allow(dead_code)]
+pub mod sound {
+    /// Shortest gap between two completion sounds (milliseconds), so completing
+    /// many todos at once plays a single tick
+    pub const COMPLETION_SOUND_DEBOUNCE_MS: i64 = 250;
+
+    /// Volume of the completion sound (0.0-1.0)
+    pub const COMPLETION_SOUND_VOLUME: f64 = 0.4;
+}
+
+/// CalDAV sync constants
+#[allow(dead_code)]
+pub mod caldav {
+    /// PRODID written into calendar objects created by the app
+    pub const PRODUCT_ID: &str = "-//Dioxus Todo App//Todo//EN";
+
+    /// Longest iCalendar content line before it is folded (octets)
+    pub const ICAL_LINE_LIMIT: usize = 75;
+
+    /// Minutes between background syncs while sync is turned on
+    pub const SYNC_INTERVAL_MINS: u64 = 15;
+
+    /// Seconds before a request to the server is given up
+    pub const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+    /// Suffix of the UIDs given to todos first uploaded by the app
+    pub const UID_DOMAIN: &str = "todo.dioxus.local";
+}
+
+/// Markdown file sync constants
+#[allow(dead_code)]
+pub mod markdown_sync {
+    /// Tag carried by the todos synced with the file
+    pub const DEFAULT_SYNC_TAG: &str = "TODO.md";
+
+    /// Milliseconds without changes to the todos before the file is written
+    pub const WRITE_DEBOUNCE_MS: u64 = 1000;
+
+    /// Milliseconds to let a burst of file events from one save settle
+    pub const FILE_EVENT_SETTLE_MS: u64 = 200;
+}
+
+/// Onboarding-related constants
+pub mod onboarding {
+    /// Sample todos offered on first launch as (text, tags, due in days)
+    pub const SAMPLE_TODOS: [(&str, &[&str], Option<i64>); 3] = [
+        (
+            "Double-click a todo to edit it, or tick the box when it's done",
+            &["Personal"],
+            None,
+        ),
+        (
+            "Plan the week: todos can have a due date",
+            &["Work"],
+            Some(2),
+        ),
+        (
+            "Drag todos to reorder them and tag them to filter later",
+            &["Work", "Urgent"],
+            Some(7),
+        ),
+    ];
+
+    /// Steps of the guided tour as (title, description)
+    pub const TOUR_STEPS: [(&str, &str); 4] = [
+        (
+            "Add todos",
+            "Type a task here and press Enter to add it to your list.",
+        ),
+        (
+            "Filter your list",
+            "Switch between all, active and completed todos, or clear the finished ones.",
+        ),
+        (
+            "Filter by tag",
+            "Click a tag to show only the todos carrying it. Tags are added from each todo.",
+        ),
+        (
+            "Keyboard shortcuts",
+            "Use these shortcuts to switch filters and themes without the mouse.",
+        ),
+    ];
+}
+
+/// Debug log constants
+#[allow(dead_code)]
+pub mod debug_log {
+    /// Number of recent log entries kept in memory for the debug log panel
+    pub const BUFFER_CAPACITY: usize = 500;
+
+    /// Most entries kept per module in any one second; the rest are counted and dropped
+    pub const MAX_ENTRIES_PER_SECOND: usize = 20;
+
+    /// Size (bytes) past which the log file is rotated
+    pub const LOG_FILE_MAX_BYTES: u64 = 1024 * 1024;
+
+    /// Number of rotated log files kept beside the current one
+    pub const LOG_FILES_KEPT: usize = 3;
+
+    /// Name of the log file in the `logs` folder of the data directory
+    pub const LOG_FILE_NAME: &str = "todo.log";
 }
 
 /// UI-related constants
@@ -21,20 +220,46 @@ pub mod ui {
         pub const DEFAULT_HEIGHT: f64 = 1200.0;
     }
 
+    /// How long a toast message stays on screen (seconds)
+    pub const TOAST_DURATION_SECS: u64 = 5;
+
+    /// Number of characters of a todo's text quoted in a toast
+    pub const TOAST_TODO_TEXT_CHARS: usize = 40;
+
+    /// Number of days, starting today, in the planner strip below the add form
+    pub const PLANNER_DAYS: usize = 7;
+
+    /// Window width (CSS pixels) from which the detail panel docks beside the list
+    pub const SPLIT_VIEW_MIN_WIDTH: f64 = 1100.0;
+
+    /// Window width (CSS pixels) below which todo rows put their actions in a "⋯" menu
+    pub const COMPACT_ROW_MAX_WIDTH: f64 = 480.0;
+
+    /// Number of rows on screen from which the list shows its due-date minimap
+    pub const MINIMAP_MIN_ROWS: usize = 30;
+
+    /// Width of a day on the timeline (pixels)
+    pub const TIMELINE_DAY_WIDTH: f64 = 32.0;
+
+    /// Fewest days the timeline spans, so a few close due dates are not crammed
+    pub const TIMELINE_MIN_DAYS: u32 = 14;
+
+    /// Most results listed in the quick switcher (Ctrl+K)
+    pub const PALETTE_MAX_RESULTS: usize = 8;
+
+    /// Pause in typing after which the editors' word and character counts update (milliseconds)
+    pub const TEXT_STATS_DEBOUNCE_MS: u64 = 250;
+
+    /// Pause in filter changes, such as typing a search, after which the filters are saved (milliseconds)
+    pub const FILTERS_SAVE_DEBOUNCE_MS: u64 = 500;
+
     /// CSS class definitions for consistent theming
     pub mod css {
-        /// Background classes with dark mode support
-        pub const BG_DARK_CLASS: &str = "bg-gray-100 dark:bg-gray-900";
+        /// Background of the page in dark mode
+        pub const BG_DARK_CLASS: &str = "bg-gray-900";
 
-        /// Background classes for light mode
+        /// Background of the page in light mode
         pub const BG_LIGHT_CLASS: &str = "";
-
-        /// Button classes for primary actions
-        pub const PRIMARY_BUTTON_CLASS: &str =
-            "rounded px-2 py-1 text-white bg-blue-500 hover:bg-blue-600";
-
-        /// Button classes for secondary actions
-        pub const SECONDARY_BUTTON_CLASS: &str = "rounded px-2 py-1 text-gray-700 bg-gray-200 hover:bg-gray-300 dark:text-white dark:bg-gray-600 dark:hover:bg-gray-700";
     }
 }
 
@@ -49,6 +274,112 @@ pub mod todo {
 
     /// Maximum number of tags per todo
     pub const MAX_TAGS_PER_TODO: usize = 5;
+
+    /// Default number of todos above which a warning suggests archiving or exporting
+    pub const DEFAULT_TODO_COUNT_WARNING: usize = 5_000;
+
+    /// Number of earlier texts kept per todo
+    pub const TEXT_HISTORY_LIMIT: usize = 10;
+
+    /// Number of changes to the list that can be undone
+    pub const UNDO_HISTORY_LIMIT: usize = 100;
+
+    /// Earliest year a todo can be due in
+    pub const EARLIEST_DUE_YEAR: i32 = 1970;
+
+    /// Number of years ahead of today a todo can be due at the latest
+    pub const MAX_DUE_YEARS_AHEAD: u32 = 50;
+
+    /// Time within which adding the same text again is taken for a repeated
+    /// submit rather than a new todo (milliseconds)
+    pub const REPEATED_ADD_WINDOW_MS: i64 = 300;
+
+    /// Reading speed used to estimate how long notes take to read, in words per minute
+    pub const READING_WORDS_PER_MINUTE: usize = 200;
+
+    /// Reading speed for Chinese and Japanese text, counted by character, per minute
+    pub const READING_CJK_CHARS_PER_MINUTE: usize = 500;
+
+    /// Number of words from which notes show an estimated reading time
+    pub const READING_TIME_MIN_WORDS: usize = 200;
+
+    /// Number of characters shown before long todo text is truncated
+    pub const TRUNCATE_TEXT_AT: usize = 500;
+
+    /// Number of characters a web address in todo text is shortened to
+    pub const URL_DISPLAY_CHARS: usize = 48;
+
+    /// Default number of hours before the due date that a todo shows up under the bell
+    pub const DEFAULT_REMINDER_LEAD_HOURS: u32 = 24;
+
+    /// Minutes a snoozed reminder stays away before it shows up again
+    pub const REMINDER_SNOOZE_MINUTES: i64 = 60;
+
+    /// Hours between runs of the scheduled rules: escalation, auto-archive
+    /// and the trash and archive retention
+    pub const AUTO_ARCHIVE_CHECK_HOURS: u64 = 24;
+
+    /// Default number of days a todo stays in the trash before it is deleted for good
+    pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+
+    /// Largest number of days due dates can be shifted at once, either way
+    pub const MAX_DATE_SHIFT_DAYS: i64 = 3650;
+
+    /// Default number of days without changes after which an open todo is stale
+    pub const DEFAULT_STALE_AFTER_DAYS: u32 = 14;
+
+    /// Default tag of todos waiting on someone else, listed for follow-up
+    pub const DEFAULT_FOLLOW_UP_TAG: &str = "waiting";
+
+    /// Default number of days a waiting todo goes unchanged before it needs follow-up
+    pub const DEFAULT_FOLLOW_UP_AFTER_DAYS: u32 = 7;
+
+    /// Tag marking high-priority todos, which earn extra karma when completed
+    pub const HIGH_PRIORITY_TAG: &str = "Urgent";
+
+    /// Tag given to open todos without a due date that have waited too long
+    pub const REVIEW_TAG: &str = "review";
+
+    /// Karma points for completing a todo
+    pub const KARMA_COMPLETION_POINTS: u32 = 1;
+
+    /// Extra karma points for completing a high-priority todo
+    pub const KARMA_PRIORITY_BONUS: u32 = 2;
+
+    /// Extra karma points for completing a todo after its due date
+    pub const KARMA_OVERDUE_BONUS: u32 = 1;
+
+    /// Karma points needed for each level
+    pub const KARMA_POINTS_PER_LEVEL: u32 = 25;
+
+    /// Default number of todos to complete each week
+    pub const DEFAULT_WEEKLY_GOAL: usize = 20;
+
+    /// Default local time of the daily digest notification (hour, minute)
+    pub const DEFAULT_DIGEST_TIME: (u32, u32) = (8, 30);
+
+    /// Longest wait between checks for the daily digest, so a computer waking
+    /// from sleep notices a missed digest soon (seconds)
+    pub const DIGEST_CHECK_INTERVAL_SECS: u64 = 300;
+
+    /// Time between checks for todos entering the reminder window (seconds)
+    pub const REMINDER_CHECK_INTERVAL_SECS: u64 = 60;
+
+    /// Most time a single tick of a periodic check counts for; a longer gap
+    /// between ticks means the computer slept or the app was hidden (seconds)
+    pub const MAX_TICK_CREDIT_SECS: i64 = 120;
+
+    /// Local hour at which "Do not disturb until tomorrow" ends
+    pub const QUIET_UNTIL_TOMORROW_HOUR: u32 = 8;
+
+    /// Number of todos a tag of the user's own must be on before it is suggested
+    pub const FREQUENT_TAG_MIN_USES: usize = 2;
+
+    /// Most tags suggested at once when tagging a todo
+    pub const MAX_TAG_SUGGESTIONS: usize = 8;
+
+    /// Most todos listed in the most-postponed insight
+    pub const MOST_POSTPONED_LIMIT: usize = 5;
 }
 
 /// Application-wide constants
@@ -57,6 +388,19 @@ pub mod app {
     /// Application name
     pub const APP_NAME: &str = "Dioxus Todo App";
 
-    /// Application version
-    pub const APP_VERSION: &str = "0.1.0";
+    /// Application version, taken from the package manifest
+    pub const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+    /// Release notes shown in the "What's new" dialog, newest first
+    pub const RELEASE_NOTES: &[(&str, &[&str])] = &[(
+        "0.1.0",
+        &[
+            "Lock a todo from its right-click menu so it cannot be edited or deleted by accident.",
+            "Old completed todos can be archived automatically after a number of days.",
+            "Overdue, Today and This week pills above the list filter by due date.",
+            "Search with week:2025-W12 and show ISO week numbers next to due dates.",
+            "Share a read-only snapshot of the shown todos through a link.",
+            "Turn action items in a todo's notes into todos of their own.",
+        ],
+    )];
 }
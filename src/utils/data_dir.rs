@@ -0,0 +1,146 @@
+//! Where the desktop app keeps its data
+//!
+//! The database, the profiles and the log files live in the user's local
+//! data folder unless told otherwise at launch. `--data-dir <path>` or the
+//! `TODO_DATA_DIR` environment variable puts them in a folder of choice,
+//! and `--portable` or a `portable.flag` file next to the executable keeps
+//! them next to it, so the app can run from a USB stick without leaving
+//! anything behind on the machine.
+
+use crate::utils::constants::data_dir::{
+    DATA_DIR_ARG, DATA_DIR_ENV, PORTABLE_ARG, PORTABLE_FLAG_FILE,
+};
+use std::path::{Path, PathBuf};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::OnceLock;
+
+#[cfg(not(target_arch = "wasm32"))]
+static ACTIVE_DATA_DIR: OnceLock<DataDir> = OnceLock::new();
+
+/// The folder the app keeps its data in, and what picked it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DataDir {
+    /// Given with `--data-dir` on the command line
+    Arg(PathBuf),
+    /// Given with the `TODO_DATA_DIR` environment variable
+    Env(PathBuf),
+    /// Next to the executable, with `--portable` or a `portable.flag` file
+    Portable(PathBuf),
+    /// The user's local data folder
+    Default,
+}
+
+impl DataDir {
+    /// Gets the folder picked, `None` for the user's local data folder.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            DataDir::Arg(path) | DataDir::Env(path) | DataDir::Portable(path) => Some(path),
+            DataDir::Default => None,
+        }
+    }
+
+    /// Describes how the folder was picked, for the "Your data" section,
+    /// `None` for the user's local data folder.
+    ///
+    /// # Example
+    /// ```
+    /// use std::path::PathBuf;
+    /// use todo::utils::data_dir::DataDir;
+    ///
+    /// assert_eq!(DataDir::Portable(PathBuf::from("E:/todo")).mode(), Some("portable"));
+    /// assert_eq!(DataDir::Default.mode(), None);
+    /// ```
+    pub fn mode(&self) -> Option<&'static str> {
+        match self {
+            DataDir::Arg(_) => Some("set with --data-dir"),
+            DataDir::Env(_) => Some("set with TODO_DATA_DIR"),
+            DataDir::Portable(_) => Some("portable"),
+            DataDir::Default => None,
+        }
+    }
+}
+
+/// Finds the data folder in command-line arguments, given as
+/// `--data-dir <path>` or `--data-dir=<path>`.
+fn from_args(args: &[String]) -> Option<PathBuf> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = if arg == DATA_DIR_ARG {
+            args.next().map(String::as_str)
+        } else {
+            arg.strip_prefix(DATA_DIR_ARG)
+                .and_then(|rest| rest.strip_prefix('='))
+        };
+        if let Some(value) = value {
+            return non_empty(value);
+        }
+    }
+    None
+}
+
+fn non_empty(value: &str) -> Option<PathBuf> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| PathBuf::from(value))
+}
+
+/// Picks the folder the app keeps its data in.
+///
+/// A folder given on the command line wins over one in the environment,
+/// which wins over portable mode; without any of them the app uses the
+/// user's local data folder.
+///
+/// # Arguments
+/// * `args` - The arguments the app was started with, without the program
+/// * `env` - The value of `TODO_DATA_DIR`, if set
+/// * `exe_dir` - The folder holding the executable, checked for a
+///   `portable.flag` file; `None` if it cannot be found
+///
+/// # Example
+/// ```
+/// use std::path::{Path, PathBuf};
+/// use todo::utils::data_dir::{DataDir, resolve_data_dir};
+///
+/// let exe_dir = Path::new("E:/apps/todo");
+/// let args = ["--portable".to_string()];
+/// assert_eq!(
+///     resolve_data_dir(&args, None, Some(exe_dir)),
+///     DataDir::Portable(exe_dir.to_path_buf())
+/// );
+/// assert_eq!(
+///     resolve_data_dir(&args, Some("D:/todo"), Some(exe_dir)),
+///     DataDir::Env(PathBuf::from("D:/todo"))
+/// );
+/// assert_eq!(resolve_data_dir(&[], None, None), DataDir::Default);
+/// ```
+pub fn resolve_data_dir(args: &[String], env: Option<&str>, exe_dir: Option<&Path>) -> DataDir {
+    if let Some(path) = from_args(args) {
+        return DataDir::Arg(path);
+    }
+    if let Some(path) = env.and_then(non_empty) {
+        return DataDir::Env(path);
+    }
+    match exe_dir {
+        Some(dir) if args.iter().any(|arg| arg == PORTABLE_ARG) => {
+            DataDir::Portable(dir.to_path_buf())
+        }
+        Some(dir) if dir.join(PORTABLE_FLAG_FILE).is_file() => DataDir::Portable(dir.to_path_buf()),
+        _ => DataDir::Default,
+    }
+}
+
+/// Gets the folder the app keeps its data in.
+///
+/// The folder is picked once, from the command line, the environment and
+/// the executable's folder, and kept for the lifetime of the process.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn active() -> &'static DataDir {
+    ACTIVE_DATA_DIR.get_or_init(|| {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        let env = std::env::var(DATA_DIR_ENV).ok();
+        let exe = std::env::current_exe().ok();
+        let data_dir =
+            resolve_data_dir(&args, env.as_deref(), exe.as_deref().and_then(Path::parent));
+        tracing::info!("Data directory: {:?}", data_dir);
+        data_dir
+    })
+}
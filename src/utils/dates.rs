@@ -0,0 +1,321 @@
+//! Date helpers shared by filters and calendar views
+//!
+//! Week boundaries depend on the user's [`WeekStart`] setting, so everything
+//! that talks about "this week" should go through these helpers. ISO week
+//! numbers are the exception: they always run Monday to Sunday.
+
+use crate::models::WeekStart;
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, NaiveTime, Utc, Weekday};
+use std::fmt;
+
+/// Gets the first day of the week containing the given date.
+///
+/// # Arguments
+/// * `date` - Any date within the week
+/// * `week_start` - The first day of the week
+///
+/// # Returns
+/// The date of the first day of that week, which may fall in the previous year
+#[allow(dead_code)]
+pub fn start_of_week(date: NaiveDate, week_start: WeekStart) -> NaiveDate {
+    let days_since_start = date.weekday().days_since(week_start.weekday());
+    date - Duration::days(i64::from(days_since_start))
+}
+
+/// Gets the first and last day of the week containing the given date.
+///
+/// # Arguments
+/// * `date` - Any date within the week
+/// * `week_start` - The first day of the week
+///
+/// # Returns
+/// The inclusive range `(first, last)` of the week
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use todo::models::WeekStart;
+/// use todo::utils::dates::week_range;
+///
+/// let new_year = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+/// let (first, last) = week_range(new_year, WeekStart::Sunday);
+/// assert_eq!(first, NaiveDate::from_ymd_opt(2024, 12, 29).unwrap());
+/// assert_eq!(last, NaiveDate::from_ymd_opt(2025, 1, 4).unwrap());
+/// ```
+#[allow(dead_code)]
+pub fn week_range(date: NaiveDate, week_start: WeekStart) -> (NaiveDate, NaiveDate) {
+    let first = start_of_week(date, week_start);
+    (first, first + Duration::days(6))
+}
+
+/// Where a due date falls relative to today.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DueBucket {
+    /// Before today
+    Overdue,
+    /// Today
+    Today,
+    /// After today, before the week ends
+    ThisWeek,
+    /// After the end of this week
+    Later,
+}
+
+/// Sorts a due date into a [`DueBucket`].
+///
+/// # Arguments
+/// * `due` - The due date
+/// * `today` - The current date
+/// * `week_start` - The first day of the week, which decides where this week ends
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use todo::models::WeekStart;
+/// use todo::utils::dates::{DueBucket, bucket};
+///
+/// let wednesday = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+/// let sunday = NaiveDate::from_ymd_opt(2025, 3, 16).unwrap();
+/// assert_eq!(bucket(sunday, wednesday, WeekStart::Monday), DueBucket::ThisWeek);
+/// assert_eq!(bucket(sunday, wednesday, WeekStart::Sunday), DueBucket::Later);
+/// ```
+pub fn bucket(due: NaiveDate, today: NaiveDate, week_start: WeekStart) -> DueBucket {
+    let (_, week_end) = week_range(today, week_start);
+    if due < today {
+        DueBucket::Overdue
+    } else if due == today {
+        DueBucket::Today
+    } else if due <= week_end {
+        DueBucket::ThisWeek
+    } else {
+        DueBucket::Later
+    }
+}
+
+/// Gets the weekdays in display order, starting with the first day of the week.
+///
+/// # Arguments
+/// * `week_start` - The first day of the week
+#[allow(dead_code)]
+pub fn weekdays(week_start: WeekStart) -> [Weekday; 7] {
+    let mut day = week_start.weekday();
+    std::array::from_fn(|_| {
+        let current = day;
+        day = day.succ();
+        current
+    })
+}
+
+/// An ISO 8601 week, like `2025-W12`.
+///
+/// Weeks start on Monday, and week 1 is the week holding the year's first
+/// Thursday. The days from December 29 to January 3 can therefore belong to
+/// a week of the other year: 2024-12-30 is in 2025-W01, and 2027-01-01 is in
+/// 2026-W53.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IsoWeek {
+    /// The ISO week-numbering year, which can differ from the calendar year
+    pub year: i32,
+    /// The week number, 1 to 52 or 53
+    pub week: u32,
+}
+
+impl IsoWeek {
+    /// Gets the ISO week containing a date.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use todo::utils::dates::IsoWeek;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2024, 12, 30).unwrap();
+    /// assert_eq!(IsoWeek::of(date), IsoWeek { year: 2025, week: 1 });
+    /// ```
+    pub fn of(date: NaiveDate) -> Self {
+        let week = date.iso_week();
+        Self {
+            year: week.year(),
+            week: week.week(),
+        }
+    }
+
+    /// Parses a week written as `2025-W12`, ignoring case.
+    ///
+    /// # Returns
+    /// The week, or `None` if the text is not in that form or the year has
+    /// no such week
+    pub fn parse(text: &str) -> Option<Self> {
+        let (year, week) = text.split_once(['W', 'w'])?;
+        let year: i32 = year.strip_suffix('-')?.parse().ok()?;
+        if week.is_empty() || week.len() > 2 || !week.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let week: u32 = week.parse().ok()?;
+        NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)?;
+        Some(Self { year, week })
+    }
+
+    /// Gets the Monday and Sunday of the week.
+    #[allow(dead_code)]
+    pub fn range(self) -> (NaiveDate, NaiveDate) {
+        let monday = NaiveDate::from_isoywd_opt(self.year, self.week, Weekday::Mon)
+            .expect("IsoWeek always holds a valid week");
+        (monday, monday + Duration::days(6))
+    }
+
+    /// Checks if a date falls in the week.
+    pub fn contains(self, date: NaiveDate) -> bool {
+        Self::of(date) == self
+    }
+
+    /// Gets the short label shown next to dates, like `W12`.
+    pub fn label(self) -> String {
+        format!("W{:02}", self.week)
+    }
+}
+
+impl fmt::Display for IsoWeek {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-W{:02}", self.year, self.week)
+    }
+}
+
+/// A day offered in the planner strip below the add form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayChip {
+    /// The date of the day
+    pub date: NaiveDate,
+    /// The short weekday name, like `Mon`
+    pub weekday: String,
+    /// The short date, like `Mar 31`
+    pub label: String,
+}
+
+impl DayChip {
+    /// Creates the chip for a date.
+    pub fn new(date: NaiveDate) -> Self {
+        Self {
+            date,
+            weekday: date.format("%a").to_string(),
+            label: date.format("%b %-d").to_string(),
+        }
+    }
+}
+
+/// Gets the chips for the days starting today.
+///
+/// # Arguments
+/// * `today` - The first day
+/// * `count` - The number of days
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use todo::utils::dates::upcoming_days;
+///
+/// let days = upcoming_days(NaiveDate::from_ymd_opt(2025, 3, 30).unwrap(), 3);
+/// let labels: Vec<_> = days.iter().map(|day| format!("{} {}", day.weekday, day.label)).collect();
+/// assert_eq!(labels, ["Sun Mar 30", "Mon Mar 31", "Tue Apr 1"]);
+/// ```
+pub fn upcoming_days(today: NaiveDate, count: usize) -> Vec<DayChip> {
+    today.iter_days().take(count).map(DayChip::new).collect()
+}
+
+/// Gets the due date stored for a day, midnight UTC as the date picker sets it.
+pub fn due_date_on(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN).and_utc()
+}
+
+/// Gets the day a todo is due, the one [`due_date_on`] stored it for.
+///
+/// Due days are read back as stored, in UTC, never in the local time zone:
+/// midnight UTC is still the day before west of Greenwich, where a todo due
+/// today would otherwise count as overdue. Every check of what is due today,
+/// this week or overdue compares this day with the local date.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use todo::utils::dates::{due_date_on, due_day};
+///
+/// let day = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+/// assert_eq!(due_day(due_date_on(day)), day);
+/// ```
+pub fn due_day(due: DateTime<Utc>) -> NaiveDate {
+    due.date_naive()
+}
+
+/// Checks if a weekday is part of the weekend (Saturday or Sunday).
+pub fn is_weekend(day: Weekday) -> bool {
+    matches!(day, Weekday::Sat | Weekday::Sun)
+}
+
+/// Number of weeks a month grid shows, enough for any month and week start
+pub const MONTH_GRID_WEEKS: usize = 6;
+
+/// Gets the first day of the month containing the given date.
+pub fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).expect("every month has a first day")
+}
+
+/// Moves a date by whole months, keeping the day where the month allows it.
+///
+/// Days past the end of the target month land on its last day, so
+/// January 31 plus one month is the end of February.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use todo::utils::dates::add_months;
+///
+/// let end_of_january = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+/// assert_eq!(add_months(end_of_january, 1), NaiveDate::from_ymd_opt(2025, 2, 28).unwrap());
+/// assert_eq!(add_months(end_of_january, -2), NaiveDate::from_ymd_opt(2024, 11, 30).unwrap());
+/// ```
+pub fn add_months(date: NaiveDate, months: i32) -> NaiveDate {
+    let shifted = if months >= 0 {
+        date.checked_add_months(Months::new(months.unsigned_abs()))
+    } else {
+        date.checked_sub_months(Months::new(months.unsigned_abs()))
+    };
+    shifted.unwrap_or(date)
+}
+
+/// Gets the days of a month grid, week by week, for a date picker.
+///
+/// The grid starts on the first day of the week containing the first of the
+/// month and runs [`MONTH_GRID_WEEKS`] weeks, so it begins and ends with
+/// days of the neighbouring months.
+///
+/// # Arguments
+/// * `date` - Any date within the month
+/// * `week_start` - The first day of the week
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use todo::models::WeekStart;
+/// use todo::utils::dates::month_grid;
+///
+/// let grid = month_grid(NaiveDate::from_ymd_opt(2025, 3, 12).unwrap(), WeekStart::Monday);
+/// assert_eq!(grid.len(), 6);
+/// assert_eq!(grid[0][0], NaiveDate::from_ymd_opt(2025, 2, 24).unwrap());
+/// assert_eq!(grid[5][6], NaiveDate::from_ymd_opt(2025, 4, 6).unwrap());
+/// ```
+pub fn month_grid(date: NaiveDate, week_start: WeekStart) -> Vec<[NaiveDate; 7]> {
+    let first = start_of_week(first_of_month(date), week_start);
+    (0..MONTH_GRID_WEEKS)
+        .map(|week| std::array::from_fn(|day| first + Duration::days((week * 7 + day) as i64)))
+        .collect()
+}
+
+/// Keeps a date within optional bounds.
+///
+/// # Arguments
+/// * `date` - The date to keep in range
+/// * `min` - The earliest allowed date, if any
+/// * `max` - The latest allowed date, if any
+pub fn clamp_date(date: NaiveDate, min: Option<NaiveDate>, max: Option<NaiveDate>) -> NaiveDate {
+    let date = min.map_or(date, |min| date.max(min));
+    max.map_or(date, |max| date.min(max))
+}
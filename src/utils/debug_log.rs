@@ -0,0 +1,475 @@
+//! Recent log entries kept for bug reports
+//!
+//! Every log event and every closed span of the app goes into a rolling
+//! buffer of the last [`BUFFER_CAPACITY`] entries, which the debug log panel
+//! shows and exports as text. On desktop the same lines are also written to
+//! a rotating log file in the data directory. Each module gets at most
+//! [`MAX_ENTRIES_PER_SECOND`] entries a second; the rest are dropped and
+//! counted so a busy loop cannot push everything else out of the buffer.
+//!
+//! Logs must never hold todo text: log ids and lengths instead.
+
+use crate::utils::constants::debug_log::{BUFFER_CAPACITY, MAX_ENTRIES_PER_SECOND};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Write as _};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::SetGlobalDefaultError;
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+#[cfg(not(target_arch = "wasm32"))]
+use {
+    std::fs::{self, File, OpenOptions},
+    std::io::{self, Write as _},
+    std::path::PathBuf,
+};
+
+/// One line of the debug log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogEntry {
+    /// When the event happened or the span closed
+    pub time: DateTime<Utc>,
+    /// Severity of the entry
+    pub level: Level,
+    /// Module the entry came from
+    pub target: String,
+    /// The message followed by its fields, like `save key=todo/v1/todos bytes=42`
+    pub message: String,
+}
+
+impl LogEntry {
+    /// Creates an entry stamped with the current time.
+    pub fn new(level: Level, target: &str, message: impl Into<String>) -> Self {
+        Self {
+            time: Utc::now(),
+            level,
+            target: target.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:>5} {}: {}",
+            self.time.format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+            self.level,
+            self.target,
+            self.message
+        )
+    }
+}
+
+/// Entries a module logged in the current second.
+#[derive(Debug)]
+struct RateWindow {
+    second: i64,
+    kept: usize,
+    dropped: usize,
+}
+
+/// Rolling buffer of the most recent log entries, limited per module.
+#[derive(Debug)]
+pub struct LogBuffer {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+    max_per_second: usize,
+    windows: HashMap<String, RateWindow>,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(BUFFER_CAPACITY, MAX_ENTRIES_PER_SECOND)
+    }
+}
+
+impl LogBuffer {
+    /// Creates an empty buffer.
+    ///
+    /// # Arguments
+    /// * `capacity` - How many entries to keep; older ones are dropped first
+    /// * `max_per_second` - How many entries a module may add in one second
+    pub fn new(capacity: usize, max_per_second: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            max_per_second,
+            windows: HashMap::new(),
+        }
+    }
+
+    /// Adds an entry unless its module already logged too much this second.
+    ///
+    /// The first entry a module adds in a new second is preceded by a note
+    /// saying how many of its entries were dropped in the last busy second.
+    ///
+    /// # Returns
+    /// How many entries were added: 0, 1, or 2 with the note
+    ///
+    /// # Example
+    /// ```
+    /// use tracing::Level;
+    /// use todo::utils::debug_log::{LogBuffer, LogEntry};
+    ///
+    /// let mut buffer = LogBuffer::new(500, 2);
+    /// let entry = LogEntry::new(Level::INFO, "todo::models", "add len=12");
+    /// assert_eq!(buffer.push(entry.clone()), 1);
+    /// assert_eq!(buffer.push(entry.clone()), 1);
+    /// assert_eq!(buffer.push(entry), 0);
+    /// assert_eq!(buffer.len(), 2);
+    /// ```
+    pub fn push(&mut self, entry: LogEntry) -> usize {
+        let second = entry.time.timestamp();
+        let window = self
+            .windows
+            .entry(entry.target.clone())
+            .or_insert(RateWindow {
+                second,
+                kept: 0,
+                dropped: 0,
+            });
+
+        let mut dropped_before = 0;
+        if window.second != second {
+            dropped_before = window.dropped;
+            *window = RateWindow {
+                second,
+                kept: 0,
+                dropped: 0,
+            };
+        }
+        if window.kept >= self.max_per_second {
+            window.dropped += 1;
+            return 0;
+        }
+        window.kept += 1;
+
+        let mut added = 0;
+        if dropped_before > 0 {
+            let note = LogEntry {
+                time: entry.time,
+                level: Level::WARN,
+                target: entry.target.clone(),
+                message: format!("{} entries dropped, too many in one second", dropped_before),
+            };
+            self.append(note);
+            added += 1;
+        }
+        self.append(entry);
+        added + 1
+    }
+
+    /// Appends an entry, dropping the oldest one when the buffer is full.
+    fn append(&mut self, entry: LogEntry) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        if self.capacity > 0 {
+            self.entries.push_back(entry);
+        }
+    }
+
+    /// Gets the entries, oldest first.
+    pub fn entries(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    /// Gets the `count` most recently added entries, oldest first.
+    pub fn latest(&self, count: usize) -> impl Iterator<Item = &LogEntry> {
+        self.entries
+            .iter()
+            .skip(self.entries.len().saturating_sub(count))
+    }
+
+    /// Gets the number of entries kept.
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks whether no entries are kept.
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Formats the entries as text, one line each, oldest first.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for entry in &self.entries {
+            let _ = writeln!(text, "{}", entry);
+        }
+        text
+    }
+}
+
+/// Log file that is moved aside once it grows past a size limit.
+///
+/// `todo.log` is renamed to `todo.log.1`, which moves to `todo.log.2` and so
+/// on; the oldest file beyond the number kept is overwritten.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    len: u64,
+    max_bytes: u64,
+    kept: usize,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl RotatingFile {
+    /// Opens the log file for appending, creating it and its folder if needed.
+    ///
+    /// # Arguments
+    /// * `path` - Where the current log file lives
+    /// * `max_bytes` - Size past which the file is rotated
+    /// * `kept` - How many rotated files to keep
+    #[allow(dead_code)]
+    pub fn open(path: impl Into<PathBuf>, max_bytes: u64, kept: usize) -> io::Result<Self> {
+        let path = path.into();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            path,
+            file,
+            len,
+            max_bytes,
+            kept,
+        })
+    }
+
+    /// Writes a line, rotating first if it would make the file too large.
+    pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let line_len = line.len() as u64 + 1;
+        if self.len > 0 && self.len + line_len > self.max_bytes {
+            self.rotate()?;
+        }
+        writeln!(self.file, "{}", line)?;
+        self.len += line_len;
+        Ok(())
+    }
+
+    /// Gets the path of the `number`th rotated file.
+    pub fn rotated_path(&self, number: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", number));
+        PathBuf::from(name)
+    }
+
+    /// Shifts the rotated files up by one and starts an empty log file.
+    fn rotate(&mut self) -> io::Result<()> {
+        if self.kept > 0 {
+            for number in (1..self.kept).rev() {
+                let from = self.rotated_path(number);
+                if from.exists() {
+                    fs::rename(&from, self.rotated_path(number + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.len = 0;
+        Ok(())
+    }
+}
+
+/// The app's debug log: the buffer and, on desktop, the log file.
+#[derive(Default)]
+struct DebugLog {
+    buffer: LogBuffer,
+    #[cfg(not(target_arch = "wasm32"))]
+    file: Option<RotatingFile>,
+}
+
+static DEBUG_LOG: LazyLock<Mutex<DebugLog>> = LazyLock::new(Default::default);
+
+fn debug_log() -> MutexGuard<'static, DebugLog> {
+    DEBUG_LOG
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Adds an entry to the app's debug log and its log file.
+pub fn record(entry: LogEntry) {
+    let mut log = debug_log();
+    let added = log.buffer.push(entry);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let DebugLog { buffer, file } = &mut *log;
+        if let Some(file) = file {
+            for entry in buffer.latest(added) {
+                // Nowhere left to report a failing log file; the buffer still has the entry
+                let _ = file.write_line(&entry.to_string());
+            }
+        }
+    }
+    #[cfg(target_arch = "wasm32")]
+    let _ = added;
+}
+
+/// Gets the entries of the app's debug log, oldest first.
+pub fn entries() -> Vec<LogEntry> {
+    debug_log().buffer.entries().cloned().collect()
+}
+
+/// Formats the app's debug log as text for a bug report.
+pub fn export_text() -> String {
+    debug_log().buffer.to_text()
+}
+
+/// Fields collected from a span when it opened, for the entry written when it closes.
+struct SpanFields {
+    started: DateTime<Utc>,
+    fields: String,
+}
+
+/// Writes the fields of an event or span as ` name=value` pairs, with the
+/// message first and bare.
+struct FieldWriter<'a>(&'a mut String);
+
+impl Visit for FieldWriter<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            let _ = write!(self.0, " {}", value);
+        } else {
+            let _ = write!(self.0, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.0, " {:?}", value);
+        } else {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Tracing layer feeding events and closed spans into the app's debug log.
+///
+/// A span becomes one entry when it closes: its name, its fields and how
+/// long it was open.
+pub struct DebugLogLayer;
+
+impl<S> Layer<S> for DebugLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = String::new();
+        attrs.record(&mut FieldWriter(&mut fields));
+        span.extensions_mut().insert(SpanFields {
+            started: Utc::now(),
+            fields,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        if let Some(span_fields) = span.extensions_mut().get_mut::<SpanFields>() {
+            values.record(&mut FieldWriter(&mut span_fields.fields));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut message = String::new();
+        event.record(&mut FieldWriter(&mut message));
+        let metadata = event.metadata();
+        record(LogEntry::new(
+            *metadata.level(),
+            metadata.target(),
+            message.trim_start(),
+        ));
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let extensions = span.extensions();
+        let Some(span_fields) = extensions.get::<SpanFields>() else {
+            return;
+        };
+        let took = (Utc::now() - span_fields.started)
+            .num_microseconds()
+            .unwrap_or_default() as f64
+            / 1000.0;
+        let metadata = span.metadata();
+        record(LogEntry::new(
+            *metadata.level(),
+            metadata.target(),
+            format!("{}{} took={:.2}ms", span.name(), span_fields.fields, took),
+        ));
+    }
+}
+
+/// Sets up logging: the console at the given level, and the debug log with
+/// the app's own debug entries as well. On desktop the debug log is also
+/// written to the log file.
+pub fn init(level: Level) -> Result<(), SetGlobalDefaultError> {
+    let debug_layer = DebugLogLayer.with_filter(
+        Targets::new()
+            .with_target("todo", Level::DEBUG)
+            .with_default(level),
+    );
+    let registry = tracing_subscriber::registry().with(debug_layer);
+
+    #[cfg(target_arch = "wasm32")]
+    let registry = {
+        console_error_panic_hook::set_once();
+        registry.with(tracing_wasm::WASMLayer::new(
+            tracing_wasm::WASMLayerConfigBuilder::new()
+                .set_max_level(level)
+                .build(),
+        ))
+    };
+    #[cfg(not(target_arch = "wasm32"))]
+    let registry = registry.with(
+        tracing_subscriber::fmt::layer()
+            .with_filter(tracing_subscriber::filter::LevelFilter::from_level(level)),
+    );
+
+    tracing::subscriber::set_global_default(registry)?;
+
+    #[cfg(feature = "desktop")]
+    open_log_file();
+    Ok(())
+}
+
+/// Starts writing the debug log to the log file in the data directory.
+#[cfg(feature = "desktop")]
+fn open_log_file() {
+    use crate::utils::constants::debug_log::{LOG_FILE_MAX_BYTES, LOG_FILE_NAME, LOG_FILES_KEPT};
+
+    let path = crate::utils::storage::app_data_dir()
+        .join("logs")
+        .join(LOG_FILE_NAME);
+    match RotatingFile::open(&path, LOG_FILE_MAX_BYTES, LOG_FILES_KEPT) {
+        Ok(file) => {
+            debug_log().file = Some(file);
+            tracing::info!("Writing the debug log to {:?}", path);
+        }
+        Err(e) => tracing::warn!("Failed to open the log file {:?}: {}", path, e),
+    }
+}
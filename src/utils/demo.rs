@@ -0,0 +1,71 @@
+//! Demo mode detection
+//!
+//! In demo mode the app starts with a sample list, keeps every change in
+//! memory and never writes to localStorage or the database. It is turned on
+//! with `?demo=1` in the web build, the `TODO_DEMO_MODE` environment variable
+//! in native builds, or the `demo` cargo feature.
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::utils::constants::demo::DEMO_MODE_ENV;
+#[cfg(target_arch = "wasm32")]
+use crate::utils::constants::demo::DEMO_QUERY_PARAM;
+use std::sync::OnceLock;
+
+static DEMO_MODE: OnceLock<bool> = OnceLock::new();
+
+/// Checks if a flag value turns demo mode on.
+///
+/// # Arguments
+/// * `value` - The value of the query parameter or environment variable
+///
+/// # Returns
+/// `true` for `1`, `true`, `yes` and `on`, ignoring case
+pub fn parse_flag(value: &str) -> bool {
+    matches!(
+        value.trim().to_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
+
+/// Finds the demo flag in a URL query string such as `?demo=1&lang=en`.
+///
+/// # Arguments
+/// * `query` - The query string, with or without the leading `?`
+/// * `param` - The name of the parameter holding the flag
+///
+/// # Returns
+/// `true` if the parameter is present and turns demo mode on; a bare
+/// `?demo` counts as on
+#[allow(dead_code)]
+pub fn query_has_flag(query: &str, param: &str) -> bool {
+    query
+        .trim_start_matches('?')
+        .split('&')
+        .filter_map(|pair| {
+            let mut parts = pair.splitn(2, '=');
+            let name = parts.next()?;
+            (name == param).then(|| parts.next().is_none_or(parse_flag))
+        })
+        .any(|on| on)
+}
+
+/// Reads the demo flag from the page URL.
+#[cfg(target_arch = "wasm32")]
+fn detect() -> bool {
+    web_sys::window()
+        .and_then(|window| window.location().search().ok())
+        .is_some_and(|query| query_has_flag(&query, DEMO_QUERY_PARAM))
+}
+
+/// Reads the demo flag from the environment.
+#[cfg(not(target_arch = "wasm32"))]
+fn detect() -> bool {
+    std::env::var(DEMO_MODE_ENV).is_ok_and(|value| parse_flag(&value))
+}
+
+/// Checks if the app runs in demo mode.
+///
+/// The result is detected once and cached for the lifetime of the process.
+pub fn is_demo_mode() -> bool {
+    *DEMO_MODE.get_or_init(|| cfg!(feature = "demo") || detect())
+}
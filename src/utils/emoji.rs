@@ -0,0 +1,205 @@
+//! Emoji shortcodes
+//!
+//! Todo text can contain GitHub-style shortcodes such as `:rocket:`, which
+//! [`expand`] replaces with the emoji when the todo is saved. A shortcode is
+//! kept as typed by escaping its first colon: `\:rocket:`.
+
+/// Known shortcodes and their emoji, sorted by name
+pub const SHORTCODES: &[(&str, &str)] = &[
+    ("+1", "👍"),
+    ("-1", "👎"),
+    ("alarm_clock", "⏰"),
+    ("apple", "🍎"),
+    ("art", "🎨"),
+    ("bell", "🔔"),
+    ("birthday", "🎂"),
+    ("book", "📖"),
+    ("bug", "🐛"),
+    ("bulb", "💡"),
+    ("calendar", "📅"),
+    ("car", "🚗"),
+    ("check", "✔️"),
+    ("clap", "👏"),
+    ("coffee", "☕"),
+    ("computer", "💻"),
+    ("construction", "🚧"),
+    ("dog", "🐶"),
+    ("email", "📧"),
+    ("eyes", "👀"),
+    ("fire", "🔥"),
+    ("gift", "🎁"),
+    ("hammer", "🔨"),
+    ("heart", "❤️"),
+    ("hourglass", "⌛"),
+    ("house", "🏠"),
+    ("iphone", "📱"),
+    ("key", "🔑"),
+    ("lock", "🔒"),
+    ("memo", "📝"),
+    ("money_with_wings", "💸"),
+    ("moneybag", "💰"),
+    ("muscle", "💪"),
+    ("music", "🎵"),
+    ("no_entry", "⛔"),
+    ("package", "📦"),
+    ("paperclip", "📎"),
+    ("phone", "☎️"),
+    ("pill", "💊"),
+    ("pizza", "🍕"),
+    ("pushpin", "📌"),
+    ("question", "❓"),
+    ("rocket", "🚀"),
+    ("running", "🏃"),
+    ("shopping_cart", "🛒"),
+    ("smile", "😄"),
+    ("sparkles", "✨"),
+    ("star", "⭐"),
+    ("sunny", "☀️"),
+    ("tada", "🎉"),
+    ("thinking", "🤔"),
+    ("warning", "⚠️"),
+    ("wave", "👋"),
+    ("white_check_mark", "✅"),
+    ("wrench", "🔧"),
+    ("x", "❌"),
+    ("zap", "⚡"),
+    ("zzz", "💤"),
+];
+
+/// Shortcodes offered by the emoji picker, most frequently used first
+pub const PICKER: [&str; 30] = [
+    "white_check_mark",
+    "fire",
+    "rocket",
+    "tada",
+    "warning",
+    "star",
+    "bulb",
+    "bug",
+    "memo",
+    "calendar",
+    "alarm_clock",
+    "bell",
+    "pushpin",
+    "email",
+    "phone",
+    "shopping_cart",
+    "house",
+    "car",
+    "computer",
+    "book",
+    "moneybag",
+    "gift",
+    "birthday",
+    "coffee",
+    "heart",
+    "+1",
+    "eyes",
+    "thinking",
+    "sparkles",
+    "zzz",
+];
+
+/// Looks up the emoji for a shortcode name, without the colons.
+pub fn lookup(name: &str) -> Option<&'static str> {
+    SHORTCODES
+        .binary_search_by(|(code, _)| (*code).cmp(name))
+        .ok()
+        .map(|index| SHORTCODES[index].1)
+}
+
+/// Replaces every known `:shortcode:` in the text with its emoji.
+///
+/// Unknown names are left untouched, and `\:` stands for a literal colon
+/// that never starts a shortcode.
+///
+/// # Example
+/// ```
+/// use todo::utils::emoji::expand;
+///
+/// assert_eq!(expand("Launch :rocket::tada:"), "Launch 🚀🎉");
+/// assert_eq!(expand(":nope: and \\:rocket:"), ":nope: and :rocket:");
+/// ```
+pub fn expand(text: &str) -> String {
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(position) = rest.find([':', '\\']) {
+        expanded.push_str(&rest[..position]);
+        rest = &rest[position..];
+
+        if let Some(after_escape) = rest.strip_prefix("\\:") {
+            expanded.push(':');
+            rest = after_escape;
+            continue;
+        }
+        if let Some(after_backslash) = rest.strip_prefix('\\') {
+            expanded.push('\\');
+            rest = after_backslash;
+            continue;
+        }
+
+        // `rest` starts with a colon that may open a shortcode
+        let candidate = &rest[1..];
+        let shortcode = candidate
+            .find(':')
+            .map(|end| (end, &candidate[..end]))
+            .filter(|(_, name)| is_shortcode_name(name))
+            .and_then(|(end, name)| lookup(name).map(|emoji| (end, emoji)));
+        match shortcode {
+            Some((end, emoji)) => {
+                expanded.push_str(emoji);
+                rest = &candidate[end + 1..];
+            }
+            None => {
+                expanded.push(':');
+                rest = candidate;
+            }
+        }
+    }
+
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Checks if a name has the shape of a shortcode name.
+fn is_shortcode_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-'))
+}
+
+/// Inserts text at a cursor position reported by the browser.
+///
+/// Browsers count the cursor in UTF-16 code units, so the position is
+/// converted before inserting. A missing or out-of-range cursor appends.
+///
+/// # Arguments
+/// * `text` - The current text
+/// * `cursor` - The cursor position in UTF-16 code units, if known
+/// * `insert` - The text to insert
+///
+/// # Returns
+/// The new text and the cursor position just after the inserted text, in UTF-16 code units
+pub fn insert_at_cursor(text: &str, cursor: Option<usize>, insert: &str) -> (String, usize) {
+    let byte_index = cursor
+        .and_then(|cursor| {
+            let mut units = 0;
+            for (index, c) in text.char_indices() {
+                if units >= cursor {
+                    return Some(index);
+                }
+                units += c.len_utf16();
+            }
+            None
+        })
+        .unwrap_or(text.len());
+
+    let mut result = String::with_capacity(text.len() + insert.len());
+    result.push_str(&text[..byte_index]);
+    result.push_str(insert);
+    let new_cursor = result.encode_utf16().count();
+    result.push_str(&text[byte_index..]);
+    (result, new_cursor)
+}
@@ -0,0 +1,341 @@
+//! Export and import of todo backups
+//!
+//! Exports are a versioned JSON document. They can optionally be wrapped in a
+//! passphrase-protected `.todoenc` bundle: a magic header and format version
+//! byte, followed by the KDF salt, a key check value, the AEAD nonce and the
+//! encrypted document.
+
+use crate::models::{Todo, TodoList};
+use crate::utils::constants::export::{
+    ENCRYPTED_MAGIC, ENCRYPTED_VERSION, EXPORT_FORMAT_VERSION, KEY_CHECK_LEN, NONCE_LEN, SALT_LEN,
+};
+use crate::utils::text;
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Error types for export and import operations.
+#[derive(Debug, PartialEq)]
+pub enum ExportError {
+    /// Error serializing the export document
+    SerializeError(String),
+    /// The file is damaged or is not a todo export
+    CorruptFile(String),
+    /// The passphrase does not match the one used for the export
+    WrongPassphrase,
+    /// The file was written by a newer, unknown format version
+    UnsupportedVersion(u32),
+    /// Error deriving keys or encrypting data
+    CryptoError(String),
+    /// Error writing the export file
+    IoError(String),
+    /// Todos whose text is not valid, one message per todo
+    #[allow(dead_code)]
+    InvalidTodos(Vec<String>),
+}
+
+/// Versioned document written by every export.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportDocument {
+    /// Version of the export document format
+    pub version: u32,
+    /// When the export was created
+    pub exported_at: DateTime<Utc>,
+    /// The exported todos, in list order
+    pub todos: Vec<Todo>,
+}
+
+impl ExportDocument {
+    /// Creates an export document from the given todo list.
+    pub fn new(list: &TodoList) -> Self {
+        Self {
+            version: EXPORT_FORMAT_VERSION,
+            exported_at: Utc::now(),
+            todos: list.all_including_archived(),
+        }
+    }
+
+    /// Creates an export document of the todos with the given ids, in list order.
+    pub fn subset(list: &TodoList, ids: &[usize]) -> Self {
+        let ids: HashSet<usize> = ids.iter().copied().collect();
+        Self {
+            version: EXPORT_FORMAT_VERSION,
+            exported_at: Utc::now(),
+            todos: list
+                .all_including_archived()
+                .into_iter()
+                .filter(|todo| ids.contains(&todo.id))
+                .collect(),
+        }
+    }
+
+    /// Converts the document back into a todo list.
+    pub fn into_list(self) -> TodoList {
+        TodoList::from_todos(self.todos)
+    }
+}
+
+/// Serializes the todo list into a plain JSON export.
+pub fn export_json(list: &TodoList) -> Result<String, ExportError> {
+    to_json(&ExportDocument::new(list))
+}
+
+/// Serializes the todos with the given ids into a plain JSON export, leaving
+/// the others out.
+pub fn export_json_subset(list: &TodoList, ids: &[usize]) -> Result<String, ExportError> {
+    to_json(&ExportDocument::subset(list, ids))
+}
+
+/// Copies the todo list without the earlier texts of its todos.
+///
+/// Exports leave the text history out unless asked to keep it, since it can
+/// hold wording that was deliberately replaced.
+pub fn without_text_history(list: &TodoList) -> TodoList {
+    TodoList::from_todos(
+        list.all_including_archived()
+            .into_iter()
+            .map(|mut todo| {
+                todo.text_history.clear();
+                todo
+            })
+            .collect(),
+    )
+}
+
+/// Serializes an export document.
+fn to_json(document: &ExportDocument) -> Result<String, ExportError> {
+    serde_json::to_string_pretty(document).map_err(|e| ExportError::SerializeError(e.to_string()))
+}
+
+/// Names an export file after what it holds and the day it was made.
+///
+/// The parts describe the exported view; characters other than letters and
+/// digits become dashes. No parts means everything was exported.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use todo::utils::export::export_file_name;
+///
+/// let date = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+/// assert_eq!(export_file_name(&["active", "Work"], date, "json"), "todos-active-Work-2025-03-03.json");
+/// assert_eq!(export_file_name(&["this week"], date, "json"), "todos-this-week-2025-03-03.json");
+/// assert_eq!(export_file_name::<&str>(&[], date, "todoenc"), "todos-2025-03-03.todoenc");
+/// ```
+pub fn export_file_name<S: AsRef<str>>(parts: &[S], date: NaiveDate, extension: &str) -> String {
+    let mut name = String::from("todos");
+    for part in parts {
+        let slug = part
+            .as_ref()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+        if !slug.is_empty() {
+            name.push('-');
+            name.push_str(&slug);
+        }
+    }
+    format!("{}-{}.{}", name, date.format("%Y-%m-%d"), extension)
+}
+
+/// Parses a plain JSON export back into a todo list.
+///
+/// Every todo's text goes through the same validation as typed text: it is
+/// trimmed, and empty or overlong texts are reported per todo as
+/// [`ExportError::InvalidTodos`], as are links that are not web addresses.
+#[allow(dead_code)]
+pub fn import_json(json: &str) -> Result<TodoList, ExportError> {
+    let mut document = parse_document(json)?;
+
+    let mut problems = Vec::new();
+    for (position, todo) in document.todos.iter_mut().enumerate() {
+        match text::validate_todo_text(&todo.text) {
+            Ok(valid) => todo.text = valid,
+            Err(e) => problems.push(format!("Todo {} (id {}): {}", position + 1, todo.id, e)),
+        }
+        if let Some(url) = &mut todo.url {
+            match text::validate_url(url) {
+                Ok(valid) => *url = valid,
+                Err(e) => problems.push(format!("Todo {} (id {}): {}", position + 1, todo.id, e)),
+            }
+        }
+    }
+    if !problems.is_empty() {
+        return Err(ExportError::InvalidTodos(problems));
+    }
+
+    Ok(document.into_list())
+}
+
+/// Reads the todos of a plain JSON export in list order, leaving their
+/// validation to the [`ImportPlan`](crate::models::import_plan::ImportPlan)
+/// made from them.
+pub fn read_json(json: &str) -> Result<Vec<Todo>, ExportError> {
+    Ok(parse_document(json)?.into_list().all_including_archived())
+}
+
+/// Parses an export document, refusing versions newer than this app knows.
+fn parse_document(json: &str) -> Result<ExportDocument, ExportError> {
+    let document: ExportDocument =
+        serde_json::from_str(json).map_err(|e| ExportError::CorruptFile(e.to_string()))?;
+    if document.version > EXPORT_FORMAT_VERSION {
+        return Err(ExportError::UnsupportedVersion(document.version));
+    }
+    Ok(document)
+}
+
+/// Returns true if the bytes start with the encrypted bundle header.
+pub fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.starts_with(ENCRYPTED_MAGIC)
+}
+
+/// Derives the encryption key and the key check value from a passphrase.
+fn derive_keys(
+    passphrase: &str,
+    salt: &[u8],
+) -> Result<([u8; 32], [u8; KEY_CHECK_LEN]), ExportError> {
+    let mut output = [0u8; 32 + KEY_CHECK_LEN];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut output)
+        .map_err(|e| ExportError::CryptoError(e.to_string()))?;
+
+    let mut key = [0u8; 32];
+    let mut check = [0u8; KEY_CHECK_LEN];
+    key.copy_from_slice(&output[..32]);
+    check.copy_from_slice(&output[32..]);
+    Ok((key, check))
+}
+
+/// Exports the todo list as a passphrase-protected `.todoenc` bundle.
+///
+/// The key is derived with Argon2id and the document is sealed with
+/// ChaCha20-Poly1305.
+pub fn export_encrypted(list: &TodoList, passphrase: &str) -> Result<Vec<u8>, ExportError> {
+    seal(&export_json(list)?, passphrase)
+}
+
+/// Exports the todos with the given ids as a passphrase-protected `.todoenc`
+/// bundle, leaving the others out.
+pub fn export_encrypted_subset(
+    list: &TodoList,
+    ids: &[usize],
+    passphrase: &str,
+) -> Result<Vec<u8>, ExportError> {
+    seal(&export_json_subset(list, ids)?, passphrase)
+}
+
+/// Encrypts a JSON export into a `.todoenc` bundle.
+fn seal(json: &str, passphrase: &str) -> Result<Vec<u8>, ExportError> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    getrandom::getrandom(&mut salt).map_err(|e| ExportError::CryptoError(e.to_string()))?;
+    getrandom::getrandom(&mut nonce).map_err(|e| ExportError::CryptoError(e.to_string()))?;
+
+    let (key, check) = derive_keys(passphrase, &salt)?;
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&key))
+        .encrypt(Nonce::from_slice(&nonce), json.as_bytes())
+        .map_err(|e| ExportError::CryptoError(e.to_string()))?;
+
+    let mut bundle = Vec::with_capacity(
+        ENCRYPTED_MAGIC.len() + 1 + SALT_LEN + KEY_CHECK_LEN + NONCE_LEN + ciphertext.len(),
+    );
+    bundle.extend_from_slice(ENCRYPTED_MAGIC);
+    bundle.push(ENCRYPTED_VERSION);
+    bundle.extend_from_slice(&salt);
+    bundle.extend_from_slice(&check);
+    bundle.extend_from_slice(&nonce);
+    bundle.extend_from_slice(&ciphertext);
+    Ok(bundle)
+}
+
+/// Decrypts a `.todoenc` bundle back into a todo list.
+///
+/// A passphrase that does not match the stored key check value is reported as
+/// [`ExportError::WrongPassphrase`]; any other damage as
+/// [`ExportError::CorruptFile`].
+#[allow(dead_code)]
+pub fn import_encrypted(bytes: &[u8], passphrase: &str) -> Result<TodoList, ExportError> {
+    import_json(&decrypt_bundle(bytes, passphrase)?)
+}
+
+/// Decrypts a `.todoenc` bundle into the JSON export it holds.
+///
+/// Fails the same way as [`import_encrypted`].
+pub fn decrypt_bundle(bytes: &[u8], passphrase: &str) -> Result<String, ExportError> {
+    let rest = bytes
+        .strip_prefix(ENCRYPTED_MAGIC)
+        .ok_or_else(|| ExportError::CorruptFile("Not an encrypted todo backup".to_string()))?;
+    let (&version, rest) = rest
+        .split_first()
+        .ok_or_else(|| ExportError::CorruptFile("Missing format version".to_string()))?;
+    if version != ENCRYPTED_VERSION {
+        return Err(ExportError::UnsupportedVersion(version as u32));
+    }
+    if rest.len() < SALT_LEN + KEY_CHECK_LEN + NONCE_LEN {
+        return Err(ExportError::CorruptFile("File is truncated".to_string()));
+    }
+
+    let (salt, rest) = rest.split_at(SALT_LEN);
+    let (stored_check, rest) = rest.split_at(KEY_CHECK_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let (key, check) = derive_keys(passphrase, salt)?;
+    if check != stored_check {
+        return Err(ExportError::WrongPassphrase);
+    }
+
+    let plaintext = ChaCha20Poly1305::new(Key::from_slice(&key))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| ExportError::CorruptFile("Encrypted data is damaged".to_string()))?;
+    String::from_utf8(plaintext).map_err(|e| ExportError::CorruptFile(e.to_string()))
+}
+
+/// Hands an export file to the user.
+///
+/// On web this starts a browser download; on desktop the file is written to
+/// the downloads directory. Returns a short description of where it went.
+#[cfg(target_arch = "wasm32")]
+pub fn save_export_file(file_name: &str, bytes: &[u8]) -> Result<String, ExportError> {
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let download_error = |_: JsValue| ExportError::IoError("Failed to start download".to_string());
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+    let blob = web_sys::Blob::new_with_u8_array_sequence(&parts).map_err(download_error)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(download_error)?;
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| ExportError::IoError("No document available".to_string()))?;
+    let anchor: web_sys::HtmlAnchorElement = document
+        .create_element("a")
+        .map_err(download_error)?
+        .dyn_into()
+        .map_err(|_| ExportError::IoError("Failed to create download link".to_string()))?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+    let _ = web_sys::Url::revoke_object_url(&url);
+
+    Ok(format!("Downloaded {}", file_name))
+}
+
+/// Hands an export file to the user.
+///
+/// On web this starts a browser download; on desktop the file is written to
+/// the downloads directory. Returns a short description of where it went.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_export_file(file_name: &str, bytes: &[u8]) -> Result<String, ExportError> {
+    #[cfg(feature = "desktop")]
+    let dir = dirs::download_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
+    #[cfg(not(feature = "desktop"))]
+    let dir = std::path::PathBuf::from(".");
+
+    let path = dir.join(file_name);
+    std::fs::write(&path, bytes)
+        .map_err(|e| ExportError::IoError(format!("Failed to write {:?}: {}", path, e)))?;
+    Ok(format!("Saved to {}", path.display()))
+}
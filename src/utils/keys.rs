@@ -0,0 +1,141 @@
+//! Storage keys used by the app
+//!
+//! Every value lives under a namespaced key of the form `todo/v1/<name>`, so
+//! new features can add keys without clashing and a future layout change can
+//! move to `todo/v2/`. Values written by earlier versions under their old
+//! ad-hoc keys are moved over by [`migrate`] when storage is first opened.
+
+use crate::models::{FilterCriteria, QuickFilter};
+use crate::utils::storage::{RawStorage, StorageError};
+use tracing::info;
+
+/// Prefix shared by every key
+pub const NAMESPACE: &str = "todo/v1/";
+
+/// Key of the todo list
+pub const TODOS: &str = "todo/v1/todos";
+
+/// Key of the user settings
+pub const SETTINGS: &str = "todo/v1/settings";
+
+/// Key of the light or dark theme choice
+pub const THEME: &str = "todo/v1/theme";
+
+/// Key of whether the first-run onboarding was completed
+pub const ONBOARDING_DONE: &str = "todo/v1/onboarding-done";
+
+/// Key of the due soon reminders already seen in the bell dropdown
+pub const SEEN_REMINDERS: &str = "todo/v1/seen-reminders";
+
+/// Key of the filters applied to the list
+pub const FILTERS: &str = "todo/v1/filters";
+
+/// Key of the order the list is sorted in
+pub const SORT_MODE: &str = "todo/v1/sort-mode";
+
+/// Key of the todo shown in the docked detail panel
+pub const SELECTED_TODO: &str = "todo/v1/selected-todo";
+
+/// Key of the day the follow-up banner was last shown
+pub const FOLLOW_UP_SHOWN: &str = "todo/v1/follow-up-shown";
+
+/// Key of the day the daily digest notification last fired
+pub const DIGEST_FIRED: &str = "todo/v1/digest-fired";
+
+/// Key of when "Do not disturb" ends
+pub const QUIET_MODE: &str = "todo/v1/quiet-mode";
+
+/// Keys used before the namespaced scheme, paired with their replacements
+pub const LEGACY_KEYS: [(&str, &str); 5] = [
+    ("dioxus-todo-app", TODOS),
+    ("dioxus-todo-app.corrupt", "todo/v1/todos.corrupt"),
+    ("dioxus-todo-app-settings", SETTINGS),
+    ("dioxus-todo-app-theme", THEME),
+    ("dioxus-todo-app-onboarding-done", ONBOARDING_DONE),
+];
+
+/// Key the quick filter pill was kept under before it joined [`FILTERS`]
+pub const LEGACY_QUICK_FILTER: &str = "todo/v1/quick-filter";
+
+/// Builds the namespaced key for a value.
+///
+/// # Example
+/// ```
+/// use todo::utils::keys;
+///
+/// assert_eq!(keys::key("todos"), keys::TODOS);
+/// ```
+#[allow(dead_code)]
+pub fn key(name: &str) -> String {
+    format!("{}{}", NAMESPACE, name)
+}
+
+/// Moves values from the legacy keys to their namespaced keys.
+///
+/// A value already present under the new key is kept, and the legacy key is
+/// deleted either way, so running the migration again changes nothing.
+///
+/// # Arguments
+/// * `storage` - The backend to migrate
+///
+/// # Returns
+/// The number of values moved to a new key
+pub fn migrate(storage: &dyn RawStorage) -> Result<usize, StorageError> {
+    let mut migrated = 0;
+    for (old_key, new_key) in LEGACY_KEYS {
+        let value = match storage.load_str(old_key) {
+            Ok(value) => value,
+            Err(StorageError::NotFound(_)) => continue,
+            Err(e) => return Err(e),
+        };
+
+        if matches!(storage.load_str(new_key), Err(StorageError::NotFound(_))) {
+            storage.save_str(new_key, &value)?;
+            migrated += 1;
+            info!("Migrated stored value from {} to {}", old_key, new_key);
+        }
+        storage.delete(old_key)?;
+    }
+    if migrate_quick_filter(storage)? {
+        migrated += 1;
+    }
+
+    Ok(migrated)
+}
+
+/// Moves the quick filter pill kept under [`LEGACY_QUICK_FILTER`] into the
+/// filters stored under [`FILTERS`].
+///
+/// Filters already stored are kept, and the old key is deleted either way.
+///
+/// # Returns
+/// Whether a pill was moved
+fn migrate_quick_filter(storage: &dyn RawStorage) -> Result<bool, StorageError> {
+    let value = match storage.load_str(LEGACY_QUICK_FILTER) {
+        Ok(value) => value,
+        Err(StorageError::NotFound(_)) => return Ok(false),
+        Err(e) => return Err(e),
+    };
+
+    let quick_filter = serde_json::from_str::<Option<QuickFilter>>(&value)
+        .ok()
+        .flatten();
+    let moved = quick_filter.is_some()
+        && matches!(storage.load_str(FILTERS), Err(StorageError::NotFound(_)));
+    if moved {
+        let criteria = FilterCriteria {
+            quick_filter,
+            ..FilterCriteria::default()
+        };
+        let json = serde_json::to_string(&criteria)
+            .map_err(|e| StorageError::SerializeError(e.to_string()))?;
+        storage.save_str(FILTERS, &json)?;
+        info!(
+            "Migrated stored value from {} to {}",
+            LEGACY_QUICK_FILTER, FILTERS
+        );
+    }
+    storage.delete(LEGACY_QUICK_FILTER)?;
+
+    Ok(moved)
+}
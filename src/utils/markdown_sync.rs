@@ -0,0 +1,424 @@
+//! Keeping a Markdown checklist and the todos in step
+//!
+//! A checklist file such as `TODO.md` holds one todo per line:
+//!
+//! ```text
+//! - [ ] Call the plumber #home (due: 2025-03-14)
+//! - [x] Renew the passport
+//! ```
+//!
+//! Tags are written as `#tag` words and the due date as a `(due: YYYY-MM-DD)`
+//! suffix, both at the end of the line. Every other line, such as headings,
+//! prose and checklist lines that cannot be read, is kept exactly as it was
+//! when the file is written back. The todos synced with the file carry the
+//! sync tag from the settings, which is not written to the file.
+//!
+//! Lines are matched to todos by their text first and then by position, so
+//! renaming a line on either side updates the same todo.
+
+use crate::models::{Todo, TodoList};
+use crate::utils::dates;
+use chrono::NaiveDate;
+
+/// A checklist line read as a todo.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MarkdownItem {
+    /// Whitespace before the list marker
+    pub indent: String,
+    /// The list marker, `-`, `*` or `+`
+    pub marker: char,
+    pub done: bool,
+    pub text: String,
+    pub tags: Vec<String>,
+    pub due: Option<NaiveDate>,
+}
+
+impl MarkdownItem {
+    /// Describes a todo as a checklist line, leaving out the sync tag.
+    pub fn from_todo(todo: &Todo, sync_tag: &str) -> Self {
+        Self {
+            indent: String::new(),
+            marker: '-',
+            done: todo.completed,
+            text: todo.text.clone(),
+            tags: todo
+                .tags
+                .iter()
+                .filter(|tag| *tag != sync_tag && is_tag_word(tag))
+                .cloned()
+                .collect(),
+            due: todo.due_date.map(|due| due.date_naive()),
+        }
+    }
+
+    /// Formats the item as a checklist line.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use todo::utils::markdown_sync::MarkdownItem;
+    ///
+    /// let item = MarkdownItem {
+    ///     indent: String::new(),
+    ///     marker: '-',
+    ///     done: false,
+    ///     text: "Call the plumber".to_string(),
+    ///     tags: vec!["home".to_string()],
+    ///     due: NaiveDate::from_ymd_opt(2025, 3, 14),
+    /// };
+    /// assert_eq!(item.to_line(), "- [ ] Call the plumber #home (due: 2025-03-14)");
+    /// ```
+    pub fn to_line(&self) -> String {
+        let mut line = format!(
+            "{}{} [{}] {}",
+            self.indent,
+            self.marker,
+            if self.done { 'x' } else { ' ' },
+            self.text
+        );
+        for tag in &self.tags {
+            line.push_str(&format!(" #{}", tag));
+        }
+        if let Some(due) = self.due {
+            line.push_str(&format!(" (due: {})", due.format("%Y-%m-%d")));
+        }
+        line
+    }
+
+    /// Checks if the item says the same as another, ignoring how it is laid out.
+    fn same_content(&self, other: &Self) -> bool {
+        self.done == other.done
+            && self.text == other.text
+            && self.tags == other.tags
+            && self.due == other.due
+    }
+}
+
+/// A line of a checklist file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MarkdownLine {
+    /// A checklist line, with the line as it was read
+    Item { item: MarkdownItem, raw: String },
+    /// A line that looks like a checklist line but cannot be read
+    Malformed(String),
+    /// Any other line
+    Text(String),
+}
+
+/// Checks if a word can be written as a `#tag`.
+fn is_tag_word(tag: &str) -> bool {
+    !tag.is_empty() && !tag.contains(char::is_whitespace) && !tag.starts_with('#')
+}
+
+/// Reads a `#tag` and `(due: …)` suffixes off the end of an item's text.
+///
+/// # Returns
+/// The text, tags and due date, or `None` if a due date cannot be read or
+/// no text is left
+fn split_suffixes(rest: &str) -> Option<(String, Vec<String>, Option<NaiveDate>)> {
+    let mut text = rest.trim_end();
+    let mut tags = Vec::new();
+    let mut due = None;
+    loop {
+        if text.ends_with(')')
+            && let Some(start) = text.rfind("(due:")
+        {
+            let value = text[start + "(due:".len()..text.len() - 1].trim();
+            due = Some(NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?);
+            text = text[..start].trim_end();
+            continue;
+        }
+        let word_start = text.rfind(char::is_whitespace).map_or(0, |at| at + 1);
+        match text[word_start..].strip_prefix('#') {
+            Some(tag) if word_start > 0 && is_tag_word(tag) => {
+                tags.insert(0, tag.to_string());
+                text = text[..word_start].trim_end();
+            }
+            _ => break,
+        }
+    }
+    (!text.is_empty()).then(|| (text.to_string(), tags, due))
+}
+
+/// Reads one line of a checklist file.
+fn parse_line(line: &str) -> MarkdownLine {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+    let mut chars = trimmed.chars();
+    let marker = match chars.next() {
+        Some(marker @ ('-' | '*' | '+')) if chars.next() == Some(' ') => marker,
+        _ => return MarkdownLine::Text(line.to_string()),
+    };
+    let after_marker = &trimmed[2..];
+    // A checkbox is three characters like "[ ]"; "[link](…)" is not one
+    if !after_marker.starts_with('[') || after_marker.chars().nth(2) != Some(']') {
+        return MarkdownLine::Text(line.to_string());
+    }
+    let done = match after_marker.chars().nth(1) {
+        Some(' ') => false,
+        Some('x' | 'X') => true,
+        _ => return MarkdownLine::Malformed(line.to_string()),
+    };
+    let Some(rest) = after_marker[3..].strip_prefix(' ') else {
+        return MarkdownLine::Malformed(line.to_string());
+    };
+    match split_suffixes(rest) {
+        Some((text, tags, due)) => MarkdownLine::Item {
+            item: MarkdownItem {
+                indent: indent.to_string(),
+                marker,
+                done,
+                text,
+                tags,
+                due,
+            },
+            raw: line.to_string(),
+        },
+        None => MarkdownLine::Malformed(line.to_string()),
+    }
+}
+
+/// A checklist file read line by line.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MarkdownDoc {
+    pub lines: Vec<MarkdownLine>,
+    /// Whether lines end with `\r\n`
+    crlf: bool,
+}
+
+impl MarkdownDoc {
+    /// Reads a checklist file.
+    ///
+    /// # Example
+    /// ```
+    /// use todo::utils::markdown_sync::MarkdownDoc;
+    ///
+    /// let text = "# Plans\n- [ ] Call the plumber #home\n- [?] Not sure\n";
+    /// let doc = MarkdownDoc::parse(text);
+    /// assert_eq!(doc.items().count(), 1);
+    /// assert_eq!(doc.malformed_count(), 1);
+    /// assert_eq!(doc.to_text(), text);
+    /// ```
+    pub fn parse(text: &str) -> Self {
+        let crlf = text.contains("\r\n");
+        let lines = text
+            .split('\n')
+            .map(|line| parse_line(line.strip_suffix('\r').unwrap_or(line)))
+            .collect();
+        Self { lines, crlf }
+    }
+
+    /// Writes the file out again; unchanged items keep their original layout.
+    pub fn to_text(&self) -> String {
+        let lines: Vec<&str> = self
+            .lines
+            .iter()
+            .map(|line| match line {
+                MarkdownLine::Item { raw, .. } => raw.as_str(),
+                MarkdownLine::Malformed(raw) | MarkdownLine::Text(raw) => raw.as_str(),
+            })
+            .collect();
+        lines.join(if self.crlf { "\r\n" } else { "\n" })
+    }
+
+    /// Lists the checklist items in file order.
+    pub fn items(&self) -> impl Iterator<Item = &MarkdownItem> {
+        self.lines.iter().filter_map(|line| match line {
+            MarkdownLine::Item { item, .. } => Some(item),
+            _ => None,
+        })
+    }
+
+    /// Counts the checklist lines that cannot be read.
+    pub fn malformed_count(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| matches!(line, MarkdownLine::Malformed(_)))
+            .count()
+    }
+}
+
+/// Matches checklist items to todos, by text first and then by position.
+///
+/// # Returns
+/// For each item, the id of the todo it stands for, or `None` for a new item
+pub fn match_items(items: &[&MarkdownItem], todos: &[&Todo]) -> Vec<Option<usize>> {
+    let mut matched: Vec<Option<usize>> = vec![None; items.len()];
+    let mut taken = vec![false; todos.len()];
+    for (slot, item) in matched.iter_mut().zip(items) {
+        if let Some(index) = (0..todos.len()).find(|&i| !taken[i] && todos[i].text == item.text) {
+            taken[index] = true;
+            *slot = Some(todos[index].id);
+        }
+    }
+    // What is left pairs up in order, as an item renamed on one side
+    let mut left = (0..todos.len()).filter(|&i| !taken[i]);
+    for slot in matched.iter_mut().filter(|slot| slot.is_none()) {
+        match left.next() {
+            Some(index) => *slot = Some(todos[index].id),
+            None => break,
+        }
+    }
+    matched
+}
+
+/// Changes to make to the todos after the file changed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PullPlan {
+    /// Todos to update, with their item in the file
+    pub updates: Vec<(usize, MarkdownItem)>,
+    /// Items new in the file
+    pub added: Vec<MarkdownItem>,
+    /// Todos no longer in the file
+    pub removed: Vec<usize>,
+}
+
+/// Works out how the synced todos change to match the file.
+///
+/// # Arguments
+/// * `doc` - The file as read
+/// * `synced` - The todos carrying the sync tag, in list order
+pub fn plan_pull(doc: &MarkdownDoc, synced: &[&Todo]) -> PullPlan {
+    let items: Vec<&MarkdownItem> = doc.items().collect();
+    let matched = match_items(&items, synced);
+    let mut plan = PullPlan::default();
+    for (item, id) in items.into_iter().zip(&matched) {
+        match id {
+            Some(id) => plan.updates.push((*id, item.clone())),
+            None => plan.added.push(item.clone()),
+        }
+    }
+    plan.removed = synced
+        .iter()
+        .map(|todo| todo.id)
+        .filter(|id| !matched.contains(&Some(*id)))
+        .collect();
+    plan
+}
+
+/// Applies the changes from the file to the todos.
+///
+/// Updated todos change only where they differ from their item, so their
+/// history and completion time stay right; locked todos keep their text.
+/// New todos carry the sync tag, and removed ones go to the trash.
+///
+/// # Returns
+/// The number of todos added, changed or removed
+pub fn apply_pull(list: &mut TodoList, plan: &PullPlan, sync_tag: &str) -> usize {
+    let mut changed = 0;
+    for (id, item) in &plan.updates {
+        let Some(todo) = list.get(*id) else {
+            continue;
+        };
+        if MarkdownItem::from_todo(todo, sync_tag).same_content(item) {
+            continue;
+        }
+        let todo = todo.clone();
+        changed += 1;
+        if todo.text != item.text {
+            let _ = list.update_text(*id, item.text.clone());
+        }
+        if todo.completed != item.done {
+            list.toggle(*id);
+        }
+        // A date outside the allowed window leaves the todo's as it was
+        let _ = list.set_due_date(*id, item.due.map(dates::due_date_on));
+        for tag in todo.tags.iter().filter(|tag| *tag != sync_tag) {
+            if !item.tags.contains(tag) {
+                list.remove_tag(*id, tag);
+            }
+        }
+        for tag in &item.tags {
+            list.add_tag(*id, tag.clone());
+        }
+    }
+    for item in &plan.added {
+        let id = list.add(item.text.clone());
+        if item.done {
+            list.toggle(id);
+        }
+        let _ = list.set_due_date(id, item.due.map(dates::due_date_on));
+        list.add_tag(id, sync_tag.to_string());
+        for tag in &item.tags {
+            list.add_tag(id, tag.clone());
+        }
+        changed += 1;
+    }
+    changed + list.move_many_to_trash(&plan.removed).changed
+}
+
+/// Writes the synced todos into the file.
+///
+/// Items keep their place and layout unless their todo changed; items whose
+/// todo is gone are removed. Todos not in the file yet are added after the
+/// last item. Other lines, including ones that cannot be read, are kept as
+/// they were.
+///
+/// # Example
+/// ```
+/// use todo::models::TodoList;
+/// use todo::utils::markdown_sync::{MarkdownDoc, write_back};
+///
+/// let mut list = TodoList::new();
+/// let id = list.add("Call the plumber".to_string());
+/// list.add_tag(id, "TODO.md".to_string());
+/// list.toggle(id);
+///
+/// let doc = MarkdownDoc::parse("# Plans\n- [ ] Call the plumber\n- [ ] broken (due: soon)\n");
+/// let synced = list.all();
+/// let written = write_back(&doc, &synced.iter().collect::<Vec<_>>(), "TODO.md");
+/// assert_eq!(
+///     written.to_text(),
+///     "# Plans\n- [x] Call the plumber\n- [ ] broken (due: soon)\n"
+/// );
+/// ```
+pub fn write_back(doc: &MarkdownDoc, synced: &[&Todo], sync_tag: &str) -> MarkdownDoc {
+    let items: Vec<&MarkdownItem> = doc.items().collect();
+    let matched = match_items(&items, synced);
+    let todo_of = |id: usize| synced.iter().find(|todo| todo.id == id);
+
+    let mut lines = Vec::with_capacity(doc.lines.len() + synced.len());
+    let mut after_last_item = None;
+    let mut item_ids = matched.iter();
+    for line in &doc.lines {
+        let MarkdownLine::Item { item, .. } = line else {
+            lines.push(line.clone());
+            continue;
+        };
+        // Items whose todo is gone are left out
+        let Some(todo) = item_ids.next().copied().flatten().and_then(todo_of) else {
+            continue;
+        };
+        let mut updated = MarkdownItem::from_todo(todo, sync_tag);
+        if updated.same_content(item) {
+            lines.push(line.clone());
+        } else {
+            updated.indent = item.indent.clone();
+            updated.marker = item.marker;
+            let raw = updated.to_line();
+            lines.push(MarkdownLine::Item { item: updated, raw });
+        }
+        after_last_item = Some(lines.len());
+    }
+
+    let new_items: Vec<MarkdownLine> = synced
+        .iter()
+        .filter(|todo| !matched.contains(&Some(todo.id)))
+        .map(|todo| {
+            let item = MarkdownItem::from_todo(todo, sync_tag);
+            let raw = item.to_line();
+            MarkdownLine::Item { item, raw }
+        })
+        .collect();
+    // New items go after the last one, or before the final newline of a file without any
+    let at = after_last_item.unwrap_or(match lines.last() {
+        Some(MarkdownLine::Text(last)) if last.is_empty() => lines.len() - 1,
+        _ => lines.len(),
+    });
+    lines.splice(at..at, new_items);
+
+    MarkdownDoc {
+        lines,
+        crlf: doc.crlf,
+    }
+}
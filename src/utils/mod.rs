@@ -1,5 +1,32 @@
+pub mod autosave;
+#[cfg(feature = "ui")]
+pub mod clipboard;
+pub mod clock;
 pub mod constants;
+pub mod data_dir;
+pub mod dates;
+pub mod debug_log;
+pub mod demo;
+pub mod emoji;
+pub mod export;
+pub mod keys;
+// Used by the app only with the `markdown-sync` feature
+#[cfg_attr(not(feature = "markdown-sync"), allow(dead_code))]
+pub mod markdown_sync;
+pub mod notes;
+pub mod onboarding;
+pub mod profile;
+pub mod reports;
+pub mod share;
+pub mod sound;
+pub mod startup;
 pub mod storage;
+pub mod text;
 pub mod theme;
+#[cfg(feature = "ui")]
+pub mod timer;
+pub mod version;
 
-pub use storage::{load, save};
+// The app itself loads its todos through `storage::initial_load_async`
+#[allow(unused_imports)]
+pub use storage::{load, load_async, save, save_async};
@@ -0,0 +1,99 @@
+//! Helpers for the free-form notes attached to a todo
+//!
+//! Notes are plain text. Lines starting with `- ` (or `* `, optionally with a
+//! `[ ]` checkbox) are action items that can be turned into todos of their
+//! own. Extracted lines can be struck through with markdown `~~`.
+
+/// Bullet prefixes that mark an action item
+const BULLETS: [&str; 2] = ["- ", "* "];
+
+/// Splits a line into its bullet prefix and the rest.
+fn split_bullet(line: &str) -> Option<(&str, &str)> {
+    let indent = line.len() - line.trim_start().len();
+    let rest = &line[indent..];
+    let bullet = BULLETS.iter().find(|bullet| rest.starts_with(**bullet))?;
+    let mut prefix_len = indent + bullet.len();
+    if line[prefix_len..].starts_with("[ ] ") {
+        prefix_len += "[ ] ".len();
+    }
+    Some(line.split_at(prefix_len))
+}
+
+/// Checks if a line was already struck through.
+pub fn is_struck(line: &str) -> bool {
+    let content = split_bullet(line).map_or(line, |(_, rest)| rest).trim();
+    content.len() > 4 && content.starts_with("~~") && content.ends_with("~~")
+}
+
+/// Gets the text of an action item line, without its bullet.
+///
+/// # Returns
+/// The item text, or `None` if the line is not an open action item
+///
+/// # Example
+/// ```
+/// use todo::utils::notes::action_item;
+///
+/// assert_eq!(action_item("- Email Bob"), Some("Email Bob"));
+/// assert_eq!(action_item("* [ ] Book flights"), Some("Book flights"));
+/// assert_eq!(action_item("Background info"), None);
+/// assert_eq!(action_item("- ~~Done already~~"), None);
+/// ```
+pub fn action_item(line: &str) -> Option<&str> {
+    let (_, rest) = split_bullet(line)?;
+    let text = rest.trim();
+    (!text.is_empty() && !is_struck(line)).then_some(text)
+}
+
+/// Gets the text a line would become as a todo.
+///
+/// Action items lose their bullet; any other line is used as it is.
+pub fn line_text(line: &str) -> &str {
+    action_item(line).unwrap_or(line.trim())
+}
+
+/// Strikes a line of the notes through, keeping its bullet.
+///
+/// # Arguments
+/// * `notes` - The notes text
+/// * `line_index` - The 0-based index of the line
+///
+/// # Returns
+/// The notes with that line struck through; unchanged if the line does not
+/// exist, is blank or is already struck through
+pub fn strike_line(notes: &str, line_index: usize) -> String {
+    notes
+        .split('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            if index != line_index || line.trim().is_empty() || is_struck(line) {
+                return line.to_string();
+            }
+            let (prefix, rest) = split_bullet(line).unwrap_or(("", line));
+            let content = rest.trim();
+            let leading = &rest[..rest.len() - rest.trim_start().len()];
+            format!("{}{}~~{}~~", prefix, leading, content)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Gets the index of the line containing a cursor reported by the browser.
+///
+/// # Arguments
+/// * `notes` - The notes text
+/// * `cursor` - The cursor position in UTF-16 code units
+pub fn line_at(notes: &str, cursor: usize) -> usize {
+    let mut units = 0;
+    let mut line = 0;
+    for c in notes.chars() {
+        if units >= cursor {
+            break;
+        }
+        if c == '\n' {
+            line += 1;
+        }
+        units += c.len_utf16();
+    }
+    line
+}
@@ -0,0 +1,53 @@
+//! First-run onboarding helpers
+//!
+//! This module decides whether the app is being launched for the first time
+//! and builds the sample todos offered to new users.
+
+use crate::models::TodoList;
+use crate::utils::constants::onboarding::SAMPLE_TODOS;
+use crate::utils::keys;
+use crate::utils::storage::{self, StorageError};
+use chrono::{Duration, Utc};
+
+/// Checks if no todo data has ever been stored.
+///
+/// # Returns
+/// `true` only when the storage is readable and holds no todos. Unreadable
+/// storage is not treated as a first run, so existing data is never hidden
+/// behind the welcome prompt.
+pub fn is_first_run() -> bool {
+    storage::get_storage()
+        .map(|storage| {
+            matches!(
+                storage.load_str(keys::TODOS),
+                Err(StorageError::NotFound(_))
+            )
+        })
+        .unwrap_or(false)
+}
+
+/// Builds the list of sample todos offered on first launch.
+///
+/// # Returns
+/// A todo list with the todos from `SAMPLE_TODOS`, with due dates relative to now
+///
+/// # Example
+/// ```
+/// use todo::utils::onboarding::sample_todo_list;
+///
+/// let list = sample_todo_list();
+/// assert_eq!(list.total_count(), 3);
+/// ```
+pub fn sample_todo_list() -> TodoList {
+    let mut list = TodoList::new();
+    for (text, tags, due_in_days) in SAMPLE_TODOS {
+        let id = list.add(text.to_string());
+        for tag in tags {
+            list.add_tag(id, tag.to_string());
+        }
+        if let Some(days) = due_in_days {
+            let _ = list.set_due_date(id, Some(Utc::now() + Duration::days(days)));
+        }
+    }
+    list
+}
@@ -0,0 +1,222 @@
+//! Named profiles keeping separate sets of data
+//!
+//! Every profile has its own todos, settings and other stored values, so a
+//! work list and a personal list can live on the same machine. The profile
+//! is picked at launch: with `--profile <name>` or the `TODO_PROFILE`
+//! environment variable in native builds, and with `?profile=<name>` in the
+//! web build. Without one, or with `default`, the app uses the data it
+//! always did.
+//!
+//! On desktop each profile gets its own database in a subdirectory of the
+//! data folder; on web and in memory its keys get a prefix.
+
+use crate::utils::constants::profile::{
+    DEFAULT_PROFILE, MAX_PROFILE_NAME_LENGTH, PROFILE_ARG, PROFILE_ENV, PROFILE_QUERY_PARAM,
+};
+use std::sync::OnceLock;
+
+static ACTIVE_PROFILE: OnceLock<Option<String>> = OnceLock::new();
+
+/// Checks a profile name.
+///
+/// # Arguments
+/// * `value` - The name as typed, passed on the command line or in the URL
+///
+/// # Returns
+/// The name in lower case, or `None` for the default profile and for names
+/// that are empty, too long or hold anything other than letters, digits,
+/// `-` and `_`
+///
+/// # Example
+/// ```
+/// use todo::utils::profile::parse_name;
+///
+/// assert_eq!(parse_name(" Work "), Some("work".to_string()));
+/// assert_eq!(parse_name("default"), None);
+/// assert_eq!(parse_name("../secrets"), None);
+/// ```
+pub fn parse_name(value: &str) -> Option<String> {
+    let name = value.trim().to_lowercase();
+    let valid = !name.is_empty()
+        && name.chars().count() <= MAX_PROFILE_NAME_LENGTH
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    (valid && name != DEFAULT_PROFILE).then_some(name)
+}
+
+/// Finds the profile in command-line arguments, given as `--profile work` or
+/// `--profile=work`.
+///
+/// # Example
+/// ```
+/// use todo::utils::profile::from_args;
+///
+/// let args = ["todo", "--profile", "work"].map(String::from);
+/// assert_eq!(from_args(args), Some("work".to_string()));
+/// assert_eq!(from_args(["todo", "--profile=home"].map(String::from)), Some("home".to_string()));
+/// assert_eq!(from_args(["todo"].map(String::from)), None);
+/// ```
+pub fn from_args(args: impl IntoIterator<Item = String>) -> Option<String> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == PROFILE_ARG {
+            return args.next().as_deref().and_then(parse_name);
+        }
+        if let Some(value) = arg
+            .strip_prefix(PROFILE_ARG)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return parse_name(value);
+        }
+    }
+    None
+}
+
+/// Finds the profile in a URL query string such as `?profile=work&demo=1`.
+///
+/// # Example
+/// ```
+/// use todo::utils::profile::from_query;
+///
+/// assert_eq!(from_query("?demo=1&profile=work"), Some("work".to_string()));
+/// assert_eq!(from_query("?demo=1"), None);
+/// ```
+#[allow(dead_code)]
+pub fn from_query(query: &str) -> Option<String> {
+    query
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|pair| pair.strip_prefix(PROFILE_QUERY_PARAM)?.strip_prefix('='))
+        .and_then(parse_name)
+}
+
+/// Sets the profile in a URL query string, keeping the other parameters.
+///
+/// # Arguments
+/// * `query` - The query string, with or without the leading `?`
+/// * `profile` - The profile to switch to, `None` for the default one
+///
+/// # Example
+/// ```
+/// use todo::utils::profile::with_profile_query;
+///
+/// assert_eq!(with_profile_query("?demo=1", Some("work")), "?demo=1&profile=work");
+/// assert_eq!(with_profile_query("?profile=work&demo=1", Some("home")), "?demo=1&profile=home");
+/// assert_eq!(with_profile_query("?profile=work", None), "");
+/// ```
+#[allow(dead_code)]
+pub fn with_profile_query(query: &str, profile: Option<&str>) -> String {
+    let mut pairs: Vec<String> = query
+        .trim_start_matches('?')
+        .split('&')
+        .filter(|pair| !pair.is_empty() && pair.split('=').next() != Some(PROFILE_QUERY_PARAM))
+        .map(String::from)
+        .collect();
+    if let Some(profile) = profile {
+        pairs.push(format!("{}={}", PROFILE_QUERY_PARAM, profile));
+    }
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", pairs.join("&"))
+    }
+}
+
+/// Gets the arguments to relaunch the app with in another profile.
+///
+/// # Arguments
+/// * `args` - The arguments the app was started with, without the program
+/// * `profile` - The profile to switch to, `None` for the default one
+///
+/// # Example
+/// ```
+/// use todo::utils::profile::relaunch_args;
+///
+/// let args = ["--profile", "work", "--verbose"].map(String::from);
+/// assert_eq!(relaunch_args(args, Some("home")), ["--verbose", "--profile", "home"]);
+/// assert_eq!(relaunch_args(["--profile=work".to_string()], None), Vec::<String>::new());
+/// ```
+pub fn relaunch_args(args: impl IntoIterator<Item = String>, profile: Option<&str>) -> Vec<String> {
+    let mut kept = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == PROFILE_ARG {
+            args.next();
+        } else if !arg.starts_with(&format!("{}=", PROFILE_ARG)) {
+            kept.push(arg);
+        }
+    }
+    if let Some(profile) = profile {
+        kept.extend([PROFILE_ARG.to_string(), profile.to_string()]);
+    }
+    kept
+}
+
+/// Reads the profile from the page URL.
+#[cfg(target_arch = "wasm32")]
+fn detect() -> Option<String> {
+    web_sys::window()
+        .and_then(|window| window.location().search().ok())
+        .and_then(|query| from_query(&query))
+}
+
+/// Reads the profile from the command line, then the environment.
+#[cfg(not(target_arch = "wasm32"))]
+fn detect() -> Option<String> {
+    from_args(std::env::args().skip(1)).or_else(|| {
+        std::env::var(PROFILE_ENV)
+            .ok()
+            .and_then(|value| parse_name(&value))
+    })
+}
+
+/// Gets the profile the app runs in, `None` for the default one.
+///
+/// The profile is detected once and kept for the lifetime of the process;
+/// switching to another one restarts the app.
+pub fn active() -> Option<&'static str> {
+    ACTIVE_PROFILE.get_or_init(detect).as_deref()
+}
+
+/// Restarts the app in another profile.
+///
+/// The web build reloads the page with the profile in its URL; native builds
+/// start a new process with `--profile` and exit this one. Unsaved changes
+/// must be written before.
+///
+/// # Arguments
+/// * `profile` - The profile to switch to, `None` for the default one
+pub fn switch_to(profile: Option<&str>) {
+    #[cfg(target_arch = "wasm32")]
+    if let Some(location) = web_sys::window().map(|window| window.location()) {
+        let query = location.search().unwrap_or_default();
+        let _ = location.set_search(&with_profile_query(&query, profile));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let relaunched = std::env::current_exe().and_then(|exe| {
+            std::process::Command::new(exe)
+                .args(relaunch_args(std::env::args().skip(1), profile))
+                .env_remove(PROFILE_ENV)
+                .spawn()
+        });
+        match relaunched {
+            Ok(_) => std::process::exit(0),
+            Err(e) => tracing::error!("Failed to relaunch in profile {:?}: {}", profile, e),
+        }
+    }
+}
+
+/// Gets the prefix of the storage keys of a profile on web and in memory.
+///
+/// # Example
+/// ```
+/// use todo::utils::profile::key_prefix;
+///
+/// assert_eq!(key_prefix("work"), "profiles/work/");
+/// ```
+pub fn key_prefix(profile: &str) -> String {
+    format!("profiles/{}/", profile)
+}
@@ -0,0 +1,201 @@
+//! The weekly summary as a stand-alone HTML page
+//!
+//! The page lists what was completed during the week, what is overdue, what
+//! is due the week after, and how many todos each tag has. It is built with
+//! plain string formatting and inline styles, so it can be pasted into an
+//! email as is. Every piece of todo text goes through [`escape_html`]. Days
+//! are taken in UTC, like due dates.
+
+use crate::models::{Todo, TodoList, WeekStart};
+use crate::utils::dates;
+use chrono::{Duration, NaiveDate};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Escapes text for use in HTML content and attribute values.
+///
+/// # Example
+/// ```
+/// use todo::utils::reports::escape_html;
+///
+/// assert_eq!(escape_html("Fish & <chips>"), "Fish &amp; &lt;chips&gt;");
+/// assert_eq!(escape_html("Café ☕"), "Café ☕");
+/// ```
+pub fn escape_html(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '<', '>', '"', '\'']) {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len() + 16);
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Todos completed and still open under one tag.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct TagCounts {
+    completed: usize,
+    open: usize,
+}
+
+/// Formats a day the way the report shows it, like "Mar 12".
+fn short_date(date: NaiveDate) -> String {
+    date.format("%b %-d").to_string()
+}
+
+/// Writes a titled section listing todos, with a line for when there are none.
+fn write_section(html: &mut String, title: &str, todos: &[(&Todo, NaiveDate)], empty: &str) {
+    let _ = write!(
+        html,
+        r#"<h2 style="font-size:16px;margin:20px 0 8px;">{} ({})</h2>"#,
+        escape_html(title),
+        todos.len()
+    );
+    if todos.is_empty() {
+        let _ = write!(
+            html,
+            r#"<p style="color:#6b7280;margin:0;">{}</p>"#,
+            escape_html(empty)
+        );
+        return;
+    }
+    html.push_str(r#"<ul style="margin:0;padding-left:20px;">"#);
+    for (todo, day) in todos {
+        let _ = write!(
+            html,
+            r#"<li style="margin:2px 0;">{} <span style="color:#6b7280;">· {}</span>"#,
+            escape_html(&todo.text),
+            short_date(*day)
+        );
+        for tag in &todo.tags {
+            let _ = write!(
+                html,
+                r#" <span style="background:#e0e7ff;color:#3730a3;border-radius:8px;padding:0 6px;font-size:12px;">{}</span>"#,
+                escape_html(tag)
+            );
+        }
+        html.push_str("</li>");
+    }
+    html.push_str("</ul>");
+}
+
+/// Builds the weekly report as a complete HTML page.
+///
+/// # Arguments
+/// * `list` - The todos; the trash is left out
+/// * `week_of` - The day the report is made, which picks the week and
+///   decides what counts as overdue
+/// * `week_start` - The first day of the week
+///
+/// # Returns
+/// The page, with inline styles only
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use todo::models::{TodoList, WeekStart};
+/// use todo::utils::reports::weekly_html;
+///
+/// let mut list = TodoList::new();
+/// list.add("Order <tiles> & grout".to_string());
+/// let html = weekly_html(&list, NaiveDate::from_ymd_opt(2025, 3, 12).unwrap(), WeekStart::Monday);
+/// assert!(html.contains("Weekly report: Mar 10 – Mar 16, 2025"));
+/// ```
+pub fn weekly_html(list: &TodoList, week_of: NaiveDate, week_start: WeekStart) -> String {
+    let (first, last) = dates::week_range(week_of, week_start);
+    let (next_first, next_last) = (first + Duration::days(7), last + Duration::days(7));
+    let todos = list.all();
+
+    let mut completed = Vec::new();
+    let mut overdue = Vec::new();
+    let mut due_next_week = Vec::new();
+    let mut tags: BTreeMap<&str, TagCounts> = BTreeMap::new();
+    for todo in &todos {
+        let completed_on = todo
+            .completed_at
+            .filter(|_| todo.completed)
+            .map(|at| at.date_naive())
+            .filter(|day| (first..=last).contains(day));
+        if let Some(day) = completed_on {
+            completed.push((todo, day));
+        }
+        if !todo.completed
+            && let Some(due) = todo.due_date.map(dates::due_day)
+        {
+            if due < week_of {
+                overdue.push((todo, due));
+            } else if (next_first..=next_last).contains(&due) {
+                due_next_week.push((todo, due));
+            }
+        }
+        for tag in &todo.tags {
+            let counts = tags.entry(tag).or_default();
+            if completed_on.is_some() {
+                counts.completed += 1;
+            } else if !todo.completed {
+                counts.open += 1;
+            }
+        }
+    }
+    completed.sort_by_key(|(_, day)| *day);
+    overdue.sort_by_key(|(_, due)| *due);
+    due_next_week.sort_by_key(|(_, due)| *due);
+    tags.retain(|_, counts| *counts != TagCounts::default());
+
+    let title = format!(
+        "Weekly report: {} – {}",
+        short_date(first),
+        last.format("%b %-d, %Y")
+    );
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        r#"<!DOCTYPE html><html><head><meta charset="utf-8"><title>{title}</title></head><body style="font-family:-apple-system,'Segoe UI',Helvetica,Arial,sans-serif;color:#111827;max-width:640px;margin:0 auto;padding:16px;"><h1 style="font-size:20px;margin:0 0 4px;">{title}</h1>"#,
+        title = escape_html(&title)
+    );
+
+    write_section(
+        &mut html,
+        "Completed this week",
+        &completed,
+        "Nothing completed yet.",
+    );
+    write_section(&mut html, "Overdue", &overdue, "Nothing overdue.");
+    write_section(
+        &mut html,
+        "Due next week",
+        &due_next_week,
+        "Nothing due next week.",
+    );
+
+    html.push_str(r#"<h2 style="font-size:16px;margin:20px 0 8px;">By tag</h2>"#);
+    if tags.is_empty() {
+        html.push_str(r#"<p style="color:#6b7280;margin:0;">No tagged todos.</p>"#);
+    } else {
+        html.push_str(
+            r#"<table style="border-collapse:collapse;font-size:14px;"><tr><th style="text-align:left;padding:4px 12px 4px 0;">Tag</th><th style="text-align:right;padding:4px 12px;">Completed this week</th><th style="text-align:right;padding:4px 0 4px 12px;">Open</th></tr>"#,
+        );
+        for (tag, counts) in &tags {
+            let _ = write!(
+                html,
+                r#"<tr style="border-top:1px solid #e5e7eb;"><td style="padding:4px 12px 4px 0;">{}</td><td style="text-align:right;padding:4px 12px;">{}</td><td style="text-align:right;padding:4px 0 4px 12px;">{}</td></tr>"#,
+                escape_html(tag),
+                counts.completed,
+                counts.open
+            );
+        }
+        html.push_str("</table>");
+    }
+
+    html.push_str("</body></html>");
+    html
+}
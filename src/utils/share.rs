@@ -0,0 +1,370 @@
+//! Shareable text snippets for single todos and snapshot links for lists
+//!
+//! A todo is shared as one line such as
+//! `☐ Prepare slides — due 2025-03-04 #Work #Urgent`. Parsing accepts that
+//! format as well as markdown checkboxes (`- [ ] Prepare slides`), and treats
+//! anything else as the plain text of a new todo.
+//!
+//! A list of todos is shared as a read-only snapshot in the URL fragment,
+//! `#share=<payload>`: the todos as JSON, deflate-compressed and base64url
+//! encoded. The fragment never reaches a server, so no backend is needed.
+//! Only what the snapshot view shows is sent: the text, whether the todo is
+//! done, the due date and the tags. Notes, earlier texts and sync ids stay
+//! behind.
+
+use crate::models::Todo;
+use crate::utils::constants::share::{
+    MAX_SNAPSHOT_BYTES, MAX_SNAPSHOT_JSON_BYTES, SNAPSHOT_COMPRESSION_LEVEL,
+    SNAPSHOT_FORMAT_VERSION, SNAPSHOT_FRAGMENT_PARAM,
+};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Marker for an open todo
+const OPEN_MARKER: &str = "☐";
+/// Marker for a completed todo
+const DONE_MARKER: &str = "☑";
+/// Separator written before the due date
+const DUE_SEPARATOR: &str = " — due ";
+/// Date format of the due date
+const DUE_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// A todo read back from a shared snippet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SharedTodo {
+    /// The todo text
+    pub text: String,
+    /// Whether the todo is completed
+    pub completed: bool,
+    /// Optional due date, at midnight UTC
+    pub due_date: Option<DateTime<Utc>>,
+    /// Tags, in the order they appeared
+    pub tags: Vec<String>,
+}
+
+impl SharedTodo {
+    /// Creates an open todo with only text, used when a snippet is not recognized.
+    fn plain(text: &str) -> Self {
+        Self {
+            text: text.trim().to_string(),
+            completed: false,
+            due_date: None,
+            tags: Vec::new(),
+        }
+    }
+}
+
+/// Formats a todo as a single shareable line.
+///
+/// Whitespace inside tags is replaced with `-` so every tag stays one `#word`.
+///
+/// # Arguments
+/// * `todo` - The todo to format
+///
+/// # Returns
+/// A line like `☐ Prepare slides — due 2025-03-04 #Work #Urgent`
+///
+/// # Example
+/// ```
+/// use todo::models::Todo;
+/// use todo::utils::share::format_todo;
+///
+/// let mut todo = Todo::new(1, "Prepare slides".to_string());
+/// todo.add_tag("Work".to_string());
+/// assert_eq!(format_todo(&todo), "☐ Prepare slides #Work");
+/// ```
+pub fn format_todo(todo: &Todo) -> String {
+    let marker = if todo.completed {
+        DONE_MARKER
+    } else {
+        OPEN_MARKER
+    };
+    let mut line = format!("{} {}", marker, todo.text.trim());
+
+    if let Some(due) = todo.due_date {
+        line.push_str(DUE_SEPARATOR);
+        line.push_str(&due.format(DUE_DATE_FORMAT).to_string());
+    }
+    for tag in &todo.tags {
+        line.push_str(" #");
+        line.push_str(&tag.split_whitespace().collect::<Vec<_>>().join("-"));
+    }
+
+    line
+}
+
+/// Parses a shared snippet back into a todo.
+///
+/// Recognizes the format written by [`format_todo`] and markdown checkboxes
+/// (`- [ ]`, `- [x]`, also with `*`). Only the first non-empty line is read.
+/// Content in any other shape is kept as the plain text of an open todo.
+///
+/// # Arguments
+/// * `snippet` - The text to parse, usually clipboard content
+///
+/// # Returns
+/// The todo described by the snippet, or `None` if it holds no text at all
+pub fn parse_todo(snippet: &str) -> Option<SharedTodo> {
+    let line = snippet
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())?;
+
+    let Some((completed, rest)) = strip_marker(line) else {
+        return Some(SharedTodo::plain(line));
+    };
+
+    // Tags are the trailing `#word` tokens
+    let mut body = rest.trim_end();
+    let mut tags = Vec::new();
+    while let Some((head, last)) = body.rsplit_once(char::is_whitespace) {
+        match last.strip_prefix('#') {
+            Some(tag) if !tag.is_empty() => {
+                tags.push(tag.to_string());
+                body = head.trim_end();
+            }
+            _ => break,
+        }
+    }
+    tags.reverse();
+
+    // The due date comes right before the tags
+    let mut due_date = None;
+    if let Some((head, date)) = body.rsplit_once(DUE_SEPARATOR.trim_start())
+        && let Ok(date) = NaiveDate::parse_from_str(date.trim(), DUE_DATE_FORMAT)
+    {
+        due_date = date.and_hms_opt(0, 0, 0).map(|dt| dt.and_utc());
+        body = head;
+    }
+
+    let text = body.trim();
+    if text.is_empty() {
+        return Some(SharedTodo::plain(line));
+    }
+
+    Some(SharedTodo {
+        text: text.to_string(),
+        completed,
+        due_date,
+        tags,
+    })
+}
+
+/// Strips a completion marker from the start of a line.
+///
+/// # Returns
+/// Whether the marker means completed, and the rest of the line
+fn strip_marker(line: &str) -> Option<(bool, &str)> {
+    if let Some(rest) = line.strip_prefix(OPEN_MARKER) {
+        return Some((false, rest));
+    }
+    if let Some(rest) = line.strip_prefix(DONE_MARKER) {
+        return Some((true, rest));
+    }
+
+    let rest = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))?;
+    if let Some(rest) = rest.strip_prefix("[ ]") {
+        Some((false, rest))
+    } else if let Some(rest) = rest
+        .strip_prefix("[x]")
+        .or_else(|| rest.strip_prefix("[X]"))
+    {
+        Some((true, rest))
+    } else {
+        None
+    }
+}
+
+/// Errors creating or reading a snapshot link.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SnapshotError {
+    /// The encoded snapshot is larger than a link should be
+    TooLarge {
+        /// Size of the encoded snapshot (bytes)
+        size: usize,
+        /// Largest size allowed (bytes)
+        max: usize,
+    },
+    /// The link does not hold a readable snapshot
+    Invalid(String),
+    /// The snapshot was made by a newer version of the app
+    #[allow(dead_code)]
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::TooLarge { size, max } => write!(
+                f,
+                "The snapshot is {} KB, more than the {} KB a link can hold. Narrow the list with a filter or search first.",
+                size.div_ceil(1024),
+                max / 1024
+            ),
+            SnapshotError::Invalid(reason) => write!(f, "The shared link is damaged: {}", reason),
+            SnapshotError::UnsupportedVersion(version) => write!(
+                f,
+                "The shared link was made by a newer version of the app (format {}).",
+                version
+            ),
+        }
+    }
+}
+
+/// Document stored in a snapshot link.
+#[derive(Serialize, Deserialize)]
+struct SnapshotDocument {
+    version: u32,
+    todos: Vec<SnapshotTodo>,
+}
+
+/// The part of a todo a snapshot link shares.
+///
+/// Links made before the slim form held whole todos, whose other fields are
+/// ignored when read.
+#[derive(Serialize, Deserialize)]
+struct SnapshotTodo {
+    text: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    completed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due_date: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+impl SnapshotTodo {
+    fn of(todo: &Todo) -> Self {
+        Self {
+            text: todo.text.clone(),
+            completed: todo.completed,
+            due_date: todo.due_date,
+            tags: todo.tags.clone(),
+        }
+    }
+
+    /// Turns the shared part back into a todo, numbered by its position.
+    fn into_todo(self, id: usize) -> Todo {
+        let mut todo = Todo::new(id, self.text);
+        todo.completed = self.completed;
+        todo.due_date = self.due_date;
+        todo.tags = self.tags;
+        todo
+    }
+}
+
+/// Encodes todos into the payload of a snapshot link.
+///
+/// # Arguments
+/// * `todos` - The todos to share, in display order; only their text,
+///   completion, due date and tags are kept
+///
+/// # Returns
+/// The base64url payload, or [`SnapshotError::TooLarge`] if it exceeds
+/// [`MAX_SNAPSHOT_BYTES`]
+pub fn encode_snapshot(todos: &[Todo]) -> Result<String, SnapshotError> {
+    let document = SnapshotDocument {
+        version: SNAPSHOT_FORMAT_VERSION,
+        todos: todos.iter().map(SnapshotTodo::of).collect(),
+    };
+    let json = serde_json::to_vec(&document).map_err(|e| SnapshotError::Invalid(e.to_string()))?;
+    let compressed = miniz_oxide::deflate::compress_to_vec(&json, SNAPSHOT_COMPRESSION_LEVEL);
+    let payload = URL_SAFE_NO_PAD.encode(compressed);
+
+    if payload.len() > MAX_SNAPSHOT_BYTES {
+        return Err(SnapshotError::TooLarge {
+            size: payload.len(),
+            max: MAX_SNAPSHOT_BYTES,
+        });
+    }
+    Ok(payload)
+}
+
+/// Decodes the payload of a snapshot link back into todos, numbered from 1
+/// in display order.
+///
+/// # Example
+/// ```
+/// use todo::models::Todo;
+/// use todo::utils::share::{decode_snapshot, encode_snapshot};
+///
+/// let mut todo = Todo::new(7, "Prepare slides 🎉".to_string());
+/// todo.notes = "Ask Zoë for the numbers".to_string();
+/// let payload = encode_snapshot(&[todo]).unwrap();
+///
+/// let shared = decode_snapshot(&payload).unwrap();
+/// assert_eq!(shared[0].text, "Prepare slides 🎉");
+/// assert_eq!(shared[0].notes, "");
+/// ```
+#[allow(dead_code)]
+pub fn decode_snapshot(payload: &str) -> Result<Vec<Todo>, SnapshotError> {
+    if payload.len() > MAX_SNAPSHOT_BYTES {
+        return Err(SnapshotError::TooLarge {
+            size: payload.len(),
+            max: MAX_SNAPSHOT_BYTES,
+        });
+    }
+    let compressed = URL_SAFE_NO_PAD
+        .decode(payload.trim())
+        .map_err(|e| SnapshotError::Invalid(e.to_string()))?;
+    let json =
+        miniz_oxide::inflate::decompress_to_vec_with_limit(&compressed, MAX_SNAPSHOT_JSON_BYTES)
+            .map_err(|e| SnapshotError::Invalid(format!("{:?}", e.status)))?;
+    let document: SnapshotDocument =
+        serde_json::from_slice(&json).map_err(|e| SnapshotError::Invalid(e.to_string()))?;
+    if document.version > SNAPSHOT_FORMAT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion(document.version));
+    }
+    Ok(document
+        .todos
+        .into_iter()
+        .zip(1..)
+        .map(|(todo, id)| todo.into_todo(id))
+        .collect())
+}
+
+/// Builds the URL fragment sharing the todos, like `#share=…`.
+pub fn snapshot_fragment(todos: &[Todo]) -> Result<String, SnapshotError> {
+    encode_snapshot(todos).map(|payload| format!("#{}={}", SNAPSHOT_FRAGMENT_PARAM, payload))
+}
+
+/// Finds the snapshot payload in a URL fragment.
+///
+/// # Arguments
+/// * `fragment` - The fragment, with or without the leading `#`
+///
+/// # Returns
+/// The payload, or `None` if the fragment does not share a snapshot
+#[allow(dead_code)]
+pub fn fragment_payload(fragment: &str) -> Option<&str> {
+    fragment
+        .trim_start_matches('#')
+        .split('&')
+        .find_map(|pair| {
+            pair.strip_prefix(SNAPSHOT_FRAGMENT_PARAM)?
+                .strip_prefix('=')
+        })
+        .filter(|payload| !payload.is_empty())
+}
+
+/// Reads a shared snapshot from the page URL.
+///
+/// # Returns
+/// `None` when the page was not opened from a snapshot link, otherwise the
+/// shared todos or why they cannot be shown
+#[cfg(target_arch = "wasm32")]
+pub fn snapshot_from_location() -> Option<Result<Vec<Todo>, SnapshotError>> {
+    let hash = web_sys::window()?.location().hash().ok()?;
+    fragment_payload(&hash).map(decode_snapshot)
+}
+
+/// Reads a shared snapshot from the page URL; native builds have none.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn snapshot_from_location() -> Option<Result<Vec<Todo>, SnapshotError>> {
+    None
+}
@@ -0,0 +1,124 @@
+//! Short sound effects, played through the webview
+//!
+//! Both the web and desktop builds render into a webview, so sounds are
+//! played with the browser Web Audio API through `document::eval`, falling
+//! back to an `<audio>` element. When neither can be set up (headless runs,
+//! CI, a blocked autoplay policy) playback turns itself off and every later
+//! call is a no-op.
+
+use crate::utils::constants::sound::COMPLETION_SOUND_DEBOUNCE_MS;
+use chrono::{DateTime, Duration, Utc};
+
+/// Lets a sound through at most once per interval.
+///
+/// Completing many todos in one go would otherwise play a burst of ticks;
+/// only the first one within the interval is played.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SoundDebouncer {
+    interval: Duration,
+    last_played: Option<DateTime<Utc>>,
+}
+
+impl SoundDebouncer {
+    /// Creates a debouncer letting one sound through per `interval`.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_played: None,
+        }
+    }
+
+    /// Checks if a sound may be played now, recording it if so.
+    ///
+    /// # Arguments
+    /// * `now` - The current time
+    ///
+    /// # Returns
+    /// `true` if no sound was let through within the interval before `now`
+    pub fn try_play(&mut self, now: DateTime<Utc>) -> bool {
+        if self
+            .last_played
+            .is_some_and(|last| now < last + self.interval)
+        {
+            return false;
+        }
+        self.last_played = Some(now);
+        true
+    }
+}
+
+impl Default for SoundDebouncer {
+    fn default() -> Self {
+        Self::new(Duration::milliseconds(COMPLETION_SOUND_DEBOUNCE_MS))
+    }
+}
+
+#[cfg(feature = "ui")]
+pub use playback::play_completion_sound;
+
+#[cfg(feature = "ui")]
+mod playback {
+    use crate::utils::constants::sound::COMPLETION_SOUND_VOLUME;
+    use dioxus::prelude::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tracing::debug;
+
+    const TICK_SOUND: Asset = asset!("/assets/tick.wav");
+
+    /// Cleared once audio fails to start, so later sounds are skipped
+    static AUDIO_AVAILABLE: AtomicBool = AtomicBool::new(true);
+
+    /// Plays the completion "tick", doing nothing if audio is unavailable.
+    pub fn play_completion_sound() {
+        if !AUDIO_AVAILABLE.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let eval = document::eval(
+            r#"
+            const [url, volume] = await dioxus.recv();
+            try {
+                const Context = window.AudioContext || window.webkitAudioContext;
+                if (Context) {
+                    const sound = (window.__todoTick ??= { context: new Context() });
+                    sound.buffer ??= await fetch(url)
+                        .then((response) => response.arrayBuffer())
+                        .then((data) => sound.context.decodeAudioData(data));
+                    await sound.context.resume();
+                    const source = sound.context.createBufferSource();
+                    const gain = sound.context.createGain();
+                    gain.gain.value = volume;
+                    source.buffer = sound.buffer;
+                    source.connect(gain).connect(sound.context.destination);
+                    source.start();
+                } else {
+                    const audio = new Audio(url);
+                    audio.volume = volume;
+                    await audio.play();
+                }
+                return true;
+            } catch (e) {
+                return false;
+            }
+            "#,
+        );
+        if eval
+            .send((TICK_SOUND.to_string(), COMPLETION_SOUND_VOLUME))
+            .is_err()
+        {
+            disable();
+            return;
+        }
+        spawn(async move {
+            if !eval.join::<bool>().await.unwrap_or(false) {
+                disable();
+            }
+        });
+    }
+
+    fn disable() {
+        if AUDIO_AVAILABLE.swap(false, Ordering::Relaxed) {
+            debug!("Audio is unavailable, completion sounds are turned off");
+        }
+    }
+}
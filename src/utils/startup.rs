@@ -0,0 +1,103 @@
+//! Time from launch to a usable app
+//!
+//! `main` marks the launch, and the app records two milestones: when its
+//! shell first renders, and when the stored todos have loaded so the list
+//! responds. Each is logged once in a `startup` span with the milliseconds
+//! since launch, so runs before and after a change can be compared in the
+//! debug log. Web builds have no monotonic clock here and log nothing.
+
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tracing::{info, info_span};
+
+/// Points on the way to a usable app, in the order they are reached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Milestone {
+    /// The header, form and an empty or loading list are on screen
+    Shell,
+    /// The stored todos are shown and can be edited
+    Interactive,
+}
+
+impl Milestone {
+    /// Gets the name logged for the milestone.
+    pub fn name(self) -> &'static str {
+        match self {
+            Milestone::Shell => "shell",
+            Milestone::Interactive => "interactive",
+        }
+    }
+}
+
+/// Milestones reached so far, each with the time since launch.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StartupTimes {
+    reached: Vec<(Milestone, Duration)>,
+}
+
+impl StartupTimes {
+    /// Notes a milestone, keeping only the first time it is reached.
+    ///
+    /// # Returns
+    /// The time since launch, or `None` if the milestone was reached before
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use todo::utils::startup::{Milestone, StartupTimes};
+    ///
+    /// let mut times = StartupTimes::default();
+    /// let shell = Duration::from_millis(40);
+    /// assert_eq!(times.reach(Milestone::Shell, shell), Some(shell));
+    /// // A later render of the shell is not a new milestone
+    /// assert_eq!(times.reach(Milestone::Shell, Duration::from_millis(90)), None);
+    /// assert_eq!(times.get(Milestone::Shell), Some(shell));
+    /// ```
+    pub fn reach(&mut self, milestone: Milestone, since_launch: Duration) -> Option<Duration> {
+        if self.get(milestone).is_some() {
+            return None;
+        }
+        self.reached.push((milestone, since_launch));
+        Some(since_launch)
+    }
+
+    /// Gets the time since launch at which a milestone was reached.
+    pub fn get(&self, milestone: Milestone) -> Option<Duration> {
+        self.reached
+            .iter()
+            .find_map(|(reached, at)| (*reached == milestone).then_some(*at))
+    }
+}
+
+static LAUNCHED_AT: OnceLock<Instant> = OnceLock::new();
+static TIMES: Mutex<StartupTimes> = Mutex::new(StartupTimes {
+    reached: Vec::new(),
+});
+
+/// Marks the launch of the app; the first call counts.
+pub fn mark_launch() {
+    if cfg!(not(target_arch = "wasm32")) {
+        LAUNCHED_AT.get_or_init(Instant::now);
+    }
+}
+
+/// Records a milestone, logging the time since launch the first time only.
+pub fn record(milestone: Milestone) {
+    let Some(launched_at) = LAUNCHED_AT.get() else {
+        return;
+    };
+    let elapsed = launched_at.elapsed();
+    let mut times = TIMES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if times.reach(milestone, elapsed).is_some() {
+        let _span = info_span!("startup").entered();
+        info!(
+            milestone = milestone.name(),
+            elapsed_ms = elapsed.as_millis() as u64,
+            "Reached {}",
+            milestone.name()
+        );
+    }
+}
@@ -1,6 +1,12 @@
-#[cfg(feature = "desktop")]
-use dioxus_logger::tracing::{debug, error, info};
+use crate::models::{Todo, TodoList};
+use crate::utils::constants::storage::{CORRUPT_BACKUP_SUFFIX, STORAGE_BACKEND_ENV};
+use crate::utils::{demo, keys, profile};
 use serde::{Serialize, de::DeserializeOwned};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, OnceLock};
+use tracing::field::Empty;
+use tracing::{Instrument, debug, error, info, info_span, warn};
 
 /// Error types for storage operations.
 #[derive(Debug)]
@@ -21,15 +27,175 @@ pub enum StorageError {
     /// Database error (SQLite)
     #[cfg(not(target_arch = "wasm32"))]
     DbError(String),
+    /// Saving is paused because the stored data could not be loaded
+    Blocked,
+    /// The background task running the storage I/O panicked or was cancelled
+    TaskFailed(String),
 }
 
-/// Storage trait defining common operations
-pub trait StorageProvider {
-    /// Save data to storage
-    fn save<T: Serialize>(&self, key: &str, data: &T) -> Result<(), StorageError>;
+/// Low-level, object-safe storage operations on raw string values.
+///
+/// Every backend implements this trait so that the active backend can be
+/// chosen at runtime and passed around as `Box<dyn RawStorage>`. Typed access
+/// goes through [`save_with`] and [`load_with`]. Backends must be shareable
+/// across threads so the cached provider can serve background writes.
+#[allow(dead_code)]
+pub trait RawStorage: Send + Sync {
+    /// Stores a raw string value under the given key
+    fn save_str(&self, key: &str, value: &str) -> Result<(), StorageError>;
+
+    /// Loads the raw string value stored under the given key
+    fn load_str(&self, key: &str) -> Result<String, StorageError>;
+
+    /// Removes the value stored under the given key, if any
+    fn delete(&self, key: &str) -> Result<(), StorageError>;
+
+    /// Lists all keys currently present in storage
+    fn list_keys(&self) -> Result<Vec<String>, StorageError>;
+
+    /// Runs the backend's own consistency check, returning any problems found.
+    ///
+    /// Backends without such a check report no problems.
+    fn integrity_check(&self) -> Result<Vec<String>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    /// Gives space taken by deleted values back to the system.
+    ///
+    /// Backends that never hold on to such space do nothing.
+    fn compact(&self) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    /// Runs the backend's housekeeping after todos were deleted for good,
+    /// reporting how much space it gave back.
+    ///
+    /// Backends without housekeeping, such as localStorage, do nothing.
+    fn maintenance(&self) -> Result<MaintenanceReport, StorageError> {
+        Ok(MaintenanceReport::default())
+    }
+}
+
+/// What a [`RawStorage::maintenance`] run did.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MaintenanceReport {
+    /// Bytes of storage given back to the system
+    pub bytes_freed: usize,
+}
+
+/// Storage backends that can be selected at runtime.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum StorageBackend {
+    /// The platform storage: localStorage on web, SQLite on desktop
+    #[default]
+    Native,
+    /// Process-wide in-memory storage, nothing is persisted
+    Memory,
+}
+
+impl StorageBackend {
+    /// Parses a backend name such as `"memory"` or `"sqlite"`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "native" | "sqlite" | "local" | "localstorage" => Some(Self::Native),
+            "memory" => Some(Self::Memory),
+            _ => None,
+        }
+    }
+
+    /// Selects the backend for this session: in-memory storage in demo mode,
+    /// otherwise the backend named by the environment.
+    pub fn detect() -> Self {
+        if demo::is_demo_mode() {
+            Self::Memory
+        } else {
+            Self::from_env()
+        }
+    }
+
+    /// Reads the backend from the `TODO_STORAGE_BACKEND` environment variable,
+    /// falling back to the native backend when unset or unknown.
+    pub fn from_env() -> Self {
+        std::env::var(STORAGE_BACKEND_ENV)
+            .ok()
+            .and_then(|name| Self::parse(&name))
+            .unwrap_or_default()
+    }
+
+    /// Describes where the backend keeps a profile's data, like the path of
+    /// the database on desktop.
+    pub fn location(self, profile: Option<&str>) -> String {
+        match self {
+            Self::Memory => "In memory, nothing is saved".to_string(),
+            #[cfg(target_arch = "wasm32")]
+            Self::Native => "Browser localStorage".to_string(),
+            #[cfg(all(not(target_arch = "wasm32"), feature = "sqlite"))]
+            Self::Native => {
+                let location = format!("SQLite database at {}", database_path(profile).display());
+                match crate::utils::data_dir::active().mode() {
+                    Some(mode) => format!("{} ({})", location, mode),
+                    None => location,
+                }
+            }
+            #[cfg(all(not(target_arch = "wasm32"), not(feature = "sqlite")))]
+            Self::Native => {
+                let _ = profile;
+                "SQLite, not enabled in this build".to_string()
+            }
+        }
+    }
+}
+
+/// In-memory storage backend, used for tests and ephemeral sessions.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStorage {
+    entries: Arc<Mutex<HashMap<String, String>>>,
+}
+
+static SHARED_MEMORY: LazyLock<MemoryStorage> = LazyLock::new(MemoryStorage::new);
+
+impl MemoryStorage {
+    /// Creates a new, empty in-memory store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a handle to the process-wide in-memory store used when the
+    /// memory backend is selected at runtime.
+    pub fn shared() -> Self {
+        SHARED_MEMORY.clone()
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<String, String>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl RawStorage for MemoryStorage {
+    fn save_str(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        self.entries().insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn load_str(&self, key: &str) -> Result<String, StorageError> {
+        self.entries()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| StorageError::NotFound(format!("No data found for key: {}", key)))
+    }
+
+    fn delete(&self, key: &str) -> Result<(), StorageError> {
+        self.entries().remove(key);
+        Ok(())
+    }
 
-    /// Load data from storage
-    fn load<T: DeserializeOwned>(&self, key: &str) -> Result<T, StorageError>;
+    fn list_keys(&self) -> Result<Vec<String>, StorageError> {
+        let mut keys: Vec<String> = self.entries().keys().cloned().collect();
+        keys.sort();
+        Ok(keys)
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -54,31 +220,41 @@ mod web {
         }
     }
 
-    impl StorageProvider for WebStorage {
-        fn save<T: Serialize>(&self, key: &str, data: &T) -> Result<(), StorageError> {
+    impl RawStorage for WebStorage {
+        fn save_str(&self, key: &str, value: &str) -> Result<(), StorageError> {
             let storage = self.local_storage()?;
-            let json = serde_json::to_string(data)
-                .map_err(|e| StorageError::SerializeError(e.to_string()))?;
-
-            storage.set_item(key, &json).map_err(|e| {
+            storage.set_item(key, value).map_err(|e| {
                 StorageError::SetError(format!("Failed to set item for key {}: {:?}", key, e))
             })
         }
 
-        fn load<T: DeserializeOwned>(&self, key: &str) -> Result<T, StorageError> {
+        fn load_str(&self, key: &str) -> Result<String, StorageError> {
             let storage = self.local_storage()?;
-            let json = storage
+            storage
                 .get_item(key)
                 .map_err(|_| StorageError::AccessError)?
-                .ok_or_else(|| StorageError::NotFound(format!("No data found for key: {}", key)))?;
+                .ok_or_else(|| StorageError::NotFound(format!("No data found for key: {}", key)))
+        }
 
-            serde_json::from_str(&json).map_err(|e| {
-                StorageError::DeserializeError(format!(
-                    "Failed to deserialize data for key {}: {}",
-                    key, e
-                ))
+        fn delete(&self, key: &str) -> Result<(), StorageError> {
+            let storage = self.local_storage()?;
+            storage.remove_item(key).map_err(|e| {
+                StorageError::SetError(format!("Failed to remove item for key {}: {:?}", key, e))
             })
         }
+
+        fn list_keys(&self) -> Result<Vec<String>, StorageError> {
+            let storage = self.local_storage()?;
+            let len = storage.length().map_err(|_| StorageError::AccessError)?;
+            let mut keys = Vec::with_capacity(len as usize);
+            for index in 0..len {
+                if let Some(key) = storage.key(index).map_err(|_| StorageError::AccessError)? {
+                    keys.push(key);
+                }
+            }
+            keys.sort();
+            Ok(keys)
+        }
     }
 }
 
@@ -86,23 +262,19 @@ mod web {
 mod desktop {
     use super::*;
 
-    #[cfg(feature = "desktop")]
-    use {
-        rusqlite::{Connection, params},
-        std::path::PathBuf,
-    };
+    #[cfg(feature = "sqlite")]
+    use rusqlite::{Connection, params};
 
     pub struct SqliteStorage {
-        #[cfg(feature = "desktop")]
-        conn: Connection,
+        #[cfg(feature = "sqlite")]
+        conn: Mutex<Connection>,
     }
 
     impl SqliteStorage {
-        #[cfg(feature = "desktop")]
-        pub fn new() -> Result<Self, StorageError> {
-            let app_dir = dirs::data_local_dir()
-                .unwrap_or_else(|| PathBuf::from("."))
-                .join("editor");
+        /// Opens the database of a profile, `None` for the default one.
+        #[cfg(feature = "sqlite")]
+        pub fn new(profile: Option<&str>) -> Result<Self, StorageError> {
+            let app_dir = profile_data_dir(profile);
 
             info!("App directory: {:?}", app_dir);
 
@@ -112,7 +284,7 @@ mod desktop {
                 StorageError::DbError(error_msg)
             })?;
 
-            let db_path = app_dir.join("storage.db");
+            let db_path = database_path(profile);
             let conn = Connection::open(&db_path).map_err(|e| {
                 let error_msg = format!("Failed to open database at {:?}: {}", db_path, e);
                 error!("{}", error_msg);
@@ -133,31 +305,52 @@ mod desktop {
                 StorageError::DbError(error_msg)
             })?;
 
-            Ok(Self { conn })
+            Ok(Self {
+                conn: Mutex::new(conn),
+            })
         }
 
-        #[cfg(not(feature = "desktop"))]
-        pub fn new() -> Result<Self, StorageError> {
+        #[cfg(not(feature = "sqlite"))]
+        pub fn new(_profile: Option<&str>) -> Result<Self, StorageError> {
             Err(StorageError::DbError(
-                "Desktop feature not enabled".to_string(),
+                "SQLite feature not enabled".to_string(),
             ))
         }
     }
 
-    #[cfg(feature = "desktop")]
-    impl StorageProvider for SqliteStorage {
-        fn save<T: Serialize>(&self, key: &str, data: &T) -> Result<(), StorageError> {
-            let json = serde_json::to_string(data).map_err(|e| {
-                StorageError::SerializeError(format!(
-                    "Failed to serialize data for key {}: {}",
-                    key, e
-                ))
-            })?;
-
+    #[cfg(feature = "sqlite")]
+    impl SqliteStorage {
+        /// Locks the connection, recovering it if a previous holder panicked.
+        fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
             self.conn
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+        }
+
+        /// Gets the size of the database in bytes, free pages included.
+        fn database_bytes(&self) -> Result<usize, StorageError> {
+            let pragma = |name: &str| {
+                self.conn()
+                    .query_row(&format!("PRAGMA {}", name), [], |row| {
+                        row.get::<_, usize>(0)
+                    })
+                    .map_err(|e| {
+                        let error_msg = format!("Failed to read the database size: {}", e);
+                        error!("{}", error_msg);
+                        StorageError::DbError(error_msg)
+                    })
+            };
+            Ok(pragma("page_count")? * pragma("page_size")?)
+        }
+    }
+
+    #[cfg(feature = "sqlite")]
+    impl RawStorage for SqliteStorage {
+        fn save_str(&self, key: &str, value: &str) -> Result<(), StorageError> {
+            self.conn()
                 .execute(
                     "INSERT OR REPLACE INTO kv_store (key, value) VALUES (?1, ?2)",
-                    params![key, json],
+                    params![key, value],
                 )
                 .map_err(|e| {
                     let error_msg = format!("Failed to save data for key {}: {}", key, e);
@@ -169,9 +362,9 @@ mod desktop {
             Ok(())
         }
 
-        fn load<T: DeserializeOwned>(&self, key: &str) -> Result<T, StorageError> {
-            let mut stmt = self
-                .conn
+        fn load_str(&self, key: &str) -> Result<String, StorageError> {
+            let conn = self.conn();
+            let mut stmt = conn
                 .prepare("SELECT value FROM kv_store WHERE key = ?1")
                 .map_err(|e| {
                     let error_msg = format!("Failed to prepare query for key {}: {}", key, e);
@@ -179,38 +372,115 @@ mod desktop {
                     StorageError::DbError(error_msg)
                 })?;
 
-            let json: String = stmt
-                .query_row(params![key], |row| row.get(0))
+            stmt.query_row(params![key], |row| row.get(0)).map_err(|e| {
+                if let rusqlite::Error::QueryReturnedNoRows = e {
+                    debug!("No data found for key: {}", key);
+                    StorageError::NotFound(format!("No data found for key: {}", key))
+                } else {
+                    let error_msg = format!("Failed to query data for key {}: {}", key, e);
+                    error!("{}", error_msg);
+                    StorageError::DbError(error_msg)
+                }
+            })
+        }
+
+        fn delete(&self, key: &str) -> Result<(), StorageError> {
+            self.conn()
+                .execute("DELETE FROM kv_store WHERE key = ?1", params![key])
                 .map_err(|e| {
-                    if let rusqlite::Error::QueryReturnedNoRows = e {
-                        debug!("No data found for key: {}", key);
-                        StorageError::NotFound(format!("No data found for key: {}", key))
-                    } else {
-                        let error_msg = format!("Failed to query data for key {}: {}", key, e);
-                        error!("{}", error_msg);
-                        StorageError::DbError(error_msg)
-                    }
+                    let error_msg = format!("Failed to delete data for key {}: {}", key, e);
+                    error!("{}", error_msg);
+                    StorageError::DbError(error_msg)
+                })?;
+            Ok(())
+        }
+
+        fn list_keys(&self) -> Result<Vec<String>, StorageError> {
+            let conn = self.conn();
+            let mut stmt = conn
+                .prepare("SELECT key FROM kv_store ORDER BY key")
+                .map_err(|e| {
+                    let error_msg = format!("Failed to prepare key listing: {}", e);
+                    error!("{}", error_msg);
+                    StorageError::DbError(error_msg)
                 })?;
 
-            serde_json::from_str(&json).map_err(|e| {
-                let error_msg = format!("Failed to deserialize data for key {}: {}", key, e);
+            let keys = stmt
+                .query_map([], |row| row.get(0))
+                .and_then(|rows| rows.collect::<Result<Vec<String>, _>>())
+                .map_err(|e| {
+                    let error_msg = format!("Failed to list keys: {}", e);
+                    error!("{}", error_msg);
+                    StorageError::DbError(error_msg)
+                })?;
+            Ok(keys)
+        }
+
+        fn integrity_check(&self) -> Result<Vec<String>, StorageError> {
+            let conn = self.conn();
+            let mut stmt = conn.prepare("PRAGMA integrity_check").map_err(|e| {
+                let error_msg = format!("Failed to prepare integrity check: {}", e);
                 error!("{}", error_msg);
-                StorageError::DeserializeError(error_msg)
-            })
+                StorageError::DbError(error_msg)
+            })?;
+
+            let messages = stmt
+                .query_map([], |row| row.get::<_, String>(0))
+                .and_then(|rows| rows.collect::<Result<Vec<String>, _>>())
+                .map_err(|e| {
+                    let error_msg = format!("Failed to run integrity check: {}", e);
+                    error!("{}", error_msg);
+                    StorageError::DbError(error_msg)
+                })?;
+
+            Ok(messages
+                .into_iter()
+                .filter(|message| message != "ok")
+                .collect())
+        }
+
+        fn compact(&self) -> Result<(), StorageError> {
+            self.conn().execute("VACUUM", []).map_err(|e| {
+                let error_msg = format!("Failed to compact database: {}", e);
+                error!("{}", error_msg);
+                StorageError::DbError(error_msg)
+            })?;
+            info!("Database compacted");
+            Ok(())
+        }
+
+        fn maintenance(&self) -> Result<MaintenanceReport, StorageError> {
+            let before = self.database_bytes()?;
+            self.compact()?;
+            let bytes_freed = before.saturating_sub(self.database_bytes()?);
+            info!("Database maintenance freed {} bytes", bytes_freed);
+            Ok(MaintenanceReport { bytes_freed })
         }
     }
 
-    #[cfg(not(feature = "desktop"))]
-    impl StorageProvider for SqliteStorage {
-        fn save<T: Serialize>(&self, _key: &str, _data: &T) -> Result<(), StorageError> {
+    #[cfg(not(feature = "sqlite"))]
+    impl RawStorage for SqliteStorage {
+        fn save_str(&self, _key: &str, _value: &str) -> Result<(), StorageError> {
+            Err(StorageError::DbError(
+                "SQLite feature not enabled".to_string(),
+            ))
+        }
+
+        fn load_str(&self, _key: &str) -> Result<String, StorageError> {
+            Err(StorageError::DbError(
+                "SQLite feature not enabled".to_string(),
+            ))
+        }
+
+        fn delete(&self, _key: &str) -> Result<(), StorageError> {
             Err(StorageError::DbError(
-                "Desktop feature not enabled".to_string(),
+                "SQLite feature not enabled".to_string(),
             ))
         }
 
-        fn load<T: DeserializeOwned>(&self, _key: &str) -> Result<T, StorageError> {
+        fn list_keys(&self) -> Result<Vec<String>, StorageError> {
             Err(StorageError::DbError(
-                "Desktop feature not enabled".to_string(),
+                "SQLite feature not enabled".to_string(),
             ))
         }
     }
@@ -219,16 +489,242 @@ mod desktop {
 #[cfg(target_arch = "wasm32")]
 pub use web::WebStorage as Storage;
 
-/// Get the platform-specific storage provider
-pub fn get_storage() -> Result<impl StorageProvider, StorageError> {
-    #[cfg(target_arch = "wasm32")]
-    {
-        Ok(web::WebStorage::new())
+/// Gets the folder the app keeps its database and log files in: the one
+/// picked at launch, see [`crate::utils::data_dir::active`], or else the
+/// user's local data folder.
+#[cfg(feature = "sqlite")]
+pub fn app_data_dir() -> std::path::PathBuf {
+    match crate::utils::data_dir::active().path() {
+        Some(path) => path.to_path_buf(),
+        None => dirs::data_local_dir()
+            .unwrap_or_else(|| std::path::PathBuf::from("."))
+            .join("editor"),
     }
+}
 
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        desktop::SqliteStorage::new()
+/// Gets the folder holding the database of a profile: the data folder itself
+/// for the default profile, a subfolder of it for the others.
+#[cfg(feature = "sqlite")]
+pub fn profile_data_dir(profile: Option<&str>) -> std::path::PathBuf {
+    match profile {
+        Some(profile) => app_data_dir().join("profiles").join(profile),
+        None => app_data_dir(),
+    }
+}
+
+/// Gets the path of a profile's database file.
+#[cfg(feature = "sqlite")]
+pub fn database_path(profile: Option<&str>) -> std::path::PathBuf {
+    profile_data_dir(profile).join("storage.db")
+}
+
+/// Opens the folder holding the active profile's database in the system's
+/// file manager.
+#[cfg(feature = "sqlite")]
+pub fn open_data_folder() -> std::io::Result<()> {
+    let dir = profile_data_dir(profile::active());
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    std::process::Command::new(opener)
+        .arg(dir)
+        .spawn()
+        .map(|_| ())
+}
+
+/// Storage of a profile inside a backend shared by all profiles.
+///
+/// Keys are stored with the profile's prefix, and keys without it are out of
+/// sight, so profiles never see each other's values.
+pub struct PrefixedStorage {
+    inner: Box<dyn RawStorage>,
+    prefix: String,
+}
+
+impl PrefixedStorage {
+    /// Wraps a backend, storing every key under `prefix`.
+    pub fn new(inner: Box<dyn RawStorage>, prefix: String) -> Self {
+        Self { inner, prefix }
+    }
+
+    fn full_key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+}
+
+impl RawStorage for PrefixedStorage {
+    fn save_str(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        self.inner.save_str(&self.full_key(key), value)
+    }
+
+    fn load_str(&self, key: &str) -> Result<String, StorageError> {
+        self.inner.load_str(&self.full_key(key))
+    }
+
+    fn delete(&self, key: &str) -> Result<(), StorageError> {
+        self.inner.delete(&self.full_key(key))
+    }
+
+    fn list_keys(&self) -> Result<Vec<String>, StorageError> {
+        Ok(self
+            .inner
+            .list_keys()?
+            .into_iter()
+            .filter_map(|key| key.strip_prefix(&self.prefix).map(String::from))
+            .collect())
+    }
+
+    fn integrity_check(&self) -> Result<Vec<String>, StorageError> {
+        self.inner.integrity_check()
+    }
+
+    fn compact(&self) -> Result<(), StorageError> {
+        self.inner.compact()
+    }
+
+    fn maintenance(&self) -> Result<MaintenanceReport, StorageError> {
+        self.inner.maintenance()
+    }
+}
+
+/// Opens the given storage backend with the default profile's data.
+#[allow(dead_code)]
+pub fn open_storage(backend: StorageBackend) -> Result<Box<dyn RawStorage>, StorageError> {
+    open_profile_storage(backend, None)
+}
+
+/// Opens the given storage backend with a profile's data.
+///
+/// # Arguments
+/// * `backend` - The backend to open
+/// * `profile` - The profile, `None` for the default one
+pub fn open_profile_storage(
+    backend: StorageBackend,
+    profile: Option<&str>,
+) -> Result<Box<dyn RawStorage>, StorageError> {
+    let storage: Box<dyn RawStorage> = match backend {
+        StorageBackend::Memory => Box::new(MemoryStorage::shared()),
+        StorageBackend::Native => {
+            // Each profile has a database of its own on desktop
+            #[cfg(not(target_arch = "wasm32"))]
+            return Ok(Box::new(desktop::SqliteStorage::new(profile)?));
+
+            #[cfg(target_arch = "wasm32")]
+            Box::new(web::WebStorage::new())
+        }
+    };
+    Ok(match profile {
+        Some(profile) => Box::new(PrefixedStorage::new(storage, profile::key_prefix(profile))),
+        None => storage,
+    })
+}
+
+static STORAGE: OnceLock<Arc<dyn RawStorage>> = OnceLock::new();
+
+/// Get the storage provider selected for this session.
+///
+/// Demo mode always gets the in-memory provider, so nothing is written to
+/// localStorage or the database.
+///
+/// The provider is opened once and cached for the lifetime of the process, so
+/// the SQLite connection and table setup are not repeated on every call. A
+/// failed open is not cached and will be retried on the next call.
+///
+/// The provider holds the data of the profile picked at launch, see
+/// [`profile::active`].
+///
+/// Values stored under the keys of earlier versions are moved to their
+/// namespaced keys when the provider is first opened.
+pub fn get_storage() -> Result<Arc<dyn RawStorage>, StorageError> {
+    if let Some(storage) = STORAGE.get() {
+        return Ok(storage.clone());
+    }
+
+    let storage: Arc<dyn RawStorage> = Arc::from(open_profile_storage(
+        StorageBackend::detect(),
+        profile::active(),
+    )?);
+    if let Err(e) = keys::migrate(storage.as_ref()) {
+        warn!("Failed to migrate storage keys: {:?}", e);
+    }
+    Ok(STORAGE.get_or_init(|| storage).clone())
+}
+
+/// Serializes data and stores it in the given backend.
+///
+/// # Arguments
+/// * `storage` - The backend to write to
+/// * `key` - The key under which to store the data
+/// * `data` - The data to store, must implement Serialize
+pub fn save_with<T: Serialize>(
+    storage: &dyn RawStorage,
+    key: &str,
+    data: &T,
+) -> Result<(), StorageError> {
+    let span = info_span!("save", key, bytes = Empty, result = Empty);
+    let _entered = span.enter();
+    let json = serde_json::to_string(data).map_err(|e| {
+        StorageError::SerializeError(format!("Failed to serialize data for key {}: {}", key, e))
+    })?;
+    span.record("bytes", json.len());
+    let result = storage.save_str(key, &json);
+    span.record("result", outcome(&result));
+    result
+}
+
+/// Loads and deserializes data from the given backend.
+///
+/// # Arguments
+/// * `storage` - The backend to read from
+/// * `key` - The key under which the data is stored
+pub fn load_with<T: DeserializeOwned>(
+    storage: &dyn RawStorage,
+    key: &str,
+) -> Result<T, StorageError> {
+    let span = info_span!("load", key, bytes = Empty, result = Empty);
+    let _entered = span.enter();
+    let result = storage.load_str(key).and_then(|json| {
+        span.record("bytes", json.len());
+        deserialize(key, &json)
+    });
+    span.record("result", outcome(&result));
+    result
+}
+
+/// Deserializes stored JSON, logging where it is damaged but not what it holds.
+fn deserialize<T: DeserializeOwned>(key: &str, json: &str) -> Result<T, StorageError> {
+    serde_json::from_str(json).map_err(|e| {
+        error!(
+            "Failed to deserialize data for key {}: {}",
+            key,
+            describe_json_error(&e)
+        );
+        StorageError::DeserializeError(format!("Failed to deserialize data for key {}: {}", key, e))
+    })
+}
+
+/// Describes a JSON error by its kind and position only, since its message
+/// can quote stored todo text.
+fn describe_json_error(error: &serde_json::Error) -> String {
+    format!(
+        "{:?} error at line {} column {}",
+        error.classify(),
+        error.line(),
+        error.column()
+    )
+}
+
+/// Sums up the result of a storage operation for the debug log.
+fn outcome<T>(result: &Result<T, StorageError>) -> &'static str {
+    match result {
+        Ok(_) => "ok",
+        Err(StorageError::NotFound(_)) => "not_found",
+        Err(StorageError::Blocked) => "blocked",
+        Err(_) => "error",
     }
 }
 
@@ -245,23 +741,17 @@ pub fn get_storage() -> Result<impl StorageProvider, StorageError> {
 /// # Example
 /// ```
 /// # use todo::models::TodoList;
-/// # use todo::utils::constants::storage::TODO_STORAGE_KEY;
+/// # use todo::utils::keys;
 /// # use todo::utils::save;
 /// let todo_list = TodoList::new();
-/// let result = save(TODO_STORAGE_KEY, &todo_list);
+/// let result = save(keys::TODOS, &todo_list);
 /// ```
 pub fn save<T: Serialize>(key: &str, data: &T) -> Result<(), StorageError> {
-    let storage = get_storage().map_err(|e| {
-        #[cfg(feature = "desktop")]
-        error!("Failed to get storage provider: {:?}", e);
-        e
-    })?;
+    let storage =
+        get_storage().inspect_err(|e| error!("Failed to get storage provider: {:?}", e))?;
 
-    storage.save(key, data).map_err(|e| {
-        #[cfg(feature = "desktop")]
-        error!("Failed to save data for key {}: {:?}", key, e);
-        e
-    })
+    save_with(storage.as_ref(), key, data)
+        .inspect_err(|e| error!("Failed to save data for key {}: {:?}", key, e))
 }
 
 /// Loads data from storage.
@@ -276,29 +766,465 @@ pub fn save<T: Serialize>(key: &str, data: &T) -> Result<(), StorageError> {
 /// # Example
 /// ```
 /// # use todo::models::TodoList;
-/// # use todo::utils::constants::storage::TODO_STORAGE_KEY;
+/// # use todo::utils::keys;
 /// # use todo::utils::load;
-/// let result: Result<TodoList, _> = load(TODO_STORAGE_KEY);
+/// let result: Result<TodoList, _> = load(keys::TODOS);
 /// match result {
 ///     Ok(todo_list) => println!("Loaded {} todos", todo_list.total_count()),
 ///     Err(e) => println!("Error loading todos: {:?}", e),
 /// }
 /// ```
 pub fn load<T: DeserializeOwned>(key: &str) -> Result<T, StorageError> {
-    let storage = get_storage().map_err(|e| {
-        #[cfg(feature = "desktop")]
-        error!("Failed to get storage provider: {:?}", e);
-        e
+    let storage =
+        get_storage().inspect_err(|e| error!("Failed to get storage provider: {:?}", e))?;
+
+    load_with(storage.as_ref(), key).inspect_err(|e| match e {
+        StorageError::NotFound(_) => debug!("No data found for key: {}", key),
+        // Already logged without the stored text it may quote
+        StorageError::DeserializeError(_) => {}
+        _ => error!("Failed to load data for key {}: {:?}", key, e),
+    })
+}
+
+/// What loading stored data at startup found.
+#[derive(Debug, PartialEq)]
+pub enum InitialLoad<T> {
+    /// The stored data was read
+    Loaded(T),
+    /// Nothing is stored yet, as on a fresh install
+    Empty,
+    /// Data may be stored but could not be read; saving over it would lose it
+    Failed(LoadFailure),
+}
+
+/// Why stored data could not be read, with what is left of it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadFailure {
+    /// Description of the error
+    pub error: String,
+    /// The stored text, when it could be read but not understood
+    pub raw: Option<String>,
+}
+
+impl<T: DeserializeOwned> InitialLoad<T> {
+    /// Sorts the result of reading a key into the three startup cases.
+    fn from_raw(key: &str, raw: Result<String, StorageError>) -> Self {
+        match raw {
+            Ok(json) => match serde_json::from_str(&json) {
+                Ok(data) => InitialLoad::Loaded(data),
+                Err(e) => {
+                    error!(
+                        "Failed to deserialize data for key {}: {}",
+                        key,
+                        describe_json_error(&e)
+                    );
+                    InitialLoad::Failed(LoadFailure {
+                        error: format!("The stored data is damaged: {}", e),
+                        raw: Some(json),
+                    })
+                }
+            },
+            Err(StorageError::NotFound(_)) => InitialLoad::Empty,
+            Err(e) => {
+                error!("Failed to load data for key {}: {:?}", key, e);
+                InitialLoad::Failed(LoadFailure {
+                    error: format!("The storage could not be read: {:?}", e),
+                    raw: None,
+                })
+            }
+        }
+    }
+}
+
+/// Loads data from the given backend at startup, telling a fresh install
+/// apart from data that cannot be read.
+///
+/// # Arguments
+/// * `storage` - The backend to read from
+/// * `key` - The key under which the data is stored
+#[allow(dead_code)]
+pub fn initial_load_with<T: DeserializeOwned>(
+    storage: &dyn RawStorage,
+    key: &str,
+) -> InitialLoad<T> {
+    InitialLoad::from_raw(key, storage.load_str(key))
+}
+
+/// Sequence number handed out to every async save, used to drop stale writes.
+static SAVE_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Highest sequence number written so far for each key.
+static LAST_WRITTEN: LazyLock<Mutex<HashMap<String, u64>>> = LazyLock::new(Default::default);
+
+/// Runs blocking storage I/O off the UI thread when a tokio runtime is available.
+///
+/// # Returns
+/// * `Ok(R)` with what the task returned
+/// * `Err(StorageError::TaskFailed)` if the task panicked or was cancelled
+async fn run_blocking<R, F>(task: F) -> Result<R, StorageError>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    #[cfg(feature = "desktop")]
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        return handle.spawn_blocking(task).await.map_err(|e| {
+            error!("Storage task failed: {}", e);
+            StorageError::TaskFailed(e.to_string())
+        });
+    }
+
+    Ok(task())
+}
+
+/// Saves data to storage without blocking the UI thread.
+///
+/// The data is serialized on the calling thread, and the write itself runs on
+/// a background thread on desktop. When several saves for the same key are in
+/// flight, only the most recently issued one is allowed to land so an older
+/// snapshot can never overwrite a newer one.
+///
+/// # Arguments
+/// * `key` - The key under which to store the data
+/// * `data` - The data to store, must implement Serialize
+///
+/// # Returns
+/// * `Ok(())` if the data was stored (or superseded by a newer save)
+/// * `Err(StorageError)` if there was an error storing the data
+pub async fn save_async<T: Serialize>(key: &str, data: &T) -> Result<(), StorageError> {
+    let span = info_span!("save_async", key, bytes = Empty, result = Empty);
+    let json = serde_json::to_string(data).map_err(|e| {
+        StorageError::SerializeError(format!("Failed to serialize data for key {}: {}", key, e))
     })?;
+    span.record("bytes", json.len());
+    let storage =
+        get_storage().inspect_err(|e| error!("Failed to get storage provider: {:?}", e))?;
+    let sequence = SAVE_SEQUENCE.fetch_add(1, Ordering::SeqCst);
+    let key = key.to_string();
 
-    storage.load(key).map_err(|e| {
-        if let StorageError::NotFound(_) = &e {
-            #[cfg(feature = "desktop")]
-            debug!("No data found for key: {}", key);
-        } else {
-            #[cfg(feature = "desktop")]
-            error!("Failed to load data for key {}: {:?}", key, e);
+    let result = run_blocking(move || {
+        let mut last_written = LAST_WRITTEN
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if last_written.get(&key).is_some_and(|&last| last > sequence) {
+            debug!("Skipping stale save for key: {}", key);
+            return Ok(());
         }
-        e
+
+        storage
+            .save_str(&key, &json)
+            .inspect_err(|e| error!("Failed to save data for key {}: {:?}", key, e))?;
+        last_written.insert(key, sequence);
+        Ok(())
     })
+    .instrument(span.clone())
+    .await
+    .and_then(|saved| saved);
+    span.record("result", outcome(&result));
+    result
+}
+
+/// Loads data from storage without blocking the UI thread.
+///
+/// # Arguments
+/// * `key` - The key under which the data is stored
+///
+/// # Returns
+/// * `Ok(T)` containing the loaded data
+/// * `Err(StorageError)` if there was an error loading the data
+#[allow(dead_code)]
+pub async fn load_async<T: DeserializeOwned>(key: &str) -> Result<T, StorageError> {
+    let storage =
+        get_storage().inspect_err(|e| error!("Failed to get storage provider: {:?}", e))?;
+    let span = info_span!("load_async", key, bytes = Empty, result = Empty);
+    let owned_key = key.to_string();
+    let result = run_blocking(move || storage.load_str(&owned_key))
+        .instrument(span.clone())
+        .await
+        .and_then(|loaded| loaded)
+        .inspect_err(|e| {
+            if let StorageError::NotFound(_) = e {
+                debug!("No data found for key: {}", key);
+            } else {
+                error!("Failed to load data for key {}: {:?}", key, e);
+            }
+        })
+        .and_then(|json| {
+            span.record("bytes", json.len());
+            deserialize(key, &json)
+        });
+    span.record("result", outcome(&result));
+    result
+}
+
+/// Loads data from the configured backend at startup without blocking the
+/// UI thread, telling a fresh install apart from data that cannot be read.
+///
+/// Opening the backend, reading and parsing all run as one blocking task on
+/// desktop, so the window paints before a large list is ready.
+pub async fn initial_load_async<T: DeserializeOwned + Send + 'static>(key: &str) -> InitialLoad<T> {
+    let span = info_span!("initial_load", key, bytes = Empty, result = Empty);
+    let task_span = span.clone();
+    let owned_key = key.to_string();
+    let loaded = run_blocking(move || {
+        let storage = match get_storage() {
+            Ok(storage) => storage,
+            Err(e) => {
+                error!("Failed to get storage provider: {:?}", e);
+                return InitialLoad::Failed(LoadFailure {
+                    error: format!("The storage could not be opened: {:?}", e),
+                    raw: None,
+                });
+            }
+        };
+        let raw = storage.load_str(&owned_key);
+        if let Ok(json) = &raw {
+            task_span.record("bytes", json.len());
+        }
+        InitialLoad::from_raw(&owned_key, raw)
+    })
+    .instrument(span.clone())
+    .await
+    .unwrap_or_else(|e| {
+        InitialLoad::Failed(LoadFailure {
+            error: format!("The storage could not be read: {:?}", e),
+            raw: None,
+        })
+    });
+    span.record(
+        "result",
+        match loaded {
+            InitialLoad::Loaded(_) => "ok",
+            InitialLoad::Empty => "not_found",
+            InitialLoad::Failed(_) => "error",
+        },
+    );
+    loaded
+}
+
+/// State of the stored todo data as seen by [`health_check`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum DataHealth {
+    /// Nothing has been stored yet
+    Missing,
+    /// The stored value parses into a todo list
+    Valid { todos: usize },
+    /// The stored value could not be parsed
+    Corrupt { error: String },
+}
+
+/// Structured result of a storage health check.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HealthReport {
+    /// Problems reported by the backend's integrity check, empty when healthy
+    pub integrity_errors: Vec<String>,
+    /// State of the value stored under `keys::TODOS`
+    pub data: DataHealth,
+}
+
+impl HealthReport {
+    /// Returns true if neither the backend nor the stored todos have problems.
+    pub fn is_healthy(&self) -> bool {
+        self.integrity_errors.is_empty() && !matches!(self.data, DataHealth::Corrupt { .. })
+    }
+}
+
+/// Outcome of a [`repair`] run.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RepairOutcome {
+    /// The stored todos were already valid, nothing was changed
+    NothingToRepair,
+    /// Parseable todos were salvaged from the corrupt value and saved back
+    Salvaged { recovered: usize },
+}
+
+/// Checks the platform storage and the stored todos for problems.
+pub fn health_check() -> Result<HealthReport, StorageError> {
+    health_check_with(get_storage()?.as_ref())
+}
+
+/// Checks the given backend and the todos stored in it for problems.
+pub fn health_check_with(storage: &dyn RawStorage) -> Result<HealthReport, StorageError> {
+    let integrity_errors = storage.integrity_check()?;
+    let data = match storage.load_str(keys::TODOS) {
+        Ok(json) => match serde_json::from_str::<TodoList>(&json) {
+            Ok(list) => DataHealth::Valid {
+                todos: list.total_count(),
+            },
+            Err(e) => DataHealth::Corrupt {
+                error: e.to_string(),
+            },
+        },
+        Err(StorageError::NotFound(_)) => DataHealth::Missing,
+        Err(e) => return Err(e),
+    };
+
+    Ok(HealthReport {
+        integrity_errors,
+        data,
+    })
+}
+
+/// Repairs the stored todos in the platform storage.
+///
+/// Returns the outcome together with the repaired list, if one was written.
+pub fn repair() -> Result<(RepairOutcome, Option<TodoList>), StorageError> {
+    repair_with(get_storage()?.as_ref())
+}
+
+/// Repairs the todos stored in the given backend.
+///
+/// A corrupt value is first copied aside under a `.corrupt` key, then every
+/// todo that can still be parsed out of it is saved back as a fresh list.
+pub fn repair_with(
+    storage: &dyn RawStorage,
+) -> Result<(RepairOutcome, Option<TodoList>), StorageError> {
+    let json = match storage.load_str(keys::TODOS) {
+        Ok(json) => json,
+        Err(StorageError::NotFound(_)) => {
+            info!("Repair: no stored todos, nothing to repair");
+            return Ok((RepairOutcome::NothingToRepair, None));
+        }
+        Err(e) => return Err(e),
+    };
+
+    if serde_json::from_str::<TodoList>(&json).is_ok() {
+        info!("Repair: stored todos are valid, nothing to repair");
+        return Ok((RepairOutcome::NothingToRepair, None));
+    }
+
+    let backup_key = format!("{}{}", keys::TODOS, CORRUPT_BACKUP_SUFFIX);
+    storage.save_str(&backup_key, &json)?;
+    warn!("Repair: copied corrupt todo data to key {}", backup_key);
+
+    let salvaged = salvage_todos(&json);
+    let recovered = salvaged.len();
+    let list = TodoList::from_todos(salvaged);
+    save_with(storage, keys::TODOS, &list)?;
+    warn!("Repair: salvaged {} todos from corrupt data", recovered);
+
+    Ok((RepairOutcome::Salvaged { recovered }, Some(list)))
+}
+
+/// Extracts every complete todo object from possibly truncated JSON.
+///
+/// Scans for `{` and tries to parse a todo starting there; anything that is
+/// not a complete todo (the outer wrapper, a half-written tail) is skipped.
+pub fn salvage_todos(json: &str) -> Vec<Todo> {
+    let mut todos = Vec::new();
+    let mut offset = 0;
+
+    while let Some(start) = json[offset..].find('{').map(|pos| offset + pos) {
+        let mut stream = serde_json::Deserializer::from_str(&json[start..]).into_iter::<Todo>();
+        match stream.next() {
+            Some(Ok(todo)) => {
+                offset = start + stream.byte_offset();
+                todos.push(todo);
+            }
+            _ => offset = start + 1,
+        }
+    }
+
+    todos
+}
+
+/// Figures shown in the "Your data" section of the settings.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StorageStats {
+    /// Todos not completed yet
+    pub active: usize,
+    /// Completed todos still in the list
+    pub completed: usize,
+    /// Archived todos
+    pub archived: usize,
+    /// Todos in the trash
+    pub trashed: usize,
+    /// Distinct tags on the todos in the list
+    pub tags: usize,
+    /// Size in bytes of the stored todo document
+    pub document_bytes: usize,
+    /// Size in bytes of every value the app stores, the todos included
+    pub total_bytes: usize,
+    /// Number of stored values
+    pub keys: usize,
+    /// Where the data is kept, see [`StorageBackend::location`]
+    pub location: String,
+}
+
+impl StorageStats {
+    /// Collects the figures from the storage of this session.
+    pub fn collect() -> Result<Self, StorageError> {
+        let location = StorageBackend::detect().location(profile::active());
+        Self::collect_with(get_storage()?.as_ref(), location)
+    }
+
+    /// Collects the figures from the given backend.
+    ///
+    /// Only values under [`keys::NAMESPACE`] count, so values of other apps
+    /// sharing the backend are left out.
+    ///
+    /// # Arguments
+    /// * `storage` - The backend to look at
+    /// * `location` - Where the backend keeps its data, shown as is
+    pub fn collect_with(storage: &dyn RawStorage, location: String) -> Result<Self, StorageError> {
+        let mut stats = Self {
+            location,
+            ..Self::default()
+        };
+
+        for key in namespaced_keys(storage)? {
+            let value = match storage.load_str(&key) {
+                Ok(value) => value,
+                Err(StorageError::NotFound(_)) => continue,
+                Err(e) => return Err(e),
+            };
+            stats.keys += 1;
+            stats.total_bytes += value.len();
+            if key == keys::TODOS {
+                let list: TodoList = serde_json::from_str(&value)
+                    .map_err(|e| StorageError::DeserializeError(e.to_string()))?;
+                stats.active = list.active_count();
+                stats.completed = list.completed_count();
+                stats.archived = list.archived().len();
+                stats.trashed = list.trashed().len();
+                stats.tags = list.all_tags().len();
+                stats.document_bytes = value.len();
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+/// Lists the keys under [`keys::NAMESPACE`] in a backend.
+fn namespaced_keys(storage: &dyn RawStorage) -> Result<Vec<String>, StorageError> {
+    Ok(storage
+        .list_keys()?
+        .into_iter()
+        .filter(|key| key.starts_with(keys::NAMESPACE))
+        .collect())
+}
+
+/// Runs the housekeeping of the storage of this session, see
+/// [`RawStorage::maintenance`].
+pub fn maintenance() -> Result<MaintenanceReport, StorageError> {
+    get_storage()?.maintenance()
+}
+
+/// Deletes every value the app stored in the storage of this session.
+pub fn delete_all() -> Result<usize, StorageError> {
+    delete_all_with(get_storage()?.as_ref())
+}
+
+/// Deletes every value under [`keys::NAMESPACE`] in the given backend.
+///
+/// # Returns
+/// The number of values deleted
+pub fn delete_all_with(storage: &dyn RawStorage) -> Result<usize, StorageError> {
+    let keys = namespaced_keys(storage)?;
+    for key in &keys {
+        storage.delete(key)?;
+    }
+    warn!("Deleted all {} stored values", keys.len());
+    Ok(keys.len())
 }
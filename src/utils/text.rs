@@ -0,0 +1,539 @@
+//! Text helpers for validating and displaying todo content
+
+use crate::utils::constants::todo::{
+    MAX_TODO_TEXT_LENGTH, READING_CJK_CHARS_PER_MINUTE, READING_TIME_MIN_WORDS,
+    READING_WORDS_PER_MINUTE, REPEATED_ADD_WINDOW_MS,
+};
+use chrono::{DateTime, Duration, Utc};
+use std::fmt;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Reasons todo text is rejected.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TextError {
+    /// The text is empty or only whitespace
+    Empty,
+    /// The text has more characters than allowed
+    TooLong {
+        /// Number of characters after trimming
+        length: usize,
+        /// Maximum number of characters
+        max: usize,
+    },
+}
+
+impl fmt::Display for TextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextError::Empty => write!(f, "text is empty"),
+            TextError::TooLong { length, max } => {
+                write!(
+                    f,
+                    "text is {} characters, more than the {} allowed",
+                    length, max
+                )
+            }
+        }
+    }
+}
+
+/// Reasons a todo's link is rejected.
+#[derive(Clone, Debug, PartialEq)]
+pub enum UrlError {
+    /// The link does not start with `http://` or `https://`
+    NotWeb,
+    /// The link has no host, or contains spaces or characters a host cannot have
+    Invalid,
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlError::NotWeb => write!(f, "link must start with http:// or https://"),
+            UrlError::Invalid => write!(f, "link is not a valid web address"),
+        }
+    }
+}
+
+/// How close text is to the length limit, for the character counter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LengthLevel {
+    /// Well within the limit
+    Normal,
+    /// At 90% of the limit or more
+    Warning,
+    /// At the limit or over it
+    Limit,
+}
+
+/// Validates todo text entered in any editor, import or paste.
+///
+/// Surrounding whitespace is trimmed before the checks, so the same text is
+/// accepted or rejected no matter where it was entered.
+///
+/// # Arguments
+/// * `text` - The text as entered
+///
+/// # Returns
+/// The trimmed text, or why it cannot be used
+///
+/// # Example
+/// ```
+/// use todo::utils::text::{TextError, validate_todo_text};
+///
+/// assert_eq!(validate_todo_text("  Buy milk "), Ok("Buy milk".to_string()));
+/// assert_eq!(validate_todo_text("   "), Err(TextError::Empty));
+/// ```
+pub fn validate_todo_text(text: &str) -> Result<String, TextError> {
+    let text = text.trim();
+    let length = char_count(text);
+    if length == 0 {
+        Err(TextError::Empty)
+    } else if length > MAX_TODO_TEXT_LENGTH {
+        Err(TextError::TooLong {
+            length,
+            max: MAX_TODO_TEXT_LENGTH,
+        })
+    } else {
+        Ok(text.to_string())
+    }
+}
+
+/// Validates the link of a todo, entered in an editor or found in an import.
+///
+/// Surrounding whitespace is trimmed. The link must be an http(s) address
+/// with a host, such as `https://example.com/docs`.
+///
+/// # Example
+/// ```
+/// use todo::utils::text::{UrlError, validate_url};
+///
+/// assert_eq!(validate_url(" https://example.com/a?b=1 "), Ok("https://example.com/a?b=1".to_string()));
+/// assert_eq!(validate_url("ftp://example.com"), Err(UrlError::NotWeb));
+/// assert_eq!(validate_url("https://exa mple.com"), Err(UrlError::Invalid));
+/// ```
+pub fn validate_url(url: &str) -> Result<String, UrlError> {
+    let url = url.trim();
+    let lowercase = url.to_ascii_lowercase();
+    let rest = ["http://", "https://"]
+        .iter()
+        .find(|scheme| lowercase.starts_with(*scheme))
+        .map(|scheme| &url[scheme.len()..])
+        .ok_or(UrlError::NotWeb)?;
+
+    // The host sits between the scheme and the path, after any user name
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host_and_port = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = match host_and_port.strip_prefix('[') {
+        Some(ipv6) => ipv6.split_once(']').map_or("", |(address, _)| address),
+        None => host_and_port.split(':').next().unwrap_or_default(),
+    };
+    let host_is_valid = !host.is_empty()
+        && host
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '.' | ':'));
+    if !host_is_valid || url.chars().any(char::is_whitespace) {
+        return Err(UrlError::Invalid);
+    }
+    Ok(url.to_string())
+}
+
+/// Splits a web address off the end of todo text, for moving it into the
+/// todo's link.
+///
+/// # Returns
+/// The text before the address and the address, or `None` if the text does
+/// not end with a valid http(s) address or is nothing but one
+///
+/// # Example
+/// ```
+/// use todo::utils::text::split_trailing_url;
+///
+/// assert_eq!(
+///     split_trailing_url("Read the guide https://example.com/guide"),
+///     Some(("Read the guide", "https://example.com/guide"))
+/// );
+/// assert_eq!(split_trailing_url("https://example.com"), None);
+/// assert_eq!(split_trailing_url("Call https://example.com later"), None);
+/// ```
+pub fn split_trailing_url(text: &str) -> Option<(&str, &str)> {
+    let (head, last) = text.trim_end().rsplit_once(char::is_whitespace)?;
+    let head = head.trim_end();
+    if head.is_empty() || validate_url(last).is_err() {
+        return None;
+    }
+    Some((head, last))
+}
+
+/// Counts the characters of todo text the way the validator does, after trimming.
+///
+/// Characters are what a reader sees as one: an emoji with a skin tone or a
+/// letter with a combining accent counts once.
+///
+/// # Example
+/// ```
+/// use todo::utils::text::char_count;
+///
+/// assert_eq!(char_count(" cafe\u{301} "), 4);
+/// assert_eq!(char_count("👍🏽"), 1);
+/// ```
+pub fn char_count(text: &str) -> usize {
+    text.trim().graphemes(true).count()
+}
+
+/// Live figures about text being edited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TextStats {
+    /// Characters as a reader sees them, after trimming; see [`char_count`]
+    pub characters: usize,
+    /// Words, see [`text_stats`] for how they are counted
+    pub words: usize,
+    /// Estimated minutes to read the text, only for text of at least
+    /// [`READING_TIME_MIN_WORDS`] words
+    pub reading_minutes: Option<usize>,
+}
+
+impl fmt::Display for TextStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+        write!(
+            f,
+            "{} character{} · {} word{}",
+            self.characters,
+            plural(self.characters),
+            self.words,
+            plural(self.words)
+        )?;
+        if let Some(minutes) = self.reading_minutes {
+            write!(f, " · {} min read", minutes)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks if a character is written without spaces between words: Chinese
+/// characters and Japanese kana.
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2FA1F}')
+}
+
+/// Counts the characters and words of text, and estimates reading time.
+///
+/// Words follow the Unicode word boundary rules, so "don't" and "3.5" are
+/// one word each and punctuation and emoji are none. Chinese and Japanese
+/// have no spaces between words and those rules split them into single
+/// characters, so there every Chinese character and hiragana counts as a
+/// word, while a run of katakana counts once. That is close enough for a
+/// live count, as splitting real words takes a dictionary. Reading time
+/// takes [`READING_WORDS_PER_MINUTE`] for spaced words and the faster
+/// [`READING_CJK_CHARS_PER_MINUTE`] for those single characters, rounded up.
+///
+/// # Example
+/// ```
+/// use todo::utils::text::text_stats;
+///
+/// let stats = text_stats("Don't forget the 3.5 mm jack 🎧");
+/// assert_eq!((stats.characters, stats.words), (30, 6));
+/// assert_eq!(stats.reading_minutes, None);
+/// assert_eq!(text_stats("買牛奶").words, 3);
+/// ```
+pub fn text_stats(text: &str) -> TextStats {
+    let (cjk, spaced) = text.unicode_words().fold((0, 0), |(cjk, spaced), word| {
+        if word.chars().all(is_cjk) && word.chars().count() == 1 {
+            (cjk + 1, spaced)
+        } else {
+            (cjk, spaced + 1)
+        }
+    });
+    let words = cjk + spaced;
+    // Minutes = spaced / wpm + cjk / cpm, rounded up, in whole numbers
+    let reading_minutes = (words >= READING_TIME_MIN_WORDS).then(|| {
+        let scaled = spaced * READING_CJK_CHARS_PER_MINUTE + cjk * READING_WORDS_PER_MINUTE;
+        scaled.div_ceil(READING_WORDS_PER_MINUTE * READING_CJK_CHARS_PER_MINUTE)
+    });
+    TextStats {
+        characters: char_count(text),
+        words,
+        reading_minutes,
+    }
+}
+
+/// Gets how close a character count is to the todo text limit.
+pub fn length_level(length: usize) -> LengthLevel {
+    if length >= MAX_TODO_TEXT_LENGTH {
+        LengthLevel::Limit
+    } else if length * 10 >= MAX_TODO_TEXT_LENGTH * 9 {
+        LengthLevel::Warning
+    } else {
+        LengthLevel::Normal
+    }
+}
+
+/// Cuts text down to at most `max_chars` characters for display.
+///
+/// # Arguments
+/// * `text` - The text to shorten
+/// * `max_chars` - The number of characters to keep
+///
+/// # Returns
+/// The leading `max_chars` characters if the text is longer, otherwise `None`
+///
+/// # Example
+/// ```
+/// use todo::utils::text::truncate_chars;
+///
+/// assert_eq!(truncate_chars("héllo world", 5), Some("héllo"));
+/// assert_eq!(truncate_chars("short", 5), None);
+/// ```
+pub fn truncate_chars(text: &str, max_chars: usize) -> Option<&str> {
+    text.char_indices()
+        .nth(max_chars)
+        .map(|(byte_index, _)| &text[..byte_index])
+}
+
+/// Shortens text by cutting out its middle, keeping both ends readable.
+///
+/// # Returns
+/// The text with an ellipsis in place of its middle if it is longer than
+/// `max_chars`, otherwise `None`
+///
+/// # Example
+/// ```
+/// use todo::utils::text::truncate_middle;
+///
+/// assert_eq!(
+///     truncate_middle("https://example.com/a/very/long/path", 21),
+///     Some("https://ex…/long/path".to_string())
+/// );
+/// assert_eq!(truncate_middle("short", 5), None);
+/// ```
+pub fn truncate_middle(text: &str, max_chars: usize) -> Option<String> {
+    let length = char_count(text);
+    if length <= max_chars {
+        return None;
+    }
+    let kept = max_chars.saturating_sub(1);
+    let head = kept.div_ceil(2);
+    let tail = kept - head;
+    let mut shortened: String = text.chars().take(head).collect();
+    shortened.push('…');
+    shortened.extend(text.chars().skip(length - tail));
+    Some(shortened)
+}
+
+/// A piece of todo text as it is displayed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DisplaySegment<'a> {
+    /// Text shown as is
+    Text(&'a str),
+    /// A web address, with the form shown (shortened if it is long)
+    Url { full: &'a str, shown: String },
+    /// An issue-tracker reference, with the address it links to
+    Issue { text: &'a str, url: String },
+}
+
+/// Checks if a word is a web address.
+fn is_url(word: &str) -> bool {
+    ["http://", "https://", "www."]
+        .iter()
+        .any(|prefix| word.len() > prefix.len() && word.starts_with(prefix))
+}
+
+/// Splits text into plain runs and web addresses for display.
+///
+/// Addresses longer than `max_url_chars` are shortened in the middle; the
+/// full address is kept in the segment. Punctuation ending a sentence after
+/// an address is not part of it.
+///
+/// # Example
+/// ```
+/// use todo::utils::text::{display_segments, DisplaySegment};
+///
+/// assert_eq!(
+///     display_segments("Read https://example.com/docs/guide.", 21),
+///     vec![
+///         DisplaySegment::Text("Read "),
+///         DisplaySegment::Url {
+///             full: "https://example.com/docs/guide",
+///             shown: "https://ex…docs/guide".to_string(),
+///         },
+///         DisplaySegment::Text("."),
+///     ]
+/// );
+/// ```
+pub fn display_segments(text: &str, max_url_chars: usize) -> Vec<DisplaySegment<'_>> {
+    let mut segments = Vec::new();
+    let mut plain_start = 0;
+    let mut word_start = None;
+
+    // Walk the words, with a trailing space to end the last one
+    for (index, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (c.is_whitespace(), word_start) {
+            (false, None) => word_start = Some(index),
+            (true, Some(start)) => {
+                word_start = None;
+                let word = text[start..index].trim_end_matches(|c: char| {
+                    matches!(c, '.' | ',' | ';' | ':' | '!' | '?' | ')')
+                });
+                if !is_url(word) {
+                    continue;
+                }
+                if plain_start < start {
+                    segments.push(DisplaySegment::Text(&text[plain_start..start]));
+                }
+                segments.push(DisplaySegment::Url {
+                    full: word,
+                    shown: truncate_middle(word, max_url_chars).unwrap_or_else(|| word.to_string()),
+                });
+                plain_start = start + word.len();
+            }
+            _ => {}
+        }
+    }
+    if plain_start < text.len() {
+        segments.push(DisplaySegment::Text(&text[plain_start..]));
+    }
+    segments
+}
+
+/// Scores how well `query` matches `candidate` as a fuzzy search.
+///
+/// The query's characters must appear in the candidate in order, ignoring
+/// case and spaces in the query. Runs of consecutive characters, matches at
+/// the start of a word and matches near the front score higher, so "clc"
+/// ranks "Clear completed" above "Cancel lock".
+///
+/// # Returns
+/// A score where higher is better, `Some(0)` for an empty query, or `None`
+/// if the candidate does not contain the query's characters in order
+///
+/// # Example
+/// ```
+/// use todo::utils::text::fuzzy_score;
+///
+/// assert!(fuzzy_score("dark", "Toggle dark mode").is_some());
+/// assert!(fuzzy_score("tdm", "Toggle dark mode").is_some());
+/// assert_eq!(fuzzy_score("mdt", "Toggle dark mode"), None);
+/// assert!(fuzzy_score("exp", "Export JSON") > fuzzy_score("exp", "Switch to Today: expired"));
+/// ```
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let mut query_chars = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .peekable();
+    if query_chars.peek().is_none() {
+        return Some(0);
+    }
+
+    let mut score = 0;
+    let mut previous: Option<char> = None;
+    let mut run = 0;
+    let mut first_match = None;
+    for (position, c) in candidate.chars().enumerate() {
+        let Some(&wanted) = query_chars.peek() else {
+            break;
+        };
+        let matched = c.to_lowercase().eq([wanted]);
+        if matched {
+            query_chars.next();
+            first_match.get_or_insert(position as i64);
+            let word_start = previous.is_none_or(|p| !p.is_alphanumeric());
+            run += 1;
+            score += 1 + run * 2 + if word_start { 8 } else { 0 };
+        } else {
+            run = 0;
+        }
+        previous = Some(c);
+    }
+    if query_chars.peek().is_some() {
+        return None;
+    }
+    // Matches starting further in count a little less
+    Some(score - first_match.unwrap_or(0).min(20))
+}
+
+/// Lets the same entered text through once per interval.
+///
+/// Holding Enter can submit the same todo several times before the cleared
+/// input is seen. Text is held back while it repeats the previous entry within
+/// the interval, so a burst of any length counts once; different text always
+/// gets through.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepeatGuard {
+    interval: Duration,
+    last: Option<(String, DateTime<Utc>)>,
+}
+
+impl RepeatGuard {
+    /// Creates a guard letting identical text through once per `interval`.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: None,
+        }
+    }
+
+    /// Checks if text entered now is new, recording it if so.
+    ///
+    /// # Returns
+    /// `false` if the previous entry was the same text, within the interval
+    /// before `now`
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use todo::utils::text::RepeatGuard;
+    ///
+    /// let mut guard = RepeatGuard::default();
+    /// let now = Utc::now();
+    /// assert!(guard.try_enter("Buy milk", now));
+    /// assert!(!guard.try_enter("Buy milk", now + Duration::milliseconds(200)));
+    /// assert!(!guard.try_enter("Buy milk", now + Duration::milliseconds(400)));
+    /// assert!(guard.try_enter("Buy milk", now + Duration::seconds(1)));
+    /// assert!(guard.try_enter("Buy bread", now + Duration::seconds(1)));
+    /// ```
+    pub fn try_enter(&mut self, text: &str, now: DateTime<Utc>) -> bool {
+        let repeated = self
+            .last
+            .as_ref()
+            .is_some_and(|(last, at)| last == text && now < *at + self.interval);
+        self.last = Some((text.to_string(), now));
+        !repeated
+    }
+
+    /// Forgets the last entry if the text was since changed to something
+    /// else, so entering it again right away counts as new.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::Utc;
+    /// use todo::utils::text::RepeatGuard;
+    ///
+    /// let mut guard = RepeatGuard::default();
+    /// let now = Utc::now();
+    /// assert!(guard.try_enter("Buy milk", now));
+    /// guard.edited("Buy milk");
+    /// assert!(!guard.try_enter("Buy milk", now));
+    /// guard.edited("Buy milk and eggs");
+    /// assert!(guard.try_enter("Buy milk", now));
+    /// ```
+    pub fn edited(&mut self, text: &str) {
+        if self.last.as_ref().is_some_and(|(last, _)| last != text) {
+            self.last = None;
+        }
+    }
+}
+
+impl Default for RepeatGuard {
+    fn default() -> Self {
+        Self::new(Duration::milliseconds(REPEATED_ADD_WINDOW_MS))
+    }
+}
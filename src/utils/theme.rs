@@ -2,86 +2,838 @@
 //!
 //! This module provides utility functions for generating CSS classes
 //! with support for dark mode and other theme variations.
+//!
+//! Transition and animation classes go through [`motion`], so turning
+//! animations off in settings, or asking the system for reduced motion,
+//! covers the whole app.
 
 use crate::utils::constants::ui::css::*;
 
+/// Tailwind class prefixes that make an element move or fade
+const MOTION_PREFIXES: [&str; 5] = ["transition", "duration-", "ease-", "delay-", "animate-"];
+
+/// Checks if a Tailwind class animates, looking past variants like `hover:`.
+fn is_motion_class(class: &str) -> bool {
+    let utility = class.rsplit(':').next().unwrap_or(class);
+    MOTION_PREFIXES
+        .iter()
+        .any(|prefix| utility.starts_with(prefix))
+}
+
+/// Get transition and animation classes, or none when animations are off
+///
+/// # Arguments
+/// * `classes` - Space-separated classes; any that do not animate are kept
+/// * `animations_enabled` - Whether animations play
+///
+/// # Returns
+/// The classes, without the animating ones when animations are off
+///
+/// # Example
+/// ```
+/// use todo::utils::theme::motion;
+///
+/// assert_eq!(motion("transition-colors duration-300", true), "transition-colors duration-300");
+/// assert_eq!(motion("transition-colors duration-300", false), "");
+/// assert_eq!(motion("opacity-0 hover:animate-pulse ease-in", false), "opacity-0");
+/// ```
+pub fn motion(classes: &str, animations_enabled: bool) -> String {
+    if animations_enabled {
+        return classes.to_string();
+    }
+    classes
+        .split_whitespace()
+        .filter(|class| !is_motion_class(class))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Get the appropriate CSS class for a container element based on dark mode
 ///
 /// # Arguments
 /// * `is_dark_mode` - Whether dark mode is enabled
+/// * `animations_enabled` - Whether animations play
 ///
 /// # Returns
 /// CSS classes for the container
-pub fn container_class(is_dark_mode: bool) -> String {
+pub fn container_class(is_dark_mode: bool, animations_enabled: bool) -> String {
+    let fade = motion("transition-colors duration-300", animations_enabled);
+    if is_dark_mode {
+        format!("{} {}", BG_DARK_CLASS, fade)
+    } else {
+        format!("{} {}", BG_LIGHT_CLASS, fade)
+    }
+}
+
+/// A color for text that lights up on hover, such as an action's icon
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Accent {
+    Blue,
+    Green,
+    Purple,
+    Indigo,
+    Amber,
+    Red,
+    Gray,
+}
+
+/// The kind of message a notice banner carries
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tone {
+    /// News and hints
+    Info,
+    /// Something that needs attention soon
+    Warning,
+    /// Something that went wrong
+    Danger,
+}
+
+/// Get the background of a panel, card or bar
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS class for the background
+///
+/// # Example
+/// ```
+/// use todo::utils::theme::surface_class;
+///
+/// assert_eq!(surface_class(true), "bg-gray-800");
+/// assert_eq!(surface_class(false), "bg-white");
+/// ```
+pub fn surface_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "bg-gray-800"
+    } else {
+        "bg-white"
+    }
+}
+
+/// Get the background of a quieter area set into a surface
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS class for the background
+pub fn subtle_surface_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "bg-gray-800/60"
+    } else {
+        "bg-gray-50"
+    }
+}
+
+/// Get the colors of a card with a border
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the card background, border and text
+pub fn card_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "bg-gray-800 border-gray-700 text-gray-200"
+    } else {
+        "bg-white border-gray-200 text-gray-800"
+    }
+}
+
+/// Get the colors of a block set into a card, such as a preview or a log
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the block background, border and text
+pub fn inset_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "bg-gray-900/40 border-gray-700 text-gray-300"
+    } else {
+        "bg-gray-50 border-gray-200 text-gray-700"
+    }
+}
+
+/// Get the background marking the selected row of a list, or a drop target
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS class for the background
+pub fn highlight_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "bg-gray-700"
+    } else {
+        "bg-blue-50"
+    }
+}
+
+/// Get the background an icon button or menu entry takes on hover
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS class for the hover background
+pub fn hover_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "hover:bg-gray-700"
+    } else {
+        "hover:bg-gray-200"
+    }
+}
+
+/// Get the text color an icon button takes on hover
+///
+/// # Arguments
+/// * `accent` - The color the button lights up in
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS class for the hover text color
+///
+/// # Example
+/// ```
+/// use todo::utils::theme::{Accent, hover_text_class};
+///
+/// assert_eq!(hover_text_class(Accent::Red, true), "hover:text-red-400");
+/// assert_eq!(hover_text_class(Accent::Red, false), "hover:text-red-600");
+/// ```
+pub fn hover_text_class(accent: Accent, is_dark_mode: bool) -> &'static str {
+    match (accent, is_dark_mode) {
+        (Accent::Blue, true) => "hover:text-blue-400",
+        (Accent::Blue, false) => "hover:text-blue-600",
+        (Accent::Green, true) => "hover:text-green-400",
+        (Accent::Green, false) => "hover:text-green-600",
+        (Accent::Purple, true) => "hover:text-purple-400",
+        (Accent::Purple, false) => "hover:text-purple-600",
+        (Accent::Indigo, true) => "hover:text-indigo-400",
+        (Accent::Indigo, false) => "hover:text-indigo-600",
+        (Accent::Amber, true) => "hover:text-amber-400",
+        (Accent::Amber, false) => "hover:text-amber-600",
+        (Accent::Red, true) => "hover:text-red-400",
+        (Accent::Red, false) => "hover:text-red-600",
+        (Accent::Gray, true) => "hover:text-gray-300",
+        (Accent::Gray, false) => "hover:text-gray-700",
+    }
+}
+
+/// Get the colors of a notice banner
+///
+/// # Arguments
+/// * `tone` - The kind of message shown
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the banner background, border and text
+pub fn notice_class(tone: Tone, is_dark_mode: bool) -> &'static str {
+    match (tone, is_dark_mode) {
+        (Tone::Info, true) => "bg-blue-900/40 border-blue-700 text-blue-100",
+        (Tone::Info, false) => "bg-blue-50 border-blue-200 text-blue-900",
+        (Tone::Warning, true) => "bg-amber-900/40 border-amber-700 text-amber-200",
+        (Tone::Warning, false) => "bg-amber-50 border-amber-200 text-amber-800",
+        (Tone::Danger, true) => "bg-red-900/40 border-red-700 text-red-100",
+        (Tone::Danger, false) => "bg-red-50 border-red-200 text-red-900",
+    }
+}
+
+/// Get the colors of a small label tinted with an accent, such as the
+/// active profile or an enabled mode
+///
+/// # Arguments
+/// * `accent` - The color of the label
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the label background and text
+pub fn tint_class(accent: Accent, is_dark_mode: bool) -> &'static str {
+    match (accent, is_dark_mode) {
+        (Accent::Blue, true) => "bg-blue-900 text-blue-200",
+        (Accent::Blue, false) => "bg-blue-100 text-blue-800",
+        (Accent::Green, true) => "bg-green-900 text-green-200",
+        (Accent::Green, false) => "bg-green-100 text-green-800",
+        (Accent::Purple, true) => "bg-purple-900 text-purple-200",
+        (Accent::Purple, false) => "bg-purple-100 text-purple-800",
+        (Accent::Indigo, true) => "bg-indigo-900 text-indigo-200",
+        (Accent::Indigo, false) => "bg-indigo-100 text-indigo-700",
+        (Accent::Amber, true) => "bg-amber-900 text-amber-200",
+        (Accent::Amber, false) => "bg-amber-100 text-amber-800",
+        (Accent::Red, true) => "bg-red-900 text-red-200",
+        (Accent::Red, false) => "bg-red-100 text-red-800",
+        (Accent::Gray, true) => "bg-gray-700 text-gray-300",
+        (Accent::Gray, false) => "bg-gray-200 text-gray-600",
+    }
+}
+
+/// Get the classes of a button for the main action of a form
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the button
+pub fn primary_button_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "px-3 py-1 rounded text-white bg-blue-600 hover:bg-blue-700 disabled:opacity-50"
+    } else {
+        "px-3 py-1 rounded text-white bg-blue-500 hover:bg-blue-600 disabled:opacity-50"
+    }
+}
+
+/// Get the colors of a button filled with the accent color, for a button
+/// sized to fit the field it sits next to
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the button background, its hover state and text
+pub fn accent_button_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "bg-blue-600 text-white hover:bg-blue-700"
+    } else {
+        "bg-blue-500 text-white hover:bg-blue-600"
+    }
+}
+
+/// Get the classes of a button for any other action
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the button
+pub fn secondary_button_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "px-3 py-1 rounded bg-gray-700 text-gray-300 hover:bg-gray-600 disabled:opacity-50"
+    } else {
+        "px-3 py-1 rounded bg-gray-100 text-gray-600 hover:bg-gray-200 disabled:opacity-50"
+    }
+}
+
+/// Get the classes of a button deleting something for good
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the button
+pub fn danger_button_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "px-3 py-1 rounded bg-red-700 text-white hover:bg-red-600 disabled:opacity-50"
+    } else {
+        "px-3 py-1 rounded bg-red-500 text-white hover:bg-red-600 disabled:opacity-50"
+    }
+}
+
+/// Get the colors of a button set on a card or banner, which stands out
+/// from it with a border in light mode
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the button background, border and text
+pub fn outline_button_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "bg-gray-700 border-gray-600 text-gray-200 hover:bg-gray-600"
+    } else {
+        "bg-white border-gray-300 text-gray-700 hover:bg-gray-100"
+    }
+}
+
+/// Get the colors of one option of a segmented control
+///
+/// # Arguments
+/// * `selected` - Whether the option is the one chosen
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the option background and text
+pub fn segment_class(selected: bool, is_dark_mode: bool) -> &'static str {
+    match (selected, is_dark_mode) {
+        (true, true) => "bg-gray-700 text-white",
+        (true, false) => "bg-gray-200 text-gray-900",
+        (false, true) => "text-gray-300 hover:bg-gray-700",
+        (false, false) => "text-gray-600 hover:bg-gray-100",
+    }
+}
+
+/// Get the colors of a filter pill, filled in while it is on
+///
+/// # Arguments
+/// * `selected` - Whether the filter is on
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the pill background, border and text
+///
+/// # Example
+/// ```
+/// use todo::utils::theme::pill_class;
+///
+/// assert!(pill_class(true, true).contains("bg-blue-600"));
+/// assert!(pill_class(false, false).contains("bg-white"));
+/// ```
+pub fn pill_class(selected: bool, is_dark_mode: bool) -> &'static str {
+    match (selected, is_dark_mode) {
+        (true, true) => "bg-blue-600 text-white border-blue-600",
+        (true, false) => "bg-blue-500 text-white border-blue-500",
+        (false, true) => "bg-gray-800 text-gray-300 border-gray-700 hover:bg-gray-700",
+        (false, false) => "bg-white text-gray-600 border-gray-200 hover:bg-gray-100",
+    }
+}
+
+/// Get the classes of a text field or select in a settings form
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the control
+pub fn form_control_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "px-2 py-1 rounded border border-gray-600 bg-gray-700 text-gray-200"
+    } else {
+        "px-2 py-1 rounded border border-gray-300 bg-white text-gray-900"
+    }
+}
+
+/// Get the colors of a text field or text area
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the field border, background and text
+pub fn input_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "border-gray-600 bg-gray-700 text-gray-200"
+    } else {
+        "border-gray-300 bg-white text-gray-900"
+    }
+}
+
+/// Get the color of a placeholder in a text field
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS class for the placeholder color
+pub fn placeholder_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "placeholder-gray-500"
+    } else {
+        "placeholder-gray-400"
+    }
+}
+
+/// Get the ring around a focused field
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the focus ring and border
+pub fn focus_ring_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "focus:ring-blue-400 focus:border-blue-400"
+    } else {
+        "focus:ring-blue-500 focus:border-blue-500"
+    }
+}
+
+/// Get the colors of a checkbox
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the box, its tick and focus ring
+pub fn checkbox_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "text-blue-400 border-gray-600 bg-gray-700 focus:ring-blue-400 checked:bg-blue-400 checked:border-blue-400"
+    } else {
+        "text-blue-500 border-gray-300 focus:ring-blue-500"
+    }
+}
+
+/// Get the color between the items of a list
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS class for the divider color
+pub fn divide_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "divide-gray-700"
+    } else {
+        "divide-gray-200"
+    }
+}
+
+/// Get the color of the unfilled part of a progress ring
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS class for the track color
+pub fn track_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "text-gray-700"
+    } else {
+        "text-gray-200"
+    }
+}
+
+/// Get the color of the gap between an element and its focus ring, matching
+/// the page background
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS class for the ring offset color
+pub fn ring_offset_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "ring-offset-gray-900"
+    } else {
+        "ring-offset-white"
+    }
+}
+
+/// Get the colors of a toast, which stands out from the page in both themes
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the toast background, border and text
+pub fn toast_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "bg-gray-700 border-gray-600 text-gray-100"
+    } else {
+        "bg-gray-800 border-gray-900 text-white"
+    }
+}
+
+/// Get the color of body text
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS class for the text color
+pub fn primary_text_class(is_dark_mode: bool) -> &'static str {
     if is_dark_mode {
-        format!("{} transition-colors duration-300", BG_DARK_CLASS)
+        "text-gray-200"
     } else {
-        format!("{} transition-colors duration-300", BG_LIGHT_CLASS)
+        "text-gray-800"
     }
 }
 
-/// Get the appropriate CSS class for a primary button
+/// Get the color of links and text buttons that underline on hover
 ///
 /// # Arguments
 /// * `is_dark_mode` - Whether dark mode is enabled
-/// * `disabled` - Whether the button is disabled
 ///
 /// # Returns
-/// CSS classes for the primary button
-#[allow(dead_code)]
-pub fn primary_button_class(_is_dark_mode: bool, disabled: bool) -> String {
-    if disabled {
-        format!("{} opacity-50 cursor-not-allowed", PRIMARY_BUTTON_CLASS)
+/// CSS classes for the link color and hover state
+pub fn link_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "text-blue-400 hover:underline"
     } else {
-        PRIMARY_BUTTON_CLASS.to_string()
+        "text-blue-600 hover:underline"
     }
 }
 
-/// Get the appropriate CSS class for a secondary button
+/// Get the color of text that needs attention but is not an error
 ///
 /// # Arguments
 /// * `is_dark_mode` - Whether dark mode is enabled
-/// * `disabled` - Whether the button is disabled
 ///
 /// # Returns
-/// CSS classes for the secondary button
-#[allow(dead_code)]
-pub fn secondary_button_class(_is_dark_mode: bool, disabled: bool) -> String {
-    if disabled {
-        format!("{} opacity-50 cursor-not-allowed", SECONDARY_BUTTON_CLASS)
+/// CSS class for the text color
+pub fn warning_text_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "text-amber-400"
     } else {
-        SECONDARY_BUTTON_CLASS.to_string()
+        "text-amber-600"
     }
 }
 
-/// Get the appropriate CSS class for a card element
+/// Get the background of a todo's row
 ///
 /// # Arguments
+/// * `completed` - Whether the todo is done, which dims the row
 /// * `is_dark_mode` - Whether dark mode is enabled
 ///
 /// # Returns
-/// CSS classes for the card
-#[allow(dead_code)]
-pub fn card_class(is_dark_mode: bool) -> String {
+/// CSS classes for the row background and its hover state
+pub fn todo_row_class(completed: bool, is_dark_mode: bool) -> &'static str {
+    match (completed, is_dark_mode) {
+        (true, true) => "bg-gray-800/50 hover:bg-gray-700/50",
+        (true, false) => "bg-gray-50 hover:bg-gray-100",
+        (false, true) => "bg-gray-800 hover:bg-gray-750",
+        (false, false) => "bg-white hover:bg-gray-50",
+    }
+}
+
+/// Get the text color of a todo, struck through once it is done
+///
+/// # Arguments
+/// * `completed` - Whether the todo is done
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the todo text
+///
+/// # Example
+/// ```
+/// use todo::utils::theme::todo_text_class;
+///
+/// assert_eq!(todo_text_class(false, true), "text-gray-200");
+/// assert_eq!(todo_text_class(true, true), todo_text_class(true, false));
+/// ```
+pub fn todo_text_class(completed: bool, is_dark_mode: bool) -> &'static str {
+    if completed {
+        "line-through text-gray-500"
+    } else {
+        primary_text_class(is_dark_mode)
+    }
+}
+
+/// Get the color of borders and dividers
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS class for the border color
+pub fn border_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "border-gray-700"
+    } else {
+        "border-gray-200"
+    }
+}
+
+/// Get the colors of a small inline text field
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the field background, text and placeholder
+pub fn field_class(is_dark_mode: bool) -> &'static str {
     if is_dark_mode {
-        "bg-gray-800 border-gray-700 shadow-md rounded-lg p-4".to_string()
+        "bg-gray-700 text-gray-200 placeholder:text-gray-400"
     } else {
-        "bg-white border border-gray-200 shadow-md rounded-lg p-4".to_string()
+        "bg-white text-gray-800 placeholder:text-gray-400"
     }
 }
 
-/// Get the appropriate CSS class for an input element
+/// Get the color of muted text and of icon buttons, which brighten on hover
 ///
 /// # Arguments
 /// * `is_dark_mode` - Whether dark mode is enabled
 ///
 /// # Returns
-/// CSS classes for the input
-#[allow(dead_code)]
-pub fn input_class(is_dark_mode: bool) -> String {
+/// CSS class for the text color
+pub fn muted_text_class(is_dark_mode: bool) -> &'static str {
     if is_dark_mode {
-        "bg-gray-700 border-gray-600 text-white rounded p-2 w-full".to_string()
+        "text-gray-400"
     } else {
-        "bg-white border border-gray-300 text-gray-900 rounded p-2 w-full".to_string()
+        "text-gray-500"
+    }
+}
+
+/// Get the color of secondary text, such as due dates and captions
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS class for the text color
+pub fn secondary_text_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "text-gray-400"
+    } else {
+        "text-gray-600"
+    }
+}
+
+/// Get the color of links and text buttons
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS class for the text color
+pub fn accent_text_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "text-blue-400"
+    } else {
+        "text-blue-600"
+    }
+}
+
+/// Get the color of warnings, such as a todo being in the trash
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS class for the text color
+pub fn danger_text_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "text-red-400"
+    } else {
+        "text-red-600"
+    }
+}
+
+/// Get the colors of a tag pill
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the pill background, its hover state and text
+pub fn tag_pill_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "bg-blue-900/70 hover:bg-blue-800/70 text-blue-300"
+    } else {
+        "bg-blue-100 hover:bg-blue-200 text-blue-800"
+    }
+}
+
+/// Get the colors of a small gray chip button, such as "+3 more"
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the chip background, its hover state and text
+pub fn chip_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "bg-gray-700 text-gray-300 hover:bg-gray-600"
+    } else {
+        "bg-gray-200 text-gray-600 hover:bg-gray-300"
+    }
+}
+
+/// Get the classes of a suggested tag, offered for adding with one click
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the suggestion button
+pub fn tag_suggestion_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "text-xs px-2.5 py-0.5 rounded-full border border-gray-600 bg-gray-700 text-gray-300 opacity-80 hover:opacity-100 hover:border-gray-500"
+    } else {
+        "text-xs px-2.5 py-0.5 rounded-full border border-gray-300 bg-gray-100 text-gray-700 opacity-80 hover:opacity-100 hover:border-gray-400"
+    }
+}
+
+/// Get the background of a button confirming an addition
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the button background and its hover state
+pub fn add_button_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "bg-green-700 hover:bg-green-600"
+    } else {
+        "bg-green-500 hover:bg-green-600"
+    }
+}
+
+/// Get the colors of a small muted badge
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the badge background and text
+pub fn badge_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "bg-gray-700 text-gray-400"
+    } else {
+        "bg-gray-100 text-gray-500"
+    }
+}
+
+/// Get the colors of a popup menu
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the menu background, border and text
+pub fn menu_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "bg-gray-800 border-gray-600 text-gray-200"
+    } else {
+        "bg-white border-gray-200 text-gray-800"
+    }
+}
+
+/// Get the panel of a modal dialog
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the dialog background, border and text
+pub fn modal_panel_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "bg-gray-800 border-gray-700 text-gray-200"
+    } else {
+        "bg-white border-gray-200 text-gray-900"
+    }
+}
+
+/// Get the hover highlight of a popup menu's items
+///
+/// # Arguments
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS class for the item hover state
+pub fn menu_item_class(is_dark_mode: bool) -> &'static str {
+    if is_dark_mode {
+        "hover:bg-gray-700"
+    } else {
+        "hover:bg-gray-100"
+    }
+}
+
+/// Get the colors of a day in a date picker's month grid
+///
+/// # Arguments
+/// * `selected` - Whether the day is the picked date
+/// * `in_month` - Whether the day belongs to the month shown
+/// * `is_dark_mode` - Whether dark mode is enabled
+///
+/// # Returns
+/// CSS classes for the day's background and text
+pub fn calendar_day_class(selected: bool, in_month: bool, is_dark_mode: bool) -> &'static str {
+    match (selected, in_month, is_dark_mode) {
+        (true, _, _) => "bg-blue-500 text-white",
+        (false, true, true) => "text-gray-200 hover:bg-gray-700",
+        (false, true, false) => "text-gray-800 hover:bg-gray-100",
+        (false, false, true) => "text-gray-500 hover:bg-gray-700",
+        (false, false, false) => "text-gray-400 hover:bg-gray-100",
     }
 }
@@ -0,0 +1,45 @@
+//! Async timers that work on every platform
+//!
+//! The webview's `setTimeout` is used so the same code runs on web and
+//! desktop without pulling in a platform timer crate.
+
+use dioxus::prelude::*;
+use std::time::Duration;
+
+/// Waits for the given duration without blocking the UI.
+pub async fn sleep(duration: Duration) {
+    let eval = document::eval(
+        r#"
+        const ms = await dioxus.recv();
+        await new Promise((resolve) => setTimeout(resolve, ms));
+        return true;
+        "#,
+    );
+    let _ = eval.send(duration.as_millis() as u64);
+    let _ = eval.await;
+}
+
+/// Waits for the given duration, or until the window regains focus or becomes
+/// visible again, whichever comes first.
+///
+/// Timers are paused while a computer sleeps, so code waiting for a time of
+/// day uses this to check again as soon as the user is back.
+pub async fn sleep_or_wake(duration: Duration) {
+    let eval = document::eval(
+        r#"
+        const ms = await dioxus.recv();
+        await new Promise((resolve) => {
+            const timer = setTimeout(resolve, ms);
+            const wake = () => {
+                clearTimeout(timer);
+                resolve();
+            };
+            window.addEventListener("focus", wake, { once: true });
+            document.addEventListener("visibilitychange", wake, { once: true });
+        });
+        return true;
+        "#,
+    );
+    let _ = eval.send(duration.as_millis() as u64);
+    let _ = eval.await;
+}
@@ -0,0 +1,95 @@
+//! Version numbers of the app and the release notes between them
+//!
+//! Versions are compared semver-style on their `major.minor.patch` numbers.
+//! A leading `v` is accepted, missing parts count as zero, and pre-release or
+//! build suffixes (`-beta.1`, `+abc`) are ignored.
+
+use crate::utils::constants::app::RELEASE_NOTES;
+use std::fmt;
+
+/// A `major.minor.patch` version number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    /// Parses a version such as `1.4.2`, `v2.0` or `1.0.0-beta.1`.
+    ///
+    /// # Returns
+    /// The version, or `None` if the text does not start with a number
+    ///
+    /// # Example
+    /// ```
+    /// use todo::utils::version::Version;
+    ///
+    /// assert!(Version::parse("0.10.0") > Version::parse("0.9.3"));
+    /// assert_eq!(Version::parse("v2"), Version::parse("2.0.0"));
+    /// ```
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let text = text.strip_prefix(['v', 'V']).unwrap_or(text);
+        let core = text.split(['-', '+']).next()?;
+
+        let mut parts = core.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().map_or(Some(0), |part| part.parse().ok())?;
+        let patch = parts.next().map_or(Some(0), |part| part.parse().ok())?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Checks if the "What's new" dialog should be shown at startup.
+///
+/// Only upgrades show it: a fresh install has nothing to compare with, and
+/// going back to an older version has no news to tell.
+///
+/// # Arguments
+/// * `last_seen` - The version stored at the last visit, `None` on a fresh install
+/// * `current` - The running version
+pub fn should_show_whats_new(last_seen: Option<&str>, current: &str) -> bool {
+    match (last_seen.and_then(Version::parse), Version::parse(current)) {
+        (Some(last_seen), Some(current)) => current > last_seen,
+        _ => false,
+    }
+}
+
+/// Gets the release notes for the versions after `last_seen` up to `current`.
+///
+/// # Arguments
+/// * `last_seen` - The version stored at the last visit; `None` includes every older release
+/// * `current` - The running version
+///
+/// # Returns
+/// `(version, notes)` pairs, newest first
+pub fn notes_since(
+    last_seen: Option<&str>,
+    current: &str,
+) -> Vec<(&'static str, &'static [&'static str])> {
+    let last_seen = last_seen.and_then(Version::parse);
+    let current = Version::parse(current);
+    RELEASE_NOTES
+        .iter()
+        .filter(|(version, _)| {
+            let version = Version::parse(version);
+            version > last_seen && version <= current
+        })
+        .copied()
+        .collect()
+}
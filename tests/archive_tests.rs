@@ -0,0 +1,118 @@
+use chrono::{Duration, NaiveDate, Utc};
+use todo::models::{FilterState, Todo, TodoList};
+use todo::utils::export::{export_json, read_json};
+
+/// Builds a list of "Buy milk", the completed "Write report" and "Pay rent",
+/// and "Call mum", archived after it was completed.
+fn list() -> TodoList {
+    let mut list = TodoList::new();
+    list.add("Buy milk".to_string());
+    let report = list.add("Write report".to_string());
+    list.toggle_completion(report);
+    let rent = list.add("Pay rent".to_string());
+    list.toggle_completion(rent);
+    let call = list.add("Call mum".to_string());
+    list.toggle_completion(call);
+    list.archive(call);
+    list
+}
+
+fn ids(todos: &[Todo]) -> Vec<usize> {
+    todos.iter().map(|todo| todo.id).collect()
+}
+
+#[test]
+fn test_archived_todos_leave_the_list_but_are_kept() {
+    let mut list = list();
+    assert_eq!(ids(&list.all()), [1, 2, 3]);
+    assert_eq!(ids(&list.archived()), [4]);
+    assert_eq!(ids(&list.all_including_archived()), [1, 2, 3, 4]);
+    assert_eq!(list.total_count(), 4);
+    assert!(list.get(4).unwrap().archived);
+
+    assert!(!list.archive(4));
+    assert!(!list.archive(999));
+    assert!(list.unarchive(4));
+    assert!(!list.unarchive(4));
+    assert_eq!(ids(&list.all()), [1, 2, 3, 4]);
+    assert!(list.archived().is_empty());
+}
+
+#[test]
+fn test_filters_and_counts_ignore_archived_todos() {
+    let list = list();
+    let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+    assert_eq!(ids(&list.filtered(FilterState::All, today)), [1, 2, 3]);
+    assert_eq!(ids(&list.filtered(FilterState::Active, today)), [1]);
+    assert_eq!(ids(&list.filtered(FilterState::Completed, today)), [2, 3]);
+    assert_eq!(ids(&list.filtered(FilterState::Archived, today)), [4]);
+    assert_eq!(list.active_count(), 1);
+    assert_eq!(list.completed_count(), 2);
+    assert!(list.search("mum").is_empty());
+
+    // An archived open todo is not counted as active either
+    let mut list = list;
+    list.archive(1);
+    assert_eq!(list.active_count(), 0);
+}
+
+#[test]
+fn test_archived_todos_are_not_reminded_of() {
+    let mut list = TodoList::new();
+    let id = list.add("Renew passport".to_string());
+    let now = Utc::now();
+    list.set_due_date(id, Some(now + Duration::hours(1)))
+        .unwrap();
+    assert_eq!(list.due_soon(now, Duration::days(1)).len(), 1);
+
+    list.archive(id);
+    assert!(list.due_soon(now, Duration::days(1)).is_empty());
+}
+
+#[test]
+fn test_archive_completed_keeps_the_todos() {
+    let mut list = list();
+    list.set_locked(3, true);
+
+    let archived = list.archive_completed(&[3, 1, 2, 4, 999]);
+    assert_eq!(archived, [2, 3]);
+    assert_eq!(ids(&list.all()), [1]);
+    assert_eq!(ids(&list.archived()), [2, 3, 4]);
+
+    // Undo brings them back where they were
+    assert_eq!(list.unarchive_many(&archived), 2);
+    assert_eq!(ids(&list.all()), [1, 2, 3]);
+}
+
+#[test]
+fn test_clearing_completed_leaves_archived_todos_alone() {
+    let mut list = list();
+    let cleared = list.clear_completed();
+    assert_eq!(ids(&cleared.removed), [2, 3]);
+    assert_eq!(ids(&list.archived()), [4]);
+
+    let cleared = list.clear_completed_filtered(&[4]);
+    assert!(cleared.removed.is_empty());
+    assert_eq!(cleared.skipped_locked, 0);
+}
+
+#[test]
+fn test_archive_survives_saving_and_exports() {
+    let list = list();
+    let json = serde_json::to_string(&list).unwrap();
+    let restored: TodoList = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, list);
+
+    let exported = read_json(&export_json(&list).unwrap()).unwrap();
+    assert_eq!(ids(&exported), [1, 2, 3, 4]);
+    assert!(exported[3].archived);
+
+    // Todos saved before archiving existed load as not archived
+    let legacy = concat!(
+        r#"{"todos":[{"id":1,"text":"Buy milk","completed":true,"#,
+        r#""due_date":null,"tags":[],"order":1}],"next_id":2}"#
+    );
+    let legacy: TodoList = serde_json::from_str(legacy).unwrap();
+    assert_eq!(ids(&legacy.all()), [1]);
+    assert!(!json.contains(r#""archived":false"#));
+}
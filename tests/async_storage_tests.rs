@@ -0,0 +1,36 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use todo::models::TodoList;
+use todo::utils::constants::storage::STORAGE_BACKEND_ENV;
+use todo::utils::storage::StorageError;
+use todo::utils::{load_async, save_async};
+
+#[tokio::test]
+async fn test_async_save_and_load_round_trip() {
+    // The provider is cached process-wide, so select the backend before first use.
+    // SAFETY: this is the only test in this binary, nothing else reads the env concurrently.
+    unsafe { std::env::set_var(STORAGE_BACKEND_ENV, "memory") };
+
+    let mut list = TodoList::new();
+    list.add("Async todo".to_string());
+    save_async("async-test-key", &list).await.unwrap();
+
+    let loaded: TodoList = load_async("async-test-key").await.unwrap();
+    assert_eq!(loaded.all().len(), 1);
+    assert_eq!(loaded.all()[0].text, "Async todo");
+
+    // Later saves win over earlier ones, even when both are in flight
+    let empty = TodoList::new();
+    list.add("Second async todo".to_string());
+    let (first, second) = tokio::join!(
+        save_async("async-test-key", &empty),
+        save_async("async-test-key", &list)
+    );
+    first.unwrap();
+    second.unwrap();
+    let loaded: TodoList = load_async("async-test-key").await.unwrap();
+    assert_eq!(loaded.all().len(), 2);
+
+    let missing: Result<TodoList, StorageError> = load_async("async-missing-key").await;
+    assert!(matches!(missing, Err(StorageError::NotFound(_))));
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use common::clock;
+use todo::models::auto_archive::select_auto_archivable;
+use todo::models::{AppSettings, Todo, TodoList};
+use todo::utils::clock::Clock;
+
+/// Builds a todo completed the given number of days ago, or open for `None`.
+fn todo_completed_days_ago(id: usize, days_ago: Option<i64>, now: DateTime<Utc>) -> Todo {
+    let mut todo = Todo::new(id, format!("Todo {id}"));
+    if let Some(days) = days_ago {
+        todo.completed = true;
+        todo.completed_at = Some(now - Duration::days(days));
+    }
+    todo
+}
+
+#[test]
+fn test_select_auto_archivable_by_completion_age() {
+    let now = clock().now_utc();
+    let list = TodoList::from_todos(vec![
+        todo_completed_days_ago(1, Some(31), now),
+        todo_completed_days_ago(2, Some(5), now),
+        todo_completed_days_ago(3, None, now),
+        todo_completed_days_ago(4, Some(90), now),
+    ]);
+
+    assert_eq!(select_auto_archivable(&list, now, 30), vec![1, 4]);
+    assert_eq!(select_auto_archivable(&list, now, 60), vec![4]);
+    assert_eq!(select_auto_archivable(&list, now, 1), vec![1, 2, 4]);
+    assert!(select_auto_archivable(&list, now, 365).is_empty());
+}
+
+#[test]
+fn test_select_auto_archivable_keeps_locked_and_legacy_todos() {
+    let now = clock().now_utc();
+    let mut locked = todo_completed_days_ago(1, Some(400), now);
+    locked.locked = true;
+    // Completed before completion times were recorded
+    let mut legacy = todo_completed_days_ago(2, None, now);
+    legacy.completed = true;
+    let list = TodoList::from_todos(vec![locked, legacy]);
+
+    assert!(select_auto_archivable(&list, now, 30).is_empty());
+}
+
+#[test]
+fn test_select_auto_archivable_boundary() {
+    let now = clock().now_utc();
+    let list = TodoList::from_todos(vec![todo_completed_days_ago(1, Some(30), now)]);
+
+    // A todo is archived only once it is older than the limit
+    assert!(select_auto_archivable(&list, now, 30).is_empty());
+    assert_eq!(
+        select_auto_archivable(&list, now + Duration::seconds(1), 30),
+        vec![1]
+    );
+}
+
+#[test]
+fn test_archived_todos_can_be_restored_in_one_step() {
+    let mut list = TodoList::new();
+    let first = list.add("First".to_string());
+    let second = list.add("Second".to_string());
+    let kept = list.add("Kept".to_string());
+    list.set_locked(kept, true);
+
+    let outcome = list.move_many_to_trash(&[first, second, kept, 999]);
+    assert_eq!(outcome.changed, 2);
+    assert_eq!(outcome.skipped_locked, 1);
+    assert_eq!(list.trashed().len(), 2);
+
+    assert_eq!(list.restore_many(&[first, second, 999]), 2);
+    assert!(list.trashed().is_empty());
+    let ids: Vec<usize> = list.all().iter().map(|todo| todo.id).collect();
+    assert_eq!(ids, vec![kept, first, second]);
+    list.check_invariants().unwrap();
+}
+
+#[test]
+fn test_auto_archive_is_off_by_default() {
+    let settings: AppSettings = serde_json::from_str("{}").unwrap();
+    assert_eq!(settings.auto_archive_after_days, None);
+    assert_eq!(AppSettings::default().auto_archive_after_days, None);
+}
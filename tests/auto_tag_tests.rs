@@ -0,0 +1,107 @@
+use todo::models::auto_tag::AutoTagError;
+use todo::models::{AppSettings, AutoTagRule, AutoTagger, TodoList};
+
+fn rule(pattern: &str, is_regex: bool, tag: &str) -> AutoTagRule {
+    AutoTagRule {
+        pattern: pattern.to_string(),
+        is_regex,
+        tag: tag.to_string(),
+    }
+}
+
+#[test]
+fn test_plain_rules_match_whole_words_ignoring_case() {
+    let tagger = AutoTagger::new(&[rule("buy, order, pick up", false, "Shopping")]);
+
+    assert_eq!(tagger.tags_for("BUY milk"), vec!["Shopping"]);
+    assert_eq!(tagger.tags_for("Order: pizza"), vec!["Shopping"]);
+    assert_eq!(tagger.tags_for("Pick up the parcel"), vec!["Shopping"]);
+    assert!(tagger.tags_for("Sort out the disorder").is_empty());
+    assert!(tagger.tags_for("Buyers meeting").is_empty());
+}
+
+#[test]
+fn test_regex_rules_ignore_case() {
+    let tagger = AutoTagger::new(&[rule(r"^call\s+\w+", true, "Phone")]);
+
+    assert_eq!(tagger.tags_for("Call mom"), vec!["Phone"]);
+    assert!(tagger.tags_for("Recall the letter").is_empty());
+}
+
+#[test]
+fn test_tags_are_not_duplicated() {
+    let tagger = AutoTagger::new(&[
+        rule("buy", false, "Shopping"),
+        rule("order", false, "Shopping"),
+        rule("urgent", false, "Urgent"),
+    ]);
+
+    assert_eq!(
+        tagger.tags_for("Urgent: buy and order"),
+        vec!["Shopping", "Urgent"]
+    );
+}
+
+#[test]
+fn test_validate_reports_errors_when_saving() {
+    assert_eq!(rule("buy", false, "Shopping").validate(), Ok(()));
+    assert_eq!(
+        rule(" , ", false, "Shopping").validate(),
+        Err(AutoTagError::EmptyPattern)
+    );
+    assert_eq!(
+        rule("buy", false, "  ").validate(),
+        Err(AutoTagError::EmptyTag)
+    );
+    assert!(matches!(
+        rule("(unclosed", true, "Broken").validate(),
+        Err(AutoTagError::InvalidRegex(_))
+    ));
+
+    // Invalid rules that slipped into settings are skipped rather than failing
+    let tagger = AutoTagger::new(&[rule("(unclosed", true, "Broken")]);
+    assert!(tagger.is_empty());
+}
+
+#[test]
+fn test_apply_auto_tags_is_idempotent() {
+    let tagger = AutoTagger::new(&[rule("buy", false, "Shopping")]);
+    let mut list = TodoList::new();
+    let id = list.add("Buy milk".to_string());
+    list.add_tag(id, "Personal".to_string());
+
+    assert!(list.apply_auto_tags(id, &tagger));
+    assert!(!list.apply_auto_tags(id, &tagger));
+    assert_eq!(list.all()[0].tags, vec!["Personal", "Shopping"]);
+    assert!(!list.apply_auto_tags(999, &tagger));
+}
+
+#[test]
+fn test_apply_auto_tags_to_all_counts_tagged_todos() {
+    let tagger = AutoTagger::new(&[rule("buy, order", false, "Shopping")]);
+    let mut list = TodoList::new();
+    list.add("Buy milk".to_string());
+    list.add("Order pizza".to_string());
+    list.add("Write report".to_string());
+    let tagged = list.add("Buy stamps".to_string());
+    list.add_tag(tagged, "Shopping".to_string());
+
+    assert_eq!(list.apply_auto_tags_to_all(&tagger), 2);
+    assert_eq!(list.apply_auto_tags_to_all(&tagger), 0);
+    assert_eq!(list.apply_auto_tags_to_all(&AutoTagger::default()), 0);
+}
+
+#[test]
+fn test_rules_are_stored_in_settings() {
+    let settings = AppSettings {
+        auto_tag_rules: vec![rule("buy", false, "Shopping")],
+        ..AppSettings::default()
+    };
+    let json = serde_json::to_string(&settings).unwrap();
+    let loaded: AppSettings = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, settings);
+
+    // Settings saved before rules existed load with none
+    let old: AppSettings = serde_json::from_str(r#"{"week_start":"Monday"}"#).unwrap();
+    assert!(old.auto_tag_rules.is_empty());
+}
@@ -0,0 +1,197 @@
+use std::time::Duration;
+use todo::models::{AppSettings, AutosaveInterval, TodoList};
+use todo::utils::autosave::{SaveStatus, SaveTracker, flush_with};
+use todo::utils::storage::{
+    InitialLoad, LoadFailure, MemoryStorage, RawStorage, StorageError, initial_load_with, load_with,
+};
+
+/// A backend whose writes always fail.
+struct FailingStorage;
+
+impl RawStorage for FailingStorage {
+    fn save_str(&self, _key: &str, _value: &str) -> Result<(), StorageError> {
+        Err(StorageError::DbError("disk full".to_string()))
+    }
+
+    fn load_str(&self, key: &str) -> Result<String, StorageError> {
+        Err(StorageError::NotFound(key.to_string()))
+    }
+
+    fn delete(&self, _key: &str) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn list_keys(&self) -> Result<Vec<String>, StorageError> {
+        Ok(Vec::new())
+    }
+}
+
+/// A backend whose reads fail, as when the database cannot be opened.
+struct UnreadableStorage;
+
+impl RawStorage for UnreadableStorage {
+    fn save_str(&self, _key: &str, _value: &str) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn load_str(&self, _key: &str) -> Result<String, StorageError> {
+        Err(StorageError::DbError("database is locked".to_string()))
+    }
+
+    fn delete(&self, _key: &str) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn list_keys(&self) -> Result<Vec<String>, StorageError> {
+        Ok(Vec::new())
+    }
+}
+
+#[test]
+fn test_tracker_starts_saved() {
+    let tracker = SaveTracker::default();
+    assert_eq!(tracker.status(), SaveStatus::Saved);
+    assert!(!tracker.is_dirty());
+}
+
+#[test]
+fn test_tracker_change_save_cycle() {
+    let storage = MemoryStorage::new();
+    let mut tracker = SaveTracker::default();
+    let mut list = TodoList::new();
+
+    list.add("Buy milk".to_string());
+    tracker.mark_dirty();
+    assert_eq!(tracker.status(), SaveStatus::Unsaved);
+
+    flush_with(&mut tracker, &storage, "todos", &list).unwrap();
+    assert_eq!(tracker.status(), SaveStatus::Saved);
+    assert_eq!(load_with::<TodoList>(&storage, "todos").unwrap(), list);
+}
+
+#[test]
+fn test_tracker_change_during_save_stays_unsaved() {
+    let mut tracker = SaveTracker::default();
+    tracker.mark_dirty();
+
+    tracker.begin_save();
+    assert_eq!(tracker.status(), SaveStatus::Saving);
+    tracker.mark_dirty();
+    assert_eq!(tracker.status(), SaveStatus::Saving);
+
+    tracker.finish_save(&Ok(()));
+    assert_eq!(tracker.status(), SaveStatus::Unsaved);
+}
+
+#[test]
+fn test_tracker_reports_failed_save_until_next_success() {
+    let mut tracker = SaveTracker::default();
+    let list = TodoList::new();
+    tracker.mark_dirty();
+
+    assert!(flush_with(&mut tracker, &FailingStorage, "todos", &list).is_err());
+    let SaveStatus::Failed(error) = tracker.status() else {
+        panic!("expected a failed status, got {:?}", tracker.status());
+    };
+    assert!(error.contains("disk full"));
+    assert_eq!(tracker.status().label(), "Save failed");
+    assert!(tracker.is_dirty());
+    assert_eq!(tracker.last_saved(), None);
+
+    // More changes keep showing the failure
+    tracker.mark_dirty();
+    assert!(matches!(tracker.status(), SaveStatus::Failed(_)));
+
+    let before = chrono::Utc::now();
+    flush_with(&mut tracker, &MemoryStorage::new(), "todos", &list).unwrap();
+    assert_eq!(tracker.status(), SaveStatus::Saved);
+    assert!(!tracker.is_dirty());
+    assert!(tracker.last_saved().is_some_and(|saved| saved >= before));
+}
+
+#[test]
+fn test_autosave_interval_setting() {
+    assert_eq!(AppSettings::default().autosave, AutosaveInterval::Immediate);
+    assert_eq!(AutosaveInterval::Immediate.delay(), Some(Duration::ZERO));
+    assert_eq!(
+        AutosaveInterval::FiveSeconds.delay(),
+        Some(Duration::from_secs(5))
+    );
+    assert_eq!(AutosaveInterval::Manual.delay(), None);
+
+    for interval in AutosaveInterval::ALL {
+        assert_eq!(AutosaveInterval::parse(interval.label()), Some(interval));
+    }
+
+    let settings: AppSettings = serde_json::from_str(r#"{"autosave":"Manual"}"#).unwrap();
+    assert_eq!(settings.autosave, AutosaveInterval::Manual);
+}
+
+#[test]
+fn test_initial_load_of_missing_data_is_empty() {
+    assert_eq!(
+        initial_load_with::<TodoList>(&FailingStorage, "todos"),
+        InitialLoad::Empty
+    );
+}
+
+#[test]
+fn test_initial_load_of_stored_data() {
+    let storage = MemoryStorage::new();
+    let mut list = TodoList::new();
+    list.add("Buy milk".to_string());
+    flush_with(&mut SaveTracker::default(), &storage, "todos", &list).unwrap();
+
+    assert_eq!(
+        initial_load_with::<TodoList>(&storage, "todos"),
+        InitialLoad::Loaded(list)
+    );
+}
+
+#[test]
+fn test_initial_load_storage_error_blocks_saves() {
+    let InitialLoad::Failed(LoadFailure { error, raw }) =
+        initial_load_with::<TodoList>(&UnreadableStorage, "todos")
+    else {
+        panic!("a read error must not look like a fresh install");
+    };
+    assert!(error.contains("database is locked"));
+    assert_eq!(raw, None);
+
+    let storage = MemoryStorage::new();
+    let mut tracker = SaveTracker::default();
+    tracker.block_persistence();
+    tracker.mark_dirty();
+    assert_eq!(tracker.status(), SaveStatus::Blocked);
+
+    let result = flush_with(&mut tracker, &storage, "todos", &TodoList::new());
+    assert!(matches!(result, Err(StorageError::Blocked)));
+    assert!(matches!(
+        storage.load_str("todos"),
+        Err(StorageError::NotFound(_))
+    ));
+}
+
+#[test]
+fn test_initial_load_corrupt_data_is_kept_until_recovered() {
+    let storage = MemoryStorage::new();
+    storage.save_str("todos", "{\"todos\": [").unwrap();
+
+    let InitialLoad::Failed(failure) = initial_load_with::<TodoList>(&storage, "todos") else {
+        panic!("corrupt data must not load");
+    };
+    assert_eq!(failure.raw.as_deref(), Some("{\"todos\": ["));
+
+    let mut tracker = SaveTracker::default();
+    tracker.block_persistence();
+    assert!(flush_with(&mut tracker, &storage, "todos", &TodoList::new()).is_err());
+    assert_eq!(storage.load_str("todos").unwrap(), "{\"todos\": [");
+
+    // Restoring a backup allows saving again
+    tracker.unblock_persistence();
+    let mut restored = TodoList::new();
+    restored.add("From backup".to_string());
+    flush_with(&mut tracker, &storage, "todos", &restored).unwrap();
+    assert_eq!(tracker.status(), SaveStatus::Saved);
+    assert_eq!(load_with::<TodoList>(&storage, "todos").unwrap(), restored);
+}
@@ -0,0 +1,448 @@
+use chrono::{DateTime, TimeZone, Utc};
+use todo::models::TodoList;
+use todo::sync::caldav::ical::{VTodo, parse_vtodos, write_vtodo};
+use todo::sync::caldav::plan::SyncAction;
+use todo::sync::caldav::xml::{CalendarResource, parse_multistatus};
+use todo::sync::caldav::{CalDavError, plan_sync};
+
+/// REPORT response captured from a Nextcloud Tasks collection
+const NEXTCLOUD_REPORT: &str = include_str!("fixtures/caldav/nextcloud_report.xml");
+/// REPORT response captured from a Radicale collection synced with Apple Reminders
+const RADICALE_REPORT: &str = include_str!("fixtures/caldav/radicale_report.xml");
+
+fn synced_at() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap()
+}
+
+fn nextcloud_resources() -> Vec<CalendarResource> {
+    parse_multistatus(NEXTCLOUD_REPORT).unwrap()
+}
+
+/// A list synced once with the Nextcloud collection
+fn synced_list() -> TodoList {
+    let mut list = TodoList::new();
+    let plan = plan_sync(&list, nextcloud_resources());
+    plan.apply(&mut list, &[], synced_at());
+    list
+}
+
+/// Changes a resource's entity tag and data, as an edit on the server does
+fn edited_on_server(resource: &CalendarResource, from: &str, to: &str) -> CalendarResource {
+    CalendarResource {
+        href: resource.href.clone(),
+        etag: Some("\"edited\"".to_string()),
+        calendar_data: resource.calendar_data.replace(from, to),
+    }
+}
+
+#[test]
+fn test_parse_nextcloud_multistatus() {
+    let resources = nextcloud_resources();
+
+    // The resource whose calendar data came back 404 is skipped
+    assert_eq!(resources.len(), 2);
+    assert_eq!(
+        resources[0].href,
+        "/remote.php/dav/calendars/alex/tasks/8F3A2C1E-5B7D-4E21-9A0C-3D2F1B6E7A90.ics"
+    );
+    assert_eq!(
+        resources[0].etag.as_deref(),
+        Some("\"6b1e1d0c0f4d3a2b1e9f8a7c6d5e4f3a\"")
+    );
+    assert!(
+        resources[1]
+            .calendar_data
+            .contains("UID:buy-milk@example.com")
+    );
+}
+
+#[test]
+fn test_parse_radicale_multistatus() {
+    let resources = parse_multistatus(RADICALE_REPORT).unwrap();
+
+    // The collection itself has no calendar data; CDATA is read as text
+    assert_eq!(resources.len(), 1);
+    assert_eq!(resources[0].href, "/alex/tasks/call%20mom.ics");
+    assert_eq!(resources[0].etag.as_deref(), Some("\"a1b2c3\""));
+    assert!(resources[0].calendar_data.starts_with("BEGIN:VCALENDAR"));
+}
+
+#[test]
+fn test_parse_multistatus_rejects_other_documents() {
+    assert!(matches!(
+        parse_multistatus("<html><body>Login</body></html>"),
+        Err(CalDavError::Parse(_))
+    ));
+    assert!(matches!(
+        parse_multistatus("not xml"),
+        Err(CalDavError::Parse(_))
+    ));
+}
+
+#[test]
+fn test_parse_vtodo_with_folding_escapes_and_alarm() {
+    let todos = parse_vtodos(&nextcloud_resources()[0].calendar_data).unwrap();
+
+    assert_eq!(
+        todos,
+        vec![VTodo {
+            uid: "8F3A2C1E-5B7D-4E21-9A0C-3D2F1B6E7A90".to_string(),
+            summary: "Prepare slides for the quarterly review, including the budget overview"
+                .to_string(),
+            due: Some(Utc.with_ymd_and_hms(2025, 3, 7, 0, 0, 0).unwrap()),
+            completed: false,
+            completed_at: None,
+            categories: vec!["Work".to_string(), "Presentations".to_string()],
+            sequence: 2,
+            last_modified: Some(Utc.with_ymd_and_hms(2025, 3, 3, 17, 42, 10).unwrap()),
+        }]
+    );
+}
+
+#[test]
+fn test_parse_completed_vtodo_with_time_zone() {
+    let todo = parse_vtodos(&nextcloud_resources()[1].calendar_data)
+        .unwrap()
+        .remove(0);
+
+    assert!(todo.completed);
+    assert_eq!(
+        todo.completed_at,
+        Some(Utc.with_ymd_and_hms(2025, 3, 2, 8, 15, 0).unwrap())
+    );
+    // Times in a named zone are read as UTC
+    assert_eq!(
+        todo.due,
+        Some(Utc.with_ymd_and_hms(2025, 3, 4, 18, 0, 0).unwrap())
+    );
+    // CATEGORIES may be repeated
+    assert_eq!(todo.categories, vec!["Shopping", "Errands"]);
+    assert_eq!(todo.sequence, 0);
+    assert_eq!(todo.last_modified, None);
+}
+
+#[test]
+fn test_parse_vtodo_from_other_clients() {
+    let resources = parse_multistatus(RADICALE_REPORT).unwrap();
+    let todo = parse_vtodos(&resources[0].calendar_data).unwrap().remove(0);
+
+    assert_eq!(todo.summary, "Call mom; ask about the weekend");
+    assert!(!todo.completed, "IN-PROCESS is still open");
+    assert_eq!(
+        todo.due,
+        Some(Utc.with_ymd_and_hms(2025, 3, 5, 16, 30, 0).unwrap())
+    );
+}
+
+#[test]
+fn test_parse_vtodo_errors() {
+    let no_uid = "BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\nSUMMARY:x\r\nEND:VTODO\r\nEND:VCALENDAR\r\n";
+    assert!(matches!(parse_vtodos(no_uid), Err(CalDavError::Parse(_))));
+
+    let unclosed = "BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\nUID:1\r\n";
+    assert!(matches!(parse_vtodos(unclosed), Err(CalDavError::Parse(_))));
+
+    let events_only = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:1\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+    assert_eq!(parse_vtodos(events_only).unwrap(), Vec::new());
+}
+
+#[test]
+fn test_write_new_vtodo_round_trips() {
+    let todo = VTodo {
+        uid: "abc@todo.dioxus.local".to_string(),
+        summary: "Write the long report on the migration; list risks, owners and a very long tail"
+            .to_string(),
+        due: Some(Utc.with_ymd_and_hms(2025, 3, 7, 0, 0, 0).unwrap()),
+        completed: true,
+        completed_at: Some(Utc.with_ymd_and_hms(2025, 3, 6, 9, 0, 0).unwrap()),
+        categories: vec!["Work".to_string(), "a,b".to_string()],
+        sequence: 3,
+        last_modified: None,
+    };
+    let written = write_vtodo(&todo, None, synced_at());
+
+    assert!(written.contains("DUE;VALUE=DATE:20250307\r\n"));
+    assert!(written.contains("STATUS:COMPLETED\r\n"));
+    assert!(written.lines().all(|line| line.trim_end().len() <= 75));
+
+    let parsed = parse_vtodos(&written).unwrap().remove(0);
+    assert_eq!(
+        parsed,
+        VTodo {
+            last_modified: Some(synced_at()),
+            ..todo
+        }
+    );
+}
+
+#[test]
+fn test_write_vtodo_keeps_other_properties() {
+    let original = &nextcloud_resources()[0].calendar_data;
+    let mut todo = parse_vtodos(original).unwrap().remove(0);
+    todo.summary = "Prepare slides".to_string();
+    todo.completed = true;
+    todo.due = None;
+    todo.sequence = 3;
+
+    let written = write_vtodo(&todo, Some(original), synced_at());
+
+    for kept in [
+        "PRIORITY:5",
+        "BEGIN:VALARM",
+        "TRIGGER:-PT1H",
+        "CREATED:20250301T091500Z",
+    ] {
+        assert!(written.contains(kept), "{} is kept", kept);
+    }
+    assert!(!written.contains("NEEDS-ACTION"));
+    assert!(!written.contains("DUE"));
+    assert_eq!(written.matches("SUMMARY:").count(), 1);
+    let parsed = parse_vtodos(&written).unwrap().remove(0);
+    assert_eq!(parsed.summary, "Prepare slides");
+    assert!(parsed.completed);
+    assert_eq!(parsed.sequence, 3);
+}
+
+#[test]
+fn test_first_sync_imports_and_uploads() {
+    let mut list = TodoList::new();
+    let local = list.add("Local only".to_string());
+
+    let plan = plan_sync(&list, nextcloud_resources());
+
+    assert_eq!(plan.actions.len(), 3);
+    assert_eq!(plan.actions[0], SyncAction::Upload { id: local });
+    assert!(
+        matches!(&plan.actions[1], SyncAction::Import(remote) if remote.vtodo.summary.starts_with("Prepare slides"))
+    );
+    assert!(
+        matches!(&plan.actions[2], SyncAction::Import(remote) if remote.vtodo.summary == "Buy milk")
+    );
+    assert_eq!(
+        plan.describe(&list),
+        vec![
+            "Upload \"Local only\"".to_string(),
+            "Add \"Prepare slides for the quarterly review, including the budg…\" from the server"
+                .to_string(),
+            "Add \"Buy milk\" from the server".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_apply_links_imported_todos() {
+    let list = synced_list();
+
+    let todos = list.all();
+    assert_eq!(todos.len(), 2);
+    assert_eq!(todos[1].text, "Buy milk");
+    assert!(todos[1].completed);
+    assert_eq!(todos[1].tags, vec!["Shopping", "Errands"]);
+    let link = todos[1].remote.as_ref().unwrap();
+    assert_eq!(link.uid, "buy-milk@example.com");
+    assert_eq!(link.synced_at, synced_at());
+    assert!(!todos[1].changed_since_sync());
+
+    // Nothing changed on either side
+    assert!(plan_sync(&list, nextcloud_resources()).is_empty());
+}
+
+#[test]
+fn test_one_sided_changes() {
+    let mut list = synced_list();
+    let resources = nextcloud_resources();
+
+    // Edited here
+    list.update_text(2, "Buy oat milk".to_string()).unwrap();
+    let plan = plan_sync(&list, resources.clone());
+    assert!(matches!(
+        &plan.actions[..],
+        [SyncAction::UpdateRemote {
+            id: 2,
+            conflict: false,
+            ..
+        }]
+    ));
+
+    // Edited on the server
+    let list = synced_list();
+    let edited = vec![
+        resources[0].clone(),
+        edited_on_server(
+            &resources[1],
+            "SUMMARY:Buy milk",
+            "SUMMARY:Buy milk and eggs",
+        ),
+    ];
+    let plan = plan_sync(&list, edited);
+    assert!(matches!(
+        &plan.actions[..],
+        [SyncAction::UpdateLocal {
+            id: 2,
+            conflict: false,
+            ..
+        }]
+    ));
+
+    let mut list = list;
+    assert_eq!(plan.apply(&mut list, &[], synced_at()), 1);
+    assert_eq!(list.get(2).unwrap().text, "Buy milk and eggs");
+    assert_eq!(
+        list.get(2)
+            .unwrap()
+            .remote
+            .as_ref()
+            .unwrap()
+            .etag
+            .as_deref(),
+        Some("\"edited\"")
+    );
+}
+
+#[test]
+fn test_conflict_goes_to_the_later_change() {
+    let resources = nextcloud_resources();
+
+    // The local edit is newer than the server's LAST-MODIFIED in 2025
+    let mut list = synced_list();
+    list.update_text(1, "Prepare slides".to_string()).unwrap();
+    let edited = vec![
+        edited_on_server(&resources[0], "PRIORITY:5", "PRIORITY:1"),
+        resources[1].clone(),
+    ];
+    assert!(matches!(
+        &plan_sync(&list, edited).actions[..],
+        [SyncAction::UpdateRemote {
+            id: 1,
+            conflict: true,
+            ..
+        }]
+    ));
+
+    // The server's change is stamped later than the local edit
+    let edited = vec![
+        edited_on_server(
+            &resources[0],
+            "LAST-MODIFIED:20250303T174210Z",
+            "LAST-MODIFIED:20990101T000000Z",
+        ),
+        resources[1].clone(),
+    ];
+    assert!(matches!(
+        &plan_sync(&list, edited).actions[..],
+        [SyncAction::UpdateLocal {
+            id: 1,
+            conflict: true,
+            ..
+        }]
+    ));
+}
+
+#[test]
+fn test_conflict_without_last_modified_uses_sequence() {
+    let resources = nextcloud_resources();
+    let mut list = synced_list();
+    list.add_tag(2, "Dairy".to_string());
+
+    // Same SEQUENCE as last seen: the local edit wins
+    let edited = vec![
+        resources[0].clone(),
+        edited_on_server(&resources[1], "PERCENT-COMPLETE:100", "PRIORITY:1"),
+    ];
+    assert!(matches!(
+        &plan_sync(&list, edited).actions[..],
+        [SyncAction::UpdateRemote {
+            id: 2,
+            conflict: true,
+            ..
+        }]
+    ));
+
+    // A raised SEQUENCE means the server's copy is a newer revision
+    let edited = vec![
+        resources[0].clone(),
+        edited_on_server(&resources[1], "PERCENT-COMPLETE:100", "SEQUENCE:1"),
+    ];
+    assert!(matches!(
+        &plan_sync(&list, edited).actions[..],
+        [SyncAction::UpdateLocal {
+            id: 2,
+            conflict: true,
+            ..
+        }]
+    ));
+}
+
+#[test]
+fn test_deletions_on_either_side() {
+    let resources = nextcloud_resources();
+
+    // Deleted on the server and unchanged here: moved to the trash
+    let mut list = synced_list();
+    let plan = plan_sync(&list, vec![resources[0].clone()]);
+    assert_eq!(plan.actions, vec![SyncAction::TrashLocal { id: 2 }]);
+    assert_eq!(plan.apply(&mut list, &[], synced_at()), 1);
+    assert!(list.get(2).is_none());
+
+    // Deleted on the server but changed here: uploaded again
+    let mut list = synced_list();
+    list.toggle(2);
+    let plan = plan_sync(&list, vec![resources[0].clone()]);
+    assert_eq!(plan.actions, vec![SyncAction::Upload { id: 2 }]);
+
+    // Deleted here and unchanged on the server: deleted there
+    let mut list = synced_list();
+    list.move_to_trash(2).unwrap();
+    let plan = plan_sync(&list, resources.clone());
+    assert!(matches!(
+        &plan.actions[..],
+        [SyncAction::DeleteRemote { id: 2, .. }]
+    ));
+    assert_eq!(
+        plan.describe(&list),
+        vec!["Delete \"Buy milk\" from the server (deleted here)".to_string()]
+    );
+}
+
+#[test]
+fn test_apply_keeps_edits_made_during_the_sync() {
+    let resources = nextcloud_resources();
+    let mut list = synced_list();
+    let edited = vec![
+        resources[0].clone(),
+        edited_on_server(
+            &resources[1],
+            "SUMMARY:Buy milk",
+            "SUMMARY:Buy milk and eggs",
+        ),
+    ];
+    let plan = plan_sync(&list, edited);
+
+    // Edited after the list was read for the sync
+    list.update_text(2, "Buy soy milk".to_string()).unwrap();
+    assert_eq!(plan.apply(&mut list, &[], synced_at()), 0);
+    assert_eq!(list.get(2).unwrap().text, "Buy soy milk");
+}
+
+#[test]
+fn test_unreadable_resources_are_skipped() {
+    let resources = vec![CalendarResource {
+        href: "/tasks/bad.ics".to_string(),
+        etag: None,
+        calendar_data:
+            "BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\nSUMMARY:x\r\nEND:VTODO\r\nEND:VCALENDAR\r\n"
+                .to_string(),
+    }];
+    let list = TodoList::new();
+
+    let plan = plan_sync(&list, resources);
+
+    assert!(plan.is_empty());
+    assert_eq!(
+        plan.describe(&list),
+        vec![
+            "Skip /tasks/bad.ics: Could not read the server's answer: a VTODO has no UID"
+                .to_string()
+        ]
+    );
+}
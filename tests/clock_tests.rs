@@ -0,0 +1,103 @@
+use chrono::{Duration, NaiveDate};
+use todo::models::auto_archive::select_auto_archivable;
+use todo::models::{ListView, QuickFilter, Todo, TodoList, WeekStart};
+use todo::utils::clock::{Clock, FixedClock};
+use todo::utils::dates::{self, DueBucket};
+
+fn day(d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, d).unwrap()
+}
+
+/// A list with one open todo due on 12 March.
+fn due_on_the_12th() -> Vec<Todo> {
+    let mut todo = Todo::new(1, "File the report".to_string());
+    todo.due_date = Some(dates::due_date_on(day(12)));
+    vec![todo]
+}
+
+/// Gets the ids the quick filter shows at the clock's time.
+fn shown(todos: &[Todo], pill: QuickFilter, clock: &impl Clock) -> Vec<usize> {
+    let view = ListView {
+        quick_filter: Some(pill),
+        ..ListView::default()
+    };
+    view.visible_ids(todos, clock.now_utc(), clock.today_local())
+}
+
+#[test]
+fn test_fixed_clock_keeps_local_date_apart_from_utc() {
+    // East of UTC, the local day starts while UTC is still on the day before
+    let berlin = FixedClock::at_local(day(12), 0, 1, 2);
+    assert_eq!(berlin.today_local(), day(12));
+    assert_eq!(berlin.now_utc().date_naive(), day(11));
+
+    // West of UTC, the local day goes on after UTC has moved to the next one
+    let new_york = FixedClock::at_local(day(12), 23, 59, -5);
+    assert_eq!(new_york.today_local(), day(12));
+    assert_eq!(new_york.now_utc().date_naive(), day(13));
+}
+
+#[test]
+fn test_fixed_clock_advances_over_midnight() {
+    let mut clock = FixedClock::at_local(day(12), 23, 59, 1);
+    let before = clock.now_utc();
+    clock.advance(Duration::minutes(2));
+    assert_eq!(clock.now_utc() - before, Duration::minutes(2));
+    assert_eq!(clock.today_local(), day(13));
+    assert_eq!(clock, FixedClock::at_local(day(13), 0, 1, 1));
+}
+
+#[test]
+fn test_due_today_turns_overdue_after_local_midnight() {
+    let todos = due_on_the_12th();
+    let mut clock = FixedClock::at_local(day(12), 23, 59, 2);
+    assert_eq!(shown(&todos, QuickFilter::Today, &clock), vec![1]);
+    assert!(shown(&todos, QuickFilter::Overdue, &clock).is_empty());
+
+    clock.advance(Duration::minutes(2));
+    assert!(shown(&todos, QuickFilter::Today, &clock).is_empty());
+    assert_eq!(shown(&todos, QuickFilter::Overdue, &clock), vec![1]);
+}
+
+#[test]
+fn test_due_today_stays_today_until_local_midnight_west_of_utc() {
+    // At 23:59 in New York it is already the 13th in UTC, but not locally
+    let todos = due_on_the_12th();
+    let clock = FixedClock::at_local(day(12), 23, 59, -5);
+    assert_eq!(shown(&todos, QuickFilter::Today, &clock), vec![1]);
+    assert!(shown(&todos, QuickFilter::Overdue, &clock).is_empty());
+    assert_eq!(
+        dates::bucket(day(12), clock.today_local(), WeekStart::Monday),
+        DueBucket::Today
+    );
+}
+
+#[test]
+fn test_due_tomorrow_becomes_today_at_00_01() {
+    let clock = FixedClock::at_local(day(13), 0, 1, 9);
+    assert_eq!(
+        dates::bucket(day(13), clock.today_local(), WeekStart::Monday),
+        DueBucket::Today
+    );
+    assert_eq!(
+        dates::bucket(day(12), clock.today_local(), WeekStart::Monday),
+        DueBucket::Overdue
+    );
+    assert_eq!(
+        dates::upcoming_days(clock.today_local(), 1)[0].date,
+        day(13)
+    );
+}
+
+#[test]
+fn test_auto_archive_waits_for_the_full_number_of_days() {
+    let mut clock = FixedClock::at_local(day(12), 23, 59, 0);
+    let mut todo = Todo::new(1, "Book the venue".to_string());
+    todo.completed = true;
+    todo.completed_at = Some(clock.now_utc() - Duration::days(30) + Duration::minutes(1));
+    let list = TodoList::from_todos(vec![todo]);
+
+    assert!(select_auto_archivable(&list, clock.now_utc(), 30).is_empty());
+    clock.advance(Duration::minutes(2));
+    assert_eq!(select_auto_archivable(&list, clock.now_utc(), 30), vec![1]);
+}
@@ -0,0 +1,21 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use chrono::NaiveDate;
+use todo::utils::clock::FixedClock;
+
+/// A clock at the given local time on March 12, 2025, an hour ahead of UTC,
+/// fixed so the tests do not depend on the date.
+pub fn clock_at(hour: u32, minute: u32) -> FixedClock {
+    FixedClock::at_local(
+        NaiveDate::from_ymd_opt(2025, 3, 12).unwrap(),
+        hour,
+        minute,
+        1,
+    )
+}
+
+/// The moment most tests run at: the morning of March 12, 2025.
+pub fn clock() -> FixedClock {
+    clock_at(9, 30)
+}
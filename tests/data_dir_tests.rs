@@ -0,0 +1,83 @@
+use std::path::{Path, PathBuf};
+use todo::utils::data_dir::{DataDir, resolve_data_dir};
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+/// Makes an empty folder standing in for the one holding the executable.
+fn exe_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("todo-data-dir-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_data_dir_precedence() {
+    let exe = exe_dir("precedence");
+    std::fs::write(exe.join("portable.flag"), "").unwrap();
+    let everything = args(&["--portable", "--data-dir", "/from/arg"]);
+
+    assert_eq!(
+        resolve_data_dir(&everything, Some("/from/env"), Some(&exe)),
+        DataDir::Arg(PathBuf::from("/from/arg"))
+    );
+    assert_eq!(
+        resolve_data_dir(&args(&["--portable"]), Some("/from/env"), Some(&exe)),
+        DataDir::Env(PathBuf::from("/from/env"))
+    );
+    assert_eq!(
+        resolve_data_dir(&[], None, Some(&exe)),
+        DataDir::Portable(exe.clone())
+    );
+    assert_eq!(resolve_data_dir(&[], None, None), DataDir::Default);
+}
+
+#[test]
+fn test_portable_mode_needs_the_flag_file_or_argument() {
+    let exe = exe_dir("portable");
+    assert_eq!(resolve_data_dir(&[], None, Some(&exe)), DataDir::Default);
+    assert_eq!(
+        resolve_data_dir(&args(&["--portable"]), None, Some(&exe)),
+        DataDir::Portable(exe.clone())
+    );
+
+    // A folder named like the flag does not count
+    std::fs::create_dir(exe.join("portable.flag")).unwrap();
+    assert_eq!(resolve_data_dir(&[], None, Some(&exe)), DataDir::Default);
+
+    // Without the executable's folder there is nothing to be portable next to
+    assert_eq!(
+        resolve_data_dir(&args(&["--portable"]), None, None),
+        DataDir::Default
+    );
+}
+
+#[test]
+fn test_data_dir_argument_forms() {
+    assert_eq!(
+        resolve_data_dir(&args(&["--data-dir=/usb/todo"]), None, None),
+        DataDir::Arg(PathBuf::from("/usb/todo"))
+    );
+    assert_eq!(
+        resolve_data_dir(
+            &args(&["--profile", "work", "--data-dir", "/usb/todo"]),
+            None,
+            None
+        )
+        .path(),
+        Some(Path::new("/usb/todo"))
+    );
+
+    // Empty values are ignored rather than putting the data in the working
+    // folder
+    assert_eq!(
+        resolve_data_dir(&args(&["--data-dir", " "]), Some(""), None),
+        DataDir::Default
+    );
+    assert_eq!(
+        resolve_data_dir(&args(&["--data-dir"]), Some("/from/env"), None),
+        DataDir::Env(PathBuf::from("/from/env"))
+    );
+}
@@ -0,0 +1,345 @@
+use chrono::{Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday};
+use todo::models::{AppSettings, WeekStart};
+use todo::utils::dates::{
+    DueBucket, IsoWeek, add_months, bucket, clamp_date, due_date_on, is_weekend, month_grid,
+    start_of_week, upcoming_days, week_range, weekdays,
+};
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn test_week_range_across_year_boundary() {
+    // 2025-01-01 is a Wednesday
+    let new_year = date(2025, 1, 1);
+    let cases = [
+        (WeekStart::Monday, date(2024, 12, 30), date(2025, 1, 5)),
+        (WeekStart::Sunday, date(2024, 12, 29), date(2025, 1, 4)),
+        (WeekStart::Saturday, date(2024, 12, 28), date(2025, 1, 3)),
+    ];
+
+    for (week_start, first, last) in cases {
+        assert_eq!(
+            week_range(new_year, week_start),
+            (first, last),
+            "{:?}",
+            week_start
+        );
+        // Every day of the week maps to the same range
+        for offset in 0..7 {
+            let day = first + chrono::Duration::days(offset);
+            assert_eq!(
+                week_range(day, week_start),
+                (first, last),
+                "{:?} {}",
+                week_start,
+                day
+            );
+        }
+        assert_eq!(
+            week_range(last + chrono::Duration::days(1), week_start).0,
+            last + chrono::Duration::days(1)
+        );
+    }
+}
+
+#[test]
+fn test_week_range_ending_a_year() {
+    // 2026-12-31 is a Thursday; the week runs into 2027 for every start day
+    let last_day = date(2026, 12, 31);
+    assert_eq!(
+        week_range(last_day, WeekStart::Monday),
+        (date(2026, 12, 28), date(2027, 1, 3))
+    );
+    assert_eq!(
+        week_range(last_day, WeekStart::Sunday),
+        (date(2026, 12, 27), date(2027, 1, 2))
+    );
+    assert_eq!(
+        week_range(last_day, WeekStart::Saturday),
+        (date(2026, 12, 26), date(2027, 1, 1))
+    );
+}
+
+#[test]
+fn test_start_of_week_is_identity_on_start_day() {
+    for week_start in WeekStart::ALL {
+        let start_day = (0..7)
+            .map(|offset| date(2025, 6, 1) + chrono::Duration::days(offset))
+            .find(|day| day.weekday() == week_start.weekday())
+            .unwrap();
+        assert_eq!(start_of_week(start_day, week_start), start_day);
+    }
+}
+
+#[test]
+fn test_weekdays_order_and_weekends() {
+    assert_eq!(weekdays(WeekStart::Monday)[0], Weekday::Mon);
+    assert_eq!(weekdays(WeekStart::Sunday)[6], Weekday::Sat);
+    assert_eq!(
+        weekdays(WeekStart::Saturday),
+        [
+            Weekday::Sat,
+            Weekday::Sun,
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri
+        ]
+    );
+
+    assert!(is_weekend(Weekday::Sat));
+    assert!(is_weekend(Weekday::Sun));
+    assert!(!is_weekend(Weekday::Fri));
+}
+
+#[test]
+fn test_settings_parse_and_defaults() {
+    assert_eq!(WeekStart::parse("sunday"), Some(WeekStart::Sunday));
+    assert_eq!(WeekStart::parse("Someday"), None);
+
+    // Settings saved before an option existed load with its default
+    let settings: AppSettings = serde_json::from_str("{}").unwrap();
+    assert_eq!(settings.week_start, WeekStart::Monday);
+
+    let json = serde_json::to_string(&AppSettings {
+        week_start: WeekStart::Saturday,
+        ..AppSettings::default()
+    })
+    .unwrap();
+    assert!(json.contains(r#""week_start":"Saturday""#));
+}
+
+fn iso(year: i32, week: u32) -> IsoWeek {
+    IsoWeek { year, week }
+}
+
+#[test]
+fn test_iso_week_around_new_year() {
+    // (date, ISO week) pairs for every Dec 29 - Jan 3 shape
+    let cases = [
+        // 2025 starts on a Wednesday, so its week 1 begins in December
+        (date(2024, 12, 29), iso(2024, 52)),
+        (date(2024, 12, 30), iso(2025, 1)),
+        (date(2024, 12, 31), iso(2025, 1)),
+        (date(2025, 1, 1), iso(2025, 1)),
+        // 2021 starts on a Friday, so its first days belong to 2020-W53
+        (date(2020, 12, 31), iso(2020, 53)),
+        (date(2021, 1, 1), iso(2020, 53)),
+        (date(2021, 1, 3), iso(2020, 53)),
+        (date(2021, 1, 4), iso(2021, 1)),
+        // 2026 starts and ends on a Thursday, giving it 53 weeks
+        (date(2026, 12, 28), iso(2026, 53)),
+        (date(2026, 12, 31), iso(2026, 53)),
+        (date(2027, 1, 1), iso(2026, 53)),
+        (date(2027, 1, 3), iso(2026, 53)),
+        (date(2027, 1, 4), iso(2027, 1)),
+        // 2023 starts on a Sunday, which still belongs to 2022
+        (date(2023, 1, 1), iso(2022, 52)),
+        (date(2023, 1, 2), iso(2023, 1)),
+        // Leap year ending on a Tuesday
+        (date(2024, 2, 29), iso(2024, 9)),
+        (date(2024, 12, 31), iso(2025, 1)),
+    ];
+
+    for (day, expected) in cases {
+        assert_eq!(IsoWeek::of(day), expected, "week of {day}");
+        assert!(expected.contains(day));
+    }
+    assert_eq!(IsoWeek::of(date(2025, 3, 18)), iso(2025, 12));
+}
+
+#[test]
+fn test_iso_week_range_is_monday_to_sunday() {
+    assert_eq!(iso(2025, 1).range(), (date(2024, 12, 30), date(2025, 1, 5)));
+    assert_eq!(
+        iso(2020, 53).range(),
+        (date(2020, 12, 28), date(2021, 1, 3))
+    );
+    assert_eq!(
+        iso(2025, 12).range(),
+        (date(2025, 3, 17), date(2025, 3, 23))
+    );
+
+    let (monday, sunday) = iso(2026, 53).range();
+    assert_eq!(monday.weekday(), Weekday::Mon);
+    assert_eq!(sunday.weekday(), Weekday::Sun);
+    assert!(!iso(2026, 53).contains(monday - Duration::days(1)));
+    assert!(!iso(2026, 53).contains(sunday + Duration::days(1)));
+}
+
+#[test]
+fn test_iso_week_parse_and_display() {
+    assert_eq!(IsoWeek::parse("2025-W12"), Some(iso(2025, 12)));
+    assert_eq!(IsoWeek::parse("2025-w3"), Some(iso(2025, 3)));
+    assert_eq!(IsoWeek::parse("2020-W53"), Some(iso(2020, 53)));
+    assert_eq!(IsoWeek::parse("2026-W53"), Some(iso(2026, 53)));
+
+    for text in [
+        "",
+        "2025",
+        "2025W12",
+        "2025-12",
+        "2025-W",
+        "2025-W0",
+        "2025-W54",
+        "2025-W53",
+        "2025-W123",
+        "2025-W+1",
+        "-W12",
+        "twenty-W12",
+    ] {
+        assert_eq!(IsoWeek::parse(text), None, "{text:?} should not parse");
+    }
+
+    assert_eq!(iso(2025, 3).to_string(), "2025-W03");
+    assert_eq!(iso(2025, 3).label(), "W03");
+    assert_eq!(
+        IsoWeek::parse(&iso(2020, 53).to_string()),
+        Some(iso(2020, 53))
+    );
+}
+
+#[test]
+fn test_week_numbers_setting_defaults_off() {
+    let settings: AppSettings = serde_json::from_str("{}").unwrap();
+    assert!(!settings.show_week_numbers);
+}
+
+#[test]
+fn test_due_bucket() {
+    // Wednesday
+    let today = date(2025, 3, 12);
+    assert_eq!(
+        bucket(date(2025, 3, 11), today, WeekStart::Monday),
+        DueBucket::Overdue
+    );
+    assert_eq!(
+        bucket(date(2024, 12, 31), today, WeekStart::Monday),
+        DueBucket::Overdue
+    );
+    assert_eq!(bucket(today, today, WeekStart::Monday), DueBucket::Today);
+    assert_eq!(
+        bucket(date(2025, 3, 13), today, WeekStart::Monday),
+        DueBucket::ThisWeek
+    );
+    assert_eq!(
+        bucket(date(2025, 3, 16), today, WeekStart::Monday),
+        DueBucket::ThisWeek
+    );
+    assert_eq!(
+        bucket(date(2025, 3, 17), today, WeekStart::Monday),
+        DueBucket::Later
+    );
+
+    // The end of the week follows the week start setting
+    assert_eq!(
+        bucket(date(2025, 3, 15), today, WeekStart::Sunday),
+        DueBucket::ThisWeek
+    );
+    assert_eq!(
+        bucket(date(2025, 3, 16), today, WeekStart::Sunday),
+        DueBucket::Later
+    );
+    assert_eq!(
+        bucket(date(2025, 3, 14), today, WeekStart::Saturday),
+        DueBucket::ThisWeek
+    );
+    assert_eq!(
+        bucket(date(2025, 3, 15), today, WeekStart::Saturday),
+        DueBucket::Later
+    );
+
+    // On the last day of the week nothing later is in this week
+    let sunday = date(2025, 3, 16);
+    assert_eq!(bucket(sunday, sunday, WeekStart::Monday), DueBucket::Today);
+    assert_eq!(
+        bucket(date(2025, 3, 17), sunday, WeekStart::Monday),
+        DueBucket::Later
+    );
+}
+
+#[test]
+fn test_upcoming_days_across_month_boundary() {
+    let days = upcoming_days(date(2025, 2, 26), 7);
+    let dates: Vec<NaiveDate> = days.iter().map(|day| day.date).collect();
+    assert_eq!(dates.first(), Some(&date(2025, 2, 26)));
+    assert_eq!(dates.last(), Some(&date(2025, 3, 4)));
+    assert!(
+        dates
+            .windows(2)
+            .all(|pair| pair[1] == pair[0] + Duration::days(1))
+    );
+
+    let labels: Vec<String> = days
+        .iter()
+        .map(|day| format!("{} {}", day.weekday, day.label))
+        .collect();
+    assert_eq!(
+        labels,
+        [
+            "Wed Feb 26",
+            "Thu Feb 27",
+            "Fri Feb 28",
+            "Sat Mar 1",
+            "Sun Mar 2",
+            "Mon Mar 3",
+            "Tue Mar 4"
+        ]
+    );
+}
+
+#[test]
+fn test_upcoming_days_across_leap_day_and_year_end() {
+    let labels: Vec<String> = upcoming_days(date(2024, 2, 28), 3)
+        .into_iter()
+        .map(|day| day.label)
+        .collect();
+    assert_eq!(labels, ["Feb 28", "Feb 29", "Mar 1"]);
+
+    let days = upcoming_days(date(2025, 12, 30), 4);
+    assert_eq!(days[2].date, date(2026, 1, 1));
+    assert_eq!(days[2].label, "Jan 1");
+    assert_eq!(days[3].weekday, "Fri");
+    assert!(upcoming_days(date(2025, 12, 30), 0).is_empty());
+}
+
+#[test]
+fn test_due_date_on_is_midnight_utc() {
+    let due = due_date_on(date(2025, 3, 31));
+    assert_eq!(due, Utc.with_ymd_and_hms(2025, 3, 31, 0, 0, 0).unwrap());
+    assert_eq!(due.date_naive(), date(2025, 3, 31));
+}
+
+#[test]
+fn test_month_grid_covers_the_month_for_every_week_start() {
+    // February 2026 starts on a Sunday and fits four weeks exactly
+    let february = date(2026, 2, 10);
+    for week_start in WeekStart::ALL {
+        let grid = month_grid(february, week_start);
+        let days: Vec<NaiveDate> = grid.iter().flatten().copied().collect();
+        assert_eq!(days.len(), 42);
+        assert_eq!(days[0].weekday(), week_start.weekday());
+        assert!(
+            days.windows(2)
+                .all(|pair| pair[1] == pair[0] + Duration::days(1))
+        );
+        assert!(days.contains(&date(2026, 2, 1)));
+        assert!(days.contains(&date(2026, 2, 28)));
+    }
+}
+
+#[test]
+fn test_add_months_and_clamp_date() {
+    assert_eq!(add_months(date(2024, 2, 29), 12), date(2025, 2, 28));
+    assert_eq!(add_months(date(2025, 1, 15), -1), date(2024, 12, 15));
+    assert_eq!(add_months(date(2025, 1, 15), 0), date(2025, 1, 15));
+
+    let (min, max) = (Some(date(2025, 1, 1)), Some(date(2025, 6, 30)));
+    assert_eq!(clamp_date(date(2024, 12, 31), min, max), date(2025, 1, 1));
+    assert_eq!(clamp_date(date(2025, 7, 1), min, max), date(2025, 6, 30));
+    assert_eq!(clamp_date(date(2025, 3, 3), min, max), date(2025, 3, 3));
+    assert_eq!(clamp_date(date(1999, 1, 1), None, None), date(1999, 1, 1));
+}
@@ -0,0 +1,113 @@
+use chrono::{DateTime, Duration, Utc};
+use todo::models::TodoList;
+use todo::utils::debug_log::{self, DebugLogLayer, LogBuffer, LogEntry, RotatingFile};
+use todo::utils::storage::{MemoryStorage, save_with};
+use tracing::Level;
+use tracing_subscriber::layer::SubscriberExt;
+
+/// Builds an entry from the given module at the given time.
+fn entry_at(target: &str, message: &str, time: DateTime<Utc>) -> LogEntry {
+    LogEntry {
+        time,
+        level: Level::INFO,
+        target: target.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// Runs the closure with the debug log layer as the thread's subscriber.
+fn with_debug_log(run: impl FnOnce()) {
+    let subscriber = tracing_subscriber::registry().with(DebugLogLayer);
+    tracing::subscriber::with_default(subscriber, run);
+}
+
+#[test]
+fn test_log_buffer_keeps_latest_entries() {
+    let mut buffer = LogBuffer::new(3, 100);
+    let now = Utc::now();
+    for n in 1..=5 {
+        buffer.push(entry_at("todo::models", &format!("entry {n}"), now));
+    }
+
+    let messages: Vec<_> = buffer.entries().map(|e| e.message.as_str()).collect();
+    assert_eq!(messages, ["entry 3", "entry 4", "entry 5"]);
+    assert_eq!(buffer.to_text().lines().count(), 3);
+}
+
+#[test]
+fn test_log_buffer_limits_each_module_per_second() {
+    let mut buffer = LogBuffer::new(500, 2);
+    let second = DateTime::from_timestamp(1_750_000_000, 0).unwrap();
+    for _ in 0..5 {
+        buffer.push(entry_at("todo::models", "toggle", second));
+    }
+    // Another module still gets its share of the same second
+    assert_eq!(
+        buffer.push(entry_at("todo::utils::storage", "save", second)),
+        1
+    );
+    assert_eq!(buffer.len(), 3);
+
+    let next = second + Duration::seconds(1);
+    assert_eq!(buffer.push(entry_at("todo::models", "add", next)), 2);
+    let latest: Vec<_> = buffer.latest(2).collect();
+    assert_eq!(latest[0].level, Level::WARN);
+    assert!(latest[0].message.starts_with("3 entries dropped"));
+    assert_eq!(latest[1].message, "add");
+}
+
+#[test]
+fn test_rotating_file_keeps_given_number_of_files() {
+    let dir = std::env::temp_dir().join(format!("todo-debug-log-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let path = dir.join("todo.log");
+
+    let mut file = RotatingFile::open(&path, 64, 2).unwrap();
+    for n in 0..20 {
+        file.write_line(&format!("line {n:02} of the test log"))
+            .unwrap();
+    }
+
+    assert!(std::fs::metadata(&path).unwrap().len() <= 64);
+    assert!(file.rotated_path(1).exists());
+    assert!(file.rotated_path(2).exists());
+    assert!(!file.rotated_path(3).exists());
+    let newest = std::fs::read_to_string(&path).unwrap();
+    assert!(newest.ends_with("line 19 of the test log\n"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_model_mutations_are_logged_without_todo_text() {
+    with_debug_log(|| {
+        let mut list = TodoList::new();
+        let id = list.add("Buy a birthday gift for Alex".to_string());
+        list.set_notes(id, "Ask Sam about the size".to_string());
+    });
+
+    let text = debug_log::export_text();
+    assert!(text.contains("add len=28 id=1 took="));
+    assert!(text.contains("set_notes id=1 len=22 took="));
+    assert!(!text.contains("birthday"));
+    assert!(!text.contains("Sam"));
+}
+
+#[test]
+fn test_storage_operations_are_logged_with_key_size_and_result() {
+    let storage = MemoryStorage::new();
+    with_debug_log(|| {
+        save_with(&storage, "todo/v1/debug-log-test", &vec![1, 2, 3]).unwrap();
+    });
+
+    let entries = debug_log::entries();
+    let save = entries
+        .iter()
+        .find(|entry| entry.message.contains("key=todo/v1/debug-log-test"))
+        .expect("the save is logged");
+    assert_eq!(save.target, "todo::utils::storage");
+    assert!(
+        save.message
+            .starts_with("save key=todo/v1/debug-log-test bytes=7 result=ok took=")
+    );
+}
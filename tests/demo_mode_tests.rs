@@ -0,0 +1,49 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use todo::models::TodoList;
+use todo::utils::constants::demo::DEMO_MODE_ENV;
+use todo::utils::constants::storage::STORAGE_BACKEND_ENV;
+use todo::utils::demo::{self, parse_flag, query_has_flag};
+use todo::utils::storage::{MemoryStorage, RawStorage, StorageBackend, open_storage};
+use todo::utils::{load, save};
+
+#[test]
+fn test_demo_mode_never_writes_to_the_native_backend() {
+    // Demo mode and the provider are cached process-wide, so set both before first use.
+    // SAFETY: this is the only test in this binary touching the env or the storage.
+    unsafe {
+        std::env::set_var(DEMO_MODE_ENV, "1");
+        std::env::set_var(STORAGE_BACKEND_ENV, "native");
+    }
+    assert!(demo::is_demo_mode());
+    assert_eq!(StorageBackend::detect(), StorageBackend::Memory);
+
+    let key = "demo-mode-test-key";
+    let mut list = TodoList::new();
+    list.add("Demo todo".to_string());
+    save(key, &list).unwrap();
+
+    let loaded: TodoList = load(key).unwrap();
+    assert_eq!(loaded, list);
+    assert!(MemoryStorage::shared().load_str(key).is_ok());
+
+    // The native backend is either unavailable in this build or has no trace of the save
+    if let Ok(native) = open_storage(StorageBackend::Native) {
+        assert!(native.load_str(key).is_err());
+    }
+}
+
+#[test]
+fn test_demo_flag_parsing() {
+    assert!(parse_flag("1"));
+    assert!(parse_flag(" TRUE "));
+    assert!(!parse_flag("0"));
+    assert!(!parse_flag(""));
+
+    assert!(query_has_flag("?demo=1", "demo"));
+    assert!(query_has_flag("?lang=en&demo=yes", "demo"));
+    assert!(query_has_flag("?demo", "demo"));
+    assert!(!query_has_flag("?demo=0", "demo"));
+    assert!(!query_has_flag("?demolition=1", "demo"));
+    assert!(!query_has_flag("", "demo"));
+}
@@ -0,0 +1,117 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use todo::models::AppSettings;
+use todo::models::Todo;
+use todo::models::digest::{DigestSummary, is_due, next_fire, summarize};
+use todo::utils::clock::{Clock, FixedClock};
+use todo::utils::dates;
+
+/// A day in March 2025; the 7th is a Friday.
+fn day(d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, d).unwrap()
+}
+
+fn at(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+}
+
+fn time(d: u32, hour: u32, minute: u32) -> NaiveDateTime {
+    day(d).and_time(at(hour, minute))
+}
+
+#[test]
+fn test_next_fire_is_later_today_before_the_digest_time() {
+    let next = next_fire(time(11, 7, 0), at(8, 30), Some(day(10)), false);
+    assert_eq!(next, time(11, 8, 30));
+    assert!(!is_due(time(11, 7, 0), at(8, 30), Some(day(10)), false));
+    assert!(is_due(time(11, 8, 30), at(8, 30), Some(day(10)), false));
+}
+
+#[test]
+fn test_launch_after_the_digest_time_fires_once() {
+    // Started at 09:00 without a digest yet today: it is due now
+    assert!(is_due(time(11, 9, 0), at(8, 30), Some(day(10)), false));
+    assert!(is_due(time(11, 9, 0), at(8, 30), None, false));
+
+    // Started again at 09:05 after it fired: not until tomorrow
+    assert!(!is_due(time(11, 9, 5), at(8, 30), Some(day(11)), false));
+    assert_eq!(
+        next_fire(time(11, 9, 5), at(8, 30), Some(day(11)), false),
+        time(12, 8, 30)
+    );
+}
+
+#[test]
+fn test_next_fire_skips_weekends_when_asked() {
+    // Fired on Friday: the next one is on Monday
+    assert_eq!(
+        next_fire(time(7, 9, 0), at(8, 30), Some(day(7)), true),
+        time(10, 8, 30)
+    );
+    assert_eq!(
+        next_fire(time(7, 9, 0), at(8, 30), Some(day(7)), false),
+        time(8, 8, 30)
+    );
+
+    // Waking the computer on Saturday does not fire
+    assert!(!is_due(time(8, 12, 0), at(8, 30), Some(day(7)), true));
+    assert!(is_due(time(8, 12, 0), at(8, 30), Some(day(7)), false));
+}
+
+#[test]
+fn test_wake_after_a_missed_digest_fires_at_once() {
+    // Asleep over the digest time: the next check after waking finds it due
+    let mut clock = FixedClock::at_local(day(11), 8, 0, 1);
+    assert!(!is_due(clock.now_local(), at(8, 30), Some(day(10)), false));
+    clock.advance(Duration::hours(3));
+    assert!(is_due(clock.now_local(), at(8, 30), Some(day(10)), false));
+
+    // A clock turned back to before the last digest does not fire again
+    assert!(!is_due(time(10, 23, 0), at(8, 30), Some(day(11)), false));
+}
+
+#[test]
+fn test_summarize_counts_open_todos_overdue_and_due_today() {
+    let today = day(11);
+    let due = |id: usize, date: NaiveDate| {
+        let mut todo = Todo::new(id, format!("Todo {id}"));
+        todo.due_date = Some(dates::due_date_on(date));
+        todo
+    };
+    let mut done = due(4, day(9));
+    done.completed = true;
+    let todos = vec![
+        due(1, day(9)),
+        due(2, day(10)),
+        due(3, today),
+        done,
+        due(5, day(12)),
+        Todo::new(6, "No due date".to_string()),
+    ];
+
+    let summary = summarize(&todos, today);
+    assert_eq!(
+        summary,
+        DigestSummary {
+            overdue: 2,
+            due_today: 1
+        }
+    );
+    assert_eq!(summary.message(), "2 overdue, 1 due today");
+    assert!(summarize(&todos[4..], today).is_empty());
+}
+
+#[test]
+fn test_digest_settings_default_to_half_past_eight() {
+    let settings: AppSettings = serde_json::from_str("{}").unwrap();
+    assert!(settings.daily_digest);
+    assert_eq!(settings.digest_time, at(8, 30));
+    assert!(!settings.digest_skip_weekends);
+
+    let json = serde_json::to_string(&AppSettings {
+        digest_time: at(7, 15),
+        ..settings
+    })
+    .unwrap();
+    let loaded: AppSettings = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.digest_time, at(7, 15));
+}
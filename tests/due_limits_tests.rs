@@ -0,0 +1,94 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use todo::models::due_limits::{DueDateError, DueDateLimits};
+use todo::models::{TodoError, TodoList};
+use todo::utils::dates;
+
+fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+/// The day the tests run on, fixed so they do not depend on the date.
+fn today() -> NaiveDate {
+    day(2025, 3, 12)
+}
+
+#[test]
+fn test_earliest_boundary() {
+    let limits = DueDateLimits::default();
+
+    assert!(limits.check_day(day(1970, 1, 1), today()).is_ok());
+    assert_eq!(
+        limits.check_day(day(1969, 12, 31), today()),
+        Err(DueDateError::TooEarly {
+            due: day(1969, 12, 31),
+            earliest: day(1970, 1, 1),
+        })
+    );
+}
+
+#[test]
+fn test_latest_boundary() {
+    let limits = DueDateLimits::default();
+    assert_eq!(limits.latest(today()), day(2075, 3, 12));
+
+    assert!(limits.check_day(day(2075, 3, 12), today()).is_ok());
+    assert_eq!(
+        limits.check_day(day(2075, 3, 13), today()),
+        Err(DueDateError::TooLate {
+            due: day(2075, 3, 13),
+            latest: day(2075, 3, 12),
+        })
+    );
+    // A leap day with no match fifty years on ends the window on the 28th
+    assert_eq!(limits.latest(day(2024, 2, 29)), day(2074, 2, 28));
+}
+
+#[test]
+fn test_clearing_is_always_allowed() {
+    let now = Utc.with_ymd_and_hms(2025, 3, 12, 9, 30, 0).unwrap();
+    let none = DueDateLimits {
+        earliest: day(2030, 1, 1),
+        years_ahead: 0,
+    };
+    assert!(none.check(None, now).is_ok());
+
+    let mut list = TodoList::new();
+    let id = list.add("Pay rent".to_string());
+    list.set_due_date(id, Some(Utc::now())).unwrap();
+    list.set_due_date_within(id, None, none, now).unwrap();
+    assert_eq!(list.get(id).unwrap().due_date, None);
+}
+
+#[test]
+fn test_setting_a_due_date_out_of_range_changes_nothing() {
+    let now = Utc.with_ymd_and_hms(2025, 3, 12, 9, 30, 0).unwrap();
+    let limits = DueDateLimits {
+        earliest: day(2025, 1, 1),
+        years_ahead: 1,
+    };
+    let mut list = TodoList::new();
+    let id = list.add("Pay rent".to_string());
+    let due = dates::due_date_on(day(2025, 4, 1));
+    list.set_due_date_within(id, Some(due), limits, now)
+        .unwrap();
+
+    let too_late = dates::due_date_on(day(2026, 3, 13));
+    assert_eq!(
+        list.set_due_date_within(id, Some(too_late), limits, now),
+        Err(TodoError::InvalidDueDate(
+            id,
+            DueDateError::TooLate {
+                due: day(2026, 3, 13),
+                latest: day(2026, 3, 12),
+            }
+        ))
+    );
+    let typo = dates::due_date_on(day(200, 4, 1));
+    assert!(list.set_due_date(id, Some(typo)).is_err());
+    assert_eq!(list.get(id).unwrap().due_date, Some(due));
+
+    assert_eq!(
+        list.set_due_date_within(99, Some(too_late), limits, now),
+        Err(TodoError::NotFound(99))
+    );
+}
@@ -0,0 +1,102 @@
+use chrono::{TimeZone, Utc};
+use todo::models::TodoList;
+use todo::models::duplicates::{Merge, find_duplicates};
+
+fn ids(list: &TodoList) -> Vec<usize> {
+    list.all().iter().map(|todo| todo.id).collect()
+}
+
+#[test]
+fn test_open_todos_are_grouped_by_normalized_text() {
+    let mut list = TodoList::new();
+    let domain = list.add("Renew domain".to_string());
+    let milk = list.add("Buy milk".to_string());
+    let done = list.add("Renew domain".to_string());
+    list.toggle(done);
+    let archived = list.add("buy milk".to_string());
+    list.toggle(archived);
+    list.archive(archived);
+    let repeat = list.add("  renew   DOMAIN ".to_string());
+    list.add("Renew domains".to_string());
+
+    let groups = find_duplicates(&list);
+    assert_eq!(groups.len(), 1);
+    let group_ids: Vec<usize> = groups[0].todos.iter().map(|todo| todo.id).collect();
+    assert_eq!(group_ids, [domain, repeat]);
+    assert!(!groups[0].differs());
+
+    // Completed and archived todos do not count, so milk repeats nothing
+    assert!(groups.iter().all(|group| group.todos[0].id != milk));
+}
+
+#[test]
+fn test_merge_unions_tags_and_keeps_the_earliest_due_date() {
+    let early = Utc.with_ymd_and_hms(2025, 3, 12, 0, 0, 0).unwrap();
+    let late = Utc.with_ymd_and_hms(2025, 4, 1, 0, 0, 0).unwrap();
+    let mut list = TodoList::new();
+    let first = list.add("Renew domain".to_string());
+    list.add_tag(first, "Work".to_string());
+    list.set_due_date(first, Some(late)).unwrap();
+    let second = list.add("Renew domain".to_string());
+    list.add_tag(second, "Admin".to_string());
+    list.add_tag(second, "Work".to_string());
+    let third = list.add("Renew domain".to_string());
+    list.set_due_date(third, Some(early)).unwrap();
+
+    let group = &find_duplicates(&list)[0];
+    assert!(group.differs());
+    assert_eq!(
+        group.merge(),
+        Merge {
+            keep: first,
+            tags: vec!["Work".to_string(), "Admin".to_string()],
+            due_date: Some(early),
+            remove: vec![second, third],
+        }
+    );
+}
+
+#[test]
+fn test_a_locked_todo_is_the_one_kept() {
+    let mut list = TodoList::new();
+    let first = list.add("Renew domain".to_string());
+    let locked = list.add("Renew domain".to_string());
+    list.set_locked(locked, true);
+
+    let merge = find_duplicates(&list)[0].merge();
+    assert_eq!(merge.keep, locked);
+    assert_eq!(merge.remove, [first]);
+}
+
+#[test]
+fn test_applying_a_merge_trashes_the_rest() {
+    let due = Utc.with_ymd_and_hms(2025, 3, 12, 0, 0, 0).unwrap();
+    let mut list = TodoList::new();
+    let first = list.add("Renew domain".to_string());
+    let second = list.add("renew domain".to_string());
+    list.add_tag(second, "Work".to_string());
+    list.set_due_date(second, Some(due)).unwrap();
+    let locked = list.add("Renew domain".to_string());
+    list.set_locked(locked, true);
+    let other = list.add("Buy milk".to_string());
+
+    let merge = Merge {
+        keep: first,
+        tags: vec!["Work".to_string()],
+        due_date: Some(due),
+        remove: vec![second, locked],
+    };
+    // Locked todos cannot be deleted, so they stay
+    assert_eq!(merge.apply(&mut list), 1);
+    assert_eq!(ids(&list), [first, locked, other]);
+    let kept = list.get(first).unwrap();
+    assert_eq!(kept.tags, ["Work"]);
+    assert_eq!(kept.due_date, Some(due));
+    assert_eq!(list.trash_count(), 1);
+    list.check_invariants().unwrap();
+
+    // A kept todo that is gone leaves the list alone
+    list.move_to_trash(first).unwrap();
+    assert_eq!(merge.apply(&mut list), 0);
+    assert_eq!(list.trash_count(), 2);
+}
@@ -0,0 +1,77 @@
+use todo::models::AppSettings;
+use todo::utils::emoji::{PICKER, SHORTCODES, expand, insert_at_cursor, lookup};
+
+#[test]
+fn test_expand_single_shortcode() {
+    assert_eq!(expand(":rocket:"), "🚀");
+    assert_eq!(expand("Ship it :rocket: today"), "Ship it 🚀 today");
+    assert_eq!(expand("no shortcodes here"), "no shortcodes here");
+    assert_eq!(expand(""), "");
+}
+
+#[test]
+fn test_expand_adjacent_shortcodes() {
+    assert_eq!(expand(":fire::rocket:"), "🔥🚀");
+    assert_eq!(expand(":+1::-1:"), "👍👎");
+    assert_eq!(expand("a:tada:b"), "a🎉b");
+}
+
+#[test]
+fn test_expand_leaves_unknown_names_untouched() {
+    assert_eq!(expand(":not_an_emoji:"), ":not_an_emoji:");
+    assert_eq!(expand(":unknown::rocket:"), ":unknown:🚀");
+    assert_eq!(
+        expand("Meet at 10:30: bring :coffee:"),
+        "Meet at 10:30: bring ☕"
+    );
+    assert_eq!(expand("time 12:00"), "time 12:00");
+    assert_eq!(expand(": rocket :"), ": rocket :");
+    assert_eq!(expand("::"), "::");
+    assert_eq!(expand(":rocket"), ":rocket");
+}
+
+#[test]
+fn test_expand_escaped_colon() {
+    assert_eq!(expand("\\:rocket:"), ":rocket:");
+    assert_eq!(expand("\\:rocket::fire:"), ":rocket:🔥");
+    assert_eq!(expand("C:\\Users\\me"), "C:\\Users\\me");
+    assert_eq!(expand("trailing \\"), "trailing \\");
+}
+
+#[test]
+fn test_shortcode_table() {
+    assert!(
+        SHORTCODES.windows(2).all(|pair| pair[0].0 < pair[1].0),
+        "shortcodes must stay sorted for lookup"
+    );
+    assert!(PICKER.iter().all(|name| lookup(name).is_some()));
+    assert_eq!(lookup("tada"), Some("🎉"));
+    assert_eq!(lookup("Tada"), None);
+}
+
+#[test]
+fn test_insert_at_cursor() {
+    assert_eq!(
+        insert_at_cursor("ab", Some(1), "🚀"),
+        ("a🚀b".to_string(), 3)
+    );
+    assert_eq!(insert_at_cursor("ab", None, "🚀"), ("ab🚀".to_string(), 4));
+    assert_eq!(
+        insert_at_cursor("ab", Some(99), "!"),
+        ("ab!".to_string(), 3)
+    );
+    assert_eq!(insert_at_cursor("", Some(0), "!"), ("!".to_string(), 1));
+
+    // The cursor counts UTF-16 code units: 🎉 is two of them
+    assert_eq!(
+        insert_at_cursor("🎉é!", Some(3), "x"),
+        ("🎉éx!".to_string(), 4)
+    );
+}
+
+#[test]
+fn test_expand_emoji_setting_defaults_on() {
+    assert!(AppSettings::default().expand_emoji);
+    let settings: AppSettings = serde_json::from_str(r#"{"expand_emoji":false}"#).unwrap();
+    assert!(!settings.expand_emoji);
+}
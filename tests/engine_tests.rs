@@ -0,0 +1,50 @@
+use todo::engine::{MemoryStorage, RawStorage, SaveStatus, StorageError, Store, keys};
+
+#[test]
+fn test_store_starts_empty_without_saved_data() {
+    let store = Store::load(Box::new(MemoryStorage::new())).unwrap();
+    assert_eq!(store.list().total_count(), 0);
+    assert_eq!(store.status(), SaveStatus::Saved);
+}
+
+#[test]
+fn test_store_mutate_flush_and_reload() {
+    let storage = MemoryStorage::new();
+    let mut store = Store::load(Box::new(storage.clone())).unwrap();
+
+    let id = store.mutate(|list| list.add("Buy milk".to_string()));
+    store.mutate(|list| list.add_tag(id, "Shopping".to_string()));
+    assert_eq!(store.status(), SaveStatus::Unsaved);
+    // Nothing is written before the flush
+    assert!(storage.load_str(keys::TODOS).is_err());
+
+    store.flush().unwrap();
+    assert_eq!(store.status(), SaveStatus::Saved);
+
+    let reloaded = Store::load(Box::new(storage)).unwrap();
+    assert_eq!(reloaded.list(), store.list());
+    assert_eq!(reloaded.into_list().all()[0].tags, vec!["Shopping"]);
+}
+
+#[test]
+fn test_store_flush_without_changes_writes_nothing() {
+    let storage = MemoryStorage::new();
+    let mut store = Store::load(Box::new(storage.clone())).unwrap();
+    store.flush().unwrap();
+    assert!(storage.list_keys().unwrap().is_empty());
+}
+
+#[test]
+fn test_store_custom_key_and_unreadable_data() {
+    let storage = MemoryStorage::new();
+    let mut store = Store::load_from(Box::new(storage.clone()), "work").unwrap();
+    store.mutate(|list| list.add("Ship it".to_string()));
+    store.flush().unwrap();
+    assert_eq!(storage.list_keys().unwrap(), vec!["work"]);
+
+    storage.save_str(keys::TODOS, "{not json").unwrap();
+    assert!(matches!(
+        Store::load(Box::new(storage)),
+        Err(StorageError::DeserializeError(_))
+    ));
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use common::clock;
+use todo::models::escalation::{Escalation, plan_escalations};
+use todo::models::{AppSettings, Todo, TodoList};
+use todo::utils::clock::Clock;
+use todo::utils::dates;
+
+/// Both rules on, reviewing undated todos after a week.
+fn settings() -> AppSettings {
+    AppSettings {
+        escalate_overdue: true,
+        review_undated_after_days: Some(7),
+        ..AppSettings::default()
+    }
+}
+
+/// Builds a todo added the given number of days ago, due the given number
+/// of days from today, or without a due date for `None`.
+fn todo(id: usize, added_days_ago: i64, due_in_days: Option<i64>, now: DateTime<Utc>) -> Todo {
+    let mut todo = Todo::new(id, format!("Todo {id}"));
+    todo.created_at = Some(now - Duration::days(added_days_ago));
+    todo.due_date =
+        due_in_days.map(|days| dates::due_date_on(clock().today_local() + Duration::days(days)));
+    todo
+}
+
+fn plan(list: &TodoList, settings: &AppSettings) -> Vec<Escalation> {
+    let clock = clock();
+    plan_escalations(list, settings, clock.now_utc(), clock.today_local())
+}
+
+#[test]
+fn test_overdue_open_todos_are_escalated() {
+    let now = clock().now_utc();
+    let list = TodoList::from_todos(vec![
+        todo(1, 1, Some(-1), now),
+        todo(2, 1, Some(0), now),
+        todo(3, 1, Some(3), now),
+        todo(4, 30, Some(-20), now),
+    ]);
+
+    assert_eq!(
+        plan(&list, &settings()),
+        [Escalation::Urgent(1), Escalation::Urgent(4)]
+    );
+}
+
+#[test]
+fn test_undated_todos_are_reviewed_after_the_configured_days() {
+    let now = clock().now_utc();
+    let mut legacy = todo(4, 0, None, now);
+    legacy.created_at = None;
+    let list = TodoList::from_todos(vec![
+        todo(1, 7, None, now),
+        todo(2, 6, None, now),
+        todo(3, 40, Some(5), now),
+        legacy,
+    ]);
+
+    assert_eq!(plan(&list, &settings()), [Escalation::Review(1)]);
+    let sooner = AppSettings {
+        review_undated_after_days: Some(1),
+        ..settings()
+    };
+    assert_eq!(
+        plan(&list, &sooner),
+        [Escalation::Review(1), Escalation::Review(2)]
+    );
+}
+
+#[test]
+fn test_each_rule_has_its_own_setting() {
+    let now = clock().now_utc();
+    let list = TodoList::from_todos(vec![todo(1, 30, Some(-1), now), todo(2, 30, None, now)]);
+
+    assert!(plan(&list, &AppSettings::default()).is_empty());
+    let overdue_only = AppSettings {
+        review_undated_after_days: None,
+        ..settings()
+    };
+    assert_eq!(plan(&list, &overdue_only), [Escalation::Urgent(1)]);
+    let review_only = AppSettings {
+        escalate_overdue: false,
+        ..settings()
+    };
+    assert_eq!(plan(&list, &review_only), [Escalation::Review(2)]);
+}
+
+#[test]
+fn test_completed_archived_and_locked_todos_are_left_alone() {
+    let now = clock().now_utc();
+    let mut done = todo(1, 30, Some(-1), now);
+    done.completed = true;
+    let mut archived = todo(2, 30, None, now);
+    archived.archived = true;
+    let mut locked = todo(3, 30, Some(-1), now);
+    locked.locked = true;
+    let mut tagged = todo(4, 30, None, now);
+    tagged.tags = vec!["Review".to_string()];
+    let mut urgent = todo(5, 30, Some(-1), now);
+    urgent.tags = vec!["urgent".to_string()];
+    let list = TodoList::from_todos(vec![done, archived, locked, tagged, urgent]);
+
+    assert!(plan(&list, &settings()).is_empty());
+}
+
+#[test]
+fn test_escalation_happens_once() {
+    let now = clock().now_utc();
+    let mut list = TodoList::from_todos(vec![todo(1, 30, Some(-1), now), todo(2, 30, None, now)]);
+
+    for escalation in plan(&list, &settings()) {
+        assert!(escalation.apply(&mut list));
+    }
+    let overdue = list.get(1).unwrap();
+    assert_eq!(overdue.tags, ["Urgent"]);
+    assert!(overdue.escalated);
+    assert_eq!(list.get(2).unwrap().tags, ["review"]);
+    assert!(plan(&list, &settings()).is_empty());
+
+    // Lowering the priority again sticks
+    list.remove_tag(1, "Urgent");
+    assert!(plan(&list, &settings()).is_empty());
+    assert!(!Escalation::Urgent(1).apply(&mut list));
+    assert!(!Escalation::Review(2).apply(&mut list));
+    assert!(!Escalation::Review(999).apply(&mut list));
+}
+
+#[test]
+fn test_escalated_flag_is_saved_only_when_set() {
+    let now = clock().now_utc();
+    let mut list = TodoList::from_todos(vec![todo(1, 30, Some(-1), now), todo(2, 1, None, now)]);
+    list.escalate(1);
+
+    let json = serde_json::to_string(&list).unwrap();
+    assert_eq!(json.matches(r#""escalated":true"#).count(), 1);
+    assert!(!json.contains(r#""escalated":false"#));
+    let restored: TodoList = serde_json::from_str(&json).unwrap();
+    assert!(restored.get(1).unwrap().escalated);
+}
+
+#[test]
+fn test_rules_settings_default_to_off() {
+    let settings: AppSettings = serde_json::from_str("{}").unwrap();
+    assert!(!settings.escalate_overdue);
+    assert_eq!(settings.review_undated_after_days, None);
+}
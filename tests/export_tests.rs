@@ -0,0 +1,278 @@
+use chrono::NaiveDate;
+use todo::models::{FilterState, ListView, TagFilter, TodoList};
+use todo::utils::clock::{Clock, SystemClock};
+use todo::utils::constants::export::ENCRYPTED_MAGIC;
+use todo::utils::constants::todo::MAX_TODO_TEXT_LENGTH;
+use todo::utils::export::{
+    ExportError, export_encrypted, export_encrypted_subset, export_file_name, export_json,
+    export_json_subset, import_encrypted, import_json, is_encrypted, without_text_history,
+};
+
+fn sample_list() -> TodoList {
+    let mut list = TodoList::new();
+    let id = list.add("Buy milk".to_string());
+    list.add("Write report".to_string());
+    list.add_tag(id, "Shopping".to_string());
+    list.toggle(id);
+    list
+}
+
+#[test]
+fn test_json_export_round_trip() {
+    let list = sample_list();
+    let json = export_json(&list).unwrap();
+    let imported = import_json(&json).unwrap();
+
+    assert_eq!(imported.all(), list.all());
+}
+
+#[test]
+fn test_json_import_rejects_garbage() {
+    assert!(matches!(
+        import_json("not a backup"),
+        Err(ExportError::CorruptFile(_))
+    ));
+}
+
+#[test]
+fn test_encrypted_export_round_trip() {
+    let list = sample_list();
+    let bundle = export_encrypted(&list, "correct horse").unwrap();
+
+    assert!(is_encrypted(&bundle));
+    assert!(bundle.starts_with(ENCRYPTED_MAGIC));
+    assert_eq!(bundle[ENCRYPTED_MAGIC.len()], 1);
+
+    let imported = import_encrypted(&bundle, "correct horse").unwrap();
+    assert_eq!(imported.all(), list.all());
+}
+
+#[test]
+fn test_encrypted_import_wrong_passphrase() {
+    let bundle = export_encrypted(&sample_list(), "correct horse").unwrap();
+    assert_eq!(
+        import_encrypted(&bundle, "battery staple").unwrap_err(),
+        ExportError::WrongPassphrase
+    );
+}
+
+#[test]
+fn test_encrypted_import_corrupt_file() {
+    let mut bundle = export_encrypted(&sample_list(), "correct horse").unwrap();
+
+    // Flip a byte in the ciphertext: the password is right, the data is not
+    let last = bundle.len() - 1;
+    bundle[last] ^= 0xff;
+    assert!(matches!(
+        import_encrypted(&bundle, "correct horse"),
+        Err(ExportError::CorruptFile(_))
+    ));
+
+    // Truncated right after the header
+    assert!(matches!(
+        import_encrypted(&bundle[..ENCRYPTED_MAGIC.len() + 4], "correct horse"),
+        Err(ExportError::CorruptFile(_))
+    ));
+}
+
+#[test]
+fn test_encrypted_import_unknown_version() {
+    let mut bundle = export_encrypted(&sample_list(), "correct horse").unwrap();
+    bundle[ENCRYPTED_MAGIC.len()] = 99;
+    assert_eq!(
+        import_encrypted(&bundle, "correct horse").unwrap_err(),
+        ExportError::UnsupportedVersion(99)
+    );
+}
+
+/// Builds a list whose display order differs from both id and insertion order.
+fn rearranged_list() -> TodoList {
+    let mut list = TodoList::new();
+    let ids: Vec<usize> = (1..=8).map(|i| list.add(format!("Todo {}", i))).collect();
+    list.reorder(ids[7], ids[0]);
+    list.reorder(ids[2], ids[5]);
+    list.remove(ids[4]).unwrap();
+    list.reorder(ids[1], ids[6]);
+    list
+}
+
+#[test]
+fn test_json_export_lists_todos_in_display_order() {
+    let list = rearranged_list();
+    let json = export_json(&list).unwrap();
+    let document: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    let exported: Vec<(u64, u64)> = document["todos"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|todo| {
+            (
+                todo["id"].as_u64().unwrap(),
+                todo["order"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    let expected: Vec<(u64, u64)> = list
+        .all()
+        .iter()
+        .map(|todo| (todo.id as u64, todo.order as u64))
+        .collect();
+    assert_eq!(exported, expected);
+}
+
+#[test]
+fn test_export_clear_import_restores_arrangement() {
+    let mut list = rearranged_list();
+    let expected = list.all();
+    let json = export_json(&list).unwrap();
+    let bundle = export_encrypted(&list, "pass").unwrap();
+
+    for todo in list.all() {
+        list.remove(todo.id).unwrap();
+    }
+    assert!(list.all().is_empty());
+
+    assert_eq!(import_json(&json).unwrap().all(), expected);
+    assert_eq!(import_encrypted(&bundle, "pass").unwrap().all(), expected);
+}
+
+#[test]
+fn test_import_orders_by_order_field_not_file_position() {
+    let json = r#"{"version":1,"exported_at":"2025-01-01T00:00:00Z","todos":[
+        {"id":1,"text":"Third","completed":false,"due_date":null,"tags":[],"order":3},
+        {"id":2,"text":"First","completed":false,"due_date":null,"tags":[],"order":1},
+        {"id":3,"text":"Second","completed":false,"due_date":null,"tags":[],"order":2}
+    ]}"#;
+    let texts: Vec<String> = import_json(json)
+        .unwrap()
+        .all()
+        .into_iter()
+        .map(|todo| todo.text)
+        .collect();
+    assert_eq!(texts, vec!["First", "Second", "Third"]);
+}
+
+#[test]
+fn test_import_without_order_field_falls_back_to_id_order() {
+    let json = r#"{"version":1,"exported_at":"2025-01-01T00:00:00Z","todos":[
+        {"id":2,"text":"Second","completed":false,"due_date":null,"tags":[]},
+        {"id":1,"text":"First","completed":false,"due_date":null,"tags":[]}
+    ]}"#;
+    let list = import_json(json).unwrap();
+    let orders: Vec<(usize, usize)> = list.all().iter().map(|t| (t.id, t.order)).collect();
+    assert_eq!(orders, vec![(1, 1), (2, 2)]);
+    assert!(list.check_invariants().is_ok());
+}
+
+#[test]
+fn test_import_trims_todo_text() {
+    let json = r#"{"version":1,"exported_at":"2025-01-01T00:00:00Z","todos":[
+        {"id":1,"text":"  Buy milk  ","completed":false,"due_date":null,"tags":[]}
+    ]}"#;
+    let list = import_json(json).unwrap();
+    assert_eq!(list.all()[0].text, "Buy milk");
+}
+
+#[test]
+fn test_import_reports_invalid_text_per_todo() {
+    let long_text = "x".repeat(MAX_TODO_TEXT_LENGTH + 1);
+    let json = format!(
+        r#"{{"version":1,"exported_at":"2025-01-01T00:00:00Z","todos":[
+            {{"id":1,"text":"Fine","completed":false,"due_date":null,"tags":[]}},
+            {{"id":2,"text":"   ","completed":false,"due_date":null,"tags":[]}},
+            {{"id":3,"text":"{long_text}","completed":false,"due_date":null,"tags":[]}}
+        ]}}"#
+    );
+
+    let Err(ExportError::InvalidTodos(problems)) = import_json(&json) else {
+        panic!("expected the invalid todos to be reported");
+    };
+    assert_eq!(problems.len(), 2);
+    assert!(problems[0].starts_with("Todo 2 (id 2)"));
+    assert!(problems[0].contains("empty"));
+    assert!(problems[1].starts_with("Todo 3 (id 3)"));
+    assert!(problems[1].contains(&format!("{}", MAX_TODO_TEXT_LENGTH + 1)));
+}
+
+#[test]
+fn test_subset_export_leaves_out_hidden_todos() {
+    let mut list = sample_list();
+    let report = list.add("Send invoice".to_string());
+    list.add_tag(report, "Work".to_string());
+
+    // The active Work todos are what the user is looking at
+    let view = ListView {
+        filter: FilterState::Active,
+        tag: TagFilter::Tag("Work".to_string()),
+        ..ListView::default()
+    };
+    let todos = list.all();
+    let clock = SystemClock;
+    let visible = view.visible_ids(&todos, clock.now_utc(), clock.today_local());
+    assert_eq!(visible, vec![report]);
+
+    let imported = import_json(&export_json_subset(&list, &visible).unwrap()).unwrap();
+    let texts: Vec<_> = imported.all().into_iter().map(|todo| todo.text).collect();
+    assert_eq!(texts, ["Send invoice"]);
+
+    let bundle = export_encrypted_subset(&list, &visible, "correct horse").unwrap();
+    let imported = import_encrypted(&bundle, "correct horse").unwrap();
+    assert_eq!(imported.total_count(), 1);
+    assert_eq!(imported.get(report).unwrap().text, "Send invoice");
+}
+
+#[test]
+fn test_subset_export_keeps_list_order_and_skips_unknown_ids() {
+    let list = rearranged_list();
+    let order: Vec<usize> = list.all().iter().map(|todo| todo.id).collect();
+    let mut ids = vec![order[2], order[0], 999];
+    ids.sort_unstable();
+
+    let imported = import_json(&export_json_subset(&list, &ids).unwrap()).unwrap();
+    let exported: Vec<usize> = imported.all().iter().map(|todo| todo.id).collect();
+    assert_eq!(exported, vec![order[0], order[2]]);
+
+    let empty = import_json(&export_json_subset(&list, &[]).unwrap()).unwrap();
+    assert_eq!(empty.total_count(), 0);
+}
+
+#[test]
+fn test_export_file_name_names_the_scope_and_date() {
+    let date = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+    let view = ListView {
+        filter: FilterState::Active,
+        tag: TagFilter::Tag("Work".to_string()),
+        ..ListView::default()
+    };
+    assert_eq!(
+        export_file_name(&view.name_parts(), date, "csv"),
+        "todos-active-Work-2025-03-03.csv"
+    );
+    assert_eq!(
+        export_file_name(&["due-2025-03-04", "Home / Garden"], date, "json"),
+        "todos-due-2025-03-04-Home-Garden-2025-03-03.json"
+    );
+    assert_eq!(
+        export_file_name::<String>(&[], date, "json"),
+        "todos-2025-03-03.json"
+    );
+}
+
+#[test]
+fn test_text_history_is_exported_only_when_asked() {
+    let mut list = sample_list();
+    let id = list.add("Wrte report".to_string());
+    list.update_text(id, "Write the report".to_string())
+        .unwrap();
+
+    let with_history = import_json(&export_json(&list).unwrap()).unwrap();
+    assert_eq!(with_history.text_history(id), list.text_history(id));
+
+    let json = export_json(&without_text_history(&list)).unwrap();
+    assert!(!json.contains("Wrte report"));
+    let imported = import_json(&json).unwrap();
+    assert!(imported.text_history(id).is_empty());
+    assert_eq!(imported.get(id).unwrap().text, "Write the report");
+    assert_eq!(imported.total_count(), list.total_count());
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use common::clock;
+use todo::models::follow_up::select_follow_ups;
+use todo::models::{AppSettings, Todo, TodoList};
+use todo::utils::clock::Clock;
+
+/// Builds an open todo with the given tags, last changed some days before `now`.
+fn todo_waiting(id: usize, tags: &[&str], days_ago: i64, now: DateTime<Utc>) -> Todo {
+    let mut todo = Todo::new(id, format!("Todo {id}"));
+    todo.tags = tags.iter().map(|tag| tag.to_string()).collect();
+    todo.created_at = Some(now - Duration::days(days_ago));
+    todo
+}
+
+#[test]
+fn test_select_follow_ups_by_tag_and_age() {
+    let now = clock().now_utc();
+    let list = TodoList::from_todos(vec![
+        todo_waiting(1, &["waiting"], 9, now),
+        todo_waiting(2, &["waiting"], 3, now),
+        todo_waiting(3, &["Work"], 30, now),
+        todo_waiting(4, &["Work", "Waiting"], 20, now),
+    ]);
+
+    assert_eq!(select_follow_ups(&list, "waiting", now, 7), vec![4, 1]);
+    assert_eq!(select_follow_ups(&list, "waiting", now, 2), vec![4, 1, 2]);
+    assert_eq!(select_follow_ups(&list, "Work", now, 25), vec![3]);
+}
+
+#[test]
+fn test_select_follow_ups_skips_completed_and_untimed_todos() {
+    let now = clock().now_utc();
+    let mut done = todo_waiting(1, &["waiting"], 10, now);
+    done.completed = true;
+    let mut untimed = todo_waiting(2, &["waiting"], 10, now);
+    untimed.created_at = None;
+    let list = TodoList::from_todos(vec![done, untimed]);
+
+    assert!(select_follow_ups(&list, "waiting", now, 7).is_empty());
+}
+
+#[test]
+fn test_select_follow_ups_is_off_without_a_tag() {
+    let now = clock().now_utc();
+    let list = TodoList::from_todos(vec![todo_waiting(1, &["waiting"], 10, now)]);
+    assert!(select_follow_ups(&list, "", now, 7).is_empty());
+    assert!(select_follow_ups(&list, "  ", now, 7).is_empty());
+}
+
+#[test]
+fn test_bump_restarts_the_wait_and_notes_the_day() {
+    let now = clock().now_utc();
+    let mut todo = todo_waiting(1, &["waiting"], 9, now);
+    todo.notes = "Emailed on Monday".to_string();
+    let mut list = TodoList::from_todos(vec![todo]);
+    let id = 1;
+    assert_eq!(select_follow_ups(&list, "waiting", now, 7), vec![id]);
+
+    let today = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+    assert!(list.bump(id, Some(today)));
+    assert!(select_follow_ups(&list, "waiting", now, 7).is_empty());
+    assert_eq!(
+        list.get(id).unwrap().notes,
+        "Emailed on Monday\nFollowed up on 2025-03-03"
+    );
+
+    assert!(list.bump(id, None));
+    assert_eq!(
+        list.get(id).unwrap().notes,
+        "Emailed on Monday\nFollowed up on 2025-03-03"
+    );
+    assert!(!list.bump(99, None));
+}
+
+#[test]
+fn test_follow_up_settings_defaults() {
+    let settings = AppSettings::default();
+    assert_eq!(settings.follow_up_tag, "waiting");
+    assert_eq!(settings.follow_up_after_days, 7);
+    assert!(settings.follow_up_note);
+
+    // Settings saved before follow-ups existed load with the defaults
+    let old: AppSettings = serde_json::from_str(r#"{"show_stale":true}"#).unwrap();
+    assert_eq!(old.follow_up_tag, "waiting");
+}
@@ -0,0 +1,211 @@
+use chrono::{TimeZone, Utc};
+use todo::models::{Todo, TodoHistory, TodoList};
+use todo::utils::constants::todo::UNDO_HISTORY_LIMIT;
+
+/// One edit in a sequence of changes.
+type Step = Box<dyn FnOnce(&mut TodoList)>;
+
+/// Makes a change to the list and records it in the history.
+fn change(list: &mut TodoList, history: &mut TodoHistory, edit: impl FnOnce(&mut TodoList)) {
+    let before = list.clone();
+    edit(list);
+    history.record(before, list);
+}
+
+/// Adds the given number of todos, one recorded change each.
+fn add_many(list: &mut TodoList, history: &mut TodoHistory, count: usize) {
+    for n in 0..count {
+        change(list, history, |list| {
+            list.add(format!("Todo {n}"));
+        });
+    }
+}
+
+#[test]
+fn test_undoing_each_operation_restores_the_previous_list() {
+    let mut list = TodoList::new();
+    let mut history = TodoHistory::default();
+    let plumber = list.add("Call the plumber".to_string());
+    let passport = list.add("Renew the passport".to_string());
+    let milk = list.add("Buy milk".to_string());
+    let mut states: Vec<Vec<Todo>> = vec![list.all()];
+
+    let due = Utc.with_ymd_and_hms(2025, 3, 14, 17, 0, 0).unwrap();
+    let steps: Vec<Step> = vec![
+        Box::new(|list| {
+            list.add("Water the plants".to_string());
+        }),
+        Box::new(move |list| {
+            list.toggle(passport);
+        }),
+        Box::new(move |list| {
+            list.update_text(plumber, "Call the plumber about the sink".to_string())
+                .unwrap();
+        }),
+        Box::new(move |list| {
+            list.add_tag(milk, "shopping".to_string());
+        }),
+        Box::new(move |list| {
+            list.set_due_date(plumber, Some(due)).unwrap();
+        }),
+        Box::new(move |list| {
+            list.reorder(milk, plumber);
+        }),
+        Box::new(move |list| {
+            list.move_to_trash(passport).unwrap();
+        }),
+    ];
+    for step in steps {
+        change(&mut list, &mut history, step);
+        states.push(list.all());
+    }
+
+    let ordered_ids = |todos: &[Todo]| todos.iter().map(|todo| todo.id).collect::<Vec<_>>();
+    states.pop();
+    while let Some(expected) = states.pop() {
+        assert!(history.undo(&mut list));
+        assert_eq!(ordered_ids(&list.all()), ordered_ids(&expected));
+        assert_eq!(list.all(), expected);
+    }
+    assert!(!history.undo(&mut list));
+    assert_eq!(list.trash_count(), 0);
+}
+
+#[test]
+fn test_redo_makes_undone_changes_again() {
+    let mut list = TodoList::new();
+    let mut history = TodoHistory::default();
+    add_many(&mut list, &mut history, 3);
+    let first = list.all()[0].id;
+    let last = list.all()[2].id;
+    change(&mut list, &mut history, |list| {
+        list.reorder(last, first);
+    });
+    let done = list.all();
+
+    for _ in 0..4 {
+        assert!(history.undo(&mut list));
+    }
+    assert!(list.all().is_empty());
+    for _ in 0..4 {
+        assert!(history.redo(&mut list));
+    }
+    assert_eq!(list.all(), done);
+    assert!(!history.redo(&mut list));
+}
+
+#[test]
+fn test_a_new_change_clears_redo() {
+    let mut list = TodoList::new();
+    let mut history = TodoHistory::default();
+    add_many(&mut list, &mut history, 2);
+
+    assert!(history.undo(&mut list));
+    assert!(history.can_redo());
+    change(&mut list, &mut history, |list| {
+        list.add("Something else".to_string());
+    });
+
+    assert!(!history.can_redo());
+    assert!(!history.redo(&mut list));
+    assert_eq!(
+        list.all()
+            .iter()
+            .map(|todo| todo.text.as_str())
+            .collect::<Vec<_>>(),
+        ["Todo 0", "Something else"]
+    );
+}
+
+#[test]
+fn test_history_keeps_only_the_latest_changes() {
+    let mut list = TodoList::new();
+    let mut history = TodoHistory::default();
+    add_many(&mut list, &mut history, UNDO_HISTORY_LIMIT + 20);
+
+    let mut undone = 0;
+    while history.undo(&mut list) {
+        undone += 1;
+    }
+    assert_eq!(undone, UNDO_HISTORY_LIMIT);
+    assert_eq!(list.all().len(), 20);
+
+    let mut small = TodoHistory::with_limit(2);
+    let mut list = TodoList::new();
+    add_many(&mut list, &mut small, 5);
+    assert!(small.undo(&mut list));
+    assert!(small.undo(&mut list));
+    assert!(!small.undo(&mut list));
+    assert_eq!(list.all().len(), 3);
+}
+
+#[test]
+fn test_changes_that_change_nothing_are_not_recorded() {
+    let mut list = TodoList::new();
+    let mut history = TodoHistory::default();
+    change(&mut list, &mut history, |list| {
+        list.toggle(42);
+    });
+    assert!(!history.can_undo());
+}
+
+#[test]
+fn test_changes_made_elsewhere_are_not_undone() {
+    let mut list = TodoList::new();
+    let mut history = TodoHistory::default();
+    add_many(&mut list, &mut history, 2);
+
+    // A sync edits the list behind the history's back
+    let first = list.all()[0].id;
+    list.set_notes(first, "Changed on another device".to_string());
+
+    assert!(!history.undo(&mut list));
+    assert!(!history.can_undo());
+    assert_eq!(list.all().len(), 2);
+    assert_eq!(list.get(first).unwrap().notes, "Changed on another device");
+
+    // Changes recorded afterwards are undone as usual
+    change(&mut list, &mut history, |list| {
+        list.add("After the sync".to_string());
+    });
+    assert!(history.undo(&mut list));
+    assert_eq!(list.all().len(), 2);
+}
+
+#[test]
+fn test_ids_are_not_handed_out_again_after_undo() {
+    let mut list = TodoList::new();
+    let mut history = TodoHistory::default();
+    add_many(&mut list, &mut history, 1);
+    let undone_id = list.all()[0].id;
+
+    assert!(history.undo(&mut list));
+    change(&mut list, &mut history, |list| {
+        list.add("Next".to_string());
+    });
+    assert!(list.all()[0].id > undone_id);
+}
+
+#[test]
+fn test_snoozing_is_a_change_like_any_other() {
+    let mut list = TodoList::new();
+    let mut history = TodoHistory::default();
+    let due = Utc.with_ymd_and_hms(2025, 3, 14, 17, 0, 0).unwrap();
+    let id = list.add("Send the invoice".to_string());
+    list.set_due_date(id, Some(due)).unwrap();
+    change(&mut list, &mut history, |list| {
+        list.add_tag(id, "work".to_string());
+    });
+    change(&mut list, &mut history, |list| {
+        list.snooze(id, due);
+    });
+
+    // Undoing the snooze keeps the tag change before it undoable
+    assert!(history.undo(&mut list));
+    assert_eq!(list.snoozed_until(id), None);
+    assert!(history.undo(&mut list));
+    assert!(list.get(id).unwrap().tags.is_empty());
+    assert!(history.redo(&mut list));
+    assert!(history.redo(&mut list));
+    assert_eq!(list.snoozed_until(id), Some(due));
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use chrono::Duration;
+use common::clock_at;
+use todo::models::idle::{Tick, TickMonitor, format_away};
+use todo::utils::clock::{Clock, FixedClock};
+use todo::utils::constants::todo::{MAX_TICK_CREDIT_SECS, REMINDER_CHECK_INTERVAL_SECS};
+
+fn monitor(clock: &FixedClock) -> TickMonitor {
+    TickMonitor::new(clock.now_utc(), Duration::seconds(MAX_TICK_CREDIT_SECS))
+}
+
+fn interval() -> Duration {
+    Duration::seconds(REMINDER_CHECK_INTERVAL_SECS as i64)
+}
+
+#[test]
+fn test_regular_ticks_are_credited_in_full() {
+    let mut clock = clock_at(22, 30);
+    let mut monitor = monitor(&clock);
+
+    for _ in 0..5 {
+        clock.advance(interval());
+        assert_eq!(
+            monitor.tick(clock.now_utc()),
+            Tick {
+                credited: interval(),
+                away: None,
+            }
+        );
+    }
+}
+
+#[test]
+fn test_late_ticks_within_the_cap_are_not_time_away() {
+    let mut clock = clock_at(22, 30);
+    let mut monitor = monitor(&clock);
+
+    // A busy moment delays the tick, but not past the cap
+    clock.advance(Duration::seconds(MAX_TICK_CREDIT_SECS));
+    let tick = monitor.tick(clock.now_utc());
+    assert_eq!(tick.credited, Duration::seconds(MAX_TICK_CREDIT_SECS));
+    assert!(!tick.resumed());
+}
+
+#[test]
+fn test_sleeping_overnight_is_time_away() {
+    let mut clock = clock_at(22, 30);
+    let mut monitor = monitor(&clock);
+
+    clock.advance(interval());
+    monitor.tick(clock.now_utc());
+    clock.advance(Duration::hours(8));
+    let tick = monitor.tick(clock.now_utc());
+
+    assert_eq!(tick.credited, Duration::seconds(MAX_TICK_CREDIT_SECS));
+    assert_eq!(
+        tick.away,
+        Some(Duration::hours(8) - Duration::seconds(MAX_TICK_CREDIT_SECS))
+    );
+
+    // Ticking resumes as normal after waking
+    clock.advance(interval());
+    assert!(!monitor.tick(clock.now_utc()).resumed());
+}
+
+#[test]
+fn test_clock_set_back_counts_for_nothing() {
+    let mut clock = clock_at(22, 30);
+    let mut monitor = monitor(&clock);
+
+    clock.advance(-Duration::hours(1));
+    assert_eq!(
+        monitor.tick(clock.now_utc()),
+        Tick {
+            credited: Duration::zero(),
+            away: None,
+        }
+    );
+    clock.advance(interval());
+    assert_eq!(monitor.tick(clock.now_utc()).credited, interval());
+}
+
+#[test]
+fn test_time_away_is_shown_in_hours_and_minutes() {
+    assert_eq!(format_away(Duration::minutes(3 * 60 + 12)), "3h 12m");
+    assert_eq!(format_away(Duration::seconds(59)), "0m");
+    assert_eq!(format_away(Duration::hours(26)), "26h 0m");
+}
@@ -0,0 +1,189 @@
+use chrono::{TimeZone, Utc};
+use std::collections::HashSet;
+use todo::models::import_plan::{ImportPlan, ImportRow};
+use todo::models::{Todo, TodoList};
+use todo::utils::constants::todo::MAX_TODO_TEXT_LENGTH;
+use todo::utils::export::{ExportError, decrypt_bundle, export_encrypted, export_json, read_json};
+
+/// Builds a list holding "Buy milk", tagged "Shopping", and "Write report".
+fn existing_list() -> TodoList {
+    let mut list = TodoList::new();
+    let id = list.add("Buy milk".to_string());
+    list.add_tag(id, "Shopping".to_string());
+    list.add("Write report".to_string());
+    list
+}
+
+fn todo(id: usize, text: &str) -> Todo {
+    Todo::new(id, text.to_string())
+}
+
+#[test]
+fn test_plan_sorts_imported_todos() {
+    let list = existing_list();
+    let mut bad_link = todo(4, "Read the docs");
+    bad_link.url = Some("not a link".to_string());
+    let imported = vec![
+        todo(1, "  Call mum "),
+        todo(2, "WRITE   report"),
+        todo(3, ""),
+        bad_link,
+        todo(5, &"x".repeat(MAX_TODO_TEXT_LENGTH + 1)),
+    ];
+
+    let plan = ImportPlan::new(imported, &list.all());
+
+    assert_eq!(plan.additions.len(), 1);
+    assert_eq!(plan.additions[0].text, "Call mum");
+    assert_eq!(plan.duplicates.len(), 1);
+    assert_eq!(plan.duplicates[0].existing_id, 2);
+    assert_eq!(plan.duplicates[0].existing_text, "Write report");
+    let positions: Vec<usize> = plan.errors.iter().map(|problem| problem.position).collect();
+    assert_eq!(positions, [3, 4, 5]);
+    assert!(plan.errors.iter().all(|problem| !problem.reason.is_empty()));
+}
+
+#[test]
+fn test_todos_repeated_within_the_file_are_added_once() {
+    let plan = ImportPlan::new(
+        vec![
+            todo(1, "Call mum"),
+            todo(2, "call  mum"),
+            todo(3, "Book van"),
+        ],
+        &[],
+    );
+
+    let texts: Vec<&str> = plan
+        .additions
+        .iter()
+        .map(|todo| todo.text.as_str())
+        .collect();
+    assert_eq!(texts, ["Call mum", "Book van"]);
+    assert_eq!(plan.errors.len(), 1);
+    assert_eq!(plan.errors[0].position, 2);
+    assert_eq!(plan.errors[0].reason, "repeats todo 1 of the file");
+}
+
+#[test]
+fn test_due_dates_out_of_range_are_reported() {
+    let mut typo = todo(1, "Pay rent");
+    typo.due_date = Some(Utc.with_ymd_and_hms(200, 4, 1, 9, 0, 0).unwrap());
+    let mut far = todo(2, "Renew lease");
+    far.due_date = Some(Utc.with_ymd_and_hms(9999, 4, 1, 9, 0, 0).unwrap());
+    let mut fine = todo(3, "Book van");
+    fine.due_date = Some(Utc.with_ymd_and_hms(2025, 4, 1, 9, 0, 0).unwrap());
+
+    let plan = ImportPlan::new(vec![typo, far, fine, todo(4, "Call mum")], &[]);
+
+    assert_eq!(plan.additions.len(), 2);
+    assert_eq!(plan.errors.len(), 2);
+    assert_eq!(
+        plan.errors[0].reason,
+        "Due date 0200-04-01 is before 1970-01-01, the earliest allowed"
+    );
+    assert_eq!(plan.errors[1].position, 2);
+    assert!(
+        plan.errors[1]
+            .reason
+            .starts_with("Due date 9999-04-01 is after")
+    );
+}
+
+#[test]
+fn test_plan_without_skipped_rows() {
+    let list = existing_list();
+    let plan = ImportPlan::new(
+        vec![
+            todo(1, "Call mum"),
+            todo(2, "Book van"),
+            todo(3, "buy milk"),
+        ],
+        &list.all(),
+    );
+
+    let skipped = HashSet::from([ImportRow::Addition(0), ImportRow::Duplicate(0)]);
+    let kept = plan.without(&skipped);
+    assert_eq!(kept.additions.len(), 1);
+    assert_eq!(kept.additions[0].text, "Book van");
+    assert!(kept.duplicates.is_empty());
+    assert!(!kept.is_empty());
+
+    let everything = HashSet::from([
+        ImportRow::Addition(0),
+        ImportRow::Addition(1),
+        ImportRow::Duplicate(0),
+    ]);
+    assert!(plan.without(&everything).is_empty());
+}
+
+#[test]
+fn test_apply_adds_and_merges_in_one_go() {
+    let mut list = existing_list();
+    let due = Utc.with_ymd_and_hms(2025, 3, 14, 12, 0, 0).unwrap();
+    let mut repeat = todo(8, "buy milk");
+    repeat.tags = vec!["Shopping".to_string(), "Weekly".to_string()];
+    repeat.due_date = Some(due);
+    repeat.notes = "Oat, not cow".to_string();
+    repeat.completed = true;
+    let plan = ImportPlan::new(vec![todo(7, "Call mum"), repeat], &list.all());
+
+    assert_eq!(plan.apply(&mut list), (1, 1));
+
+    let texts: Vec<String> = list.all().into_iter().map(|todo| todo.text).collect();
+    assert_eq!(texts, ["Buy milk", "Write report", "Call mum"]);
+    let merged = list.get(1).unwrap();
+    assert_eq!(merged.text, "Buy milk");
+    assert_eq!(merged.tags, ["Shopping", "Weekly"]);
+    assert_eq!(merged.due_date, Some(due));
+    assert_eq!(merged.notes, "Oat, not cow");
+    assert!(!merged.completed);
+}
+
+#[test]
+fn test_merging_keeps_what_the_existing_todo_has() {
+    let mut list = existing_list();
+    let kept_due = Utc.with_ymd_and_hms(2025, 1, 2, 12, 0, 0).unwrap();
+    list.set_due_date(1, Some(kept_due)).unwrap();
+    list.set_notes(1, "Semi-skimmed".to_string());
+    let mut repeat = todo(8, "Buy milk");
+    repeat.due_date = Some(Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap());
+    repeat.notes = "Oat".to_string();
+
+    let plan = ImportPlan::new(vec![repeat], &list.all());
+    assert_eq!(plan.apply(&mut list), (0, 1));
+
+    let merged = list.get(1).unwrap();
+    assert_eq!(merged.due_date, Some(kept_due));
+    assert_eq!(merged.notes, "Semi-skimmed");
+    assert_eq!(merged.postponed_count, 0);
+}
+
+#[test]
+fn test_read_json_and_decrypt_bundle_feed_a_plan() {
+    let list = existing_list();
+    let json = export_json(&list).unwrap();
+    let todos = read_json(&json).unwrap();
+    assert_eq!(todos, list.all());
+
+    let bundle = export_encrypted(&list, "pass").unwrap();
+    let decrypted = decrypt_bundle(&bundle, "pass").unwrap();
+    assert_eq!(read_json(&decrypted).unwrap(), list.all());
+    assert!(matches!(
+        decrypt_bundle(&bundle, "wrong"),
+        Err(ExportError::WrongPassphrase)
+    ));
+
+    // Importing a list into itself only finds duplicates
+    let plan = ImportPlan::new(todos, &list.all());
+    assert!(plan.additions.is_empty());
+    assert_eq!(plan.duplicates.len(), 2);
+}
+
+#[test]
+fn test_read_json_rejects_garbage() {
+    assert!(matches!(
+        read_json("not a backup"),
+        Err(ExportError::CorruptFile(_))
+    ));
+}
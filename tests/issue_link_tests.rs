@@ -0,0 +1,169 @@
+use todo::models::issue_link::{IssueLinkError, IssueLinker};
+use todo::models::{AppSettings, IssueLinkRule};
+use todo::utils::text::{DisplaySegment, UrlError, display_segments};
+
+fn rule(pattern: &str, url_template: &str) -> IssueLinkRule {
+    IssueLinkRule {
+        pattern: pattern.to_string(),
+        url_template: url_template.to_string(),
+    }
+}
+
+fn jira() -> IssueLinkRule {
+    rule(r"[A-Z]+-\d+", "https://jira.example.com/browse/{0}")
+}
+
+/// The linked text and address of each reference found in `text`
+fn links(linker: &IssueLinker, text: &str) -> Vec<(String, String)> {
+    linker
+        .find(text)
+        .into_iter()
+        .map(|link| (text[link.range].to_string(), link.url))
+        .collect()
+}
+
+#[test]
+fn test_matches_fill_the_template() {
+    let linker = IssueLinker::new(&[jira()]);
+
+    assert_eq!(
+        links(&linker, "ABC-12 blocks XY-3"),
+        vec![
+            (
+                "ABC-12".to_string(),
+                "https://jira.example.com/browse/ABC-12".to_string()
+            ),
+            (
+                "XY-3".to_string(),
+                "https://jira.example.com/browse/XY-3".to_string()
+            ),
+        ]
+    );
+    assert!(links(&linker, "No references here").is_empty());
+}
+
+#[test]
+fn test_groups_fill_numbered_placeholders_and_are_encoded() {
+    let linker = IssueLinker::new(&[rule(
+        r"(\w+)#(\d+)",
+        "https://github.com/tyrchen/{1}/issues/{2}",
+    )]);
+    assert_eq!(
+        links(&linker, "See todo#42"),
+        vec![(
+            "todo#42".to_string(),
+            "https://github.com/tyrchen/todo/issues/42".to_string()
+        )]
+    );
+
+    let linker = IssueLinker::new(&[rule(r"ticket \S+", "https://help.example.com/?q={0}")]);
+    assert_eq!(
+        links(&linker, "ticket a&b")[0].1,
+        "https://help.example.com/?q=ticket%20a%26b"
+    );
+}
+
+#[test]
+fn test_overlapping_matches_keep_the_earliest_then_longest() {
+    let linker = IssueLinker::new(&[
+        rule(r"\d+", "https://numbers.example.com/{0}"),
+        jira(),
+        rule(r"ABC-\d+", "https://abc.example.com/{0}"),
+    ]);
+
+    // The number inside the reference starts later, so the reference wins
+    assert_eq!(
+        links(&linker, "Fix ABC-12"),
+        vec![(
+            "ABC-12".to_string(),
+            "https://jira.example.com/browse/ABC-12".to_string()
+        )]
+    );
+
+    // Two rules matching the same text: the one listed first wins
+    let linker = IssueLinker::new(&[rule(r"ABC-\d+", "https://abc.example.com/{0}"), jira()]);
+    assert_eq!(
+        links(&linker, "ABC-12")[0].1,
+        "https://abc.example.com/ABC-12"
+    );
+
+    // Same start, the longer match wins
+    let linker = IssueLinker::new(&[rule(r"ABC", "https://abc.example.com/"), jira()]);
+    assert_eq!(links(&linker, "ABC-12")[0].0, "ABC-12");
+}
+
+#[test]
+fn test_matches_inside_markdown_links_are_skipped() {
+    let linker = IssueLinker::new(&[jira()]);
+
+    assert!(links(&linker, "[ABC-1](https://example.com/ABC-1)").is_empty());
+    assert_eq!(
+        links(&linker, "[notes](https://example.com) for XY-2"),
+        vec![(
+            "XY-2".to_string(),
+            "https://jira.example.com/browse/XY-2".to_string()
+        )]
+    );
+    // Brackets alone are not a link
+    assert_eq!(links(&linker, "[ABC-1] retry").len(), 1);
+}
+
+#[test]
+fn test_web_addresses_are_left_alone() {
+    let linker = IssueLinker::new(&[jira()]);
+    let text = "ABC-1 see https://example.com/ABC-2";
+
+    assert_eq!(
+        linker.link_segments(display_segments(text, 80)),
+        vec![
+            DisplaySegment::Issue {
+                text: "ABC-1",
+                url: "https://jira.example.com/browse/ABC-1".to_string(),
+            },
+            DisplaySegment::Text(" see "),
+            DisplaySegment::Url {
+                full: "https://example.com/ABC-2",
+                shown: "https://example.com/ABC-2".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_invalid_rules_are_rejected_and_skipped() {
+    assert_eq!(jira().validate(), Ok(()));
+    assert_eq!(
+        rule("", "https://example.com/{0}").validate(),
+        Err(IssueLinkError::EmptyPattern)
+    );
+    assert!(matches!(
+        rule("ABC-(", "https://example.com/{0}").validate(),
+        Err(IssueLinkError::InvalidRegex(_))
+    ));
+    assert_eq!(
+        rule(r"\d*", "https://example.com/{0}").validate(),
+        Err(IssueLinkError::MatchesEmpty)
+    );
+    assert_eq!(
+        rule(r"\d+", "jira/{0}").validate(),
+        Err(IssueLinkError::InvalidTemplate(UrlError::NotWeb))
+    );
+
+    let linker = IssueLinker::new(&[rule("ABC-(", "https://example.com/{0}"), jira()]);
+    assert_eq!(links(&linker, "ABC-1").len(), 1);
+}
+
+#[test]
+fn test_rules_are_stored_in_settings() {
+    let settings = AppSettings {
+        issue_link_rules: vec![jira()],
+        ..AppSettings::default()
+    };
+    let json = serde_json::to_string(&settings).unwrap();
+    let loaded: AppSettings = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, settings);
+
+    // Settings saved before rules existed load with none
+    let old: AppSettings = serde_json::from_str(r#"{"week_start":"Monday"}"#).unwrap();
+    assert!(old.issue_link_rules.is_empty());
+}
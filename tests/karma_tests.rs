@@ -0,0 +1,111 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use todo::models::karma::{Karma, WeeklyProgress, karma, score_for, weekly_progress};
+use todo::models::{Todo, WeekStart};
+use todo::utils::clock::{Clock, FixedClock};
+use todo::utils::dates;
+
+/// Wednesday 12 March 2025, 09:30 UTC.
+fn now() -> DateTime<Utc> {
+    FixedClock::at_local(NaiveDate::from_ymd_opt(2025, 3, 12).unwrap(), 9, 30, 0).now_utc()
+}
+
+fn completed(id: usize, at: DateTime<Utc>) -> Todo {
+    let mut todo = Todo::new(id, format!("Todo {id}"));
+    todo.completed = true;
+    todo.completed_at = Some(at);
+    todo
+}
+
+#[test]
+fn test_score_for_rewards_priority_and_clearing_overdue() {
+    let mut todo = completed(1, now());
+    assert_eq!(score_for(&todo), 1);
+
+    // Finished on the day it was due: not overdue
+    todo.due_date = Some(dates::due_date_on(now().date_naive()));
+    assert_eq!(score_for(&todo), 1);
+
+    todo.due_date = Some(dates::due_date_on(now().date_naive() - Duration::days(1)));
+    assert_eq!(score_for(&todo), 2);
+
+    todo.tags.push("URGENT".to_string());
+    assert_eq!(score_for(&todo), 4);
+}
+
+#[test]
+fn test_score_for_skips_open_todos() {
+    let mut todo = completed(1, now());
+    todo.completed = false;
+    assert_eq!(score_for(&todo), 0);
+
+    // Completed before completion times were recorded
+    let mut todo = Todo::new(2, "Old".to_string());
+    todo.completed = true;
+    assert_eq!(score_for(&todo), 0);
+}
+
+#[test]
+fn test_karma_levels_up_every_25_points() {
+    let todos: Vec<_> = (1..=27).map(|id| completed(id, now())).collect();
+    assert_eq!(
+        karma(&todos[..24]),
+        Karma {
+            score: 24,
+            level: 1,
+            level_points: 24
+        }
+    );
+    assert_eq!(
+        karma(&todos),
+        Karma {
+            score: 27,
+            level: 2,
+            level_points: 2
+        }
+    );
+}
+
+#[test]
+fn test_weekly_progress_counts_completions_since_the_week_started() {
+    let todos = vec![
+        completed(1, now()),
+        // Monday morning, the first day of the week
+        completed(2, now() - Duration::days(2) - Duration::hours(9)),
+        // Last Sunday night
+        completed(3, now() - Duration::days(2) - Duration::hours(10)),
+        Todo::new(4, "Open".to_string()),
+    ];
+
+    let progress = weekly_progress(&todos, now(), WeekStart::Monday, 4);
+    assert_eq!(
+        progress,
+        WeeklyProgress {
+            completed: 2,
+            goal: 4
+        }
+    );
+    assert_eq!(progress.fraction(), 0.5);
+    assert!(!progress.is_met());
+
+    // A week starting on Sunday includes last Sunday
+    let progress = weekly_progress(&todos, now(), WeekStart::Sunday, 3);
+    assert_eq!(progress.completed, 3);
+    assert!(progress.is_met());
+}
+
+#[test]
+fn test_weekly_progress_fraction_is_capped() {
+    let progress = WeeklyProgress {
+        completed: 30,
+        goal: 20,
+    };
+    assert_eq!(progress.fraction(), 1.0);
+    assert_eq!(
+        WeeklyProgress {
+            completed: 0,
+            goal: 0
+        }
+        .fraction(),
+        1.0
+    );
+}
@@ -0,0 +1,84 @@
+use todo::models::AppSettings;
+use todo::utils::constants::todo::{
+    DEFAULT_TODO_COUNT_WARNING, MAX_TODO_TEXT_LENGTH, TRUNCATE_TEXT_AT,
+};
+use todo::utils::text::{LengthLevel, TextError, length_level, truncate_chars, validate_todo_text};
+
+#[test]
+fn test_truncate_chars_short_text() {
+    assert_eq!(truncate_chars("Buy milk", 8), None);
+    assert_eq!(truncate_chars("", 0), None);
+}
+
+#[test]
+fn test_truncate_chars_long_text() {
+    assert_eq!(truncate_chars("Buy milk", 3), Some("Buy"));
+
+    let long_text = "x".repeat(TRUNCATE_TEXT_AT * 2);
+    assert_eq!(
+        truncate_chars(&long_text, TRUNCATE_TEXT_AT).map(str::len),
+        Some(TRUNCATE_TEXT_AT)
+    );
+}
+
+#[test]
+fn test_truncate_chars_respects_char_boundaries() {
+    // Multi-byte characters are counted once, never split
+    assert_eq!(truncate_chars("héllo wörld", 5), Some("héllo"));
+    assert_eq!(truncate_chars("🎉🎉🎉", 2), Some("🎉🎉"));
+    assert_eq!(truncate_chars("🎉🎉🎉", 3), None);
+}
+
+#[test]
+fn test_todo_count_warning_setting() {
+    assert_eq!(
+        AppSettings::default().todo_count_warning,
+        DEFAULT_TODO_COUNT_WARNING
+    );
+
+    // Settings saved before the limit existed use the default
+    let settings: AppSettings = serde_json::from_str(r#"{"week_start":"Sunday"}"#).unwrap();
+    assert_eq!(settings.todo_count_warning, DEFAULT_TODO_COUNT_WARNING);
+
+    let settings: AppSettings = serde_json::from_str(r#"{"todo_count_warning":100}"#).unwrap();
+    assert_eq!(settings.todo_count_warning, 100);
+}
+
+#[test]
+fn test_validate_todo_text_trims() {
+    assert_eq!(
+        validate_todo_text("  Buy milk\n"),
+        Ok("Buy milk".to_string())
+    );
+    assert_eq!(validate_todo_text(""), Err(TextError::Empty));
+    assert_eq!(validate_todo_text(" \t "), Err(TextError::Empty));
+}
+
+#[test]
+fn test_validate_todo_text_length_limit() {
+    let at_limit = "é".repeat(MAX_TODO_TEXT_LENGTH);
+    assert_eq!(validate_todo_text(&at_limit), Ok(at_limit.clone()));
+
+    // Surrounding whitespace doesn't count towards the limit
+    assert!(validate_todo_text(&format!("  {}  ", at_limit)).is_ok());
+
+    let too_long = "x".repeat(MAX_TODO_TEXT_LENGTH + 1);
+    assert_eq!(
+        validate_todo_text(&too_long),
+        Err(TextError::TooLong {
+            length: MAX_TODO_TEXT_LENGTH + 1,
+            max: MAX_TODO_TEXT_LENGTH,
+        })
+    );
+}
+
+#[test]
+fn test_length_level_thresholds() {
+    let warning_at = (MAX_TODO_TEXT_LENGTH * 9).div_ceil(10);
+    assert_eq!(length_level(0), LengthLevel::Normal);
+    assert_eq!(length_level(warning_at - 1), LengthLevel::Normal);
+    assert_eq!(length_level(warning_at), LengthLevel::Warning);
+    assert_eq!(length_level(MAX_TODO_TEXT_LENGTH - 1), LengthLevel::Warning);
+    assert_eq!(length_level(MAX_TODO_TEXT_LENGTH), LengthLevel::Limit);
+    assert_eq!(length_level(MAX_TODO_TEXT_LENGTH + 5), LengthLevel::Limit);
+}
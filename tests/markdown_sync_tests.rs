@@ -0,0 +1,182 @@
+use chrono::NaiveDate;
+use todo::models::{Todo, TodoList};
+use todo::utils::markdown_sync::{
+    MarkdownDoc, MarkdownItem, MarkdownLine, apply_pull, plan_pull, write_back,
+};
+
+const TAG: &str = "TODO.md";
+
+const FILE: &str = "\
+# Plans
+
+- [ ] Call the plumber #home (due: 2025-03-14)
+- [x] Renew the passport
+  * [ ] Pack the charger #travel
+- [?] Unsure about this one
+- [ ] Broken date (due: next week)
+- [link](https://example.com)
+";
+
+/// Gets the todos carrying the sync tag, in list order.
+fn synced(list: &TodoList) -> Vec<Todo> {
+    list.all()
+        .into_iter()
+        .filter(|todo| todo.tags.iter().any(|tag| tag == TAG))
+        .collect()
+}
+
+/// Pulls a file into the list, returning how many todos changed.
+fn pull(list: &mut TodoList, text: &str) -> usize {
+    let todos = synced(list);
+    let plan = plan_pull(&MarkdownDoc::parse(text), &todos.iter().collect::<Vec<_>>());
+    apply_pull(list, &plan, TAG)
+}
+
+/// Writes the list back into a file.
+fn push(list: &TodoList, text: &str) -> String {
+    let todos = synced(list);
+    write_back(
+        &MarkdownDoc::parse(text),
+        &todos.iter().collect::<Vec<_>>(),
+        TAG,
+    )
+    .to_text()
+}
+
+#[test]
+fn test_round_trip_keeps_the_file_as_is() {
+    let doc = MarkdownDoc::parse(FILE);
+    assert_eq!(doc.to_text(), FILE);
+
+    let crlf = FILE.replace('\n', "\r\n");
+    assert_eq!(MarkdownDoc::parse(&crlf).to_text(), crlf);
+}
+
+#[test]
+fn test_items_read_tags_due_dates_and_nesting() {
+    let doc = MarkdownDoc::parse(FILE);
+    let items: Vec<&MarkdownItem> = doc.items().collect();
+    assert_eq!(items.len(), 3);
+
+    assert_eq!(items[0].text, "Call the plumber");
+    assert_eq!(items[0].tags, vec!["home".to_string()]);
+    assert_eq!(items[0].due, NaiveDate::from_ymd_opt(2025, 3, 14));
+    assert!(!items[0].done);
+
+    assert!(items[1].done);
+    assert!(items[1].tags.is_empty());
+
+    assert_eq!(items[2].indent, "  ");
+    assert_eq!(items[2].marker, '*');
+    assert_eq!(items[2].tags, vec!["travel".to_string()]);
+}
+
+#[test]
+fn test_malformed_lines_are_kept_verbatim() {
+    let doc = MarkdownDoc::parse(FILE);
+    assert_eq!(doc.malformed_count(), 2);
+    assert!(doc.lines.contains(&MarkdownLine::Malformed(
+        "- [?] Unsure about this one".to_string()
+    )));
+    // A link is prose, not a checklist line
+    assert!(doc.lines.contains(&MarkdownLine::Text(
+        "- [link](https://example.com)".to_string()
+    )));
+
+    let mut list = TodoList::new();
+    pull(&mut list, FILE);
+    assert_eq!(push(&list, FILE), FILE);
+}
+
+#[test]
+fn test_pull_adds_updates_and_removes_todos() {
+    let mut list = TodoList::new();
+    assert_eq!(pull(&mut list, FILE), 3);
+    let todos = synced(&list);
+    assert_eq!(todos.len(), 3);
+    assert!(todos[0].tags.contains(&"home".to_string()));
+    assert_eq!(
+        todos[0].due_date.map(|due| due.date_naive()),
+        NaiveDate::from_ymd_opt(2025, 3, 14)
+    );
+    assert!(todos[1].completed);
+
+    // Reading the same file again changes nothing
+    assert_eq!(pull(&mut list, FILE), 0);
+
+    let edited = FILE
+        .replace("- [ ] Call the plumber", "- [x] Call the plumber")
+        .replace("  * [ ] Pack the charger #travel\n", "");
+    assert_eq!(pull(&mut list, &edited), 2);
+    let todos = synced(&list);
+    assert_eq!(todos.len(), 2);
+    assert!(todos[0].completed);
+}
+
+#[test]
+fn test_renaming_a_line_updates_the_same_todo() {
+    let mut list = TodoList::new();
+    pull(&mut list, "- [ ] Call the plumber\n");
+    let id = synced(&list)[0].id;
+
+    assert_eq!(pull(&mut list, "- [ ] Call the electrician\n"), 1);
+    let todos = synced(&list);
+    assert_eq!(todos.len(), 1);
+    assert_eq!(todos[0].id, id);
+    assert_eq!(todos[0].text, "Call the electrician");
+}
+
+#[test]
+fn test_write_back_updates_appends_and_removes_items() {
+    let mut list = TodoList::new();
+    pull(&mut list, FILE);
+    let todos = synced(&list);
+
+    list.toggle(todos[1].id);
+    list.move_to_trash(todos[2].id).unwrap();
+    let removed = push(&list, FILE);
+    assert_eq!(
+        removed,
+        "\
+# Plans
+
+- [ ] Call the plumber #home (due: 2025-03-14)
+- [ ] Renew the passport
+- [?] Unsure about this one
+- [ ] Broken date (due: next week)
+- [link](https://example.com)
+"
+    );
+
+    let added = list.add("Book the flights".to_string());
+    list.add_tag(added, TAG.to_string());
+    list.add_tag(added, "travel".to_string());
+    // Todos without the sync tag stay out of the file
+    list.add("Private".to_string());
+    assert_eq!(
+        push(&list, &removed),
+        "\
+# Plans
+
+- [ ] Call the plumber #home (due: 2025-03-14)
+- [ ] Renew the passport
+- [ ] Book the flights #travel
+- [?] Unsure about this one
+- [ ] Broken date (due: next week)
+- [link](https://example.com)
+"
+    );
+}
+
+#[test]
+fn test_write_back_into_an_empty_file() {
+    let mut list = TodoList::new();
+    let id = list.add("Call the plumber".to_string());
+    list.add_tag(id, TAG.to_string());
+
+    assert_eq!(push(&list, ""), "- [ ] Call the plumber\n");
+    assert_eq!(
+        push(&list, "# Plans\n"),
+        "# Plans\n- [ ] Call the plumber\n"
+    );
+}
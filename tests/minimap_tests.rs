@@ -0,0 +1,110 @@
+mod common;
+
+use chrono::Duration;
+use common::clock;
+use todo::models::minimap::{MinimapMark, marks, row_at, scroll_top_for};
+use todo::models::view::ListViewModel;
+use todo::models::{ListView, Todo};
+use todo::utils::clock::Clock;
+use todo::utils::constants::ui::MINIMAP_MIN_ROWS;
+use todo::utils::dates;
+
+fn due(id: usize, days_from_today: i64) -> Todo {
+    let mut todo = Todo::new(id, format!("Todo {id}"));
+    todo.due_date = Some(dates::due_date_on(
+        clock().today_local() + Duration::days(days_from_today),
+    ));
+    todo
+}
+
+#[test]
+fn test_marks_color_rows_by_due_date() {
+    let today = clock().today_local();
+    let mut done_overdue = due(3, -2);
+    done_overdue.completed = true;
+    let todos = [
+        due(1, -1),
+        due(2, 0),
+        done_overdue,
+        due(4, 1),
+        Todo::new(5, "No due date".to_string()),
+    ];
+
+    assert_eq!(
+        marks(&todos, today, 0),
+        Some(vec![
+            MinimapMark::Overdue,
+            MinimapMark::DueToday,
+            MinimapMark::Completed,
+            MinimapMark::Other,
+            MinimapMark::Other,
+        ])
+    );
+}
+
+#[test]
+fn test_marks_are_left_out_for_short_lists() {
+    let clock = clock();
+    let todos: Vec<_> = (1..=MINIMAP_MIN_ROWS).map(|id| due(id, -1)).collect();
+    let visible = |todos: &[Todo]| {
+        let model = ListViewModel::compute(
+            todos,
+            &ListView::default(),
+            "",
+            false,
+            clock.now_utc(),
+            clock.today_local(),
+        );
+        marks(
+            model.rows.iter().map(|row| row.todo),
+            clock.today_local(),
+            MINIMAP_MIN_ROWS,
+        )
+    };
+
+    assert_eq!(
+        visible(&todos).map(|marks| marks.len()),
+        Some(MINIMAP_MIN_ROWS)
+    );
+    assert_eq!(visible(&todos[1..]), None);
+}
+
+#[test]
+fn test_row_at_maps_clicks_along_the_strip() {
+    // 300 rows in a 150px strip: two rows per pixel
+    assert_eq!(row_at(0.0, 150.0, 300), Some(0));
+    assert_eq!(row_at(0.4, 150.0, 300), Some(0));
+    assert_eq!(row_at(0.6, 150.0, 300), Some(1));
+    assert_eq!(row_at(75.0, 150.0, 300), Some(150));
+    assert_eq!(row_at(149.9, 150.0, 300), Some(299));
+
+    // Clicks on the border land on the first or last row
+    assert_eq!(row_at(-3.0, 150.0, 300), Some(0));
+    assert_eq!(row_at(151.0, 150.0, 300), Some(299));
+
+    assert_eq!(row_at(10.0, 150.0, 0), None);
+    assert_eq!(row_at(10.0, 0.0, 300), None);
+    assert_eq!(row_at(f64::NAN, 150.0, 300), None);
+}
+
+#[test]
+fn test_scroll_top_centers_the_row_within_the_list() {
+    // 200 rows of 50px in a 400px list
+    assert_eq!(scroll_top_for(100, 200, 10_000.0, 400.0), 4825.0);
+    assert_eq!(scroll_top_for(3, 200, 10_000.0, 400.0), 0.0);
+    assert_eq!(scroll_top_for(199, 200, 10_000.0, 400.0), 9600.0);
+
+    // A list that fits does not scroll
+    assert_eq!(scroll_top_for(5, 10, 300.0, 400.0), 0.0);
+    assert_eq!(scroll_top_for(0, 0, 300.0, 400.0), 0.0);
+}
+
+#[test]
+fn test_click_scrolls_proportionally() {
+    let (strip_height, rows, scroll_height, client_height) = (400.0, 400, 16_000.0, 400.0);
+    let row = row_at(100.0, strip_height, rows).unwrap();
+    assert_eq!(row, 100);
+    let top = scroll_top_for(row, rows, scroll_height, client_height);
+    // A quarter down the strip shows the list a quarter of the way down
+    assert_eq!(top + client_height / 2.0, scroll_height / 4.0 + 20.0);
+}
@@ -0,0 +1,97 @@
+use todo::models::TodoList;
+use todo::utils::notes::{action_item, is_struck, line_at, strike_line};
+
+const NOTES: &str = "Call notes\n- Email Bob\n  * [ ] Book flights\n- ~~Order cake~~";
+
+#[test]
+fn test_action_items() {
+    assert_eq!(action_item("- Email Bob"), Some("Email Bob"));
+    assert_eq!(action_item("  * [ ] Book flights "), Some("Book flights"));
+    assert_eq!(action_item("Call notes"), None);
+    assert_eq!(action_item("- "), None);
+    assert_eq!(action_item("-no space"), None);
+    assert_eq!(action_item("- ~~Order cake~~"), None);
+}
+
+#[test]
+fn test_strike_line_keeps_bullets() {
+    assert_eq!(
+        strike_line(NOTES, 2),
+        "Call notes\n- Email Bob\n  * [ ] ~~Book flights~~\n- ~~Order cake~~"
+    );
+    assert_eq!(
+        strike_line(NOTES, 0),
+        "~~Call notes~~\n- Email Bob\n  * [ ] Book flights\n- ~~Order cake~~"
+    );
+
+    // Struck, blank and missing lines are left alone
+    assert_eq!(strike_line(NOTES, 3), NOTES);
+    assert_eq!(strike_line(NOTES, 10), NOTES);
+    assert!(is_struck("- ~~Order cake~~"));
+    assert!(!is_struck("- ~~"));
+}
+
+#[test]
+fn test_line_at_cursor() {
+    assert_eq!(line_at(NOTES, 0), 0);
+    assert_eq!(line_at(NOTES, 10), 0);
+    assert_eq!(line_at(NOTES, 11), 1);
+    assert_eq!(line_at(NOTES, 1_000), 3);
+    // Cursors count UTF-16 code units
+    assert_eq!(line_at("🎉\nnext", 3), 1);
+}
+
+#[test]
+fn test_extract_from_notes() {
+    let mut list = TodoList::new();
+    let parent = list.add("Plan the trip".to_string());
+    list.add_tag(parent, "Personal".to_string());
+    list.set_notes(parent, NOTES.to_string());
+
+    let child = list.extract_from_notes(parent, 2, true).unwrap();
+    let todo = list.get(child).unwrap();
+    assert_eq!(todo.text, "Book flights");
+    assert_eq!(todo.tags, vec!["Personal"]);
+    assert_eq!(todo.derived_from, Some(parent));
+    assert_eq!(list.all().last().unwrap().id, child);
+    assert!(list.get(parent).unwrap().notes.contains("~~Book flights~~"));
+    list.check_invariants().unwrap();
+
+    // Struck lines can't be extracted twice, and nothing else changes
+    assert_eq!(list.extract_from_notes(parent, 2, true), None);
+    assert_eq!(list.extract_from_notes(parent, 99, true), None);
+    assert_eq!(list.extract_from_notes(999, 0, true), None);
+
+    // Without striking, the notes stay as they were
+    let before = list.get(parent).unwrap().notes.clone();
+    let other = list.extract_from_notes(parent, 1, false).unwrap();
+    assert_eq!(list.get(other).unwrap().text, "Email Bob");
+    assert_eq!(list.get(parent).unwrap().notes, before);
+}
+
+#[test]
+fn test_notes_and_links_are_persisted() {
+    let mut list = TodoList::new();
+    let parent = list.add("Parent".to_string());
+    list.set_notes(parent, "- Child".to_string());
+    let child = list.extract_from_notes(parent, 0, false).unwrap();
+
+    let json = serde_json::to_string(&list).unwrap();
+    let restored: TodoList = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, list);
+    assert_eq!(restored.get(child).unwrap().derived_from, Some(parent));
+
+    // Todos without notes or links don't write the fields
+    let plain = serde_json::to_string(&TodoList::new()).unwrap();
+    assert!(!plain.contains("notes") && !plain.contains("derived_from"));
+}
+
+#[test]
+fn test_lists_saved_before_notes_still_load() {
+    let json = concat!(
+        r#"{"todos":[{"id":1,"text":"Call the plumber","completed":false,"#,
+        r#""due_date":null,"tags":[],"order":1}],"next_id":2}"#
+    );
+    let list: TodoList = serde_json::from_str(json).unwrap();
+    assert_eq!(list.get(1).unwrap().notes, "");
+}
@@ -0,0 +1,42 @@
+use chrono::Utc;
+use todo::utils::constants::onboarding::SAMPLE_TODOS;
+use todo::utils::onboarding::sample_todo_list;
+
+#[test]
+fn test_sample_todo_list_matches_constants() {
+    let list = sample_todo_list();
+    let todos = list.all();
+
+    assert_eq!(todos.len(), SAMPLE_TODOS.len());
+    assert!(list.check_invariants().is_ok());
+    for (todo, (text, tags, due_in_days)) in todos.iter().zip(SAMPLE_TODOS) {
+        assert_eq!(todo.text, text);
+        assert_eq!(
+            todo.tags,
+            tags.iter().map(|t| t.to_string()).collect::<Vec<_>>()
+        );
+        assert_eq!(todo.due_date.is_some(), due_in_days.is_some());
+        assert!(!todo.completed);
+    }
+}
+
+#[test]
+fn test_sample_todo_due_dates_are_in_the_future() {
+    let now = Utc::now();
+    for todo in sample_todo_list().all() {
+        if let Some(due) = todo.due_date {
+            assert!(due > now);
+        }
+    }
+}
+
+#[test]
+fn test_sample_todos_are_regular_todos() {
+    let mut list = sample_todo_list();
+    let first = list.all()[0].id;
+
+    assert_eq!(list.active_count(), SAMPLE_TODOS.len());
+    assert!(list.remove(first).is_ok());
+    assert_eq!(list.total_count(), SAMPLE_TODOS.len() - 1);
+    assert!(list.check_invariants().is_ok());
+}
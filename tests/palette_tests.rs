@@ -0,0 +1,108 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use todo::models::Todo;
+use todo::models::palette::{Command, CommandRegistry, PaletteItem, rank};
+use todo::utils::text::fuzzy_score;
+
+fn registry() -> CommandRegistry {
+    let mut registry = CommandRegistry::new();
+    registry.register(
+        Command::new("theme.toggle", "Toggle dark mode", || {}).with_keywords(&["theme", "light"]),
+    );
+    registry.register(
+        Command::new("export.json", "Export JSON", || {}).with_keywords(&["backup", "download"]),
+    );
+    registry.register(Command::new(
+        "todos.clear_completed",
+        "Clear completed",
+        || {},
+    ));
+    registry.register(Command::new("view.today", "Switch to Today view", || {}));
+    registry
+}
+
+fn todos() -> Vec<Todo> {
+    let mut done = Todo::new(3, "Export tax records".to_string());
+    done.completed = true;
+    vec![
+        Todo::new(1, "Call the dentist".to_string()),
+        Todo::new(2, "Export photos for the album".to_string()),
+        done,
+    ]
+}
+
+fn items(query: &str) -> Vec<PaletteItem> {
+    let tags = vec!["Work".to_string(), "Today-ish".to_string()];
+    rank(query, &registry(), &tags, &todos(), 8)
+        .into_iter()
+        .map(|found| found.item)
+        .collect()
+}
+
+#[test]
+fn test_fuzzy_score_prefers_word_starts_and_runs() {
+    assert!(fuzzy_score("tdm", "Toggle dark mode").is_some());
+    assert!(fuzzy_score("DARK", "Toggle dark mode").is_some());
+    assert_eq!(fuzzy_score("dentist call", "Call the dentist"), None);
+    assert!(fuzzy_score("cc", "Clear completed") > fuzzy_score("cc", "Accept"));
+    assert!(fuzzy_score("today", "Today") > fuzzy_score("today", "Switch to Today view"));
+}
+
+#[test]
+fn test_empty_query_lists_commands_then_tags() {
+    assert_eq!(
+        items(""),
+        vec![
+            PaletteItem::Command("theme.toggle"),
+            PaletteItem::Command("export.json"),
+            PaletteItem::Command("todos.clear_completed"),
+            PaletteItem::Command("view.today"),
+            PaletteItem::Tag("Work".to_string()),
+            PaletteItem::Tag("Today-ish".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_mixed_results_are_ranked_by_score() {
+    // The command's title starts with the query, the todo's only contains it
+    let results = items("export");
+    assert_eq!(
+        results,
+        vec![PaletteItem::Command("export.json"), PaletteItem::Todo(2)]
+    );
+
+    // A todo that matches better beats a command
+    assert_eq!(items("dentist")[0], PaletteItem::Todo(1));
+
+    // Tags can be typed with a leading "#"
+    assert_eq!(items("#work"), vec![PaletteItem::Tag("Work".to_string())]);
+}
+
+#[test]
+fn test_keywords_find_commands_and_completed_todos_are_left_out() {
+    assert_eq!(items("backup"), vec![PaletteItem::Command("export.json")]);
+    assert!(!items("tax").contains(&PaletteItem::Todo(3)));
+}
+
+#[test]
+fn test_results_are_limited() {
+    let tags: Vec<String> = (0..20).map(|n| format!("tag{n}")).collect();
+    assert_eq!(rank("tag", &registry(), &tags, &todos(), 5).len(), 5);
+}
+
+#[test]
+fn test_registry_runs_commands_and_replaces_by_id() {
+    let runs = Rc::new(Cell::new(0));
+    let mut registry = registry();
+    let counted = runs.clone();
+    registry.register(Command::new("view.today", "Show today", move || {
+        counted.set(counted.get() + 1)
+    }));
+
+    assert_eq!(registry.commands().len(), 4);
+    assert_eq!(registry.get("view.today").unwrap().title, "Show today");
+    assert!(registry.run("view.today"));
+    assert!(!registry.run("missing"));
+    assert_eq!(runs.get(), 1);
+}
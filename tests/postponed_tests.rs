@@ -0,0 +1,83 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use todo::models::postponed::most_postponed;
+use todo::models::{Todo, TodoList};
+use todo::utils::export::export_json;
+
+fn due() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 3, 12, 12, 0, 0).unwrap()
+}
+
+#[test]
+fn test_only_moving_a_due_date_later_counts_as_postponing() {
+    let mut todo = Todo::new(1, "File taxes".to_string());
+
+    // Setting a first date is not postponing
+    todo.set_due_date(Some(due()));
+    assert_eq!(todo.postponed_count, 0);
+
+    todo.set_due_date(Some(due() + Duration::days(1)));
+    todo.set_due_date(Some(due() + Duration::hours(30)));
+    assert_eq!(todo.postponed_count, 2);
+
+    // Earlier, unchanged and cleared dates are not
+    todo.set_due_date(Some(due()));
+    todo.set_due_date(Some(due()));
+    todo.set_due_date(None);
+    assert_eq!(todo.postponed_count, 2);
+
+    // Neither is setting a date again after clearing it
+    todo.set_due_date(Some(due() + Duration::days(30)));
+    assert_eq!(todo.postponed_count, 2);
+}
+
+#[test]
+fn test_list_set_due_date_counts_postponing() {
+    let mut list = TodoList::new();
+    let id = list.add("File taxes".to_string());
+    list.set_due_date(id, Some(due())).unwrap();
+    list.set_due_date(id, Some(due() + Duration::days(7)))
+        .unwrap();
+    assert_eq!(list.get(id).unwrap().postponed_count, 1);
+}
+
+#[test]
+fn test_most_postponed_lists_open_todos_most_postponed_first() {
+    let todo = |id: usize, postponed_count: u32| {
+        let mut todo = Todo::new(id, format!("Todo {id}"));
+        todo.postponed_count = postponed_count;
+        todo
+    };
+    let mut done = todo(1, 9);
+    done.completed = true;
+    let mut trashed = todo(2, 9);
+    trashed.deleted_at = Some(due());
+    let mut todos = vec![done, trashed, todo(3, 0)];
+    todos.extend((4..=10).map(|id| todo(id, (id % 4) as u32 + 1)));
+
+    let ids: Vec<usize> = most_postponed(&todos).iter().map(|todo| todo.id).collect();
+    assert_eq!(ids, [7, 6, 10, 5, 9]);
+}
+
+#[test]
+fn test_postponed_count_is_saved_and_exported() {
+    let mut list = TodoList::new();
+    let id = list.add("File taxes".to_string());
+    list.add("Water plants".to_string());
+    list.set_due_date(id, Some(due())).unwrap();
+    list.set_due_date(id, Some(due() + Duration::days(1)))
+        .unwrap();
+
+    let json = export_json(&list).unwrap();
+    assert_eq!(json.matches(r#""postponed_count": 1"#).count(), 1);
+
+    let restored: TodoList = serde_json::from_str(&serde_json::to_string(&list).unwrap()).unwrap();
+    assert_eq!(restored.get(id).unwrap().postponed_count, 1);
+
+    // Lists saved before the count was kept load as never postponed
+    let json = concat!(
+        r#"{"todos":[{"id":1,"text":"Call the plumber","completed":false,"#,
+        r#""due_date":null,"tags":[],"order":1}],"next_id":2}"#
+    );
+    let list: TodoList = serde_json::from_str(json).unwrap();
+    assert_eq!(list.get(1).unwrap().postponed_count, 0);
+}
@@ -0,0 +1,117 @@
+use todo::models::TodoList;
+use todo::utils::keys;
+use todo::utils::profile::{from_args, parse_name, relaunch_args, with_profile_query};
+use todo::utils::storage::{
+    MemoryStorage, PrefixedStorage, RawStorage, StorageBackend, StorageError, load_with,
+    open_profile_storage, save_with,
+};
+
+fn list_of(text: &str) -> TodoList {
+    let mut list = TodoList::new();
+    list.add(text.to_string());
+    list
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn test_profiles_in_the_memory_backend_never_see_each_other() {
+    let work = open_profile_storage(StorageBackend::Memory, Some("work")).unwrap();
+    let personal = open_profile_storage(StorageBackend::Memory, Some("personal")).unwrap();
+    let default = open_profile_storage(StorageBackend::Memory, None).unwrap();
+
+    save_with(work.as_ref(), keys::TODOS, &list_of("Quarterly review")).unwrap();
+    save_with(personal.as_ref(), keys::TODOS, &list_of("Book the dentist")).unwrap();
+
+    let from_work: TodoList = load_with(work.as_ref(), keys::TODOS).unwrap();
+    let from_personal: TodoList = load_with(personal.as_ref(), keys::TODOS).unwrap();
+    assert_eq!(from_work.all()[0].text, "Quarterly review");
+    assert_eq!(from_personal.all()[0].text, "Book the dentist");
+    assert!(matches!(
+        load_with::<TodoList>(default.as_ref(), keys::TODOS),
+        Err(StorageError::NotFound(_))
+    ));
+
+    // Each profile lists and deletes only its own keys
+    assert_eq!(work.list_keys().unwrap(), [keys::TODOS]);
+    work.delete(keys::TODOS).unwrap();
+    assert!(work.list_keys().unwrap().is_empty());
+    assert_eq!(personal.list_keys().unwrap(), [keys::TODOS]);
+
+    // Opening a profile again finds its data
+    let personal_again = open_profile_storage(StorageBackend::Memory, Some("personal")).unwrap();
+    let reopened: TodoList = load_with(personal_again.as_ref(), keys::TODOS).unwrap();
+    assert_eq!(reopened, from_personal);
+}
+
+#[test]
+fn test_prefixed_storage_hides_unprefixed_keys() {
+    let shared = MemoryStorage::new();
+    shared.save_str("todo/v1/theme", "\"dark\"").unwrap();
+    let work = PrefixedStorage::new(Box::new(shared.clone()), "profiles/work/".to_string());
+
+    assert!(work.list_keys().unwrap().is_empty());
+    assert!(matches!(
+        work.load_str("todo/v1/theme"),
+        Err(StorageError::NotFound(_))
+    ));
+
+    work.save_str("todo/v1/theme", "\"light\"").unwrap();
+    assert_eq!(shared.load_str("todo/v1/theme").unwrap(), "\"dark\"");
+    assert_eq!(
+        shared.load_str("profiles/work/todo/v1/theme").unwrap(),
+        "\"light\""
+    );
+}
+
+#[test]
+fn test_profile_names_are_safe_for_paths_and_keys() {
+    assert_eq!(
+        parse_name("work_laptop-2"),
+        Some("work_laptop-2".to_string())
+    );
+    assert_eq!(parse_name("DEFAULT"), None);
+    for bad in [
+        "",
+        "   ",
+        "a/b",
+        "..",
+        "work laptop",
+        "wörk",
+        &"x".repeat(33),
+    ] {
+        assert_eq!(parse_name(bad), None, "{bad:?}");
+    }
+}
+
+#[test]
+fn test_profile_from_command_line() {
+    assert_eq!(
+        from_args(args(&["--profile", "Work"])),
+        Some("work".to_string())
+    );
+    assert_eq!(
+        from_args(args(&["--verbose", "--profile=home"])),
+        Some("home".to_string())
+    );
+    // A missing or unusable name falls back to the default profile
+    assert_eq!(from_args(args(&["--profile"])), None);
+    assert_eq!(from_args(args(&["--profile", "../etc"])), None);
+    assert_eq!(from_args(args(&["--profiles", "work"])), None);
+}
+
+#[test]
+fn test_switching_keeps_other_arguments_and_parameters() {
+    assert_eq!(
+        relaunch_args(args(&["--profile=work", "--verbose"]), Some("home")),
+        args(&["--verbose", "--profile", "home"])
+    );
+    assert_eq!(
+        relaunch_args(args(&["--verbose"]), None),
+        args(&["--verbose"])
+    );
+    assert_eq!(with_profile_query("", Some("work")), "?profile=work");
+    assert_eq!(with_profile_query("?demo=1&profile=work", None), "?demo=1");
+}
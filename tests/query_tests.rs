@@ -0,0 +1,164 @@
+mod common;
+
+use chrono::{Duration, TimeZone, Utc};
+use common::clock;
+use todo::models::query::parse_duration;
+use todo::models::{SearchQuery, Todo, TodoList};
+use todo::utils::clock::Clock;
+use todo::utils::dates::IsoWeek;
+
+#[test]
+fn test_parse_duration_units() {
+    assert_eq!(parse_duration("7d"), Some(Duration::days(7)));
+    assert_eq!(parse_duration("2w"), Some(Duration::weeks(2)));
+    assert_eq!(parse_duration("3m"), Some(Duration::days(90)));
+    assert_eq!(parse_duration("1D"), Some(Duration::days(1)));
+    assert_eq!(parse_duration("0d"), Some(Duration::zero()));
+}
+
+#[test]
+fn test_parse_duration_rejects_invalid_values() {
+    for value in [
+        "",
+        "d",
+        "7",
+        "7y",
+        "-1d",
+        "1.5d",
+        "seven d",
+        "99999999999999999m",
+    ] {
+        assert_eq!(parse_duration(value), None, "{value:?} should not parse");
+    }
+}
+
+#[test]
+fn test_parse_query_operators() {
+    let query = SearchQuery::parse("Report completed:<7d");
+    assert_eq!(query.text, "report");
+    assert_eq!(query.completed_within, Some(Duration::days(7)));
+    assert!(!query.deleted);
+
+    let query = SearchQuery::parse("deleted:any  milk");
+    assert_eq!(query.text, "milk");
+    assert_eq!(query.completed_within, None);
+    assert!(query.deleted);
+
+    let query = SearchQuery::parse("DELETED:Any Completed:<1M");
+    assert!(query.deleted);
+    assert_eq!(query.completed_within, Some(Duration::days(30)));
+    assert!(query.text.is_empty());
+}
+
+#[test]
+fn test_parse_query_keeps_invalid_operators_as_text() {
+    let query = SearchQuery::parse("completed:soon deleted:yesterday completed:7d note:");
+    assert_eq!(
+        query.text,
+        "completed:soon deleted:yesterday completed:7d note:"
+    );
+    assert_eq!(query.completed_within, None);
+    assert!(!query.deleted);
+
+    assert_eq!(SearchQuery::parse(""), SearchQuery::default());
+}
+
+#[test]
+fn test_query_matches_completed_within() {
+    let now = clock().now_utc();
+    let query = SearchQuery::parse("completed:<7d");
+
+    let mut recent = Todo::new(1, "Recent".to_string());
+    recent.completed = true;
+    recent.completed_at = Some(now - Duration::days(2));
+    assert!(query.matches(&recent, now));
+
+    let mut old = Todo::new(2, "Old".to_string());
+    old.completed = true;
+    old.completed_at = Some(now - Duration::days(8));
+    assert!(!query.matches(&old, now));
+
+    // Completed before completion times were recorded
+    let mut legacy = Todo::new(3, "Legacy".to_string());
+    legacy.completed = true;
+    assert!(!query.matches(&legacy, now));
+
+    let open = Todo::new(4, "Open".to_string());
+    assert!(!query.matches(&open, now));
+    assert!(SearchQuery::parse("open").matches(&open, now));
+}
+
+#[test]
+fn test_query_matches_text_and_operator_together() {
+    let now = clock().now_utc();
+    let mut list = TodoList::new();
+    let report = list.add("Write report".to_string());
+    let milk = list.add("Buy milk".to_string());
+    list.add("Call mom".to_string());
+    list.toggle(report);
+    list.toggle(milk);
+
+    let query = SearchQuery::parse("completed:<1w report");
+    let found: Vec<usize> = list
+        .all()
+        .iter()
+        .filter(|todo| query.matches(todo, now))
+        .map(|todo| todo.id)
+        .collect();
+    assert_eq!(found, vec![report]);
+}
+
+#[test]
+fn test_deleted_query_searches_trash() {
+    let now = clock().now_utc();
+    let mut list = TodoList::new();
+    let milk = list.add("Buy milk".to_string());
+    list.add("Buy bread".to_string());
+    assert!(list.move_to_trash(milk).is_ok());
+
+    let query = SearchQuery::parse("deleted:any buy");
+    assert!(query.deleted);
+    let found: Vec<String> = list
+        .trashed()
+        .into_iter()
+        .filter(|todo| query.matches(todo, now))
+        .map(|todo| todo.text)
+        .collect();
+    assert_eq!(found, vec!["Buy milk".to_string()]);
+}
+
+#[test]
+fn test_parse_week_operator() {
+    let query = SearchQuery::parse("week:2025-W12 slides");
+    assert_eq!(query.text, "slides");
+    assert_eq!(
+        query.due_week,
+        Some(IsoWeek {
+            year: 2025,
+            week: 12
+        })
+    );
+
+    // Weeks the year does not have are searched for as text
+    let query = SearchQuery::parse("week:2025-W53 week:soon");
+    assert_eq!(query.due_week, None);
+    assert_eq!(query.text, "week:2025-w53 week:soon");
+}
+
+#[test]
+fn test_query_matches_due_week() {
+    let now = clock().now_utc();
+    let query = SearchQuery::parse("week:2025-W01");
+
+    let due = |id, year, month, day| {
+        let mut todo = Todo::new(id, format!("Todo {id}"));
+        todo.due_date = Some(Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap());
+        todo
+    };
+    // 2025-W01 runs from Monday 2024-12-30 to Sunday 2025-01-05
+    assert!(query.matches(&due(1, 2024, 12, 30), now));
+    assert!(query.matches(&due(2, 2025, 1, 5), now));
+    assert!(!query.matches(&due(3, 2024, 12, 29), now));
+    assert!(!query.matches(&due(4, 2025, 1, 6), now));
+    assert!(!query.matches(&Todo::new(5, "No due date".to_string()), now));
+}
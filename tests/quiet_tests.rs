@@ -0,0 +1,73 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use todo::models::quiet::{QuietFor, QuietMode};
+use todo::utils::clock::{Clock, FixedClock};
+
+fn evening() -> FixedClock {
+    // 22:30 on March 12 in a time zone five hours west of UTC
+    FixedClock::at_local(NaiveDate::from_ymd_opt(2025, 3, 12).unwrap(), 22, 30, -5)
+}
+
+fn local(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2025, 3, day)
+        .unwrap()
+        .and_hms_opt(hour, minute, 0)
+        .unwrap()
+}
+
+#[test]
+fn test_one_hour_runs_out_after_an_hour() {
+    let mut clock = evening();
+    let quiet = QuietMode::start(QuietFor::OneHour, &clock);
+    assert!(!quiet.notifications_allowed(clock.now_utc()));
+
+    clock.advance(Duration::minutes(59));
+    assert!(!quiet.notifications_allowed(clock.now_utc()));
+
+    clock.advance(Duration::minutes(1));
+    assert!(quiet.notifications_allowed(clock.now_utc()));
+    assert_eq!(quiet.active_until(clock.now_utc()), None);
+}
+
+#[test]
+fn test_until_tomorrow_ends_in_the_local_morning() {
+    let mut clock = evening();
+    let quiet = QuietMode::start(QuietFor::UntilTomorrow, &clock);
+
+    // 8:00 local on March 13 is 13:00 UTC
+    let until = quiet.until.unwrap();
+    assert_eq!(until.to_rfc3339(), "2025-03-13T13:00:00+00:00");
+
+    // Just after midnight it is still quiet until the morning
+    clock.advance(Duration::minutes(90));
+    assert_eq!(
+        clock.today_local(),
+        NaiveDate::from_ymd_opt(2025, 3, 13).unwrap()
+    );
+    assert!(!quiet.notifications_allowed(clock.now_utc()));
+}
+
+#[test]
+fn test_custom_time_is_local_and_must_be_ahead() {
+    let clock = evening();
+
+    let quiet = QuietMode::start(QuietFor::Until(local(12, 23, 45)), &clock);
+    assert_eq!(quiet.until, Some(clock.now_utc() + Duration::minutes(75)));
+
+    // A time already past leaves quiet mode off
+    let past = QuietMode::start(QuietFor::Until(local(12, 22, 0)), &clock);
+    assert_eq!(past, QuietMode::default());
+    assert!(past.notifications_allowed(clock.now_utc()));
+}
+
+#[test]
+fn test_quiet_mode_survives_a_restart() {
+    let mut clock = evening();
+    let quiet = QuietMode::start(QuietFor::OneHour, &clock);
+
+    let stored = serde_json::to_string(&quiet).unwrap();
+    let restored: QuietMode = serde_json::from_str(&stored).unwrap();
+    assert_eq!(restored, quiet);
+
+    clock.advance(Duration::minutes(30));
+    assert!(!restored.notifications_allowed(clock.now_utc()));
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use common::clock_at;
+use todo::models::reminder::{next_reminder, snooze_end};
+use todo::models::{Todo, TodoList};
+use todo::utils::clock::Clock;
+
+/// Builds a list with one open todo due some hours after `now`.
+fn list_due_in(hours: i64, now: DateTime<Utc>) -> (TodoList, usize) {
+    let mut list = TodoList::new();
+    let id = list.add("Send the invoice".to_string());
+    list.set_due_date(id, Some(now + Duration::hours(hours)))
+        .unwrap();
+    (list, id)
+}
+
+#[test]
+fn test_next_reminder_combines_due_date_lead_and_snooze() {
+    let now = clock_at(14, 0).now_utc();
+    let lead = Duration::hours(2);
+    let mut todo = Todo::new(1, "Send the invoice".to_string());
+    assert_eq!(next_reminder(&todo, None, lead), None, "undated");
+
+    let due = now + Duration::hours(3);
+    todo.due_date = Some(due);
+    assert_eq!(
+        next_reminder(&todo, None, lead),
+        Some(now + Duration::hours(1))
+    );
+
+    // A snooze puts the reminder off, but one ending before the lead time
+    // does not bring it forward
+    let snoozed = snooze_end(now + Duration::hours(1));
+    assert_eq!(snoozed, now + Duration::hours(2));
+    assert_eq!(next_reminder(&todo, Some(snoozed), lead), Some(snoozed));
+    assert_eq!(
+        next_reminder(&todo, Some(now), lead),
+        Some(now + Duration::hours(1))
+    );
+
+    todo.completed = true;
+    assert_eq!(next_reminder(&todo, Some(snoozed), lead), None);
+}
+
+#[test]
+fn test_snoozed_todo_leaves_the_due_soon_window_until_the_snooze_ends() {
+    let mut clock = clock_at(14, 0);
+    let lead = Duration::hours(24);
+    let (mut list, id) = list_due_in(3, clock.now_utc());
+    assert_eq!(list.due_soon(clock.now_utc(), lead).len(), 1);
+
+    assert!(list.snooze(id, snooze_end(clock.now_utc())));
+    assert!(list.due_soon(clock.now_utc(), lead).is_empty());
+
+    clock.advance(Duration::minutes(59));
+    assert!(list.due_soon(clock.now_utc(), lead).is_empty());
+    clock.advance(Duration::minutes(1));
+    assert_eq!(list.due_soon(clock.now_utc(), lead).len(), 1);
+
+    // Cancelling brings the reminder back right away
+    list.snooze(id, snooze_end(clock.now_utc()));
+    assert!(list.cancel_snooze(id));
+    assert!(!list.cancel_snooze(id));
+    assert_eq!(list.due_soon(clock.now_utc(), lead).len(), 1);
+}
+
+#[test]
+fn test_snoozes_survive_a_save_and_load() {
+    let now = clock_at(14, 0).now_utc();
+    let (mut list, id) = list_due_in(3, now);
+    let json = serde_json::to_string(&list).unwrap();
+    assert!(!json.contains("snoozes"), "no snoozes, nothing written");
+
+    list.snooze(id, snooze_end(now));
+    let json = serde_json::to_string(&list).unwrap();
+    let loaded: TodoList = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.snoozed_until(id), Some(snooze_end(now)));
+    assert_eq!(loaded, list);
+}
+
+#[test]
+fn test_snoozes_are_forgotten_for_closed_todos() {
+    let now = clock_at(14, 0).now_utc();
+    let (mut list, id) = list_due_in(3, now);
+    let other = list.add("Book the venue".to_string());
+    assert!(!list.snooze(999, snooze_end(now)), "unknown todo");
+
+    list.snooze(id, snooze_end(now));
+    list.toggle(id);
+    assert_eq!(list.snoozed_until(id), None, "completed");
+    assert!(
+        !list.snooze(id, snooze_end(now)),
+        "completed todos have no reminder"
+    );
+
+    list.snooze(other, snooze_end(now));
+    list.move_to_trash(other).unwrap();
+    list.restore(other);
+    assert_eq!(list.snoozed_until(other), None, "deleted");
+
+    list.snooze(other, snooze_end(now));
+    list.archive(other);
+    assert_eq!(list.snoozed_until(other), None, "archived");
+}
+
+#[test]
+fn test_prune_snoozes() {
+    let mut clock = clock_at(14, 0);
+    let (mut list, id) = list_due_in(3, clock.now_utc());
+    let other = list.add("Book the venue".to_string());
+    list.snooze(id, snooze_end(clock.now_utc()));
+    list.snooze(other, snooze_end(clock.now_utc()) + Duration::hours(1));
+    assert_eq!(list.prune_snoozes(clock.now_utc()), 0);
+
+    clock.advance(Duration::hours(1));
+    assert_eq!(list.prune_snoozes(clock.now_utc()), 1, "run out");
+    assert_eq!(list.snoozed_until(id), None);
+    assert!(list.snoozed_until(other).is_some());
+
+    // Stored data may hold a snooze of a todo completed by a sync
+    let mut todo = list.get(other).unwrap().clone();
+    todo.completed = true;
+    list.update_from_remote(other, &todo);
+    assert_eq!(list.prune_snoozes(clock.now_utc()), 1);
+}
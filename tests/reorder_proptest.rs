@@ -0,0 +1,144 @@
+use proptest::prelude::*;
+use todo::models::TodoList;
+
+/// A single mutation applied to both the real list and the reference model.
+/// Indices are reduced modulo the current length when applied.
+#[derive(Clone, Debug)]
+enum Op {
+    Add,
+    Remove(usize),
+    Reorder(usize, usize),
+    Toggle(usize),
+    ClearCompleted,
+}
+
+fn op_strategy() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        3 => Just(Op::Add),
+        1 => any::<usize>().prop_map(Op::Remove),
+        3 => (any::<usize>(), any::<usize>()).prop_map(|(a, b)| Op::Reorder(a, b)),
+        2 => any::<usize>().prop_map(Op::Toggle),
+        1 => Just(Op::ClearCompleted),
+    ]
+}
+
+/// Naive reference model: (id, completed) pairs in display order.
+#[derive(Default)]
+struct Model {
+    items: Vec<(usize, bool)>,
+}
+
+proptest! {
+    #[test]
+    fn reorder_matches_reference_model(ops in prop::collection::vec(op_strategy(), 1..60)) {
+        let mut list = TodoList::new();
+        let mut model = Model::default();
+
+        for op in ops {
+            match op {
+                Op::Add => {
+                    let id = list.add(format!("todo {}", model.items.len()));
+                    model.items.push((id, false));
+                }
+                Op::Remove(index) if !model.items.is_empty() => {
+                    let (id, _) = model.items.remove(index % model.items.len());
+                    prop_assert!(list.remove(id).is_ok());
+                }
+                Op::Reorder(from, to) if !model.items.is_empty() => {
+                    let from = from % model.items.len();
+                    let to = to % model.items.len();
+                    let (source_id, target_id) = (model.items[from].0, model.items[to].0);
+                    let moved = list.reorder(source_id, target_id);
+                    prop_assert_eq!(moved, from != to);
+                    if from != to {
+                        let item = model.items.remove(from);
+                        model.items.insert(to, item);
+                    }
+                }
+                Op::Toggle(index) if !model.items.is_empty() => {
+                    let index = index % model.items.len();
+                    prop_assert!(list.toggle(model.items[index].0));
+                    model.items[index].1 = !model.items[index].1;
+                }
+                Op::ClearCompleted => {
+                    let before = model.items.len();
+                    model.items.retain(|(_, completed)| !completed);
+                    prop_assert_eq!(list.clear_completed().removed.len(), before - model.items.len());
+                }
+                _ => {}
+            }
+
+            prop_assert_eq!(list.check_invariants(), Ok(()));
+            let actual: Vec<(usize, bool)> =
+                list.all().iter().map(|todo| (todo.id, todo.completed)).collect();
+            prop_assert_eq!(&actual, &model.items);
+        }
+    }
+
+    #[test]
+    fn order_snapshot_undoes_any_reorder(count in 1usize..20, from in any::<usize>(), to in any::<usize>()) {
+        let mut list = TodoList::new();
+        let ids: Vec<usize> = (0..count).map(|i| list.add(format!("todo {}", i))).collect();
+        let before = list.order_snapshot();
+
+        list.reorder(ids[from % count], ids[to % count]);
+        list.set_order_snapshot(before.clone());
+
+        prop_assert_eq!(list.check_invariants(), Ok(()));
+        prop_assert_eq!(list.order_snapshot(), before);
+    }
+}
+
+#[test]
+fn test_adjacent_swaps_and_moves_to_ends() {
+    let mut list = TodoList::new();
+    let ids: Vec<usize> = (0..4).map(|i| list.add(format!("todo {}", i))).collect();
+
+    // Adjacent swap down and back up
+    assert!(list.reorder(ids[0], ids[1]));
+    assert!(list.reorder(ids[0], ids[1]));
+    // Move the last item to the front, then the front to the end
+    assert!(list.reorder(ids[3], ids[0]));
+    assert!(list.reorder(ids[3], ids[2]));
+
+    assert_eq!(list.check_invariants(), Ok(()));
+    let order: Vec<usize> = list.all().iter().map(|todo| todo.id).collect();
+    assert_eq!(order, vec![ids[0], ids[1], ids[2], ids[3]]);
+}
+
+#[test]
+fn test_invariants_hold_after_removals_and_adds() {
+    let mut list = TodoList::new();
+    let first = list.add("first".to_string());
+    list.add("second".to_string());
+    list.remove(first).unwrap();
+    list.add("third".to_string());
+
+    assert_eq!(list.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_order_snapshot_skips_removed_and_keeps_new_todos_last() {
+    let mut list = TodoList::new();
+    let ids: Vec<usize> = (0..4).map(|i| list.add(format!("todo {}", i))).collect();
+    let before = list.order_snapshot();
+
+    assert!(list.reorder(ids[3], ids[0]));
+    list.remove(ids[1]).unwrap();
+    let added = list.add("added".to_string());
+    assert!(list.reorder(added, ids[3]));
+
+    assert_eq!(list.set_order_snapshot(before), 4);
+    assert_eq!(list.check_invariants(), Ok(()));
+    let order: Vec<usize> = list.all().iter().map(|todo| todo.id).collect();
+    assert_eq!(order, vec![ids[0], ids[2], ids[3], added]);
+}
+
+#[test]
+fn test_restoring_the_current_order_moves_nothing() {
+    let mut list = TodoList::new();
+    list.add("first".to_string());
+    list.add("second".to_string());
+
+    assert_eq!(list.set_order_snapshot(list.order_snapshot()), 0);
+}
@@ -0,0 +1,111 @@
+use chrono::{Duration, NaiveDate};
+use todo::models::{Todo, TodoList, WeekStart};
+use todo::utils::dates;
+use todo::utils::reports::{escape_html, weekly_html};
+
+/// Wednesday 12 March 2025; its week runs from Monday 10 to Sunday 16 March.
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, 12).unwrap()
+}
+
+/// Builds a list with a todo completed this week, one completed last week,
+/// one overdue, one due next week and one due later.
+fn sample_list() -> TodoList {
+    let todo = |id: usize, text: &str, tags: &[&str]| {
+        let mut todo = Todo::new(id, text.to_string());
+        todo.order = id;
+        todo.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        todo
+    };
+    let completed_on = |mut todo: Todo, day: NaiveDate| {
+        todo.completed = true;
+        todo.completed_at = Some(dates::due_date_on(day));
+        todo
+    };
+    let due_in = |mut todo: Todo, days: i64| {
+        todo.due_date = Some(dates::due_date_on(today() + Duration::days(days)));
+        todo
+    };
+
+    TodoList::from_todos(vec![
+        completed_on(todo(1, "Ship release", &["Work"]), today()),
+        completed_on(todo(2, "Old chore", &[]), today() - Duration::days(7)),
+        due_in(todo(3, "Renew passport", &["Personal"]), -3),
+        due_in(todo(4, "Book dentist", &["Personal"]), 6),
+        due_in(todo(5, "Plan holiday", &[]), 20),
+    ])
+}
+
+/// Gets the part of the page from one heading to the next.
+fn section<'a>(html: &'a str, heading: &str) -> &'a str {
+    let start = html.find(heading).expect("the section is in the report");
+    let rest = &html[start + heading.len()..];
+    &rest[..rest.find("<h2").unwrap_or(rest.len())]
+}
+
+#[test]
+fn test_escape_html_escapes_markup_characters() {
+    assert_eq!(
+        escape_html(r#"<b>"Tom" & 'Jerry'</b>"#),
+        "&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;"
+    );
+    assert_eq!(escape_html("already &amp;"), "already &amp;amp;");
+    assert_eq!(escape_html("plain"), "plain");
+}
+
+#[test]
+fn test_report_sorts_todos_into_sections() {
+    let html = weekly_html(&sample_list(), today(), WeekStart::Monday);
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("Weekly report: Mar 10 – Mar 16, 2025"));
+
+    let completed = section(&html, "Completed this week (1)");
+    assert!(completed.contains("Ship release"));
+    assert!(!completed.contains("Old chore"));
+    let overdue = section(&html, "Overdue (1)");
+    assert!(overdue.contains("Renew passport") && overdue.contains("· Mar 9"));
+    let next_week = section(&html, "Due next week (1)");
+    assert!(next_week.contains("Book dentist"));
+    assert!(!html.contains("Plan holiday"));
+}
+
+#[test]
+fn test_report_breaks_todos_down_by_tag() {
+    let html = weekly_html(&sample_list(), today(), WeekStart::Monday);
+    let by_tag = section(&html, "By tag");
+
+    assert!(by_tag.contains(concat!(
+        r#"<td style="padding:4px 12px 4px 0;">Personal</td>"#,
+        r#"<td style="text-align:right;padding:4px 12px;">0</td>"#,
+        r#"<td style="text-align:right;padding:4px 0 4px 12px;">2</td>"#
+    )));
+    assert!(by_tag.contains(">Work</td><td style=\"text-align:right;padding:4px 12px;\">1</td>"));
+}
+
+#[test]
+fn test_report_escapes_todo_text_and_keeps_unicode() {
+    let mut list = TodoList::new();
+    let id = list.add("Fix <script>alert(1)</script> & tidy up ✨ naïve café".to_string());
+    list.add_tag(id, "R&D <core>".to_string());
+    list.set_due_date(id, Some(dates::due_date_on(today() - Duration::days(1))))
+        .unwrap();
+
+    let html = weekly_html(&list, today(), WeekStart::Monday);
+
+    assert!(!html.contains("<script>"));
+    assert!(html.contains("Fix &lt;script&gt;alert(1)&lt;/script&gt; &amp; tidy up ✨ naïve café"));
+    assert!(html.contains("R&amp;D &lt;core&gt;"));
+    assert!(html.contains(r#"<meta charset="utf-8">"#));
+}
+
+#[test]
+fn test_empty_report_says_so() {
+    let html = weekly_html(&TodoList::new(), today(), WeekStart::Sunday);
+
+    assert!(html.contains("Weekly report: Mar 9 – Mar 15, 2025"));
+    assert!(html.contains("Nothing completed yet."));
+    assert!(html.contains("Nothing overdue."));
+    assert!(html.contains("Nothing due next week."));
+    assert!(html.contains("No tagged todos."));
+}
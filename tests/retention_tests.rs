@@ -0,0 +1,185 @@
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use common::clock;
+use todo::models::retention::{Purgeable, RetentionPolicy, select_purgeable};
+use todo::models::{AppSettings, Todo, TodoList};
+use todo::utils::clock::Clock;
+
+/// Builds a todo moved to the trash the given number of days ago, or
+/// trashed before deletion times were recorded for `None`.
+fn trashed(id: usize, days_ago: Option<i64>, now: DateTime<Utc>) -> Todo {
+    let mut todo = Todo::new(id, format!("Trashed {id}"));
+    todo.deleted_at = days_ago.map(|days| now - Duration::days(days));
+    todo
+}
+
+/// Builds a todo archived the given number of days ago, or archived before
+/// archive times were recorded for `None`.
+fn archived(id: usize, days_ago: Option<i64>, now: DateTime<Utc>) -> Todo {
+    let mut todo = Todo::new(id, format!("Archived {id}"));
+    todo.completed = true;
+    todo.archived = true;
+    todo.archived_at = days_ago.map(|days| now - Duration::days(days));
+    todo
+}
+
+/// Builds a list holding the given todos and trash, the way it is stored.
+fn list(todos: Vec<Todo>, trash: Vec<Todo>) -> TodoList {
+    serde_json::from_value(serde_json::json!({
+        "todos": todos,
+        "trash": trash,
+        "next_id": 100,
+    }))
+    .unwrap()
+}
+
+fn policy(trash_days: Option<u32>, archive_days: Option<u32>) -> RetentionPolicy {
+    RetentionPolicy {
+        trash_days,
+        archive_days,
+    }
+}
+
+#[test]
+fn test_trash_is_purged_by_deletion_age() {
+    let now = clock().now_utc();
+    let list = list(
+        vec![Todo::new(1, "Open".to_string())],
+        vec![
+            trashed(2, Some(45), now),
+            trashed(3, Some(3), now),
+            trashed(4, Some(31), now),
+            trashed(5, None, now),
+        ],
+    );
+
+    assert_eq!(
+        select_purgeable(&list, policy(Some(30), None), now).trashed,
+        vec![2, 4]
+    );
+    assert_eq!(
+        select_purgeable(&list, policy(Some(1), None), now).trashed,
+        vec![2, 3, 4]
+    );
+    assert!(select_purgeable(&list, policy(None, None), now).is_empty());
+}
+
+#[test]
+fn test_archive_is_purged_by_archive_age() {
+    let now = clock().now_utc();
+    let mut locked = archived(4, Some(800), now);
+    locked.locked = true;
+    let list = list(
+        vec![
+            archived(1, Some(400), now),
+            archived(2, Some(200), now),
+            archived(3, None, now),
+            locked,
+            Todo::new(5, "Open".to_string()),
+        ],
+        Vec::new(),
+    );
+
+    // Locked todos and todos archived before archive times were recorded stay
+    assert_eq!(
+        select_purgeable(&list, policy(None, Some(365)), now),
+        Purgeable {
+            trashed: Vec::new(),
+            archived: vec![1],
+        }
+    );
+    assert_eq!(
+        select_purgeable(&list, policy(None, Some(30)), now).archived,
+        vec![1, 2]
+    );
+    assert!(select_purgeable(&list, policy(Some(30), None), now).is_empty());
+}
+
+#[test]
+fn test_retention_boundary() {
+    let now = clock().now_utc();
+    let list = list(
+        vec![archived(1, Some(365), now)],
+        vec![trashed(2, Some(30), now)],
+    );
+    let policy = policy(Some(30), Some(365));
+
+    // A todo is deleted only once it is older than the limit
+    assert!(select_purgeable(&list, policy, now).is_empty());
+    assert_eq!(
+        select_purgeable(&list, policy, now + Duration::seconds(1)),
+        Purgeable {
+            trashed: vec![2],
+            archived: vec![1],
+        }
+    );
+}
+
+#[test]
+fn test_applying_deletes_for_good() {
+    let now = clock().now_utc();
+    let mut list = list(
+        vec![
+            Todo::new(1, "Open".to_string()),
+            archived(2, Some(400), now),
+            archived(3, Some(10), now),
+        ],
+        vec![trashed(4, Some(60), now), trashed(5, Some(1), now)],
+    );
+
+    let purgeable = select_purgeable(&list, policy(Some(30), Some(365)), now);
+    assert_eq!(purgeable.apply(&mut list), 2);
+
+    assert!(list.get(2).is_none());
+    assert!(!list.restore(4));
+    assert_eq!(
+        list.archived()
+            .iter()
+            .map(|todo| todo.id)
+            .collect::<Vec<_>>(),
+        [3]
+    );
+    assert_eq!(list.trash_count(), 1);
+    assert_eq!(list.all().len(), 1);
+    list.check_invariants().unwrap();
+
+    // Applying again finds nothing left to delete
+    assert_eq!(purgeable.apply(&mut list), 0);
+}
+
+#[test]
+fn test_archiving_records_when() {
+    let mut list = TodoList::new();
+    let id = list.add("Old project".to_string());
+    list.toggle(id);
+
+    list.archive(id);
+    assert!(list.get(id).unwrap().archived_at.is_some());
+    list.unarchive(id);
+    assert_eq!(list.get(id).unwrap().archived_at, None);
+
+    assert_eq!(list.archive_completed(&[id]), vec![id]);
+    assert!(list.get(id).unwrap().archived_at.is_some());
+}
+
+#[test]
+fn test_policy_from_settings() {
+    // The trash keeps todos for 30 days and the archive keeps them forever,
+    // also for settings saved before retention could be set
+    let settings: AppSettings = serde_json::from_str("{}").unwrap();
+    assert_eq!(
+        RetentionPolicy::from_settings(&settings),
+        policy(Some(30), None)
+    );
+
+    let settings = AppSettings {
+        trash_retention_days: None,
+        archive_retention_days: Some(365),
+        ..AppSettings::default()
+    };
+    assert_eq!(
+        RetentionPolicy::from_settings(&settings),
+        policy(None, Some(365))
+    );
+}
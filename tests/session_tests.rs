@@ -0,0 +1,83 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use todo::models::session::is_added_since;
+use todo::models::{ListView, SearchQuery, Todo};
+use todo::utils::clock::{Clock, FixedClock};
+
+/// A todo added the given number of minutes after `start`, or with no
+/// creation time for `None`.
+fn todo_added(id: usize, start: DateTime<Utc>, minutes: Option<i64>) -> Todo {
+    let mut todo = Todo::new(id, format!("Idea {}", id));
+    todo.created_at = minutes.map(|minutes| start + Duration::minutes(minutes));
+    todo
+}
+
+fn launch() -> FixedClock {
+    FixedClock::at_local(NaiveDate::from_ymd_opt(2025, 3, 12).unwrap(), 9, 0, 1)
+}
+
+#[test]
+fn test_added_since_counts_from_the_session_start() {
+    let start = launch().now_utc();
+    assert!(is_added_since(&todo_added(1, start, Some(0)), start));
+    assert!(is_added_since(&todo_added(2, start, Some(5)), start));
+    assert!(!is_added_since(&todo_added(3, start, Some(-1)), start));
+    assert!(!is_added_since(&todo_added(4, start, None), start));
+}
+
+#[test]
+fn test_is_new_lists_todos_added_this_session() {
+    let mut clock = launch();
+    let start = clock.now_utc();
+    let todos = vec![
+        todo_added(1, start, Some(-60 * 24)),
+        todo_added(2, start, Some(2)),
+        todo_added(3, start, None),
+        todo_added(4, start, Some(10)),
+    ];
+    clock.advance(Duration::minutes(30));
+
+    let query = SearchQuery::parse("is:new");
+    assert!(query.added_this_session);
+    assert_eq!(query.text, "");
+    assert!(SearchQuery::parse("IS:New").added_this_session);
+
+    let view = ListView {
+        query,
+        session_start: Some(start),
+        ..ListView::default()
+    };
+    let (now, today) = (clock.now_utc(), clock.today_local());
+    assert_eq!(view.visible_ids(&todos, now, today), vec![2, 4]);
+
+    // Combines with the text of the search
+    let view = ListView {
+        query: SearchQuery::parse("is:new idea 4"),
+        ..view
+    };
+    assert_eq!(view.visible_ids(&todos, now, today), vec![4]);
+
+    // Without a session nothing is new
+    let view = ListView {
+        session_start: None,
+        ..view
+    };
+    assert!(view.visible_ids(&todos, now, today).is_empty());
+}
+
+#[test]
+fn test_restart_starts_a_new_session() {
+    let mut clock = launch();
+    let first_start = clock.now_utc();
+    let todos = vec![todo_added(1, first_start, Some(5))];
+    clock.advance(Duration::hours(2));
+
+    let view = ListView {
+        query: SearchQuery::parse("is:new"),
+        session_start: Some(clock.now_utc()),
+        ..ListView::default()
+    };
+    assert!(
+        view.visible_ids(&todos, clock.now_utc(), clock.today_local())
+            .is_empty()
+    );
+}
@@ -0,0 +1,234 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{TimeZone, Utc};
+use todo::models::todo::RemoteLink;
+use todo::models::{Todo, TodoList};
+use todo::utils::constants::share::MAX_SNAPSHOT_BYTES;
+use todo::utils::share::{
+    SharedTodo, SnapshotError, decode_snapshot, encode_snapshot, format_todo, fragment_payload,
+    parse_todo, snapshot_fragment,
+};
+
+fn sample_todo() -> Todo {
+    let mut todo = Todo::new(1, "Prepare slides".to_string());
+    todo.set_due_date(Some(Utc.with_ymd_and_hms(2025, 3, 4, 0, 0, 0).unwrap()));
+    todo.add_tag("Work".to_string());
+    todo.add_tag("Urgent".to_string());
+    todo
+}
+
+#[test]
+fn test_format_todo() {
+    let mut todo = sample_todo();
+    assert_eq!(
+        format_todo(&todo),
+        "☐ Prepare slides — due 2025-03-04 #Work #Urgent"
+    );
+
+    todo.toggle();
+    todo.set_due_date(None);
+    todo.tags.clear();
+    assert_eq!(format_todo(&todo), "☑ Prepare slides");
+}
+
+#[test]
+fn test_share_round_trip() {
+    let mut todo = sample_todo();
+    for completed in [false, true] {
+        todo.completed = completed;
+        let parsed = parse_todo(&format_todo(&todo)).unwrap();
+        assert_eq!(
+            parsed,
+            SharedTodo {
+                text: todo.text.clone(),
+                completed,
+                due_date: todo.due_date,
+                tags: todo.tags.clone(),
+            }
+        );
+    }
+}
+
+#[test]
+fn test_format_todo_keeps_tags_as_single_words() {
+    let mut todo = Todo::new(1, "Call the bank".to_string());
+    todo.add_tag("Home office".to_string());
+
+    assert_eq!(format_todo(&todo), "☐ Call the bank #Home-office");
+    assert_eq!(
+        parse_todo(&format_todo(&todo)).unwrap().tags,
+        vec!["Home-office"]
+    );
+}
+
+#[test]
+fn test_parse_markdown_checkbox() {
+    let open = parse_todo("- [ ] Buy milk #Shopping").unwrap();
+    assert_eq!(open.text, "Buy milk");
+    assert!(!open.completed);
+    assert_eq!(open.tags, vec!["Shopping"]);
+
+    let done = parse_todo("* [x] Send invoice — due 2025-01-31").unwrap();
+    assert_eq!(done.text, "Send invoice");
+    assert!(done.completed);
+    assert_eq!(
+        done.due_date,
+        Some(Utc.with_ymd_and_hms(2025, 1, 31, 0, 0, 0).unwrap())
+    );
+}
+
+#[test]
+fn test_parse_falls_back_to_plain_text() {
+    let plain = parse_todo("  Just some text #notatag here ").unwrap();
+    assert_eq!(plain.text, "Just some text #notatag here");
+    assert!(!plain.completed);
+    assert!(plain.tags.is_empty());
+    assert_eq!(plain.due_date, None);
+
+    // A marker without any text is not a todo snippet
+    assert_eq!(parse_todo("☐ #Work").unwrap().text, "☐ #Work");
+
+    // An unparseable date stays part of the text
+    let bad_date = parse_todo("☐ Plan trip — due someday").unwrap();
+    assert_eq!(bad_date.text, "Plan trip — due someday");
+    assert_eq!(bad_date.due_date, None);
+}
+
+#[test]
+fn test_parse_reads_first_non_empty_line() {
+    let parsed = parse_todo("\n\n☑ First #A\n☐ Second").unwrap();
+    assert_eq!(parsed.text, "First");
+    assert!(parsed.completed);
+
+    assert_eq!(parse_todo(""), None);
+    assert_eq!(parse_todo("   \n  "), None);
+}
+
+#[test]
+fn test_snapshot_round_trip_with_unicode() {
+    let mut first = Todo::new(1, "Préparer les diapos 🎉".to_string());
+    first.add_tag("Travail".to_string());
+    first.notes = "- 東京で会議\n- Call Zoë".to_string();
+    let mut second = Todo::new(2, "Buy 牛乳".to_string());
+    second.toggle();
+    second.set_due_date(Some(Utc.with_ymd_and_hms(2025, 3, 4, 0, 0, 0).unwrap()));
+    let todos = vec![first, second];
+
+    let payload = encode_snapshot(&todos).unwrap();
+    assert!(
+        payload
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+        "payload must be safe in a URL fragment"
+    );
+    let shared = decode_snapshot(&payload).unwrap();
+    assert_eq!(shared.len(), todos.len());
+    for (id, (shared, todo)) in (1..).zip(shared.iter().zip(&todos)) {
+        assert_eq!(shared.id, id);
+        assert_eq!(shared.text, todo.text);
+        assert_eq!(shared.completed, todo.completed);
+        assert_eq!(shared.due_date, todo.due_date);
+        assert_eq!(shared.tags, todo.tags);
+    }
+
+    let fragment = snapshot_fragment(&todos).unwrap();
+    assert_eq!(fragment_payload(&fragment), Some(payload.as_str()));
+}
+
+#[test]
+fn test_snapshot_shares_only_what_the_view_shows() {
+    let mut todo = sample_todo();
+    todo.notes = "Door code 4411".to_string();
+    todo.text_history
+        .push((Utc::now(), "Prepare slides for Zoë".to_string()));
+    todo.remote = Some(RemoteLink {
+        uid: "a1b2".to_string(),
+        href: "/tasks/a1b2.ics".to_string(),
+        etag: None,
+        sequence: 3,
+        synced_at: Utc::now(),
+    });
+    let shown = sample_todo();
+
+    assert_eq!(encode_snapshot(&[todo]), encode_snapshot(&[shown]));
+}
+
+#[test]
+fn test_snapshot_links_holding_whole_todos_still_open() {
+    let todo = sample_todo();
+    let json = serde_json::json!({ "version": 1, "todos": [todo] });
+    let compressed = miniz_oxide::deflate::compress_to_vec(json.to_string().as_bytes(), 6);
+    let payload = URL_SAFE_NO_PAD.encode(compressed);
+
+    let shared = decode_snapshot(&payload).unwrap();
+    assert_eq!(shared.len(), 1);
+    assert_eq!(shared[0].text, "Prepare slides");
+    assert_eq!(shared[0].due_date, todo.due_date);
+    assert_eq!(shared[0].tags, todo.tags);
+}
+
+#[test]
+fn test_snapshot_size_cap() {
+    // Random-looking text barely compresses, so several hundred todos go over the cap
+    let todos: Vec<Todo> = (1..=800)
+        .map(|id| {
+            Todo::new(
+                id,
+                format!("{:x}", (id as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)),
+            )
+        })
+        .collect();
+
+    match encode_snapshot(&todos) {
+        Err(SnapshotError::TooLarge { size, max }) => {
+            assert_eq!(max, MAX_SNAPSHOT_BYTES);
+            assert!(size > max);
+        }
+        other => panic!("expected the snapshot to be too large, got {:?}", other),
+    }
+    assert!(encode_snapshot(&todos[..10]).is_ok());
+}
+
+#[test]
+fn test_snapshot_rejects_damaged_links() {
+    assert!(matches!(
+        decode_snapshot("not*base64"),
+        Err(SnapshotError::Invalid(_))
+    ));
+    assert!(matches!(
+        decode_snapshot("AAAA"),
+        Err(SnapshotError::Invalid(_))
+    ));
+}
+
+#[test]
+fn test_fragment_payload() {
+    assert_eq!(fragment_payload("#share=abc"), Some("abc"));
+    assert_eq!(fragment_payload("other=1&share=abc"), Some("abc"));
+    assert_eq!(fragment_payload("#share="), None);
+    assert_eq!(fragment_payload("#shared=abc"), None);
+    assert_eq!(fragment_payload(""), None);
+}
+
+#[test]
+fn test_add_copies_from_snapshot() {
+    let mut parent = Todo::new(7, "Parent".to_string());
+    parent.add_tag("Work".to_string());
+    let mut child = Todo::new(8, "Child".to_string());
+    child.derived_from = Some(7);
+    child.toggle();
+    let blank = Todo::new(9, "   ".to_string());
+
+    let mut list = TodoList::new();
+    list.add("Mine".to_string());
+    assert_eq!(list.add_copies(&[parent, child, blank]), 2);
+
+    let todos = list.all();
+    let texts: Vec<&str> = todos.iter().map(|todo| todo.text.as_str()).collect();
+    assert_eq!(texts, vec!["Mine", "Parent", "Child"]);
+    assert_eq!(todos[1].id, 2);
+    assert_eq!(todos[1].tags, vec!["Work"]);
+    assert!(todos[2].completed);
+    assert_eq!(todos[2].derived_from, None);
+    list.check_invariants().unwrap();
+}
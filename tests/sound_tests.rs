@@ -0,0 +1,25 @@
+use chrono::{Duration, TimeZone, Utc};
+use todo::models::AppSettings;
+use todo::utils::sound::SoundDebouncer;
+
+#[test]
+fn test_debouncer_lets_one_sound_through_per_interval() {
+    let start = Utc.with_ymd_and_hms(2025, 3, 4, 9, 0, 0).unwrap();
+    let mut debouncer = SoundDebouncer::new(Duration::milliseconds(250));
+
+    assert!(debouncer.try_play(start));
+    // A burst, like completing many todos at once, plays only the first tick
+    for ms in [0, 10, 100, 249] {
+        assert!(!debouncer.try_play(start + Duration::milliseconds(ms)));
+    }
+    assert!(debouncer.try_play(start + Duration::milliseconds(250)));
+    assert!(!debouncer.try_play(start + Duration::milliseconds(300)));
+    assert!(debouncer.try_play(start + Duration::seconds(2)));
+}
+
+#[test]
+fn test_completion_sound_is_off_by_default() {
+    assert!(!AppSettings::default().completion_sound);
+    let settings: AppSettings = serde_json::from_str(r#"{"completion_sound":true}"#).unwrap();
+    assert!(settings.completion_sound);
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use common::clock;
+use todo::models::staleness::{is_stale, staleness};
+use todo::models::{ListView, SearchQuery, Todo, TodoList};
+use todo::utils::clock::Clock;
+
+/// Builds an open todo added the given number of days before `now` and,
+/// optionally, changed some days before.
+fn todo_aged(
+    id: usize,
+    created_days_ago: i64,
+    updated_days_ago: Option<i64>,
+    now: DateTime<Utc>,
+) -> Todo {
+    let mut todo = Todo::new(id, format!("Todo {id}"));
+    todo.created_at = Some(now - Duration::days(created_days_ago));
+    todo.updated_at = updated_days_ago.map(|days| now - Duration::days(days));
+    todo
+}
+
+fn days(age: Option<Duration>) -> Option<i64> {
+    age.map(|age| age.num_days())
+}
+
+#[test]
+fn test_staleness_counts_from_last_change() {
+    let now = clock().now_utc();
+    assert_eq!(days(staleness(&todo_aged(1, 23, None, now), now)), Some(23));
+    assert_eq!(
+        days(staleness(&todo_aged(2, 40, Some(3), now), now)),
+        Some(3)
+    );
+}
+
+#[test]
+fn test_staleness_ignores_done_trashed_and_legacy_todos() {
+    let now = clock().now_utc();
+    let mut done = todo_aged(1, 30, None, now);
+    done.completed = true;
+    let mut trashed = todo_aged(2, 30, None, now);
+    trashed.deleted_at = Some(now);
+    // Added before creation times were recorded
+    let mut legacy = Todo::new(3, "Legacy".to_string());
+    legacy.created_at = None;
+
+    for todo in [done, trashed, legacy] {
+        assert_eq!(staleness(&todo, now), None);
+        assert!(!is_stale(&todo, now, 0));
+    }
+}
+
+#[test]
+fn test_staleness_never_negative() {
+    let now = clock().now_utc();
+    let todo = todo_aged(1, 0, None, now);
+    let earlier = now - Duration::hours(1);
+    assert_eq!(staleness(&todo, earlier), Some(Duration::zero()));
+}
+
+#[test]
+fn test_is_stale_threshold() {
+    let now = clock().now_utc();
+    let todo = todo_aged(1, 14, None, now);
+    assert!(is_stale(&todo, now, 14));
+    assert!(!is_stale(&todo, now, 15));
+}
+
+#[test]
+fn test_edit_resets_staleness() {
+    let mut list = TodoList::new();
+    let id = list.add("Call the plumber".to_string());
+    // The list stamps additions and edits with the system clock
+    let later = Utc::now() + Duration::days(20);
+    assert!(is_stale(list.get(id).unwrap(), later, 14));
+
+    list.update_text(id, "Call the plumber about the sink".to_string())
+        .unwrap();
+    let touched = list.get(id).unwrap().updated_at.unwrap();
+    assert!(!is_stale(list.get(id).unwrap(), touched, 14));
+}
+
+#[test]
+fn test_stale_query_uses_view_threshold() {
+    let now = clock().now_utc();
+    let todos = [
+        todo_aged(1, 30, None, now),
+        todo_aged(2, 30, Some(1), now),
+        todo_aged(3, 10, None, now),
+    ];
+    let query = SearchQuery::parse("is:stale");
+    assert!(query.stale);
+    assert_eq!(query.text, "");
+    assert!(SearchQuery::parse("is:STALE").stale);
+    // Unknown values are searched for as text
+    assert_eq!(SearchQuery::parse("is:old").text, "is:old");
+
+    let view = ListView {
+        query,
+        stale_after_days: 14,
+        ..ListView::default()
+    };
+    let today = now.date_naive();
+    assert_eq!(view.visible_ids(&todos, now, today), vec![1]);
+
+    let view = ListView {
+        stale_after_days: 7,
+        ..view
+    };
+    assert_eq!(view.visible_ids(&todos, now, today), vec![1, 3]);
+}
@@ -0,0 +1,126 @@
+use todo::models::TodoList;
+use todo::utils::keys;
+use todo::utils::storage::{
+    MaintenanceReport, MemoryStorage, PrefixedStorage, RawStorage, StorageBackend, StorageError,
+    StorageStats, delete_all_with, save_with,
+};
+
+/// Stores two active todos, one completed, one archived and one in the
+/// trash, tagged "Work" and "Home" between them, together with the theme.
+fn stored() -> MemoryStorage {
+    let mut list = TodoList::new();
+    let report = list.add("Write report".to_string());
+    list.add_tag(report, "Work".to_string());
+    let milk = list.add("Buy milk".to_string());
+    list.add_tag(milk, "Home".to_string());
+    list.add_tag(milk, "work".to_string());
+    let call = list.add("Call mum".to_string());
+    list.toggle(call);
+    let done = list.add("Done long ago".to_string());
+    list.toggle(done);
+    list.archive(done);
+    let old = list.add("Old idea".to_string());
+    list.move_to_trash(old).unwrap();
+
+    let storage = MemoryStorage::new();
+    save_with(&storage, keys::TODOS, &list).unwrap();
+    storage.save_str(keys::THEME, "\"dark\"").unwrap();
+    storage
+}
+
+fn stats(storage: &dyn RawStorage) -> StorageStats {
+    StorageStats::collect_with(storage, "here".to_string()).unwrap()
+}
+
+#[test]
+fn test_stats_count_todos_and_tags() {
+    let storage = stored();
+    let stats = stats(&storage);
+
+    assert_eq!(stats.active, 2);
+    assert_eq!(stats.completed, 1);
+    assert_eq!(stats.archived, 1);
+    assert_eq!(stats.trashed, 1);
+    assert_eq!(stats.tags, 3);
+    assert_eq!(stats.location, "here");
+}
+
+#[test]
+fn test_stats_measure_the_stored_values() {
+    let storage = stored();
+    let document = storage.load_str(keys::TODOS).unwrap().len();
+    let stats = stats(&storage);
+
+    assert_eq!(stats.document_bytes, document);
+    assert_eq!(stats.total_bytes, document + "\"dark\"".len());
+    assert_eq!(stats.keys, 2);
+
+    // Copies kept aside by a repair count, values of other apps do not
+    storage.save_str("todo/v1/todos.corrupt", "{").unwrap();
+    storage
+        .save_str("other-app", "x".repeat(100).as_str())
+        .unwrap();
+    let stats = self::stats(&storage);
+    assert_eq!(stats.keys, 3);
+    assert_eq!(stats.total_bytes, document + "\"dark\"".len() + 1);
+}
+
+#[test]
+fn test_stats_of_empty_and_corrupt_storage() {
+    let storage = MemoryStorage::new();
+    assert_eq!(
+        stats(&storage),
+        StorageStats {
+            location: "here".to_string(),
+            ..StorageStats::default()
+        }
+    );
+
+    storage.save_str(keys::TODOS, "{\"todos\":").unwrap();
+    assert!(matches!(
+        StorageStats::collect_with(&storage, String::new()),
+        Err(StorageError::DeserializeError(_))
+    ));
+}
+
+#[test]
+fn test_delete_all_clears_only_namespaced_keys() {
+    let storage = stored();
+    storage.save_str("other-app", "keep").unwrap();
+
+    assert_eq!(delete_all_with(&storage).unwrap(), 2);
+    assert_eq!(storage.list_keys().unwrap(), ["other-app"]);
+    assert_eq!(stats(&storage).keys, 0);
+    assert_eq!(delete_all_with(&storage).unwrap(), 0);
+}
+
+#[test]
+fn test_delete_all_stays_inside_a_profile() {
+    let shared = MemoryStorage::new();
+    let work = PrefixedStorage::new(Box::new(shared.clone()), "profiles/work/".to_string());
+    save_with(&work, keys::TODOS, &TodoList::new()).unwrap();
+    save_with(&shared, keys::TODOS, &TodoList::new()).unwrap();
+
+    assert_eq!(delete_all_with(&work).unwrap(), 1);
+    assert_eq!(shared.list_keys().unwrap(), [keys::TODOS]);
+}
+
+#[test]
+fn test_backends_without_housekeeping_free_nothing() {
+    let storage = stored();
+    assert_eq!(storage.maintenance().unwrap(), MaintenanceReport::default());
+
+    let work = PrefixedStorage::new(Box::new(storage), "profiles/work/".to_string());
+    assert_eq!(work.maintenance().unwrap().bytes_freed, 0);
+}
+
+#[test]
+fn test_memory_backend_compacts_and_describes_itself() {
+    let storage = stored();
+    storage.compact().unwrap();
+    assert_eq!(stats(&storage).active, 2);
+    assert_eq!(
+        StorageBackend::Memory.location(None),
+        "In memory, nothing is saved"
+    );
+}
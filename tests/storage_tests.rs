@@ -75,3 +75,286 @@ mod desktop_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod backend_tests {
+    use super::*;
+    use todo::models::TodoList;
+    use todo::utils::storage::{
+        MemoryStorage, RawStorage, StorageBackend, load_with, open_storage, save_with,
+    };
+
+    #[test]
+    fn test_typed_round_trip_through_dyn_backend() {
+        let storage: Box<dyn RawStorage> = Box::new(MemoryStorage::new());
+        let mut list = TodoList::new();
+        list.add("Boxed todo".to_string());
+
+        save_with(storage.as_ref(), "todos", &list).unwrap();
+        let loaded: TodoList = load_with(storage.as_ref(), "todos").unwrap();
+
+        assert_eq!(loaded.all().len(), 1);
+        assert_eq!(loaded.all()[0].text, "Boxed todo");
+    }
+
+    #[test]
+    fn test_swapping_backends_at_runtime() {
+        let backends: Vec<Box<dyn RawStorage>> = vec![
+            Box::new(MemoryStorage::new()),
+            Box::new(MemoryStorage::new()),
+        ];
+
+        save_with(backends[0].as_ref(), "key", &"first".to_string()).unwrap();
+
+        let from_first: String = load_with(backends[0].as_ref(), "key").unwrap();
+        let from_second: Result<String, StorageError> = load_with(backends[1].as_ref(), "key");
+
+        assert_eq!(from_first, "first");
+        assert!(matches!(from_second, Err(StorageError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_delete_and_list_keys() {
+        let storage = MemoryStorage::new();
+        storage.save_str("b", "2").unwrap();
+        storage.save_str("a", "1").unwrap();
+        assert_eq!(storage.list_keys().unwrap(), vec!["a", "b"]);
+
+        storage.delete("a").unwrap();
+        assert_eq!(storage.list_keys().unwrap(), vec!["b"]);
+        assert!(matches!(
+            storage.load_str("a"),
+            Err(StorageError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_memory_backend_is_shared_across_opens() {
+        let first = open_storage(StorageBackend::Memory).unwrap();
+        first.save_str("shared-backend-key", "value").unwrap();
+
+        let second = open_storage(StorageBackend::Memory).unwrap();
+        assert_eq!(second.load_str("shared-backend-key").unwrap(), "value");
+    }
+
+    #[test]
+    fn test_backend_parse() {
+        assert_eq!(
+            StorageBackend::parse("memory"),
+            Some(StorageBackend::Memory)
+        );
+        assert_eq!(
+            StorageBackend::parse(" SQLite "),
+            Some(StorageBackend::Native)
+        );
+        assert_eq!(StorageBackend::parse("floppy"), None);
+    }
+
+    #[test]
+    fn test_deserialize_error_on_malformed_value() {
+        let storage = MemoryStorage::new();
+        storage.save_str("todos", "{not json").unwrap();
+
+        let result: Result<TodoList, StorageError> = load_with(&storage, "todos");
+        assert!(matches!(result, Err(StorageError::DeserializeError(_))));
+    }
+}
+
+#[cfg(test)]
+mod health_tests {
+    use todo::models::TodoList;
+    use todo::utils::keys;
+    use todo::utils::storage::{
+        DataHealth, MemoryStorage, RawStorage, RepairOutcome, health_check_with, load_with,
+        repair_with, salvage_todos, save_with,
+    };
+
+    fn three_todos_json() -> String {
+        let mut list = TodoList::new();
+        list.add("First".to_string());
+        list.add("Second".to_string());
+        list.add("Third".to_string());
+        serde_json::to_string(&list).unwrap()
+    }
+
+    #[test]
+    fn test_health_check_reports_valid_and_missing() {
+        let storage = MemoryStorage::new();
+        let report = health_check_with(&storage).unwrap();
+        assert_eq!(report.data, DataHealth::Missing);
+        assert!(report.is_healthy());
+
+        save_with(&storage, keys::TODOS, &TodoList::new()).unwrap();
+        let report = health_check_with(&storage).unwrap();
+        assert_eq!(report.data, DataHealth::Valid { todos: 0 });
+    }
+
+    #[test]
+    fn test_health_check_detects_truncated_json() {
+        let storage = MemoryStorage::new();
+        let json = three_todos_json();
+        storage
+            .save_str(keys::TODOS, &json[..json.len() / 2])
+            .unwrap();
+
+        let report = health_check_with(&storage).unwrap();
+        assert!(matches!(report.data, DataHealth::Corrupt { .. }));
+        assert!(!report.is_healthy());
+    }
+
+    #[test]
+    fn test_salvage_recovers_complete_todos_from_truncated_json() {
+        let json = three_todos_json();
+        // Cut into the middle of the last todo object
+        let cut = json.rfind("\"text\"").unwrap();
+        let salvaged = salvage_todos(&json[..cut]);
+        assert_eq!(salvaged.len(), 2);
+
+        assert_eq!(salvage_todos(&json).len(), 3);
+        assert!(salvage_todos("").is_empty());
+        assert!(salvage_todos("{\"todos\":{").is_empty());
+    }
+
+    #[test]
+    fn test_repair_salvages_and_keeps_corrupt_copy() {
+        let storage = MemoryStorage::new();
+        let json = three_todos_json();
+        let cut = json.rfind("\"text\"").unwrap();
+        storage.save_str(keys::TODOS, &json[..cut]).unwrap();
+
+        let (outcome, list) = repair_with(&storage).unwrap();
+        assert_eq!(outcome, RepairOutcome::Salvaged { recovered: 2 });
+        assert_eq!(list.unwrap().total_count(), 2);
+
+        let repaired: TodoList = load_with(&storage, keys::TODOS).unwrap();
+        assert_eq!(repaired.total_count(), 2);
+        let backup_key = format!("{}.corrupt", keys::TODOS);
+        assert_eq!(storage.load_str(&backup_key).unwrap(), &json[..cut]);
+
+        let (outcome, list) = repair_with(&storage).unwrap();
+        assert_eq!(outcome, RepairOutcome::NothingToRepair);
+        assert!(list.is_none());
+    }
+}
+
+#[cfg(test)]
+mod key_migration_tests {
+    use todo::models::{FilterCriteria, QuickFilter, TodoList};
+    use todo::utils::keys;
+    use todo::utils::storage::{MemoryStorage, RawStorage, load_with, save_with};
+
+    /// Seeds a backend the way earlier versions left it.
+    fn legacy_storage(list: &TodoList) -> MemoryStorage {
+        let storage = MemoryStorage::new();
+        save_with(&storage, "dioxus-todo-app", list).unwrap();
+        save_with(&storage, "dioxus-todo-app-theme", &"dark").unwrap();
+        save_with(&storage, "dioxus-todo-app-onboarding-done", &true).unwrap();
+        storage
+    }
+
+    #[test]
+    fn test_migrate_moves_legacy_keys() {
+        let mut list = TodoList::new();
+        list.add("Buy milk".to_string());
+        let storage = legacy_storage(&list);
+
+        assert_eq!(keys::migrate(&storage).unwrap(), 3);
+
+        assert_eq!(load_with::<TodoList>(&storage, keys::TODOS).unwrap(), list);
+        assert_eq!(load_with::<String>(&storage, keys::THEME).unwrap(), "dark");
+        assert!(load_with::<bool>(&storage, keys::ONBOARDING_DONE).unwrap());
+        assert_eq!(
+            storage.list_keys().unwrap(),
+            vec![keys::ONBOARDING_DONE, keys::THEME, keys::TODOS]
+        );
+        assert!(
+            storage
+                .list_keys()
+                .unwrap()
+                .iter()
+                .all(|key| key.starts_with(keys::NAMESPACE))
+        );
+    }
+
+    #[test]
+    fn test_migrate_is_idempotent() {
+        let mut list = TodoList::new();
+        list.add("Buy milk".to_string());
+        let storage = legacy_storage(&list);
+
+        keys::migrate(&storage).unwrap();
+        let keys_after_first_run = storage.list_keys().unwrap();
+        assert_eq!(keys::migrate(&storage).unwrap(), 0);
+        assert_eq!(storage.list_keys().unwrap(), keys_after_first_run);
+        assert_eq!(load_with::<TodoList>(&storage, keys::TODOS).unwrap(), list);
+
+        // Nothing to do on a fresh install
+        let empty = MemoryStorage::new();
+        assert_eq!(keys::migrate(&empty).unwrap(), 0);
+        assert!(empty.list_keys().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_migrate_keeps_newer_values() {
+        let mut old_list = TodoList::new();
+        old_list.add("Old".to_string());
+        let storage = legacy_storage(&old_list);
+
+        let mut new_list = TodoList::new();
+        new_list.add("New".to_string());
+        save_with(&storage, keys::TODOS, &new_list).unwrap();
+
+        assert_eq!(keys::migrate(&storage).unwrap(), 2);
+        assert_eq!(
+            load_with::<TodoList>(&storage, keys::TODOS).unwrap(),
+            new_list
+        );
+        assert!(storage.load_str("dioxus-todo-app").is_err());
+    }
+
+    #[test]
+    fn test_migrate_moves_the_quick_filter_into_the_filters() {
+        let storage = MemoryStorage::new();
+        save_with(
+            &storage,
+            keys::LEGACY_QUICK_FILTER,
+            &Some(QuickFilter::Overdue),
+        )
+        .unwrap();
+
+        assert_eq!(keys::migrate(&storage).unwrap(), 1);
+        assert_eq!(
+            load_with::<FilterCriteria>(&storage, keys::FILTERS).unwrap(),
+            FilterCriteria {
+                quick_filter: Some(QuickFilter::Overdue),
+                ..FilterCriteria::default()
+            }
+        );
+        assert_eq!(storage.list_keys().unwrap(), vec![keys::FILTERS]);
+        assert_eq!(keys::migrate(&storage).unwrap(), 0);
+
+        // Filters saved since then win, and an empty pill is not worth moving
+        let storage = MemoryStorage::new();
+        let newer = FilterCriteria {
+            search_text: "milk".to_string(),
+            ..FilterCriteria::default()
+        };
+        save_with(&storage, keys::FILTERS, &newer).unwrap();
+        save_with(
+            &storage,
+            keys::LEGACY_QUICK_FILTER,
+            &Some(QuickFilter::Today),
+        )
+        .unwrap();
+        assert_eq!(keys::migrate(&storage).unwrap(), 0);
+        assert_eq!(
+            load_with::<FilterCriteria>(&storage, keys::FILTERS).unwrap(),
+            newer
+        );
+
+        let storage = MemoryStorage::new();
+        save_with(&storage, keys::LEGACY_QUICK_FILTER, &None::<QuickFilter>).unwrap();
+        assert_eq!(keys::migrate(&storage).unwrap(), 0);
+        assert!(storage.list_keys().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+use todo::models::{Subtask, TodoList};
+
+/// Gets the subtasks of a todo as (text, completed) pairs.
+fn subtasks(list: &TodoList, id: usize) -> Vec<(&str, bool)> {
+    list.get(id)
+        .unwrap()
+        .subtasks
+        .iter()
+        .map(|subtask| (subtask.text.as_str(), subtask.completed))
+        .collect()
+}
+
+#[test]
+fn test_subtasks_are_added_in_order_with_their_own_ids() {
+    let mut list = TodoList::new();
+    let id = list.add("Move house".to_string());
+    let other = list.add("Water plants".to_string());
+
+    assert_eq!(list.add_subtask(id, "Pack boxes".to_string()), Some(1));
+    assert_eq!(list.add_subtask(id, "Book van".to_string()), Some(2));
+    assert_eq!(list.add_subtask(other, "Kitchen".to_string()), Some(1));
+    assert_eq!(list.add_subtask(999, "Nowhere".to_string()), None);
+
+    assert_eq!(
+        subtasks(&list, id),
+        [("Pack boxes", false), ("Book van", false)]
+    );
+
+    // Ids of removed subtasks are not handed out again while later ones remain
+    assert!(list.remove_subtask(id, 1));
+    assert_eq!(list.add_subtask(id, "Return keys".to_string()), Some(3));
+}
+
+#[test]
+fn test_completing_every_subtask_leaves_the_todo_open() {
+    let mut list = TodoList::new();
+    let id = list.add("Move house".to_string());
+    let boxes = list.add_subtask(id, "Pack boxes".to_string()).unwrap();
+    let van = list.add_subtask(id, "Book van".to_string()).unwrap();
+    assert_eq!(list.get(id).unwrap().subtask_progress(), (0, 2));
+
+    assert!(list.toggle_subtask(id, boxes));
+    assert!(list.toggle_subtask(id, van));
+    let todo = list.get(id).unwrap();
+    assert_eq!(todo.subtask_progress(), (2, 2));
+    assert!(!todo.completed);
+
+    assert!(list.toggle_subtask(id, van));
+    assert_eq!(
+        subtasks(&list, id),
+        [("Pack boxes", true), ("Book van", false)]
+    );
+    assert!(!list.toggle_subtask(id, 99));
+    assert!(!list.toggle_subtask(999, boxes));
+}
+
+#[test]
+fn test_remove_subtask() {
+    let mut list = TodoList::new();
+    let id = list.add("Move house".to_string());
+    let boxes = list.add_subtask(id, "Pack boxes".to_string()).unwrap();
+    list.add_subtask(id, "Book van".to_string());
+
+    assert!(list.remove_subtask(id, boxes));
+    assert!(!list.remove_subtask(id, boxes));
+    assert!(!list.remove_subtask(999, 2));
+    assert_eq!(subtasks(&list, id), [("Book van", false)]);
+    assert_eq!(list.get(id).unwrap().subtask_progress(), (0, 1));
+}
+
+#[test]
+fn test_subtasks_round_trip_through_json() {
+    let mut list = TodoList::new();
+    let id = list.add("Move house".to_string());
+    let boxes = list.add_subtask(id, "Pack boxes ✨".to_string()).unwrap();
+    list.toggle_subtask(id, boxes);
+
+    let json = serde_json::to_string(&list).unwrap();
+    let restored: TodoList = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, list);
+    assert_eq!(
+        restored.get(id).unwrap().subtasks,
+        [Subtask {
+            id: boxes,
+            text: "Pack boxes ✨".to_string(),
+            completed: true,
+        }]
+    );
+
+    // Todos without subtasks don't write the field
+    let mut plain = TodoList::new();
+    plain.add("Water plants".to_string());
+    let plain = serde_json::to_string(&plain).unwrap();
+    assert!(!plain.contains("subtasks"));
+}
+
+#[test]
+fn test_lists_saved_before_subtasks_still_load() {
+    let json = concat!(
+        r#"{"todos":[{"id":1,"text":"Call the plumber","completed":false,"#,
+        r#""due_date":null,"tags":[],"order":1}],"next_id":2}"#
+    );
+    let list: TodoList = serde_json::from_str(json).unwrap();
+    let todo = list.get(1).unwrap();
+    assert!(todo.subtasks.is_empty());
+    assert_eq!(todo.subtask_progress(), (0, 0));
+}
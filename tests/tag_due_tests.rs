@@ -0,0 +1,138 @@
+use chrono::{NaiveDate, Weekday};
+use todo::models::tag_due::{RelativeDue, TagDueRule, apply_tag_rules};
+use todo::models::{AppSettings, Todo, TodoList};
+use todo::utils::dates;
+
+/// Wednesday 12 March 2025.
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, 12).unwrap()
+}
+
+fn march(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, day).unwrap()
+}
+
+fn rule(tag: &str, due: RelativeDue) -> TagDueRule {
+    TagDueRule {
+        tag: tag.to_string(),
+        due,
+    }
+}
+
+/// Urgent todos are due tomorrow, groceries on Saturday and chores in a week.
+fn rules() -> Vec<TagDueRule> {
+    vec![
+        rule("Groceries", RelativeDue::Weekday(Weekday::Sat)),
+        rule("Urgent", RelativeDue::InDays(1)),
+        rule("Chores", RelativeDue::InDays(7)),
+    ]
+}
+
+fn tagged(tags: &[&str]) -> Todo {
+    let mut todo = Todo::new(1, "Buy eggs".to_string());
+    todo.tags = tags.iter().map(|tag| tag.to_string()).collect();
+    todo
+}
+
+fn due_day(todo: &Todo) -> Option<NaiveDate> {
+    apply_tag_rules(todo, &rules(), today()).map(|due| due.date_naive())
+}
+
+#[test]
+fn test_relative_dates_resolve_from_today() {
+    assert_eq!(RelativeDue::InDays(0).resolve(today()), today());
+    assert_eq!(RelativeDue::InDays(7).resolve(today()), march(19));
+    assert_eq!(
+        RelativeDue::Weekday(Weekday::Mon).resolve(today()),
+        march(17)
+    );
+    assert_eq!(RelativeDue::Weekday(Weekday::Wed).resolve(today()), today());
+    assert_eq!(
+        RelativeDue::Weekday(Weekday::Sun).resolve(today()),
+        march(16)
+    );
+
+    assert_eq!(RelativeDue::InDays(0).to_string(), "today");
+    assert_eq!(RelativeDue::InDays(1).to_string(), "tomorrow");
+    assert_eq!(RelativeDue::InDays(3).to_string(), "in 3 days");
+    assert_eq!(RelativeDue::Weekday(Weekday::Sat).to_string(), "Saturday");
+}
+
+#[test]
+fn test_earliest_date_wins_when_several_tags_have_rules() {
+    assert_eq!(due_day(&tagged(&["Groceries", "Urgent"])), Some(march(13)));
+    assert_eq!(due_day(&tagged(&["Chores", "Groceries"])), Some(march(15)));
+    assert_eq!(due_day(&tagged(&["Chores"])), Some(march(19)));
+
+    // The order of the rules and of the tags makes no difference
+    let mut reversed = rules();
+    reversed.reverse();
+    let todo = tagged(&["Urgent", "Groceries"]);
+    assert_eq!(
+        apply_tag_rules(&todo, &reversed, today()).map(|due| due.date_naive()),
+        Some(march(13))
+    );
+}
+
+#[test]
+fn test_rules_match_tags_ignoring_case_and_skip_other_tags() {
+    assert_eq!(due_day(&tagged(&["  urgent "])), Some(march(13)));
+    assert_eq!(due_day(&tagged(&["Work"])), None);
+    assert_eq!(due_day(&tagged(&[])), None);
+
+    // Rules without a tag match nothing
+    let blank = [rule(" ", RelativeDue::InDays(0))];
+    assert_eq!(apply_tag_rules(&tagged(&[""]), &blank, today()), None);
+}
+
+#[test]
+fn test_rules_never_replace_a_due_date() {
+    let mut todo = tagged(&["Urgent"]);
+    todo.due_date = Some(dates::due_date_on(march(30)));
+    assert_eq!(due_day(&todo), None);
+}
+
+#[test]
+fn test_list_sets_the_due_date_when_a_tag_is_added() {
+    let mut list = TodoList::new();
+    let id = list.add("Buy eggs".to_string());
+    let dated = list.add("Pay rent".to_string());
+    list.set_due_date(dated, Some(dates::due_date_on(march(31))))
+        .unwrap();
+
+    assert!(!list.apply_tag_due_rules(id, &rules(), today()));
+    list.add_tag(id, "Groceries".to_string());
+    assert!(list.apply_tag_due_rules(id, &rules(), today()));
+    assert_eq!(
+        list.get(id).unwrap().due_date,
+        Some(dates::due_date_on(march(15)))
+    );
+
+    // A later tag with an earlier rule leaves the date alone
+    list.add_tag(id, "Urgent".to_string());
+    assert!(!list.apply_tag_due_rules(id, &rules(), today()));
+    assert_eq!(
+        list.get(id).unwrap().due_date,
+        Some(dates::due_date_on(march(15)))
+    );
+
+    list.add_tag(dated, "Urgent".to_string());
+    assert!(!list.apply_tag_due_rules(dated, &rules(), today()));
+    assert!(!list.apply_tag_due_rules(999, &rules(), today()));
+    assert_eq!(list.get(id).unwrap().postponed_count, 0);
+}
+
+#[test]
+fn test_rules_round_trip_through_settings() {
+    let settings = AppSettings {
+        tag_due_rules: rules(),
+        ..AppSettings::default()
+    };
+    let json = serde_json::to_string(&settings).unwrap();
+    let restored: AppSettings = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.tag_due_rules, rules());
+
+    // Settings saved before the rules existed load with none
+    let legacy: AppSettings = serde_json::from_str(r#"{"week_start":"Monday"}"#).unwrap();
+    assert!(legacy.tag_due_rules.is_empty());
+}
@@ -0,0 +1,81 @@
+use std::collections::{BTreeSet, HashMap};
+use todo::models::tag_suggestion::{suggest_tags, tag_usage};
+use todo::models::{AppSettings, Todo};
+
+const DEFAULTS: [&str; 4] = ["Work", "Personal", "Urgent", "Shopping"];
+
+fn tagged(id: usize, tags: &[&str]) -> Todo {
+    let mut todo = Todo::new(id, format!("Todo {id}"));
+    todo.tags = tags.iter().map(|tag| tag.to_string()).collect();
+    todo
+}
+
+fn usage(counts: &[(&str, usize)]) -> HashMap<String, usize> {
+    counts
+        .iter()
+        .map(|(tag, count)| (tag.to_string(), *count))
+        .collect()
+}
+
+#[test]
+fn test_tag_usage_counts_todos_per_tag() {
+    let todos = [
+        tagged(1, &["Work", "errands"]),
+        tagged(2, &["errands"]),
+        tagged(3, &[]),
+    ];
+    assert_eq!(tag_usage(&todos), usage(&[("Work", 1), ("errands", 2)]));
+}
+
+#[test]
+fn test_suggestions_are_ranked_by_usage() {
+    let usage = usage(&[("Shopping", 5), ("errands", 3), ("Work", 3)]);
+    assert_eq!(
+        suggest_tags(&DEFAULTS, &usage, &BTreeSet::new(), &[]),
+        ["Shopping", "Work", "errands", "Personal", "Urgent"]
+    );
+}
+
+#[test]
+fn test_rarely_used_own_tags_are_not_suggested() {
+    let usage = usage(&[("one-off", 1), ("garden", 2)]);
+    let suggestions = suggest_tags(&DEFAULTS, &usage, &BTreeSet::new(), &[]);
+    assert_eq!(suggestions[0], "garden");
+    assert!(!suggestions.contains(&"one-off".to_string()));
+}
+
+#[test]
+fn test_suppressed_and_present_tags_are_left_out() {
+    let usage = usage(&[("Work", 4), ("garden", 2)]);
+    let suppressed = BTreeSet::from(["Urgent".to_string(), "garden".to_string()]);
+    assert_eq!(
+        suggest_tags(&DEFAULTS, &usage, &suppressed, &["Work".to_string()]),
+        ["Personal", "Shopping"]
+    );
+}
+
+#[test]
+fn test_suggestions_are_capped() {
+    let counts: Vec<(String, usize)> = (0..20).map(|i| (format!("tag{i:02}"), 2)).collect();
+    let usage: HashMap<String, usize> = counts.into_iter().collect();
+    let suggestions = suggest_tags(&DEFAULTS, &usage, &BTreeSet::new(), &[]);
+    assert_eq!(suggestions.len(), 8);
+    assert_eq!(suggestions[0], "tag00");
+}
+
+#[test]
+fn test_settings_without_suppressed_suggestions_still_load() {
+    let settings: AppSettings = serde_json::from_str(r#"{"show_karma": false}"#).unwrap();
+    assert!(settings.suppressed_suggestions.is_empty());
+
+    let mut settings = AppSettings::default();
+    settings
+        .suppressed_suggestions
+        .insert("Shopping".to_string());
+    let json = serde_json::to_string(&settings).unwrap();
+    let loaded: AppSettings = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        loaded.suppressed_suggestions,
+        settings.suppressed_suggestions
+    );
+}
@@ -0,0 +1,83 @@
+use todo::utils::constants::todo::{MAX_TODO_TEXT_LENGTH, READING_TIME_MIN_WORDS};
+use todo::utils::text::{TextError, TextStats, char_count, text_stats, validate_todo_text};
+
+#[test]
+fn test_emoji_count_as_one_character() {
+    // Skin tone, family joined with zero-width joiners, and a flag
+    for emoji in ["👍🏽", "👨‍👩‍👧‍👦", "🇳🇱", "❤️"] {
+        assert_eq!(char_count(emoji), 1, "{emoji}");
+    }
+    let stats = text_stats("Party 🎉🎉 tonight");
+    assert_eq!(stats.characters, 16);
+    assert_eq!(stats.words, 2);
+}
+
+#[test]
+fn test_combining_characters_count_with_their_letter() {
+    let composed = "café crème";
+    let decomposed = "cafe\u{301} cre\u{300}me";
+    assert_ne!(composed.chars().count(), decomposed.chars().count());
+    assert_eq!(text_stats(composed), text_stats(decomposed));
+    assert_eq!(text_stats(decomposed).characters, 10);
+    assert_eq!(text_stats(decomposed).words, 2);
+}
+
+#[test]
+fn test_words_follow_word_boundaries() {
+    assert_eq!(text_stats("").words, 0);
+    assert_eq!(text_stats("  — !? ").words, 0);
+    assert_eq!(text_stats("don't stop-start, e.g. 3.5").words, 5);
+    assert_eq!(text_stats("line one\nline two\tend").words, 5);
+}
+
+#[test]
+fn test_cjk_words_are_counted_by_character() {
+    // Every Chinese character and hiragana is a word, a katakana run is one
+    assert_eq!(text_stats("买牛奶").words, 3);
+    assert_eq!(text_stats("コーヒーを買う").words, 4);
+    assert_eq!(text_stats("Buy 牛奶 today").words, 4);
+    // Korean separates words with spaces
+    assert_eq!(text_stats("우유 사기").words, 2);
+}
+
+#[test]
+fn test_reading_time_only_for_long_text() {
+    let short = "word ".repeat(READING_TIME_MIN_WORDS - 1);
+    assert_eq!(text_stats(&short).reading_minutes, None);
+
+    let long = "word ".repeat(READING_TIME_MIN_WORDS);
+    assert_eq!(text_stats(&long).reading_minutes, Some(1));
+    assert_eq!(text_stats(&"word ".repeat(401)).reading_minutes, Some(3));
+
+    // Chinese reads faster per character than spaced words
+    assert_eq!(text_stats(&"读".repeat(1000)).reading_minutes, Some(2));
+}
+
+#[test]
+fn test_stats_display() {
+    assert_eq!(text_stats("Hi").to_string(), "2 characters · 1 word");
+    assert_eq!(text_stats("a").to_string(), "1 character · 1 word");
+    let stats = TextStats {
+        characters: 1500,
+        words: 250,
+        reading_minutes: Some(2),
+    };
+    assert_eq!(
+        stats.to_string(),
+        "1500 characters · 250 words · 2 min read"
+    );
+}
+
+#[test]
+fn test_todo_length_limit_counts_what_the_reader_sees() {
+    let at_limit = "👍🏽".repeat(MAX_TODO_TEXT_LENGTH);
+    assert!(validate_todo_text(&at_limit).is_ok());
+    let over = format!("{at_limit}e\u{301}");
+    assert_eq!(
+        validate_todo_text(&over),
+        Err(TextError::TooLong {
+            length: MAX_TODO_TEXT_LENGTH + 1,
+            max: MAX_TODO_TEXT_LENGTH
+        })
+    );
+}
@@ -0,0 +1,66 @@
+use todo::models::{Animations, AppSettings};
+use todo::utils::theme::{container_class, motion};
+
+#[test]
+fn test_motion_keeps_classes_when_animations_are_on() {
+    assert_eq!(
+        motion("transition-all duration-200 ease-in-out", true),
+        "transition-all duration-200 ease-in-out"
+    );
+}
+
+#[test]
+fn test_motion_strips_animation_classes_when_off() {
+    for classes in [
+        "transition",
+        "transition-colors duration-300",
+        "transition-all duration-200 ease-in-out",
+        "transition-opacity delay-75",
+        "animate-spin",
+        "motion-safe:animate-pulse hover:transition-none",
+    ] {
+        assert_eq!(motion(classes, false), "", "{classes}");
+    }
+
+    // Classes that only look alike are kept
+    assert_eq!(
+        motion(
+            "opacity-0 group-hover:opacity-100 transition-opacity",
+            false
+        ),
+        "opacity-0 group-hover:opacity-100"
+    );
+    assert_eq!(
+        motion("  translate-x-1   transition ", false),
+        "translate-x-1"
+    );
+}
+
+#[test]
+fn test_container_class_follows_animations() {
+    assert!(container_class(true, true).contains("transition-colors"));
+    let still = container_class(true, false);
+    assert!(!still.contains("transition") && !still.contains("duration"));
+    assert!(still.contains("bg-gray-900") && !still.contains("dark:"));
+}
+
+#[test]
+fn test_animations_setting_overrides_the_system() {
+    for (setting, normal, reduced) in [
+        (Animations::System, true, false),
+        (Animations::On, true, true),
+        (Animations::Off, false, false),
+    ] {
+        assert_eq!(setting.is_enabled(false), normal, "{setting:?}");
+        assert_eq!(setting.is_enabled(true), reduced, "{setting:?}");
+        assert_eq!(Animations::parse(setting.label()), Some(setting));
+    }
+    assert_eq!(Animations::parse("off "), Some(Animations::Off));
+    assert_eq!(Animations::parse("sometimes"), None);
+}
+
+#[test]
+fn test_animations_default_to_following_the_system() {
+    let settings: AppSettings = serde_json::from_str("{}").unwrap();
+    assert_eq!(settings.animations, Animations::System);
+}
@@ -0,0 +1,83 @@
+use chrono::{Duration, NaiveDate};
+use todo::models::Todo;
+use todo::models::timeline::{TimelineAxis, rows};
+use todo::utils::constants::ui::{TIMELINE_DAY_WIDTH, TIMELINE_MIN_DAYS};
+use todo::utils::dates;
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, 12).unwrap()
+}
+
+fn day(offset: i64) -> NaiveDate {
+    today() + Duration::days(offset)
+}
+
+fn due(id: usize, days_from_today: i64) -> Todo {
+    let mut todo = Todo::new(id, format!("Todo {id}"));
+    todo.due_date = Some(dates::due_date_on(day(days_from_today)));
+    todo
+}
+
+fn axis() -> TimelineAxis {
+    TimelineAxis {
+        start: day(-1),
+        days: 10,
+        px_per_day: 20.0,
+    }
+}
+
+#[test]
+fn test_axis_spans_due_dates_and_today_with_a_day_to_spare() {
+    let axis = TimelineAxis::spanning([day(-20), day(5)], today(), 7, 10.0);
+    assert_eq!(axis.start, day(-21));
+    assert_eq!(axis.days, 28);
+    assert_eq!(axis.dates().last(), Some(day(6)));
+
+    // Today is on the axis even when everything is due later
+    let axis = TimelineAxis::spanning([day(30)], today(), 7, 10.0);
+    assert_eq!(axis.start, day(-1));
+    assert_eq!(axis.dates().last(), Some(day(31)));
+}
+
+#[test]
+fn test_axis_is_at_least_the_minimum_length() {
+    let axis = TimelineAxis::spanning([], today(), TIMELINE_MIN_DAYS, TIMELINE_DAY_WIDTH);
+    assert_eq!(axis.days, TIMELINE_MIN_DAYS);
+    assert_eq!(axis.width(), TIMELINE_MIN_DAYS as f64 * TIMELINE_DAY_WIDTH);
+    assert_eq!(axis.dates().count(), TIMELINE_MIN_DAYS as usize);
+}
+
+#[test]
+fn test_days_map_to_pixels_and_back() {
+    let axis = axis();
+    assert_eq!(axis.x_of(day(-1)), 0.0);
+    assert_eq!(axis.x_of(day(2)), 60.0);
+    assert_eq!(axis.center_of(day(2)), 70.0);
+
+    assert_eq!(axis.date_at(0.0), day(-1));
+    assert_eq!(axis.date_at(59.9), day(1));
+    assert_eq!(axis.date_at(60.0), day(2));
+    // Points off either end land on the first or last day
+    assert_eq!(axis.date_at(-15.0), day(-1));
+    assert_eq!(axis.date_at(1_000.0), day(8));
+}
+
+#[test]
+fn test_drags_snap_to_whole_days() {
+    let axis = axis();
+    assert_eq!(axis.dragged(day(2), 0.0), day(2));
+    assert_eq!(axis.dragged(day(2), 9.0), day(2));
+    assert_eq!(axis.dragged(day(2), 11.0), day(3));
+    assert_eq!(axis.dragged(day(2), 45.0), day(4));
+    assert_eq!(axis.dragged(day(2), -11.0), day(1));
+    assert_eq!(axis.dragged(day(2), -500.0), day(-1));
+}
+
+#[test]
+fn test_rows_are_dated_todos_earliest_first() {
+    let undated = Todo::new(9, "Someday".to_string());
+    let todos = [due(1, 4), undated, due(2, -3), due(3, 4)];
+
+    let ids: Vec<usize> = rows(&todos).into_iter().map(|todo| todo.id).collect();
+    assert_eq!(ids, vec![2, 1, 3]);
+}
@@ -1,4 +1,9 @@
-use todo::models::{FilterState, Todo, TodoList};
+use chrono::{Datelike, Duration, NaiveDate, TimeZone, Utc};
+use todo::models::due_limits::DueDateLimits;
+use todo::models::{FilterState, QuickFilter, SortMode, Todo, TodoError, TodoList, WeekStart};
+use todo::utils::clock::{Clock, FixedClock};
+use todo::utils::constants::todo::TEXT_HISTORY_LIMIT;
+use todo::utils::dates::due_date_on;
 
 #[test]
 fn test_todo_creation() {
@@ -20,20 +25,99 @@ fn test_todo_toggle() {
     assert!(!todo.completed);
 }
 
+/// Wednesday, March 12, 2025.
+fn march_12() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, 12).unwrap()
+}
+
 #[test]
 fn test_filter_state_matches() {
     let active_todo = Todo::new(1, "Active todo".to_string());
     let mut completed_todo = Todo::new(2, "Completed todo".to_string());
     completed_todo.toggle();
+    let matches =
+        |filter: FilterState, todo: &Todo| filter.matches(todo, march_12(), WeekStart::Monday);
+
+    assert!(matches(FilterState::All, &active_todo));
+    assert!(matches(FilterState::All, &completed_todo));
+
+    assert!(matches(FilterState::Active, &active_todo));
+    assert!(!matches(FilterState::Active, &completed_todo));
+
+    assert!(!matches(FilterState::Completed, &active_todo));
+    assert!(matches(FilterState::Completed, &completed_todo));
+}
+
+#[test]
+fn test_due_filters_compare_due_days_with_the_local_date() {
+    let due = |day: u32| {
+        let mut todo = Todo::new(1, "Pay rent".to_string());
+        todo.set_due_date(Some(due_date_on(march_12().with_day(day).unwrap())));
+        todo
+    };
+    for utc_offset in [2, -8] {
+        // Just after midnight and late at night, where the local date and
+        // the UTC one differ on one side or the other
+        for (hour, minute) in [(0, 30), (23, 30)] {
+            let today = FixedClock::at_local(march_12(), hour, minute, utc_offset).today_local();
+            let due_today =
+                |todo: &Todo| FilterState::DueToday.matches(todo, today, WeekStart::Monday);
+            let this_week =
+                |todo: &Todo, week_start| FilterState::DueThisWeek.matches(todo, today, week_start);
 
-    assert!(FilterState::All.matches(&active_todo));
-    assert!(FilterState::All.matches(&completed_todo));
+            assert!(due_today(&due(12)));
+            assert!(!due_today(&due(11)));
+            assert!(!due_today(&due(13)));
 
-    assert!(FilterState::Active.matches(&active_todo));
-    assert!(!FilterState::Active.matches(&completed_todo));
+            // Monday to Sunday, or Sunday to Saturday
+            assert!(this_week(&due(10), WeekStart::Monday));
+            assert!(this_week(&due(16), WeekStart::Monday));
+            assert!(!this_week(&due(17), WeekStart::Monday));
+            assert!(!this_week(&due(16), WeekStart::Sunday));
+            assert!(this_week(&due(9), WeekStart::Sunday));
+        }
+    }
 
-    assert!(!FilterState::Completed.matches(&active_todo));
-    assert!(FilterState::Completed.matches(&completed_todo));
+    let mut archived = due(12);
+    archived.archived = true;
+    assert!(!FilterState::DueToday.matches(&archived, march_12(), WeekStart::Monday));
+}
+
+#[test]
+fn test_overdue_filter_starts_at_the_end_of_the_due_day() {
+    let due = |day: u32| {
+        let mut todo = Todo::new(1, "Pay rent".to_string());
+        todo.set_due_date(Some(due_date_on(march_12().with_day(day).unwrap())));
+        todo
+    };
+    for utc_offset in [2, -8] {
+        let today = FixedClock::at_local(march_12(), 23, 30, utc_offset).today_local();
+        let overdue = |todo: &Todo| FilterState::Overdue.matches(todo, today, WeekStart::Monday);
+
+        // Due today is not overdue yet; due yesterday is
+        assert!(!overdue(&due(12)));
+        assert!(overdue(&due(11)));
+        assert!(!overdue(&due(13)));
+
+        let mut done = due(10);
+        done.toggle();
+        assert!(!overdue(&done));
+        let mut archived = due(10);
+        archived.archived = true;
+        assert!(!overdue(&archived));
+    }
+}
+
+#[test]
+fn test_todos_without_a_due_date_match_no_due_filter() {
+    let todo = Todo::new(1, "Someday".to_string());
+    for filter in [
+        FilterState::DueToday,
+        FilterState::DueThisWeek,
+        FilterState::Overdue,
+    ] {
+        assert!(!filter.matches(&todo, march_12(), WeekStart::Monday));
+    }
 }
 
 #[test]
@@ -54,18 +138,21 @@ fn test_todo_list_operations() {
     assert_eq!(list.completed_count(), 1);
 
     // Update text
-    assert!(list.update_text(id2, "Updated second todo".to_string()));
+    assert!(
+        list.update_text(id2, "Updated second todo".to_string())
+            .is_ok()
+    );
     let todos = list.all();
     assert!(todos.iter().any(|t| t.text == "Updated second todo"));
 
     // Remove todo
-    assert!(list.remove(id1).is_some());
+    assert!(list.remove(id1).is_ok());
     assert_eq!(list.active_count(), 1);
     assert_eq!(list.completed_count(), 0);
 
     // Clear completed (none should be completed now)
     let cleared = list.clear_completed();
-    assert_eq!(cleared, 0);
+    assert_eq!(cleared.removed.len(), 0);
 }
 
 #[test]
@@ -85,17 +172,972 @@ fn test_todo_list_filtering() {
     let all = list.all();
     assert_eq!(all.len(), 3);
 
-    let filtered = list.filtered(FilterState::Active);
+    let filtered = list.filtered(FilterState::Active, march_12());
     assert_eq!(filtered.len(), 1);
     assert_eq!(filtered[0].id, id2);
 
-    let completed = list.filtered(FilterState::Completed);
+    let completed = list.filtered(FilterState::Completed, march_12());
     assert_eq!(completed.len(), 2);
     assert!(completed.iter().any(|t| t.id == id1));
     assert!(completed.iter().any(|t| t.id == id3));
 
     // Clear completed
     let cleared = list.clear_completed();
-    assert_eq!(cleared, 2);
+    assert_eq!(cleared.removed.len(), 2);
     assert_eq!(list.all().len(), 1);
 }
+
+#[test]
+fn test_todo_matches_search() {
+    let mut todo = Todo::new(1, "Buy MILK at the Café".to_string());
+    todo.add_tag("Errands".to_string());
+
+    assert!(todo.matches_search(""));
+    assert!(todo.matches_search("milk"));
+    assert!(todo.matches_search("café"));
+    assert!(todo.matches_search("errand"));
+    assert!(!todo.matches_search("bread"));
+    assert!(!todo.matches_search("buy milk at the café and more"));
+}
+
+#[test]
+fn test_search_looks_in_notes() {
+    let mut list = TodoList::new();
+    let plumber = list.add("Call the plumber".to_string());
+    list.add("Pay rent".to_string());
+    assert!(list.set_notes(plumber, "Ask about the KITCHEN tap".to_string()));
+
+    let ids: Vec<usize> = list.search("kitchen").iter().map(|todo| todo.id).collect();
+    assert_eq!(ids, vec![plumber]);
+    assert!(!list.set_notes(99, "No such todo".to_string()));
+}
+
+#[test]
+fn test_todo_list_search() {
+    let mut list = TodoList::new();
+    let first = list.add("Write report".to_string());
+    list.add("Walk the dog".to_string());
+    let third = list.add("Review REPORT draft".to_string());
+    list.reorder(third, first);
+
+    let ids: Vec<usize> = list.search("Report").iter().map(|todo| todo.id).collect();
+    assert_eq!(ids, vec![third, first]);
+    assert_eq!(list.search("").len(), 3);
+    assert!(list.search("groceries").is_empty());
+}
+
+/// A small list with a tag, a completed todo and a reordered pair. Built from
+/// plain todos with their fields set directly and their creation times
+/// cleared, so no timestamps are recorded and the JSON stays fixed.
+fn fixture_list() -> TodoList {
+    let mut buy_milk = Todo::new(1, "Buy milk".to_string());
+    buy_milk.tags.push("Shopping".to_string());
+    buy_milk.order = 2;
+    let mut write_report = Todo::new(2, "Write report".to_string());
+    write_report.completed = true;
+    write_report.order = 1;
+    let call_mom = Todo::new(3, "Call mom".to_string());
+    let mut todos = vec![buy_milk, write_report, call_mom];
+    for todo in &mut todos {
+        todo.created_at = None;
+    }
+    TodoList::from_todos(todos)
+}
+
+#[test]
+fn test_todo_list_snapshot() {
+    let json = serde_json::to_string(&fixture_list()).unwrap();
+    assert_eq!(
+        json,
+        concat!(
+            r#"{"todos":["#,
+            r#"{"id":2,"text":"Write report","completed":true,"due_date":null,"tags":[],"order":1},"#,
+            r#"{"id":1,"text":"Buy milk","completed":false,"due_date":null,"tags":["Shopping"],"order":2},"#,
+            r#"{"id":3,"text":"Call mom","completed":false,"due_date":null,"tags":[],"order":3}"#,
+            r#"],"next_id":4}"#
+        )
+    );
+}
+
+#[test]
+fn test_todo_list_serialization_is_stable() {
+    let first = serde_json::to_string(&fixture_list()).unwrap();
+    for _ in 0..10 {
+        assert_eq!(serde_json::to_string(&fixture_list()).unwrap(), first);
+    }
+}
+
+#[test]
+fn test_todo_list_equality_and_round_trip() {
+    let list = fixture_list();
+    let json = serde_json::to_string(&list).unwrap();
+    let restored: TodoList = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, list);
+
+    let mut changed = list.clone();
+    changed.toggle(1);
+    assert_ne!(changed, list);
+}
+
+#[test]
+fn test_todo_list_loads_legacy_map_format() {
+    let legacy = concat!(
+        r#"{"todos":{"#,
+        r#""3":{"id":3,"text":"Call mom","completed":false,"due_date":null,"tags":[],"order":3},"#,
+        r#""1":{"id":1,"text":"Buy milk","completed":false,"due_date":null,"tags":["Shopping"],"order":2},"#,
+        r#""2":{"id":2,"text":"Write report","completed":true,"due_date":null,"tags":[],"order":1}"#,
+        r#"},"next_id":4}"#
+    );
+    let restored: TodoList = serde_json::from_str(legacy).unwrap();
+    assert_eq!(restored, fixture_list());
+}
+
+#[test]
+fn test_persisted_json_round_trip_keeps_arrangement() {
+    let mut list = TodoList::new();
+    let ids: Vec<usize> = (1..=5).map(|i| list.add(format!("Todo {}", i))).collect();
+    list.reorder(ids[4], ids[0]);
+    list.reorder(ids[1], ids[3]);
+
+    let restored: TodoList = serde_json::from_str(&serde_json::to_string(&list).unwrap()).unwrap();
+    assert_eq!(restored.all(), list.all());
+    assert_eq!(
+        restored.filtered(FilterState::All, march_12()),
+        list.all(),
+        "filtered() lists todos in display order"
+    );
+}
+
+#[test]
+fn test_toggle_records_completed_at() {
+    let mut todo = Todo::new(1, "Test".to_string());
+    assert_eq!(todo.completed_at, None);
+
+    todo.toggle();
+    assert!(todo.completed_at.is_some());
+
+    todo.toggle();
+    assert_eq!(todo.completed_at, None);
+
+    let mut list = TodoList::new();
+    let id = list.add("Test".to_string());
+    let before = Utc::now();
+    list.toggle_completion(id);
+    assert!(list.get(id).unwrap().completed_at.unwrap() >= before);
+    list.toggle_completion(id);
+    assert_eq!(list.get(id).unwrap().completed_at, None);
+}
+
+#[test]
+fn test_completed_between_lists_todos_in_completion_order() {
+    let at = |hour: u32| Utc.with_ymd_and_hms(2025, 3, 5, hour, 0, 0).unwrap();
+    let done = |id: usize, completed_at: Option<u32>| {
+        let mut todo = Todo::new(id, format!("Todo {id}"));
+        todo.completed = true;
+        todo.completed_at = completed_at.map(at);
+        todo
+    };
+    let mut reopened = done(5, Some(10));
+    reopened.completed = false;
+    let list = TodoList::from_todos(vec![
+        done(1, Some(11)),
+        done(2, Some(9)),
+        done(3, Some(12)),
+        done(4, None),
+        reopened,
+        done(6, Some(8)),
+    ]);
+
+    let ids: Vec<usize> = list
+        .completed_between(at(9), at(12))
+        .iter()
+        .map(|todo| todo.id)
+        .collect();
+    assert_eq!(ids, [2, 1]);
+    assert!(list.completed_between(at(12), at(9)).is_empty());
+}
+
+#[test]
+fn test_new_todos_record_when_they_were_created() {
+    let before = Utc::now();
+    let todo = Todo::new(1, "Test".to_string());
+    let created_at = todo.created_at.unwrap();
+    assert!(created_at >= before && created_at <= Utc::now());
+
+    let json = serde_json::to_string(&todo).unwrap();
+    let restored: Todo = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.created_at, Some(created_at));
+}
+
+#[test]
+fn test_todos_saved_before_creation_times_still_load() {
+    let json = concat!(
+        r#"{"todos":[{"id":1,"text":"Call the plumber","completed":false,"#,
+        r#""due_date":null,"tags":[],"order":1}],"next_id":2}"#
+    );
+    let list: TodoList = serde_json::from_str(json).unwrap();
+    assert_eq!(list.get(1).unwrap().created_at, None);
+    assert_eq!(list.get(1).unwrap().completed_at, None);
+}
+
+#[test]
+fn test_sorted_by_created_puts_untimed_todos_first() {
+    let now = Utc::now();
+    let created = |id: usize, days_ago: Option<i64>| {
+        let mut todo = Todo::new(id, format!("Todo {id}"));
+        todo.created_at = days_ago.map(|days| now - Duration::days(days));
+        todo
+    };
+    let list = TodoList::from_todos(vec![
+        created(1, Some(1)),
+        created(2, None),
+        created(3, Some(5)),
+        created(4, Some(1)),
+        created(5, None),
+    ]);
+
+    let ids: Vec<usize> = list
+        .sorted_by_created()
+        .iter()
+        .map(|todo| todo.id)
+        .collect();
+    assert_eq!(ids, [2, 5, 3, 1, 4]);
+}
+
+#[test]
+fn test_trash_and_restore() {
+    let mut list = TodoList::new();
+    let first = list.add("First".to_string());
+    let second = list.add("Second".to_string());
+    let third = list.add("Third".to_string());
+
+    assert!(list.move_to_trash(first).is_ok());
+    assert!(list.move_to_trash(third).is_ok());
+    assert_eq!(list.move_to_trash(first), Err(TodoError::NotFound(first)));
+    assert_eq!(list.total_count(), 1);
+    list.check_invariants().unwrap();
+
+    let trashed = list.trashed();
+    assert_eq!(
+        trashed.iter().map(|todo| todo.id).collect::<Vec<_>>(),
+        vec![third, first]
+    );
+    assert!(trashed.iter().all(|todo| todo.deleted_at.is_some()));
+
+    // Restored todos keep their id and go to the end of the list
+    assert!(list.restore(first));
+    assert!(!list.restore(first));
+    let ids: Vec<usize> = list.all().iter().map(|todo| todo.id).collect();
+    assert_eq!(ids, vec![second, first]);
+    assert_eq!(list.all()[1].deleted_at, None);
+    list.check_invariants().unwrap();
+
+    // New todos never reuse a trashed id
+    let fourth = list.add("Fourth".to_string());
+    assert!(fourth > third);
+}
+
+#[test]
+fn test_trash_is_persisted() {
+    let mut list = TodoList::new();
+    let id = list.add("Deleted".to_string());
+    list.add("Kept".to_string());
+    list.move_to_trash(id).unwrap();
+
+    let json = serde_json::to_string(&list).unwrap();
+    let restored: TodoList = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, list);
+    assert_eq!(restored.trashed().len(), 1);
+
+    // Lists saved without a trash load with an empty one
+    let without_trash = serde_json::to_string(&fixture_list()).unwrap();
+    assert!(!without_trash.contains("trash"));
+    let restored: TodoList = serde_json::from_str(&without_trash).unwrap();
+    assert!(restored.trashed().is_empty());
+}
+
+#[test]
+fn test_purging_the_trash() {
+    let mut list = TodoList::new();
+    let old = list.add("Old".to_string());
+    let recent = list.add("Recent".to_string());
+    list.move_to_trash(old).unwrap();
+    list.move_to_trash(recent).unwrap();
+    assert_eq!(list.trash_count(), 2);
+
+    let deleted_at = list.trashed()[0].deleted_at.unwrap();
+    assert_eq!(list.purge_older_than(deleted_at + Duration::days(5), 30), 0);
+    assert_eq!(
+        list.purge_older_than(deleted_at + Duration::days(31), 30),
+        2
+    );
+    assert_eq!(list.trash_count(), 0);
+
+    let mut list = TodoList::new();
+    for text in ["One", "Two", "Three"] {
+        let id = list.add(text.to_string());
+        list.move_to_trash(id).unwrap();
+    }
+    let kept = list.add("Kept".to_string());
+    assert!(list.purge(2));
+    assert!(!list.purge(2));
+    // Todos in the list are not deleted
+    assert!(!list.purge(kept));
+    assert_eq!(list.trash_count(), 2);
+    assert_eq!(list.empty_trash(), 2);
+    assert_eq!(list.empty_trash(), 0);
+    list.check_invariants().unwrap();
+}
+
+#[test]
+fn test_trash_filter_matches_only_trashed_todos() {
+    let mut list = TodoList::new();
+    let id = list.add("Deleted".to_string());
+    list.add("Kept".to_string());
+    list.move_to_trash(id).unwrap();
+
+    assert!(list.filtered(FilterState::Trash, march_12()).is_empty());
+    assert!(list.trashed().iter().all(|todo| FilterState::Trash.matches(
+        todo,
+        march_12(),
+        WeekStart::Monday
+    )));
+    assert!(!FilterState::Trash.matches(&list.all()[0], march_12(), WeekStart::Monday));
+}
+
+#[test]
+fn test_due_soon_window() {
+    let now = Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap();
+    let lead = Duration::hours(24);
+
+    let mut list = TodoList::new();
+    let later = list.add("Later".to_string());
+    let tomorrow = list.add("Tomorrow".to_string());
+    let overdue = list.add("Overdue".to_string());
+    let done = list.add("Done".to_string());
+    list.add("No due date".to_string());
+
+    list.set_due_date(later, Some(now + Duration::hours(25)))
+        .unwrap();
+    list.set_due_date(tomorrow, Some(now + Duration::hours(24)))
+        .unwrap();
+    list.set_due_date(overdue, Some(now - Duration::hours(3)))
+        .unwrap();
+    list.set_due_date(done, Some(now + Duration::hours(1)))
+        .unwrap();
+    list.toggle(done);
+
+    // Soonest first; overdue todos stay listed until completed
+    let ids: Vec<usize> = list
+        .due_soon(now, lead)
+        .iter()
+        .map(|todo| todo.id)
+        .collect();
+    assert_eq!(ids, vec![overdue, tomorrow]);
+
+    assert_eq!(list.due_soon(now, Duration::zero()).len(), 1);
+    assert_eq!(list.due_soon(now, Duration::hours(25)).len(), 3);
+}
+
+#[test]
+fn test_locked_todo_rejects_edits_and_deletion() {
+    let mut list = TodoList::new();
+    let passport = list.add("Renew passport".to_string());
+    let milk = list.add("Buy milk".to_string());
+    assert!(list.set_locked(passport, true));
+    assert!(!list.set_locked(999, true));
+
+    assert_eq!(
+        list.update_text(passport, "Renew".to_string()),
+        Err(TodoError::Locked(passport))
+    );
+    assert_eq!(list.remove(passport), Err(TodoError::Locked(passport)));
+    assert_eq!(
+        list.move_to_trash(passport),
+        Err(TodoError::Locked(passport))
+    );
+    assert_eq!(
+        list.update_text(999, "Missing".to_string()),
+        Err(TodoError::NotFound(999))
+    );
+    assert_eq!(list.get(passport).unwrap().text, "Renew passport");
+    assert!(list.trashed().is_empty());
+
+    // Locked todos can still be completed, tagged and given a due date
+    assert!(list.toggle(passport));
+    assert!(list.add_tag(passport, "important-docs".to_string()));
+
+    // Unlocking allows changes again
+    assert!(list.set_locked(passport, false));
+    assert!(
+        list.update_text(passport, "Renew passport now".to_string())
+            .is_ok()
+    );
+    assert!(list.move_to_trash(milk).is_ok());
+    list.check_invariants().unwrap();
+}
+
+#[test]
+fn test_clear_completed_skips_locked_todos() {
+    let mut list = TodoList::new();
+    let locked = list.add("Locked".to_string());
+    let done = list.add("Done".to_string());
+    let open = list.add("Open".to_string());
+    list.toggle(locked);
+    list.toggle(done);
+    list.set_locked(locked, true);
+
+    let outcome = list.clear_completed();
+    assert_eq!(outcome.removed.len(), 1);
+    assert_eq!(outcome.skipped_locked, 1);
+    let ids: Vec<usize> = list.all().iter().map(|todo| todo.id).collect();
+    assert_eq!(ids, vec![locked, open]);
+    list.check_invariants().unwrap();
+}
+
+#[test]
+fn test_restore_cleared_puts_todos_back_in_place() {
+    let mut list = TodoList::new();
+    let ids: Vec<usize> = ["One", "Two", "Three", "Four"]
+        .into_iter()
+        .map(|text| list.add(text.to_string()))
+        .collect();
+    list.toggle(ids[0]);
+    list.toggle(ids[2]);
+    let before = list.all();
+
+    let cleared = list.clear_completed();
+    assert_eq!(cleared.removed.len(), 2);
+    assert_eq!(list.all().len(), 2);
+
+    assert_eq!(list.restore_cleared(cleared.removed.clone()), 2);
+    assert_eq!(list.all(), before);
+    list.check_invariants().unwrap();
+
+    // Restoring again is a no-op, as the ids are in use
+    assert_eq!(list.restore_cleared(cleared.removed), 0);
+}
+
+#[test]
+fn test_locked_flag_is_persisted_only_when_set() {
+    let mut list = TodoList::new();
+    let id = list.add("Renew passport".to_string());
+    list.add("Buy milk".to_string());
+
+    let json = serde_json::to_string(&list).unwrap();
+    assert!(!json.contains("locked"));
+
+    list.set_locked(id, true);
+    let json = serde_json::to_string(&list).unwrap();
+    assert_eq!(json.matches(r#""locked":true"#).count(), 1);
+    let restored: TodoList = serde_json::from_str(&json).unwrap();
+    assert!(restored.get(id).unwrap().locked);
+}
+
+#[test]
+fn test_quick_filters_match_due_dates() {
+    // Wednesday
+    let today = chrono::NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+    let due = |id: usize, day: u32, completed: bool| {
+        let mut todo = Todo::new(id, format!("Todo {id}"));
+        todo.due_date = Some(Utc.with_ymd_and_hms(2025, 3, day, 0, 0, 0).unwrap());
+        todo.completed = completed;
+        todo
+    };
+    let overdue = due(1, 10, false);
+    let done_late = due(2, 10, true);
+    let due_today = due(3, 12, false);
+    let due_friday = due(4, 14, false);
+    let due_next_week = due(5, 18, false);
+    let undated = Todo::new(6, "Someday".to_string());
+
+    let matching = |quick_filter: QuickFilter| -> Vec<usize> {
+        [
+            &overdue,
+            &done_late,
+            &due_today,
+            &due_friday,
+            &due_next_week,
+            &undated,
+        ]
+        .into_iter()
+        .filter(|todo| quick_filter.matches(todo, today, WeekStart::Monday))
+        .map(|todo| todo.id)
+        .collect()
+    };
+    assert_eq!(matching(QuickFilter::Overdue), vec![1]);
+    assert_eq!(matching(QuickFilter::Today), vec![3]);
+    assert_eq!(matching(QuickFilter::ThisWeek), vec![3, 4]);
+
+    // The pill is persisted as its name
+    assert_eq!(
+        serde_json::to_string(&Some(QuickFilter::ThisWeek)).unwrap(),
+        r#""ThisWeek""#
+    );
+}
+
+#[test]
+fn test_shift_due_dates_rolls_over_months_and_years() {
+    let mut list = TodoList::new();
+    let end_of_month = list.add("Rent".to_string());
+    let end_of_year = list.add("Review".to_string());
+    let undated = list.add("Someday".to_string());
+    list.set_due_date(
+        end_of_month,
+        Some(Utc.with_ymd_and_hms(2025, 1, 28, 0, 0, 0).unwrap()),
+    )
+    .unwrap();
+    list.set_due_date(
+        end_of_year,
+        Some(Utc.with_ymd_and_hms(2025, 12, 29, 0, 0, 0).unwrap()),
+    )
+    .unwrap();
+
+    let ids = [end_of_month, end_of_year, undated, 99];
+    assert_eq!(
+        list.shift_due_dates(&ids, 7).shifted,
+        [end_of_month, end_of_year]
+    );
+    let due = |list: &TodoList, id| list.get(id).unwrap().due_date;
+    assert_eq!(
+        due(&list, end_of_month),
+        Some(Utc.with_ymd_and_hms(2025, 2, 4, 0, 0, 0).unwrap())
+    );
+    assert_eq!(
+        due(&list, end_of_year),
+        Some(Utc.with_ymd_and_hms(2026, 1, 5, 0, 0, 0).unwrap())
+    );
+    assert_eq!(due(&list, undated), None);
+}
+
+#[test]
+fn test_shift_due_dates_backwards_and_undo() {
+    let mut list = TodoList::new();
+    let id = list.add("Report".to_string());
+    let original = Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap();
+    list.set_due_date(id, Some(original)).unwrap();
+
+    // Back across the end of a non-leap February, then into the previous year
+    assert_eq!(list.shift_due_dates(&[id], -1).shifted, [id]);
+    assert_eq!(
+        list.get(id).unwrap().due_date,
+        Some(Utc.with_ymd_and_hms(2025, 2, 28, 0, 0, 0).unwrap())
+    );
+    assert_eq!(list.shift_due_dates(&[id], -60).shifted, [id]);
+    assert_eq!(
+        list.get(id).unwrap().due_date,
+        Some(Utc.with_ymd_and_hms(2024, 12, 30, 0, 0, 0).unwrap())
+    );
+
+    // Shifting back by the opposite offsets restores the date
+    list.shift_due_dates(&[id], 61);
+    assert_eq!(list.get(id).unwrap().due_date, Some(original));
+
+    // Offsets that leave the supported range change nothing
+    assert_eq!(list.shift_due_dates(&[id], i64::MAX).refused, [id]);
+    assert_eq!(list.get(id).unwrap().due_date, Some(original));
+}
+
+#[test]
+fn test_shift_due_dates_keeps_to_the_due_date_limits() {
+    let now = Utc.with_ymd_and_hms(2025, 3, 12, 9, 0, 0).unwrap();
+    let limits = DueDateLimits::default();
+    let mut list = TodoList::new();
+    let soon = list.add("Report".to_string());
+    let far = list.add("Renew passport".to_string());
+    list.set_due_date(soon, Some(now)).unwrap();
+    let last_day = limits.latest(now.date_naive());
+    list.set_due_date(far, Some(due_date_on(last_day))).unwrap();
+
+    // Past the latest allowed day is refused, and the todo left alone
+    let moved = list.shift_due_dates_within(&[soon, far], 1, limits, now);
+    assert_eq!(moved.shifted, [soon]);
+    assert_eq!(moved.refused, [far]);
+    assert_eq!(list.get(far).unwrap().due_date, Some(due_date_on(last_day)));
+
+    // Moving later postpones, moving earlier does not
+    assert_eq!(list.get(soon).unwrap().postponed_count, 1);
+    assert_eq!(list.get(far).unwrap().postponed_count, 0);
+    list.shift_due_dates_within(&[soon, far], -1, limits, now);
+    assert_eq!(list.get(soon).unwrap().postponed_count, 1);
+    assert_eq!(
+        list.get(far).unwrap().due_date,
+        Some(due_date_on(last_day - Duration::days(1)))
+    );
+}
+
+#[test]
+fn test_due_date_range() {
+    let mut list = TodoList::new();
+    let a = list.add("A".to_string());
+    let b = list.add("B".to_string());
+    let c = list.add("C".to_string());
+    let early = Utc.with_ymd_and_hms(2025, 3, 3, 0, 0, 0).unwrap();
+    let late = Utc.with_ymd_and_hms(2025, 3, 20, 0, 0, 0).unwrap();
+    list.set_due_date(a, Some(late)).unwrap();
+    list.set_due_date(b, Some(early)).unwrap();
+
+    assert_eq!(list.due_date_range(&[a, b, c]), Some((early, late)));
+    assert_eq!(list.due_date_range(&[a]), Some((late, late)));
+    assert_eq!(list.due_date_range(&[c]), None);
+}
+
+/// Gets the earlier texts of a todo, oldest first.
+fn history_texts(list: &TodoList, id: usize) -> Vec<&str> {
+    list.text_history(id)
+        .iter()
+        .map(|(_, text)| text.as_str())
+        .collect()
+}
+
+#[test]
+fn test_text_history_keeps_the_last_versions() {
+    let mut list = TodoList::new();
+    let id = list.add("Draft 0".to_string());
+    assert!(list.text_history(id).is_empty());
+
+    for draft in 1..=TEXT_HISTORY_LIMIT + 2 {
+        list.update_text(id, format!("Draft {}", draft)).unwrap();
+    }
+    let history = history_texts(&list, id);
+    assert_eq!(history.len(), TEXT_HISTORY_LIMIT);
+    assert_eq!(history.first(), Some(&"Draft 2"));
+    assert_eq!(history.last(), Some(&"Draft 11"));
+
+    // Saving the same text again is not a new version
+    list.update_text(id, "Draft 12".to_string()).unwrap();
+    assert_eq!(history_texts(&list, id).last(), Some(&"Draft 11"));
+    assert!(list.text_history(999).is_empty());
+}
+
+#[test]
+fn test_restore_text_is_a_new_version() {
+    let mut list = TodoList::new();
+    let id = list.add("Call mum".to_string());
+    list.update_text(id, "Call mum on Sunday".to_string())
+        .unwrap();
+    list.update_text(id, "Call dad".to_string()).unwrap();
+
+    list.restore_text(id, 0).unwrap();
+    assert_eq!(list.get(id).unwrap().text, "Call mum");
+    assert_eq!(
+        history_texts(&list, id),
+        ["Call mum", "Call mum on Sunday", "Call dad"]
+    );
+
+    // The text replaced by the restore can be brought back in turn
+    list.restore_text(id, 2).unwrap();
+    assert_eq!(list.get(id).unwrap().text, "Call dad");
+
+    assert_eq!(
+        list.restore_text(id, 10),
+        Err(TodoError::VersionNotFound(id))
+    );
+    assert_eq!(
+        list.restore_text(999, 0),
+        Err(TodoError::VersionNotFound(999))
+    );
+    assert!(list.set_locked(id, true));
+    assert_eq!(list.restore_text(id, 0), Err(TodoError::Locked(id)));
+    assert_eq!(list.get(id).unwrap().text, "Call dad");
+}
+
+/// Gets whether each todo in the list is done, in list order.
+fn completed_states(list: &TodoList) -> Vec<bool> {
+    list.all().iter().map(|todo| todo.completed).collect()
+}
+
+#[test]
+fn test_toggle_all_completes_an_all_active_list() {
+    let mut list = TodoList::new();
+    for text in ["Buy milk", "Call the plumber", "Renew the passport"] {
+        list.add(text.to_string());
+    }
+
+    assert_eq!(list.toggle_all(None), 3);
+    assert_eq!(completed_states(&list), [true, true, true]);
+}
+
+#[test]
+fn test_toggle_all_completes_the_rest_of_a_mixed_list() {
+    let mut list = TodoList::new();
+    let milk = list.add("Buy milk".to_string());
+    list.add("Call the plumber".to_string());
+    list.add("Renew the passport".to_string());
+    list.toggle(milk);
+    let completed_at = list.get(milk).unwrap().completed_at;
+
+    // Only the active todos change; the done one keeps its completion time
+    assert_eq!(list.toggle_all(None), 2);
+    assert_eq!(completed_states(&list), [true, true, true]);
+    assert_eq!(list.get(milk).unwrap().completed_at, completed_at);
+}
+
+#[test]
+fn test_toggle_all_reopens_an_all_completed_list() {
+    let mut list = TodoList::new();
+    let ids: Vec<usize> = ["Buy milk", "Call the plumber"]
+        .into_iter()
+        .map(|text| list.add(text.to_string()))
+        .collect();
+    for id in &ids {
+        list.toggle(*id);
+    }
+
+    assert_eq!(list.toggle_all(None), 2);
+    assert_eq!(completed_states(&list), [false, false]);
+    assert!(list.all().iter().all(|todo| todo.completed_at.is_none()));
+}
+
+#[test]
+fn test_toggle_all_only_changes_the_given_todos() {
+    let mut list = TodoList::new();
+    let milk = list.add("Buy milk".to_string());
+    let plumber = list.add("Call the plumber".to_string());
+    let passport = list.add("Renew the passport".to_string());
+    let old = list.add("Old project".to_string());
+    list.toggle(plumber);
+    list.toggle(old);
+    list.archive(old);
+
+    // The given todos are all done, so they are reopened; the rest stay
+    assert_eq!(list.toggle_all(Some(&[plumber, old, 999])), 1);
+    assert_eq!(completed_states(&list), [false, false, false]);
+    assert!(list.get(old).unwrap().completed);
+
+    assert_eq!(list.toggle_all(Some(&[milk, passport])), 2);
+    assert!(!list.get(plumber).unwrap().completed);
+    assert_eq!(list.toggle_all(Some(&[])), 0);
+}
+
+#[test]
+fn test_add_tag_many_skips_todos_already_tagged() {
+    let mut list = TodoList::new();
+    let milk = list.add("Buy milk".to_string());
+    let plumber = list.add("Call the plumber".to_string());
+    let passport = list.add("Renew the passport".to_string());
+    list.add_tag(plumber, "v2-release".to_string());
+    let untouched = list.get(plumber).unwrap().updated_at;
+
+    assert_eq!(
+        list.add_tag_many(&[milk, plumber, passport, 999], "v2-release"),
+        2
+    );
+    for id in [milk, plumber, passport] {
+        assert_eq!(list.get(id).unwrap().tags, ["v2-release"]);
+    }
+    assert_eq!(list.get(plumber).unwrap().updated_at, untouched);
+
+    // Tagging again changes nothing
+    assert_eq!(list.add_tag_many(&[milk, plumber], "v2-release"), 0);
+    assert_eq!(list.get(milk).unwrap().tags, ["v2-release"]);
+}
+
+#[test]
+fn test_remove_tag_many_counts_only_todos_with_the_tag() {
+    let mut list = TodoList::new();
+    let milk = list.add("Buy milk".to_string());
+    let plumber = list.add("Call the plumber".to_string());
+    let passport = list.add("Renew the passport".to_string());
+    list.add_tag(milk, "v2-release".to_string());
+    list.add_tag(milk, "home".to_string());
+    list.add_tag(passport, "v2-release".to_string());
+
+    assert_eq!(
+        list.remove_tag_many(&[milk, plumber, passport, 999], "v2-release"),
+        2
+    );
+    assert_eq!(list.get(milk).unwrap().tags, ["home"]);
+    assert!(list.get(passport).unwrap().tags.is_empty());
+    assert_eq!(list.remove_tag_many(&[milk, passport], "v2-release"), 0);
+    assert_eq!(list.tags_of(&[milk, plumber, passport]), ["home"]);
+}
+
+#[test]
+fn test_remove_tag_everywhere_keeps_the_todos() {
+    let mut list = TodoList::new();
+    let milk = list.add("Buy milk".to_string());
+    let plumber = list.add("Call the plumber".to_string());
+    let passport = list.add("Renew the passport".to_string());
+    list.add_tag(milk, "v2-release".to_string());
+    list.add_tag(milk, "home".to_string());
+    list.add_tag(plumber, "v2-release".to_string());
+    list.add_tag(passport, "v2-release".to_string());
+    list.move_to_trash(passport).unwrap();
+
+    assert_eq!(
+        list.remove_tag_everywhere("v2-release"),
+        3,
+        "trash included"
+    );
+    assert_eq!(list.all().len(), 2);
+    assert_eq!(list.get(milk).unwrap().tags, ["home"]);
+    assert!(list.get(plumber).unwrap().tags.is_empty());
+    assert_eq!(list.remove_tag_everywhere("v2-release"), 0);
+
+    // Restoring a deleted todo does not bring the tag back
+    list.restore(passport);
+    assert_eq!(list.all_tags(), ["home"]);
+}
+
+#[test]
+fn test_deleted_default_tags_stay_listed() {
+    let defaults = ["Personal", "Work"];
+    let mut list = TodoList::new();
+    let report = list.add("Write the report".to_string());
+    list.add_tag(report, "Work".to_string());
+    list.add_tag(report, "q3".to_string());
+    assert_eq!(list.sorted_tags(&defaults), ["Personal", "Work", "q3"]);
+
+    assert_eq!(list.remove_tag_everywhere("q3"), 1);
+    assert_eq!(list.sorted_tags(&defaults), ["Personal", "Work"]);
+
+    // A default tag is stripped from the todos but still offered
+    assert_eq!(list.remove_tag_everywhere("Work"), 1);
+    assert!(list.get(report).unwrap().tags.is_empty());
+    assert_eq!(list.sorted_tags(&defaults), ["Personal", "Work"]);
+    assert_eq!(list.remove_tag_everywhere("Personal"), 0);
+}
+
+/// Gets the texts of todos in the order given.
+fn texts(todos: &[Todo]) -> Vec<&str> {
+    todos.iter().map(|todo| todo.text.as_str()).collect()
+}
+
+#[test]
+fn test_sorted_by_due_date_puts_undated_todos_last() {
+    let mut list = TodoList::new();
+    let someday = list.add("Someday".to_string());
+    let taxes = list.add("File taxes".to_string());
+    let dentist = list.add("Dentist".to_string());
+    let plumber = list.add("Call the plumber".to_string());
+    list.add("Maybe later".to_string());
+    let march = |day| Some(Utc.with_ymd_and_hms(2025, 3, day, 17, 0, 0).unwrap());
+    list.set_due_date(taxes, march(20)).unwrap();
+    list.set_due_date(dentist, march(12)).unwrap();
+    list.set_due_date(plumber, march(20)).unwrap();
+
+    // Todos due the same day, and the undated ones, keep the manual order
+    assert_eq!(
+        texts(&list.sorted(SortMode::DueDateAsc)),
+        [
+            "Dentist",
+            "File taxes",
+            "Call the plumber",
+            "Someday",
+            "Maybe later"
+        ]
+    );
+    list.reorder(plumber, taxes);
+    list.set_due_date(someday, march(1)).unwrap();
+    assert_eq!(
+        texts(&list.sorted(SortMode::DueDateAsc)),
+        [
+            "Someday",
+            "Dentist",
+            "Call the plumber",
+            "File taxes",
+            "Maybe later"
+        ]
+    );
+}
+
+#[test]
+fn test_sorted_by_other_modes() {
+    let mut list = TodoList::new();
+    let milk = list.add("buy milk".to_string());
+    let plumber = list.add("Call the plumber".to_string());
+    let passport = list.add("Archive old mail".to_string());
+    list.add_tag(plumber, "urgent".to_string());
+    list.set_due_date(passport, Some(Utc::now())).unwrap();
+    list.toggle(milk);
+    list.archive(milk);
+
+    assert_eq!(list.sorted(SortMode::ManualOrder), list.all());
+    assert_eq!(
+        texts(&list.sorted(SortMode::Alphabetical)),
+        ["Archive old mail", "Call the plumber"]
+    );
+    assert_eq!(
+        texts(&list.sorted(SortMode::Priority)),
+        ["Call the plumber", "Archive old mail"]
+    );
+}
+
+#[test]
+fn test_sorted_newest_first() {
+    let created = |id: usize, days_ago: Option<i64>| {
+        let mut todo = Todo::new(id, format!("Todo {id}"));
+        todo.order = id;
+        todo.created_at = days_ago.map(|days| Utc::now() - Duration::days(days));
+        todo
+    };
+    let mut todos = vec![
+        created(1, Some(3)),
+        created(2, None),
+        created(3, Some(1)),
+        created(4, Some(2)),
+    ];
+
+    SortMode::CreatedDesc.sort(&mut todos);
+    assert_eq!(texts(&todos), ["Todo 3", "Todo 4", "Todo 1", "Todo 2"]);
+    SortMode::ManualOrder.sort(&mut todos);
+    assert_eq!(texts(&todos), ["Todo 1", "Todo 2", "Todo 3", "Todo 4"]);
+}
+
+#[test]
+fn test_sort_mode_labels_round_trip() {
+    for mode in SortMode::ALL {
+        assert_eq!(SortMode::parse(mode.label()), Some(mode));
+    }
+    assert_eq!(SortMode::parse(" due DATE "), Some(SortMode::DueDateAsc));
+    assert_eq!(SortMode::parse("Random"), None);
+    assert_eq!(SortMode::default(), SortMode::ManualOrder);
+}
+
+#[test]
+fn test_overdue_is_before_the_start_of_today_in_local_time() {
+    let march = |day: u32| NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+    for utc_offset in [2, -8] {
+        // Just after midnight and late at night, where the local date and
+        // the UTC one differ on one side or the other
+        for (hour, minute) in [(0, 30), (23, 30)] {
+            let today = FixedClock::at_local(march(12), hour, minute, utc_offset).today_local();
+            assert_eq!(today, march(12));
+            let mut todo = Todo::new(1, "File taxes".to_string());
+            assert!(!todo.is_overdue(today));
+
+            // Due today is not overdue yet, due yesterday is
+            todo.set_due_date(Some(due_date_on(march(12))));
+            assert!(!todo.is_overdue(today));
+            todo.set_due_date(Some(due_date_on(march(13))));
+            assert!(!todo.is_overdue(today));
+            todo.set_due_date(Some(due_date_on(march(11))));
+            assert!(todo.is_overdue(today));
+        }
+    }
+}
+
+#[test]
+fn test_completed_todos_are_never_overdue() {
+    let today = FixedClock::at_local(NaiveDate::from_ymd_opt(2025, 3, 12).unwrap(), 9, 30, -8)
+        .today_local();
+    let mut list = TodoList::new();
+    let taxes = list.add("File taxes".to_string());
+    let rent = list.add("Pay rent".to_string());
+    let archived = list.add("Old errand".to_string());
+    list.add("No date".to_string());
+    for id in [taxes, rent, archived] {
+        list.set_due_date(id, Some(due_date_on(today - Duration::days(11))))
+            .unwrap();
+    }
+    assert_eq!(list.overdue_count(today), 3);
+
+    list.toggle(rent);
+    assert!(!list.get(rent).unwrap().is_overdue(today));
+    list.toggle(archived);
+    list.archive(archived);
+    list.toggle(archived);
+    assert!(list.get(archived).unwrap().is_overdue(today));
+    assert_eq!(list.overdue_count(today), 1);
+}
@@ -0,0 +1,136 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use todo::models::{Todo, TodoList};
+
+/// When the sample todos last changed, long before any test runs.
+fn long_ago() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()
+}
+
+/// Builds a list of "Buy milk", tagged "Shopping", and "Write report", both
+/// last changed [`long_ago`].
+fn list() -> TodoList {
+    let mut milk = Todo::new(1, "Buy milk".to_string());
+    milk.tags.push("Shopping".to_string());
+    let report = Todo::new(2, "Write report".to_string());
+    let mut todos = vec![milk, report];
+    for todo in &mut todos {
+        todo.updated_at = Some(long_ago());
+    }
+    TodoList::from_todos(todos)
+}
+
+/// Runs a change on the sample list and gets when todo 1 last changed.
+fn updated_after(change: impl FnOnce(&mut TodoList)) -> DateTime<Utc> {
+    let mut list = list();
+    change(&mut list);
+    list.get(1).unwrap().updated_at.unwrap()
+}
+
+/// Checks that a change bumped todo 1 to about now.
+fn assert_bumped(change: impl FnOnce(&mut TodoList)) {
+    let before = Utc::now();
+    let updated_at = updated_after(change);
+    assert!(updated_at >= before && updated_at <= Utc::now());
+}
+
+#[test]
+fn test_update_text_bumps_updated_at() {
+    assert_bumped(|list| list.update_text(1, "Buy oat milk".to_string()).unwrap());
+    assert_bumped(|list| {
+        list.update_text(1, "Buy oat milk".to_string()).unwrap();
+        list.restore_text(1, 0).unwrap();
+    });
+}
+
+#[test]
+fn test_set_due_date_bumps_updated_at() {
+    assert_bumped(|list| {
+        list.set_due_date(1, Some(Utc::now())).unwrap();
+    });
+    assert_bumped(|list| {
+        list.shift_due_dates(&[1], 0);
+        list.set_due_date(1, Some(Utc::now())).unwrap();
+        list.shift_due_dates(&[1], 3);
+    });
+}
+
+#[test]
+fn test_add_and_remove_tag_bump_updated_at() {
+    assert_bumped(|list| {
+        list.add_tag(1, "Weekly".to_string());
+    });
+    assert_bumped(|list| {
+        list.remove_tag(1, "Shopping");
+    });
+}
+
+#[test]
+fn test_toggle_completion_bumps_updated_at() {
+    assert_bumped(|list| {
+        list.toggle_completion(1);
+    });
+    assert_bumped(|list| {
+        list.toggle_completion(1);
+        list.toggle_completion(1);
+    });
+}
+
+#[test]
+fn test_changes_that_change_nothing_leave_updated_at_alone() {
+    assert_eq!(
+        updated_after(|list| list.update_text(1, "Buy milk".to_string()).unwrap()),
+        long_ago()
+    );
+    assert_eq!(
+        updated_after(|list| {
+            list.set_due_date(1, None).unwrap();
+        }),
+        long_ago()
+    );
+    assert_eq!(
+        updated_after(|list| {
+            list.add_tag(1, "Shopping".to_string());
+            list.remove_tag(1, "Weekly");
+        }),
+        long_ago()
+    );
+
+    // Other todos keep theirs
+    let mut list = list();
+    list.toggle_completion(1);
+    assert_eq!(list.get(2).unwrap().updated_at, Some(long_ago()));
+}
+
+#[test]
+fn test_locked_todos_refusing_edits_are_not_bumped() {
+    let mut list = list();
+    list.set_locked(1, true);
+    assert!(list.update_text(1, "Buy oat milk".to_string()).is_err());
+    assert_eq!(list.get(1).unwrap().updated_at, Some(long_ago()));
+}
+
+#[test]
+fn test_recently_modified_lists_the_latest_changes_first() {
+    let mut list = list();
+    let added = list.add("Call mum".to_string());
+    let since = Utc::now();
+    assert!(list.recently_modified(since).is_empty());
+
+    list.toggle_completion(2);
+    list.add_tag(1, "Weekly".to_string());
+    let ids: Vec<usize> = list
+        .recently_modified(since)
+        .iter()
+        .map(|todo| todo.id)
+        .collect();
+    assert_eq!(ids, [1, 2]);
+
+    // Everything changed since long ago, except what was never changed
+    let ids: Vec<usize> = list
+        .recently_modified(long_ago() - Duration::days(1))
+        .iter()
+        .map(|todo| todo.id)
+        .collect();
+    assert_eq!(ids, [1, 2]);
+    assert!(!ids.contains(&added));
+}
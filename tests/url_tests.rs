@@ -0,0 +1,103 @@
+use todo::models::{Todo, TodoList};
+use todo::utils::export::{ExportError, export_json, import_json};
+use todo::utils::text::{UrlError, split_trailing_url, validate_url};
+
+#[test]
+fn test_validate_url_accepts_web_addresses() {
+    for url in [
+        "https://example.com",
+        "http://localhost:8080/tasks?id=4#notes",
+        "HTTPS://Example.com/Docs",
+        "https://user@git.example.org/repo",
+        "https://[::1]:3000/",
+    ] {
+        assert_eq!(validate_url(url), Ok(url.to_string()), "{url}");
+    }
+    assert_eq!(
+        validate_url("  https://example.com/a \n"),
+        Ok("https://example.com/a".to_string())
+    );
+}
+
+#[test]
+fn test_validate_url_rejects_other_values() {
+    for url in [
+        "example.com",
+        "www.example.com",
+        "mailto:sam@example.com",
+        "ftp://example.com",
+        "",
+    ] {
+        assert_eq!(validate_url(url), Err(UrlError::NotWeb), "{url}");
+    }
+    for url in [
+        "https://",
+        "https:///path",
+        "https://exa mple.com",
+        "https://bad_host!/",
+    ] {
+        assert_eq!(validate_url(url), Err(UrlError::Invalid), "{url}");
+    }
+}
+
+#[test]
+fn test_split_trailing_url_needs_text_before_a_valid_link() {
+    assert_eq!(
+        split_trailing_url("Review the PR https://example.com/pull/7  "),
+        Some(("Review the PR", "https://example.com/pull/7"))
+    );
+    assert_eq!(split_trailing_url("Review www.example.com"), None);
+    assert_eq!(split_trailing_url("Review https://"), None);
+    assert_eq!(split_trailing_url("   https://example.com"), None);
+    assert_eq!(split_trailing_url(""), None);
+}
+
+#[test]
+fn test_set_url_sets_and_clears_the_link() {
+    let mut list = TodoList::new();
+    let id = list.add("Read the release notes".to_string());
+
+    assert!(list.set_url(id, Some("https://example.com/notes".to_string())));
+    assert_eq!(
+        list.get(id).unwrap().url.as_deref(),
+        Some("https://example.com/notes")
+    );
+    assert!(list.set_url(id, None));
+    assert_eq!(list.get(id).unwrap().url, None);
+    assert!(!list.set_url(99, None));
+}
+
+#[test]
+fn test_url_round_trips_through_json_export() {
+    let mut list = TodoList::new();
+    let id = list.add("Pay the invoice".to_string());
+    list.set_url(id, Some("https://example.com/invoice/42".to_string()));
+    list.add("Water the plants".to_string());
+
+    let json = export_json(&list).unwrap();
+    assert_eq!(json.matches("\"url\"").count(), 1);
+    assert_eq!(import_json(&json).unwrap().all(), list.all());
+
+    // Todos saved before links existed load without one
+    let todo: Todo = serde_json::from_str(
+        r#"{"id":1,"text":"Old todo","completed":false,"due_date":null,"tags":[]}"#,
+    )
+    .unwrap();
+    assert_eq!(todo.url, None);
+}
+
+#[test]
+fn test_import_reports_invalid_links() {
+    let mut list = TodoList::new();
+    let id = list.add("Check the docs".to_string());
+    list.set_url(id, Some("javascript:alert(1)".to_string()));
+
+    let json = export_json(&list).unwrap();
+    let Err(ExportError::InvalidTodos(problems)) = import_json(&json) else {
+        panic!("an invalid link is rejected");
+    };
+    assert_eq!(
+        problems,
+        ["Todo 1 (id 1): link must start with http:// or https://"]
+    );
+}
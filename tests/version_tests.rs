@@ -0,0 +1,81 @@
+use todo::utils::constants::app::{APP_VERSION, RELEASE_NOTES};
+use todo::utils::version::{Version, notes_since, should_show_whats_new};
+
+#[test]
+fn test_version_parse() {
+    let version = |major, minor, patch| {
+        Some(Version {
+            major,
+            minor,
+            patch,
+        })
+    };
+    assert_eq!(Version::parse("1.4.2"), version(1, 4, 2));
+    assert_eq!(Version::parse(" v2.0 "), version(2, 0, 0));
+    assert_eq!(Version::parse("3"), version(3, 0, 0));
+    assert_eq!(Version::parse("1.0.0-beta.1"), version(1, 0, 0));
+    assert_eq!(Version::parse("1.0.0+build.7"), version(1, 0, 0));
+
+    for text in ["", "v", "one.two", "1..2", "1.2.3.4", "-1.0.0", "1.x"] {
+        assert_eq!(Version::parse(text), None, "{text:?} should not parse");
+    }
+    assert_eq!(Version::parse("1.4.2").unwrap().to_string(), "1.4.2");
+}
+
+#[test]
+fn test_version_order_is_numeric() {
+    assert!(Version::parse("0.10.0") > Version::parse("0.9.9"));
+    assert!(Version::parse("1.0.0") > Version::parse("0.99.99"));
+    assert!(Version::parse("1.2.10") > Version::parse("1.2.9"));
+    assert_eq!(Version::parse("1.2"), Version::parse("1.2.0"));
+}
+
+#[test]
+fn test_whats_new_shown_after_upgrade() {
+    assert!(should_show_whats_new(Some("0.1.0"), "0.2.0"));
+    assert!(should_show_whats_new(Some("0.9.3"), "0.10.0"));
+    assert!(should_show_whats_new(Some("1.0.0"), "1.0.1"));
+}
+
+#[test]
+fn test_whats_new_hidden_after_downgrade_or_same_version() {
+    assert!(!should_show_whats_new(Some("0.2.0"), "0.1.0"));
+    assert!(!should_show_whats_new(Some("0.10.0"), "0.9.3"));
+    assert!(!should_show_whats_new(Some("1.0.0"), "1.0.0"));
+    assert!(!should_show_whats_new(Some("v1.0"), "1.0.0"));
+}
+
+#[test]
+fn test_whats_new_hidden_on_fresh_install() {
+    assert!(!should_show_whats_new(None, "0.1.0"));
+    assert!(!should_show_whats_new(None, APP_VERSION));
+    // An unreadable stored version is treated like a fresh install
+    assert!(!should_show_whats_new(Some("garbage"), "0.1.0"));
+}
+
+#[test]
+fn test_notes_since_last_seen_version() {
+    let versions = |notes: Vec<(&str, &[&str])>| -> Vec<String> {
+        notes
+            .iter()
+            .map(|(version, _)| version.to_string())
+            .collect()
+    };
+
+    let all = versions(notes_since(None, APP_VERSION));
+    assert_eq!(all.len(), RELEASE_NOTES.len());
+    assert!(versions(notes_since(Some(APP_VERSION), APP_VERSION)).is_empty());
+    assert!(versions(notes_since(Some("0.0.1"), "0.0.2")).is_empty());
+}
+
+#[test]
+fn test_release_notes_are_well_formed() {
+    // The running version has notes, and versions are listed newest first
+    assert_eq!(RELEASE_NOTES[0].0, APP_VERSION);
+    let parsed: Vec<Version> = RELEASE_NOTES
+        .iter()
+        .map(|(version, _)| Version::parse(version).expect("release notes use valid versions"))
+        .collect();
+    assert!(parsed.windows(2).all(|pair| pair[0] > pair[1]));
+    assert!(RELEASE_NOTES.iter().all(|(_, notes)| !notes.is_empty()));
+}
@@ -0,0 +1,654 @@
+mod common;
+
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use common::clock;
+use std::rc::Rc;
+use todo::models::view::{
+    EmptyState, ListViewModel, PositionError, index_for_position, row_for_number,
+    selection_after_removal, step_selection,
+};
+use todo::models::{
+    AppSettings, FilterCriteria, FilterState, ListView, QuickFilter, SearchQuery, SplitView,
+    TagFilter, Todo, TodoList, WeekStart,
+};
+use todo::utils::clock::Clock;
+
+/// A list with completed and open todos across two tags and due dates:
+/// 1 "Report" #Work done, due today; 2 "Slides" #Work open, due today;
+/// 3 "Groceries" #Home done; 4 "Report draft" #Home done, due today;
+/// 5 "Taxes" #Work done, locked
+fn sample_list() -> TodoList {
+    let due_today = Utc.with_ymd_and_hms(2025, 3, 12, 10, 0, 0).unwrap();
+    let mut list = TodoList::new();
+    for (text, tag, done, due) in [
+        ("Report", "Work", true, Some(due_today)),
+        ("Slides", "Work", false, Some(due_today)),
+        ("Groceries", "Home", true, None),
+        ("Report draft", "Home", true, Some(due_today)),
+        ("Taxes", "Work", true, None),
+    ] {
+        let id = list.add(text.to_string());
+        list.add_tag(id, tag.to_string());
+        list.set_due_date(id, due).unwrap();
+        if done {
+            list.toggle(id);
+        }
+    }
+    list.set_locked(5, true);
+    list
+}
+
+fn today() -> NaiveDate {
+    clock().today_local()
+}
+
+fn visible_completed_ids(list: &TodoList, view: &ListView) -> Vec<usize> {
+    let todos = list.all();
+    view.visible_ids(
+        todos.iter().filter(|todo| todo.completed),
+        clock().now_utc(),
+        today(),
+    )
+}
+
+#[test]
+fn test_default_view_shows_everything() {
+    let list = sample_list();
+    assert_eq!(
+        visible_completed_ids(&list, &ListView::default()),
+        vec![1, 3, 4, 5]
+    );
+}
+
+#[test]
+fn test_view_combines_tag_and_search() {
+    let list = sample_list();
+    let view = ListView {
+        tag: TagFilter::Tag("Work".to_string()),
+        query: SearchQuery::parse("report"),
+        ..ListView::default()
+    };
+    assert_eq!(visible_completed_ids(&list, &view), vec![1]);
+
+    let view = ListView {
+        tag: TagFilter::Tag("Home".to_string()),
+        ..view
+    };
+    assert_eq!(visible_completed_ids(&list, &view), vec![4]);
+}
+
+#[test]
+fn test_view_combines_status_and_quick_filter() {
+    let list = sample_list();
+    let view = ListView {
+        filter: FilterState::Completed,
+        quick_filter: Some(QuickFilter::Today),
+        week_start: WeekStart::Monday,
+        ..ListView::default()
+    };
+    assert_eq!(visible_completed_ids(&list, &view), vec![1, 4]);
+
+    // Active hides every completed todo, whatever else is selected
+    let view = ListView {
+        filter: FilterState::Active,
+        ..view
+    };
+    assert!(visible_completed_ids(&list, &view).is_empty());
+}
+
+#[test]
+fn test_view_combines_day_and_tag() {
+    let list = sample_list();
+    let view = ListView {
+        due_on: Some(today()),
+        ..ListView::default()
+    };
+    assert_eq!(visible_completed_ids(&list, &view), vec![1, 4]);
+
+    let view = ListView {
+        tag: TagFilter::Tag("Home".to_string()),
+        ..view
+    };
+    assert_eq!(visible_completed_ids(&list, &view), vec![4]);
+
+    let view = ListView {
+        due_on: today().succ_opt(),
+        ..view
+    };
+    assert!(visible_completed_ids(&list, &view).is_empty());
+}
+
+/// Ids of the sample todos the criteria let through.
+fn matching_ids(list: &TodoList, criteria: &FilterCriteria) -> Vec<usize> {
+    list.all()
+        .iter()
+        .filter(|todo| criteria.matches(todo, clock().now_utc(), today()))
+        .map(|todo| todo.id)
+        .collect()
+}
+
+#[test]
+fn test_filter_criteria_combine_every_filter() {
+    let list = sample_list();
+    assert_eq!(
+        matching_ids(&list, &FilterCriteria::default()),
+        vec![1, 2, 3, 4, 5]
+    );
+
+    let criteria = FilterCriteria {
+        filter: FilterState::Completed,
+        tag: TagFilter::Tag("Work".to_string()),
+        ..FilterCriteria::default()
+    };
+    assert_eq!(matching_ids(&list, &criteria), vec![1, 5]);
+
+    let criteria = FilterCriteria {
+        search_text: "report".to_string(),
+        ..criteria
+    };
+    assert_eq!(matching_ids(&list, &criteria), vec![1]);
+
+    let criteria = FilterCriteria {
+        due_on: NaiveDate::from_ymd_opt(2025, 3, 12),
+        tag: TagFilter::All,
+        ..criteria
+    };
+    assert_eq!(matching_ids(&list, &criteria), vec![1, 4]);
+}
+
+#[test]
+fn test_filter_criteria_give_the_same_view() {
+    let criteria = FilterCriteria {
+        filter: FilterState::Active,
+        tag: TagFilter::Tag("Work".to_string()),
+        search_text: "slides".to_string(),
+        quick_filter: Some(QuickFilter::Today),
+        due_on: Some(today()),
+    };
+    let view = ListView {
+        week_start: WeekStart::Sunday,
+        ..criteria.view()
+    };
+
+    assert_eq!(view.query, SearchQuery::parse("slides"));
+    assert_eq!(view.tag, TagFilter::Tag("Work".to_string()));
+    assert_eq!(view.week_start, WeekStart::Sunday);
+    let todos = sample_list().all();
+    assert_eq!(
+        view.visible_ids(todos.iter(), clock().now_utc(), today()),
+        vec![2]
+    );
+}
+
+#[test]
+fn test_filter_criteria_are_stored_whole() {
+    let criteria = FilterCriteria {
+        filter: FilterState::DueThisWeek,
+        tag: TagFilter::Tag("Home".to_string()),
+        search_text: "milk".to_string(),
+        quick_filter: Some(QuickFilter::Overdue),
+        due_on: Some(today()),
+    };
+    let json = serde_json::to_string(&criteria).unwrap();
+    assert_eq!(
+        serde_json::from_str::<FilterCriteria>(&json).unwrap(),
+        criteria
+    );
+
+    // Filters saved by an older version keep what they have
+    let older: FilterCriteria = serde_json::from_str(r#"{"filter":"Active"}"#).unwrap();
+    assert_eq!(
+        older,
+        FilterCriteria {
+            filter: FilterState::Active,
+            ..FilterCriteria::default()
+        }
+    );
+}
+
+#[test]
+fn test_filter_criteria_with_a_single_tag_still_load() {
+    // Before the untagged option, the tag was stored as a plain name or null
+    let single: FilterCriteria =
+        serde_json::from_str(r#"{"filter":"Active","tag":"Home","search_text":"milk"}"#).unwrap();
+    assert_eq!(
+        single,
+        FilterCriteria {
+            filter: FilterState::Active,
+            tag: TagFilter::Tag("Home".to_string()),
+            search_text: "milk".to_string(),
+            ..FilterCriteria::default()
+        }
+    );
+    let json = serde_json::to_string(&single).unwrap();
+    assert_eq!(
+        serde_json::from_str::<FilterCriteria>(&json).unwrap(),
+        single
+    );
+
+    let none: FilterCriteria = serde_json::from_str(r#"{"filter":"All","tag":null}"#).unwrap();
+    assert_eq!(none.tag, TagFilter::All);
+    let untagged = FilterCriteria {
+        tag: TagFilter::Untagged,
+        ..FilterCriteria::default()
+    };
+    let json = serde_json::to_string(&untagged).unwrap();
+    assert_eq!(
+        serde_json::from_str::<FilterCriteria>(&json).unwrap(),
+        untagged
+    );
+}
+
+#[test]
+fn test_clear_completed_filtered_clears_only_visible_todos() {
+    let mut list = sample_list();
+    let view = ListView {
+        tag: TagFilter::Tag("Work".to_string()),
+        ..ListView::default()
+    };
+    let ids = visible_completed_ids(&list, &view);
+    assert_eq!(ids, vec![1, 5]);
+
+    let cleared = list.clear_completed_filtered(&ids);
+    let removed: Vec<usize> = cleared.removed.iter().map(|todo| todo.id).collect();
+    assert_eq!(removed, vec![1]);
+    assert_eq!(cleared.skipped_locked, 1);
+
+    let remaining: Vec<usize> = list.all().iter().map(|todo| todo.id).collect();
+    assert_eq!(remaining, vec![2, 3, 4, 5]);
+    list.check_invariants().unwrap();
+}
+
+#[test]
+fn test_step_selection_moves_within_visible_todos() {
+    let visible = [5, 2, 9];
+    assert_eq!(step_selection(&visible, Some(5), 1), Some(2));
+    assert_eq!(step_selection(&visible, Some(2), -1), Some(5));
+    assert_eq!(step_selection(&visible, Some(5), -1), Some(5));
+    assert_eq!(step_selection(&visible, Some(9), 1), Some(9));
+    // A selection filtered out of view starts over from the edge
+    assert_eq!(step_selection(&visible, Some(4), 1), Some(5));
+    assert_eq!(step_selection(&visible, Some(4), -1), Some(9));
+    assert_eq!(step_selection(&[], None, 1), None);
+}
+
+#[test]
+fn test_selection_after_removal_keeps_the_place() {
+    let visible = [5, 2, 9, 4];
+    // A middle row is replaced by the one below it
+    assert_eq!(selection_after_removal(&visible, &[2]), Some(9));
+    assert_eq!(selection_after_removal(&visible, &[5]), Some(2));
+    // The last row hands over to the one above
+    assert_eq!(selection_after_removal(&visible, &[4]), Some(9));
+    // Several rows count from the first of them
+    assert_eq!(selection_after_removal(&visible, &[2, 4]), Some(9));
+    assert_eq!(selection_after_removal(&visible, &[9, 4]), Some(2));
+    assert_eq!(selection_after_removal(&visible, &[5, 2, 9, 4]), None);
+}
+
+/// Gets the todos tagged Work in list order, as the tag filter shows them.
+fn work_todos(list: &TodoList) -> Vec<Todo> {
+    let filter = TagFilter::Tag("Work".to_string());
+    list.all()
+        .into_iter()
+        .filter(|todo| filter.matches(todo))
+        .collect()
+}
+
+fn ids(todos: &[Todo]) -> Vec<usize> {
+    todos.iter().map(|todo| todo.id).collect()
+}
+
+#[test]
+fn test_position_under_a_filter_moves_next_to_shown_todos() {
+    let mut list = sample_list();
+    let shown = work_todos(&list);
+    assert_eq!(ids(&shown), vec![1, 2, 5]);
+
+    // To the top of what is shown, which is the top of the list
+    let index = index_for_position(&shown, 5, "1").unwrap();
+    assert!(list.reorder_to_index(5, index));
+    assert_eq!(ids(&work_todos(&list)), vec![5, 1, 2]);
+    assert_eq!(ids(&list.all()), vec![5, 1, 2, 3, 4]);
+
+    // To the bottom of what is shown, taking the place of the last todo
+    // shown; the hidden Home todos after it stay where they are
+    let shown = work_todos(&list);
+    let index = index_for_position(&shown, 5, "3").unwrap();
+    assert!(list.reorder_to_index(5, index));
+    assert_eq!(ids(&work_todos(&list)), vec![1, 2, 5]);
+    assert_eq!(ids(&list.all()), vec![1, 2, 5, 3, 4]);
+    list.check_invariants().unwrap();
+
+    let shown = work_todos(&list);
+    assert_eq!(
+        index_for_position(&shown, 2, "2"),
+        Err(PositionError::SamePosition)
+    );
+    assert_eq!(
+        index_for_position(&shown, 2, "4"),
+        Err(PositionError::OutOfRange(3))
+    );
+    assert_eq!(
+        index_for_position(&shown, 2, "0"),
+        Err(PositionError::OutOfRange(3))
+    );
+    assert_eq!(
+        index_for_position(&shown, 2, "first"),
+        Err(PositionError::NotANumber)
+    );
+}
+
+#[test]
+fn test_visible_ids_list_stale_todos_oldest_first() {
+    let now = clock().now_utc();
+    let todos: Vec<Todo> = [(1, 20), (2, 3), (3, 40)]
+        .into_iter()
+        .map(|(id, days)| {
+            let mut todo = Todo::new(id, format!("Todo {id}"));
+            todo.created_at = Some(now - Duration::days(days));
+            todo
+        })
+        .collect();
+    let view = ListView {
+        query: SearchQuery::parse("is:stale"),
+        stale_after_days: 14,
+        ..ListView::default()
+    };
+    assert_eq!(view.visible_ids(&todos, now, today()), vec![3, 1]);
+    // Other views keep the list order
+    assert_eq!(
+        ListView::default().visible_ids(&todos, now, today()),
+        vec![1, 2, 3]
+    );
+}
+
+#[test]
+fn test_split_view_setting() {
+    assert!(SplitView::Auto.is_split(Some(1100.0)));
+    assert!(!SplitView::Auto.is_split(Some(1099.0)));
+    assert!(!SplitView::Auto.is_split(None));
+    assert!(SplitView::Always.is_split(Some(400.0)));
+    assert!(!SplitView::Never.is_split(Some(2000.0)));
+
+    for split in SplitView::ALL {
+        assert_eq!(SplitView::parse(split.label()), Some(split));
+    }
+    assert_eq!(AppSettings::default().split_view, SplitView::Auto);
+}
+
+/// Computes the list's view model the way the list component does.
+fn compute<'a>(
+    todos: &'a [Rc<Todo>],
+    view: &ListView,
+    search_text: &str,
+    in_trash: bool,
+) -> ListViewModel<'a, Rc<Todo>> {
+    ListViewModel::compute(
+        todos,
+        view,
+        search_text,
+        in_trash,
+        clock().now_utc(),
+        today(),
+    )
+}
+
+fn row_ids(model: &ListViewModel<'_, Rc<Todo>>) -> Vec<usize> {
+    model.rows.iter().map(|row| row.todo.id).collect()
+}
+
+fn shared(list: &TodoList) -> Vec<Rc<Todo>> {
+    list.all().into_iter().map(Rc::new).collect()
+}
+
+#[test]
+fn test_view_model_lists_matching_todos_in_order() {
+    let todos = shared(&sample_list());
+    let model = compute(&todos, &ListView::default(), "", false);
+    assert_eq!(row_ids(&model), vec![1, 2, 3, 4, 5]);
+    assert_eq!(model.empty_state, None);
+}
+
+#[test]
+fn test_view_model_matches_selected_tag_exactly() {
+    let todos = shared(&sample_list());
+    let view = ListView {
+        tag: TagFilter::Tag("Home".to_string()),
+        ..ListView::default()
+    };
+    assert_eq!(row_ids(&compute(&todos, &view, "", false)), vec![3, 4]);
+
+    let view = ListView {
+        tag: TagFilter::Tag("home".to_string()),
+        ..ListView::default()
+    };
+    let model = compute(&todos, &view, "", false);
+    assert!(model.rows.is_empty());
+    assert_eq!(model.empty_state, Some(EmptyState::Tag));
+}
+
+#[test]
+fn test_view_model_untagged_leaves_out_tagged_todos() {
+    let mut list = sample_list();
+    let loose = list.add("Call the bank".to_string());
+    let untagged = ListView {
+        tag: TagFilter::Untagged,
+        ..ListView::default()
+    };
+    assert_eq!(untagged.name_parts(), ["untagged"]);
+    let todos = shared(&list);
+    assert_eq!(row_ids(&compute(&todos, &untagged, "", false)), vec![loose]);
+
+    list.add_tag(loose, "Finance".to_string());
+    let todos = shared(&list);
+    let model = compute(&todos, &untagged, "", false);
+    assert_eq!(model.empty_state, Some(EmptyState::Untagged));
+    assert_eq!(model.empty_state.unwrap().to_string(), "No untagged todos.");
+}
+
+#[test]
+fn test_view_model_searches_text_and_tags_ignoring_case() {
+    let todos = shared(&sample_list());
+    for search in ["REPORT", "rePort"] {
+        let view = ListView {
+            query: SearchQuery::parse(search),
+            ..ListView::default()
+        };
+        assert_eq!(row_ids(&compute(&todos, &view, search, false)), vec![1, 4]);
+    }
+
+    let view = ListView {
+        query: SearchQuery::parse("work"),
+        ..ListView::default()
+    };
+    assert_eq!(
+        row_ids(&compute(&todos, &view, "work", false)),
+        vec![1, 2, 5]
+    );
+}
+
+#[test]
+fn test_view_model_picks_the_most_specific_empty_state() {
+    let todos = shared(&sample_list());
+    let nothing_matches = ListView {
+        query: SearchQuery::parse("holiday"),
+        quick_filter: Some(QuickFilter::Overdue),
+        tag: TagFilter::Tag("Travel".to_string()),
+        ..ListView::default()
+    };
+
+    let cases = [
+        (&todos[..0], "", false, EmptyState::NoTodos),
+        (&todos[..], "holiday", true, EmptyState::Trash),
+        (&todos[..0], "", true, EmptyState::TrashEmpty),
+        (
+            &todos[..],
+            "holiday",
+            false,
+            EmptyState::Search("holiday".to_string()),
+        ),
+        (
+            &todos[..],
+            "",
+            false,
+            EmptyState::QuickFilter(QuickFilter::Overdue),
+        ),
+    ];
+    for (todos, search, in_trash, expected) in cases {
+        let model = compute(todos, &nothing_matches, search, in_trash);
+        assert_eq!(model.empty_state, Some(expected));
+    }
+
+    let view = ListView {
+        due_on: today().succ_opt(),
+        tag: TagFilter::Tag("Travel".to_string()),
+        ..ListView::default()
+    };
+    let model = compute(&todos, &view, "", false);
+    assert_eq!(model.empty_state, today().succ_opt().map(EmptyState::DueOn));
+    assert_eq!(
+        model.empty_state.unwrap().to_string(),
+        "No todos due on Mar 13."
+    );
+}
+
+#[test]
+fn test_view_model_empty_state_follows_status_filter() {
+    let mut list = TodoList::new();
+    let id = list.add("Water the plants".to_string());
+    list.toggle(id);
+    let todos = shared(&list);
+
+    let active = ListView {
+        filter: FilterState::Active,
+        ..ListView::default()
+    };
+    assert_eq!(
+        compute(&todos, &active, "", false).empty_state,
+        Some(EmptyState::AllDone)
+    );
+
+    list.toggle(id);
+    let todos = shared(&list);
+    let completed = ListView {
+        filter: FilterState::Completed,
+        ..ListView::default()
+    };
+    let model = compute(&todos, &completed, "", false);
+    assert_eq!(model.empty_state, Some(EmptyState::NoneCompleted));
+    assert_eq!(
+        model.empty_state.unwrap().to_string(),
+        "No completed tasks yet."
+    );
+
+    // Only archived todos show in the archived view
+    let archived = ListView {
+        filter: FilterState::Archived,
+        ..ListView::default()
+    };
+    assert_eq!(archived.name_parts(), ["archived"]);
+    let model = compute(&todos, &archived, "", false);
+    assert_eq!(model.empty_state, Some(EmptyState::NoneArchived));
+    list.archive(id);
+    let todos: Vec<Rc<Todo>> = list
+        .all_including_archived()
+        .into_iter()
+        .map(Rc::new)
+        .collect();
+    assert_eq!(row_ids(&compute(&todos, &archived, "", false)), vec![id]);
+    assert!(compute(&todos, &completed, "", false).rows.is_empty());
+}
+
+#[test]
+fn test_view_model_overdue_filter() {
+    let now = clock().now_utc();
+    let mut list = TodoList::new();
+    let today_id = list.add("Due today".to_string());
+    list.set_due_date(today_id, Some(now - Duration::minutes(5)))
+        .unwrap();
+    let overdue = ListView {
+        filter: FilterState::Overdue,
+        ..ListView::default()
+    };
+    assert_eq!(overdue.name_parts(), ["overdue"]);
+
+    // A todo due earlier today is not overdue yet
+    let todos = shared(&list);
+    let model = compute(&todos, &overdue, "", false);
+    assert_eq!(model.empty_state, Some(EmptyState::NoneOverdue));
+    assert_eq!(model.empty_state.unwrap().to_string(), "Nothing overdue 🎉");
+
+    let late_id = list.add("Due yesterday".to_string());
+    list.set_due_date(late_id, Some(now - Duration::days(1)))
+        .unwrap();
+    let done_id = list.add("Done yesterday".to_string());
+    list.set_due_date(done_id, Some(now - Duration::days(1)))
+        .unwrap();
+    list.toggle(done_id);
+    let todos = shared(&list);
+    let model = compute(&todos, &overdue, "", false);
+    assert_eq!(row_ids(&model), vec![late_id]);
+    assert_eq!(list.overdue_count(today()), 1);
+}
+
+#[test]
+fn test_view_model_rows_carry_parent_text_and_stale_days() {
+    let mut list = TodoList::new();
+    let parent = list.add("Plan the move".to_string());
+    list.set_notes(parent, "- Book the van".to_string());
+    let child = list.extract_from_notes(parent, 0, false).unwrap();
+    // Added at the test clock's time rather than the system clock's
+    let todos: Vec<Rc<Todo>> = list
+        .all()
+        .into_iter()
+        .map(|mut todo| {
+            todo.created_at = Some(clock().now_utc());
+            todo.updated_at = None;
+            Rc::new(todo)
+        })
+        .collect();
+
+    let view = ListView {
+        stale_after_days: 14,
+        ..ListView::default()
+    };
+    let later = clock().now_utc() + Duration::days(20);
+    let model = ListViewModel::compute(&todos, &view, "", false, later, today());
+    let row = model.rows.iter().find(|row| row.todo.id == child).unwrap();
+    assert_eq!(row.parent_text, Some("Plan the move"));
+    assert_eq!(row.stale_days, Some(20));
+
+    let model = compute(&todos, &view, "", false);
+    assert!(model.rows.iter().all(|row| row.stale_days.is_none()));
+}
+
+#[test]
+fn test_number_keys_pick_rows_in_screen_order() {
+    let ids = [7, 3, 9];
+    assert_eq!(row_for_number(&ids, 1), Some(7));
+    assert_eq!(row_for_number(&ids, 2), Some(3));
+    assert_eq!(row_for_number(&ids, 3), Some(9));
+    assert_eq!(row_for_number(&ids, 4), None);
+    assert_eq!(row_for_number(&ids, 0), None);
+    assert_eq!(row_for_number(&[], 1), None);
+
+    // Only 1-9 have keys, even on longer lists
+    let long: Vec<usize> = (1..=12).collect();
+    assert_eq!(row_for_number(&long, 9), Some(9));
+    assert_eq!(row_for_number(&long, 10), None);
+}
+
+#[test]
+fn test_number_keys_follow_the_rendered_rows() {
+    let todos = shared(&sample_list());
+    let view = ListView {
+        tag: TagFilter::Tag("Work".to_string()),
+        ..ListView::default()
+    };
+    let model = compute(&todos, &view, "", false);
+    let visible = view.visible_ids(todos.iter().map(|todo| &**todo), clock().now_utc(), today());
+    assert_eq!(visible, row_ids(&model));
+    assert_eq!(row_for_number(&visible, 2), Some(2));
+    assert_eq!(row_for_number(&visible, 3), Some(5));
+}